@@ -1,5 +1,13 @@
 import { relations } from "drizzle-orm";
-import { nodes, repositories, worktrees } from "./schema";
+import {
+	gateRuns,
+	nodes,
+	nodeSnapshots,
+	repositories,
+	reviewComments,
+	testRuns,
+	worktrees,
+} from "./schema";
 
 export const repositoriesRelations = relations(repositories, ({ many }) => ({
 	worktrees: many(worktrees),
@@ -14,7 +22,7 @@ export const worktreesRelations = relations(worktrees, ({ one, many }) => ({
 	nodes: many(nodes),
 }));
 
-export const nodesRelations = relations(nodes, ({ one }) => ({
+export const nodesRelations = relations(nodes, ({ one, many }) => ({
 	repository: one(repositories, {
 		fields: [nodes.repositoryId],
 		references: [repositories.id],
@@ -23,4 +31,36 @@ export const nodesRelations = relations(nodes, ({ one }) => ({
 		fields: [nodes.worktreeId],
 		references: [worktrees.id],
 	}),
+	reviewComments: many(reviewComments),
+	testRuns: many(testRuns),
+	gateRuns: many(gateRuns),
+	snapshots: many(nodeSnapshots),
+}));
+
+export const reviewCommentsRelations = relations(reviewComments, ({ one }) => ({
+	node: one(nodes, {
+		fields: [reviewComments.nodeId],
+		references: [nodes.id],
+	}),
+}));
+
+export const testRunsRelations = relations(testRuns, ({ one }) => ({
+	node: one(nodes, {
+		fields: [testRuns.nodeId],
+		references: [nodes.id],
+	}),
+}));
+
+export const gateRunsRelations = relations(gateRuns, ({ one }) => ({
+	node: one(nodes, {
+		fields: [gateRuns.nodeId],
+		references: [nodes.id],
+	}),
+}));
+
+export const nodeSnapshotsRelations = relations(nodeSnapshots, ({ one }) => ({
+	node: one(nodes, {
+		fields: [nodeSnapshots.nodeId],
+		references: [nodes.id],
+	}),
 }));
@@ -1,13 +1,33 @@
 import { sql } from "drizzle-orm";
 import { index, integer, sqliteTable, text, uniqueIndex } from "drizzle-orm/sqlite-core";
+import type { UpdateChannel } from "shared/auto-update";
+import type { Locale } from "shared/i18n/translate";
+import type { NotificationType } from "shared/notification-types";
+import type { AuditActor, AuditEventType } from "shared/types/audit";
+import type { IssueLink } from "shared/types/issues";
+import type { QualityGateStatus, QualityGateStepResult } from "shared/types/quality-gate";
+import type {
+	FileCoverage,
+	TestCaseResult,
+	TestFramework,
+	TestRunStatus,
+	TestRunSummary,
+} from "shared/types/test-runs";
+import type { WebhookDeliveryStatus, WebhookEventType } from "shared/types/webhooks";
 import { v4 as uuidv4 } from "uuid";
 
 import type {
 	BranchPrefixMode,
+	CommitSigningFormat,
 	ExternalApp,
 	GitHubStatus,
 	GitStatus,
 	NodeType,
+	NotificationPreferences,
+	OutputRedactionMode,
+	RepositoryInitStatus,
+	ReviewCommentSide,
+	TelemetryLevel,
 	TerminalLinkBehavior,
 	TerminalPreset,
 } from "./zod";
@@ -39,10 +59,24 @@ export const repositories = sqliteTable(
 		githubOwner: text("github_owner"),
 		branchPrefixMode: text("branch_prefix_mode").$type<BranchPrefixMode>(),
 		branchPrefixCustom: text("branch_prefix_custom"),
+		// Per-repository commit identity; null falls back to the user's git config
+		commitAuthorName: text("commit_author_name"),
+		commitAuthorEmail: text("commit_author_email"),
+		commitSigningKey: text("commit_signing_key"),
+		commitSigningFormat: text("commit_signing_format").$type<CommitSigningFormat>(),
+		notificationPreferences: text("notification_preferences", {
+			mode: "json",
+		}).$type<NotificationPreferences>(),
+		// GitHub account used for PRs and pushes; null uses the default gh login or token
+		githubAccountId: text("github_account_id"),
+		// Cloned repositories stay "cloning" until the clone completes, so an
+		// interrupted clone can be resumed or cleaned up
+		initStatus: text("init_status").$type<RepositoryInitStatus>().notNull().default("ready"),
 	},
 	(table) => [
 		index("projects_main_repo_path_idx").on(table.mainRepoPath),
 		index("projects_last_opened_at_idx").on(table.lastOpenedAt),
+		index("projects_created_at_idx").on(table.createdAt),
 	],
 );
 
@@ -74,6 +108,7 @@ export const worktrees = sqliteTable(
 	(table) => [
 		index("worktrees_project_id_idx").on(table.repositoryId),
 		index("worktrees_branch_idx").on(table.branch),
+		index("worktrees_created_at_idx").on(table.createdAt),
 	],
 );
 
@@ -110,16 +145,25 @@ export const nodes = sqliteTable(
 			.notNull()
 			.$defaultFn(() => Date.now()),
 		isUnread: integer("is_unread", { mode: "boolean" }).default(false),
+		// Last time the user looked at the node; agent activity after this counts as unread
+		lastViewedAt: integer("last_viewed_at"),
 		// Timestamp when deletion was initiated. Non-null means deletion in progress.
 		// Nodes with deletingAt set should be filtered out from queries.
 		deletingAt: integer("deleting_at"),
 		// Custom teardown script for this node (overrides repository-level config)
 		customTeardownScript: text("custom_teardown_script"),
+		// Subdirectory the node is limited to, relative to the repository root (e.g. "packages/api")
+		scopePath: text("scope_path"),
+		// Cone-mode sparse-checkout directories; null checks out the whole repository
+		sparsePatterns: text("sparse_patterns", { mode: "json" }).$type<string[]>(),
+		// Issue tracker link; the repository's config says which tracker
+		issue: text("issue", { mode: "json" }).$type<IssueLink>(),
 	},
 	(table) => [
 		index("nodes_project_id_idx").on(table.repositoryId),
 		index("nodes_worktree_id_idx").on(table.worktreeId),
 		index("nodes_last_opened_at_idx").on(table.lastOpenedAt),
+		index("nodes_created_at_idx").on(table.createdAt),
 		uniqueIndex("nodes_unique_branch_per_project")
 			.on(table.repositoryId)
 			.where(sql`${table.type} = 'branch'`),
@@ -129,6 +173,270 @@ export const nodes = sqliteTable(
 export type InsertNode = typeof nodes.$inferInsert;
 export type SelectNode = typeof nodes.$inferSelect;
 
+/**
+ * Review comments table - inline comments left on a node's diff lines
+ */
+export const reviewComments = sqliteTable(
+	"review_comments",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		nodeId: text("node_id")
+			.notNull()
+			.references(() => nodes.id, { onDelete: "cascade" }),
+		filePath: text("file_path").notNull(),
+		line: integer("line").notNull(),
+		side: text("side").notNull().$type<ReviewCommentSide>(),
+		body: text("body").notNull(),
+		// Non-null once the comment has been addressed
+		resolvedAt: integer("resolved_at"),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+		updatedAt: integer("updated_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+	},
+	(table) => [
+		index("review_comments_node_id_idx").on(table.nodeId),
+		index("review_comments_created_at_idx").on(table.createdAt),
+	],
+);
+
+export type InsertReviewComment = typeof reviewComments.$inferInsert;
+export type SelectReviewComment = typeof reviewComments.$inferSelect;
+
+/**
+ * Test runs table - parsed results of test commands run in a node
+ */
+export const testRuns = sqliteTable(
+	"test_runs",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		nodeId: text("node_id")
+			.notNull()
+			.references(() => nodes.id, { onDelete: "cascade" }),
+		command: text("command").notNull(),
+		framework: text("framework").$type<TestFramework>(),
+		status: text("status").notNull().$type<TestRunStatus>(),
+		exitCode: integer("exit_code"),
+		summary: text("summary", { mode: "json" }).notNull().$type<TestRunSummary>(),
+		results: text("results", { mode: "json" }).notNull().$type<TestCaseResult[]>(),
+		// Tail of combined stdout/stderr
+		output: text("output").notNull(),
+		durationMs: integer("duration_ms").notNull(),
+		// Parsed lcov/cobertura report written by this run, if any
+		coverage: text("coverage", { mode: "json" }).$type<FileCoverage[]>(),
+		coverageReportPath: text("coverage_report_path"),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+	},
+	(table) => [
+		index("test_runs_node_id_idx").on(table.nodeId),
+		index("test_runs_created_at_idx").on(table.createdAt),
+	],
+);
+
+export type InsertTestRun = typeof testRuns.$inferInsert;
+export type SelectTestRun = typeof testRuns.$inferSelect;
+
+/**
+ * Gate runs table - lint/test/build quality gate results per node
+ */
+export const gateRuns = sqliteTable(
+	"gate_runs",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		nodeId: text("node_id")
+			.notNull()
+			.references(() => nodes.id, { onDelete: "cascade" }),
+		// Commit the gate ran against; a pass only counts while HEAD is unchanged
+		headSha: text("head_sha"),
+		status: text("status").notNull().$type<QualityGateStatus>(),
+		steps: text("steps", { mode: "json" }).notNull().$type<QualityGateStepResult[]>(),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+		completedAt: integer("completed_at"),
+	},
+	(table) => [
+		index("gate_runs_node_id_idx").on(table.nodeId),
+		index("gate_runs_created_at_idx").on(table.createdAt),
+	],
+);
+
+export type InsertGateRun = typeof gateRuns.$inferInsert;
+export type SelectGateRun = typeof gateRuns.$inferSelect;
+
+export const nodeSnapshots = sqliteTable(
+	"node_snapshots",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		nodeId: text("node_id")
+			.notNull()
+			.references(() => nodes.id, { onDelete: "cascade" }),
+		label: text("label").notNull(),
+		// Branch checked out when the snapshot was taken; restores are refused on another branch
+		branch: text("branch"),
+		headSha: text("head_sha").notNull(),
+		// Commit holding the full working tree (tracked and untracked, minus ignored files)
+		commitSha: text("commit_sha").notNull(),
+		// Tree of the index at snapshot time so staged changes come back as staged
+		indexTree: text("index_tree").notNull(),
+		// Ref under refs/caspian/snapshots that keeps the commit from being garbage collected
+		ref: text("ref").notNull(),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+	},
+	(table) => [
+		index("node_snapshots_node_id_idx").on(table.nodeId),
+		index("node_snapshots_created_at_idx").on(table.createdAt),
+	],
+);
+
+export type InsertNodeSnapshot = typeof nodeSnapshots.$inferInsert;
+export type SelectNodeSnapshot = typeof nodeSnapshots.$inferSelect;
+
+/**
+ * Index of audit events for cross-node and cross-repository queries.
+ * The per-node JSONL files are the source of truth; rows outlive deleted nodes.
+ */
+export const auditEvents = sqliteTable(
+	"audit_events",
+	{
+		id: text("id").primaryKey(),
+		repositoryId: text("repository_id").notNull(),
+		nodeId: text("node_id"),
+		type: text("type").notNull().$type<AuditEventType>(),
+		actor: text("actor").notNull().$type<AuditActor>(),
+		summary: text("summary").notNull(),
+		data: text("data", { mode: "json" }).$type<Record<string, unknown>>(),
+		timestamp: integer("timestamp").notNull(),
+	},
+	(table) => [
+		index("audit_events_repository_timestamp_idx").on(table.repositoryId, table.timestamp),
+		index("audit_events_node_timestamp_idx").on(table.nodeId, table.timestamp),
+		index("audit_events_timestamp_idx").on(table.timestamp),
+	],
+);
+
+export type InsertAuditEvent = typeof auditEvents.$inferInsert;
+export type SelectAuditEvent = typeof auditEvents.$inferSelect;
+
+/**
+ * Notifications shown to the user, kept so the app can list them in an
+ * inbox after the OS notification is gone.
+ */
+export const notificationHistory = sqliteTable(
+	"notification_history",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		type: text("type").notNull().$type<NotificationType>(),
+		nodeId: text("node_id"),
+		tabId: text("tab_id"),
+		paneId: text("pane_id"),
+		title: text("title").notNull(),
+		body: text("body").notNull(),
+		readAt: integer("read_at"),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+	},
+	(table) => [
+		index("notification_history_created_at_idx").on(table.createdAt),
+		index("notification_history_node_id_idx").on(table.nodeId),
+	],
+);
+
+export type InsertNotificationHistory = typeof notificationHistory.$inferInsert;
+export type SelectNotificationHistory = typeof notificationHistory.$inferSelect;
+
+/**
+ * Additional GitHub identities that repositories can be bound to. Each
+ * authenticates through its own gh config directory, a personal access
+ * token kept in the secret store, or both.
+ */
+export const githubAccounts = sqliteTable("github_accounts", {
+	id: text("id")
+		.primaryKey()
+		.$defaultFn(() => uuidv4()),
+	label: text("label").notNull(),
+	username: text("username").notNull(),
+	// Passed to gh as GH_CONFIG_DIR
+	ghConfigDir: text("gh_config_dir"),
+	hasToken: integer("has_token", { mode: "boolean" }).notNull().default(false),
+	createdAt: integer("created_at")
+		.notNull()
+		.$defaultFn(() => Date.now()),
+});
+
+export type InsertGitHubAccount = typeof githubAccounts.$inferInsert;
+export type SelectGitHubAccount = typeof githubAccounts.$inferSelect;
+
+/**
+ * Outbound webhooks. The URL itself is a secret (Slack URLs carry a token),
+ * so it lives in the secret store; only its host is kept here for display.
+ */
+export const webhooks = sqliteTable("webhooks", {
+	id: text("id")
+		.primaryKey()
+		.$defaultFn(() => uuidv4()),
+	name: text("name").notNull(),
+	urlHost: text("url_host").notNull(),
+	events: text("events", { mode: "json" }).notNull().$type<WebhookEventType[]>(),
+	enabled: integer("enabled", { mode: "boolean" }).notNull().default(true),
+	createdAt: integer("created_at")
+		.notNull()
+		.$defaultFn(() => Date.now()),
+});
+
+export type InsertWebhook = typeof webhooks.$inferInsert;
+export type SelectWebhook = typeof webhooks.$inferSelect;
+
+/**
+ * Delivery log for webhooks, one row per event per webhook.
+ * Retries update the row rather than adding new ones.
+ */
+export const webhookDeliveries = sqliteTable(
+	"webhook_deliveries",
+	{
+		id: text("id")
+			.primaryKey()
+			.$defaultFn(() => uuidv4()),
+		webhookId: text("webhook_id")
+			.notNull()
+			.references(() => webhooks.id, { onDelete: "cascade" }),
+		event: text("event").notNull().$type<WebhookEventType>(),
+		status: text("status").notNull().$type<WebhookDeliveryStatus>(),
+		attempts: integer("attempts").notNull().default(0),
+		responseStatus: integer("response_status"),
+		error: text("error"),
+		createdAt: integer("created_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+		updatedAt: integer("updated_at")
+			.notNull()
+			.$defaultFn(() => Date.now()),
+	},
+	(table) => [
+		index("webhook_deliveries_webhook_created_at_idx").on(table.webhookId, table.createdAt),
+	],
+);
+
+export type InsertWebhookDelivery = typeof webhookDeliveries.$inferInsert;
+export type SelectWebhookDelivery = typeof webhookDeliveries.$inferSelect;
+
 export const settings = sqliteTable("settings", {
 	id: integer("id").primaryKey().default(1),
 	lastActiveNodeId: text("last_active_node_id"),
@@ -150,6 +458,27 @@ export const settings = sqliteTable("settings", {
 	notificationSoundsMuted: integer("notification_sounds_muted", {
 		mode: "boolean",
 	}),
+	metricsEndpointEnabled: integer("metrics_endpoint_enabled", { mode: "boolean" }),
+	automationApiEnabled: integer("automation_api_enabled", { mode: "boolean" }),
+	// IANA time zone for scheduled work (e.g. digests); null follows the system zone
+	timeZone: text("time_zone"),
+	notificationPreferences: text("notification_preferences", {
+		mode: "json",
+	}).$type<NotificationPreferences>(),
+	// Launcher commands that override detection, e.g. { vscode: "/opt/vscode/bin/code" }
+	editorLaunchers: text("editor_launchers", { mode: "json" }).$type<
+		Partial<Record<ExternalApp, string>>
+	>(),
+	// null follows the channel of the installed build
+	updateChannel: text("update_channel").$type<UpdateChannel>(),
+	telemetryLevel: text("telemetry_level").$type<TelemetryLevel>(),
+	// Stops Caspian's own network traffic; git and agent commands still run
+	localOnlyMode: integer("local_only_mode", { mode: "boolean" }),
+	// Language for notifications and dialogs from the main process; null follows the system
+	locale: text("locale").$type<Locale>(),
+	// Agent terminals using more memory than this are stopped; null means no limit
+	agentMemoryLimitMb: integer("agent_memory_limit_mb"),
+	outputRedaction: text("output_redaction").$type<OutputRedactionMode>(),
 });
 
 export type InsertSettings = typeof settings.$inferInsert;
@@ -178,6 +507,7 @@ export const users = sqliteTable(
 	(table) => [
 		index("users_email_idx").on(table.email),
 		index("users_clerk_id_idx").on(table.clerk_id),
+		index("users_created_at_idx").on(table.created_at),
 	],
 );
 
@@ -202,6 +532,7 @@ export const organizations = sqliteTable(
 	(table) => [
 		index("organizations_slug_idx").on(table.slug),
 		index("organizations_clerk_org_id_idx").on(table.clerk_org_id),
+		index("organizations_created_at_idx").on(table.created_at),
 	],
 );
 
@@ -227,6 +558,7 @@ export const organizationMembers = sqliteTable(
 	(table) => [
 		index("organization_members_organization_id_idx").on(table.organization_id),
 		index("organization_members_user_id_idx").on(table.user_id),
+		index("organization_members_created_at_idx").on(table.created_at),
 	],
 );
 
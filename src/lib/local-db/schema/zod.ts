@@ -52,6 +52,25 @@ export const EXECUTION_MODES = ["sequential", "parallel"] as const;
 
 export type ExecutionMode = (typeof EXECUTION_MODES)[number];
 
+const clockTimeSchema = z.string().regex(/^([01]\d|2[0-3]):[0-5]\d$/, "Expected HH:MM");
+
+/**
+ * Notification preferences. Stored globally in settings and optionally per
+ * repository, where unset fields fall back to the global value.
+ */
+export const notificationPreferencesSchema = z.object({
+	agentComplete: z.boolean().optional(),
+	needsInput: z.boolean().optional(),
+	/** Wall-clock window in the settings time zone; may wrap past midnight */
+	quietHours: z.object({ start: clockTimeSchema, end: clockTimeSchema }).nullable().optional(),
+	/** Per-repository only; the global switch is notificationSoundsMuted */
+	sound: z.boolean().optional(),
+	/** Global only. Coalesces notifications arriving within this many seconds; 0 turns it off */
+	digestWindowSeconds: z.number().int().min(0).max(600).optional(),
+});
+
+export type NotificationPreferences = z.infer<typeof notificationPreferencesSchema>;
+
 /**
  * Terminal preset
  */
@@ -113,9 +132,47 @@ export const TERMINAL_LINK_BEHAVIORS = ["external-editor", "file-viewer"] as con
 
 export type TerminalLinkBehavior = (typeof TERMINAL_LINK_BEHAVIORS)[number];
 
+/**
+ * What error reporting and analytics may send: nothing, errors only, or
+ * errors with breadcrumbs plus usage analytics
+ */
+export const TELEMETRY_LEVELS = ["off", "errors", "full"] as const;
+
+export type TelemetryLevel = (typeof TELEMETRY_LEVELS)[number];
+
+/**
+ * How secrets are scrubbed from output Caspian stores: not at all, known
+ * token formats and credential assignments, or those plus random-looking keys
+ */
+export const OUTPUT_REDACTION_MODES = ["off", "patterns", "strict"] as const;
+
+export type OutputRedactionMode = (typeof OUTPUT_REDACTION_MODES)[number];
+
 /**
  * Branch prefix modes for node branch naming
  */
 export const BRANCH_PREFIX_MODES = ["github", "author", "custom", "none"] as const;
 
 export type BranchPrefixMode = (typeof BRANCH_PREFIX_MODES)[number];
+
+/**
+ * Repository initialization status; "cloning" while a clone is in flight
+ * or was interrupted by the app quitting
+ */
+export const REPOSITORY_INIT_STATUSES = ["cloning", "ready"] as const;
+
+export type RepositoryInitStatus = (typeof REPOSITORY_INIT_STATUSES)[number];
+
+/**
+ * Commit signing formats, matching git's `gpg.format` values
+ */
+export const COMMIT_SIGNING_FORMATS = ["openpgp", "ssh", "x509"] as const;
+
+export type CommitSigningFormat = (typeof COMMIT_SIGNING_FORMATS)[number];
+
+/**
+ * Which side of a diff a review comment is anchored to
+ */
+export const REVIEW_COMMENT_SIDES = ["old", "new"] as const;
+
+export type ReviewCommentSide = (typeof REVIEW_COMMENT_SIDES)[number];
@@ -0,0 +1,95 @@
+import { TRPCError } from "@trpc/server";
+import type { CaspianErrorCode, CaspianErrorData } from "shared/types/errors";
+
+type TRPCErrorCode = TRPCError["code"];
+
+const TRPC_CODES: Record<CaspianErrorCode, TRPCErrorCode> = {
+	NODE_NOT_FOUND: "NOT_FOUND",
+	NODE_NO_WORKING_DIRECTORY: "PRECONDITION_FAILED",
+	NOT_A_WORKTREE_NODE: "BAD_REQUEST",
+	REPOSITORY_NOT_FOUND: "NOT_FOUND",
+	NOT_A_GIT_REPOSITORY: "BAD_REQUEST",
+	WORKTREE_NOT_FOUND: "NOT_FOUND",
+	WORKTREE_MISSING: "PRECONDITION_FAILED",
+	PATH_NOT_FOUND: "PRECONDITION_FAILED",
+	BRANCH_NOT_FOUND: "NOT_FOUND",
+	BRANCH_EXISTS: "CONFLICT",
+	BRANCH_CHECKED_OUT: "CONFLICT",
+	GIT_IDENTITY_MISSING: "PRECONDITION_FAILED",
+	GIT_OPERATION_FAILED: "INTERNAL_SERVER_ERROR",
+	HOOK_FAILED: "PRECONDITION_FAILED",
+	ISSUE_TRACKER_FAILED: "BAD_GATEWAY",
+	CONFIG_MISSING: "PRECONDITION_FAILED",
+	OPERATION_IN_PROGRESS: "CONFLICT",
+	OPERATION_CANCELLED: "CLIENT_CLOSED_REQUEST",
+	CONFLICT: "CONFLICT",
+	SECURE_STORAGE_UNAVAILABLE: "PRECONDITION_FAILED",
+	LOCAL_ONLY_MODE: "PRECONDITION_FAILED",
+	NO_WINDOW: "PRECONDITION_FAILED",
+	TERMINAL_SESSION_KILLED: "BAD_REQUEST",
+	INVALID_INPUT: "BAD_REQUEST",
+	NOT_FOUND: "NOT_FOUND",
+};
+
+/**
+ * A TRPCError with a stable Caspian error code and an optional hint, both
+ * serialized to the client by the errorFormatter in ./index.
+ */
+export class CaspianError extends TRPCError {
+	readonly caspianCode: CaspianErrorCode;
+	readonly hint?: string;
+
+	constructor({
+		code,
+		message,
+		hint,
+		cause,
+	}: {
+		code: CaspianErrorCode;
+		message: string;
+		hint?: string;
+		cause?: unknown;
+	}) {
+		super({ code: TRPC_CODES[code], message, cause });
+		this.name = "CaspianError";
+		this.caspianCode = code;
+		this.hint = hint;
+	}
+
+	toData(): CaspianErrorData {
+		return { code: this.caspianCode, ...(this.hint && { hint: this.hint }) };
+	}
+}
+
+export function nodeNotFound(nodeId: string): CaspianError {
+	return new CaspianError({ code: "NODE_NOT_FOUND", message: `Node ${nodeId} not found` });
+}
+
+export function repositoryNotFound(repositoryId: string): CaspianError {
+	return new CaspianError({
+		code: "REPOSITORY_NOT_FOUND",
+		message: `Repository ${repositoryId} not found`,
+	});
+}
+
+export function worktreeNotFound(worktreeId: string): CaspianError {
+	return new CaspianError({
+		code: "WORKTREE_NOT_FOUND",
+		message: `Worktree ${worktreeId} not found`,
+	});
+}
+
+export function blockedInLocalOnlyMode(action: string): CaspianError {
+	return new CaspianError({
+		code: "LOCAL_ONLY_MODE",
+		message: `${action} is off in local-only mode`,
+		hint: "Turn off local-only mode in Settings > Preferences > Privacy",
+	});
+}
+
+export function nodeHasNoWorkingDirectory(nodeId: string): CaspianError {
+	return new CaspianError({
+		code: "NODE_NO_WORKING_DIRECTORY",
+		message: `Node ${nodeId} has no working directory`,
+	});
+}
@@ -1,6 +1,7 @@
 import { createTRPCReact } from "@trpc/react-query";
 import { initTRPC } from "@trpc/server";
 import superjson from "superjson";
+import { CaspianError } from "./errors";
 import type { AppRouter } from "./routers";
 
 /**
@@ -10,6 +11,13 @@ import type { AppRouter } from "./routers";
 const t = initTRPC.create({
 	transformer: superjson,
 	isServer: true,
+	// Stable codes and hints for CaspianErrors, so the renderer needn't parse messages
+	errorFormatter({ shape, error }) {
+		return {
+			...shape,
+			data: { ...shape.data, caspian: error instanceof CaspianError ? error.toData() : null },
+		};
+	},
 });
 
 /**
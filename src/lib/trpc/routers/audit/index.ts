@@ -0,0 +1,128 @@
+import { createHash } from "node:crypto";
+import { writeFile } from "node:fs/promises";
+import { basename } from "node:path";
+import { TRPCError } from "@trpc/server";
+import { eq } from "drizzle-orm";
+import { type BrowserWindow, dialog } from "electron";
+import { repositories } from "lib/local-db";
+import {
+	queryAuditEvents,
+	queryGlobalActivity,
+	readAuditEvents,
+	rebuildAuditIndex,
+} from "main/lib/audit";
+import { formatAuditEvents } from "main/lib/audit/format";
+import { localDb } from "main/lib/local-db";
+import { ACTIVITY_EVENT_TYPES, AUDIT_EVENT_TYPES, AUDIT_EXPORT_FORMATS } from "shared/types/audit";
+import { z } from "zod";
+import { publicProcedure, router } from "../..";
+
+type AuditExportResult =
+	| { canceled: true }
+	| { canceled: false; path: string; checksumPath: string; sha256: string; eventCount: number };
+
+export const createAuditRouter = (getWindow: () => BrowserWindow | null) => {
+	return router({
+		/**
+		 * Recent activity from the audit index, newest first. Leave repositoryId
+		 * out to see activity across all repositories.
+		 */
+		getRepoActivity: publicProcedure
+			.input(
+				z.object({
+					repositoryId: z.string().optional(),
+					nodeId: z.string().optional(),
+					types: z.array(z.enum(AUDIT_EVENT_TYPES)).optional(),
+					cursor: z.string().nullish(),
+					limit: z.number().int().positive().max(500).default(100),
+				}),
+			)
+			.query(({ input }) => queryAuditEvents(input)),
+
+		/**
+		 * What happened across all repositories, newest first: agent completions
+		 * and input requests, merges and pull request updates by default.
+		 */
+		getGlobalActivity: publicProcedure
+			.input(
+				z.object({
+					types: z.array(z.enum(AUDIT_EVENT_TYPES)).min(1).default([...ACTIVITY_EVENT_TYPES]),
+					cursor: z.string().nullish(),
+					limit: z.number().int().positive().max(200).default(50),
+				}),
+			)
+			.query(({ input }) => queryGlobalActivity(input)),
+
+		rebuildIndex: publicProcedure.mutation(() => rebuildAuditIndex()),
+
+		/**
+		 * Writes a repository's audit events to a file, prompting for the path when
+		 * none is given. A `<file>.sha256` checksum in sha256sum format is written
+		 * alongside so the export can be verified later.
+		 */
+		exportLog: publicProcedure
+			.input(
+				z.object({
+					repositoryId: z.string(),
+					format: z.enum(AUDIT_EXPORT_FORMATS).default("jsonl"),
+					from: z.number().int().optional(),
+					to: z.number().int().optional(),
+					filePath: z.string().min(1).optional(),
+				}),
+			)
+			.mutation(async ({ input }): Promise<AuditExportResult> => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.repositoryId))
+					.get();
+
+				if (!repository) {
+					throw new TRPCError({
+						code: "NOT_FOUND",
+						message: `Repository ${input.repositoryId} not found`,
+					});
+				}
+
+				let filePath = input.filePath;
+				if (!filePath) {
+					const window = getWindow();
+					if (!window) {
+						throw new TRPCError({ code: "PRECONDITION_FAILED", message: "No window available" });
+					}
+
+					const result = await dialog.showSaveDialog(window, {
+						title: "Export Audit Log",
+						defaultPath: `${repository.name}-audit.${input.format}`,
+						filters: [
+							input.format === "csv"
+								? { name: "CSV", extensions: ["csv"] }
+								: { name: "JSON Lines", extensions: ["jsonl"] },
+						],
+					});
+
+					if (result.canceled || !result.filePath) {
+						return { canceled: true };
+					}
+					filePath = result.filePath;
+				}
+
+				const events = await readAuditEvents({
+					repositoryId: repository.id,
+					from: input.from,
+					to: input.to,
+				});
+				const content = formatAuditEvents(events, input.format);
+				const sha256 = createHash("sha256").update(content).digest("hex");
+				const checksumPath = `${filePath}.sha256`;
+
+				await writeFile(filePath, content, "utf-8");
+				await writeFile(checksumPath, `${sha256}  ${basename(filePath)}\n`, "utf-8");
+
+				console.log(`[audit] Exported ${events.length} events to ${filePath}`);
+				return { canceled: false, path: filePath, checksumPath, sha256, eventCount: events.length };
+			}),
+	});
+};
+
+export type AuditRouter = ReturnType<typeof createAuditRouter>;
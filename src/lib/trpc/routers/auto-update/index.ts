@@ -2,7 +2,7 @@ import { observable } from "@trpc/server/observable";
 import {
 	type AutoUpdateStatusEvent,
 	autoUpdateEmitter,
-	checkForUpdates,
+	checkForUpdatesNow,
 	dismissUpdate,
 	getUpdateStatus,
 	installUpdate,
@@ -34,8 +34,9 @@ export const createAutoUpdateRouter = () => {
 			return getUpdateStatus();
 		}),
 
-		check: publicProcedure.mutation(() => {
-			checkForUpdates();
+		/** Checks the selected channel; the result says which version it offers, if any */
+		check: publicProcedure.mutation(async () => {
+			return checkForUpdatesNow();
 		}),
 
 		install: publicProcedure.mutation(() => {
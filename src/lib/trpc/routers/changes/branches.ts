@@ -1,6 +1,7 @@
 import { eq } from "drizzle-orm";
 import { worktrees } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
+import { pathsEqual, toNativePath } from "main/lib/platform-paths";
 import simpleGit from "simple-git";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
@@ -143,8 +144,8 @@ async function getCheckedOutBranches(
 				currentPath = line.substring(9).trim();
 			} else if (line.startsWith("branch ")) {
 				const branch = line.substring(7).trim().replace("refs/heads/", "");
-				if (currentPath && currentPath !== currentWorktreePath) {
-					checkedOutBranches[branch] = currentPath;
+				if (currentPath && !pathsEqual(currentPath, currentWorktreePath)) {
+					checkedOutBranches[branch] = toNativePath(currentPath);
 				}
 			}
 		}
@@ -0,0 +1,212 @@
+import { basename } from "node:path";
+import { operationManager } from "main/lib/operation-manager";
+import type { ChangedFile, StructuredFileDiff } from "shared/changes-types";
+import simpleGit from "simple-git";
+import { z } from "zod";
+import { publicProcedure, router } from "../..";
+import { CaspianError } from "../../errors";
+import { findNodeByPath } from "../nodes/utils/db-helpers";
+import { assertRegisteredWorktree } from "./security";
+import { applyNumstatToFiles } from "./utils/apply-numstat";
+import { applyDiffByteCap, DEFAULT_MAX_DIFF_BYTES, isGeneratedFile } from "./utils/diff-limits";
+import { parseUnifiedDiff } from "./utils/parse-diff";
+import { parseNameStatus } from "./utils/parse-status";
+
+type DiffCategory = "against-base" | "committed" | "staged" | "unstaged";
+
+/** Abbreviated or full commit sha (SHA-1 or SHA-256); also keeps option-like values out of git */
+const COMMIT_HASH = /^[0-9a-f]{4,64}$/i;
+
+/** Paths per check-attr call, to stay well under OS argument limits */
+const CHECK_ATTR_BATCH_SIZE = 500;
+
+/**
+ * Builds the git arguments that select the changes for a category.
+ * Mirrors the comparisons used by getStatus/getFileContents.
+ */
+function getDiffArgs({
+	category,
+	defaultBranch,
+	commitHash,
+}: {
+	category: DiffCategory;
+	defaultBranch: string;
+	commitHash?: string;
+}): string[] {
+	const common = ["--no-color", "--no-ext-diff", "-M"];
+
+	switch (category) {
+		case "against-base":
+			return ["diff", ...common, `origin/${defaultBranch}...HEAD`];
+		case "committed":
+			if (!commitHash) {
+				throw new Error("commitHash required for committed category");
+			}
+			if (!COMMIT_HASH.test(commitHash)) {
+				throw new Error(`Invalid commit hash: ${commitHash}`);
+			}
+			// show handles root commits, which have no parent to diff against
+			return ["show", ...common, "--format=", commitHash];
+		case "staged":
+			return ["diff", ...common, "--cached"];
+		case "unstaged":
+			return ["diff", ...common];
+	}
+}
+
+/**
+ * Returns paths the repository marks as generated via `linguist-generated` in .gitattributes.
+ */
+async function getLinguistGenerated(
+	git: ReturnType<typeof simpleGit>,
+	paths: string[],
+): Promise<Set<string>> {
+	const generated = new Set<string>();
+
+	for (let i = 0; i < paths.length; i += CHECK_ATTR_BATCH_SIZE) {
+		const batch = paths.slice(i, i + CHECK_ATTR_BATCH_SIZE);
+		try {
+			// -z output: path NUL attribute NUL value NUL
+			const output = await git.raw(["check-attr", "-z", "linguist-generated", "--", ...batch]);
+			const fields = output.split("\0");
+			for (let j = 0; j + 2 < fields.length; j += 3) {
+				const value = fields[j + 2];
+				if (value === "true" || value === "set") {
+					generated.add(fields[j]);
+				}
+			}
+		} catch (error) {
+			// Files in this batch just won't be collapsed as generated
+			console.warn(`[changes/diff] check-attr failed for a batch of ${batch.length} paths:`, error);
+		}
+	}
+
+	return generated;
+}
+
+const diffInput = {
+	worktreePath: z.string(),
+	category: z.enum(["against-base", "committed", "staged", "unstaged"]),
+	commitHash: z.string().optional(),
+	defaultBranch: z.string().optional(),
+	/** Lets the renderer cancel a slow diff through operations.cancel */
+	operationId: z.string().optional(),
+};
+
+/**
+ * Runs a diff as a cancellable operation when the caller passes an id;
+ * cancelling kills the running git process. Diffs without one are usually
+ * quick, so they stay out of the operations list.
+ */
+function runDiff<T>(
+	{ worktreePath, operationId }: { worktreePath: string; operationId?: string },
+	task: (git: ReturnType<typeof simpleGit>, pathspec: string[]) => Promise<T>,
+): Promise<T> {
+	const config = ["core.quotePath=false"];
+	// Scoped nodes only diff their subdirectory
+	const scopePath = findNodeByPath(worktreePath)?.scopePath;
+	const pathspec = scopePath ? ["--", scopePath] : [];
+	if (!operationId) {
+		return task(simpleGit(worktreePath, { config }), pathspec);
+	}
+
+	const title = `Diff ${basename(worktreePath)}`;
+	return operationManager.run({ id: operationId, kind: "diff", title }, async ({ signal }) => {
+		try {
+			return await task(simpleGit(worktreePath, { config, abort: signal }), pathspec);
+		} catch (error) {
+			if (signal.aborted) {
+				throw new CaspianError({ code: "OPERATION_CANCELLED", message: "Diff was cancelled" });
+			}
+			throw error;
+		}
+	});
+}
+
+export const createDiffRouter = () => {
+	return router({
+		/**
+		 * Lists changed files with stats but no hunks, so large change sets can be
+		 * rendered as a file list and hunks loaded per file via getStructuredDiff.
+		 */
+		listDiffFiles: publicProcedure
+			.input(z.object(diffInput))
+			.query(async ({ input }): Promise<Array<ChangedFile & { isGenerated: boolean }>> => {
+				assertRegisteredWorktree(input.worktreePath);
+
+				return runDiff(input, async (git, pathspec) => {
+					const args = getDiffArgs({
+						category: input.category,
+						defaultBranch: input.defaultBranch || "main",
+						commitHash: input.commitHash,
+					});
+
+					const files = parseNameStatus(await git.raw([...args, "--name-status", ...pathspec]));
+					await applyNumstatToFiles(git, files, [...args, "--numstat", ...pathspec]);
+
+					const linguistGenerated = await getLinguistGenerated(git, files.map((file) => file.path));
+
+					return files.map((file) => ({
+						...file,
+						isGenerated: isGeneratedFile(file.path) || linguistGenerated.has(file.path),
+					}));
+				});
+			}),
+
+		/**
+		 * Returns parsed diffs (files -> hunks -> lines) so the renderer
+		 * doesn't need to re-parse patch text.
+		 *
+		 * Generated files and lockfiles come back without hunks unless requested
+		 * explicitly via filePaths or includeGenerated. Output is capped at maxBytes;
+		 * files past the cap are marked truncated.
+		 */
+		getStructuredDiff: publicProcedure
+			.input(
+				z.object({
+					...diffInput,
+					filePaths: z.array(z.string()).optional(),
+					includeGenerated: z.boolean().optional(),
+					maxBytes: z.number().int().positive().optional(),
+				}),
+			)
+			.query(async ({ input }): Promise<StructuredFileDiff[]> => {
+				assertRegisteredWorktree(input.worktreePath);
+
+				return runDiff(input, async (git, pathspec) => {
+					const args = getDiffArgs({
+						category: input.category,
+						defaultBranch: input.defaultBranch || "main",
+						commitHash: input.commitHash,
+					});
+
+					const requestedPaths = input.filePaths ?? [];
+					if (requestedPaths.length > 0) {
+						args.push("--", ...requestedPaths);
+					} else {
+						args.push(...pathspec);
+					}
+
+					const files = parseUnifiedDiff(await git.raw(args));
+
+					// Explicitly requested files are always shown in full
+					if (!input.includeGenerated && requestedPaths.length === 0) {
+						const linguistGenerated = await getLinguistGenerated(
+							git,
+							files.map((file) => file.path),
+						);
+						for (const file of files) {
+							if (isGeneratedFile(file.path) || linguistGenerated.has(file.path)) {
+								file.hunks = [];
+								file.skippedReason = "generated";
+							}
+						}
+					}
+
+					applyDiffByteCap(files, input.maxBytes ?? DEFAULT_MAX_DIFF_BYTES);
+
+					return files;
+				});
+			}),
+	});
+};
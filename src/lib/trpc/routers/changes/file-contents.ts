@@ -1,15 +1,27 @@
-import type { FileContents } from "shared/changes-types";
+import { createHash } from "node:crypto";
+import type {
+	BinaryFileInfo,
+	BinaryFileVersion,
+	FileAtRevision,
+	FileContents,
+} from "shared/changes-types";
 import { detectLanguage } from "shared/detect-language";
 import simpleGit from "simple-git";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
-import { assertRegisteredWorktree, PathValidationError, secureFs } from "./security";
+import {
+	assertRegisteredWorktree,
+	assertValidGitPath,
+	PathValidationError,
+	secureFs,
+} from "./security";
+import { getImageMimeType, isBinaryContent, isBinaryString } from "./utils/binary";
 
 /** Maximum file size for reading (2 MiB) */
 const MAX_FILE_SIZE = 2 * 1024 * 1024;
 
-/** Bytes to scan for binary detection */
-const BINARY_CHECK_SIZE = 8192;
+/** Maximum blob size read for hashing or image previews (16 MiB) */
+const MAX_BINARY_READ_SIZE = 16 * 1024 * 1024;
 
 /**
  * Result type for readWorkingFile procedure
@@ -21,19 +33,6 @@ type ReadWorkingFileResult =
 			reason: "not-found" | "too-large" | "binary" | "outside-worktree" | "symlink-escape";
 	  };
 
-/**
- * Detects if a buffer contains binary content by checking for NUL bytes
- */
-function isBinaryContent(buffer: Buffer): boolean {
-	const checkLength = Math.min(buffer.length, BINARY_CHECK_SIZE);
-	for (let i = 0; i < checkLength; i++) {
-		if (buffer[i] === 0) {
-			return true;
-		}
-	}
-	return false;
-}
-
 export const createFileContentsRouter = () => {
 	return router({
 		getFileContents: publicProcedure
@@ -64,6 +63,16 @@ export const createFileContentsRouter = () => {
 					input.commitHash,
 				);
 
+				// Decoded binary content is garbage in a text diff; use getBinaryFileInfo instead
+				if (isBinaryString(original) || isBinaryString(modified)) {
+					return {
+						original: "",
+						modified: "",
+						language: detectLanguage(input.filePath),
+						isBinary: true,
+					};
+				}
+
 				return {
 					original,
 					modified,
@@ -71,6 +80,93 @@ export const createFileContentsRouter = () => {
 				};
 			}),
 
+		/**
+		 * Sizes, hashes and (for images) base64 payloads of both sides of a change,
+		 * for files that can't be shown as a text diff.
+		 */
+		getBinaryFileInfo: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					filePath: z.string(),
+					oldPath: z.string().optional(),
+					category: z.enum(["against-base", "committed", "staged", "unstaged"]),
+					commitHash: z.string().optional(),
+					defaultBranch: z.string().optional(),
+				}),
+			)
+			.query(async ({ input }): Promise<BinaryFileInfo> => {
+				assertRegisteredWorktree(input.worktreePath);
+				const originalPath = input.oldPath || input.filePath;
+				assertValidGitPath(input.filePath);
+				assertValidGitPath(originalPath);
+
+				const { original, modified } = await getBinaryVersions({
+					git: simpleGit(input.worktreePath),
+					worktreePath: input.worktreePath,
+					filePath: input.filePath,
+					originalPath,
+					category: input.category,
+					defaultBranch: input.defaultBranch || "main",
+					commitHash: input.commitHash,
+				});
+
+				const mimeType = getImageMimeType(input.filePath);
+				const originalVersion = describeBinaryVersion(original, getImageMimeType(originalPath));
+				const modifiedVersion = describeBinaryVersion(modified, mimeType);
+
+				return {
+					isBinary:
+						(original.buffer !== null && isBinaryContent(original.buffer)) ||
+						(modified.buffer !== null && isBinaryContent(modified.buffer)),
+					isImage: mimeType !== null,
+					original: originalVersion,
+					modified: modifiedVersion,
+				};
+			}),
+
+		/**
+		 * A file's full content at HEAD, the merge base with the default branch,
+		 * the index, or any commit-ish.
+		 */
+		getFileAtRevision: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					filePath: z.string(),
+					revision: z.string().min(1),
+					defaultBranch: z.string().optional(),
+				}),
+			)
+			.query(async ({ input }): Promise<FileAtRevision> => {
+				assertRegisteredWorktree(input.worktreePath);
+				assertValidGitPath(input.filePath);
+
+				const git = simpleGit(input.worktreePath);
+				const commitSha = await resolveRevision(git, input.revision, input.defaultBranch || "main");
+				const spec = commitSha === null ? `:0:${input.filePath}` : `${commitSha}:${input.filePath}`;
+				const version = await readGitObject(git, spec);
+
+				const result: FileAtRevision = {
+					revision: input.revision,
+					commitSha,
+					exists: version.exists,
+					content: "",
+					isBinary: false,
+					tooLarge: version.size !== null && version.size > MAX_FILE_SIZE,
+					size: version.size,
+					language: detectLanguage(input.filePath),
+				};
+
+				if (!version.buffer || result.tooLarge) {
+					return result;
+				}
+				if (isBinaryContent(version.buffer)) {
+					return { ...result, isBinary: true };
+				}
+				return { ...result, content: version.buffer.toString("utf-8") };
+			}),
+
 		saveFile: publicProcedure
 			.input(
 				z.object({
@@ -251,3 +347,152 @@ async function getUnstagedVersions(
 
 	return { original, modified };
 }
+
+/**
+ * Resolves a revision keyword or commit-ish to a commit sha.
+ * Returns null for "index", which has no commit.
+ */
+async function resolveRevision(
+	git: ReturnType<typeof simpleGit>,
+	revision: string,
+	defaultBranch: string,
+): Promise<string | null> {
+	if (revision === "index") {
+		return null;
+	}
+
+	if (revision === "base") {
+		try {
+			return (await git.raw(["merge-base", "HEAD", `origin/${defaultBranch}`])).trim();
+		} catch {
+			throw new Error(`No merge base between HEAD and origin/${defaultBranch}`);
+		}
+	}
+
+	// Reject option-like values and tree paths; the file path is supplied separately
+	if (revision.startsWith("-") || revision.includes(":")) {
+		throw new Error(`Invalid revision: ${revision}`);
+	}
+
+	let sha = "";
+	try {
+		sha = (await git.raw(["rev-parse", "--verify", "--quiet", `${revision}^{commit}`])).trim();
+	} catch {}
+	if (!sha) {
+		throw new Error(`Unknown revision: ${revision}`);
+	}
+	return sha;
+}
+
+interface RawVersion {
+	exists: boolean;
+	size: number | null;
+	/** null when missing or larger than MAX_BINARY_READ_SIZE */
+	buffer: Buffer | null;
+}
+
+const MISSING_VERSION: RawVersion = { exists: false, size: null, buffer: null };
+
+/** Reads a git object as raw bytes, skipping the read when it's too large */
+async function readGitObject(
+	git: ReturnType<typeof simpleGit>,
+	spec: string,
+): Promise<RawVersion> {
+	let size: number;
+	try {
+		size = Number.parseInt((await git.raw(["cat-file", "-s", spec])).trim(), 10);
+	} catch {
+		return MISSING_VERSION;
+	}
+
+	if (Number.isNaN(size) || size > MAX_BINARY_READ_SIZE) {
+		return { exists: true, size: Number.isNaN(size) ? null : size, buffer: null };
+	}
+
+	try {
+		return { exists: true, size, buffer: await git.showBuffer([spec]) };
+	} catch {
+		return { exists: true, size, buffer: null };
+	}
+}
+
+async function readWorktreeFile(worktreePath: string, filePath: string): Promise<RawVersion> {
+	try {
+		const stats = await secureFs.stat(worktreePath, filePath);
+		if (stats.size > MAX_BINARY_READ_SIZE) {
+			return { exists: true, size: stats.size, buffer: null };
+		}
+		const buffer = await secureFs.readFileBuffer(worktreePath, filePath);
+		return { exists: true, size: buffer.length, buffer };
+	} catch {
+		return MISSING_VERSION;
+	}
+}
+
+/** Raw-byte counterpart of getFileVersions */
+async function getBinaryVersions({
+	git,
+	worktreePath,
+	filePath,
+	originalPath,
+	category,
+	defaultBranch,
+	commitHash,
+}: {
+	git: ReturnType<typeof simpleGit>;
+	worktreePath: string;
+	filePath: string;
+	originalPath: string;
+	category: DiffCategory;
+	defaultBranch: string;
+	commitHash?: string;
+}): Promise<{ original: RawVersion; modified: RawVersion }> {
+	switch (category) {
+		case "against-base": {
+			const [original, modified] = await Promise.all([
+				readGitObject(git, `origin/${defaultBranch}:${originalPath}`),
+				readGitObject(git, `HEAD:${filePath}`),
+			]);
+			return { original, modified };
+		}
+
+		case "committed": {
+			if (!commitHash) {
+				throw new Error("commitHash required for committed category");
+			}
+			const [original, modified] = await Promise.all([
+				readGitObject(git, `${commitHash}^:${originalPath}`),
+				readGitObject(git, `${commitHash}:${filePath}`),
+			]);
+			return { original, modified };
+		}
+
+		case "staged": {
+			const [original, modified] = await Promise.all([
+				readGitObject(git, `HEAD:${originalPath}`),
+				readGitObject(git, `:0:${filePath}`),
+			]);
+			return { original, modified };
+		}
+
+		case "unstaged": {
+			// Try staged version first, fall back to HEAD
+			let original = await readGitObject(git, `:0:${originalPath}`);
+			if (!original.exists) {
+				original = await readGitObject(git, `HEAD:${originalPath}`);
+			}
+			const modified = await readWorktreeFile(worktreePath, filePath);
+			return { original, modified };
+		}
+	}
+}
+
+function describeBinaryVersion(version: RawVersion, mimeType: string | null): BinaryFileVersion {
+	return {
+		exists: version.exists,
+		size: version.size,
+		sha256: version.buffer ? createHash("sha256").update(version.buffer).digest("hex") : null,
+		dataBase64: version.buffer && mimeType ? version.buffer.toString("base64") : null,
+		mimeType,
+	};
+}
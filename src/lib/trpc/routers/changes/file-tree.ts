@@ -0,0 +1,140 @@
+import type { Dirent } from "node:fs";
+import { posix } from "node:path";
+import type { WorktreeFileEntry } from "shared/file-tree-types";
+import simpleGit from "simple-git";
+import { z } from "zod";
+import { publicProcedure, router } from "../..";
+import { getStatusNoLock } from "../nodes/utils/git";
+import {
+	assertRegisteredWorktree,
+	assertValidGitPath,
+	PathValidationError,
+	secureFs,
+} from "./security";
+import { buildGitPathIndex, getEntryFlags, type GitPathIndex } from "./utils/file-tree";
+import { parseGitStatus } from "./utils/parse-status";
+
+/** Keeps a deep listing of a large repository from flooding the renderer */
+const MAX_ENTRIES = 5000;
+
+/** "src/lib" from "./src/lib/" or "src\lib", and "" for the root */
+function toGitPath(path: string): string {
+	return posix.normalize(path.replace(/\\/g, "/")).replace(/^\.$|\/+$/g, "");
+}
+
+function splitNul(output: string): string[] {
+	return output.split("\0").filter((path) => path.length > 0);
+}
+
+export const createFileTreeRouter = () => {
+	return router({
+		/**
+		 * Lists a directory of a worktree with git's view of each entry, so the
+		 * renderer doesn't walk the disk or reimplement .gitignore. Ignored
+		 * directories are listed but not descended into.
+		 */
+		listWorktreeFiles: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					/** Directory to list, relative to the worktree; empty for the root */
+					path: z.string().default(""),
+					/** 1 lists only the directory's own entries */
+					depth: z.number().int().min(1).max(5).default(1),
+				}),
+			)
+			.query(async ({ input }): Promise<{ entries: WorktreeFileEntry[]; truncated: boolean }> => {
+				assertRegisteredWorktree(input.worktreePath);
+				assertValidGitPath(input.path);
+				const relativeRoot = toGitPath(input.path);
+				const pathspec = relativeRoot ? ["--", relativeRoot] : [];
+
+				const git = simpleGit(input.worktreePath);
+				const [tracked, ignored, status] = await Promise.all([
+					git.raw(["ls-files", "-z", ...pathspec]),
+					git.raw([
+						"ls-files",
+						"-z",
+						"--others",
+						"--ignored",
+						"--exclude-standard",
+						"--directory",
+						...pathspec,
+					]),
+					getStatusNoLock(input.worktreePath),
+				]);
+				const { staged, unstaged, untracked } = parseGitStatus(status);
+				const index = buildGitPathIndex(splitNul(tracked), splitNul(ignored), [
+					...staged,
+					...unstaged,
+					...untracked,
+				]);
+
+				const budget = { remaining: MAX_ENTRIES };
+				const entries = await listEntries({
+					worktreePath: input.worktreePath,
+					relativeDir: relativeRoot,
+					depth: input.depth,
+					index,
+					budget,
+				});
+				return { entries, truncated: budget.remaining < 0 };
+			}),
+	});
+};
+
+async function listEntries({
+	worktreePath,
+	relativeDir,
+	depth,
+	index,
+	budget,
+}: {
+	worktreePath: string;
+	relativeDir: string;
+	depth: number;
+	index: GitPathIndex;
+	budget: { remaining: number };
+}): Promise<WorktreeFileEntry[]> {
+	let dirents: Dirent[];
+	try {
+		dirents = await secureFs.readdir(worktreePath, relativeDir);
+	} catch (error) {
+		if (error instanceof PathValidationError) throw error;
+		console.warn(`[changes/listWorktreeFiles] Failed to read ${relativeDir || "."}:`, error);
+		return [];
+	}
+
+	const entries: WorktreeFileEntry[] = [];
+	const sorted = dirents
+		.filter((dirent) => dirent.name !== ".git")
+		.sort((a, b) => {
+			if (a.isDirectory() !== b.isDirectory()) return a.isDirectory() ? -1 : 1;
+			return a.name.localeCompare(b.name);
+		});
+	for (const dirent of sorted) {
+		if (--budget.remaining < 0) break;
+
+		// Symlinks are listed as files and never followed out of the worktree
+		const isDirectory = dirent.isDirectory();
+		const relativePath = relativeDir ? `${relativeDir}/${dirent.name}` : dirent.name;
+		const flags = getEntryFlags(relativePath, isDirectory, index);
+		const descend = isDirectory && !flags.ignored && depth > 1;
+		entries.push({
+			name: dirent.name,
+			relativePath,
+			isDirectory,
+			...flags,
+			children: descend
+				? await listEntries({
+						worktreePath,
+						relativeDir: relativePath,
+						depth: depth - 1,
+						index,
+						budget,
+					})
+				: null,
+		});
+	}
+	return entries;
+}
@@ -1,7 +1,23 @@
 import { shell } from "electron";
+import { recordAuditEvent } from "main/lib/audit";
+import { changesCache } from "main/lib/changes-cache";
+import { runNodePreHook } from "main/lib/node-hooks";
+import type { RepoHook } from "main/lib/repo-hooks";
+import type { AuditEventType } from "shared/types/audit";
 import simpleGit from "simple-git";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
+import { findNodeByPath } from "../nodes/utils/db-helpers";
+import { getGitCredentialOverrides } from "../nodes/utils/github/accounts";
+import {
+	buildNewPullRequestUrl,
+	getHostingProvider,
+	getOriginRemote,
+} from "../nodes/utils/hosting";
+import { formatIssueForPullRequest } from "../nodes/utils/issues";
+import { assertRequiredGatePassed } from "../nodes/utils/quality-gate";
+import { preflightSsh } from "../nodes/utils/ssh";
+import { getCommitConfigForPath } from "../repositories/utils/commit-signing";
 import { isUpstreamMissingError } from "./git-utils";
 import { assertRegisteredWorktree } from "./security";
 
@@ -16,9 +32,65 @@ async function hasUpstreamBranch(git: ReturnType<typeof simpleGit>): Promise<boo
 	}
 }
 
-async function fetchCurrentBranch(git: ReturnType<typeof simpleGit>): Promise<void> {
+async function fetchCurrentBranch(
+	git: ReturnType<typeof simpleGit>,
+	worktreePath: string,
+): Promise<void> {
 	const branch = (await git.revparse(["--abbrev-ref", "HEAD"])).trim();
 	await git.fetch(["origin", branch]);
+	// Remote-tracking refs moved, which the status cache can't detect on its own
+	changesCache.invalidate(worktreePath);
+}
+
+/** Git for operations that talk to origin, authenticating as the repository's GitHub account */
+function remoteGit(worktreePath: string, config: string[] = []): ReturnType<typeof simpleGit> {
+	const overrides = getGitCredentialOverrides(worktreePath);
+	if (!overrides) {
+		return simpleGit(worktreePath, { config });
+	}
+	return simpleGit(worktreePath, { config: [...config, ...overrides.config] }).env({
+		...process.env,
+		...overrides.env,
+	});
+}
+
+/** Fails with a fix-it message before pushing over SSH when ssh has no key to offer */
+async function assertSshReady(git: ReturnType<typeof simpleGit>): Promise<void> {
+	let remoteUrl: string;
+	try {
+		remoteUrl = (await git.remote(["get-url", "origin"])) || "";
+	} catch {
+		return; // No origin; the push reports that itself
+	}
+	const problem = await preflightSsh(remoteUrl);
+	if (problem) {
+		throw new Error(problem);
+	}
+}
+
+/** Runs the repository's pre-* hook for the node at this path; a failing hook throws */
+async function runGitPreHook({
+	worktreePath,
+	hook,
+	extra,
+}: {
+	worktreePath: string;
+	hook: RepoHook;
+	extra?: Record<string, unknown>;
+}): Promise<void> {
+	const node = findNodeByPath(worktreePath);
+	if (node) await runNodePreHook({ hook, nodeId: node.id, extra });
+}
+
+function auditGitOperation(
+	worktreePath: string,
+	type: AuditEventType,
+	summary: string,
+	data?: Record<string, unknown>,
+): void {
+	const node = findNodeByPath(worktreePath);
+	if (!node) return;
+	recordAuditEvent({ type, nodeId: node.id, repositoryId: node.repositoryId, summary, data });
 }
 
 export const createGitOperationsRouter = () => {
@@ -36,8 +108,14 @@ export const createGitOperationsRouter = () => {
 			.mutation(async ({ input }): Promise<{ success: boolean; hash: string }> => {
 				assertRegisteredWorktree(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				const git = simpleGit(input.worktreePath, {
+					config: getCommitConfigForPath(input.worktreePath),
+				});
 				const result = await git.commit(input.message);
+				auditGitOperation(input.worktreePath, "commit", `Committed ${result.commit}`, {
+					hash: result.commit,
+					message: input.message,
+				});
 				return { success: true, hash: result.commit };
 			}),
 
@@ -51,7 +129,10 @@ export const createGitOperationsRouter = () => {
 			.mutation(async ({ input }): Promise<{ success: boolean }> => {
 				assertRegisteredWorktree(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				await runGitPreHook({ worktreePath: input.worktreePath, hook: "pre-push" });
+
+				const git = remoteGit(input.worktreePath);
+				await assertSshReady(git);
 				const hasUpstream = await hasUpstreamBranch(git);
 
 				if (input.setUpstream && !hasUpstream) {
@@ -60,7 +141,8 @@ export const createGitOperationsRouter = () => {
 				} else {
 					await git.push();
 				}
-				await fetchCurrentBranch(git);
+				await fetchCurrentBranch(git, input.worktreePath);
+				auditGitOperation(input.worktreePath, "push", "Pushed to origin");
 				return { success: true };
 			}),
 
@@ -73,7 +155,8 @@ export const createGitOperationsRouter = () => {
 			.mutation(async ({ input }): Promise<{ success: boolean }> => {
 				assertRegisteredWorktree(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				// Rebasing rewrites local commits, so use the repository's commit identity
+				const git = remoteGit(input.worktreePath, getCommitConfigForPath(input.worktreePath));
 				try {
 					await git.pull(["--rebase"]);
 				} catch (error) {
@@ -85,6 +168,8 @@ export const createGitOperationsRouter = () => {
 					}
 					throw error;
 				}
+				changesCache.invalidate(input.worktreePath);
+				auditGitOperation(input.worktreePath, "pull", "Pulled with rebase");
 				return { success: true };
 			}),
 
@@ -97,7 +182,11 @@ export const createGitOperationsRouter = () => {
 			.mutation(async ({ input }): Promise<{ success: boolean }> => {
 				assertRegisteredWorktree(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				await runGitPreHook({ worktreePath: input.worktreePath, hook: "pre-push" });
+
+				// Rebasing rewrites local commits, so use the repository's commit identity
+				const git = remoteGit(input.worktreePath, getCommitConfigForPath(input.worktreePath));
+				await assertSshReady(git);
 				try {
 					await git.pull(["--rebase"]);
 				} catch (error) {
@@ -105,13 +194,16 @@ export const createGitOperationsRouter = () => {
 					if (isUpstreamMissingError(message)) {
 						const branch = await git.revparse(["--abbrev-ref", "HEAD"]);
 						await git.push(["--set-upstream", "origin", branch.trim()]);
-						await fetchCurrentBranch(git);
+						await fetchCurrentBranch(git, input.worktreePath);
+						auditGitOperation(input.worktreePath, "push", "Published branch to origin");
 						return { success: true };
 					}
 					throw error;
 				}
 				await git.push();
-				await fetchCurrentBranch(git);
+				await fetchCurrentBranch(git, input.worktreePath);
+				auditGitOperation(input.worktreePath, "pull", "Pulled with rebase");
+				auditGitOperation(input.worktreePath, "push", "Pushed to origin");
 				return { success: true };
 			}),
 
@@ -119,8 +211,8 @@ export const createGitOperationsRouter = () => {
 			.input(z.object({ worktreePath: z.string() }))
 			.mutation(async ({ input }): Promise<{ success: boolean }> => {
 				assertRegisteredWorktree(input.worktreePath);
-				const git = simpleGit(input.worktreePath);
-				await fetchCurrentBranch(git);
+				const git = remoteGit(input.worktreePath);
+				await fetchCurrentBranch(git, input.worktreePath);
 				return { success: true };
 			}),
 
@@ -132,9 +224,17 @@ export const createGitOperationsRouter = () => {
 			)
 			.mutation(async ({ input }): Promise<{ success: boolean; url: string }> => {
 				assertRegisteredWorktree(input.worktreePath);
+				await assertRequiredGatePassed(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				const git = remoteGit(input.worktreePath);
+				await assertSshReady(git);
 				const branch = (await git.revparse(["--abbrev-ref", "HEAD"])).trim();
+				await runGitPreHook({
+					worktreePath: input.worktreePath,
+					hook: "pre-create-pr",
+					extra: { branch },
+				});
+				await runGitPreHook({ worktreePath: input.worktreePath, hook: "pre-push" });
 				const hasUpstream = await hasUpstreamBranch(git);
 
 				// Ensure branch is pushed first
@@ -145,19 +245,23 @@ export const createGitOperationsRouter = () => {
 					await git.push();
 				}
 
-				// Get the remote URL to construct the GitHub compare URL
-				const remoteUrl = (await git.remote(["get-url", "origin"])) || "";
-				const repoMatch = remoteUrl.trim().match(/github\.com[:/](.+?)(?:\.git)?$/);
-
-				if (!repoMatch) {
-					throw new Error("Could not determine GitHub repository URL");
+				// The hosting service's new pull request page, or a generic compare URL
+				const remote = await getOriginRemote(input.worktreePath);
+				if (!remote) {
+					throw new Error("Could not determine the repository URL for origin");
 				}
 
-				const repo = repoMatch[1].replace(/\.git$/, "");
-				const url = `https://github.com/${repo}/compare/${branch}?expand=1`;
+				const provider = getHostingProvider(remote);
+				const issue = findNodeByPath(input.worktreePath)?.issue;
+				const body = issue ? formatIssueForPullRequest(issue) : undefined;
+				const url = buildNewPullRequestUrl(provider?.id ?? null, remote, branch, body);
 
 				await shell.openExternal(url);
-				await fetchCurrentBranch(git);
+				await fetchCurrentBranch(git, input.worktreePath);
+				auditGitOperation(input.worktreePath, "pr_created", `Opened pull request for ${branch}`, {
+					branch,
+					url,
+				});
 
 				return { success: true, url };
 			}),
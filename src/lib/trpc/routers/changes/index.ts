@@ -1,6 +1,8 @@
 import { router } from "../..";
 import { createBranchesRouter } from "./branches";
+import { createDiffRouter } from "./diff";
 import { createFileContentsRouter } from "./file-contents";
+import { createFileTreeRouter } from "./file-tree";
 import { createGitOperationsRouter } from "./git-operations";
 import { createStagingRouter } from "./staging";
 import { createStatusRouter } from "./status";
@@ -9,8 +11,10 @@ export const createChangesRouter = () => {
 	const branchesRouter = createBranchesRouter();
 	const statusRouter = createStatusRouter();
 	const fileContentsRouter = createFileContentsRouter();
+	const fileTreeRouter = createFileTreeRouter();
 	const stagingRouter = createStagingRouter();
 	const gitOperationsRouter = createGitOperationsRouter();
+	const diffRouter = createDiffRouter();
 
 	return router({
 		// Branch operations
@@ -22,10 +26,16 @@ export const createChangesRouter = () => {
 		// File contents operations
 		...fileContentsRouter._def.procedures,
 
+		// Git-aware worktree listing for file explorers
+		...fileTreeRouter._def.procedures,
+
 		// Staging operations
 		...stagingRouter._def.procedures,
 
 		// Git operations (commit, push, pull, sync, createPR)
 		...gitOperationsRouter._def.procedures,
+
+		// Structured diff operations
+		...diffRouter._def.procedures,
 	});
 };
@@ -1,4 +1,8 @@
+import { mkdtemp, rm, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
 import simpleGit from "simple-git";
+import { buildHunkPatch } from "../utils/hunk-patch";
 import { assertRegisteredWorktree, assertValidGitPath } from "./path-validation";
 
 /**
@@ -148,6 +152,78 @@ export async function gitDiscardAllStaged(worktreePath: string): Promise<void> {
 	await git.checkout(["--", "."]);
 }
 
+/**
+ * Applies the selected hunks of one file's diff to the index only.
+ * The patch goes through a temp file because simple-git can't pipe stdin.
+ */
+async function applyHunksToIndex({
+	worktreePath,
+	filePath,
+	hunkHeaders,
+	staged,
+}: {
+	worktreePath: string;
+	filePath: string;
+	hunkHeaders: string[];
+	staged: boolean;
+}): Promise<void> {
+	assertRegisteredWorktree(worktreePath);
+	assertValidGitPath(filePath);
+
+	if (hunkHeaders.length === 0) {
+		throw new Error("No hunks selected");
+	}
+
+	const git = simpleGit(worktreePath);
+	const fileDiff = await git.raw([
+		"diff",
+		"--no-color",
+		"--no-ext-diff",
+		"--no-renames",
+		...(staged ? ["--cached"] : []),
+		"--",
+		filePath,
+	]);
+	const patch = buildHunkPatch(fileDiff, hunkHeaders, { reverse: staged });
+
+	const tempDir = await mkdtemp(join(tmpdir(), "caspian-hunk-"));
+	const patchPath = join(tempDir, "selection.patch");
+	try {
+		await writeFile(patchPath, patch, "utf-8");
+		await git.raw(["apply", "--cached", ...(staged ? ["--reverse"] : []), patchPath]);
+	} finally {
+		await rm(tempDir, { recursive: true, force: true });
+	}
+}
+
+/**
+ * Stage selected hunks of an unstaged file.
+ *
+ * Uses `git apply --cached` with a patch built from `git diff -- <path>`,
+ * matching hunks by their `@@` header line. The worktree is untouched.
+ */
+export async function gitStageHunks(
+	worktreePath: string,
+	filePath: string,
+	hunkHeaders: string[],
+): Promise<void> {
+	await applyHunksToIndex({ worktreePath, filePath, hunkHeaders, staged: false });
+}
+
+/**
+ * Unstage selected hunks of a staged file.
+ *
+ * Uses `git apply --cached --reverse` with a patch built from
+ * `git diff --cached -- <path>`. The worktree is untouched.
+ */
+export async function gitUnstageHunks(
+	worktreePath: string,
+	filePath: string,
+	hunkHeaders: string[],
+): Promise<void> {
+	await applyHunksToIndex({ worktreePath, filePath, hunkHeaders, staged: true });
+}
+
 /**
  * Stash all tracked changes.
  *
@@ -14,12 +14,14 @@ export {
 	gitDiscardAllUnstaged,
 	gitStageAll,
 	gitStageFile,
+	gitStageHunks,
 	gitStash,
 	gitStashIncludeUntracked,
 	gitStashPop,
 	gitSwitchBranch,
 	gitUnstageAll,
 	gitUnstageFile,
+	gitUnstageHunks,
 } from "./git-commands";
 
 export {
@@ -2,6 +2,7 @@ import { isAbsolute, normalize, resolve, sep } from "node:path";
 import { eq } from "drizzle-orm";
 import { repositories, worktrees } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
+import { pathsEqual } from "main/lib/platform-paths";
 
 /**
  * Security model for desktop app filesystem access:
@@ -62,24 +63,12 @@ export class PathValidationError extends Error {
  */
 export function assertRegisteredWorktree(workspacePath: string): void {
 	// Check worktrees table first (most common case)
-	const worktreeExists = localDb
-		.select()
-		.from(worktrees)
-		.where(eq(worktrees.path, workspacePath))
-		.get();
-
-	if (worktreeExists) {
+	if (findWorktreeByPath(workspacePath)) {
 		return;
 	}
 
 	// Check repositories.mainRepoPath for branch nodes
-	const repositoryExists = localDb
-		.select()
-		.from(repositories)
-		.where(eq(repositories.mainRepoPath, workspacePath))
-		.get();
-
-	if (repositoryExists) {
+	if (findRepositoryByPath(workspacePath)) {
 		return;
 	}
 
@@ -96,7 +85,7 @@ export function assertRegisteredWorktree(workspacePath: string): void {
  * @throws PathValidationError if worktree is not registered
  */
 export function getRegisteredWorktree(worktreePath: string): typeof worktrees.$inferSelect {
-	const worktree = localDb.select().from(worktrees).where(eq(worktrees.path, worktreePath)).get();
+	const worktree = findWorktreeByPath(worktreePath);
 
 	if (!worktree) {
 		throw new PathValidationError("Worktree not registered in database", "UNREGISTERED_WORKTREE");
@@ -105,6 +94,38 @@ export function getRegisteredWorktree(worktreePath: string): typeof worktrees.$i
 	return worktree;
 }
 
+/**
+ * Exact lookup first; on Windows, fall back to a case- and separator-insensitive
+ * match since the same directory can be spelled several ways.
+ */
+export function findWorktreeByPath(path: string): typeof worktrees.$inferSelect | undefined {
+	const exact = localDb.select().from(worktrees).where(eq(worktrees.path, path)).get();
+	if (exact || process.platform !== "win32") {
+		return exact;
+	}
+	return localDb
+		.select()
+		.from(worktrees)
+		.all()
+		.find((worktree) => pathsEqual(worktree.path, path));
+}
+
+export function findRepositoryByPath(path: string): typeof repositories.$inferSelect | undefined {
+	const exact = localDb
+		.select()
+		.from(repositories)
+		.where(eq(repositories.mainRepoPath, path))
+		.get();
+	if (exact || process.platform !== "win32") {
+		return exact;
+	}
+	return localDb
+		.select()
+		.from(repositories)
+		.all()
+		.find((repository) => pathsEqual(repository.mainRepoPath, path));
+}
+
 /**
  * Options for path validation.
  */
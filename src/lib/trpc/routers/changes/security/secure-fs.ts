@@ -1,5 +1,14 @@
-import type { Stats } from "node:fs";
-import { lstat, readFile, readlink, realpath, rm, stat, writeFile } from "node:fs/promises";
+import { createReadStream, type Dirent, type ReadStream, type Stats } from "node:fs";
+import {
+	lstat,
+	readdir,
+	readFile,
+	readlink,
+	realpath,
+	rm,
+	stat,
+	writeFile,
+} from "node:fs/promises";
 import { dirname, isAbsolute, relative, resolve, sep } from "node:path";
 import {
 	assertRegisteredWorktree,
@@ -273,6 +282,24 @@ export const secureFs = {
 		return readFile(fullPath);
 	},
 
+	/**
+	 * Open a read stream for a file within a worktree.
+	 * Use for large files that shouldn't be loaded into memory at once.
+	 *
+	 * SECURITY: Enforces the same symlink-escape check as readFile.
+	 *
+	 * @throws PathValidationError with code "SYMLINK_ESCAPE" if file escapes worktree
+	 */
+	async createReadStream(worktreePath: string, filePath: string): Promise<ReadStream> {
+		assertRegisteredWorktree(worktreePath);
+		const fullPath = resolvePathInWorktree(worktreePath, filePath);
+
+		// Block reads through symlinks that escape the worktree
+		await assertRealpathInWorktree(worktreePath, fullPath);
+
+		return createReadStream(fullPath);
+	},
+
 	/**
 	 * Write content to a file within a worktree.
 	 *
@@ -363,6 +390,17 @@ export const secureFs = {
 		return lstat(fullPath);
 	},
 
+	/**
+	 * List a directory within a worktree; an empty path lists the root.
+	 * A symlinked directory pointing outside the worktree is rejected.
+	 */
+	async readdir(worktreePath: string, dirPath: string): Promise<Dirent[]> {
+		assertRegisteredWorktree(worktreePath);
+		const fullPath = resolvePathInWorktree(worktreePath, dirPath, { allowRoot: true });
+		await assertRealpathInWorktree(worktreePath, fullPath);
+		return readdir(fullPath, { withFileTypes: true });
+	},
+
 	/**
 	 * Check if a file exists within a worktree.
 	 *
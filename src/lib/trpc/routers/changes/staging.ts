@@ -6,11 +6,13 @@ import {
 	gitDiscardAllUnstaged,
 	gitStageAll,
 	gitStageFile,
+	gitStageHunks,
 	gitStash,
 	gitStashIncludeUntracked,
 	gitStashPop,
 	gitUnstageAll,
 	gitUnstageFile,
+	gitUnstageHunks,
 	secureFs,
 } from "./security";
 
@@ -40,6 +42,32 @@ export const createStagingRouter = () => {
 				return { success: true };
 			}),
 
+		stageHunks: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					filePath: z.string(),
+					hunkHeaders: z.array(z.string()).min(1),
+				}),
+			)
+			.mutation(async ({ input }): Promise<{ success: boolean }> => {
+				await gitStageHunks(input.worktreePath, input.filePath, input.hunkHeaders);
+				return { success: true };
+			}),
+
+		unstageHunks: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					filePath: z.string(),
+					hunkHeaders: z.array(z.string()).min(1),
+				}),
+			)
+			.mutation(async ({ input }): Promise<{ success: boolean }> => {
+				await gitUnstageHunks(input.worktreePath, input.filePath, input.hunkHeaders);
+				return { success: true };
+			}),
+
 		discardChanges: publicProcedure
 			.input(
 				z.object({
@@ -1,11 +1,23 @@
-import type { ChangedFile, GitChangesStatus } from "shared/changes-types";
+import { observable } from "@trpc/server/observable";
+import { changesCache } from "main/lib/changes-cache";
+import type {
+	BranchStats,
+	ChangedFile,
+	FilesChangedEvent,
+	GitChangesStatus,
+	WorktreeWatchStatus,
+} from "shared/changes-types";
+import { isPathInScope } from "shared/utils/scope";
 import simpleGit from "simple-git";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
+import { findNodeByPath } from "../nodes/utils/db-helpers";
 import { getStatusNoLock } from "../nodes/utils/git";
-import { assertRegisteredWorktree, secureFs } from "./security";
+import { assertRegisteredWorktree } from "./security";
 import { applyNumstatToFiles } from "./utils/apply-numstat";
+import { summarizeBranchStats } from "./utils/branch-stats";
 import { parseGitLog, parseGitStatus, parseNameStatus } from "./utils/parse-status";
+import { prepareUntrackedFiles } from "./utils/untracked";
 
 export const createStatusRouter = () => {
 	return router({
@@ -19,39 +31,115 @@ export const createStatusRouter = () => {
 			.query(async ({ input }): Promise<GitChangesStatus> => {
 				assertRegisteredWorktree(input.worktreePath);
 
-				const git = simpleGit(input.worktreePath);
+				return getCachedStatus(input.worktreePath, input.defaultBranch || "main");
+			}),
+
+		/**
+		 * Branch stats for a node card: sent once on subscribe, then
+		 * recomputed in the background whenever the watcher sees files change.
+		 * Commits made outside the app don't touch worktree files, so they
+		 * show up with the next file change.
+		 */
+		onStatsUpdated: publicProcedure
+			.input(
+				z.object({
+					worktreePath: z.string(),
+					defaultBranch: z.string().optional(),
+				}),
+			)
+			.subscription(({ input }) => {
+				assertRegisteredWorktree(input.worktreePath);
 				const defaultBranch = input.defaultBranch || "main";
 
-				// First, get status (needed for subsequent operations)
-				// Use --no-optional-locks to avoid holding locks on the repository
-				const status = await getStatusNoLock(input.worktreePath);
-				const parsed = parseGitStatus(status);
-
-				// Run independent operations in parallel
-				const [branchComparison, trackingStatus] = await Promise.all([
-					getBranchComparison(git, defaultBranch),
-					getTrackingBranchStatus(git),
-					applyNumstatToFiles(git, parsed.staged, ["diff", "--cached", "--numstat"]),
-					applyNumstatToFiles(git, parsed.unstaged, ["diff", "--numstat"]),
-					applyUntrackedLineCount(input.worktreePath, parsed.untracked),
-				]);
+				return observable<BranchStats>((emit) => {
+					let closed = false;
+					let running = false;
+					let rerun = false;
 
-				return {
-					branch: parsed.branch,
-					defaultBranch,
-					againstBase: branchComparison.againstBase,
-					commits: branchComparison.commits,
-					staged: parsed.staged,
-					unstaged: parsed.unstaged,
-					untracked: parsed.untracked,
-					ahead: branchComparison.ahead,
-					behind: branchComparison.behind,
-					pushCount: trackingStatus.pushCount,
-					pullCount: trackingStatus.pullCount,
-					hasUpstream: trackingStatus.hasUpstream,
-				};
+					// Changes arriving mid-computation trigger one more pass, not one each
+					const refresh = async () => {
+						if (running) {
+							rerun = true;
+							return;
+						}
+						running = true;
+						try {
+							do {
+								rerun = false;
+								const status = await getCachedStatus(input.worktreePath, defaultBranch);
+								if (!closed) emit.next(summarizeBranchStats(input.worktreePath, status));
+							} while (rerun && !closed);
+						} catch (error) {
+							console.warn(`[changes] Failed to compute stats for ${input.worktreePath}:`, error);
+						} finally {
+							running = false;
+						}
+					};
+
+					const unsubscribe = changesCache.subscribe(input.worktreePath, () => {
+						void refresh();
+					});
+					void refresh();
+
+					return () => {
+						closed = true;
+						unsubscribe();
+					};
+				});
+			}),
+
+		/**
+		 * Debounced batches of file changes in a worktree, classified as
+		 * created/modified/deleted/renamed with git tracking state. Paths
+		 * matched by .gitignore or .caspianignore are left out.
+		 */
+		onFilesChanged: publicProcedure
+			.input(z.object({ worktreePath: z.string() }))
+			.subscription(({ input }) => {
+				assertRegisteredWorktree(input.worktreePath);
+
+				return observable<FilesChangedEvent>((emit) => {
+					return changesCache.subscribe(input.worktreePath, (event) => {
+						emit.next(event);
+					});
+				});
+			}),
+
+		/** Watched worktrees and whether each fell back to polling */
+		getWatcherStatus: publicProcedure.query((): WorktreeWatchStatus[] => {
+			return changesCache.getWatcherStatus();
+		}),
+
+		/**
+		 * Overrides the automatic watcher lifecycle: watchers start when a
+		 * node's worktree is ready or a terminal opens in it, and stop when
+		 * the node is closed or deleted.
+		 */
+		setWatching: publicProcedure
+			.input(z.object({ worktreePath: z.string(), enabled: z.boolean() }))
+			.mutation(({ input }) => {
+				assertRegisteredWorktree(input.worktreePath);
+				if (input.enabled) {
+					changesCache.startWatching(input.worktreePath);
+				} else {
+					changesCache.stopWatching(input.worktreePath);
+				}
+				return { success: true };
 			}),
 
+		/** Emits when a worktree's native watcher fails or can't be created and polling takes over */
+		onWatcherStatus: publicProcedure.subscription(() => {
+			return observable<WorktreeWatchStatus>((emit) => {
+				const handler = (status: WorktreeWatchStatus) => {
+					emit.next(status);
+				};
+				changesCache.on("watcher-status", handler);
+				return () => {
+					changesCache.off("watcher-status", handler);
+				};
+			});
+		}),
+
 		getCommitFiles: publicProcedure
 			.input(
 				z.object({
@@ -86,6 +174,66 @@ export const createStatusRouter = () => {
 	});
 };
 
+/** Node cards and the changes panel poll this; reuse results until the worktree changes */
+async function getCachedStatus(
+	worktreePath: string,
+	defaultBranch: string,
+): Promise<GitChangesStatus> {
+	const status = await changesCache.get(worktreePath, `status:${defaultBranch}`, () =>
+		computeStatus(worktreePath, defaultBranch),
+	);
+	return filterToScope(status, findNodeByPath(worktreePath)?.scopePath ?? null);
+}
+
+/** Nodes scoped to a subdirectory only see changes inside it */
+function filterToScope(status: GitChangesStatus, scopePath: string | null): GitChangesStatus {
+	if (!scopePath) return status;
+	const inScope = (file: ChangedFile) => isPathInScope(scopePath, file.path);
+	return {
+		...status,
+		againstBase: status.againstBase.filter(inScope),
+		staged: status.staged.filter(inScope),
+		unstaged: status.unstaged.filter(inScope),
+		untracked: status.untracked.filter(inScope),
+	};
+}
+
+async function computeStatus(
+	worktreePath: string,
+	defaultBranch: string,
+): Promise<GitChangesStatus> {
+	const git = simpleGit(worktreePath);
+
+	// First, get status (needed for subsequent operations)
+	// Use --no-optional-locks to avoid holding locks on the repository
+	const status = await getStatusNoLock(worktreePath);
+	const parsed = parseGitStatus(status);
+
+	// Run independent operations in parallel
+	const [branchComparison, trackingStatus, , , untracked] = await Promise.all([
+		getBranchComparison(git, defaultBranch),
+		getTrackingBranchStatus(git),
+		applyNumstatToFiles(git, parsed.staged, ["diff", "--cached", "--numstat"]),
+		applyNumstatToFiles(git, parsed.unstaged, ["diff", "--numstat"]),
+		prepareUntrackedFiles(git, worktreePath, parsed.untracked),
+	]);
+
+	return {
+		branch: parsed.branch,
+		defaultBranch,
+		againstBase: branchComparison.againstBase,
+		commits: branchComparison.commits,
+		staged: parsed.staged,
+		unstaged: parsed.unstaged,
+		untracked,
+		ahead: branchComparison.ahead,
+		behind: branchComparison.behind,
+		pushCount: trackingStatus.pushCount,
+		pullCount: trackingStatus.pullCount,
+		hasUpstream: trackingStatus.hasUpstream,
+	};
+}
+
 interface BranchComparison {
 	commits: GitChangesStatus["commits"];
 	againstBase: ChangedFile[];
@@ -135,28 +283,6 @@ async function getBranchComparison(
 	return { commits, againstBase, ahead, behind };
 }
 
-/** Max file size for line counting (1 MiB) - skip larger files to avoid OOM */
-const MAX_LINE_COUNT_SIZE = 1 * 1024 * 1024;
-
-async function applyUntrackedLineCount(
-	worktreePath: string,
-	untracked: ChangedFile[],
-): Promise<void> {
-	for (const file of untracked) {
-		try {
-			const stats = await secureFs.stat(worktreePath, file.path);
-			if (stats.size > MAX_LINE_COUNT_SIZE) continue;
-
-			const content = await secureFs.readFile(worktreePath, file.path);
-			const lineCount = content.split("\n").length;
-			file.additions = lineCount;
-			file.deletions = 0;
-		} catch {
-			// Skip files that fail validation or reading
-		}
-	}
-}
-
 interface TrackingStatus {
 	pushCount: number;
 	pullCount: number;
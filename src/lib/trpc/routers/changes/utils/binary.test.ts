@@ -0,0 +1,36 @@
+import { describe, expect, test } from "bun:test";
+import { getImageMimeType, isBinaryContent, isBinaryString } from "./binary";
+
+describe("isBinaryContent", () => {
+	test("detects NUL bytes", () => {
+		expect(isBinaryContent(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x00]))).toBe(true);
+		expect(isBinaryContent(Buffer.from("plain text\n"))).toBe(false);
+	});
+
+	test("only scans the leading bytes", () => {
+		const buffer = Buffer.alloc(10_000, 0x61);
+		buffer[9_999] = 0;
+		expect(isBinaryContent(buffer)).toBe(false);
+	});
+});
+
+describe("isBinaryString", () => {
+	test("detects NUL characters in decoded content", () => {
+		expect(isBinaryString("PNG\0\0")).toBe(true);
+		expect(isBinaryString("hello")).toBe(false);
+	});
+});
+
+describe("getImageMimeType", () => {
+	test("maps common image extensions case-insensitively", () => {
+		expect(getImageMimeType("assets/logo.PNG")).toBe("image/png");
+		expect(getImageMimeType("photo.jpeg")).toBe("image/jpeg");
+		expect(getImageMimeType("icons/app.svg")).toBe("image/svg+xml");
+	});
+
+	test("returns null for non-images and extensionless paths", () => {
+		expect(getImageMimeType("src/index.ts")).toBeNull();
+		expect(getImageMimeType("Makefile")).toBeNull();
+		expect(getImageMimeType("some.dir/LICENSE")).toBeNull();
+	});
+});
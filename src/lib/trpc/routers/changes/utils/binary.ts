@@ -0,0 +1,43 @@
+/** Bytes to scan for binary detection */
+const BINARY_CHECK_SIZE = 8192;
+
+const IMAGE_MIME_TYPES: Record<string, string> = {
+	png: "image/png",
+	jpg: "image/jpeg",
+	jpeg: "image/jpeg",
+	gif: "image/gif",
+	webp: "image/webp",
+	bmp: "image/bmp",
+	ico: "image/x-icon",
+	avif: "image/avif",
+	svg: "image/svg+xml",
+};
+
+/**
+ * Detects if a buffer contains binary content by checking for NUL bytes
+ */
+export function isBinaryContent(buffer: Buffer): boolean {
+	const checkLength = Math.min(buffer.length, BINARY_CHECK_SIZE);
+	for (let i = 0; i < checkLength; i++) {
+		if (buffer[i] === 0) {
+			return true;
+		}
+	}
+	return false;
+}
+
+/**
+ * Same check for content that has already been decoded to a string.
+ */
+export function isBinaryString(content: string): boolean {
+	return content.slice(0, BINARY_CHECK_SIZE).includes("\0");
+}
+
+/**
+ * Returns the MIME type for image files the review panel can preview, or null.
+ */
+export function getImageMimeType(filePath: string): string | null {
+	const dot = filePath.lastIndexOf(".");
+	if (dot === -1 || dot < filePath.lastIndexOf("/")) return null;
+	return IMAGE_MIME_TYPES[filePath.slice(dot + 1).toLowerCase()] ?? null;
+}
@@ -0,0 +1,56 @@
+import { describe, expect, test } from "bun:test";
+import type { ChangedFile, GitChangesStatus } from "shared/changes-types";
+import { summarizeBranchStats } from "./branch-stats";
+
+function file(path: string, additions: number, deletions: number): ChangedFile {
+	return { path, status: "modified", additions, deletions };
+}
+
+function status(overrides: Partial<GitChangesStatus>): GitChangesStatus {
+	return {
+		branch: "feature",
+		defaultBranch: "main",
+		againstBase: [],
+		commits: [],
+		staged: [],
+		unstaged: [],
+		untracked: [],
+		ahead: 0,
+		behind: 0,
+		pushCount: 0,
+		pullCount: 0,
+		hasUpstream: false,
+		...overrides,
+	};
+}
+
+describe("summarizeBranchStats", () => {
+	test("sums committed and uncommitted changes", () => {
+		const stats = summarizeBranchStats(
+			"/repo",
+			status({
+				againstBase: [file("a.ts", 10, 2)],
+				unstaged: [file("a.ts", 1, 1)],
+				untracked: [file("b.ts", 5, 0)],
+				ahead: 2,
+				behind: 1,
+			}),
+		);
+
+		expect(stats).toEqual({
+			worktreePath: "/repo",
+			filesChanged: 2,
+			additions: 16,
+			deletions: 3,
+			ahead: 2,
+			behind: 1,
+			hasUncommittedChanges: true,
+		});
+	});
+
+	test("reports a clean worktree", () => {
+		const stats = summarizeBranchStats("/repo", status({ againstBase: [file("a.ts", 3, 0)] }));
+		expect(stats.filesChanged).toBe(1);
+		expect(stats.hasUncommittedChanges).toBe(false);
+	});
+});
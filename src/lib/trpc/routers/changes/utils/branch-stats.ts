@@ -0,0 +1,20 @@
+import type { BranchStats, GitChangesStatus } from "shared/changes-types";
+
+/**
+ * Totals a status for node cards. Line counts are summed over commits on
+ * the branch and uncommitted edits, so a line changed in both counts twice.
+ */
+export function summarizeBranchStats(worktreePath: string, status: GitChangesStatus): BranchStats {
+	const uncommitted = [...status.staged, ...status.unstaged, ...status.untracked];
+	const files = [...status.againstBase, ...uncommitted];
+
+	return {
+		worktreePath,
+		filesChanged: new Set(files.map((file) => file.path)).size,
+		additions: files.reduce((sum, file) => sum + file.additions, 0),
+		deletions: files.reduce((sum, file) => sum + file.deletions, 0),
+		ahead: status.ahead,
+		behind: status.behind,
+		hasUncommittedChanges: uncommitted.length > 0,
+	};
+}
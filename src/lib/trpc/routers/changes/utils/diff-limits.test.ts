@@ -0,0 +1,107 @@
+import { describe, expect, test } from "bun:test";
+import type { DiffHunk, StructuredFileDiff } from "shared/changes-types";
+import { applyDiffByteCap, isGeneratedFile } from "./diff-limits";
+
+function hunk(lineCount: number, lineLength = 9): DiffHunk {
+	return {
+		header: "@@",
+		oldStart: 1,
+		oldLines: 0,
+		newStart: 1,
+		newLines: lineCount,
+		lines: Array.from({ length: lineCount }, (_, i) => ({
+			type: "addition" as const,
+			content: "x".repeat(lineLength),
+			oldLineNumber: null,
+			newLineNumber: i + 1,
+		})),
+	};
+}
+
+function file(path: string, hunks: DiffHunk[]): StructuredFileDiff {
+	return { path, status: "modified", isBinary: false, additions: 0, deletions: 0, hunks };
+}
+
+describe("isGeneratedFile", () => {
+	test("matches lockfiles anywhere in the tree", () => {
+		expect(isGeneratedFile("package-lock.json")).toBe(true);
+		expect(isGeneratedFile("apps/web/pnpm-lock.yaml")).toBe(true);
+		expect(isGeneratedFile("Cargo.lock")).toBe(true);
+	});
+
+	test("matches build output and minified assets", () => {
+		expect(isGeneratedFile("dist/index.js")).toBe(true);
+		expect(isGeneratedFile("packages/ui/dist/index.js")).toBe(true);
+		expect(isGeneratedFile("public/app.min.js")).toBe(true);
+		expect(isGeneratedFile("src/schema.generated.ts")).toBe(true);
+	});
+
+	test("leaves source files alone", () => {
+		expect(isGeneratedFile("src/lock.ts")).toBe(false);
+		expect(isGeneratedFile("src/distance.ts")).toBe(false);
+		expect(isGeneratedFile("README.md")).toBe(false);
+	});
+});
+
+describe("applyDiffByteCap", () => {
+	test("keeps everything under the cap", () => {
+		const files = [file("a", [hunk(2)]), file("b", [hunk(2)])];
+		applyDiffByteCap(files, 1000);
+		expect(files.every((f) => !f.truncated)).toBe(true);
+	});
+
+	test("marks files past the cap as skipped", () => {
+		// Each hunk is 2 (header) + 10 * 10 bytes = 102 bytes
+		const files = [file("a", [hunk(10)]), file("b", [hunk(10)]), file("c", [hunk(10)])];
+		applyDiffByteCap(files, 250);
+
+		expect(files[0].truncated).toBeUndefined();
+		expect(files[1].truncated).toBeUndefined();
+		expect(files[2].truncated).toBe(true);
+		expect(files[2].skippedReason).toBe("size-limit");
+		expect(files[2].hunks).toEqual([]);
+	});
+
+	test("cuts an oversized first hunk line by line", () => {
+		const files = [file("huge", [hunk(100), hunk(5)])];
+		applyDiffByteCap(files, 52);
+
+		expect(files[0].truncated).toBe(true);
+		expect(files[0].skippedReason).toBeUndefined();
+		expect(files[0].hunks).toHaveLength(1);
+		expect(files[0].hunks[0].lines).toHaveLength(5);
+	});
+
+	test("recounts the header of a cut hunk", () => {
+		const cut: DiffHunk = {
+			header: "@@ -10,6 +10,6 @@ function main()",
+			oldStart: 10,
+			oldLines: 6,
+			newStart: 10,
+			newLines: 6,
+			lines: [
+				{ type: "context", content: "aaaa", oldLineNumber: 10, newLineNumber: 10 },
+				{ type: "deletion", content: "bbbb", oldLineNumber: 11, newLineNumber: null },
+				{ type: "addition", content: "cccc", oldLineNumber: null, newLineNumber: 11 },
+				{ type: "addition", content: "dddd", oldLineNumber: null, newLineNumber: 12 },
+				{ type: "deletion", content: "eeee", oldLineNumber: 12, newLineNumber: null },
+				{ type: "context", content: "ffff", oldLineNumber: 13, newLineNumber: 13 },
+			],
+		};
+		const files = [file("a", [cut])];
+		// Header (33 bytes) plus four 5-byte lines
+		applyDiffByteCap(files, 53);
+
+		const [kept] = files[0].hunks;
+		expect(kept.lines).toHaveLength(4);
+		expect(kept.header).toBe("@@ -10,2 +10,3 @@ function main()");
+		expect(kept.oldLines).toBe(2);
+		expect(kept.newLines).toBe(3);
+	});
+
+	test("ignores files already skipped as generated", () => {
+		const generated = { ...file("yarn.lock", []), skippedReason: "generated" as const };
+		applyDiffByteCap([generated], 0);
+		expect(generated.truncated).toBeUndefined();
+	});
+});
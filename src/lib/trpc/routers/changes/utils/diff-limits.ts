@@ -0,0 +1,113 @@
+import type { StructuredFileDiff } from "shared/changes-types";
+
+/** Default cap on total diff line content returned in one response (1 MiB) */
+export const DEFAULT_MAX_DIFF_BYTES = 1024 * 1024;
+
+const LOCKFILE_NAMES = new Set([
+	"package-lock.json",
+	"npm-shrinkwrap.json",
+	"yarn.lock",
+	"pnpm-lock.yaml",
+	"bun.lock",
+	"bun.lockb",
+	"Cargo.lock",
+	"Gemfile.lock",
+	"composer.lock",
+	"poetry.lock",
+	"uv.lock",
+	"Pipfile.lock",
+	"go.sum",
+	"Podfile.lock",
+	"flake.lock",
+]);
+
+const GENERATED_SUFFIXES = [".min.js", ".min.css", ".map", ".pb.go", "_pb2.py", ".snap"];
+
+const GENERATED_DIRECTORIES = ["node_modules/", "dist/", "build/", ".next/", "vendor/"];
+
+/**
+ * Heuristic check for lockfiles and build output whose diffs are rarely worth rendering.
+ * Repositories can mark more files via `linguist-generated` in .gitattributes.
+ */
+export function isGeneratedFile(path: string): boolean {
+	const name = path.slice(path.lastIndexOf("/") + 1);
+	if (LOCKFILE_NAMES.has(name)) return true;
+	if (name.includes(".generated.")) return true;
+	if (GENERATED_SUFFIXES.some((suffix) => name.endsWith(suffix))) return true;
+
+	const normalized = `/${path}`;
+	return GENERATED_DIRECTORIES.some((dir) => normalized.includes(`/${dir}`));
+}
+
+const HUNK_RANGES = /^@@ -\d+(?:,\d+)? \+\d+(?:,\d+)? @@/;
+
+function hunkBytes(hunk: StructuredFileDiff["hunks"][number]): number {
+	let bytes = hunk.header.length;
+	for (const line of hunk.lines) {
+		bytes += line.content.length + 1;
+	}
+	return bytes;
+}
+
+/**
+ * Keeps the leading lines of a hunk that fit within `maxBytes`, with the header's
+ * line counts rewritten to match what's left
+ */
+function truncateHunk(
+	hunk: StructuredFileDiff["hunks"][number],
+	maxBytes: number,
+): StructuredFileDiff["hunks"][number] {
+	let bytes = hunk.header.length;
+	const lines: typeof hunk.lines = [];
+	for (const line of hunk.lines) {
+		bytes += line.content.length + 1;
+		if (bytes > maxBytes) break;
+		lines.push(line);
+	}
+
+	const oldLines = lines.filter((line) => line.type !== "addition").length;
+	const newLines = lines.filter((line) => line.type !== "deletion").length;
+	const header = hunk.header.replace(
+		HUNK_RANGES,
+		`@@ -${hunk.oldStart},${oldLines} +${hunk.newStart},${newLines} @@`,
+	);
+	return { ...hunk, header, oldLines, newLines, lines };
+}
+
+/**
+ * Drops hunks once the running total exceeds `maxBytes`.
+ * Files past the cap keep their metadata and stats but are marked truncated so the
+ * caller can fetch them individually. A single oversized hunk at the start of the
+ * response is cut line-by-line rather than dropped, so one huge file still shows something.
+ */
+export function applyDiffByteCap(files: StructuredFileDiff[], maxBytes: number): void {
+	let used = 0;
+
+	for (const file of files) {
+		if (file.skippedReason) continue;
+
+		const kept: StructuredFileDiff["hunks"] = [];
+		for (const hunk of file.hunks) {
+			const size = hunkBytes(hunk);
+			if (used + size <= maxBytes) {
+				used += size;
+				kept.push(hunk);
+				continue;
+			}
+
+			if (used === 0) {
+				kept.push(truncateHunk(hunk, maxBytes));
+				used = maxBytes;
+			}
+			file.truncated = true;
+			break;
+		}
+
+		if (file.truncated) {
+			file.hunks = kept;
+			if (kept.length === 0) {
+				file.skippedReason = "size-limit";
+			}
+		}
+	}
+}
@@ -0,0 +1,71 @@
+import { describe, expect, test } from "bun:test";
+import { buildGitPathIndex, getEntryFlags } from "./file-tree";
+
+describe("getEntryFlags", () => {
+	const index = buildGitPathIndex(
+		["README.md", "src/index.ts", "src/lib/util.ts", "vendor/kept.js"],
+		["node_modules/", ".env", "src/lib/generated/", "vendor/"],
+		[
+			{ path: "src/lib/util.ts", status: "modified", additions: 0, deletions: 0 },
+			{ path: "src/new.ts", status: "untracked", additions: 0, deletions: 0 },
+			{ path: "docs/drafts/", status: "untracked", additions: 0, deletions: 0 },
+		],
+	);
+
+	test("flags tracked and modified files", () => {
+		expect(getEntryFlags("src/lib/util.ts", false, index)).toEqual({
+			ignored: false,
+			tracked: true,
+			modified: true,
+			status: "modified",
+		});
+		expect(getEntryFlags("README.md", false, index)).toEqual({
+			ignored: false,
+			tracked: true,
+			modified: false,
+			status: null,
+		});
+	});
+
+	test("reports untracked files as modified but not tracked", () => {
+		expect(getEntryFlags("src/new.ts", false, index)).toMatchObject({
+			tracked: false,
+			modified: true,
+			status: "untracked",
+		});
+	});
+
+	test("treats an untracked directory as covering its whole subtree", () => {
+		expect(getEntryFlags("docs/drafts/notes/plan.md", false, index)).toMatchObject({
+			tracked: false,
+			modified: true,
+			status: "untracked",
+		});
+		expect(getEntryFlags("docs/drafts/notes", true, index).modified).toBe(true);
+		expect(getEntryFlags("docs", true, index).modified).toBe(true);
+		expect(getEntryFlags("README.md", false, index).modified).toBe(false);
+	});
+
+	test("rolls tracked and modified up to parent directories", () => {
+		expect(getEntryFlags("src", true, index)).toMatchObject({ tracked: true, modified: true });
+		expect(getEntryFlags("src/lib", true, index)).toMatchObject({ tracked: true, modified: true });
+	});
+
+	test("marks ignored files, directories and their contents", () => {
+		expect(getEntryFlags(".env", false, index).ignored).toBe(true);
+		expect(getEntryFlags("node_modules", true, index).ignored).toBe(true);
+		expect(getEntryFlags("node_modules/react/index.js", false, index).ignored).toBe(true);
+		expect(getEntryFlags("src/lib/generated", true, index)).toMatchObject({
+			ignored: true,
+			tracked: false,
+		});
+	});
+
+	test("never reports tracked files as ignored", () => {
+		expect(getEntryFlags("vendor/kept.js", false, index)).toMatchObject({
+			ignored: false,
+			tracked: true,
+		});
+		expect(getEntryFlags("vendor", true, index).ignored).toBe(false);
+	});
+});
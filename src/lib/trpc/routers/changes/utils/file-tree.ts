@@ -0,0 +1,109 @@
+import type { ChangedFile, FileStatus } from "shared/changes-types";
+import type { WorktreeFileEntry } from "shared/file-tree-types";
+
+/** What git reports for a worktree, indexed for per-entry lookups */
+export interface GitPathIndex {
+	trackedFiles: Set<string>;
+	trackedDirs: Set<string>;
+	ignoredFiles: Set<string>;
+	ignoredDirs: Set<string>;
+	changes: Map<string, FileStatus>;
+	changedDirs: Set<string>;
+	/** Reported by `git status` as one "dir/" entry covering everything inside */
+	untrackedDirs: Set<string>;
+}
+
+function addAncestors(path: string, dirs: Set<string>): void {
+	let slash = path.lastIndexOf("/");
+	while (slash > 0) {
+		const dir = path.slice(0, slash);
+		if (dirs.has(dir)) return;
+		dirs.add(dir);
+		slash = dir.lastIndexOf("/");
+	}
+}
+
+/**
+ * @param trackedFiles `git ls-files` output
+ * @param ignoredPaths `git ls-files --others --ignored --directory` output; directories end in "/"
+ * @param changes Staged, unstaged and untracked files from `git status`
+ */
+export function buildGitPathIndex(
+	trackedFiles: string[],
+	ignoredPaths: string[],
+	changes: ChangedFile[],
+): GitPathIndex {
+	const index: GitPathIndex = {
+		trackedFiles: new Set(trackedFiles),
+		trackedDirs: new Set(),
+		ignoredFiles: new Set(),
+		ignoredDirs: new Set(),
+		changes: new Map(),
+		changedDirs: new Set(),
+		untrackedDirs: new Set(),
+	};
+	for (const file of trackedFiles) {
+		addAncestors(file, index.trackedDirs);
+	}
+	for (const path of ignoredPaths) {
+		if (path.endsWith("/")) {
+			index.ignoredDirs.add(path.slice(0, -1));
+		} else {
+			index.ignoredFiles.add(path);
+		}
+	}
+	for (const change of changes) {
+		if (change.path.endsWith("/")) {
+			const dir = change.path.slice(0, -1);
+			index.untrackedDirs.add(dir);
+			index.changedDirs.add(dir);
+			addAncestors(dir, index.changedDirs);
+			continue;
+		}
+		// Callers pass staged before unstaged, so a file edited after staging shows its latest change
+		index.changes.set(change.path, change.status);
+		addAncestors(change.path, index.changedDirs);
+	}
+	return index;
+}
+
+function isWithinDir(relativePath: string, dirs: Set<string>): boolean {
+	let path = relativePath;
+	while (path) {
+		if (dirs.has(path)) return true;
+		const slash = path.lastIndexOf("/");
+		path = slash > 0 ? path.slice(0, slash) : "";
+	}
+	return false;
+}
+
+export function getEntryFlags(
+	relativePath: string,
+	isDirectory: boolean,
+	index: GitPathIndex,
+): Pick<WorktreeFileEntry, "ignored" | "tracked" | "modified" | "status"> {
+	if (isDirectory) {
+		const tracked = index.trackedDirs.has(relativePath);
+		return {
+			// Files tracked before an ignore rule was added keep their directory tracked
+			ignored: !tracked && isWithinDir(relativePath, index.ignoredDirs),
+			tracked,
+			modified:
+				index.changedDirs.has(relativePath) || isWithinDir(relativePath, index.untrackedDirs),
+			status: null,
+		};
+	}
+
+	const tracked = index.trackedFiles.has(relativePath);
+	const status =
+		index.changes.get(relativePath) ??
+		(isWithinDir(relativePath, index.untrackedDirs) ? "untracked" : null);
+	return {
+		ignored:
+			!tracked &&
+			(index.ignoredFiles.has(relativePath) || isWithinDir(relativePath, index.ignoredDirs)),
+		tracked,
+		modified: status !== null,
+		status,
+	};
+}
@@ -0,0 +1,79 @@
+import { describe, expect, test } from "bun:test";
+import { buildHunkPatch } from "./hunk-patch";
+
+const FILE_DIFF = [
+	"diff --git a/src/app.ts b/src/app.ts",
+	"index 1111111..2222222 100644",
+	"--- a/src/app.ts",
+	"+++ b/src/app.ts",
+	"@@ -1,3 +1,4 @@",
+	" import a",
+	"+import b",
+	" ",
+	" const x = 1;",
+	"@@ -10,3 +11,5 @@ function main() {",
+	" \tfirst();",
+	"+\tsecond();",
+	"+\tthird();",
+	" \tlast();",
+	" }",
+	"@@ -20,2 +23,1 @@ function other() {",
+	"-\tremoved();",
+	" }",
+	"",
+].join("\n");
+
+const FILE_HEADER = [
+	"diff --git a/src/app.ts b/src/app.ts",
+	"index 1111111..2222222 100644",
+	"--- a/src/app.ts",
+	"+++ b/src/app.ts",
+];
+
+describe("buildHunkPatch", () => {
+	test("keeps only the selected hunks with the file header", () => {
+		const patch = buildHunkPatch(FILE_DIFF, ["@@ -1,3 +1,4 @@"]);
+		const expected = [
+			...FILE_HEADER,
+			"@@ -1,3 +1,4 @@",
+			" import a",
+			"+import b",
+			" ",
+			" const x = 1;",
+			"",
+		];
+		expect(patch).toBe(expected.join("\n"));
+	});
+
+	test("shifts new-side starts past skipped hunks", () => {
+		const patch = buildHunkPatch(FILE_DIFF, [
+			"@@ -10,3 +11,5 @@ function main() {",
+			"@@ -20,2 +23,1 @@ function other() {",
+		]);
+		expect(patch).toContain("@@ -10,3 +10,5 @@ function main() {");
+		expect(patch).toContain("@@ -20,2 +22 @@ function other() {");
+		expect(patch).not.toContain("+import b");
+	});
+
+	test("shifts old-side starts when building a reverse patch", () => {
+		const patch = buildHunkPatch(FILE_DIFF, ["@@ -20,2 +23,1 @@ function other() {"], {
+			reverse: true,
+		});
+		expect(patch).toContain("@@ -23,2 +23 @@ function other() {");
+	});
+
+	test("throws when a selected hunk no longer exists", () => {
+		expect(() => buildHunkPatch(FILE_DIFF, ["@@ -1,3 +1,5 @@"])).toThrow(
+			"The diff has changed since it was loaded",
+		);
+	});
+
+	test("throws for diffs without text hunks", () => {
+		const binary = [
+			"diff --git a/logo.png b/logo.png",
+			"index 1111111..2222222 100644",
+			"Binary files a/logo.png and b/logo.png differ",
+		].join("\n");
+		expect(() => buildHunkPatch(binary, [])).toThrow("File has no text hunks to apply");
+	});
+});
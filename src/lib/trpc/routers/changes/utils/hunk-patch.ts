@@ -0,0 +1,85 @@
+const HUNK_HEADER = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@(.*)$/;
+
+interface RawHunk {
+	header: string;
+	oldStart: number;
+	oldLines: number;
+	newStart: number;
+	newLines: number;
+	context: string;
+	body: string[];
+}
+
+function formatRange(start: number, lines: number): string {
+	return lines === 1 ? `${start}` : `${start},${lines}`;
+}
+
+/**
+ * Builds a patch containing only the selected hunks of a single-file diff,
+ * suitable for `git apply --cached` (or `--cached --reverse` when unstaging).
+ *
+ * Hunks are matched by their exact `@@` header line. Start lines on the side
+ * that isn't being patched are recomputed, since skipped hunks no longer shift them.
+ *
+ * @param reverse - true when the patch will be applied with --reverse (index = new side)
+ * @throws if a selected header isn't present, e.g. because the diff changed since it was shown
+ */
+export function buildHunkPatch(
+	fileDiff: string,
+	selectedHeaders: string[],
+	{ reverse = false }: { reverse?: boolean } = {},
+): string {
+	const lines = fileDiff.split("\n");
+	if (lines[lines.length - 1] === "") lines.pop();
+
+	const fileHeader: string[] = [];
+	const hunks: RawHunk[] = [];
+	for (const line of lines) {
+		const match = line.match(HUNK_HEADER);
+		if (match) {
+			hunks.push({
+				header: line,
+				oldStart: Number.parseInt(match[1], 10),
+				oldLines: match[2] === undefined ? 1 : Number.parseInt(match[2], 10),
+				newStart: Number.parseInt(match[3], 10),
+				newLines: match[4] === undefined ? 1 : Number.parseInt(match[4], 10),
+				context: match[5],
+				body: [],
+			});
+		} else if (hunks.length > 0) {
+			hunks[hunks.length - 1].body.push(line);
+		} else {
+			fileHeader.push(line);
+		}
+	}
+
+	if (hunks.length === 0) {
+		throw new Error("File has no text hunks to apply");
+	}
+
+	const wanted = new Set(selectedHeaders);
+	const available = new Set(hunks.map((hunk) => hunk.header));
+	for (const header of wanted) {
+		if (!available.has(header)) {
+			throw new Error("The diff has changed since it was loaded. Refresh and try again.");
+		}
+	}
+
+	const output = [...fileHeader];
+	// Net lines added by earlier hunks that were left out of the patch
+	let skippedShift = 0;
+	for (const hunk of hunks) {
+		if (!wanted.has(hunk.header)) {
+			skippedShift += hunk.newLines - hunk.oldLines;
+			continue;
+		}
+
+		const oldStart = reverse ? hunk.oldStart + skippedShift : hunk.oldStart;
+		const newStart = reverse ? hunk.newStart : hunk.newStart - skippedShift;
+		const oldRange = formatRange(oldStart, hunk.oldLines);
+		const newRange = formatRange(newStart, hunk.newLines);
+		output.push(`@@ -${oldRange} +${newRange} @@${hunk.context}`, ...hunk.body);
+	}
+
+	return `${output.join("\n")}\n`;
+}
@@ -0,0 +1,35 @@
+import { describe, expect, test } from "bun:test";
+import { LineCounter } from "./line-count";
+
+function count(...chunks: string[]): number {
+	const counter = new LineCounter();
+	for (const chunk of chunks) {
+		counter.push(Buffer.from(chunk));
+	}
+	return counter.lineCount;
+}
+
+describe("LineCounter", () => {
+	test("empty file has no lines", () => {
+		expect(count()).toBe(0);
+		expect(count("")).toBe(0);
+	});
+
+	test("counts lines with and without a trailing newline", () => {
+		expect(count("a\nb\n")).toBe(2);
+		expect(count("a\nb")).toBe(2);
+		expect(count("\n")).toBe(1);
+	});
+
+	test("counts across chunk boundaries", () => {
+		expect(count("a\nb", "c\n", "d")).toBe(3);
+		expect(count("a", "\n", "")).toBe(1);
+	});
+
+	test("tracks byte count", () => {
+		const counter = new LineCounter();
+		counter.push(Buffer.from("abc\n"));
+		counter.push(Buffer.from("de"));
+		expect(counter.byteCount).toBe(6);
+	});
+});
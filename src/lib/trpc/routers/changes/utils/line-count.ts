@@ -0,0 +1,32 @@
+const NEWLINE = 0x0a;
+
+/**
+ * Incremental line counter for streamed file content.
+ * Matches `git diff --numstat`: a final line without a trailing newline still counts.
+ */
+export class LineCounter {
+	private newlines = 0;
+	private bytes = 0;
+	private endsWithNewline = false;
+
+	push(chunk: Buffer): void {
+		if (chunk.length === 0) return;
+
+		let index = chunk.indexOf(NEWLINE);
+		while (index !== -1) {
+			this.newlines++;
+			index = chunk.indexOf(NEWLINE, index + 1);
+		}
+		this.bytes += chunk.length;
+		this.endsWithNewline = chunk[chunk.length - 1] === NEWLINE;
+	}
+
+	get byteCount(): number {
+		return this.bytes;
+	}
+
+	get lineCount(): number {
+		if (this.bytes === 0) return 0;
+		return this.endsWithNewline ? this.newlines : this.newlines + 1;
+	}
+}
@@ -0,0 +1,151 @@
+import { describe, expect, test } from "bun:test";
+import { parseUnifiedDiff } from "./parse-diff";
+
+describe("parseUnifiedDiff", () => {
+	test("parses hunks with line numbers", () => {
+		const diff = `diff --git a/src/app.ts b/src/app.ts
+index 1234567..89abcde 100644
+--- a/src/app.ts
++++ b/src/app.ts
+@@ -1,3 +1,4 @@ export function main() {
+ const a = 1;
+-const b = 2;
++const b = 3;
++const c = 4;
+ const d = 5;
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.path).toBe("src/app.ts");
+		expect(file.status).toBe("modified");
+		expect(file.additions).toBe(2);
+		expect(file.deletions).toBe(1);
+		expect(file.hunks).toHaveLength(1);
+
+		const hunk = file.hunks[0];
+		expect(hunk.oldStart).toBe(1);
+		expect(hunk.oldLines).toBe(3);
+		expect(hunk.newStart).toBe(1);
+		expect(hunk.newLines).toBe(4);
+		expect(hunk.lines).toEqual([
+			{ type: "context", content: "const a = 1;", oldLineNumber: 1, newLineNumber: 1 },
+			{ type: "deletion", content: "const b = 2;", oldLineNumber: 2, newLineNumber: null },
+			{ type: "addition", content: "const b = 3;", oldLineNumber: null, newLineNumber: 2 },
+			{ type: "addition", content: "const c = 4;", oldLineNumber: null, newLineNumber: 3 },
+			{ type: "context", content: "const d = 5;", oldLineNumber: 3, newLineNumber: 4 },
+		]);
+	});
+
+	test("detects renames with similarity", () => {
+		const diff = `diff --git a/old/name.ts b/new/name.ts
+similarity index 92%
+rename from old/name.ts
+rename to new/name.ts
+index 1234567..89abcde 100644
+--- a/old/name.ts
++++ b/new/name.ts
+@@ -1 +1 @@
+-foo
++bar
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.status).toBe("renamed");
+		expect(file.path).toBe("new/name.ts");
+		expect(file.oldPath).toBe("old/name.ts");
+		expect(file.similarity).toBe(92);
+		expect(file.hunks[0].oldLines).toBe(1);
+	});
+
+	test("flags binary files and takes the path from the header", () => {
+		const diff = `diff --git a/assets/logo.png b/assets/logo.png
+new file mode 100644
+index 0000000..89abcde
+Binary files /dev/null and b/assets/logo.png differ
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.path).toBe("assets/logo.png");
+		expect(file.status).toBe("added");
+		expect(file.isBinary).toBe(true);
+		expect(file.newMode).toBe("100644");
+		expect(file.hunks).toEqual([]);
+	});
+
+	test("keeps the old path for deleted files", () => {
+		const diff = `diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+index 1234567..0000000
+--- a/gone.txt
++++ /dev/null
+@@ -1,2 +0,0 @@
+-one
+-two
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.status).toBe("deleted");
+		expect(file.path).toBe("gone.txt");
+		expect(file.deletions).toBe(2);
+	});
+
+	test("marks lines missing a trailing newline", () => {
+		const diff = `diff --git a/a.txt b/a.txt
+index 1234567..89abcde 100644
+--- a/a.txt
++++ b/a.txt
+@@ -1 +1 @@
+-old
+\\ No newline at end of file
++new
+\\ No newline at end of file
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.hunks[0].lines.every((line) => line.noNewlineAtEnd)).toBe(true);
+	});
+
+	test("decodes quoted paths", () => {
+		const diff = `diff --git "a/caf\\303\\251.txt" "b/caf\\303\\251.txt"
+index 1234567..89abcde 100644
+--- "a/caf\\303\\251.txt"
++++ "b/caf\\303\\251.txt"
+@@ -1 +1 @@
+-a
++b
+`;
+
+		const [file] = parseUnifiedDiff(diff);
+
+		expect(file.path).toBe("café.txt");
+	});
+
+	test("splits multiple files", () => {
+		const diff = `diff --git a/one.ts b/one.ts
+index 1..2 100644
+--- a/one.ts
++++ b/one.ts
+@@ -1 +1 @@
+-a
++b
+diff --git a/two.ts b/two.ts
+old mode 100644
+new mode 100755
+`;
+
+		const files = parseUnifiedDiff(diff);
+
+		expect(files.map((file) => file.path)).toEqual(["one.ts", "two.ts"]);
+		expect(files[1].oldMode).toBe("100644");
+		expect(files[1].newMode).toBe("100755");
+	});
+
+	test("returns an empty array for empty output", () => {
+		expect(parseUnifiedDiff("")).toEqual([]);
+	});
+});
@@ -0,0 +1,196 @@
+import type { DiffHunk, StructuredFileDiff } from "shared/changes-types";
+
+const HUNK_HEADER = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/;
+
+const C_ESCAPES: Record<string, string> = {
+	a: "\x07",
+	b: "\b",
+	f: "\f",
+	n: "\n",
+	r: "\r",
+	t: "\t",
+	v: "\v",
+	'"': '"',
+	"\\": "\\",
+};
+
+/**
+ * Decodes a path that git wrapped in quotes with C-style escapes.
+ * Octal escapes are UTF-8 bytes, so they are collected and decoded together.
+ */
+function unquotePath(raw: string): string {
+	if (!raw.startsWith('"') || !raw.endsWith('"')) {
+		return raw;
+	}
+
+	const bytes: number[] = [];
+	const inner = raw.slice(1, -1);
+	for (let i = 0; i < inner.length; i++) {
+		const char = inner[i];
+		if (char !== "\\") {
+			bytes.push(...Buffer.from(char, "utf-8"));
+			continue;
+		}
+
+		const next = inner[i + 1];
+		const octal = inner.slice(i + 1, i + 4);
+		if (/^[0-7]{3}$/.test(octal)) {
+			bytes.push(Number.parseInt(octal, 8));
+			i += 3;
+		} else if (next !== undefined && next in C_ESCAPES) {
+			bytes.push(...Buffer.from(C_ESCAPES[next], "utf-8"));
+			i += 1;
+		} else {
+			bytes.push(...Buffer.from(char, "utf-8"));
+		}
+	}
+	return Buffer.from(bytes).toString("utf-8");
+}
+
+/** Strips the a/ or b/ prefix from a ---/+++ path; null for /dev/null */
+function stripSidePrefix(raw: string): string | null {
+	const path = unquotePath(raw.trim());
+	if (path === "/dev/null") return null;
+	return path.replace(/^[ab]\//, "");
+}
+
+/**
+ * Best-effort path extraction from a `diff --git a/X b/Y` header.
+ * Only used when no ---/+++ or rename lines follow (e.g. binary or mode-only changes),
+ * where both sides are the same path.
+ */
+function parseGitHeaderPath(header: string): string | null {
+	const rest = header.slice("diff --git ".length);
+	if (rest.startsWith('"')) {
+		const end = rest.indexOf('"', 1);
+		return end > 0 ? stripSidePrefix(rest.slice(0, end + 1)) : null;
+	}
+	// "a/P b/P" has length 2 * |P| + 5
+	const pathLength = (rest.length - 5) / 2;
+	if (!Number.isInteger(pathLength) || pathLength <= 0) return null;
+	return rest.slice(2, 2 + pathLength);
+}
+
+/**
+ * Parses unified diff output from `git diff` / `git show` into files, hunks and lines.
+ * Expects git's default a/ b/ prefixes and no color.
+ */
+export function parseUnifiedDiff(diffOutput: string): StructuredFileDiff[] {
+	const files: StructuredFileDiff[] = [];
+	let file: StructuredFileDiff | null = null;
+	let hunk: DiffHunk | null = null;
+	let oldLine = 0;
+	let newLine = 0;
+
+	const lines = diffOutput.split("\n");
+	// A trailing newline produces an empty final element that isn't a diff line
+	if (lines[lines.length - 1] === "") lines.pop();
+
+	for (const line of lines) {
+		if (line.startsWith("diff --git ")) {
+			const headerPath = parseGitHeaderPath(line) ?? "";
+			file = {
+				path: headerPath,
+				status: "modified",
+				isBinary: false,
+				additions: 0,
+				deletions: 0,
+				hunks: [],
+			};
+			hunk = null;
+			files.push(file);
+			continue;
+		}
+
+		if (!file) continue;
+
+		if (hunk) {
+			const marker = line[0];
+			if (marker === "+") {
+				hunk.lines.push({
+					type: "addition",
+					content: line.slice(1),
+					oldLineNumber: null,
+					newLineNumber: newLine++,
+				});
+				file.additions++;
+				continue;
+			}
+			if (marker === "-") {
+				hunk.lines.push({
+					type: "deletion",
+					content: line.slice(1),
+					oldLineNumber: oldLine++,
+					newLineNumber: null,
+				});
+				file.deletions++;
+				continue;
+			}
+			if (marker === " " || line === "") {
+				hunk.lines.push({
+					type: "context",
+					content: line.slice(1),
+					oldLineNumber: oldLine++,
+					newLineNumber: newLine++,
+				});
+				continue;
+			}
+			if (marker === "\\") {
+				const previous = hunk.lines[hunk.lines.length - 1];
+				if (previous) previous.noNewlineAtEnd = true;
+				continue;
+			}
+		}
+
+		const hunkMatch = line.match(HUNK_HEADER);
+		if (hunkMatch) {
+			oldLine = Number.parseInt(hunkMatch[1], 10);
+			newLine = Number.parseInt(hunkMatch[3], 10);
+			hunk = {
+				header: line,
+				oldStart: oldLine,
+				oldLines: hunkMatch[2] === undefined ? 1 : Number.parseInt(hunkMatch[2], 10),
+				newStart: newLine,
+				newLines: hunkMatch[4] === undefined ? 1 : Number.parseInt(hunkMatch[4], 10),
+				lines: [],
+			};
+			file.hunks.push(hunk);
+			continue;
+		}
+
+		// Extended header lines (only valid before the first hunk)
+		if (line.startsWith("new file mode ")) {
+			file.status = "added";
+			file.newMode = line.slice("new file mode ".length);
+		} else if (line.startsWith("deleted file mode ")) {
+			file.status = "deleted";
+			file.oldMode = line.slice("deleted file mode ".length);
+		} else if (line.startsWith("old mode ")) {
+			file.oldMode = line.slice("old mode ".length);
+		} else if (line.startsWith("new mode ")) {
+			file.newMode = line.slice("new mode ".length);
+		} else if (line.startsWith("rename from ")) {
+			file.status = "renamed";
+			file.oldPath = unquotePath(line.slice("rename from ".length));
+		} else if (line.startsWith("rename to ")) {
+			file.path = unquotePath(line.slice("rename to ".length));
+		} else if (line.startsWith("copy from ")) {
+			file.status = "copied";
+			file.oldPath = unquotePath(line.slice("copy from ".length));
+		} else if (line.startsWith("copy to ")) {
+			file.path = unquotePath(line.slice("copy to ".length));
+		} else if (line.startsWith("similarity index ")) {
+			file.similarity = Number.parseInt(line.slice("similarity index ".length), 10);
+		} else if (line.startsWith("Binary files ") || line === "GIT binary patch") {
+			file.isBinary = true;
+		} else if (line.startsWith("--- ")) {
+			const oldPath = stripSidePrefix(line.slice(4));
+			if (oldPath && file.status === "deleted") file.path = oldPath;
+		} else if (line.startsWith("+++ ")) {
+			const newPath = stripSidePrefix(line.slice(4));
+			if (newPath) file.path = newPath;
+		}
+	}
+
+	return files;
+}
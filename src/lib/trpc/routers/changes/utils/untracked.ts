@@ -0,0 +1,93 @@
+import type { ChangedFile } from "shared/changes-types";
+import type simpleGit from "simple-git";
+import { secureFs } from "../security";
+import { LineCounter } from "./line-count";
+
+/** Max file size for untracked line counting (10 MiB) - larger files are left at 0 */
+export const MAX_UNTRACKED_LINE_COUNT_SIZE = 10 * 1024 * 1024;
+
+/** Paths per check-ignore call, to stay well under OS argument limits */
+const CHECK_IGNORE_BATCH_SIZE = 500;
+
+/**
+ * Returns the subset of paths matched by .gitignore, info/exclude or core.excludesFile.
+ * Status already hides most of these, but this also catches paths reported
+ * before an ignore rule was added and entries surfaced by other callers.
+ */
+export async function getIgnoredPaths(
+	git: ReturnType<typeof simpleGit>,
+	paths: string[],
+): Promise<Set<string>> {
+	const ignored = new Set<string>();
+
+	for (let i = 0; i < paths.length; i += CHECK_IGNORE_BATCH_SIZE) {
+		const batch = paths.slice(i, i + CHECK_IGNORE_BATCH_SIZE);
+		try {
+			// Exits 1 when nothing matches, which isn't an error here
+			const output = await git.raw(["check-ignore", "-z", "--", ...batch]);
+			for (const path of output.split("\0")) {
+				if (path) ignored.add(path);
+			}
+		} catch (error) {
+			// Ignored files in this batch will show up as untracked changes
+			console.warn(`[changes/untracked] check-ignore failed for ${batch.length} paths:`, error);
+		}
+	}
+
+	return ignored;
+}
+
+/**
+ * Counts lines in a worktree file without loading it into memory.
+ * Returns null when the file exceeds maxBytes or can't be read.
+ */
+export async function countFileLines(
+	worktreePath: string,
+	filePath: string,
+	maxBytes = MAX_UNTRACKED_LINE_COUNT_SIZE,
+): Promise<number | null> {
+	try {
+		const stats = await secureFs.stat(worktreePath, filePath);
+		if (!stats.isFile() || stats.size > maxBytes) return null;
+
+		const counter = new LineCounter();
+		const stream = await secureFs.createReadStream(worktreePath, filePath);
+		for await (const chunk of stream) {
+			counter.push(chunk as Buffer);
+			// The file may have grown since stat
+			if (counter.byteCount > maxBytes) {
+				stream.destroy();
+				return null;
+			}
+		}
+		return counter.lineCount;
+	} catch (error) {
+		// Skip files that fail validation or reading; they keep a count of 0
+		console.debug(`[changes/untracked] Failed to count lines in ${filePath}:`, error);
+		return null;
+	}
+}
+
+/**
+ * Drops ignored entries from the untracked list and fills in line counts
+ * as additions, the way git would report a newly added file.
+ */
+export async function prepareUntrackedFiles(
+	git: ReturnType<typeof simpleGit>,
+	worktreePath: string,
+	untracked: ChangedFile[],
+): Promise<ChangedFile[]> {
+	if (untracked.length === 0) return untracked;
+
+	const ignored = await getIgnoredPaths(git, untracked.map((file) => file.path));
+	const visible = untracked.filter((file) => !ignored.has(file.path));
+
+	for (const file of visible) {
+		const lineCount = await countFileLines(worktreePath, file.path);
+		if (lineCount === null) continue;
+		file.additions = lineCount;
+		file.deletions = 0;
+	}
+
+	return visible;
+}
@@ -29,6 +29,38 @@ const APP_NAMES: Record<ExternalApp, string | null> = {
 	rustrover: "RustRover",
 };
 
+/**
+ * Command-line launchers apps install, tried in order. Used where there is
+ * no macOS bundle to `open`, i.e. on Windows and Linux.
+ */
+const LAUNCHER_COMMANDS: Partial<Record<ExternalApp, string[]>> = {
+	vscode: ["code"],
+	"vscode-insiders": ["code-insiders"],
+	cursor: ["cursor"],
+	zed: ["zed", "zeditor", "zedit"],
+	sublime: ["subl"],
+	intellij: ["idea", "idea64", "intellij-idea-ultimate", "intellij-idea-community"],
+	webstorm: ["webstorm", "webstorm64"],
+	pycharm: ["pycharm", "pycharm64", "charm"],
+	phpstorm: ["phpstorm", "phpstorm64"],
+	rubymine: ["rubymine", "rubymine64"],
+	goland: ["goland", "goland64"],
+	clion: ["clion", "clion64"],
+	rider: ["rider", "rider64"],
+	datagrip: ["datagrip", "datagrip64"],
+	fleet: ["fleet"],
+	rustrover: ["rustrover", "rustrover64"],
+};
+
+export function getLauncherCandidates(app: ExternalApp): string[] {
+	return LAUNCHER_COMMANDS[app] ?? [];
+}
+
+/** Human-readable app name for error messages */
+export function getAppDisplayName(app: ExternalApp): string {
+	return APP_NAMES[app] ?? "Finder";
+}
+
 /**
  * Get the command and args to open a path in the specified app.
  * Uses `open -a` for macOS apps to avoid PATH issues in production builds.
@@ -248,4 +280,31 @@ export function spawnAsync(command: string, args: string[]): Promise<void> {
 	});
 }
 
+/**
+ * Starts an app launcher without waiting for it to exit, since some (like
+ * JetBrains' shell scripts) keep running for as long as the IDE is open.
+ * Windows launchers are .cmd scripts, which can only be run through a shell.
+ * @throws Error if the launcher fails to spawn
+ */
+export function launchDetached(command: string, args: string[]): Promise<void> {
+	const useShell = process.platform === "win32" && /\.(cmd|bat)$/i.test(command);
+	return new Promise((resolve, reject) => {
+		const child = useShell
+			? spawn(`"${command}"`, args.map((arg) => `"${arg}"`), {
+					stdio: "ignore",
+					detached: true,
+					shell: true,
+				})
+			: spawn(command, args, { stdio: "ignore", detached: true });
+
+		child.once("error", (error) => {
+			reject(new Error(`Failed to launch '${command}': ${error.message}`));
+		});
+		child.once("spawn", () => {
+			child.unref();
+			resolve();
+		});
+	});
+}
+
 export { EXTERNAL_APPS, type ExternalApp };
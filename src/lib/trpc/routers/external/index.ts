@@ -1,26 +1,102 @@
+import { existsSync } from "node:fs";
 import { TRPCError } from "@trpc/server";
+import { eq } from "drizzle-orm";
 import { clipboard, shell } from "electron";
-import { settings } from "lib/local-db";
+import { nodes, settings } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
-import { EXTERNAL_APPS, type ExternalApp, getAppCommand, resolvePath, spawnAsync } from "./helpers";
+import { findExecutable } from "../nodes/utils/shell-env";
+import { getNodePath } from "../nodes/utils/worktree";
+import {
+	EXTERNAL_APPS,
+	type ExternalApp,
+	getAppCommand,
+	getAppDisplayName,
+	getLauncherCandidates,
+	launchDetached,
+	resolvePath,
+	spawnAsync,
+} from "./helpers";
 
 const ExternalAppSchema = z.enum(EXTERNAL_APPS);
 
+/** The configured launcher, else the first of the app's launchers found on PATH */
+async function resolveLauncher(app: ExternalApp): Promise<string | null> {
+	const configured = localDb.select().from(settings).get()?.editorLaunchers?.[app];
+	if (configured) return configured;
+	if (process.platform === "darwin") return null;
+
+	for (const name of getLauncherCandidates(app)) {
+		// Windows launchers are .exe binaries or .cmd scripts next to an extensionless sh script
+		const variants = process.platform === "win32" ? [`${name}.exe`, `${name}.cmd`] : [name];
+		for (const variant of variants) {
+			const path = await findExecutable(variant);
+			if (path) return path;
+		}
+	}
+	return null;
+}
+
 async function openPathInApp(filePath: string, app: ExternalApp): Promise<void> {
 	if (app === "finder") {
 		shell.showItemInFolder(filePath);
 		return;
 	}
 
-	const cmd = getAppCommand(app, filePath);
-	if (cmd) {
-		await spawnAsync(cmd.command, cmd.args);
+	const name = getAppDisplayName(app);
+	const launcher = await resolveLauncher(app);
+	if (launcher) {
+		await launchDetached(launcher, [filePath]);
 		return;
 	}
 
-	await shell.openPath(filePath);
+	if (process.platform === "darwin") {
+		const cmd = getAppCommand(app, filePath);
+		if (cmd) {
+			try {
+				await spawnAsync(cmd.command, cmd.args);
+			} catch (error) {
+				const message = error instanceof Error ? error.message : String(error);
+				if (message.includes("Unable to find application")) {
+					throw new TRPCError({
+						code: "PRECONDITION_FAILED",
+						message: `${name} is not installed`,
+					});
+				}
+				throw error;
+			}
+			return;
+		}
+	}
+
+	const [launcherName] = getLauncherCandidates(app);
+	if (launcherName) {
+		throw new TRPCError({
+			code: "PRECONDITION_FAILED",
+			message: `Couldn't find ${name}. Install its \`${launcherName}\` command or set a launcher in Settings.`,
+		});
+	}
+
+	const error = await shell.openPath(filePath);
+	if (error) {
+		throw new TRPCError({ code: "INTERNAL_SERVER_ERROR", message: error });
+	}
+}
+
+function getExistingNodePath(nodeId: string): string {
+	const node = localDb.select().from(nodes).where(eq(nodes.id, nodeId)).get();
+	if (!node) {
+		throw new TRPCError({ code: "NOT_FOUND", message: `Node ${nodeId} not found` });
+	}
+	const nodePath = getNodePath(node);
+	if (!nodePath || !existsSync(nodePath)) {
+		throw new TRPCError({
+			code: "PRECONDITION_FAILED",
+			message: `The folder for "${node.name}" no longer exists`,
+		});
+	}
+	return nodePath;
 }
 
 /**
@@ -65,6 +141,22 @@ export const createExternalRouter = () => {
 				await openPathInApp(input.path, input.app);
 			}),
 
+		/** Opens a node's worktree (or main checkout) in an editor or IDE */
+		openNodeInApp: publicProcedure
+			.input(z.object({ nodeId: z.string(), app: ExternalAppSchema }))
+			.mutation(async ({ input }) => {
+				await openPathInApp(getExistingNodePath(input.nodeId), input.app);
+			}),
+
+		openNodeInFileManager: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.mutation(async ({ input }) => {
+				const error = await shell.openPath(getExistingNodePath(input.nodeId));
+				if (error) {
+					throw new TRPCError({ code: "INTERNAL_SERVER_ERROR", message: error });
+				}
+			}),
+
 		copyPath: publicProcedure.input(z.string()).mutation(async ({ input }) => {
 			clipboard.writeText(input);
 		}),
@@ -1,6 +1,7 @@
 import type { BrowserWindow } from "electron";
 import { router } from "..";
 import { createAnalyticsRouter } from "./analytics";
+import { createAuditRouter } from "./audit";
 import { createAutoUpdateRouter } from "./auto-update";
 import { createCacheRouter } from "./cache";
 import { createChangesRouter } from "./changes";
@@ -8,12 +9,15 @@ import { createConfigRouter } from "./config";
 import { createExternalRouter } from "./external";
 import { createFilesystemRouter } from "./filesystem";
 import { createHotkeysRouter } from "./hotkeys";
+import { createLogsRouter } from "./logs";
 import { createMenuRouter } from "./menu";
 import { createNodesRouter } from "./nodes";
 import { createNotificationsRouter } from "./notifications";
+import { createOperationsRouter } from "./operations";
 import { createPortsRouter } from "./ports";
 import { createRepositoriesRouter } from "./repositories";
 import { createSettingsRouter } from "./settings";
+import { createSupportRouter } from "./support";
 import { createTerminalRouter } from "./terminal";
 import { createUiStateRouter } from "./ui-state";
 import { createWindowRouter } from "./window";
@@ -21,6 +25,7 @@ import { createWindowRouter } from "./window";
 export const createAppRouter = (getWindow: () => BrowserWindow | null) => {
 	return router({
 		analytics: createAnalyticsRouter(),
+		audit: createAuditRouter(getWindow),
 		autoUpdate: createAutoUpdateRouter(),
 		cache: createCacheRouter(),
 		window: createWindowRouter(getWindow),
@@ -30,11 +35,14 @@ export const createAppRouter = (getWindow: () => BrowserWindow | null) => {
 		changes: createChangesRouter(),
 		filesystem: createFilesystemRouter(),
 		notifications: createNotificationsRouter(),
+		operations: createOperationsRouter(),
 		ports: createPortsRouter(),
 		menu: createMenuRouter(),
 		hotkeys: createHotkeysRouter(getWindow),
+		logs: createLogsRouter(getWindow),
 		external: createExternalRouter(),
 		settings: createSettingsRouter(),
+		support: createSupportRouter(getWindow),
 		config: createConfigRouter(),
 		uiState: createUiStateRouter(),
 	});
@@ -0,0 +1,53 @@
+import { type BrowserWindow, dialog } from "electron";
+import { exportLogs, getRecentLogs } from "main/lib/logger";
+import { LOG_LEVELS } from "shared/types/logs";
+import { z } from "zod";
+import { publicProcedure, router } from "../..";
+
+type LogsExportResult =
+	| { canceled: true }
+	| { canceled: false; path: string }
+	| { canceled: false; error: string };
+
+export const createLogsRouter = (getWindow: () => BrowserWindow | null) => {
+	return router({
+		/** Recent main process log entries, oldest first */
+		getRecentLogs: publicProcedure
+			.input(
+				z.object({
+					level: z.enum(LOG_LEVELS).default("info"),
+					lines: z.number().int().positive().max(5000).default(200),
+				}),
+			)
+			.query(({ input }) => getRecentLogs(input)),
+
+		/** Saves all retained logs to a single file for attaching to bug reports */
+		exportLogs: publicProcedure.mutation(async (): Promise<LogsExportResult> => {
+			const window = getWindow();
+			if (!window) {
+				return { canceled: false, error: "No window available" };
+			}
+
+			const result = await dialog.showSaveDialog(window, {
+				title: "Export Logs",
+				defaultPath: "caspian-logs.log",
+				filters: [{ name: "Log files", extensions: ["log", "txt"] }],
+			});
+
+			if (result.canceled || !result.filePath) {
+				return { canceled: true };
+			}
+
+			try {
+				await exportLogs(result.filePath);
+			} catch (error) {
+				const message = error instanceof Error ? error.message : "Failed to write file";
+				return { canceled: false, error: message };
+			}
+
+			return { canceled: false, path: result.filePath };
+		}),
+	});
+};
+
+export type LogsRouter = ReturnType<typeof createLogsRouter>;
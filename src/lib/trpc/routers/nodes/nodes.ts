@@ -1,23 +1,39 @@
 import { mergeRouters } from "../..";
 import { createBranchProcedures } from "./procedures/branch";
+import { createCompareProcedures } from "./procedures/compare";
 import { createCreateProcedures } from "./procedures/create";
 import { createDeleteProcedures } from "./procedures/delete";
+import { createDevServerProcedures } from "./procedures/dev-server";
+import { createGateProcedures } from "./procedures/gate";
 import { createGitStatusProcedures } from "./procedures/git-status";
 import { createInitProcedures } from "./procedures/init";
+import { createIssueProcedures } from "./procedures/issues";
 import { createQueryProcedures } from "./procedures/query";
+import { createRestoreProcedures } from "./procedures/restore";
+import { createReviewProcedures } from "./procedures/review";
+import { createSnapshotsProcedures } from "./procedures/snapshots";
 import { createStatusProcedures } from "./procedures/status";
+import { createTestsProcedures } from "./procedures/tests";
 
 /**
  * Nodes router - manages node lifecycle, git operations, and status.
  *
  * Procedures are organized into logical groups:
- * - create: create, createBranchNode, openWorktree
+ * - create: create, cloneNode, createBranchNode, importExistingNodes, openWorktree
  * - delete: delete, close, canDelete
  * - query: get, getAll, getAllGrouped
- * - branch: getBranches, switchBranchNode
+ * - branch: getBranches, switchBranchNode, renameBranch
  * - git-status: refreshGitStatus, getGitHubStatus, getWorktreeInfo, getWorktreesByRepository
- * - status: reorder, update, setUnread
+ * - status: reorder, update, setUnread, setActive, markViewed, getUnreadCounts
  * - init: onInitProgress, retryInit, getInitProgress, getSetupCommands
+ * - compare: compare
+ * - review: addReviewComment, listReviewComments, resolveReviewComment, deleteReviewComment, getReviewPrompt
+ * - tests: runTests, listTestRuns, getNodeCoverage, getTestRun
+ * - gate: getGateConfig, runGate, getLatestGateRun, onGateProgress
+ * - snapshots: snapshotNode, listSnapshots, restoreSnapshot, deleteSnapshot
+ * - dev-server: startDevServer, stopDevServer, getDevServer, getDevServerLogs, onDevServerEvents
+ * - issues: getIssue, previewIssue, linkIssue, unlinkIssue
+ * - restore: getRestoreReport, dismissRestoreReport
  */
 export const createNodesRouter = () => {
 	return mergeRouters(
@@ -28,6 +44,14 @@ export const createNodesRouter = () => {
 		createGitStatusProcedures(),
 		createStatusProcedures(),
 		createInitProcedures(),
+		createCompareProcedures(),
+		createReviewProcedures(),
+		createTestsProcedures(),
+		createGateProcedures(),
+		createSnapshotsProcedures(),
+		createDevServerProcedures(),
+		createIssueProcedures(),
+		createRestoreProcedures(),
 	);
 };
 
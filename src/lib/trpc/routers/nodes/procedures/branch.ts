@@ -1,11 +1,28 @@
 import { and, eq, isNull } from "drizzle-orm";
-import { nodes, repositories } from "lib/local-db";
+import { nodes, repositories, worktrees } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
 import { getNodeRuntimeRegistry } from "main/lib/node-runtime";
+import { operationManager } from "main/lib/operation-manager";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
-import { getBranchNode, getNode, setLastActiveNode, touchNode } from "../utils/db-helpers";
-import { listBranches, safeCheckoutBranch } from "../utils/git";
+import { CaspianError, nodeNotFound, repositoryNotFound } from "../../../errors";
+import { getBranchNameFormatter } from "../utils/branch-name";
+import {
+	getBranchNode,
+	getNode,
+	getNodeNotDeleting,
+	getRepository,
+	getWorktree,
+	setLastActiveNode,
+	touchNode,
+} from "../utils/db-helpers";
+import {
+	fetchAndPrune,
+	listBranches,
+	renameLocalBranch,
+	safeCheckoutBranch,
+	sanitizeBranchName,
+} from "../utils/git";
 
 export const createBranchProcedures = () => {
 	return router({
@@ -14,6 +31,7 @@ export const createBranchProcedures = () => {
 				z.object({
 					repositoryId: z.string(),
 					fetch: z.boolean().optional(), // Whether to fetch remote refs (default: false, avoids UI stalls)
+					operationId: z.string().optional(), // Lets operations.cancel skip a slow fetch
 				}),
 			)
 			.query(async ({ input }) => {
@@ -23,12 +41,26 @@ export const createBranchProcedures = () => {
 					.where(eq(repositories.id, input.repositoryId))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
-				const branches = await listBranches(repository.mainRepoPath, {
-					fetch: input.fetch,
-				});
+				if (input.fetch) {
+					try {
+						await operationManager.run(
+							{
+								id: input.operationId,
+								kind: "fetch",
+								title: `Fetch ${repository.name}`,
+								subjectId: repository.id,
+							},
+							({ signal }) => fetchAndPrune(repository.mainRepoPath, signal),
+						);
+					} catch (error) {
+						// Offline or cancelled; the operation shows the failure and local refs still list
+						console.warn(`[nodes/getBranches] Fetch failed for ${repository.name}:`, error);
+					}
+				}
+				const branches = await listBranches(repository.mainRepoPath);
 
 				const repositoryNodes = localDb
 					.select()
@@ -63,12 +95,15 @@ export const createBranchProcedures = () => {
 					.where(eq(repositories.id, input.repositoryId))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
 				const node = getBranchNode(input.repositoryId);
 				if (!node) {
-					throw new Error("No branch node found for this repository");
+					throw new CaspianError({
+						code: "NOT_FOUND",
+						message: "No branch node found for this repository",
+					});
 				}
 
 				await safeCheckoutBranch(repository.mainRepoPath, input.branch);
@@ -84,7 +119,7 @@ export const createBranchProcedures = () => {
 
 				const updatedNode = getNode(node.id);
 				if (!updatedNode) {
-					throw new Error(`Node ${node.id} not found after update`);
+					throw nodeNotFound(node.id);
 				}
 
 				return {
@@ -92,5 +127,78 @@ export const createBranchProcedures = () => {
 					worktreePath: repository.mainRepoPath,
 				};
 			}),
+
+		/**
+		 * Renames a worktree node's local branch. The name goes through the
+		 * repository's branch template or prefix like a new node's would. The
+		 * remote branch, if any, is left alone.
+		 */
+		renameBranch: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					name: z.string(),
+					/** Fills {ticket} in the repository's branch template */
+					ticket: z.string().optional(),
+					applyPrefix: z.boolean().optional().default(true),
+				}),
+			)
+			.mutation(async ({ input }) => {
+				const node = getNodeNotDeleting(input.nodeId);
+				if (!node) {
+					throw nodeNotFound(input.nodeId);
+				}
+				const worktree = node.worktreeId ? getWorktree(node.worktreeId) : undefined;
+				if (node.type !== "worktree" || !worktree) {
+					throw new CaspianError({
+						code: "NOT_A_WORKTREE_NODE",
+						message: `Node ${node.id} is not a worktree node`,
+					});
+				}
+				const repository = getRepository(node.repositoryId);
+				if (!repository) {
+					throw repositoryNotFound(node.repositoryId);
+				}
+
+				const { local, remote } = await listBranches(repository.mainRepoPath);
+				const existingBranches = [...local, ...remote];
+				const formatBranch = input.applyPrefix
+					? await getBranchNameFormatter({ repository, existingBranches, ticket: input.ticket })
+					: (slug: string) => slug;
+
+				const slug = sanitizeBranchName(input.name);
+				const branch = slug ? formatBranch(slug) : "";
+				if (!branch) {
+					throw new CaspianError({ code: "INVALID_INPUT", message: "Branch name is empty" });
+				}
+				if (branch === worktree.branch) {
+					return { branch };
+				}
+				if (existingBranches.some((b) => b.toLowerCase() === branch.toLowerCase())) {
+					throw new CaspianError({
+						code: "BRANCH_EXISTS",
+						message: `Branch "${branch}" already exists`,
+					});
+				}
+
+				await renameLocalBranch(worktree.path, worktree.branch, branch);
+
+				localDb
+					.update(worktrees)
+					.set({
+						branch,
+						...(worktree.gitStatus && { gitStatus: { ...worktree.gitStatus, branch } }),
+					})
+					.where(eq(worktrees.id, worktree.id))
+					.run();
+				// Only update display name if it still matches the old branch (not custom)
+				touchNode(node.id, {
+					branch,
+					...(node.name === worktree.branch ? { name: branch } : {}),
+				});
+				getNodeRuntimeRegistry().getForNodeId(node.id).terminal.refreshPromptsForWorkspace(node.id);
+
+				return { branch };
+			}),
 	});
 };
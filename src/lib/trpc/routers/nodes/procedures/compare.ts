@@ -0,0 +1,197 @@
+import type { SelectNode } from "lib/local-db";
+import type { ChangedFile, CommitInfo } from "shared/changes-types";
+import type { QualityGateStatus } from "shared/types/quality-gate";
+import type { TestRunStatus } from "shared/types/test-runs";
+import simpleGit from "simple-git";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeNotFound } from "../../../errors";
+import { applyNumstatToFiles } from "../../changes/utils/apply-numstat";
+import { parseDiffNumstat, parseGitLog, parseNameStatus } from "../../changes/utils/parse-status";
+import { compareChangedFiles, summarizeChangedFiles } from "../utils/compare";
+import { getNodeWithRelations } from "../utils/db-helpers";
+import { getLatestGateRun } from "../utils/quality-gate";
+import { getLatestTestRun } from "../utils/test-run";
+
+interface NodeAttempt {
+	nodeId: string;
+	name: string;
+	branch: string;
+	headSha: string;
+	baseRef: string;
+	files: ChangedFile[];
+	commits: CommitInfo[];
+	filesChanged: number;
+	additions: number;
+	deletions: number;
+	createdAt: number;
+	lastCommitAt: number | null;
+	/** Time from node creation to its most recent commit */
+	durationMs: number | null;
+	/** Most recent test run, null when tests were never run */
+	latestTestRun: { status: TestRunStatus; createdAt: number } | null;
+	/** Most recent quality gate run; `isCurrent` when it ran against the node's HEAD */
+	latestGateRun: { status: QualityGateStatus; createdAt: number; isCurrent: boolean } | null;
+}
+
+function loadWorktreeNode(nodeId: string) {
+	const relations = getNodeWithRelations(nodeId);
+	if (!relations || relations.node.deletingAt) {
+		throw nodeNotFound(nodeId);
+	}
+
+	const { node, worktree, repository } = relations;
+	if (!worktree || !repository) {
+		throw new CaspianError({
+			code: "NOT_A_WORKTREE_NODE",
+			message: `Node ${nodeId} is not a worktree node`,
+		});
+	}
+	return { node, worktree, repository };
+}
+
+async function resolveBaseRef(
+	git: ReturnType<typeof simpleGit>,
+	baseBranch: string,
+): Promise<string> {
+	for (const ref of [`origin/${baseBranch}`, baseBranch]) {
+		let sha: string;
+		try {
+			// With --quiet a missing ref prints nothing rather than failing
+			sha = (await git.raw(["rev-parse", "--verify", "--quiet", `${ref}^{commit}`])).trim();
+		} catch (error) {
+			console.error(`[nodes/compare] Failed to resolve ${ref}:`, error);
+			throw error;
+		}
+		if (sha) return ref;
+	}
+	throw new CaspianError({
+		code: "BRANCH_NOT_FOUND",
+		message: `Base branch ${baseBranch} not found`,
+	});
+}
+
+/**
+ * Collects what a node changed relative to where it forked from its base branch,
+ * including uncommitted edits to tracked files.
+ */
+async function collectAttempt({
+	node,
+	worktreePath,
+	baseBranch,
+}: {
+	node: SelectNode;
+	worktreePath: string;
+	baseBranch: string;
+}): Promise<NodeAttempt> {
+	const git = simpleGit(worktreePath);
+
+	const baseRef = await resolveBaseRef(git, baseBranch);
+	const headSha = (await git.revparse(["HEAD"])).trim();
+	const mergeBase = (await git.raw(["merge-base", baseRef, "HEAD"])).trim();
+
+	const files = parseNameStatus(await git.raw(["diff", "--name-status", mergeBase]));
+	await applyNumstatToFiles(git, files, ["diff", "--numstat", mergeBase]);
+
+	const commits = parseGitLog(
+		await git.raw(["log", `${mergeBase}..HEAD`, "--format=%H|%h|%s|%an|%aI"]),
+	);
+
+	const lastCommitAt = commits.length > 0 ? commits[0].date.getTime() : null;
+	const testRun = getLatestTestRun(node.id);
+	const gateRun = getLatestGateRun(node.id);
+
+	return {
+		nodeId: node.id,
+		name: node.name,
+		branch: node.branch,
+		headSha,
+		baseRef,
+		files,
+		commits,
+		...summarizeChangedFiles(files),
+		createdAt: node.createdAt,
+		lastCommitAt,
+		durationMs: lastCommitAt !== null ? Math.max(0, lastCommitAt - node.createdAt) : null,
+		latestTestRun: testRun ? { status: testRun.status, createdAt: testRun.createdAt } : null,
+		latestGateRun: gateRun
+			? {
+					status: gateRun.status,
+					createdAt: gateRun.createdAt,
+					isCurrent: gateRun.headSha === headSha,
+				}
+			: null,
+	};
+}
+
+export const createCompareProcedures = () => {
+	return router({
+		/**
+		 * Compares two attempts at the same task (e.g. a retried or duplicated node)
+		 * so the user can decide which one to keep.
+		 */
+		compare: publicProcedure
+			.input(z.object({ nodeIdA: z.string(), nodeIdB: z.string() }))
+			.query(async ({ input }) => {
+				if (input.nodeIdA === input.nodeIdB) {
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: "Cannot compare a node with itself",
+					});
+				}
+
+				const a = loadWorktreeNode(input.nodeIdA);
+				const b = loadWorktreeNode(input.nodeIdB);
+
+				if (a.repository.id !== b.repository.id) {
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: "Nodes must belong to the same repository to be compared",
+					});
+				}
+
+				const defaultBranch = a.repository.defaultBranch ?? "main";
+				const [attemptA, attemptB] = await Promise.all([
+					collectAttempt({
+						node: a.node,
+						worktreePath: a.worktree.path,
+						baseBranch: a.worktree.baseBranch ?? defaultBranch,
+					}),
+					collectAttempt({
+						node: b.node,
+						worktreePath: b.worktree.path,
+						baseBranch: b.worktree.baseBranch ?? defaultBranch,
+					}),
+				]);
+
+				// Worktrees share an object store, so both HEADs are reachable from either side
+				const divergence = parseDiffNumstat(
+					await simpleGit(a.worktree.path).raw([
+						"diff",
+						"--numstat",
+						"--no-renames",
+						attemptA.headSha,
+						attemptB.headSha,
+					]),
+				);
+				let divergentAdditions = 0;
+				let divergentDeletions = 0;
+				for (const stat of divergence.values()) {
+					divergentAdditions += stat.additions;
+					divergentDeletions += stat.deletions;
+				}
+
+				return {
+					a: attemptA,
+					b: attemptB,
+					files: compareChangedFiles(attemptA.files, attemptB.files),
+					sameBase: attemptA.baseRef === attemptB.baseRef,
+					headsDiffer: {
+						files: divergence.size,
+						additions: divergentAdditions,
+						deletions: divergentDeletions,
+					},
+				};
+			}),
+	});
+};
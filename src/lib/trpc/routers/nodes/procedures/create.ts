@@ -1,44 +1,53 @@
 import { homedir } from "node:os";
 import { join } from "node:path";
-import { TRPCError } from "@trpc/server";
 import { and, eq, isNull, not } from "drizzle-orm";
-import { nodes, repositories, settings, worktrees } from "lib/local-db";
+import { nodes, repositories, worktrees } from "lib/local-db";
 import { track } from "main/lib/analytics";
 import { localDb } from "main/lib/local-db";
 import { nodeInitManager } from "main/lib/node-init-manager";
 import { CASPIAN_DIR_NAME, WORKTREES_DIR_NAME } from "shared/constants";
+import { sanitizeSegment } from "shared/utils/branch";
+import { normalizeScopePath, normalizeSparsePatterns } from "shared/utils/scope";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeNotFound, repositoryNotFound, worktreeNotFound } from "../../../errors";
 import { checkRepositoryHealth } from "../../repositories/utils/health";
+import { getBranchNameFormatter } from "../utils/branch-name";
 import {
 	activateRepository,
 	ensureBranchNodeExists,
 	getBranchNode,
 	getMaxNodeTabOrder,
+	getNodeNotDeleting,
 	getRepository,
 	getWorktree,
+	insertWorktreeNode,
 	setLastActiveNode,
 	touchNode,
 } from "../utils/db-helpers";
 import {
+	createBranchAt,
 	createWorktreeFromPr,
+	deleteLocalBranch,
 	fetchPrBranch,
 	generateBranchName,
-	getBranchPrefix,
 	getBranchWorktreePath,
 	getCurrentBranch,
+	getHeadSha,
 	getPrInfo,
 	getPrLocalBranchName,
 	listBranches,
 	type PullRequestInfo,
 	parsePrUrl,
 	safeCheckoutBranch,
-	sanitizeAuthorPrefix,
 	sanitizeBranchName,
 	worktreeExists,
 } from "../utils/git";
+import { fetchIssue, toIssueLink } from "../utils/issues";
 import { initializeNodeWorktree } from "../utils/node-init";
+import { findUnimportedWorktrees, listGitWorktrees } from "../utils/reconcile";
 import { loadSetupConfig } from "../utils/setup";
+import { getNodePath } from "../utils/worktree";
 
 interface CreateNodeFromWorktreeParams {
 	repositoryId: string;
@@ -173,9 +182,10 @@ async function handleNewWorktree({
 		branch: localBranchName,
 	});
 	if (existingWorktreePath) {
-		throw new Error(
-			`This PR's branch is already checked out in a worktree at: ${existingWorktreePath}`,
-		);
+		throw new CaspianError({
+			code: "BRANCH_CHECKED_OUT",
+			message: `This PR's branch is already checked out in a worktree at: ${existingWorktreePath}`,
+		});
 	}
 
 	await fetchPrBranch({
@@ -273,23 +283,56 @@ export const createCreateProcedures = () => {
 					applyPrefix: z.boolean().optional().default(true),
 					setupScript: z.string().optional(),
 					teardownScript: z.string().optional(),
+					/** Limit the node to a subdirectory, e.g. "packages/api" in a monorepo */
+					scopePath: z.string().optional(),
+					/** Fills {ticket} in the repository's branch template, e.g. "PROJ-123" */
+					ticket: z.string().optional(),
+					/**
+					 * Issue key or URL from the repository's tracker. Its title names
+					 * the node and branch unless those are given.
+					 */
+					issue: z.string().optional(),
+					/**
+					 * Check out only some directories (cone-mode sparse checkout). `true`
+					 * checks out just the scope; a list names the directories.
+					 */
+					sparseCheckout: z.union([z.boolean(), z.array(z.string())]).optional(),
 				}),
 			)
 			.mutation(async ({ input }) => {
+				let scopePath: string | null;
+				let sparsePatterns: string[] | null;
+				try {
+					scopePath = normalizeScopePath(input.scopePath);
+					sparsePatterns = normalizeSparsePatterns(
+						input.sparseCheckout === true ? [scopePath ?? ""] : input.sparseCheckout || [],
+					);
+				} catch (error) {
+					const message = error instanceof Error ? error.message : String(error);
+					throw new CaspianError({ code: "INVALID_INPUT", message });
+				}
+				if (input.sparseCheckout === true && !scopePath) {
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: "Sparse checkout of the scope needs a scope",
+					});
+				}
+
 				const repository = localDb
 					.select()
 					.from(repositories)
 					.where(eq(repositories.id, input.repositoryId))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
 				const health = checkRepositoryHealth({ mainRepoPath: repository.mainRepoPath });
 				if (!health.healthy) {
-					throw new TRPCError({
-						code: "PRECONDITION_FAILED",
+					throw new CaspianError({
+						code: "PATH_NOT_FOUND",
 						message: "Repository directory not found on disk",
+						hint: "Relocate the repository, or remove it if it was deleted",
 					});
 				}
 
@@ -297,7 +340,10 @@ export const createCreateProcedures = () => {
 				if (input.useExistingBranch) {
 					existingBranchName = input.branchName?.trim();
 					if (!existingBranchName) {
-						throw new Error("Branch name is required when using an existing branch");
+						throw new CaspianError({
+							code: "INVALID_INPUT",
+							message: "Branch name is required when using an existing branch",
+						});
 					}
 
 					const existingWorktreePath = await getBranchWorktreePath({
@@ -305,57 +351,40 @@ export const createCreateProcedures = () => {
 						branch: existingBranchName,
 					});
 					if (existingWorktreePath) {
-						throw new Error(
-							`Branch "${existingBranchName}" is already checked out in another worktree at: ${existingWorktreePath}`,
-						);
+						throw new CaspianError({
+							code: "BRANCH_CHECKED_OUT",
+							message: `Branch "${existingBranchName}" is already checked out in another worktree at: ${existingWorktreePath}`,
+						});
 					}
 				}
 
+				const issue = input.issue?.trim()
+					? await fetchIssue(repository.mainRepoPath, input.issue)
+					: null;
+				const ticket = input.ticket ?? issue?.key;
+
 				const { local, remote } = await listBranches(repository.mainRepoPath);
 				const existingBranches = [...local, ...remote];
 
-				let branchPrefix: string | undefined;
-				if (input.applyPrefix) {
-					const globalSettings = localDb.select().from(settings).get();
-					const repositoryOverrides = repository.branchPrefixMode != null;
-					const prefixMode = repositoryOverrides
-						? repository.branchPrefixMode
-						: (globalSettings?.branchPrefixMode ?? "none");
-					const customPrefix = repositoryOverrides
-						? repository.branchPrefixCustom
-						: globalSettings?.branchPrefixCustom;
-
-					const rawPrefix = await getBranchPrefix({
-						repoPath: repository.mainRepoPath,
-						mode: prefixMode,
-						customPrefix,
-					});
-					const sanitizedPrefix = rawPrefix ? sanitizeAuthorPrefix(rawPrefix) : undefined;
-
-					const existingSet = new Set(existingBranches.map((b) => b.toLowerCase()));
-					const prefixWouldCollide =
-						sanitizedPrefix && existingSet.has(sanitizedPrefix.toLowerCase());
-					branchPrefix = prefixWouldCollide ? undefined : sanitizedPrefix;
-				}
-
-				const withPrefix = (name: string): string =>
-					branchPrefix ? `${branchPrefix}/${name}` : name;
+				const formatBranch = input.applyPrefix
+					? await getBranchNameFormatter({ repository, existingBranches, ticket })
+					: (slug: string) => slug;
 
 				let branch: string;
 				if (existingBranchName) {
 					if (!existingBranches.includes(existingBranchName)) {
-						throw new Error(
-							`Branch "${existingBranchName}" does not exist. Please select an existing branch.`,
-						);
+						throw new CaspianError({
+							code: "BRANCH_NOT_FOUND",
+							message: `Branch "${existingBranchName}" does not exist. Please select an existing branch.`,
+						});
 					}
 					branch = existingBranchName;
 				} else if (input.branchName?.trim()) {
-					branch = withPrefix(sanitizeBranchName(input.branchName));
+					branch = formatBranch(sanitizeBranchName(input.branchName));
+				} else if (issue && sanitizeSegment(issue.title)) {
+					branch = formatBranch(sanitizeSegment(issue.title));
 				} else {
-					branch = generateBranchName({
-						existingBranches,
-						authorPrefix: branchPrefix,
-					});
+					branch = generateBranchName({ existingBranches, format: formatBranch });
 				}
 
 				const worktreePath = join(
@@ -369,33 +398,19 @@ export const createCreateProcedures = () => {
 				const defaultBranch = repository.defaultBranch || "main";
 				const targetBranch = input.baseBranch || defaultBranch;
 
-				const worktree = localDb
-					.insert(worktrees)
-					.values({
-						repositoryId: input.repositoryId,
-						path: worktreePath,
-						branch,
-						baseBranch: targetBranch,
-						gitStatus: null,
-					})
-					.returning()
-					.get();
-
-				const maxTabOrder = getMaxNodeTabOrder(input.repositoryId);
-
-				const node = localDb
-					.insert(nodes)
-					.values({
-						repositoryId: input.repositoryId,
-						worktreeId: worktree.id,
-						type: "worktree",
-						branch,
-						name: input.name ?? branch,
-						tabOrder: maxTabOrder + 1,
+				const { worktree, node } = insertWorktreeNode({
+					repositoryId: input.repositoryId,
+					worktreePath,
+					branch,
+					baseBranch: targetBranch,
+					node: {
+						name: input.name ?? issue?.title ?? branch,
 						customTeardownScript: input.teardownScript?.trim() || null,
-					})
-					.returning()
-					.get();
+						scopePath,
+						sparsePatterns,
+						issue: issue ? toIssueLink(issue) : null,
+					},
+				});
 
 				setLastActiveNode(node.id);
 				activateRepository(repository);
@@ -424,6 +439,7 @@ export const createCreateProcedures = () => {
 					baseBranchWasExplicit: !!input.baseBranch,
 					mainRepoPath: repository.mainRepoPath,
 					useExistingBranch: input.useExistingBranch,
+					sparsePatterns,
 				});
 
 				const setupConfig = loadSetupConfig(repository.mainRepoPath);
@@ -441,6 +457,134 @@ export const createCreateProcedures = () => {
 				};
 			}),
 
+		/**
+		 * Forks a node: creates a new worktree node whose branch starts at the source
+		 * node's current HEAD rather than its base branch. Uncommitted changes in the
+		 * source aren't carried over.
+		 */
+		cloneNode: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					name: z.string().optional(),
+					branchName: z.string().optional(),
+				}),
+			)
+			.mutation(async ({ input }) => {
+				const source = getNodeNotDeleting(input.nodeId);
+				if (!source) {
+					throw nodeNotFound(input.nodeId);
+				}
+
+				const repository = getRepository(source.repositoryId);
+				if (!repository) {
+					throw repositoryNotFound(source.repositoryId);
+				}
+
+				const sourcePath = getNodePath(source);
+				const headSha = sourcePath ? await getHeadSha(sourcePath) : null;
+				if (!headSha) {
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: `Node "${source.name}" has no commits to fork from`,
+					});
+				}
+
+				const { local, remote } = await listBranches(repository.mainRepoPath);
+				const existingSet = new Set([...local, ...remote].map((b) => b.toLowerCase()));
+
+				let branch: string;
+				if (input.branchName?.trim()) {
+					branch = sanitizeBranchName(input.branchName);
+					if (existingSet.has(branch.toLowerCase())) {
+						throw new CaspianError({
+							code: "BRANCH_EXISTS",
+							message: `Branch "${branch}" already exists`,
+						});
+					}
+				} else {
+					branch = `${source.branch}-fork`;
+					for (let n = 2; existingSet.has(branch.toLowerCase()); n++) {
+						branch = `${source.branch}-fork-${n}`;
+					}
+				}
+
+				const worktreePath = join(
+					homedir(),
+					CASPIAN_DIR_NAME,
+					WORKTREES_DIR_NAME,
+					repository.name,
+					branch,
+				);
+
+				// The fork targets the same base as its source so compare and PRs line up
+				const sourceWorktree = source.worktreeId ? getWorktree(source.worktreeId) : undefined;
+				const baseBranch =
+					sourceWorktree?.baseBranch ??
+					(source.type === "branch" ? source.branch : repository.defaultBranch || "main");
+
+				await createBranchAt(repository.mainRepoPath, branch, headSha);
+				let inserted: ReturnType<typeof insertWorktreeNode>;
+				try {
+					inserted = insertWorktreeNode({
+						repositoryId: repository.id,
+						worktreePath,
+						branch,
+						baseBranch,
+						node: {
+							name: input.name?.trim() || `${source.name} (fork)`,
+							customTeardownScript: source.customTeardownScript,
+							scopePath: source.scopePath,
+							sparsePatterns: source.sparsePatterns,
+						},
+					});
+				} catch (error) {
+					// Otherwise the branch would block forking again under the same name
+					await deleteLocalBranch(repository.mainRepoPath, branch).catch((cleanupError) => {
+						console.error(`[node/fork] Failed to delete branch ${branch}:`, cleanupError);
+					});
+					throw error;
+				}
+				const { worktree, node } = inserted;
+
+				setLastActiveNode(node.id);
+				activateRepository(repository);
+
+				track("node_created", {
+					node_id: node.id,
+					repository_id: repository.id,
+					branch,
+					base_branch: baseBranch,
+					use_existing_branch: false,
+					forked_from_node_id: source.id,
+				});
+
+				nodeInitManager.startJob(node.id, repository.id);
+				initializeNodeWorktree({
+					nodeId: node.id,
+					repositoryId: repository.id,
+					worktreeId: worktree.id,
+					worktreePath,
+					branch,
+					baseBranch,
+					baseBranchWasExplicit: true,
+					mainRepoPath: repository.mainRepoPath,
+					useExistingBranch: true,
+					sparsePatterns: source.sparsePatterns,
+				});
+
+				const setupConfig = loadSetupConfig(repository.mainRepoPath);
+
+				return {
+					node,
+					initialCommands: setupConfig?.setup || null,
+					worktreePath,
+					repositoryId: repository.id,
+					isInitializing: true,
+					forkedFrom: { nodeId: source.id, commit: headSha },
+				};
+			}),
+
 		createBranchNode: publicProcedure
 			.input(
 				z.object({
@@ -456,21 +600,25 @@ export const createCreateProcedures = () => {
 					.where(eq(repositories.id, input.repositoryId))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
 				const branch = input.branch || (await getCurrentBranch(repository.mainRepoPath));
 				if (!branch) {
-					throw new Error("Could not determine current branch");
+					throw new CaspianError({
+						code: "GIT_OPERATION_FAILED",
+						message: "Could not determine current branch",
+					});
 				}
 
 				if (input.branch) {
 					const existingBranchNode = getBranchNode(input.repositoryId);
 					if (existingBranchNode && existingBranchNode.branch !== branch) {
-						throw new Error(
-							`A main node already exists on branch "${existingBranchNode.branch}". ` +
-								`Use the branch switcher to change branches.`,
-						);
+						throw new CaspianError({
+							code: "CONFLICT",
+							message: `A main node already exists on branch "${existingBranchNode.branch}".`,
+							hint: "Use the branch switcher to change branches.",
+						});
 					}
 					await safeCheckoutBranch(repository.mainRepoPath, input.branch);
 				}
@@ -553,6 +701,51 @@ export const createCreateProcedures = () => {
 				};
 			}),
 
+		/**
+		 * Recreates nodes for worktrees Caspian created that the database has
+		 * lost track of, e.g. after a reset or re-adding the repository.
+		 */
+		importExistingNodes: publicProcedure
+			.input(z.object({ repositoryId: z.string() }))
+			.mutation(async ({ input }) => {
+				const repository = getRepository(input.repositoryId);
+				if (!repository) {
+					throw repositoryNotFound(input.repositoryId);
+				}
+
+				const knownPaths = localDb
+					.select({ path: worktrees.path })
+					.from(worktrees)
+					.where(eq(worktrees.repositoryId, repository.id))
+					.all()
+					.map((worktree) => worktree.path);
+				const found = findUnimportedWorktrees({
+					entries: await listGitWorktrees(repository.mainRepoPath),
+					worktreesDir: join(homedir(), CASPIAN_DIR_NAME, WORKTREES_DIR_NAME, repository.name),
+					knownPaths,
+				});
+
+				const imported = found.map((entry) => {
+					const { node } = insertWorktreeNode({
+						repositoryId: repository.id,
+						worktreePath: entry.path,
+						branch: entry.branch,
+						baseBranch: repository.defaultBranch ?? "main",
+						node: { name: entry.branch },
+					});
+					return node;
+				});
+
+				if (imported.length > 0) {
+					activateRepository(repository);
+					console.log(
+						`[node/import] Imported ${imported.length} existing worktree(s) for ${repository.name}`,
+					);
+				}
+
+				return { nodes: imported };
+			}),
+
 		openWorktree: publicProcedure
 			.input(
 				z.object({
@@ -563,7 +756,7 @@ export const createCreateProcedures = () => {
 			.mutation(async ({ input }) => {
 				const worktree = getWorktree(input.worktreeId);
 				if (!worktree) {
-					throw new Error(`Worktree ${input.worktreeId} not found`);
+					throw worktreeNotFound(input.worktreeId);
 				}
 
 				const existingNode = localDb
@@ -572,17 +765,23 @@ export const createCreateProcedures = () => {
 					.where(and(eq(nodes.worktreeId, input.worktreeId), isNull(nodes.deletingAt)))
 					.get();
 				if (existingNode) {
-					throw new Error("Worktree already has an active node");
+					throw new CaspianError({
+						code: "CONFLICT",
+						message: "Worktree already has an active node",
+					});
 				}
 
 				const repository = getRepository(worktree.repositoryId);
 				if (!repository) {
-					throw new Error(`Repository ${worktree.repositoryId} not found`);
+					throw repositoryNotFound(worktree.repositoryId);
 				}
 
 				const exists = await worktreeExists(repository.mainRepoPath, worktree.path);
 				if (!exists) {
-					throw new Error("Worktree no longer exists on disk");
+					throw new CaspianError({
+						code: "WORKTREE_MISSING",
+						message: "Worktree no longer exists on disk",
+					});
 				}
 
 				const maxTabOrder = getMaxNodeTabOrder(worktree.repositoryId);
@@ -629,14 +828,15 @@ export const createCreateProcedures = () => {
 			.mutation(async ({ input }) => {
 				const repository = getRepository(input.repositoryId);
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
 				const health = checkRepositoryHealth({ mainRepoPath: repository.mainRepoPath });
 				if (!health.healthy) {
-					throw new TRPCError({
-						code: "PRECONDITION_FAILED",
+					throw new CaspianError({
+						code: "PATH_NOT_FOUND",
 						message: "Repository directory not found on disk",
+						hint: "Relocate the repository, or remove it if it was deleted",
 					});
 				}
 
@@ -651,6 +851,7 @@ export const createCreateProcedures = () => {
 					owner: parsed.owner,
 					repo: parsed.repo,
 					prNumber: parsed.number,
+					repoPath: repository.mainRepoPath,
 				});
 
 				const localBranchName = getPrLocalBranchName(prInfo);
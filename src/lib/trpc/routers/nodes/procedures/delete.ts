@@ -1,9 +1,16 @@
 import type { SelectWorktree } from "lib/local-db";
 import { track } from "main/lib/analytics";
+import { recordAuditEvent } from "main/lib/audit";
+import { changesCache } from "main/lib/changes-cache";
+import { devServerManager } from "main/lib/dev-server-manager";
+import { getNodeHookParams, runNodePostHook, runNodePreHook } from "main/lib/node-hooks";
 import { nodeInitManager } from "main/lib/node-init-manager";
 import { getNodeRuntimeRegistry } from "main/lib/node-runtime";
+import { dispatchWebhookEvent } from "main/lib/webhooks";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
+import { nodeNotFound } from "../../../errors";
+import { recordWorktreeChurn } from "../../repositories/utils/maintenance";
 import {
 	clearNodeDeletingStatus,
 	deleteNode,
@@ -21,7 +28,10 @@ import {
 	removeWorktree,
 	worktreeExists,
 } from "../utils/git";
+import { transitionIssueOnClose } from "../utils/issues";
+import { deleteSnapshotRefs } from "../utils/snapshots";
 import { runTeardown } from "../utils/teardown";
+import { getNodePath } from "../utils/worktree";
 
 export const createDeleteProcedures = () => {
 	return router({
@@ -138,6 +148,15 @@ export const createDeleteProcedures = () => {
 				return { success: false, error: "Node not found" };
 			}
 
+			// Blocks deletion before anything is torn down
+			try {
+				await runNodePreHook({ hook: "pre-node-delete", nodeId: input.id });
+			} catch (error) {
+				const errorMessage = error instanceof Error ? error.message : String(error);
+				return { success: false, error: errorMessage };
+			}
+			const hookParams = getNodeHookParams(input.id);
+
 			markNodeAsDeleting(input.id);
 			updateActiveNodeIfRemoved(input.id);
 
@@ -163,6 +182,11 @@ export const createDeleteProcedures = () => {
 				.getForNodeId(input.id)
 				.terminal.killByWorkspaceId(input.id);
 
+			await devServerManager.remove(input.id);
+
+			const nodePath = getNodePath(node);
+			if (nodePath) changesCache.stopWatching(nodePath);
+
 			const repository = getRepository(node.repositoryId);
 
 			let worktree: SelectWorktree | undefined;
@@ -192,6 +216,13 @@ export const createDeleteProcedures = () => {
 						try {
 							if (exists) {
 								await removeWorktree(repository.mainRepoPath, worktree.path);
+								recordAuditEvent({
+									type: "worktree_removed",
+									nodeId: node.id,
+									repositoryId: repository.id,
+									summary: `Removed worktree on ${worktree.branch}`,
+									data: { worktreePath: worktree.path, branch: worktree.branch },
+								});
 							} else {
 								console.warn(`Worktree ${worktree.path} not found in git, skipping removal`);
 							}
@@ -210,6 +241,10 @@ export const createDeleteProcedures = () => {
 				}
 			}
 
+			if (repository) {
+				await deleteSnapshotRefs(repository.mainRepoPath, input.id);
+			}
+
 			deleteNode(input.id);
 
 			if (worktree) {
@@ -218,6 +253,9 @@ export const createDeleteProcedures = () => {
 
 			if (repository) {
 				hideRepositoryIfNoNodes(node.repositoryId);
+				if (worktree) {
+					recordWorktreeChurn(repository.id);
+				}
 			}
 
 			const terminalWarning =
@@ -226,6 +264,14 @@ export const createDeleteProcedures = () => {
 					: undefined;
 
 			track("node_deleted", { node_id: input.id });
+			dispatchWebhookEvent({
+				type: "node_deleted",
+				nodeId: input.id,
+				nodeName: node.name,
+				repositoryName: repository?.name,
+			});
+			runNodePostHook("post-node-delete", hookParams);
+			void transitionIssueOnClose(node);
 
 			// Clear after cleanup so cancellation signals remain visible during deletion
 			nodeInitManager.clearJob(input.id);
@@ -237,13 +283,18 @@ export const createDeleteProcedures = () => {
 			const node = getNode(input.id);
 
 			if (!node) {
-				throw new Error("Node not found");
+				throw nodeNotFound(input.id);
 			}
 
 			const terminalResult = await getNodeRuntimeRegistry()
 				.getForNodeId(input.id)
 				.terminal.killByWorkspaceId(input.id);
 
+			await devServerManager.remove(input.id);
+
+			const nodePath = getNodePath(node);
+			if (nodePath) changesCache.stopWatching(nodePath);
+
 			deleteNode(input.id); // keeps worktree on disk
 			hideRepositoryIfNoNodes(node.repositoryId);
 			updateActiveNodeIfRemoved(input.id);
@@ -254,6 +305,7 @@ export const createDeleteProcedures = () => {
 					: undefined;
 
 			track("node_closed", { node_id: input.id });
+			void transitionIssueOnClose(node);
 
 			return { success: true, terminalWarning };
 		}),
@@ -356,6 +408,8 @@ export const createDeleteProcedures = () => {
 					return { success: false, error: "Repository not found" };
 				}
 
+				changesCache.stopWatching(worktree.path);
+
 				// Acquire repository lock to prevent racing with concurrent operations
 				await nodeInitManager.acquireRepositoryLock(repository.id);
 
@@ -379,6 +433,12 @@ export const createDeleteProcedures = () => {
 					try {
 						if (exists) {
 							await removeWorktree(repository.mainRepoPath, worktree.path);
+							recordAuditEvent({
+								type: "worktree_removed",
+								repositoryId: repository.id,
+								summary: `Removed worktree on ${worktree.branch}`,
+								data: { worktreePath: worktree.path, branch: worktree.branch },
+							});
 						} else {
 							console.warn(`Worktree ${worktree.path} not found in git, skipping removal`);
 						}
@@ -396,6 +456,7 @@ export const createDeleteProcedures = () => {
 
 				deleteWorktreeRecord(input.worktreeId);
 				hideRepositoryIfNoNodes(worktree.repositoryId);
+				recordWorktreeChurn(worktree.repositoryId);
 
 				track("worktree_deleted", { worktree_id: input.worktreeId });
 
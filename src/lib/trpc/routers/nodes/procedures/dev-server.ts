@@ -0,0 +1,109 @@
+import { observable } from "@trpc/server/observable";
+import { devServerManager } from "main/lib/dev-server-manager";
+import { getRepositoryEnv } from "main/lib/secrets/repository-env";
+import type { DevServerEvent, DevServerLogLine, DevServerStatus } from "shared/types/dev-server";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeHasNoWorkingDirectory, nodeNotFound } from "../../../errors";
+import { getNodeWithRelations } from "../utils/db-helpers";
+import { loadSetupConfig } from "../utils/setup";
+import { getShellEnvironment } from "../utils/shell-env";
+import { getNodePath } from "../utils/worktree";
+
+export const createDevServerProcedures = () => {
+	return router({
+		/**
+		 * Runs the repository's `dev` command from .caspian/config.json in the
+		 * node's worktree, on a port of its own.
+		 */
+		startDevServer: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.mutation(async ({ input }): Promise<DevServerStatus> => {
+				const relations = getNodeWithRelations(input.nodeId);
+				if (!relations || relations.node.deletingAt || !relations.repository) {
+					throw nodeNotFound(input.nodeId);
+				}
+
+				const { node, repository } = relations;
+				const cwd = getNodePath(node);
+				if (!cwd) {
+					throw nodeHasNoWorkingDirectory(input.nodeId);
+				}
+
+				const config = loadSetupConfig(repository.mainRepoPath)?.dev;
+				if (!config?.command) {
+					throw new CaspianError({
+						code: "CONFIG_MISSING",
+						message: "No dev command configured",
+						hint: "Add `dev.command` to .caspian/config.json",
+					});
+				}
+
+				try {
+					return await devServerManager.start({
+						nodeId: node.id,
+						command: config.command,
+						cwd,
+						env: { ...(await getShellEnvironment()), ...getRepositoryEnv(repository.id) },
+						portEnv: config.portEnv,
+						basePort: config.basePort,
+					});
+				} catch (error) {
+					throw new CaspianError({
+						code: "CONFLICT",
+						message: error instanceof Error ? error.message : String(error),
+					});
+				}
+			}),
+
+		stopDevServer: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.mutation(async ({ input }) => {
+				await devServerManager.stop(input.nodeId);
+				return { success: true };
+			}),
+
+		getDevServer: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.query(({ input }): DevServerStatus | null => {
+				return devServerManager.getStatus(input.nodeId) ?? null;
+			}),
+
+		getDevServerLogs: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.query(({ input }): DevServerLogLine[] => {
+				return devServerManager.getLogs(input.nodeId);
+			}),
+
+		/** Status changes and log lines; the current status is sent first */
+		onDevServerEvents: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.subscription(({ input }) => {
+				return observable<DevServerEvent>((emit) => {
+					const onStatus = (status: DevServerStatus) => {
+						if (status.nodeId === input.nodeId) {
+							emit.next({ type: "status", status });
+						}
+					};
+					const onLog = (line: DevServerLogLine) => {
+						if (line.nodeId === input.nodeId) {
+							emit.next({ type: "log", line });
+						}
+					};
+
+					const current = devServerManager.getStatus(input.nodeId);
+					if (current) {
+						emit.next({ type: "status", status: current });
+					}
+
+					devServerManager.on("status", onStatus);
+					devServerManager.on("log", onLog);
+
+					return () => {
+						devServerManager.off("status", onStatus);
+						devServerManager.off("log", onLog);
+					};
+				});
+			}),
+	});
+};
@@ -0,0 +1,92 @@
+import { observable } from "@trpc/server/observable";
+import { qualityGateManager } from "main/lib/quality-gate-manager";
+import type { QualityGateProgress } from "shared/types/quality-gate";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeHasNoWorkingDirectory, nodeNotFound } from "../../../errors";
+import { getNodeWithRelations } from "../utils/db-helpers";
+import { GateInProgressError, getLatestGateRun, runQualityGate } from "../utils/quality-gate";
+import { hasGateSteps } from "../utils/quality-gate-steps";
+import { loadSetupConfig } from "../utils/setup";
+import { getNodePath } from "../utils/worktree";
+
+function loadNodeGate(nodeId: string) {
+	const relations = getNodeWithRelations(nodeId);
+	if (!relations || relations.node.deletingAt || !relations.repository) {
+		throw nodeNotFound(nodeId);
+	}
+
+	const { node, repository } = relations;
+	const cwd = getNodePath(node);
+	if (!cwd) {
+		throw nodeHasNoWorkingDirectory(nodeId);
+	}
+
+	const config = loadSetupConfig(repository.mainRepoPath)?.gate;
+	return { node, cwd, config };
+}
+
+export const createGateProcedures = () => {
+	return router({
+		getGateConfig: publicProcedure.input(z.object({ nodeId: z.string() })).query(({ input }) => {
+			const { node, config } = loadNodeGate(input.nodeId);
+			return {
+				config: config ?? null,
+				// Scoped nodes always have the scope check to run
+				configured: hasGateSteps(config) || !!node.scopePath,
+				latestRun: getLatestGateRun(input.nodeId) ?? null,
+			};
+		}),
+
+		runGate: publicProcedure.input(z.object({ nodeId: z.string() })).mutation(async ({ input }) => {
+			const { node, cwd, config } = loadNodeGate(input.nodeId);
+			if (!hasGateSteps(config) && !node.scopePath) {
+				throw new CaspianError({
+					code: "CONFIG_MISSING",
+					message: "No gate commands configured",
+					hint: "Add lint, test or build commands under `gate` in .caspian/config.json",
+				});
+			}
+
+			try {
+				return await runQualityGate({ nodeId: node.id, cwd, config: config ?? {} });
+			} catch (error) {
+				if (error instanceof GateInProgressError) {
+					throw new CaspianError({ code: "OPERATION_IN_PROGRESS", message: error.message });
+				}
+				throw error;
+			}
+		}),
+
+		getLatestGateRun: publicProcedure.input(z.object({ nodeId: z.string() })).query(({ input }) => {
+			return getLatestGateRun(input.nodeId) ?? null;
+		}),
+
+		onGateProgress: publicProcedure
+			.input(z.object({ nodeIds: z.array(z.string()).optional() }).optional())
+			.subscription(({ input }) => {
+				return observable<QualityGateProgress>((emit) => {
+					const matches = (progress: QualityGateProgress) =>
+						!input?.nodeIds || input.nodeIds.includes(progress.nodeId);
+
+					const handler = (progress: QualityGateProgress) => {
+						if (matches(progress)) {
+							emit.next(progress);
+						}
+					};
+
+					for (const progress of qualityGateManager.getAllProgress()) {
+						if (matches(progress)) {
+							emit.next(progress);
+						}
+					}
+
+					qualityGateManager.on("progress", handler);
+
+					return () => {
+						qualityGateManager.off("progress", handler);
+					};
+				});
+			}),
+	});
+};
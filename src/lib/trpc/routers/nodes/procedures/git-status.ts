@@ -1,8 +1,12 @@
 import { and, eq, isNull } from "drizzle-orm";
 import { nodes, worktrees } from "lib/local-db";
+import { recordAuditEvent } from "main/lib/audit";
 import { localDb } from "main/lib/local-db";
+import { getPathBasename } from "main/lib/platform-paths";
+import { isLocalOnlyMode } from "main/lib/privacy";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeNotFound, repositoryNotFound } from "../../../errors";
 import {
 	getNode,
 	getRepository,
@@ -17,7 +21,8 @@ import {
 	getDefaultBranch,
 	refreshDefaultBranch,
 } from "../utils/git";
-import { fetchGitHubPRStatus } from "../utils/github";
+import { fetchPRStatus } from "../utils/hosting";
+import { describePullRequestChange } from "../utils/hosting/activity";
 
 export const createGitStatusProcedures = () => {
 	return router({
@@ -26,17 +31,20 @@ export const createGitStatusProcedures = () => {
 			.mutation(async ({ input }) => {
 				const node = getNode(input.nodeId);
 				if (!node) {
-					throw new Error(`Node ${input.nodeId} not found`);
+					throw nodeNotFound(input.nodeId);
 				}
 
 				const worktree = node.worktreeId ? getWorktree(node.worktreeId) : null;
 				if (!worktree) {
-					throw new Error(`Worktree for node ${input.nodeId} not found`);
+					throw new CaspianError({
+						code: "WORKTREE_NOT_FOUND",
+						message: `Worktree for node ${input.nodeId} not found`,
+					});
 				}
 
 				const repository = getRepository(node.repositoryId);
 				if (!repository) {
-					throw new Error(`Repository ${node.repositoryId} not found`);
+					throw repositoryNotFound(node.repositoryId);
 				}
 
 				// Sync with remote in case the default branch changed (e.g. master -> main)
@@ -110,7 +118,12 @@ export const createGitStatusProcedures = () => {
 					);
 				}
 
-				const freshStatus = await fetchGitHubPRStatus(worktree.path);
+				// Local-only mode keeps showing the last status instead of asking the host
+				if (isLocalOnlyMode()) {
+					return { status: worktree.githubStatus ?? null, branchRenamed };
+				}
+
+				const freshStatus = await fetchPRStatus(worktree.path);
 
 				if (freshStatus) {
 					localDb
@@ -118,6 +131,16 @@ export const createGitStatusProcedures = () => {
 						.set({ githubStatus: freshStatus })
 						.where(eq(worktrees.id, worktree.id))
 						.run();
+
+					const change = describePullRequestChange(worktree.githubStatus ?? null, freshStatus);
+					if (change) {
+						recordAuditEvent({
+							...change,
+							nodeId: node.id,
+							repositoryId: node.repositoryId,
+							actor: "system",
+						});
+					}
 				}
 
 				return { status: freshStatus, branchRenamed };
@@ -135,7 +158,7 @@ export const createGitStatusProcedures = () => {
 			}
 
 			// Extract worktree name from path (last segment)
-			const worktreeName = worktree.path.split("/").pop() ?? worktree.branch;
+			const worktreeName = getPathBasename(worktree.path) || worktree.branch;
 
 			return {
 				worktreeName,
@@ -6,7 +6,7 @@ import type { NodeInitProgress } from "shared/types/node-init";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
 import { getNodeWithRelations, getRepository } from "../utils/db-helpers";
-import { initializeNodeWorktree } from "../utils/node-init";
+import { retryNodeInit } from "../utils/node-init";
 import { loadSetupConfig } from "../utils/setup";
 
 function getDefaultPreset() {
@@ -46,41 +46,7 @@ export const createInitProcedures = () => {
 		retryInit: publicProcedure
 			.input(z.object({ nodeId: z.string() }))
 			.mutation(async ({ input }) => {
-				const relations = getNodeWithRelations(input.nodeId);
-
-				if (!relations) {
-					throw new Error("Node not found");
-				}
-
-				const { node, worktree, repository } = relations;
-
-				if (node.deletingAt) {
-					throw new Error("Cannot retry initialization on a node being deleted");
-				}
-
-				if (!worktree) {
-					throw new Error("Worktree not found");
-				}
-
-				if (!repository) {
-					throw new Error("Repository not found");
-				}
-
-				nodeInitManager.clearJob(input.nodeId);
-				nodeInitManager.startJob(input.nodeId, node.repositoryId);
-
-				// baseBranch is treated as explicit on retry to prevent further auto-correction
-				initializeNodeWorktree({
-					nodeId: input.nodeId,
-					repositoryId: node.repositoryId,
-					worktreeId: worktree.id,
-					worktreePath: worktree.path,
-					branch: worktree.branch,
-					baseBranch: worktree.baseBranch ?? repository.defaultBranch ?? "main",
-					baseBranchWasExplicit: true,
-					mainRepoPath: repository.mainRepoPath,
-				});
-
+				retryNodeInit(input.nodeId);
 				return { success: true };
 			}),
 
@@ -0,0 +1,68 @@
+import { eq } from "drizzle-orm";
+import { nodes } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import type { IssueDetails } from "shared/types/issues";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { nodeNotFound, repositoryNotFound } from "../../../errors";
+import { getNodeWithRelations, getRepository } from "../utils/db-helpers";
+import { fetchIssue, toIssueLink } from "../utils/issues";
+
+function loadNodeRepository(nodeId: string) {
+	const relations = getNodeWithRelations(nodeId);
+	if (!relations || relations.node.deletingAt || !relations.repository) {
+		throw nodeNotFound(nodeId);
+	}
+	return relations;
+}
+
+export const createIssueProcedures = () => {
+	return router({
+		/** Fresh details for the node's linked issue, or null when none is linked */
+		getIssue: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.query(async ({ input }): Promise<IssueDetails | null> => {
+				const { node, repository } = loadNodeRepository(input.nodeId);
+				if (!node.issue) return null;
+				return fetchIssue(repository.mainRepoPath, node.issue.key);
+			}),
+
+		/** Looks up an issue by key or URL before creating a node from it */
+		previewIssue: publicProcedure
+			.input(z.object({ repositoryId: z.string(), reference: z.string().min(1) }))
+			.query(async ({ input }): Promise<IssueDetails> => {
+				const repository = getRepository(input.repositoryId);
+				if (!repository) {
+					throw repositoryNotFound(input.repositoryId);
+				}
+				return fetchIssue(repository.mainRepoPath, input.reference);
+			}),
+
+		linkIssue: publicProcedure
+			.input(z.object({ nodeId: z.string(), reference: z.string().min(1) }))
+			.mutation(async ({ input }): Promise<IssueDetails> => {
+				const { node, repository } = loadNodeRepository(input.nodeId);
+				const issue = await fetchIssue(repository.mainRepoPath, input.reference);
+
+				localDb
+					.update(nodes)
+					.set({ issue: toIssueLink(issue), updatedAt: Date.now() })
+					.where(eq(nodes.id, node.id))
+					.run();
+
+				return issue;
+			}),
+
+		unlinkIssue: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.mutation(({ input }) => {
+				const { node } = loadNodeRepository(input.nodeId);
+				localDb
+					.update(nodes)
+					.set({ issue: null, updatedAt: Date.now() })
+					.where(eq(nodes.id, node.id))
+					.run();
+				return { success: true };
+			}),
+	});
+};
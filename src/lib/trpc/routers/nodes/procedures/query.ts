@@ -1,10 +1,10 @@
 import { existsSync } from "node:fs";
-import { TRPCError } from "@trpc/server";
 import { eq, isNotNull, isNull } from "drizzle-orm";
 import { nodes, repositories, worktrees } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
+import { nodeNotFound } from "../../../errors";
 import { checkRepositoryHealth } from "../../repositories/utils/health";
 import { getNode } from "../utils/db-helpers";
 import { detectBaseBranch, hasOriginRemote } from "../utils/git";
@@ -41,10 +41,7 @@ export const createQueryProcedures = () => {
 		get: publicProcedure.input(z.object({ id: z.string() })).query(async ({ input }) => {
 			const node = getNode(input.id);
 			if (!node) {
-				throw new TRPCError({
-					code: "NOT_FOUND",
-					message: `Node ${input.id} not found`,
-				});
+				throw nodeNotFound(input.id);
 			}
 
 			const repository = localDb
@@ -0,0 +1,14 @@
+import { dismissRestoreReport, getRestoreReport } from "main/lib/restore-report";
+import { publicProcedure, router } from "../../..";
+
+export const createRestoreProcedures = () => {
+	return router({
+		/** Nodes the previous session left with interrupted agents, prompts or uncommitted work */
+		getRestoreReport: publicProcedure.query(() => getRestoreReport()),
+
+		dismissRestoreReport: publicProcedure.mutation(async () => {
+			await dismissRestoreReport();
+			return { success: true };
+		}),
+	});
+};
@@ -0,0 +1,96 @@
+import { and, asc, eq, isNull } from "drizzle-orm";
+import { REVIEW_COMMENT_SIDES, reviewComments } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeNotFound } from "../../../errors";
+import { getNodeNotDeleting } from "../utils/db-helpers";
+import { formatReviewCommentsPrompt } from "../utils/review-prompt";
+
+function getOpenComments(nodeId: string) {
+	return localDb
+		.select()
+		.from(reviewComments)
+		.where(and(eq(reviewComments.nodeId, nodeId), isNull(reviewComments.resolvedAt)))
+		.orderBy(asc(reviewComments.filePath), asc(reviewComments.line))
+		.all();
+}
+
+export const createReviewProcedures = () => {
+	return router({
+		addReviewComment: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					filePath: z.string().min(1),
+					line: z.number().int().positive(),
+					side: z.enum(REVIEW_COMMENT_SIDES).default("new"),
+					body: z.string().trim().min(1),
+				}),
+			)
+			.mutation(({ input }) => {
+				const node = getNodeNotDeleting(input.nodeId);
+				if (!node) {
+					throw nodeNotFound(input.nodeId);
+				}
+
+				return localDb.insert(reviewComments).values(input).returning().get();
+			}),
+
+		listReviewComments: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					includeResolved: z.boolean().optional(),
+				}),
+			)
+			.query(({ input }) => {
+				if (!input.includeResolved) {
+					return getOpenComments(input.nodeId);
+				}
+
+				return localDb
+					.select()
+					.from(reviewComments)
+					.where(eq(reviewComments.nodeId, input.nodeId))
+					.orderBy(asc(reviewComments.filePath), asc(reviewComments.line))
+					.all();
+			}),
+
+		resolveReviewComment: publicProcedure
+			.input(z.object({ id: z.string(), resolved: z.boolean().default(true) }))
+			.mutation(({ input }) => {
+				const now = Date.now();
+				const updated = localDb
+					.update(reviewComments)
+					.set({ resolvedAt: input.resolved ? now : null, updatedAt: now })
+					.where(eq(reviewComments.id, input.id))
+					.returning()
+					.get();
+
+				if (!updated) {
+					throw new CaspianError({
+						code: "NOT_FOUND",
+						message: `Review comment ${input.id} not found`,
+					});
+				}
+				return updated;
+			}),
+
+		deleteReviewComment: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(({ input }) => {
+				localDb.delete(reviewComments).where(eq(reviewComments.id, input.id)).run();
+				return { success: true };
+			}),
+
+		/** Open comments formatted for pasting into the node's agent session */
+		getReviewPrompt: publicProcedure.input(z.object({ nodeId: z.string() })).query(({ input }) => {
+			const comments = getOpenComments(input.nodeId);
+			return {
+				count: comments.length,
+				prompt: formatReviewCommentsPrompt(comments),
+			};
+		}),
+	});
+};
@@ -0,0 +1,72 @@
+import { eq } from "drizzle-orm";
+import { nodeSnapshots, type SelectNode } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeHasNoWorkingDirectory, nodeNotFound } from "../../../errors";
+import { getNodeNotDeleting } from "../utils/db-helpers";
+import { createSnapshot, deleteSnapshot, listSnapshots, restoreSnapshot } from "../utils/snapshots";
+import { getNodePath } from "../utils/worktree";
+
+function getNodeCwd(nodeId: string): { node: SelectNode; cwd: string } {
+	const node = getNodeNotDeleting(nodeId);
+	if (!node) {
+		throw nodeNotFound(nodeId);
+	}
+
+	const cwd = getNodePath(node);
+	if (!cwd) {
+		throw nodeHasNoWorkingDirectory(nodeId);
+	}
+
+	return { node, cwd };
+}
+
+function getSnapshot(id: string) {
+	const snapshot = localDb.select().from(nodeSnapshots).where(eq(nodeSnapshots.id, id)).get();
+	if (!snapshot) {
+		throw new CaspianError({ code: "NOT_FOUND", message: `Snapshot ${id} not found` });
+	}
+	return snapshot;
+}
+
+export const createSnapshotsProcedures = () => {
+	return router({
+		snapshotNode: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					label: z.string().trim().min(1).max(200),
+				}),
+			)
+			.mutation(async ({ input }) => {
+				const { node, cwd } = getNodeCwd(input.nodeId);
+				return createSnapshot({ nodeId: node.id, cwd, label: input.label });
+			}),
+
+		listSnapshots: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.query(({ input }) => listSnapshots(input.nodeId)),
+
+		/**
+		 * Restores a snapshot, discarding commits and changes made since. The
+		 * pre-restore state is saved as a new snapshot and returned as `backup`.
+		 */
+		restoreSnapshot: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(async ({ input }) => {
+				const snapshot = getSnapshot(input.id);
+				const { cwd } = getNodeCwd(snapshot.nodeId);
+				return restoreSnapshot({ snapshot, cwd });
+			}),
+
+		deleteSnapshot: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(async ({ input }) => {
+				const snapshot = getSnapshot(input.id);
+				const { cwd } = getNodeCwd(snapshot.nodeId);
+				await deleteSnapshot({ snapshot, cwd });
+				return { success: true };
+			}),
+	});
+};
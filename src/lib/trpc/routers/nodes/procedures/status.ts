@@ -1,10 +1,21 @@
-import { and, eq, isNull } from "drizzle-orm";
-import { nodes } from "lib/local-db";
+import { and, eq, gt, isNull, sql } from "drizzle-orm";
+import { auditEvents, nodes } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
+import { updateAppBadge } from "main/lib/notifications/badge";
 import { z } from "zod";
 import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeNotFound } from "../../../errors";
 import { getNodeNotDeleting, setLastActiveNode, touchNode } from "../utils/db-helpers";
 
+function markNodeViewed(nodeId: string): void {
+	localDb
+		.update(nodes)
+		.set({ isUnread: false, lastViewedAt: Date.now() })
+		.where(eq(nodes.id, nodeId))
+		.run();
+	updateAppBadge();
+}
+
 export const createStatusProcedures = () => {
 	return router({
 		reorder: publicProcedure
@@ -31,7 +42,10 @@ export const createStatusProcedures = () => {
 					toIndex < 0 ||
 					toIndex >= repositoryNodes.length
 				) {
-					throw new Error("Invalid fromIndex or toIndex");
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: "Invalid fromIndex or toIndex",
+					});
 				}
 
 				const [removed] = repositoryNodes.splice(fromIndex, 1);
@@ -60,7 +74,7 @@ export const createStatusProcedures = () => {
 			.mutation(({ input }) => {
 				const node = getNodeNotDeleting(input.id);
 				if (!node) {
-					throw new Error(`Node ${input.id} not found or is being deleted`);
+					throw nodeNotFound(input.id);
 				}
 
 				touchNode(input.id, {
@@ -75,10 +89,15 @@ export const createStatusProcedures = () => {
 			.mutation(({ input }) => {
 				const node = getNodeNotDeleting(input.id);
 				if (!node) {
-					throw new Error(`Node ${input.id} not found or is being deleted`);
+					throw nodeNotFound(input.id);
 				}
 
-				localDb.update(nodes).set({ isUnread: input.isUnread }).where(eq(nodes.id, input.id)).run();
+				if (input.isUnread) {
+					localDb.update(nodes).set({ isUnread: true }).where(eq(nodes.id, input.id)).run();
+					updateAppBadge();
+				} else {
+					markNodeViewed(input.id);
+				}
 
 				return { success: true, isUnread: input.isUnread };
 			}),
@@ -86,12 +105,46 @@ export const createStatusProcedures = () => {
 		setActive: publicProcedure.input(z.object({ nodeId: z.string() })).mutation(({ input }) => {
 			const node = getNodeNotDeleting(input.nodeId);
 			if (!node) {
-				throw new Error(`Node ${input.nodeId} not found or is being deleted`);
+				throw nodeNotFound(input.nodeId);
 			}
 
 			setLastActiveNode(input.nodeId);
+			markNodeViewed(input.nodeId);
 
 			return { success: true, nodeId: input.nodeId };
 		}),
+
+		markViewed: publicProcedure.input(z.object({ id: z.string() })).mutation(({ input }) => {
+			const node = getNodeNotDeleting(input.id);
+			if (!node) {
+				throw nodeNotFound(input.id);
+			}
+
+			markNodeViewed(input.id);
+
+			return { success: true };
+		}),
+
+		/**
+		 * Agent events recorded since each node was last viewed, keyed by node id.
+		 * Nodes with nothing unread are omitted.
+		 */
+		getUnreadCounts: publicProcedure.query((): Record<string, number> => {
+			const rows = localDb
+				.select({ nodeId: nodes.id, count: sql<number>`count(*)` })
+				.from(auditEvents)
+				.innerJoin(nodes, eq(auditEvents.nodeId, nodes.id))
+				.where(
+					and(
+						eq(auditEvents.actor, "agent"),
+						isNull(nodes.deletingAt),
+						gt(auditEvents.timestamp, sql`coalesce(${nodes.lastViewedAt}, ${nodes.createdAt})`),
+					),
+				)
+				.groupBy(nodes.id)
+				.all();
+
+			return Object.fromEntries(rows.map((row) => [row.nodeId, row.count]));
+		}),
 	});
 };
@@ -0,0 +1,131 @@
+import { and, desc, eq, isNotNull } from "drizzle-orm";
+import { testRuns } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import type { NodeCoverage } from "shared/types/test-runs";
+import { z } from "zod";
+import { publicProcedure, router } from "../../..";
+import { CaspianError, nodeHasNoWorkingDirectory, nodeNotFound } from "../../../errors";
+import { coveragePercent, diffCoverage } from "../utils/coverage";
+import { getBranchNode, getNodeNotDeleting, getRepository } from "../utils/db-helpers";
+import { loadSetupConfig } from "../utils/setup";
+import { executeTestRun } from "../utils/test-run";
+import { getNodePath } from "../utils/worktree";
+
+function getLatestCoverageRun(nodeId: string) {
+	return localDb
+		.select()
+		.from(testRuns)
+		.where(and(eq(testRuns.nodeId, nodeId), isNotNull(testRuns.coverage)))
+		.orderBy(desc(testRuns.createdAt))
+		.limit(1)
+		.get();
+}
+
+export const createTestsProcedures = () => {
+	return router({
+		runTests: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					// Falls back to defaults.testCommand, then gate.test, from .caspian/config.json
+					command: z.string().trim().min(1).optional(),
+					timeoutMs: z.number().int().positive().optional(),
+				}),
+			)
+			.mutation(async ({ input }) => {
+				const node = getNodeNotDeleting(input.nodeId);
+				if (!node) {
+					throw nodeNotFound(input.nodeId);
+				}
+
+				const cwd = getNodePath(node);
+				if (!cwd) {
+					throw nodeHasNoWorkingDirectory(input.nodeId);
+				}
+
+				const repository = getRepository(node.repositoryId);
+				const config = repository ? loadSetupConfig(repository.mainRepoPath) : null;
+				const command = input.command ?? config?.defaults?.testCommand ?? config?.gate?.test;
+				if (!command) {
+					throw new CaspianError({
+						code: "CONFIG_MISSING",
+						message: "No test command given",
+						hint: "Add `defaults.testCommand` to .caspian/config.json",
+					});
+				}
+
+				return executeTestRun({
+					nodeId: node.id,
+					command,
+					cwd,
+					timeoutMs: input.timeoutMs,
+				});
+			}),
+
+		listTestRuns: publicProcedure
+			.input(
+				z.object({
+					nodeId: z.string(),
+					limit: z.number().int().positive().max(100).default(20),
+				}),
+			)
+			.query(({ input }) => {
+				// Results and output are omitted from the list; fetch a single run for details
+				return localDb
+					.select({
+						id: testRuns.id,
+						command: testRuns.command,
+						framework: testRuns.framework,
+						status: testRuns.status,
+						exitCode: testRuns.exitCode,
+						summary: testRuns.summary,
+						durationMs: testRuns.durationMs,
+						createdAt: testRuns.createdAt,
+					})
+					.from(testRuns)
+					.where(eq(testRuns.nodeId, input.nodeId))
+					.orderBy(desc(testRuns.createdAt))
+					.limit(input.limit)
+					.all();
+			}),
+
+		/**
+		 * Latest coverage for a node with per-file deltas against the repository's
+		 * branch node (the main checkout), when that has a coverage report too.
+		 */
+		getNodeCoverage: publicProcedure
+			.input(z.object({ nodeId: z.string() }))
+			.query(({ input }): NodeCoverage | null => {
+				const node = getNodeNotDeleting(input.nodeId);
+				if (!node) {
+					throw nodeNotFound(input.nodeId);
+				}
+
+				const run = getLatestCoverageRun(node.id);
+				if (!run?.coverage) {
+					return null;
+				}
+
+				const branchNode = node.type === "branch" ? null : getBranchNode(node.repositoryId);
+				const baseRun = branchNode ? getLatestCoverageRun(branchNode.id) : undefined;
+				const baseFiles = baseRun?.coverage ?? [];
+
+				return {
+					testRunId: run.id,
+					reportPath: run.coverageReportPath ?? "",
+					percent: coveragePercent(run.coverage),
+					basePercent: baseRun?.coverage ? coveragePercent(baseRun.coverage) : null,
+					baseTestRunId: baseRun?.id ?? null,
+					files: diffCoverage(run.coverage, baseFiles),
+				};
+			}),
+
+		getTestRun: publicProcedure.input(z.object({ id: z.string() })).query(({ input }) => {
+			const run = localDb.select().from(testRuns).where(eq(testRuns.id, input.id)).get();
+			if (!run) {
+				throw new CaspianError({ code: "NOT_FOUND", message: `Test run ${input.id} not found` });
+			}
+			return run;
+		}),
+	});
+};
@@ -0,0 +1,56 @@
+import { type SelectRepository, settings } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { renderBranchTemplate } from "shared/utils/branch";
+import { getBranchPrefix, getGitAuthorName, sanitizeAuthorPrefix } from "./git";
+import { loadSetupConfig } from "./setup";
+
+/**
+ * Builds the function that turns a slug into a full branch name for the
+ * repository. The branch template from .caspian/config.json wins; otherwise
+ * the slug gets the branch prefix from config or settings, dropped when it
+ * would collide with an existing branch name.
+ */
+export async function getBranchNameFormatter({
+	repository,
+	existingBranches,
+	ticket,
+}: {
+	repository: SelectRepository;
+	existingBranches: string[];
+	ticket?: string;
+}): Promise<(slug: string) => string> {
+	const defaults = loadSetupConfig(repository.mainRepoPath)?.defaults;
+
+	const template = defaults?.branchTemplate?.trim();
+	if (template) {
+		const user = template.includes("{user}")
+			? await getGitAuthorName(repository.mainRepoPath)
+			: null;
+		return (slug) => renderBranchTemplate(template, { user, ticket, slug });
+	}
+
+	const globalSettings = localDb.select().from(settings).get();
+	const repositoryOverrides = repository.branchPrefixMode != null;
+	const prefixMode = repositoryOverrides
+		? repository.branchPrefixMode
+		: (globalSettings?.branchPrefixMode ?? "none");
+	const customPrefix = repositoryOverrides
+		? repository.branchPrefixCustom
+		: globalSettings?.branchPrefixCustom;
+
+	// A prefix set in the repository's .caspian/config.json wins over settings
+	const rawPrefix =
+		defaults?.branchPrefix?.trim() ||
+		(await getBranchPrefix({
+			repoPath: repository.mainRepoPath,
+			mode: prefixMode,
+			customPrefix,
+		}));
+	const sanitizedPrefix = rawPrefix ? sanitizeAuthorPrefix(rawPrefix) : undefined;
+
+	const existingSet = new Set(existingBranches.map((b) => b.toLowerCase()));
+	const prefixWouldCollide = sanitizedPrefix && existingSet.has(sanitizedPrefix.toLowerCase());
+	const branchPrefix = prefixWouldCollide ? undefined : sanitizedPrefix;
+
+	return (slug) => (branchPrefix ? `${branchPrefix}/${slug}` : slug);
+}
@@ -0,0 +1,43 @@
+import { describe, expect, test } from "bun:test";
+import type { ChangedFile } from "shared/changes-types";
+import { compareChangedFiles, summarizeChangedFiles } from "./compare";
+
+const file = (path: string, additions = 1, deletions = 0): ChangedFile => ({
+	path,
+	status: "modified",
+	additions,
+	deletions,
+});
+
+describe("compareChangedFiles", () => {
+	test("splits files into only-A, only-B and shared buckets", () => {
+		const result = compareChangedFiles(
+			[file("src/b.ts"), file("src/shared.ts", 3, 1)],
+			[file("src/shared.ts", 10, 2), file("src/a.ts")],
+		);
+
+		expect(result.onlyInA.map((delta) => delta.path)).toEqual(["src/b.ts"]);
+		expect(result.onlyInB.map((delta) => delta.path)).toEqual(["src/a.ts"]);
+		expect(result.inBoth).toEqual([
+			{
+				path: "src/shared.ts",
+				a: { status: "modified", additions: 3, deletions: 1 },
+				b: { status: "modified", additions: 10, deletions: 2 },
+			},
+		]);
+	});
+
+	test("returns empty buckets when neither side changed anything", () => {
+		expect(compareChangedFiles([], [])).toEqual({ onlyInA: [], onlyInB: [], inBoth: [] });
+	});
+});
+
+describe("summarizeChangedFiles", () => {
+	test("sums additions and deletions", () => {
+		expect(summarizeChangedFiles([file("a", 2, 1), file("b", 5, 4)])).toEqual({
+			filesChanged: 2,
+			additions: 7,
+			deletions: 5,
+		});
+	});
+});
@@ -0,0 +1,69 @@
+import type { ChangedFile } from "shared/changes-types";
+
+export interface FileDelta {
+	path: string;
+	a: Pick<ChangedFile, "status" | "additions" | "deletions"> | null;
+	b: Pick<ChangedFile, "status" | "additions" | "deletions"> | null;
+}
+
+export interface FileOverlap {
+	onlyInA: FileDelta[];
+	onlyInB: FileDelta[];
+	inBoth: FileDelta[];
+}
+
+/**
+ * Buckets two attempts' changed files into files touched by only one side or by both.
+ * Results are sorted by path so reports are stable between calls.
+ */
+export function compareChangedFiles(filesA: ChangedFile[], filesB: ChangedFile[]): FileOverlap {
+	const toStat = ({ status, additions, deletions }: ChangedFile) => ({
+		status,
+		additions,
+		deletions,
+	});
+
+	const byPathA = new Map(filesA.map((file) => [file.path, file]));
+	const byPathB = new Map(filesB.map((file) => [file.path, file]));
+
+	const overlap: FileOverlap = { onlyInA: [], onlyInB: [], inBoth: [] };
+
+	for (const [path, fileA] of byPathA) {
+		const fileB = byPathB.get(path);
+		if (fileB) {
+			overlap.inBoth.push({ path, a: toStat(fileA), b: toStat(fileB) });
+		} else {
+			overlap.onlyInA.push({ path, a: toStat(fileA), b: null });
+		}
+	}
+
+	for (const [path, fileB] of byPathB) {
+		if (!byPathA.has(path)) {
+			overlap.onlyInB.push({ path, a: null, b: toStat(fileB) });
+		}
+	}
+
+	const byPath = (x: FileDelta, y: FileDelta) => x.path.localeCompare(y.path);
+	overlap.onlyInA.sort(byPath);
+	overlap.onlyInB.sort(byPath);
+	overlap.inBoth.sort(byPath);
+
+	return overlap;
+}
+
+/**
+ * Sums additions/deletions across a set of changed files.
+ */
+export function summarizeChangedFiles(files: ChangedFile[]): {
+	filesChanged: number;
+	additions: number;
+	deletions: number;
+} {
+	let additions = 0;
+	let deletions = 0;
+	for (const file of files) {
+		additions += file.additions;
+		deletions += file.deletions;
+	}
+	return { filesChanged: files.length, additions, deletions };
+}
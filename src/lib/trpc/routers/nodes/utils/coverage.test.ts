@@ -0,0 +1,74 @@
+import { describe, expect, test } from "bun:test";
+import { coveragePercent, diffCoverage, parseCobertura, parseLcov } from "./coverage";
+
+describe("parseLcov", () => {
+	test("counts DA records and relativizes absolute paths", () => {
+		const report = [
+			"TN:",
+			"SF:/repo/src/a.ts",
+			"DA:1,1",
+			"DA:2,0",
+			"DA:3,4",
+			"LF:3",
+			"LH:2",
+			"end_of_record",
+			"SF:src/b.ts",
+			"LF:10",
+			"LH:5",
+			"end_of_record",
+		].join("\n");
+
+		expect(parseLcov(report, "/repo")).toEqual([
+			{ path: "src/a.ts", linesFound: 3, linesHit: 2 },
+			{ path: "src/b.ts", linesFound: 10, linesHit: 5 },
+		]);
+	});
+});
+
+describe("parseCobertura", () => {
+	test("counts line hits per class filename", () => {
+		const report = `<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+	<sources><source>/repo</source></sources>
+	<packages><package name="app"><classes>
+		<class name="a" filename="app/a.py" line-rate="0.5">
+			<lines>
+				<line number="1" hits="1"/>
+				<line number="2" hits="0"/>
+			</lines>
+		</class>
+		<class name="a2" filename="app/a.py" line-rate="1">
+			<lines><line number="10" hits="3"/></lines>
+		</class>
+	</classes></package></packages>
+</coverage>`;
+
+		expect(parseCobertura(report, "/repo")).toEqual([
+			{ path: "app/a.py", linesFound: 3, linesHit: 2 },
+		]);
+	});
+});
+
+describe("coveragePercent", () => {
+	test("returns rounded percentage or null without lines", () => {
+		expect(coveragePercent([{ linesFound: 3, linesHit: 2 }])).toBe(66.67);
+		expect(coveragePercent([])).toBeNull();
+	});
+});
+
+describe("diffCoverage", () => {
+	test("computes per-file deltas against the base", () => {
+		const deltas = diffCoverage(
+			[
+				{ path: "b.ts", linesFound: 4, linesHit: 1 },
+				{ path: "a.ts", linesFound: 4, linesHit: 4 },
+			],
+			[{ path: "a.ts", linesFound: 4, linesHit: 2 }],
+		);
+
+		expect(deltas).toEqual([
+			{ path: "a.ts", linesFound: 4, linesHit: 4, percent: 100, basePercent: 50, delta: 50 },
+			{ path: "b.ts", linesFound: 4, linesHit: 1, percent: 25, basePercent: null, delta: null },
+		]);
+	});
+});
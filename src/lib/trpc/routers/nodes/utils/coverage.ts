@@ -0,0 +1,145 @@
+import { isAbsolute, relative } from "node:path";
+import type { CoverageFileDelta, FileCoverage } from "shared/types/test-runs";
+
+/** Report locations checked after a test run, relative to the node's working directory */
+export const COVERAGE_REPORT_PATHS = [
+	"coverage/lcov.info",
+	"lcov.info",
+	"coverage/cobertura-coverage.xml",
+	"coverage.xml",
+	"cobertura.xml",
+] as const;
+
+function toRelative(path: string, rootPath: string): string {
+	const relativePath = isAbsolute(path) ? relative(rootPath, path) : path;
+	return relativePath.replace(/\\/g, "/").replace(/^\.\//, "");
+}
+
+function mergeFile(files: Map<string, FileCoverage>, file: FileCoverage): void {
+	const existing = files.get(file.path);
+	if (existing) {
+		existing.linesFound += file.linesFound;
+		existing.linesHit += file.linesHit;
+	} else {
+		files.set(file.path, file);
+	}
+}
+
+/**
+ * Parses an lcov tracefile. Uses DA records when present since LF/LH are
+ * optional in some generators.
+ */
+export function parseLcov(content: string, rootPath: string): FileCoverage[] {
+	const files = new Map<string, FileCoverage>();
+	let path: string | null = null;
+	let found = 0;
+	let hit = 0;
+	let summaryFound: number | null = null;
+	let summaryHit: number | null = null;
+
+	for (const rawLine of content.split(/\r?\n/)) {
+		const line = rawLine.trim();
+		if (line.startsWith("SF:")) {
+			path = toRelative(line.slice(3), rootPath);
+			found = 0;
+			hit = 0;
+			summaryFound = null;
+			summaryHit = null;
+		} else if (line.startsWith("DA:")) {
+			const [, hits] = line.slice(3).split(",");
+			found++;
+			if (Number.parseInt(hits, 10) > 0) hit++;
+		} else if (line.startsWith("LF:")) {
+			summaryFound = Number.parseInt(line.slice(3), 10);
+		} else if (line.startsWith("LH:")) {
+			summaryHit = Number.parseInt(line.slice(3), 10);
+		} else if (line === "end_of_record" && path) {
+			mergeFile(files, {
+				path,
+				linesFound: found > 0 ? found : (summaryFound ?? 0),
+				linesHit: found > 0 ? hit : (summaryHit ?? 0),
+			});
+			path = null;
+		}
+	}
+
+	return [...files.values()];
+}
+
+/**
+ * Parses a Cobertura XML report by counting `<line hits>` entries per class filename.
+ * Filenames are resolved against the first `<source>` when they're relative to it.
+ */
+export function parseCobertura(content: string, rootPath: string): FileCoverage[] {
+	const files = new Map<string, FileCoverage>();
+	const source = content.match(/<source>([^<]*)<\/source>/)?.[1]?.trim();
+
+	const classPattern = /<class\b[^>]*\bfilename="([^"]+)"[^>]*>([\s\S]*?)<\/class>/g;
+	for (const match of content.matchAll(classPattern)) {
+		let filename = match[1];
+		if (source && !isAbsolute(filename) && isAbsolute(source)) {
+			filename = `${source.replace(/[\\/]$/, "")}/${filename}`;
+		}
+
+		let found = 0;
+		let hit = 0;
+		for (const line of match[2].matchAll(/<line\b[^>]*\bhits="(\d+)"/g)) {
+			found++;
+			if (Number.parseInt(line[1], 10) > 0) hit++;
+		}
+
+		mergeFile(files, { path: toRelative(filename, rootPath), linesFound: found, linesHit: hit });
+	}
+
+	return [...files.values()];
+}
+
+export function parseCoverageReport(
+	reportPath: string,
+	content: string,
+	rootPath: string,
+): FileCoverage[] {
+	return reportPath.endsWith(".xml")
+		? parseCobertura(content, rootPath)
+		: parseLcov(content, rootPath);
+}
+
+/** Line coverage percentage rounded to two decimals; null when there are no lines */
+export function coveragePercent(
+	files: Pick<FileCoverage, "linesFound" | "linesHit">[],
+): number | null {
+	let found = 0;
+	let hit = 0;
+	for (const file of files) {
+		found += file.linesFound;
+		hit += file.linesHit;
+	}
+	return found === 0 ? null : Math.round((hit / found) * 10_000) / 100;
+}
+
+/**
+ * Per-file coverage deltas between a node and its parent branch.
+ * Files missing from the base report have a null base percentage.
+ */
+export function diffCoverage(current: FileCoverage[], base: FileCoverage[]): CoverageFileDelta[] {
+	const baseByPath = new Map(base.map((file) => [file.path, file]));
+
+	return current
+		.map((file): CoverageFileDelta => {
+			const percent = coveragePercent([file]);
+			const baseFile = baseByPath.get(file.path);
+			const basePercent = baseFile ? coveragePercent([baseFile]) : null;
+			return {
+				path: file.path,
+				linesFound: file.linesFound,
+				linesHit: file.linesHit,
+				percent,
+				basePercent,
+				delta:
+					percent !== null && basePercent !== null
+						? Math.round((percent - basePercent) * 100) / 100
+						: null,
+			};
+		})
+		.sort((a, b) => a.path.localeCompare(b.path));
+}
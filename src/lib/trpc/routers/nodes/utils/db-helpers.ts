@@ -1,14 +1,22 @@
 import { and, desc, eq, isNotNull, isNull, not, sql } from "drizzle-orm";
 import {
+	gateRuns,
+	type InsertNode,
 	nodes,
+	nodeSnapshots,
+	notificationHistory,
 	repositories,
+	reviewComments,
 	type SelectNode,
 	type SelectRepository,
 	type SelectWorktree,
 	settings,
+	testRuns,
 	worktrees,
 } from "lib/local-db";
 import { localDb } from "main/lib/local-db";
+import { updateAppBadge } from "main/lib/notifications/badge";
+import { findRepositoryByPath, findWorktreeByPath } from "../../changes/security/path-validation";
 
 /**
  * Set the last active node in settings.
@@ -38,6 +46,46 @@ export function getMaxNodeTabOrder(repositoryId: string): number {
 	return repositoryNodes.length > 0 ? Math.max(...repositoryNodes.map((n) => n.tabOrder)) : -1;
 }
 
+/**
+ * Records a worktree and the node that opens it, after the repository's
+ * other nodes. Creating the worktree on disk is left to the caller,
+ * usually through initializeNodeWorktree.
+ */
+export function insertWorktreeNode({
+	repositoryId,
+	worktreePath,
+	branch,
+	baseBranch,
+	node,
+}: {
+	repositoryId: string;
+	worktreePath: string;
+	branch: string;
+	baseBranch: string;
+	node: Omit<InsertNode, "repositoryId" | "worktreeId" | "type" | "branch" | "tabOrder">;
+}): { worktree: SelectWorktree; node: SelectNode } {
+	const worktree = localDb
+		.insert(worktrees)
+		.values({ repositoryId, path: worktreePath, branch, baseBranch, gitStatus: null })
+		.returning()
+		.get();
+
+	const created = localDb
+		.insert(nodes)
+		.values({
+			...node,
+			repositoryId,
+			worktreeId: worktree.id,
+			type: "worktree",
+			branch,
+			tabOrder: getMaxNodeTabOrder(repositoryId) + 1,
+		})
+		.returning()
+		.get();
+
+	return { worktree, node: created };
+}
+
 /**
  * Get the maximum tab order for active repositories.
  * Returns -1 if no active repositories exist.
@@ -227,7 +275,14 @@ export function clearNodeDeletingStatus(nodeId: string): void {
  * Delete a node record from the database.
  */
 export function deleteNode(nodeId: string): void {
+	// Foreign keys are off, so dependent rows are removed explicitly
+	localDb.delete(reviewComments).where(eq(reviewComments.nodeId, nodeId)).run();
+	localDb.delete(testRuns).where(eq(testRuns.nodeId, nodeId)).run();
+	localDb.delete(gateRuns).where(eq(gateRuns.nodeId, nodeId)).run();
+	localDb.delete(nodeSnapshots).where(eq(nodeSnapshots.nodeId, nodeId)).run();
+	localDb.delete(notificationHistory).where(eq(notificationHistory.nodeId, nodeId)).run();
 	localDb.delete(nodes).where(eq(nodes.id, nodeId)).run();
+	updateAppBadge();
 }
 
 /**
@@ -252,6 +307,20 @@ export function getBranchNode(repositoryId: string): SelectNode | undefined {
 		.get();
 }
 
+/**
+ * Finds the node working in a path: the worktree's node for a registered
+ * worktree, or the branch node for a repository's main checkout.
+ */
+export function findNodeByPath(path: string): SelectNode | undefined {
+	const worktree = findWorktreeByPath(path);
+	if (worktree) {
+		return localDb.select().from(nodes).where(eq(nodes.worktreeId, worktree.id)).get();
+	}
+
+	const repository = findRepositoryByPath(path);
+	return repository ? getBranchNode(repository.id) : undefined;
+}
+
 /**
  * Ensure a branch node exists for a repository.
  * If no branch node exists, creates one with tabOrder 0 and bumps all other nodes down.
@@ -6,11 +6,20 @@ import { promisify } from "node:util";
 import friendlyWords = require("friendly-words");
 
 import type { BranchPrefixMode } from "lib/local-db";
+import { pathsEqual, toNativePath } from "main/lib/platform-paths";
 import simpleGit, { type StatusResult } from "simple-git";
+import { getGitHubAccountEnv, getGitHubAccountForPath } from "./github/accounts";
+import { fetchGitHubUserWithToken, getGitHubToken } from "./github/api";
 import { checkGitLfsAvailable, getShellEnvironment } from "./shell-env";
 
 const execFileAsync = promisify(execFile);
 
+/**
+ * Lets git create and remove worktrees whose paths exceed MAX_PATH on Windows.
+ * Passed per-command so the user's global config is left alone.
+ */
+const LONG_PATH_GIT_ARGS = process.platform === "win32" ? ["-c", "core.longpaths=true"] : [];
+
 /**
  * Error thrown by execFile when the command fails.
  * `code` can be a number (exit code) or string (spawn error like "ENOENT").
@@ -28,7 +37,7 @@ function isExecFileException(error: unknown): error is ExecFileException {
 	return error instanceof Error && ("code" in error || "signal" in error || "killed" in error);
 }
 
-async function getGitEnv(): Promise<Record<string, string>> {
+export async function getGitEnv(): Promise<Record<string, string>> {
 	const shellEnv = await getShellEnvironment();
 	const result: Record<string, string> = {};
 
@@ -267,7 +276,11 @@ export async function getGitAuthorName(repoPath?: string): Promise<string | null
 	}
 }
 
-export async function getGitHubUsername(_repoPath?: string): Promise<string | null> {
+export async function getGitHubUsername(repoPath?: string): Promise<string | null> {
+	// Repositories bound to an account use its login, recorded when the account was added
+	const account = repoPath ? getGitHubAccountForPath(repoPath) : undefined;
+	if (account) return account.username;
+
 	const env = await getGitEnv();
 
 	try {
@@ -275,12 +288,25 @@ export async function getGitHubUsername(_repoPath?: string): Promise<string | nu
 			env,
 			timeout: 10_000,
 		});
-		return stdout.trim() || null;
+		const username = stdout.trim();
+		if (username) return username;
 	} catch (error) {
 		console.warn(
 			"[git/getGitHubUsername] Failed to get GitHub username:",
 			error instanceof Error ? error.message : String(error),
 		);
+	}
+
+	// Without gh, fall back to the personal access token if one is stored
+	const token = getGitHubToken();
+	if (!token) return null;
+	try {
+		return await fetchGitHubUserWithToken(token);
+	} catch (error) {
+		console.warn(
+			"[git/getGitHubUsername] Token lookup failed:",
+			error instanceof Error ? error.message : String(error),
+		);
 		return null;
 	}
 }
@@ -333,9 +359,12 @@ export {
 export function generateBranchName({
 	existingBranches = [],
 	authorPrefix,
+	format,
 }: {
 	existingBranches?: string[];
 	authorPrefix?: string;
+	/** Builds the full branch name from a generated slug; replaces authorPrefix */
+	format?: (slug: string) => string;
 } = {}): string {
 	const words = friendlyWords.objects as string[];
 	const existingSet = new Set(existingBranches.map((b) => b.toLowerCase()));
@@ -343,12 +372,14 @@ export function generateBranchName({
 	const prefixWouldCollide = authorPrefix && existingSet.has(authorPrefix.toLowerCase());
 	const safePrefix = prefixWouldCollide ? undefined : authorPrefix;
 
-	const addPrefix = (name: string): string => {
-		if (safePrefix) {
-			return `${safePrefix}/${name}`;
-		}
-		return name;
-	};
+	const addPrefix =
+		format ??
+		((name: string): string => {
+			if (safePrefix) {
+				return `${safePrefix}/${name}`;
+			}
+			return name;
+		});
 
 	for (let i = 0; i < MAX_ATTEMPTS; i++) {
 		const word = words[Math.floor(Math.random() * words.length)];
@@ -369,12 +400,41 @@ export function generateBranchName({
 	return addPrefix(`${baseWord}-${Date.now()}`);
 }
 
-export async function createWorktree(
-	mainRepoPath: string,
-	branch: string,
+/**
+ * Limits a worktree created with --no-checkout to cone-mode directories, then
+ * populates it. The settings live in the worktree's own config, so the main
+ * checkout and other worktrees stay complete.
+ */
+async function applySparseCheckout(
 	worktreePath: string,
-	startPoint = "origin/main",
+	directories: string[],
+	env: Record<string, string>,
 ): Promise<void> {
+	await execFileAsync(
+		"git",
+		[...LONG_PATH_GIT_ARGS, "-C", worktreePath, "sparse-checkout", "set", "--cone", ...directories],
+		{ env, timeout: 30_000 },
+	);
+	await execFileAsync("git", [...LONG_PATH_GIT_ARGS, "-C", worktreePath, "reset", "--hard", "-q"], {
+		env,
+		timeout: 120_000,
+	});
+}
+
+export async function createWorktree({
+	mainRepoPath,
+	branch,
+	worktreePath,
+	startPoint = "origin/main",
+	sparsePatterns,
+}: {
+	mainRepoPath: string;
+	branch: string;
+	worktreePath: string;
+	startPoint?: string;
+	/** Cone-mode directories to check out instead of the whole repository */
+	sparsePatterns?: string[] | null;
+}): Promise<void> {
 	const usesLfs = await repoUsesLfs(mainRepoPath);
 
 	try {
@@ -405,10 +465,12 @@ export async function createWorktree(
 		await execFileAsync(
 			"git",
 			[
+				...LONG_PATH_GIT_ARGS,
 				"-C",
 				mainRepoPath,
 				"worktree",
 				"add",
+				...(sparsePatterns ? ["--no-checkout"] : []),
 				worktreePath,
 				"-b",
 				branch,
@@ -419,6 +481,9 @@ export async function createWorktree(
 			],
 			{ env, timeout: 120_000 },
 		);
+		if (sparsePatterns) {
+			await applySparseCheckout(worktreePath, sparsePatterns, env);
+		}
 
 		console.log(`Created worktree at ${worktreePath} with branch ${branch} from ${startPoint}`);
 	} catch (error) {
@@ -483,10 +548,13 @@ export async function createWorktreeFromExistingBranch({
 	mainRepoPath,
 	branch,
 	worktreePath,
+	sparsePatterns,
 }: {
 	mainRepoPath: string;
 	branch: string;
 	worktreePath: string;
+	/** Cone-mode directories to check out instead of the whole repository */
+	sparsePatterns?: string[] | null;
 }): Promise<void> {
 	const usesLfs = await repoUsesLfs(mainRepoPath);
 
@@ -509,12 +577,23 @@ export async function createWorktreeFromExistingBranch({
 		const git = simpleGit(mainRepoPath);
 		const localBranches = await git.branchLocal();
 		const branchExistsLocally = localBranches.all.includes(branch);
+		const noCheckout = sparsePatterns ? ["--no-checkout"] : [];
 
 		if (branchExistsLocally) {
-			await execFileAsync("git", ["-C", mainRepoPath, "worktree", "add", worktreePath, branch], {
-				env,
-				timeout: 120_000,
-			});
+			await execFileAsync(
+				"git",
+				[
+					...LONG_PATH_GIT_ARGS,
+					"-C",
+					mainRepoPath,
+					"worktree",
+					"add",
+					...noCheckout,
+					worktreePath,
+					branch,
+				],
+				{ env, timeout: 120_000 },
+			);
 		} else {
 			const remoteBranches = await git.branch(["-r"]);
 			const remoteBranchName = `origin/${branch}`;
@@ -522,10 +601,12 @@ export async function createWorktreeFromExistingBranch({
 				await execFileAsync(
 					"git",
 					[
+						...LONG_PATH_GIT_ARGS,
 						"-C",
 						mainRepoPath,
 						"worktree",
 						"add",
+						...noCheckout,
 						"--track",
 						"-b",
 						branch,
@@ -538,6 +619,9 @@ export async function createWorktreeFromExistingBranch({
 				throw new Error(`Branch "${branch}" does not exist locally or on remote`);
 			}
 		}
+		if (sparsePatterns) {
+			await applySparseCheckout(worktreePath, sparsePatterns, env);
+		}
 
 		console.log(`Created worktree at ${worktreePath} using existing branch ${branch}`);
 	} catch (error) {
@@ -595,7 +679,7 @@ export async function removeWorktree(mainRepoPath: string, worktreePath: string)
 
 		await execFileAsync(
 			"git",
-			["-C", mainRepoPath, "worktree", "remove", worktreePath, "--force"],
+			[...LONG_PATH_GIT_ARGS, "-C", mainRepoPath, "worktree", "remove", worktreePath, "--force"],
 			{ env, timeout: 60_000 },
 		);
 
@@ -607,6 +691,63 @@ export async function removeWorktree(mainRepoPath: string, worktreePath: string)
 	}
 }
 
+/**
+ * Removes administrative files for worktrees whose directories no longer exist.
+ */
+export async function pruneWorktrees(mainRepoPath: string): Promise<void> {
+	const env = await getGitEnv();
+	await execFileAsync("git", ["-C", mainRepoPath, "worktree", "prune"], {
+		env,
+		timeout: 60_000,
+	});
+}
+
+/**
+ * Returns raw `git count-objects -v` output for the repository's object store.
+ */
+export async function countObjects(repoPath: string): Promise<string> {
+	const env = await getGitEnv();
+	const { stdout } = await execFileAsync(
+		"git",
+		["--no-optional-locks", "-C", repoPath, "count-objects", "-v"],
+		{ env, timeout: 30_000 },
+	);
+	return stdout;
+}
+
+/**
+ * Runs `git maintenance run`, falling back to `git gc` on git versions
+ * older than 2.29 that lack the maintenance command.
+ * @param options.auto - Only do work if git's own heuristics say it is needed
+ * @returns Whether the gc fallback was used
+ */
+export async function runGitMaintenance(
+	repoPath: string,
+	options: { auto: boolean },
+): Promise<{ usedGcFallback: boolean }> {
+	const env = await getGitEnv();
+	// Repacking large repos can take a while
+	const execOptions = { env, timeout: 15 * 60_000 };
+
+	const maintenanceArgs = options.auto
+		? ["maintenance", "run", "--auto"]
+		: ["maintenance", "run", "--task=gc"];
+
+	try {
+		await execFileAsync("git", ["-C", repoPath, ...maintenanceArgs], execOptions);
+		return { usedGcFallback: false };
+	} catch (error) {
+		const stderr = isExecFileException(error) ? (error.stderr ?? "") : "";
+		if (!stderr.includes("is not a git command")) {
+			throw error;
+		}
+	}
+
+	const gcArgs = options.auto ? ["gc", "--auto", "--quiet"] : ["gc", "--quiet"];
+	await execFileAsync("git", ["-C", repoPath, ...gcArgs], execOptions);
+	return { usedGcFallback: true };
+}
+
 export async function getGitRoot(path: string): Promise<string> {
 	try {
 		const git = simpleGit(path);
@@ -622,9 +763,14 @@ export async function worktreeExists(mainRepoPath: string, worktreePath: string)
 		const git = simpleGit(mainRepoPath);
 		const worktrees = await git.raw(["worktree", "list", "--porcelain"]);
 
-		const lines = worktrees.split("\n");
-		const worktreePrefix = `worktree ${worktreePath}`;
-		return lines.some((line) => line.trim() === worktreePrefix);
+		// git prints forward slashes on Windows, so compare as paths rather than strings
+		return worktrees
+			.split("\n")
+			.some(
+				(line) =>
+					line.startsWith("worktree ") &&
+					pathsEqual(line.slice("worktree ".length).trim(), worktreePath),
+			);
 	} catch (error) {
 		console.error(`Failed to check worktree existence: ${error}`);
 		throw error;
@@ -659,7 +805,7 @@ export async function getBranchWorktreePath({
 			} else if (line.startsWith("branch refs/heads/")) {
 				const branchName = line.slice("branch refs/heads/".length);
 				if (branchName === branch && currentWorktreePath) {
-					return currentWorktreePath;
+					return toNativePath(currentWorktreePath);
 				}
 				currentWorktreePath = null;
 			}
@@ -1013,8 +1159,12 @@ export async function isRemoteEmpty(mainRepoPath: string): Promise<boolean | nul
  * Initialize an empty repository with a first commit and push to origin.
  * Creates a README.md and pushes to the specified branch.
  */
-export async function initializeEmptyRepo(mainRepoPath: string, branchName: string): Promise<void> {
-	const git = simpleGit(mainRepoPath);
+export async function initializeEmptyRepo(
+	mainRepoPath: string,
+	branchName: string,
+	commitConfig: string[] = [],
+): Promise<void> {
+	const git = simpleGit(mainRepoPath, { config: commitConfig });
 	const readmePath = `${mainRepoPath}/README.md`;
 
 	// Create a minimal README
@@ -1085,23 +1235,19 @@ export async function detectBaseBranch(
  * Lists all local and remote branches in a repository
  * @param repoPath - Path to the repository
  * @param options.fetch - Whether to fetch and prune remote refs first (default: false)
+ * @param options.signal - Aborts the fetch; branches are then listed from existing refs
  * @returns Object with local and remote branch arrays
  */
+/** Fetches and prunes so remote branch listings are up to date */
+export async function fetchAndPrune(repoPath: string, signal?: AbortSignal): Promise<void> {
+	await simpleGit(repoPath, { abort: signal }).fetch(["--prune"]);
+}
+
 export async function listBranches(
 	repoPath: string,
-	options?: { fetch?: boolean },
 ): Promise<{ local: string[]; remote: string[] }> {
 	const git = simpleGit(repoPath);
 
-	// Optionally fetch and prune to get up-to-date remote refs
-	if (options?.fetch) {
-		try {
-			await git.fetch(["--prune"]);
-		} catch {
-			// Ignore fetch errors (e.g., offline)
-		}
-	}
-
 	const localResult = await git.branchLocal();
 	const local = localResult.all;
 
@@ -1255,6 +1401,57 @@ export async function refExistsLocally(repoPath: string, ref: string): Promise<b
 	}
 }
 
+/**
+ * Resolves HEAD to a commit SHA.
+ * @param repoPath - Path to the repository or worktree
+ * @returns The commit SHA, or null if HEAD has no commits yet
+ */
+export async function getHeadSha(repoPath: string): Promise<string | null> {
+	try {
+		const sha = await simpleGit(repoPath).raw(["rev-parse", "--verify", "--quiet", "HEAD"]);
+		return sha.trim() || null;
+	} catch {
+		return null;
+	}
+}
+
+/**
+ * Creates a local branch at an exact commit without checking it out.
+ * @param repoPath - Path to the repository
+ * @param branch - Name of the new branch
+ * @param startPoint - Commit the branch should point at
+ */
+export async function createBranchAt(
+	repoPath: string,
+	branch: string,
+	startPoint: string,
+): Promise<void> {
+	await simpleGit(repoPath).raw(["branch", "--no-track", branch, startPoint]);
+}
+
+/**
+ * Force-deletes a local branch, e.g. one created for a node that then failed to save.
+ * @param repoPath - Path to the repository
+ * @param branch - Name of the branch to delete
+ */
+export async function deleteLocalBranch(repoPath: string, branch: string): Promise<void> {
+	await simpleGit(repoPath).raw(["branch", "-D", branch]);
+}
+
+/**
+ * Renames a local branch, including one checked out in a worktree.
+ * @param repoPath - Path to the repository or any of its worktrees
+ * @param oldBranch - Current branch name
+ * @param newBranch - New branch name
+ */
+export async function renameLocalBranch(
+	repoPath: string,
+	oldBranch: string,
+	newBranch: string,
+): Promise<void> {
+	await simpleGit(repoPath).raw(["branch", "-m", oldBranch, newBranch]);
+}
+
 /**
  * Sanitizes git error messages for user display.
  * Strips "fatal:" prefixes, excessive newlines, and other git plumbing text.
@@ -1370,12 +1567,18 @@ export async function getPrInfo({
 	owner,
 	repo,
 	prNumber,
+	repoPath,
 }: {
 	owner: string;
 	repo: string;
 	prNumber: number;
+	/** Local repository whose bound GitHub account should be used */
+	repoPath?: string;
 }): Promise<PullRequestInfo> {
 	const env = await getGitEnv();
+	if (repoPath) {
+		Object.assign(env, getGitHubAccountEnv(repoPath));
+	}
 
 	try {
 		const { stdout } = await execFileAsync(
@@ -1511,7 +1714,7 @@ export async function createWorktreeFromPr({
 
 			await execFileAsync(
 				"git",
-				["-C", mainRepoPath, "worktree", "add", worktreePath, branchName],
+				[...LONG_PATH_GIT_ARGS, "-C", mainRepoPath, "worktree", "add", worktreePath, branchName],
 				{ env, timeout: 120_000 },
 			);
 
@@ -1522,7 +1725,7 @@ export async function createWorktreeFromPr({
 				});
 			}
 		} else {
-			const args = ["-C", mainRepoPath, "worktree", "add"];
+			const args = [...LONG_PATH_GIT_ARGS, "-C", mainRepoPath, "worktree", "add"];
 			if (!prInfo.isCrossRepository) {
 				args.push("--track");
 			}
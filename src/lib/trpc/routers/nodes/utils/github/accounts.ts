@@ -0,0 +1,107 @@
+import { eq } from "drizzle-orm";
+import { githubAccounts, repositories, type SelectGitHubAccount } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { getSecret, type SecretKey } from "main/lib/secrets/store";
+import {
+	findRepositoryByPath,
+	findWorktreeByPath,
+} from "../../../changes/security/path-validation";
+import { execWithShellEnv } from "../shell-env";
+
+/**
+ * Repositories can be bound to one of several GitHub accounts. An account
+ * authenticates gh through its own config dir (GH_CONFIG_DIR) and/or a
+ * token from the secret store (GH_TOKEN, which gh prefers). Unbound
+ * repositories keep using the default gh login and token.
+ */
+
+export function getAccountSecretKey(accountId: string): SecretKey {
+	return `github-token:${accountId}`;
+}
+
+export function getGitHubAccount(accountId: string): SelectGitHubAccount | undefined {
+	return localDb.select().from(githubAccounts).where(eq(githubAccounts.id, accountId)).get();
+}
+
+/** The account bound to the repository that owns a worktree or main checkout */
+export function getGitHubAccountForPath(path: string): SelectGitHubAccount | undefined {
+	const worktree = findWorktreeByPath(path);
+	const repository = worktree
+		? localDb.select().from(repositories).where(eq(repositories.id, worktree.repositoryId)).get()
+		: findRepositoryByPath(path);
+	return repository?.githubAccountId ? getGitHubAccount(repository.githubAccountId) : undefined;
+}
+
+function getAccountEnv(account: SelectGitHubAccount): Record<string, string> {
+	const env: Record<string, string> = {};
+	if (account.ghConfigDir) {
+		env.GH_CONFIG_DIR = account.ghConfigDir;
+	}
+	const token = account.hasToken ? getSecret(getAccountSecretKey(account.id)) : null;
+	if (token) {
+		env.GH_TOKEN = token;
+	}
+	return env;
+}
+
+/** GH_CONFIG_DIR/GH_TOKEN for the account bound to a path; empty when none is bound */
+export function getGitHubAccountEnv(path: string): Record<string, string> {
+	const account = getGitHubAccountForPath(path);
+	return account ? getAccountEnv(account) : {};
+}
+
+/**
+ * Environment for gh commands run against a path, or undefined to inherit
+ * process.env when the repository has no account bound.
+ */
+export function getGhEnvForPath(path: string): NodeJS.ProcessEnv | undefined {
+	const account = getGitHubAccountForPath(path);
+	return account ? { ...process.env, ...getAccountEnv(account) } : undefined;
+}
+
+/** The bound account's token for REST calls, if it has one */
+export function getGitHubTokenForPath(path: string): string | null {
+	const account = getGitHubAccountForPath(path);
+	return account?.hasToken ? getSecret(getAccountSecretKey(account.id)) : null;
+}
+
+/**
+ * Git config and environment that make HTTPS pushes authenticate as the
+ * bound account. The token is read from the environment by the credential
+ * helper so it never appears in process arguments. SSH remotes are
+ * unaffected; they authenticate with whichever key ssh picks for the host.
+ */
+export function getGitCredentialOverrides(
+	path: string,
+): { config: string[]; env: Record<string, string> } | null {
+	const account = getGitHubAccountForPath(path);
+	if (!account) return null;
+
+	const env = getAccountEnv(account);
+	let helper: string;
+	if (env.GH_TOKEN) {
+		helper = `!f() { test "$1" = get && echo username=x-access-token && echo "password=$GH_TOKEN"; }; f`;
+	} else if (env.GH_CONFIG_DIR) {
+		helper = "!gh auth git-credential";
+	} else {
+		return null;
+	}
+
+	return {
+		// The empty entry clears helpers from the user's config so theirs can't answer first
+		config: ["credential.helper=", `credential.helper=${helper}`],
+		env,
+	};
+}
+
+/** The login gh is signed in as for a config dir; throws when it isn't signed in */
+export async function fetchGhUserForConfigDir(ghConfigDir: string): Promise<string> {
+	const { stdout } = await execWithShellEnv("gh", ["api", "user", "--jq", ".login"], {
+		env: { ...process.env, GH_CONFIG_DIR: ghConfigDir },
+	});
+	const username = stdout.trim();
+	if (!username) {
+		throw new Error("gh returned no username");
+	}
+	return username;
+}
@@ -0,0 +1,37 @@
+import { describe, expect, test } from "bun:test";
+import { mapCheckRun, summarizeReviews } from "./api";
+
+function review(login: string, state: string) {
+	return { user: { login }, state };
+}
+
+describe("summarizeReviews", () => {
+	test("uses each reviewer's latest verdict", () => {
+		expect(
+			summarizeReviews([review("ana", "CHANGES_REQUESTED"), review("ana", "APPROVED")]),
+		).toBe("approved");
+	});
+
+	test("an outstanding change request wins over approvals", () => {
+		expect(
+			summarizeReviews([review("ana", "APPROVED"), review("bo", "CHANGES_REQUESTED")]),
+		).toBe("changes_requested");
+	});
+
+	test("comments don't override a verdict", () => {
+		expect(summarizeReviews([review("ana", "APPROVED"), review("ana", "COMMENTED")])).toBe(
+			"approved",
+		);
+		expect(summarizeReviews([])).toBe("pending");
+	});
+});
+
+describe("mapCheckRun", () => {
+	test("maps status and conclusion", () => {
+		const run = { name: "ci", status: "completed", conclusion: "success", html_url: "u" };
+		expect(mapCheckRun(run)).toEqual({ name: "ci", status: "success", url: "u" });
+		expect(mapCheckRun({ ...run, status: "in_progress", conclusion: null }).status).toBe("pending");
+		expect(mapCheckRun({ ...run, conclusion: "timed_out" }).status).toBe("failure");
+		expect(mapCheckRun({ ...run, conclusion: "neutral" }).status).toBe("skipped");
+	});
+});
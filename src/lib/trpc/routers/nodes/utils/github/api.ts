@@ -0,0 +1,177 @@
+import type { CheckItem, GitHubStatus } from "lib/local-db";
+import { getSecret } from "main/lib/secrets/store";
+import { z } from "zod";
+
+/**
+ * GitHub REST API client for users without gh, authenticated with a
+ * personal access token from the secret store.
+ */
+
+const REQUEST_TIMEOUT_MS = 15_000;
+
+const RESTUserSchema = z.object({ login: z.string() });
+
+const RESTPullSchema = z.object({
+	number: z.number(),
+	title: z.string(),
+	html_url: z.string(),
+	state: z.enum(["open", "closed"]),
+	draft: z.boolean().optional(),
+	merged_at: z.string().nullable(),
+	head: z.object({ sha: z.string() }),
+	additions: z.number().optional(),
+	deletions: z.number().optional(),
+});
+
+const RESTReviewSchema = z.object({
+	user: z.object({ login: z.string() }).nullable(),
+	state: z.string(),
+});
+
+const RESTCheckRunSchema = z.object({
+	name: z.string(),
+	status: z.string(),
+	conclusion: z.string().nullable(),
+	html_url: z.string().nullable().optional(),
+});
+
+type RESTPull = z.infer<typeof RESTPullSchema>;
+type RESTReview = z.infer<typeof RESTReviewSchema>;
+type RESTCheckRun = z.infer<typeof RESTCheckRunSchema>;
+
+export function getGitHubToken(): string | null {
+	return getSecret("github-token");
+}
+
+function apiBaseUrl(host: string): string {
+	return host === "github.com" ? "https://api.github.com" : `https://${host}/api/v3`;
+}
+
+async function githubApi(host: string, path: string, token: string): Promise<unknown> {
+	const response = await fetch(`${apiBaseUrl(host)}${path}`, {
+		headers: {
+			Authorization: `Bearer ${token}`,
+			Accept: "application/vnd.github+json",
+			"X-GitHub-Api-Version": "2022-11-28",
+		},
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	if (!response.ok) {
+		throw new Error(`GitHub API ${path} failed: ${response.status} ${response.statusText}`);
+	}
+	return response.json();
+}
+
+/** The token owner's login; throws if the token is rejected */
+export async function fetchGitHubUserWithToken(
+	token: string,
+	host = "github.com",
+): Promise<string> {
+	return RESTUserSchema.parse(await githubApi(host, "/user", token)).login;
+}
+
+/**
+ * Latest review per reviewer decides, like GitHub's review decision:
+ * any outstanding change request wins over approvals.
+ */
+export function summarizeReviews(
+	reviews: RESTReview[],
+): NonNullable<GitHubStatus["pr"]>["reviewDecision"] {
+	const latest = new Map<string, string>();
+	for (const review of reviews) {
+		// Comments don't change a reviewer's verdict
+		if (!review.user || review.state === "COMMENTED" || review.state === "PENDING") continue;
+		latest.set(review.user.login, review.state);
+	}
+
+	const states = [...latest.values()];
+	if (states.includes("CHANGES_REQUESTED")) return "changes_requested";
+	if (states.includes("APPROVED")) return "approved";
+	return "pending";
+}
+
+export function mapCheckRun(run: RESTCheckRun): CheckItem {
+	let status: CheckItem["status"];
+	if (run.status !== "completed") {
+		status = "pending";
+	} else if (run.conclusion === "success") {
+		status = "success";
+	} else if (run.conclusion === "skipped" || run.conclusion === "neutral") {
+		status = "skipped";
+	} else if (run.conclusion === "cancelled") {
+		status = "cancelled";
+	} else {
+		status = "failure";
+	}
+	return { name: run.name, status, url: run.html_url ?? undefined };
+}
+
+function mapPullState(pull: RESTPull): NonNullable<GitHubStatus["pr"]>["state"] {
+	if (pull.merged_at) return "merged";
+	if (pull.state === "closed") return "closed";
+	if (pull.draft) return "draft";
+	return "open";
+}
+
+function computeChecksStatus(checks: CheckItem[]): NonNullable<GitHubStatus["pr"]>["checksStatus"] {
+	if (checks.length === 0) return "none";
+	if (checks.some((check) => check.status === "failure")) return "failure";
+	if (checks.some((check) => check.status === "pending")) return "pending";
+	return "success";
+}
+
+/** The most recently updated pull request from `branch`, in any state */
+export async function fetchPRForBranchWithToken({
+	host,
+	repoPath,
+	branch,
+	token,
+}: {
+	host: string;
+	/** "owner/repo" */
+	repoPath: string;
+	branch: string;
+	token: string;
+}): Promise<GitHubStatus["pr"]> {
+	const owner = repoPath.split("/")[0];
+	const query = new URLSearchParams({
+		head: `${owner}:${branch}`,
+		state: "all",
+		sort: "updated",
+		direction: "desc",
+		per_page: "1",
+	});
+	const pulls = z.array(RESTPullSchema).parse(
+		await githubApi(host, `/repos/${repoPath}/pulls?${query}`, token),
+	);
+	if (pulls.length === 0) {
+		return null;
+	}
+
+	// The list endpoint omits line counts
+	const pullPath = `/repos/${repoPath}/pulls/${pulls[0].number}`;
+	const commitPath = `/repos/${repoPath}/commits/${pulls[0].head.sha}`;
+	const [pull, reviews, checkRuns] = await Promise.all([
+		githubApi(host, pullPath, token),
+		githubApi(host, `${pullPath}/reviews?per_page=100`, token),
+		githubApi(host, `${commitPath}/check-runs?per_page=100`, token),
+	]);
+	const data = RESTPullSchema.parse(pull);
+	const checks = z
+		.object({ check_runs: z.array(RESTCheckRunSchema) })
+		.parse(checkRuns)
+		.check_runs.map(mapCheckRun);
+
+	return {
+		number: data.number,
+		title: data.title,
+		url: data.html_url,
+		state: mapPullState(data),
+		mergedAt: data.merged_at ? new Date(data.merged_at).getTime() : undefined,
+		additions: data.additions ?? 0,
+		deletions: data.deletions ?? 0,
+		reviewDecision: summarizeReviews(z.array(RESTReviewSchema).parse(reviews)),
+		checksStatus: computeChecksStatus(checks),
+		checks,
+	};
+}
@@ -3,6 +3,7 @@ import { promisify } from "node:util";
 import type { CheckItem, GitHubStatus } from "lib/local-db";
 import { branchExistsOnRemote, branchHasBeenPushed } from "../git";
 import { execWithShellEnv } from "../shell-env";
+import { getGhEnvForPath } from "./accounts";
 import { type GHPRResponse, GHPRResponseSchema, GHRepoResponseSchema } from "./types";
 
 const execFileAsync = promisify(execFile);
@@ -61,6 +62,7 @@ async function getRepoUrl(worktreePath: string): Promise<string | null> {
 	try {
 		const { stdout } = await execWithShellEnv("gh", ["repo", "view", "--json", "url"], {
 			cwd: worktreePath,
+			env: getGhEnvForPath(worktreePath),
 		});
 		const raw = JSON.parse(stdout);
 		const result = GHRepoResponseSchema.safeParse(raw);
@@ -93,7 +95,7 @@ async function getPRForBranch(worktreePath: string, _branch: string): Promise<Gi
 				"--json",
 				"number,title,url,state,isDraft,mergedAt,additions,deletions,reviewDecision,statusCheckRollup",
 			],
-			{ cwd: worktreePath },
+			{ cwd: worktreePath, env: getGhEnvForPath(worktreePath) },
 		);
 		const raw = JSON.parse(stdout);
 		const result = GHPRResponseSchema.safeParse(raw);
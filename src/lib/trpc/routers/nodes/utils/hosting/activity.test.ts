@@ -0,0 +1,58 @@
+import { describe, expect, test } from "bun:test";
+import type { GitHubStatus } from "lib/local-db";
+import { describePullRequestChange } from "./activity";
+
+type PullRequest = NonNullable<GitHubStatus["pr"]>;
+
+function status(pr: Partial<PullRequest> | null): GitHubStatus {
+	return {
+		pr: pr && {
+			number: 12,
+			title: "Add activity feed",
+			url: "https://github.com/acme/app/pull/12",
+			state: "open",
+			additions: 10,
+			deletions: 2,
+			reviewDecision: "pending",
+			checksStatus: "pending",
+			checks: [],
+			...pr,
+		},
+		repoUrl: "https://github.com/acme/app",
+		branchExistsOnRemote: true,
+		branchHasBeenPushed: true,
+		lastRefreshed: 0,
+	};
+}
+
+describe("describePullRequestChange", () => {
+	test("records merges", () => {
+		const change = describePullRequestChange(status({}), status({ state: "merged" }));
+		expect(change?.type).toBe("pr_merged");
+		expect(change?.summary).toBe("Pull request #12 merged");
+	});
+
+	test("combines review, checks and state changes", () => {
+		const change = describePullRequestChange(
+			status({ state: "draft" }),
+			status({ state: "open", reviewDecision: "approved", checksStatus: "failure" }),
+		);
+		expect(change?.type).toBe("pr_updated");
+		expect(change?.summary).toBe(
+			"Pull request #12 marked ready for review, approved, checks failed",
+		);
+	});
+
+	test("ignores unchanged and newly seen open pull requests", () => {
+		expect(describePullRequestChange(status({}), status({}))).toBeNull();
+		expect(describePullRequestChange(null, status({}))).toBeNull();
+		expect(describePullRequestChange(status(null), status({ checksStatus: "success" }))).toBeNull();
+		expect(describePullRequestChange(status({ checksStatus: "success" }), status(null))).toBeNull();
+	});
+
+	test("records a pull request first seen already merged", () => {
+		expect(describePullRequestChange(status(null), status({ state: "merged" }))?.type).toBe(
+			"pr_merged",
+		);
+	});
+});
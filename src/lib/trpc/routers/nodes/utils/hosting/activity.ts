@@ -0,0 +1,83 @@
+import type { GitHubStatus } from "lib/local-db";
+
+type PullRequest = NonNullable<GitHubStatus["pr"]>;
+
+export interface PullRequestChange {
+	type: "pr_updated" | "pr_merged";
+	summary: string;
+	data: Record<string, unknown>;
+}
+
+function describeStateChange(from: PullRequest["state"], to: PullRequest["state"]): string | null {
+	if (from === to) return null;
+	switch (to) {
+		case "merged":
+			return "merged";
+		case "closed":
+			return "closed";
+		case "draft":
+			return "converted to draft";
+		case "open":
+			return from === "draft" ? "marked ready for review" : "reopened";
+	}
+}
+
+function describeReviewChange(
+	from: PullRequest["reviewDecision"],
+	to: PullRequest["reviewDecision"],
+): string | null {
+	if (from === to) return null;
+	if (to === "approved") return "approved";
+	if (to === "changes_requested") return "changes requested";
+	return null;
+}
+
+function describeChecksChange(
+	from: PullRequest["checksStatus"],
+	to: PullRequest["checksStatus"],
+): string | null {
+	if (from === to) return null;
+	if (to === "success") return "checks passed";
+	if (to === "failure") return "checks failed";
+	return null;
+}
+
+/**
+ * What changed between two polls of a branch's pull request, for the
+ * activity feed. Returns null when nothing worth recording changed. A PR
+ * seen for the first time is only recorded if it's already merged, since
+ * opening it was recorded as pr_created (or happened outside Caspian).
+ */
+export function describePullRequestChange(
+	previous: GitHubStatus | null,
+	next: GitHubStatus,
+): PullRequestChange | null {
+	const pr = next.pr;
+	if (!pr) return null;
+
+	const before = previous?.pr?.number === pr.number ? previous.pr : null;
+	const changes = before
+		? [
+				describeStateChange(before.state, pr.state),
+				describeReviewChange(before.reviewDecision, pr.reviewDecision),
+				describeChecksChange(before.checksStatus, pr.checksStatus),
+			].filter((change): change is string => change !== null)
+		: pr.state === "merged"
+			? ["merged"]
+			: [];
+
+	if (changes.length === 0) return null;
+
+	return {
+		type: pr.state === "merged" && before?.state !== "merged" ? "pr_merged" : "pr_updated",
+		summary: `Pull request #${pr.number} ${changes.join(", ")}`,
+		data: {
+			number: pr.number,
+			title: pr.title,
+			url: pr.url,
+			state: pr.state,
+			reviewDecision: pr.reviewDecision,
+			checksStatus: pr.checksStatus,
+		},
+	};
+}
@@ -0,0 +1,220 @@
+import type { CheckItem, GitHubStatus } from "lib/local-db";
+import { z } from "zod";
+import { branchExistsOnRemote, branchHasBeenPushed, getCurrentBranch } from "../git";
+import { getShellEnvironment } from "../shell-env";
+import type { RemoteInfo } from "./remote";
+import type { HostingAuthStatus, HostingProvider } from "./types";
+
+const API_BASE_URL = "https://api.bitbucket.org/2.0";
+const REQUEST_TIMEOUT_MS = 15_000;
+/** Access token (repository, project or workspace) read from the user's shell environment */
+const TOKEN_ENV_VAR = "BITBUCKET_TOKEN";
+
+const BBPRResponseSchema = z.object({
+	values: z.array(
+		z.object({
+			id: z.number(),
+			title: z.string(),
+			state: z.enum(["OPEN", "MERGED", "DECLINED", "SUPERSEDED"]),
+			draft: z.boolean().optional(),
+			updated_on: z.string(),
+			links: z.object({ html: z.object({ href: z.string() }) }),
+		}),
+	),
+});
+
+const BBStatusesResponseSchema = z.object({
+	values: z.array(
+		z.object({
+			name: z.string().nullable().optional(),
+			key: z.string(),
+			state: z.enum(["SUCCESSFUL", "FAILED", "INPROGRESS", "STOPPED"]),
+			url: z.string().optional(),
+		}),
+	),
+});
+
+const BBUserResponseSchema = z.object({
+	username: z.string().optional(),
+	display_name: z.string(),
+});
+
+type BBPullRequest = z.infer<typeof BBPRResponseSchema>["values"][number];
+type BBStatus = z.infer<typeof BBStatusesResponseSchema>["values"][number];
+
+const cache = new Map<string, { data: GitHubStatus; timestamp: number }>();
+const CACHE_TTL_MS = 10_000;
+
+async function getToken(): Promise<string | null> {
+	const env = await getShellEnvironment();
+	return env[TOKEN_ENV_VAR] || process.env[TOKEN_ENV_VAR] || null;
+}
+
+async function bitbucketApi(path: string, token: string): Promise<unknown> {
+	const response = await fetch(`${API_BASE_URL}${path}`, {
+		headers: { Authorization: `Bearer ${token}`, Accept: "application/json" },
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	if (!response.ok) {
+		throw new Error(`Bitbucket API ${path} failed: ${response.status} ${response.statusText}`);
+	}
+	return response.json();
+}
+
+async function getBitbucketAuthStatus(remote: RemoteInfo): Promise<HostingAuthStatus> {
+	const status: HostingAuthStatus = {
+		provider: "bitbucket",
+		host: remote.host,
+		method: "token",
+		available: false,
+		authenticated: false,
+		username: null,
+	};
+
+	const token = await getToken();
+	if (!token) return status;
+	status.available = true;
+
+	try {
+		const result = BBUserResponseSchema.safeParse(await bitbucketApi("/user", token));
+		if (result.success) {
+			status.authenticated = true;
+			status.username = result.data.username ?? result.data.display_name;
+		}
+	} catch (error) {
+		console.log(
+			"[Bitbucket/getAuthStatus] Not authenticated:",
+			error instanceof Error ? error.message : String(error),
+		);
+	}
+	return status;
+}
+
+async function fetchBitbucketPRStatus(
+	worktreePath: string,
+	remote: RemoteInfo,
+): Promise<GitHubStatus | null> {
+	const cached = cache.get(worktreePath);
+	if (cached && Date.now() - cached.timestamp < CACHE_TTL_MS) {
+		return cached.data;
+	}
+
+	// Without a token there's no API access; the renderer hides PR status
+	const token = await getToken();
+	if (!token) {
+		return null;
+	}
+
+	try {
+		const branchName = await getCurrentBranch(worktreePath);
+		if (!branchName) {
+			return null;
+		}
+
+		const [branchCheck, prInfo, hasBeenPushed] = await Promise.all([
+			branchExistsOnRemote({ worktreePath, branchName }),
+			getPRForBranch(remote, branchName, token),
+			branchHasBeenPushed({ worktreePath, branchName }),
+		]);
+
+		const result: GitHubStatus = {
+			pr: prInfo,
+			repoUrl: remote.webUrl,
+			branchExistsOnRemote: branchCheck.status === "exists",
+			branchHasBeenPushed: hasBeenPushed,
+			lastRefreshed: Date.now(),
+		};
+
+		cache.set(worktreePath, { data: result, timestamp: Date.now() });
+
+		return result;
+	} catch (error) {
+		console.warn(
+			"[Bitbucket/fetchPRStatus] Failed:",
+			error instanceof Error ? error.message : String(error),
+		);
+		return null;
+	}
+}
+
+async function getPRForBranch(
+	remote: RemoteInfo,
+	branch: string,
+	token: string,
+): Promise<GitHubStatus["pr"]> {
+	const query = new URLSearchParams({
+		q: `source.branch.name="${branch.replace(/"/g, '\\"')}"`,
+		sort: "-updated_on",
+		pagelen: "1",
+	});
+	// Only open pull requests are listed unless states are asked for
+	for (const state of ["OPEN", "MERGED", "DECLINED"]) {
+		query.append("state", state);
+	}
+
+	const repoPath = `/repositories/${remote.path}`;
+	const raw = await bitbucketApi(`${repoPath}/pullrequests?${query}`, token);
+	const result = BBPRResponseSchema.safeParse(raw);
+	if (!result.success) {
+		console.error("[Bitbucket] PR schema validation failed:", result.error);
+		throw new Error("PR schema validation failed");
+	}
+
+	const pr = result.data.values[0];
+	if (!pr) {
+		return null;
+	}
+
+	const statuses = BBStatusesResponseSchema.safeParse(
+		await bitbucketApi(`${repoPath}/pullrequests/${pr.id}/statuses`, token),
+	);
+	const checks = statuses.success ? statuses.data.values.map(toCheckItem) : [];
+
+	return {
+		number: pr.id,
+		title: pr.title,
+		url: pr.links.html.href,
+		state: mapPRState(pr),
+		mergedAt: pr.state === "MERGED" ? new Date(pr.updated_on).getTime() : undefined,
+		// Line counts need the diffstat endpoint, one page per 500 files; not worth it here
+		additions: 0,
+		deletions: 0,
+		reviewDecision: "pending",
+		checksStatus: computeChecksStatus(checks),
+		checks,
+	};
+}
+
+function mapPRState(pr: BBPullRequest): NonNullable<GitHubStatus["pr"]>["state"] {
+	if (pr.state === "MERGED") return "merged";
+	if (pr.state === "DECLINED" || pr.state === "SUPERSEDED") return "closed";
+	if (pr.draft) return "draft";
+	return "open";
+}
+
+function toCheckItem(status: BBStatus): CheckItem {
+	let checkStatus: CheckItem["status"];
+	if (status.state === "SUCCESSFUL") {
+		checkStatus = "success";
+	} else if (status.state === "FAILED") {
+		checkStatus = "failure";
+	} else if (status.state === "STOPPED") {
+		checkStatus = "cancelled";
+	} else {
+		checkStatus = "pending";
+	}
+	return { name: status.name || status.key, status: checkStatus, url: status.url };
+}
+
+function computeChecksStatus(checks: CheckItem[]): NonNullable<GitHubStatus["pr"]>["checksStatus"] {
+	if (checks.length === 0) return "none";
+	if (checks.some((check) => check.status === "failure")) return "failure";
+	if (checks.some((check) => check.status === "pending")) return "pending";
+	return "success";
+}
+
+export const bitbucketProvider: HostingProvider = {
+	id: "bitbucket",
+	getAuthStatus: getBitbucketAuthStatus,
+	fetchPRStatus: fetchBitbucketPRStatus,
+};
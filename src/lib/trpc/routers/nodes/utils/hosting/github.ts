@@ -0,0 +1,164 @@
+import type { GitHubStatus } from "lib/local-db";
+import { branchExistsOnRemote, branchHasBeenPushed, getCurrentBranch } from "../git";
+import { fetchGitHubPRStatus } from "../github";
+import { getGhEnvForPath, getGitHubTokenForPath } from "../github/accounts";
+import { fetchGitHubUserWithToken, fetchPRForBranchWithToken, getGitHubToken } from "../github/api";
+import { execWithShellEnv, isCommandNotFound } from "../shell-env";
+import type { RemoteInfo } from "./remote";
+import type { HostingAuthStatus, HostingProvider } from "./types";
+
+/**
+ * gh is used whenever it's installed and signed in. Otherwise a personal
+ * access token from the secret store is used against the REST API.
+ */
+
+const GH_CHECK_TTL_MS = 60_000;
+let ghInstalled: { value: boolean; checkedAt: number } | null = null;
+
+const tokenStatusCache = new Map<string, { data: GitHubStatus; timestamp: number }>();
+const CACHE_TTL_MS = 10_000;
+
+async function isGhInstalled(): Promise<boolean> {
+	if (ghInstalled && Date.now() - ghInstalled.checkedAt < GH_CHECK_TTL_MS) {
+		return ghInstalled.value;
+	}
+
+	let value = true;
+	try {
+		await execWithShellEnv("gh", ["--version"]);
+	} catch (error) {
+		value = !isCommandNotFound(error);
+	}
+	ghInstalled = { value, checkedAt: Date.now() };
+	return value;
+}
+
+async function getGhUser(host: string, repoPath: string): Promise<string | null> {
+	try {
+		const { stdout } = await execWithShellEnv(
+			"gh",
+			["api", "user", "--hostname", host, "--jq", ".login"],
+			{ env: getGhEnvForPath(repoPath) },
+		);
+		return stdout.trim() || null;
+	} catch (error) {
+		console.log(
+			"[GitHub/getAuthStatus] gh not authenticated:",
+			error instanceof Error ? error.message : String(error),
+		);
+		return null;
+	}
+}
+
+async function getGitHubAuthStatus(
+	remote: RemoteInfo,
+	repoPath: string,
+): Promise<HostingAuthStatus> {
+	const cliInstalled = await isGhInstalled();
+	const username = cliInstalled ? await getGhUser(remote.host, repoPath) : null;
+	if (username) {
+		return {
+			provider: "github",
+			host: remote.host,
+			method: "cli",
+			available: true,
+			authenticated: true,
+			username,
+		};
+	}
+
+	const token = getGitHubTokenForPath(repoPath) ?? getGitHubToken();
+	if (!token) {
+		return {
+			provider: "github",
+			host: remote.host,
+			method: "cli",
+			available: cliInstalled,
+			authenticated: false,
+			username: null,
+		};
+	}
+
+	const status: HostingAuthStatus = {
+		provider: "github",
+		host: remote.host,
+		method: "token",
+		available: true,
+		authenticated: false,
+		username: null,
+	};
+	try {
+		status.username = await fetchGitHubUserWithToken(token, remote.host);
+		status.authenticated = true;
+	} catch (error) {
+		console.log(
+			"[GitHub/getAuthStatus] Token rejected:",
+			error instanceof Error ? error.message : String(error),
+		);
+	}
+	return status;
+}
+
+async function fetchGitHubPRStatusWithToken(
+	worktreePath: string,
+	remote: RemoteInfo,
+	token: string,
+): Promise<GitHubStatus | null> {
+	const cached = tokenStatusCache.get(worktreePath);
+	if (cached && Date.now() - cached.timestamp < CACHE_TTL_MS) {
+		return cached.data;
+	}
+
+	try {
+		const branchName = await getCurrentBranch(worktreePath);
+		if (!branchName) {
+			return null;
+		}
+
+		const [branchCheck, prInfo, hasBeenPushed] = await Promise.all([
+			branchExistsOnRemote({ worktreePath, branchName }),
+			fetchPRForBranchWithToken({
+				host: remote.host,
+				repoPath: remote.path,
+				branch: branchName,
+				token,
+			}),
+			branchHasBeenPushed({ worktreePath, branchName }),
+		]);
+
+		const result: GitHubStatus = {
+			pr: prInfo,
+			repoUrl: remote.webUrl,
+			branchExistsOnRemote: branchCheck.status === "exists",
+			branchHasBeenPushed: hasBeenPushed,
+			lastRefreshed: Date.now(),
+		};
+
+		tokenStatusCache.set(worktreePath, { data: result, timestamp: Date.now() });
+
+		return result;
+	} catch (error) {
+		console.warn(
+			"[GitHub/fetchPRStatusWithToken] Failed:",
+			error instanceof Error ? error.message : String(error),
+		);
+		return null;
+	}
+}
+
+async function fetchStatus(worktreePath: string, remote: RemoteInfo): Promise<GitHubStatus | null> {
+	if (await isGhInstalled()) {
+		// gh resolves the repository itself, including fork PRs pushed to another remote
+		const status = await fetchGitHubPRStatus(worktreePath);
+		if (status) return status;
+	}
+
+	const token = getGitHubTokenForPath(worktreePath) ?? getGitHubToken();
+	return token ? fetchGitHubPRStatusWithToken(worktreePath, remote, token) : null;
+}
+
+export const githubProvider: HostingProvider = {
+	id: "github",
+	getAuthStatus: getGitHubAuthStatus,
+	fetchPRStatus: fetchStatus,
+};
@@ -0,0 +1,185 @@
+import type { CheckItem, GitHubStatus } from "lib/local-db";
+import { z } from "zod";
+import { branchExistsOnRemote, branchHasBeenPushed, getCurrentBranch } from "../git";
+import { execWithShellEnv, isCommandNotFound } from "../shell-env";
+import type { RemoteInfo } from "./remote";
+import type { HostingAuthStatus, HostingProvider } from "./types";
+
+// Fields of the GitLab merge request API object printed by `glab mr view --output json`
+const GLMRResponseSchema = z.object({
+	iid: z.number(),
+	title: z.string(),
+	web_url: z.string(),
+	state: z.enum(["opened", "closed", "merged", "locked"]),
+	draft: z.boolean().optional(),
+	merged_at: z.string().nullable().optional(),
+	head_pipeline: z
+		.object({
+			status: z.string(),
+			web_url: z.string().optional(),
+		})
+		.nullable()
+		.optional(),
+});
+
+const GLUserResponseSchema = z.object({
+	username: z.string(),
+});
+
+type GLMRResponse = z.infer<typeof GLMRResponseSchema>;
+
+const cache = new Map<string, { data: GitHubStatus; timestamp: number }>();
+const CACHE_TTL_MS = 10_000;
+
+/** glab's error when the current branch has no merge request */
+const NO_MR_PATTERN = /no (open )?merge requests? (available|found)/i;
+
+async function getGitLabAuthStatus(remote: RemoteInfo): Promise<HostingAuthStatus> {
+	const status: HostingAuthStatus = {
+		provider: "gitlab",
+		host: remote.host,
+		method: "cli",
+		available: true,
+		authenticated: false,
+		username: null,
+	};
+
+	try {
+		const { stdout } = await execWithShellEnv("glab", ["api", "user", "--hostname", remote.host]);
+		const result = GLUserResponseSchema.safeParse(JSON.parse(stdout));
+		if (result.success) {
+			status.authenticated = true;
+			status.username = result.data.username;
+		}
+	} catch (error) {
+		if (isCommandNotFound(error)) {
+			status.available = false;
+		} else {
+			console.log(
+				"[GitLab/getAuthStatus] Not authenticated:",
+				error instanceof Error ? error.message : String(error),
+			);
+		}
+	}
+	return status;
+}
+
+async function fetchGitLabMRStatus(
+	worktreePath: string,
+	remote: RemoteInfo,
+): Promise<GitHubStatus | null> {
+	const cached = cache.get(worktreePath);
+	if (cached && Date.now() - cached.timestamp < CACHE_TTL_MS) {
+		return cached.data;
+	}
+
+	try {
+		const branchName = await getCurrentBranch(worktreePath);
+		if (!branchName) {
+			return null;
+		}
+
+		const [branchCheck, mrInfo, hasBeenPushed] = await Promise.all([
+			branchExistsOnRemote({ worktreePath, branchName }),
+			getMRForBranch(worktreePath),
+			branchHasBeenPushed({ worktreePath, branchName }),
+		]);
+
+		const result: GitHubStatus = {
+			pr: mrInfo,
+			repoUrl: remote.webUrl,
+			branchExistsOnRemote: branchCheck.status === "exists",
+			branchHasBeenPushed: hasBeenPushed,
+			lastRefreshed: Date.now(),
+		};
+
+		cache.set(worktreePath, { data: result, timestamp: Date.now() });
+
+		return result;
+	} catch (error) {
+		console.warn(
+			"[GitLab/fetchMRStatus] Failed:",
+			error instanceof Error ? error.message : String(error),
+		);
+		return null;
+	}
+}
+
+async function getMRForBranch(worktreePath: string): Promise<GitHubStatus["pr"]> {
+	try {
+		// Without an argument glab looks up the merge request for the current branch
+		const { stdout } = await execWithShellEnv("glab", ["mr", "view", "--output", "json"], {
+			cwd: worktreePath,
+		});
+		const raw = JSON.parse(stdout);
+		const result = GLMRResponseSchema.safeParse(raw);
+		if (!result.success) {
+			console.error("[GitLab] MR schema validation failed:", result.error);
+			throw new Error("MR schema validation failed");
+		}
+		const data = result.data;
+		const checks = parsePipeline(data.head_pipeline);
+
+		return {
+			number: data.iid,
+			title: data.title,
+			url: data.web_url,
+			state: mapMRState(data),
+			mergedAt: data.merged_at ? new Date(data.merged_at).getTime() : undefined,
+			// Line counts aren't part of the merge request object
+			additions: 0,
+			deletions: 0,
+			reviewDecision: "pending",
+			checksStatus: checks.length === 0 ? "none" : mapChecksStatus(checks[0].status),
+			checks,
+		};
+	} catch (error) {
+		if (error instanceof Error && NO_MR_PATTERN.test(error.message)) {
+			return null;
+		}
+		throw error;
+	}
+}
+
+function mapMRState(data: GLMRResponse): NonNullable<GitHubStatus["pr"]>["state"] {
+	if (data.state === "merged") return "merged";
+	if (data.state === "closed" || data.state === "locked") return "closed";
+	if (data.draft) return "draft";
+	return "open";
+}
+
+/** GitLab reports one pipeline per merge request rather than individual checks */
+function parsePipeline(pipeline: GLMRResponse["head_pipeline"]): CheckItem[] {
+	if (!pipeline) {
+		return [];
+	}
+
+	let status: CheckItem["status"];
+	if (pipeline.status === "success") {
+		status = "success";
+	} else if (pipeline.status === "failed") {
+		status = "failure";
+	} else if (pipeline.status === "skipped") {
+		status = "skipped";
+	} else if (pipeline.status === "canceled") {
+		status = "cancelled";
+	} else {
+		status = "pending";
+	}
+
+	return [{ name: "Pipeline", status, url: pipeline.web_url }];
+}
+
+function mapChecksStatus(
+	status: CheckItem["status"],
+): NonNullable<GitHubStatus["pr"]>["checksStatus"] {
+	if (status === "success" || status === "skipped") return "success";
+	if (status === "failure" || status === "cancelled") return "failure";
+	return "pending";
+}
+
+export const gitlabProvider: HostingProvider = {
+	id: "gitlab",
+	getAuthStatus: getGitLabAuthStatus,
+	fetchPRStatus: fetchGitLabMRStatus,
+};
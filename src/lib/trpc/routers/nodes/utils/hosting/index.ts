@@ -0,0 +1,51 @@
+import type { GitHubStatus } from "lib/local-db";
+import simpleGit from "simple-git";
+import { fetchGitHubPRStatus } from "../github";
+import { bitbucketProvider } from "./bitbucket";
+import { githubProvider } from "./github";
+import { gitlabProvider } from "./gitlab";
+import { detectHostingProvider, parseRemoteUrl, type RemoteInfo } from "./remote";
+import type { HostingAuthStatus, HostingProvider } from "./types";
+
+export { buildNewPullRequestUrl, type HostingProviderId, type RemoteInfo } from "./remote";
+export type { HostingAuthStatus, HostingProvider } from "./types";
+
+const PROVIDERS: HostingProvider[] = [githubProvider, gitlabProvider, bitbucketProvider];
+
+export async function getOriginRemote(repoPath: string): Promise<RemoteInfo | null> {
+	try {
+		const url = await simpleGit(repoPath).remote(["get-url", "origin"]);
+		return url ? parseRemoteUrl(url) : null;
+	} catch {
+		return null;
+	}
+}
+
+/** The provider hosting a remote, chosen from its host name */
+export function getHostingProvider(remote: RemoteInfo): HostingProvider | null {
+	const id = detectHostingProvider(remote.host);
+	return PROVIDERS.find((provider) => provider.id === id) ?? null;
+}
+
+/**
+ * Pull/merge request status for a worktree from whichever service hosts
+ * its origin. Null for hosts without a provider, which hides PR status.
+ * Without a usable origin gh gets a try, since it can resolve repositories
+ * through other remotes.
+ */
+export async function fetchPRStatus(worktreePath: string): Promise<GitHubStatus | null> {
+	const remote = await getOriginRemote(worktreePath);
+	if (!remote) {
+		return fetchGitHubPRStatus(worktreePath);
+	}
+	const provider = getHostingProvider(remote);
+	return provider ? provider.fetchPRStatus(worktreePath, remote) : null;
+}
+
+/** Null when origin isn't on a recognized hosting service */
+export async function getHostingAuthStatus(repoPath: string): Promise<HostingAuthStatus | null> {
+	const remote = await getOriginRemote(repoPath);
+	const provider = remote ? getHostingProvider(remote) : null;
+	if (!remote || !provider) return null;
+	return provider.getAuthStatus(remote, repoPath);
+}
@@ -0,0 +1,81 @@
+import { describe, expect, test } from "bun:test";
+import { buildNewPullRequestUrl, detectHostingProvider, parseRemoteUrl } from "./remote";
+
+describe("parseRemoteUrl", () => {
+	test("parses scp-like SSH remotes", () => {
+		expect(parseRemoteUrl("git@github.com:acme/widgets.git")).toEqual({
+			host: "github.com",
+			path: "acme/widgets",
+			webUrl: "https://github.com/acme/widgets",
+		});
+	});
+
+	test("parses HTTPS remotes with credentials and nested groups", () => {
+		expect(parseRemoteUrl("https://user@gitlab.com/acme/platform/api.git/")).toEqual({
+			host: "gitlab.com",
+			path: "acme/platform/api",
+			webUrl: "https://gitlab.com/acme/platform/api",
+		});
+	});
+
+	test("drops the SSH port from the web URL", () => {
+		expect(parseRemoteUrl("ssh://git@gitlab.example.com:2222/team/repo.git")?.webUrl).toBe(
+			"https://gitlab.example.com/team/repo",
+		);
+	});
+
+	test("rejects local paths and file URLs", () => {
+		expect(parseRemoteUrl("/srv/git/repo.git")).toBeNull();
+		expect(parseRemoteUrl("file:///srv/git/repo.git")).toBeNull();
+		expect(parseRemoteUrl("C:/src/repo")).toBeNull();
+	});
+});
+
+describe("detectHostingProvider", () => {
+	test("recognizes hosted and self-hosted instances", () => {
+		expect(detectHostingProvider("github.com")).toBe("github");
+		expect(detectHostingProvider("GitLab.example.com")).toBe("gitlab");
+		expect(detectHostingProvider("bitbucket.org")).toBe("bitbucket");
+		expect(detectHostingProvider("git.example.com")).toBeNull();
+	});
+});
+
+describe("buildNewPullRequestUrl", () => {
+	const remote = { host: "h", path: "acme/repo", webUrl: "https://h/acme/repo" };
+
+	test("builds a GitHub compare URL", () => {
+		expect(buildNewPullRequestUrl("github", remote, "feat/x")).toBe(
+			"https://h/acme/repo/compare/feat/x?expand=1",
+		);
+	});
+
+	test("builds a GitLab new merge request URL", () => {
+		expect(buildNewPullRequestUrl("gitlab", remote, "feat/x")).toBe(
+			"https://h/acme/repo/-/merge_requests/new?merge_request%5Bsource_branch%5D=feat%2Fx",
+		);
+	});
+
+	test("builds a Bitbucket new pull request URL", () => {
+		expect(buildNewPullRequestUrl("bitbucket", remote, "feat/x")).toBe(
+			"https://h/acme/repo/pull-requests/new?source=feat%2Fx",
+		);
+	});
+
+	test("prefills the description on GitHub and GitLab", () => {
+		expect(buildNewPullRequestUrl("github", remote, "feat/x", "Closes #12")).toBe(
+			"https://h/acme/repo/compare/feat/x?expand=1&body=Closes%20%2312",
+		);
+		expect(buildNewPullRequestUrl("gitlab", remote, "feat/x", "Closes #12")).toBe(
+			"https://h/acme/repo/-/merge_requests/new?merge_request%5Bsource_branch%5D=feat%2Fx&merge_request%5Bdescription%5D=Closes%20%2312",
+		);
+		expect(buildNewPullRequestUrl("bitbucket", remote, "feat/x", "Closes #12")).toBe(
+			"https://h/acme/repo/pull-requests/new?source=feat%2Fx",
+		);
+	});
+
+	test("falls back to a compare URL on unrecognized hosts", () => {
+		expect(buildNewPullRequestUrl(null, remote, "feat/x")).toBe(
+			"https://h/acme/repo/compare/feat/x",
+		);
+	});
+});
@@ -0,0 +1,81 @@
+export type HostingProviderId = "github" | "gitlab" | "bitbucket";
+
+export interface RemoteInfo {
+	host: string;
+	/** Repository path on the host, e.g. "owner/repo" or "group/subgroup/repo" */
+	path: string;
+	/** Browser URL of the repository */
+	webUrl: string;
+}
+
+/**
+ * Parses the URL forms git accepts for a remote: scp-like
+ * (git@host:owner/repo.git), ssh:// and http(s)://. Local paths, including
+ * Windows drive paths, aren't remotes on a hosting service and give null.
+ */
+export function parseRemoteUrl(url: string): RemoteInfo | null {
+	const trimmed = url.trim();
+
+	let host: string;
+	let path: string;
+	const scpLike = trimmed.match(/^(?:[^@/]+@)?([^:/]{2,}):(?!\/\/)(.+)$/);
+	if (scpLike) {
+		host = scpLike[1];
+		path = scpLike[2];
+	} else {
+		let parsed: URL;
+		try {
+			parsed = new URL(trimmed);
+		} catch {
+			return null;
+		}
+		if (!["http:", "https:", "ssh:", "git:"].includes(parsed.protocol)) return null;
+		// The web UI isn't served on the SSH port
+		host = parsed.protocol.startsWith("http") ? parsed.host : parsed.hostname;
+		path = decodeURIComponent(parsed.pathname);
+	}
+
+	path = path.replace(/^\/+|\/+$/g, "").replace(/\.git$/, "");
+	if (!host || !path.includes("/")) return null;
+
+	return { host, path, webUrl: `https://${host}/${path}` };
+}
+
+/** Self-hosted instances are recognized by name, e.g. gitlab.example.com */
+export function detectHostingProvider(host: string): HostingProviderId | null {
+	const name = host.toLowerCase();
+	if (name.includes("github")) return "github";
+	if (name.includes("gitlab")) return "gitlab";
+	if (name === "bitbucket.org") return "bitbucket";
+	return null;
+}
+
+/**
+ * Page for opening a pull request (merge request on GitLab) from a pushed
+ * branch. Unrecognized hosts get `/compare/<branch>`, which Gitea and
+ * Forgejo serve; elsewhere it at least lands on the repository. `body`
+ * prefills the description where the host's URL supports it.
+ */
+export function buildNewPullRequestUrl(
+	provider: HostingProviderId | null,
+	remote: RemoteInfo,
+	branch: string,
+	body?: string,
+): string {
+	const source = encodeURIComponent(branch);
+	const description = body ? encodeURIComponent(body) : "";
+	switch (provider) {
+		case "github": {
+			const query = description ? `expand=1&body=${description}` : "expand=1";
+			return `${remote.webUrl}/compare/${branch}?${query}`;
+		}
+		case "gitlab": {
+			const url = `${remote.webUrl}/-/merge_requests/new?merge_request%5Bsource_branch%5D=${source}`;
+			return description ? `${url}&merge_request%5Bdescription%5D=${description}` : url;
+		}
+		case "bitbucket":
+			return `${remote.webUrl}/pull-requests/new?source=${source}`;
+		default:
+			return `${remote.webUrl}/compare/${branch}`;
+	}
+}
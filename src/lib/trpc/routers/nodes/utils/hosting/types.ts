@@ -0,0 +1,21 @@
+import type { GitHubStatus } from "lib/local-db";
+import type { HostingProviderId, RemoteInfo } from "./remote";
+
+/** Whether the provider's API can be reached, through its CLI or an access token */
+export interface HostingAuthStatus {
+	provider: HostingProviderId;
+	host: string;
+	method: "cli" | "token";
+	/** CLI installed, or token configured */
+	available: boolean;
+	authenticated: boolean;
+	username: string | null;
+}
+
+export interface HostingProvider {
+	id: HostingProviderId;
+	/** repoPath selects the GitHub account bound to the repository, if any */
+	getAuthStatus(remote: RemoteInfo, repoPath: string): Promise<HostingAuthStatus>;
+	/** Pull/merge request state for the worktree's current branch, in the GitHub status shape */
+	fetchPRStatus(worktreePath: string, remote: RemoteInfo): Promise<GitHubStatus | null>;
+}
@@ -0,0 +1,62 @@
+import { z } from "zod";
+import { getGhEnvForPath } from "../github/accounts";
+import { execWithShellEnv } from "../shell-env";
+import { parseGitHubIssueKey } from "./references";
+import type { IssueContext, IssueProvider } from "./types";
+
+// Fields printed by `gh issue view --json number,title,body,url,state`
+const GHIssueResponseSchema = z.object({
+	number: z.number(),
+	title: z.string(),
+	body: z.string().nullable().optional(),
+	url: z.string(),
+	state: z.string(),
+});
+
+/** gh arguments selecting the issue, with --repo for issues in other repositories */
+function issueArgs(key: string): string[] {
+	const [repo, number] = key.includes("#") ? key.split("#") : [null, key];
+	return repo ? [number, "--repo", repo] : [number];
+}
+
+async function gh(args: string[], { repoPath }: IssueContext): Promise<string> {
+	const { stdout } = await execWithShellEnv("gh", args, {
+		cwd: repoPath,
+		env: getGhEnvForPath(repoPath),
+	});
+	return stdout;
+}
+
+export const githubIssues: IssueProvider = {
+	id: "github",
+	parseKey: parseGitHubIssueKey,
+
+	async fetchIssue(key, context) {
+		const stdout = await gh(
+			["issue", "view", ...issueArgs(key), "--json", "number,title,body,url,state"],
+			context,
+		);
+		const issue = GHIssueResponseSchema.parse(JSON.parse(stdout));
+		return {
+			provider: "github",
+			key,
+			url: issue.url,
+			title: issue.title,
+			description: issue.body || null,
+			state: issue.state.toLowerCase(),
+		};
+	},
+
+	async transitionIssue(key, state, context) {
+		switch (state.trim().toLowerCase()) {
+			case "closed":
+				await gh(["issue", "close", ...issueArgs(key)], context);
+				return;
+			case "open":
+				await gh(["issue", "reopen", ...issueArgs(key)], context);
+				return;
+			default:
+				throw new Error(`GitHub issues can only be "open" or "closed", not "${state}"`);
+		}
+	},
+};
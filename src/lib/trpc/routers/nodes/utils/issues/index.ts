@@ -0,0 +1,87 @@
+import type { SelectNode } from "lib/local-db";
+import { isLocalOnlyMode } from "main/lib/privacy";
+import type { IssueDetails, IssueLink, IssueProviderId } from "shared/types/issues";
+import { blockedInLocalOnlyMode, CaspianError } from "../../../../errors";
+import { getRepository } from "../db-helpers";
+import { loadSetupConfig } from "../setup";
+import { githubIssues } from "./github";
+import { jiraIssues } from "./jira";
+import { linearIssues } from "./linear";
+import type { IssueContext, IssueProvider } from "./types";
+
+export { formatIssueForPullRequest } from "./references";
+export type { IssueContext, IssueProvider } from "./types";
+
+const PROVIDERS: Record<IssueProviderId, IssueProvider> = {
+	github: githubIssues,
+	jira: jiraIssues,
+	linear: linearIssues,
+};
+
+/** The repository's issue tracker; throws when none is configured */
+export function getIssueContext(mainRepoPath: string): IssueContext {
+	const config = loadSetupConfig(mainRepoPath)?.issues;
+	if (!config) {
+		throw new CaspianError({
+			code: "CONFIG_MISSING",
+			message: "No issue tracker configured",
+			hint: "Set `issues.provider` to github, jira or linear in .caspian/config.json",
+		});
+	}
+	return { repoPath: mainRepoPath, config };
+}
+
+/** Fetches an issue by key or URL from the repository's tracker */
+export async function fetchIssue(mainRepoPath: string, reference: string): Promise<IssueDetails> {
+	if (isLocalOnlyMode()) {
+		throw blockedInLocalOnlyMode("Issue tracker access");
+	}
+	const context = getIssueContext(mainRepoPath);
+	const provider = PROVIDERS[context.config.provider];
+	const key = provider.parseKey(reference);
+	if (!key) {
+		throw new CaspianError({
+			code: "INVALID_INPUT",
+			message: `"${reference.trim()}" isn't a ${provider.id} issue key or URL`,
+		});
+	}
+
+	try {
+		return await provider.fetchIssue(key, context);
+	} catch (error) {
+		const message = error instanceof Error ? error.message : String(error);
+		throw new CaspianError({
+			code: "ISSUE_TRACKER_FAILED",
+			message: `Couldn't fetch ${key}: ${message}`,
+			cause: error,
+		});
+	}
+}
+
+export function toIssueLink(issue: IssueDetails): IssueLink {
+	return { provider: issue.provider, key: issue.key, url: issue.url, title: issue.title };
+}
+
+/**
+ * Moves a closed or deleted node's issue to the repository's
+ * transitionOnClose state. Fire-and-forget: failures are logged and never
+ * propagate into closing the node.
+ */
+export async function transitionIssueOnClose(node: SelectNode): Promise<void> {
+	const issue = node.issue;
+	const repository = issue ? getRepository(node.repositoryId) : undefined;
+	if (!issue || !repository || isLocalOnlyMode()) return;
+
+	const config = loadSetupConfig(repository.mainRepoPath)?.issues;
+	const state = config?.transitionOnClose;
+	// A link made under a previous tracker can't be moved by the current one
+	if (!config || !state || config.provider !== issue.provider) return;
+
+	try {
+		const context = { repoPath: repository.mainRepoPath, config };
+		await PROVIDERS[issue.provider].transitionIssue(issue.key, state, context);
+		console.log(`[issues] Moved ${issue.key} to ${state}`);
+	} catch (error) {
+		console.warn(`[issues] Failed to move ${issue.key} to ${state}:`, error);
+	}
+}
@@ -0,0 +1,97 @@
+import { z } from "zod";
+import { getShellEnvironment } from "../shell-env";
+import { parseJiraIssueKey } from "./references";
+import type { IssueContext, IssueProvider } from "./types";
+
+const REQUEST_TIMEOUT_MS = 15_000;
+/** Atlassian account email and API token, read from the user's shell environment */
+const EMAIL_ENV_VAR = "JIRA_EMAIL";
+const TOKEN_ENV_VAR = "JIRA_API_TOKEN";
+
+// API v2 returns descriptions as wiki text; v3 returns Atlassian Document Format
+const JiraIssueResponseSchema = z.object({
+	key: z.string(),
+	fields: z.object({
+		summary: z.string(),
+		description: z.string().nullable().optional(),
+		status: z.object({ name: z.string() }).nullable().optional(),
+	}),
+});
+
+const JiraTransitionsResponseSchema = z.object({
+	transitions: z.array(
+		z.object({
+			id: z.string(),
+			name: z.string(),
+			to: z.object({ name: z.string() }).optional(),
+		}),
+	),
+});
+
+function getSite({ config }: IssueContext): string {
+	const site = config.jiraSite?.replace(/^https?:\/\//, "").replace(/\/+$/, "");
+	if (!site) {
+		throw new Error("Set issues.jiraSite in .caspian/config.json to use Jira");
+	}
+	return site;
+}
+
+async function jiraApi(
+	context: IssueContext,
+	path: string,
+	init?: { method: "POST"; body: unknown },
+): Promise<unknown> {
+	const env = await getShellEnvironment();
+	const email = env[EMAIL_ENV_VAR] || process.env[EMAIL_ENV_VAR];
+	const token = env[TOKEN_ENV_VAR] || process.env[TOKEN_ENV_VAR];
+	if (!email || !token) {
+		throw new Error(`Set ${EMAIL_ENV_VAR} and ${TOKEN_ENV_VAR} in your shell to use Jira`);
+	}
+
+	const response = await fetch(`https://${getSite(context)}/rest/api/2${path}`, {
+		method: init?.method ?? "GET",
+		headers: {
+			Authorization: `Basic ${Buffer.from(`${email}:${token}`).toString("base64")}`,
+			Accept: "application/json",
+			"Content-Type": "application/json",
+		},
+		body: init ? JSON.stringify(init.body) : undefined,
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	if (!response.ok) {
+		throw new Error(`Jira API ${path} failed: ${response.status} ${response.statusText}`);
+	}
+	return response.status === 204 ? null : response.json();
+}
+
+export const jiraIssues: IssueProvider = {
+	id: "jira",
+	parseKey: parseJiraIssueKey,
+
+	async fetchIssue(key, context) {
+		const path = `/issue/${encodeURIComponent(key)}?fields=summary,description,status`;
+		const issue = JiraIssueResponseSchema.parse(await jiraApi(context, path));
+		return {
+			provider: "jira",
+			key: issue.key,
+			url: `https://${getSite(context)}/browse/${issue.key}`,
+			title: issue.fields.summary,
+			description: issue.fields.description || null,
+			state: issue.fields.status?.name ?? null,
+		};
+	},
+
+	// Jira moves issues through transitions; match the transition or its target status
+	async transitionIssue(key, state, context) {
+		const path = `/issue/${encodeURIComponent(key)}/transitions`;
+		const { transitions } = JiraTransitionsResponseSchema.parse(await jiraApi(context, path));
+		const wanted = state.trim().toLowerCase();
+		const transition = transitions.find(
+			(t) => t.name.toLowerCase() === wanted || t.to?.name.toLowerCase() === wanted,
+		);
+		if (!transition) {
+			throw new Error(`${key} has no transition to "${state}"`);
+		}
+		await jiraApi(context, path, { method: "POST", body: { transition: { id: transition.id } } });
+	},
+};
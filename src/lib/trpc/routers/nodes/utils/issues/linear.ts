@@ -0,0 +1,100 @@
+import { z } from "zod";
+import { getShellEnvironment } from "../shell-env";
+import { parseLinearIssueKey } from "./references";
+import type { IssueProvider } from "./types";
+
+const API_URL = "https://api.linear.app/graphql";
+const REQUEST_TIMEOUT_MS = 15_000;
+/** Personal API key read from the user's shell environment */
+const TOKEN_ENV_VAR = "LINEAR_API_KEY";
+
+const LinearIssueResponseSchema = z.object({
+	issue: z.object({
+		identifier: z.string(),
+		title: z.string(),
+		description: z.string().nullable().optional(),
+		url: z.string(),
+		state: z.object({ name: z.string() }).nullable().optional(),
+	}),
+});
+
+const LinearIssueStatesResponseSchema = z.object({
+	issue: z.object({
+		id: z.string(),
+		team: z.object({
+			states: z.object({ nodes: z.array(z.object({ id: z.string(), name: z.string() })) }),
+		}),
+	}),
+});
+
+const GraphQLResponseSchema = z.object({
+	data: z.unknown().optional(),
+	errors: z.array(z.object({ message: z.string() })).optional(),
+});
+
+async function linearQuery(query: string, variables: Record<string, unknown>): Promise<unknown> {
+	const env = await getShellEnvironment();
+	const token = env[TOKEN_ENV_VAR] || process.env[TOKEN_ENV_VAR];
+	if (!token) {
+		throw new Error(`Set ${TOKEN_ENV_VAR} in your shell to use Linear`);
+	}
+
+	const response = await fetch(API_URL, {
+		method: "POST",
+		headers: { Authorization: token, "Content-Type": "application/json" },
+		body: JSON.stringify({ query, variables }),
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	if (!response.ok) {
+		throw new Error(`Linear API failed: ${response.status} ${response.statusText}`);
+	}
+	const result = GraphQLResponseSchema.parse(await response.json());
+	if (result.errors?.length) {
+		throw new Error(`Linear API failed: ${result.errors[0].message}`);
+	}
+	return result.data;
+}
+
+export const linearIssues: IssueProvider = {
+	id: "linear",
+	parseKey: parseLinearIssueKey,
+
+	async fetchIssue(key) {
+		const data = await linearQuery(
+			`query Issue($id: String!) {
+				issue(id: $id) { identifier title description url state { name } }
+			}`,
+			{ id: key },
+		);
+		const { issue } = LinearIssueResponseSchema.parse(data);
+		return {
+			provider: "linear",
+			key: issue.identifier,
+			url: issue.url,
+			title: issue.title,
+			description: issue.description || null,
+			state: issue.state?.name ?? null,
+		};
+	},
+
+	async transitionIssue(key, state) {
+		const data = await linearQuery(
+			`query IssueStates($id: String!) {
+				issue(id: $id) { id team { states { nodes { id name } } } }
+			}`,
+			{ id: key },
+		);
+		const { issue } = LinearIssueStatesResponseSchema.parse(data);
+		const wanted = state.trim().toLowerCase();
+		const target = issue.team.states.nodes.find((s) => s.name.toLowerCase() === wanted);
+		if (!target) {
+			throw new Error(`${key}'s team has no "${state}" state`);
+		}
+		await linearQuery(
+			`mutation MoveIssue($id: String!, $stateId: String!) {
+				issueUpdate(id: $id, input: { stateId: $stateId }) { success }
+			}`,
+			{ id: issue.id, stateId: target.id },
+		);
+	},
+};
@@ -0,0 +1,71 @@
+import { describe, expect, test } from "bun:test";
+import {
+	formatIssueForPullRequest,
+	parseGitHubIssueKey,
+	parseJiraIssueKey,
+	parseLinearIssueKey,
+} from "./references";
+
+describe("parseGitHubIssueKey", () => {
+	test("parses numbers in the repository", () => {
+		expect(parseGitHubIssueKey("123")).toBe("123");
+		expect(parseGitHubIssueKey(" #45 ")).toBe("45");
+	});
+
+	test("qualifies issues from URLs and other repositories", () => {
+		expect(parseGitHubIssueKey("https://github.com/acme/widgets/issues/7")).toBe(
+			"acme/widgets#7",
+		);
+		expect(parseGitHubIssueKey("acme/widgets#7")).toBe("acme/widgets#7");
+	});
+
+	test("rejects other references", () => {
+		expect(parseGitHubIssueKey("PROJ-1")).toBeNull();
+		expect(parseGitHubIssueKey("https://github.com/acme/widgets/pull/7")).toBeNull();
+	});
+});
+
+describe("parseJiraIssueKey", () => {
+	test("parses keys and URLs", () => {
+		expect(parseJiraIssueKey("proj-12")).toBe("PROJ-12");
+		expect(parseJiraIssueKey("https://acme.atlassian.net/browse/PROJ-12")).toBe("PROJ-12");
+		const boardUrl = "https://acme.atlassian.net/jira/software/projects/PROJ?selectedIssue=PROJ-3";
+		expect(parseJiraIssueKey(boardUrl)).toBe("PROJ-3");
+	});
+
+	test("rejects other references", () => {
+		expect(parseJiraIssueKey("#12")).toBeNull();
+	});
+});
+
+describe("parseLinearIssueKey", () => {
+	test("parses identifiers and URLs", () => {
+		expect(parseLinearIssueKey("ENG-9")).toBe("ENG-9");
+		expect(parseLinearIssueKey("https://linear.app/acme/issue/eng-9/fix-login")).toBe("ENG-9");
+	});
+
+	test("rejects other references", () => {
+		expect(parseLinearIssueKey("https://example.com/issue/ENG-9")).toBeNull();
+	});
+});
+
+describe("formatIssueForPullRequest", () => {
+	test("closes GitHub issues", () => {
+		const base = { provider: "github" as const, url: "u", title: null };
+		expect(formatIssueForPullRequest({ ...base, key: "12" })).toBe("Closes #12");
+		expect(formatIssueForPullRequest({ ...base, key: "acme/widgets#12" })).toBe(
+			"Closes acme/widgets#12",
+		);
+	});
+
+	test("links other trackers", () => {
+		expect(
+			formatIssueForPullRequest({
+				provider: "jira",
+				key: "PROJ-1",
+				url: "https://acme.atlassian.net/browse/PROJ-1",
+				title: null,
+			}),
+		).toBe("PROJ-1: https://acme.atlassian.net/browse/PROJ-1");
+	});
+});
@@ -0,0 +1,46 @@
+import type { IssueLink } from "shared/types/issues";
+
+/**
+ * GitHub issue key from "123", "#123", "acme/widgets#123" or an issue URL.
+ * Issues in other repositories keep their "owner/repo#" qualifier.
+ */
+export function parseGitHubIssueKey(reference: string): string | null {
+	const trimmed = reference.trim();
+	const local = trimmed.match(/^#?(\d+)$/);
+	if (local) return local[1];
+
+	const qualified =
+		trimmed.match(/^([\w.-]+\/[\w.-]+)#(\d+)$/) ??
+		trimmed.match(/^https?:\/\/[^/]+\/([\w.-]+\/[\w.-]+)\/issues\/(\d+)(?:[/?#].*)?$/);
+	return qualified ? `${qualified[1]}#${qualified[2]}` : null;
+}
+
+/** Jira issue key from "PROJ-123" or a browse/board URL */
+export function parseJiraIssueKey(reference: string): string | null {
+	const trimmed = reference.trim();
+	const match =
+		trimmed.match(/^([A-Za-z][A-Za-z0-9_]*-\d+)$/) ??
+		trimmed.match(/^https?:\/\/.*\/browse\/([A-Za-z][A-Za-z0-9_]*-\d+)(?:[/?#].*)?$/) ??
+		trimmed.match(/^https?:\/\/.*[?&]selectedIssue=([A-Za-z][A-Za-z0-9_]*-\d+)/);
+	return match ? match[1].toUpperCase() : null;
+}
+
+/** Linear issue identifier from "ENG-123" or an issue URL */
+export function parseLinearIssueKey(reference: string): string | null {
+	const trimmed = reference.trim();
+	const match =
+		trimmed.match(/^([A-Za-z][A-Za-z0-9]*-\d+)$/) ??
+		trimmed.match(/^https?:\/\/linear\.app\/[^/]+\/issue\/([A-Za-z][A-Za-z0-9]*-\d+)(?:[/?#].*)?$/);
+	return match ? match[1].toUpperCase() : null;
+}
+
+/**
+ * Line for a pull request body. GitHub issues get a closing keyword so
+ * merging the PR closes them; other trackers get a plain link.
+ */
+export function formatIssueForPullRequest(issue: IssueLink): string {
+	if (issue.provider === "github") {
+		return issue.key.includes("#") ? `Closes ${issue.key}` : `Closes #${issue.key}`;
+	}
+	return `${issue.key}: ${issue.url}`;
+}
@@ -0,0 +1,17 @@
+import type { IssueTrackerConfig } from "shared/types/config";
+import type { IssueDetails, IssueProviderId } from "shared/types/issues";
+
+export interface IssueContext {
+	/** Main repository path; gh resolves GitHub issues through its remotes */
+	repoPath: string;
+	config: IssueTrackerConfig;
+}
+
+export interface IssueProvider {
+	id: IssueProviderId;
+	/** Issue key from a key or web URL; null when it isn't one of this tracker's */
+	parseKey(reference: string): string | null;
+	fetchIssue(key: string, context: IssueContext): Promise<IssueDetails>;
+	/** Moves the issue to a state by name; throws when there's no such state */
+	transitionIssue(key: string, state: string, context: IssueContext): Promise<void>;
+}
@@ -1,8 +1,13 @@
 import { eq } from "drizzle-orm";
 import { repositories, worktrees } from "lib/local-db";
 import { track } from "main/lib/analytics";
+import { recordAuditEvent } from "main/lib/audit";
+import { changesCache } from "main/lib/changes-cache";
 import { localDb } from "main/lib/local-db";
+import { getNodeHookParams, runNodePostHook } from "main/lib/node-hooks";
 import { nodeInitManager } from "main/lib/node-init-manager";
+import { getCommitConfigForPath } from "../../repositories/utils/commit-signing";
+import { getNode, getNodeWithRelations } from "./db-helpers";
 import {
 	branchExistsOnRemote,
 	createWorktree,
@@ -32,6 +37,8 @@ export interface NodeInitParams {
 	useExistingBranch?: boolean;
 	/** If true, skip worktree creation (worktree already exists on disk) */
 	skipWorktreeCreation?: boolean;
+	/** Cone-mode sparse-checkout directories; omitted for a full checkout */
+	sparsePatterns?: string[] | null;
 }
 
 /**
@@ -51,6 +58,7 @@ export async function initializeNodeWorktree({
 	mainRepoPath,
 	useExistingBranch,
 	skipWorktreeCreation,
+	sparsePatterns,
 }: NodeInitParams): Promise<void> {
 	const manager = nodeInitManager;
 
@@ -76,6 +84,7 @@ export async function initializeNodeWorktree({
 					mainRepoPath,
 					branch,
 					worktreePath,
+					sparsePatterns,
 				});
 				manager.markWorktreeCreated(nodeId);
 			}
@@ -115,6 +124,17 @@ export async function initializeNodeWorktree({
 				.run();
 
 			manager.updateProgress(nodeId, "ready", "Ready");
+			changesCache.startWatching(worktreePath, getNode(nodeId)?.scopePath ?? null);
+			recordAuditEvent({
+				type: "worktree_created",
+				nodeId,
+				repositoryId,
+				summary: skipWorktreeCreation
+					? `Opened existing worktree on ${branch}`
+					: `Created worktree for existing branch ${branch}`,
+				data: { worktreePath, branch },
+			});
+			runNodePostHook("post-node-create", getNodeHookParams(nodeId));
 
 			track("node_initialized", {
 				node_id: nodeId,
@@ -298,7 +318,11 @@ export async function initializeNodeWorktree({
 					);
 
 					try {
-						await initializeEmptyRepo(mainRepoPath, effectiveBaseBranch);
+						await initializeEmptyRepo(
+							mainRepoPath,
+							effectiveBaseBranch,
+							getCommitConfigForPath(mainRepoPath),
+						);
 						console.log(
 							`[node-init] Successfully initialized empty repo with branch "${effectiveBaseBranch}"`,
 						);
@@ -432,7 +456,7 @@ export async function initializeNodeWorktree({
 		}
 
 		manager.updateProgress(nodeId, "creating_worktree", "Creating git worktree...");
-		await createWorktree(mainRepoPath, branch, worktreePath, startPoint);
+		await createWorktree({ mainRepoPath, branch, worktreePath, startPoint, sparsePatterns });
 		manager.markWorktreeCreated(nodeId);
 
 		if (manager.isCancellationRequested(nodeId)) {
@@ -471,6 +495,15 @@ export async function initializeNodeWorktree({
 			.run();
 
 		manager.updateProgress(nodeId, "ready", "Ready");
+		changesCache.startWatching(worktreePath, getNode(nodeId)?.scopePath ?? null);
+		recordAuditEvent({
+			type: "worktree_created",
+			nodeId,
+			repositoryId,
+			summary: `Created worktree on ${branch} from ${startPoint}`,
+			data: { worktreePath, branch, baseBranch: effectiveBaseBranch, startPoint },
+		});
+		runNodePostHook("post-node-create", getNodeHookParams(nodeId));
 
 		track("node_initialized", {
 			node_id: nodeId,
@@ -498,3 +531,47 @@ export async function initializeNodeWorktree({
 		manager.releaseRepositoryLock(repositoryId);
 	}
 }
+
+/**
+ * Restart initialization for a node whose previous attempt failed.
+ * Kicks off the background job and returns immediately; progress streams via nodeInitManager.
+ *
+ * @throws Error if the node, its worktree, or its repository cannot be found
+ */
+export function retryNodeInit(nodeId: string): void {
+	const relations = getNodeWithRelations(nodeId);
+
+	if (!relations) {
+		throw new Error("Node not found");
+	}
+
+	const { node, worktree, repository } = relations;
+
+	if (node.deletingAt) {
+		throw new Error("Cannot retry initialization on a node being deleted");
+	}
+
+	if (!worktree) {
+		throw new Error("Worktree not found");
+	}
+
+	if (!repository) {
+		throw new Error("Repository not found");
+	}
+
+	nodeInitManager.clearJob(nodeId);
+	nodeInitManager.startJob(nodeId, node.repositoryId);
+
+	// baseBranch is treated as explicit on retry to prevent further auto-correction
+	initializeNodeWorktree({
+		nodeId,
+		repositoryId: node.repositoryId,
+		worktreeId: worktree.id,
+		worktreePath: worktree.path,
+		branch: worktree.branch,
+		baseBranch: worktree.baseBranch ?? repository.defaultBranch ?? "main",
+		baseBranchWasExplicit: true,
+		mainRepoPath: repository.mainRepoPath,
+		sparsePatterns: node.sparsePatterns,
+	});
+}
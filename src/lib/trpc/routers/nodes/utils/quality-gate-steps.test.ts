@@ -0,0 +1,36 @@
+import { describe, expect, test } from "bun:test";
+import { getGateStatus, getGateSteps, hasGateSteps } from "./quality-gate-steps";
+
+describe("getGateSteps", () => {
+	test("orders steps lint, test, build and skips missing commands", () => {
+		const steps = getGateSteps({ build: "bun run build", lint: " bun run lint " });
+		expect(steps.map((step) => [step.step, step.command, step.status])).toEqual([
+			["lint", "bun run lint", "pending"],
+			["test", "", "skipped"],
+			["build", "bun run build", "pending"],
+		]);
+	});
+});
+
+describe("hasGateSteps", () => {
+	test("requires at least one non-empty command", () => {
+		expect(hasGateSteps(undefined)).toBe(false);
+		expect(hasGateSteps({ required: true })).toBe(false);
+		expect(hasGateSteps({ test: "  " })).toBe(false);
+		expect(hasGateSteps({ test: "bun test" })).toBe(true);
+	});
+});
+
+describe("getGateStatus", () => {
+	test("derives overall status from steps", () => {
+		const steps = getGateSteps({ lint: "lint", test: "test" });
+		expect(getGateStatus(steps)).toBe("running");
+
+		steps[0].status = "passed";
+		steps[1].status = "passed";
+		expect(getGateStatus(steps)).toBe("passed");
+
+		steps[1].status = "failed";
+		expect(getGateStatus(steps)).toBe("failed");
+	});
+});
@@ -0,0 +1,37 @@
+import type { QualityGateConfig } from "shared/types/config";
+import {
+	QUALITY_GATE_STEPS,
+	type QualityGateStatus,
+	type QualityGateStepResult,
+} from "shared/types/quality-gate";
+
+/**
+ * Builds the initial step list from config. Steps without a command are skipped.
+ */
+export function getGateSteps(config: QualityGateConfig): QualityGateStepResult[] {
+	return QUALITY_GATE_STEPS.map((step) => {
+		const command = config[step]?.trim() ?? "";
+		return {
+			step,
+			command,
+			status: command ? "pending" : "skipped",
+			exitCode: null,
+			durationMs: null,
+		};
+	});
+}
+
+export function hasGateSteps(config: QualityGateConfig | undefined): config is QualityGateConfig {
+	return !!config && QUALITY_GATE_STEPS.some((step) => !!config[step]?.trim());
+}
+
+/**
+ * Overall gate status: failed as soon as any step fails, passed once nothing is left to run.
+ */
+export function getGateStatus(steps: QualityGateStepResult[]): QualityGateStatus {
+	if (steps.some((step) => step.status === "failed")) return "failed";
+	if (steps.some((step) => step.status === "pending" || step.status === "running")) {
+		return "running";
+	}
+	return "passed";
+}
@@ -0,0 +1,282 @@
+import { desc, eq } from "drizzle-orm";
+import { gateRuns, type SelectGateRun } from "lib/local-db";
+import { recordAuditEvent } from "main/lib/audit";
+import { localDb } from "main/lib/local-db";
+import { operationManager } from "main/lib/operation-manager";
+import { qualityGateManager } from "main/lib/quality-gate-manager";
+import { redactOutput } from "main/lib/redaction";
+import type { QualityGateConfig } from "shared/types/config";
+import type { QualityGateStepResult } from "shared/types/quality-gate";
+import { findNodeByPath, getNodeWithRelations, getRepository } from "./db-helpers";
+import { getHeadSha } from "./git";
+import { getGateStatus, getGateSteps, hasGateSteps } from "./quality-gate-steps";
+import { runShellCommand, tailOutput } from "./run-command";
+import { findOutOfScopeChanges } from "./scope-check";
+import { loadSetupConfig } from "./setup";
+import { DEFAULT_TEST_TIMEOUT_MS, executeTestRun } from "./test-run";
+
+const STEP_TIMEOUT_MS = DEFAULT_TEST_TIMEOUT_MS;
+
+export class GateInProgressError extends Error {
+	constructor(nodeId: string) {
+		super(`A quality gate is already running for node ${nodeId}`);
+		this.name = "GateInProgressError";
+	}
+}
+
+interface NodeScope {
+	path: string;
+	baseBranch: string;
+}
+
+function getNodeScope(nodeId: string): NodeScope | null {
+	const relations = getNodeWithRelations(nodeId);
+	if (!relations?.node.scopePath) return null;
+	return {
+		path: relations.node.scopePath,
+		baseBranch: relations.worktree?.baseBranch ?? relations.repository?.defaultBranch ?? "main",
+	};
+}
+
+async function runScopeStep(
+	cwd: string,
+	scope: NodeScope,
+	step: QualityGateStepResult,
+): Promise<QualityGateStepResult> {
+	const startedAt = Date.now();
+	const outside = await findOutOfScopeChanges({
+		cwd,
+		scopePath: scope.path,
+		baseBranch: scope.baseBranch,
+	});
+	return {
+		...step,
+		status: outside.length === 0 ? "passed" : "failed",
+		exitCode: outside.length === 0 ? 0 : 1,
+		durationMs: Date.now() - startedAt,
+		output:
+			outside.length === 0
+				? `All changes are inside ${scope.path}`
+				: tailOutput(`Changed outside ${scope.path}:\n${outside.join("\n")}`),
+	};
+}
+
+async function runStep({
+	nodeId,
+	cwd,
+	step,
+	scope,
+}: {
+	nodeId: string;
+	cwd: string;
+	step: QualityGateStepResult;
+	scope: NodeScope | null;
+}): Promise<QualityGateStepResult> {
+	if (step.step === "scope") {
+		return scope ? runScopeStep(cwd, scope, step) : { ...step, status: "skipped" };
+	}
+
+	if (step.step === "test") {
+		const run = await executeTestRun({ nodeId, command: step.command, cwd });
+		return {
+			...step,
+			status: run.status === "passed" ? "passed" : "failed",
+			exitCode: run.exitCode,
+			durationMs: run.durationMs,
+			output: run.output,
+			testRunId: run.id,
+		};
+	}
+
+	const result = await runShellCommand({ command: step.command, cwd, timeoutMs: STEP_TIMEOUT_MS });
+	return {
+		...step,
+		status: result.exitCode === 0 ? "passed" : "failed",
+		exitCode: result.exitCode,
+		durationMs: result.durationMs,
+		output: redactOutput(tailOutput(result.output)),
+	};
+}
+
+function failInterruptedRun({
+	run,
+	steps,
+}: {
+	run: SelectGateRun;
+	steps: QualityGateStepResult[];
+}): void {
+	const settled = steps.map((step): QualityGateStepResult => {
+		if (step.status === "running") return { ...step, status: "failed" };
+		if (step.status === "pending") return { ...step, status: "skipped" };
+		return step;
+	});
+	try {
+		localDb
+			.update(gateRuns)
+			.set({ status: "failed", steps: settled, completedAt: Date.now() })
+			.where(eq(gateRuns.id, run.id))
+			.run();
+	} catch (error) {
+		console.error(`[quality-gate] Could not finalize gate run ${run.id}:`, error);
+	}
+	qualityGateManager.updateProgress({
+		nodeId: run.nodeId,
+		runId: run.id,
+		status: "failed",
+		steps: settled,
+	});
+}
+
+/**
+ * Runs the configured lint, test and build commands in order, stopping at the
+ * first failure. Progress is streamed through the quality gate manager and the
+ * final result is recorded in gate_runs.
+ */
+export async function runQualityGate({
+	nodeId,
+	cwd,
+	config,
+}: {
+	nodeId: string;
+	cwd: string;
+	config: QualityGateConfig;
+}): Promise<SelectGateRun> {
+	if (!qualityGateManager.begin(nodeId)) {
+		throw new GateInProgressError(nodeId);
+	}
+
+	const operation = operationManager.begin({
+		kind: "quality_gate",
+		title: "Quality gate",
+		subjectId: nodeId,
+		cancellable: false,
+	});
+	let run: SelectGateRun | null = null;
+	let steps: QualityGateStepResult[] = [];
+	let completed = false;
+	try {
+		steps = getGateSteps(config);
+		// Scoped nodes must not change files outside their subdirectory
+		const scope = getNodeScope(nodeId);
+		if (scope) {
+			steps.unshift({
+				step: "scope",
+				command: `Changes stay inside ${scope.path}`,
+				status: "pending",
+				exitCode: null,
+				durationMs: null,
+			});
+		}
+		const started = localDb
+			.insert(gateRuns)
+			.values({ nodeId, headSha: await getHeadSha(cwd), status: "running", steps })
+			.returning()
+			.get();
+		run = started;
+
+		const report = () => {
+			qualityGateManager.updateProgress({
+				nodeId,
+				runId: started.id,
+				status: getGateStatus(steps),
+				steps: steps.map((step) => ({ ...step })),
+			});
+			const current = steps.find((step) => step.status === "running");
+			const finished = steps.filter((step) => step.status !== "pending" && step !== current);
+			operation.report(
+				current ? `Running ${current.step}` : `${finished.length}/${steps.length} steps`,
+				steps.length > 0 ? Math.round((finished.length / steps.length) * 100) : null,
+			);
+		};
+		report();
+
+		for (let i = 0; i < steps.length; i++) {
+			if (steps[i].status !== "pending") continue;
+
+			// Later steps aren't worth running once one has failed
+			if (getGateStatus(steps) === "failed") {
+				steps[i] = { ...steps[i], status: "skipped" };
+				continue;
+			}
+
+			steps[i] = { ...steps[i], status: "running" };
+			report();
+			steps[i] = await runStep({ nodeId, cwd, step: steps[i], scope });
+			report();
+		}
+		report();
+
+		const status = getGateStatus(steps);
+		console.log(`[quality-gate] Node ${nodeId}: ${status}`);
+		operation.finish(status === "passed" ? "succeeded" : "failed");
+
+		recordAuditEvent({
+			type: "gate_run",
+			nodeId,
+			summary: `Quality gate ${status}`,
+			data: {
+				gateRunId: started.id,
+				headSha: started.headSha,
+				steps: steps.map((step) => ({ step: step.step, status: step.status })),
+			},
+		});
+
+		const completedRun = localDb
+			.update(gateRuns)
+			.set({ status, steps, completedAt: Date.now() })
+			.where(eq(gateRuns.id, started.id))
+			.returning()
+			.get();
+		completed = true;
+		return completedRun;
+	} catch (error) {
+		operation.finish("failed", error instanceof Error ? error.message : String(error));
+		throw error;
+	} finally {
+		// A step that throws would otherwise leave the row "running" forever
+		if (run && !completed) {
+			failInterruptedRun({ run, steps });
+		}
+		qualityGateManager.finish(nodeId);
+	}
+}
+
+export function getLatestGateRun(nodeId: string): SelectGateRun | undefined {
+	return localDb
+		.select()
+		.from(gateRuns)
+		.where(eq(gateRuns.nodeId, nodeId))
+		.orderBy(desc(gateRuns.createdAt))
+		.limit(1)
+		.get();
+}
+
+/**
+ * Throws unless the latest gate run for the node passed against the current HEAD.
+ */
+export async function assertGatePassed(nodeId: string, cwd: string): Promise<void> {
+	const latest = getLatestGateRun(nodeId);
+	if (!latest || latest.status !== "passed") {
+		throw new Error("The quality gate must pass before this node can be submitted for review");
+	}
+
+	const headSha = await getHeadSha(cwd);
+	if (latest.headSha !== headSha) {
+		throw new Error("New commits since the quality gate last passed. Run the gate again.");
+	}
+}
+
+/**
+ * Enforces a required gate for the node working in workspacePath.
+ * No-op unless the repository config sets `gate.required` with at least one command.
+ */
+export async function assertRequiredGatePassed(workspacePath: string): Promise<void> {
+	const node = findNodeByPath(workspacePath);
+	if (!node) return;
+
+	const repository = getRepository(node.repositoryId);
+	const gate = repository ? loadSetupConfig(repository.mainRepoPath)?.gate : undefined;
+	if (!gate?.required || !hasGateSteps(gate)) return;
+
+	await assertGatePassed(node.id, workspacePath);
+}
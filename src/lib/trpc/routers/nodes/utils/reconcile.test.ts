@@ -0,0 +1,54 @@
+import { describe, expect, test } from "bun:test";
+import { findUnimportedWorktrees, parseWorktreeList } from "./reconcile";
+
+const WORKTREE_LIST = [
+	"worktree /repo",
+	"HEAD 1111111111111111111111111111111111111111",
+	"branch refs/heads/main",
+	"",
+	"worktree /home/me/.caspian/worktrees/repo/feature-a",
+	"HEAD 2222222222222222222222222222222222222222",
+	"branch refs/heads/feature-a",
+	"",
+	"worktree /home/me/.caspian/worktrees/repo/detached",
+	"HEAD 3333333333333333333333333333333333333333",
+	"detached",
+	"",
+	"worktree /home/me/.caspian/worktrees/repo/gone",
+	"HEAD 4444444444444444444444444444444444444444",
+	"branch refs/heads/gone",
+	"prunable gitdir file points to non-existent location",
+	"",
+	"worktree /elsewhere/feature-b",
+	"HEAD 5555555555555555555555555555555555555555",
+	"branch refs/heads/feature-b",
+	"",
+].join("\n");
+
+describe.skipIf(process.platform === "win32")("worktree reconciliation", () => {
+	test("parses branches, detached heads and prunable entries", () => {
+		expect(parseWorktreeList(WORKTREE_LIST)).toEqual([
+			{ path: "/repo", branch: "main", prunable: false },
+			{ path: "/home/me/.caspian/worktrees/repo/feature-a", branch: "feature-a", prunable: false },
+			{ path: "/home/me/.caspian/worktrees/repo/detached", branch: null, prunable: false },
+			{ path: "/home/me/.caspian/worktrees/repo/gone", branch: "gone", prunable: true },
+			{ path: "/elsewhere/feature-b", branch: "feature-b", prunable: false },
+		]);
+	});
+
+	test("finds untracked Caspian worktrees only", () => {
+		const entries = parseWorktreeList(WORKTREE_LIST);
+		const worktreesDir = "/home/me/.caspian/worktrees/repo";
+
+		expect(
+			findUnimportedWorktrees({ entries, worktreesDir, knownPaths: [] }).map((e) => e.branch),
+		).toEqual(["feature-a"]);
+		expect(
+			findUnimportedWorktrees({
+				entries,
+				worktreesDir,
+				knownPaths: ["/home/me/.caspian/worktrees/repo/feature-a"],
+			}),
+		).toEqual([]);
+	});
+});
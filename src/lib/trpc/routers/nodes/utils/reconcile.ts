@@ -0,0 +1,59 @@
+import { isPathInside, pathsEqual, toNativePath } from "main/lib/platform-paths";
+import simpleGit from "simple-git";
+
+export interface GitWorktreeEntry {
+	path: string;
+	/** null for detached HEAD */
+	branch: string | null;
+	/** git no longer finds the worktree directory */
+	prunable: boolean;
+}
+
+/** Parses `git worktree list --porcelain`; the first entry is the main worktree */
+export function parseWorktreeList(output: string): GitWorktreeEntry[] {
+	const entries: GitWorktreeEntry[] = [];
+	let current: GitWorktreeEntry | null = null;
+
+	for (const line of output.split("\n")) {
+		if (line.startsWith("worktree ")) {
+			const path = toNativePath(line.slice("worktree ".length).trim());
+			current = { path, branch: null, prunable: false };
+			entries.push(current);
+		} else if (current && line.startsWith("branch refs/heads/")) {
+			current.branch = line.slice("branch refs/heads/".length).trim();
+		} else if (current && line.startsWith("prunable")) {
+			current.prunable = true;
+		}
+	}
+
+	return entries;
+}
+
+export async function listGitWorktrees(mainRepoPath: string): Promise<GitWorktreeEntry[]> {
+	return parseWorktreeList(await simpleGit(mainRepoPath).raw(["worktree", "list", "--porcelain"]));
+}
+
+/**
+ * Worktrees Caspian created under `worktreesDir` that have no worktree row,
+ * e.g. after the database was reset or the repository removed and re-added.
+ * Detached and missing worktrees are left alone since there's no branch to
+ * name a node after.
+ */
+export function findUnimportedWorktrees({
+	entries,
+	worktreesDir,
+	knownPaths,
+}: {
+	entries: GitWorktreeEntry[];
+	worktreesDir: string;
+	knownPaths: string[];
+}): Array<GitWorktreeEntry & { branch: string }> {
+	return entries.filter(
+		(entry): entry is GitWorktreeEntry & { branch: string } =>
+			entry.branch !== null &&
+			!entry.prunable &&
+			isPathInside(worktreesDir, entry.path) &&
+			!pathsEqual(worktreesDir, entry.path) &&
+			!knownPaths.some((path) => pathsEqual(path, entry.path)),
+	);
+}
@@ -0,0 +1,36 @@
+import { describe, expect, test } from "bun:test";
+import { formatReviewCommentsPrompt } from "./review-prompt";
+
+describe("formatReviewCommentsPrompt", () => {
+	test("returns empty string when there are no comments", () => {
+		expect(formatReviewCommentsPrompt([])).toBe("");
+	});
+
+	test("groups by file and orders by line", () => {
+		const prompt = formatReviewCommentsPrompt([
+			{ filePath: "src/b.ts", line: 3, side: "new", body: "Rename this" },
+			{ filePath: "src/a.ts", line: 20, side: "new", body: "Handle null" },
+			{ filePath: "src/a.ts", line: 4, side: "old", body: "Why was this removed?" },
+		]);
+
+		expect(prompt).toBe(
+			[
+				"Please address the following review comments on your changes:",
+				"",
+				"### src/a.ts",
+				"- line 4 (removed): Why was this removed?",
+				"- line 20: Handle null",
+				"",
+				"### src/b.ts",
+				"- line 3: Rename this",
+			].join("\n"),
+		);
+	});
+
+	test("indents multi-line comment bodies", () => {
+		const prompt = formatReviewCommentsPrompt([
+			{ filePath: "a.ts", line: 1, side: "new", body: "First\nSecond\n" },
+		]);
+		expect(prompt).toContain("- line 1: First\n  Second");
+	});
+});
@@ -0,0 +1,36 @@
+import type { SelectReviewComment } from "lib/local-db";
+
+type PromptComment = Pick<SelectReviewComment, "filePath" | "line" | "side" | "body">;
+
+/**
+ * Formats open review comments as a prompt an agent can act on.
+ * Comments are grouped by file and ordered by line. Returns an empty string when there are none.
+ */
+export function formatReviewCommentsPrompt(comments: PromptComment[]): string {
+	if (comments.length === 0) return "";
+
+	const byFile = new Map<string, PromptComment[]>();
+	for (const comment of comments) {
+		const fileComments = byFile.get(comment.filePath) ?? [];
+		fileComments.push(comment);
+		byFile.set(comment.filePath, fileComments);
+	}
+
+	const sections = [...byFile.keys()].sort().map((filePath) => {
+		const items = (byFile.get(filePath) ?? [])
+			.sort((a, b) => a.line - b.line)
+			.map((comment) => {
+				const location =
+					comment.side === "old" ? `line ${comment.line} (removed)` : `line ${comment.line}`;
+				const body = comment.body.trim().replace(/\n/g, "\n  ");
+				return `- ${location}: ${body}`;
+			});
+		return `### ${filePath}\n${items.join("\n")}`;
+	});
+
+	return [
+		"Please address the following review comments on your changes:",
+		"",
+		sections.join("\n\n"),
+	].join("\n");
+}
@@ -0,0 +1,64 @@
+import { exec } from "node:child_process";
+import { getShellEnvironment } from "./shell-env";
+
+/** Only the end of long output is stored; failures are usually reported last */
+const MAX_OUTPUT_TAIL = 64 * 1024;
+
+export function tailOutput(output: string): string {
+	return output.length > MAX_OUTPUT_TAIL ? output.slice(-MAX_OUTPUT_TAIL) : output;
+}
+
+export interface CommandResult {
+	/** Combined stdout and stderr */
+	output: string;
+	/** null when the process was killed (timeout) or couldn't start */
+	exitCode: number | null;
+	timedOut: boolean;
+	durationMs: number;
+}
+
+/**
+ * Runs a user-configured shell command in a node's working directory with the
+ * user's shell environment. Never rejects; failures are reported in the result.
+ */
+export async function runShellCommand({
+	command,
+	cwd,
+	timeoutMs,
+	env,
+}: {
+	command: string;
+	cwd: string;
+	timeoutMs: number;
+	env?: Record<string, string>;
+}): Promise<CommandResult> {
+	const shellEnv = await getShellEnvironment();
+	const startedAt = Date.now();
+
+	return new Promise((resolve) => {
+		exec(
+			command,
+			{
+				cwd,
+				timeout: timeoutMs,
+				maxBuffer: 64 * 1024 * 1024,
+				env: { ...shellEnv, ...env, CI: "1" },
+			},
+			(error, stdout, stderr) => {
+				const output = [stdout, stderr].filter(Boolean).join("\n");
+				const timedOut = error?.killed === true && error.signal === "SIGTERM";
+				let exitCode: number | null = 0;
+				if (error) {
+					exitCode = typeof error.code === "number" ? error.code : null;
+				}
+
+				resolve({
+					output,
+					exitCode,
+					timedOut,
+					durationMs: Date.now() - startedAt,
+				});
+			},
+		);
+	});
+}
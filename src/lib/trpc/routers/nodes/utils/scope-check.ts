@@ -0,0 +1,34 @@
+import { isPathInScope } from "shared/utils/scope";
+import simpleGit from "simple-git";
+
+/**
+ * Paths a scoped node changed outside its subdirectory: commits since it
+ * branched from `baseBranch`, uncommitted edits and untracked files.
+ */
+export async function findOutOfScopeChanges({
+	cwd,
+	scopePath,
+	baseBranch,
+}: {
+	cwd: string;
+	scopePath: string;
+	baseBranch: string;
+}): Promise<string[]> {
+	const git = simpleGit(cwd);
+
+	// Without a merge base only uncommitted changes can be checked
+	let base = "HEAD";
+	for (const ref of [`origin/${baseBranch}`, baseBranch]) {
+		try {
+			base = (await git.raw(["merge-base", "HEAD", ref])).trim();
+			break;
+		} catch {}
+	}
+
+	const [changed, untracked] = await Promise.all([
+		git.raw(["diff", "--name-only", "-z", base]),
+		git.raw(["ls-files", "--others", "--exclude-standard", "-z"]),
+	]);
+	const paths = new Set([...changed.split("\0"), ...untracked.split("\0")].filter(Boolean));
+	return [...paths].filter((path) => !isPathInScope(scopePath, path)).sort();
+}
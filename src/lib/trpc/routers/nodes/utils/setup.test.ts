@@ -0,0 +1,25 @@
+import { describe, expect, test } from "bun:test";
+import type { SetupConfig } from "shared/types";
+import { mergeSetupConfig } from "./setup";
+
+describe("mergeSetupConfig", () => {
+	test("replaces patched keys, removes nulls and keeps the rest", () => {
+		const current = {
+			setup: ["bun install"],
+			gate: { test: "bun test" },
+			experimental: true,
+		} as SetupConfig;
+
+		expect(
+			mergeSetupConfig(current, {
+				gate: null,
+				defaults: { testCommand: "bun test --bail" },
+				teardown: undefined,
+			}),
+		).toEqual({
+			setup: ["bun install"],
+			experimental: true,
+			defaults: { testCommand: "bun test --bail" },
+		} as SetupConfig);
+	});
+});
@@ -1,4 +1,4 @@
-import { cpSync, existsSync, readFileSync } from "node:fs";
+import { cpSync, existsSync, mkdirSync, readFileSync, writeFileSync } from "node:fs";
 import { join } from "node:path";
 import { CONFIG_FILE_NAME, REPOSITORY_CASPIAN_DIR_NAME } from "shared/constants";
 import type { SetupConfig } from "shared/types";
@@ -47,3 +47,47 @@ export function loadSetupConfig(mainRepoPath: string): SetupConfig | null {
 		return null;
 	}
 }
+
+/** Top-level keys to replace; null removes the key from the config */
+export type SetupConfigPatch = { [K in keyof SetupConfig]?: SetupConfig[K] | null };
+
+/**
+ * Applies a patch key by key. Keys not in the patch, including ones this
+ * version doesn't know about, are preserved.
+ */
+export function mergeSetupConfig(current: SetupConfig, patch: SetupConfigPatch): SetupConfig {
+	const next: Record<string, unknown> = { ...current };
+	for (const [key, value] of Object.entries(patch)) {
+		if (value === null) {
+			delete next[key];
+		} else if (value !== undefined) {
+			next[key] = value;
+		}
+	}
+	return next as SetupConfig;
+}
+
+/**
+ * Applies a patch to .caspian/config.json, creating it if needed. Refuses to
+ * touch a file that isn't valid JSON so hand edits aren't overwritten.
+ */
+export function updateSetupConfig(mainRepoPath: string, patch: SetupConfigPatch): SetupConfig {
+	const caspianDir = join(mainRepoPath, REPOSITORY_CASPIAN_DIR_NAME);
+	const configPath = join(caspianDir, CONFIG_FILE_NAME);
+
+	let current: SetupConfig = {};
+	if (existsSync(configPath)) {
+		try {
+			current = JSON.parse(readFileSync(configPath, "utf-8")) as SetupConfig;
+		} catch (error) {
+			throw new Error(
+				`${configPath} is not valid JSON: ${error instanceof Error ? error.message : String(error)}`,
+			);
+		}
+	}
+
+	const next = mergeSetupConfig(current, patch);
+	mkdirSync(caspianDir, { recursive: true });
+	writeFileSync(configPath, `${JSON.stringify(next, null, "\t")}\n`);
+	return next;
+}
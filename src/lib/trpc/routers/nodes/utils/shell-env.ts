@@ -1,5 +1,6 @@
 import { type ExecFileOptionsWithStringEncoding, execFile } from "node:child_process";
 import os from "node:os";
+import { delimiter, dirname } from "node:path";
 import { promisify } from "node:util";
 
 const execFileAsync = promisify(execFile);
@@ -164,3 +165,58 @@ export async function execWithShellEnv(
 		}
 	}
 }
+
+/** True when a spawn failed because the executable isn't on PATH */
+export function isCommandNotFound(error: unknown): boolean {
+	return error instanceof Error && "code" in error && error.code === "ENOENT";
+}
+
+/**
+ * Absolute path of an executable, or null when it can't be found. Windows
+ * has no `which`: `where.exe` searches this process's PATH, then PowerShell's
+ * Get-Command searches the PATH saved in the registry, which picks up tools
+ * installed (e.g. by winget) after Caspian was started.
+ */
+export async function findExecutable(name: string): Promise<string | null> {
+	const lookups: Array<[string, string[]]> =
+		process.platform === "win32"
+			? [
+					["where.exe", [name]],
+					[
+						"powershell.exe",
+						[
+							"-NoProfile",
+							"-NonInteractive",
+							"-Command",
+							"$env:Path = [Environment]::GetEnvironmentVariable('Path', 'Machine') + ';' + " +
+								"[Environment]::GetEnvironmentVariable('Path', 'User'); " +
+								`(Get-Command ${name} -CommandType Application -ErrorAction Stop).Source`,
+						],
+					],
+				]
+			: [["/bin/sh", ["-c", `command -v ${name}`]]];
+
+	for (const [cmd, args] of lookups) {
+		try {
+			const { stdout } = await execWithShellEnv(cmd, args, { timeout: 10_000 });
+			// where.exe lists every match; the first is the one that would run
+			const path = stdout.split(/\r?\n/)[0]?.trim();
+			if (path) return path;
+		} catch {
+			// Not found by this lookup; try the next
+		}
+	}
+	return null;
+}
+
+/**
+ * Puts an executable's directory on process.env.PATH so later spawns by
+ * bare name find it, like the macOS PATH fix above.
+ */
+export function addExecutableDirToPath(executablePath: string): void {
+	const pathKey = process.platform === "win32" ? "Path" : "PATH";
+	const dir = dirname(executablePath);
+	const current = process.env[pathKey] ?? "";
+	if (current.split(delimiter).includes(dir)) return;
+	process.env[pathKey] = current ? `${dir}${delimiter}${current}` : dir;
+}
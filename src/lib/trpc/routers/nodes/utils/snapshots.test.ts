@@ -0,0 +1,62 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { execFileSync } from "node:child_process";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { deleteAllSnapshotRefs, deleteSnapshotRefs } from "./snapshots";
+
+const GIT_ENV = {
+	...process.env,
+	GIT_AUTHOR_NAME: "Test",
+	GIT_AUTHOR_EMAIL: "test@example.com",
+	GIT_COMMITTER_NAME: "Test",
+	GIT_COMMITTER_EMAIL: "test@example.com",
+};
+
+function git(cwd: string, args: string[]): string {
+	return execFileSync("git", args, { cwd, env: GIT_ENV, encoding: "utf8" }).trim();
+}
+
+function listSnapshotRefs(repoPath: string): string[] {
+	return git(repoPath, ["for-each-ref", "--format=%(refname)", "refs/caspian/snapshots/"])
+		.split("\n")
+		.filter(Boolean);
+}
+
+describe("snapshot ref cleanup", () => {
+	let repoPath: string;
+
+	beforeEach(() => {
+		repoPath = mkdtempSync(join(tmpdir(), "caspian-snapshots-"));
+		git(repoPath, ["init", "--quiet"]);
+		git(repoPath, ["commit", "--quiet", "--allow-empty", "-m", "initial"]);
+		for (const ref of ["node-a/1", "node-a/2", "node-b/1"]) {
+			git(repoPath, ["update-ref", `refs/caspian/snapshots/${ref}`, "HEAD"]);
+		}
+	});
+
+	afterEach(() => {
+		rmSync(repoPath, { recursive: true, force: true });
+	});
+
+	test("deletes only the given node's refs", async () => {
+		await deleteSnapshotRefs(repoPath, "node-a");
+		expect(listSnapshotRefs(repoPath)).toEqual(["refs/caspian/snapshots/node-b/1"]);
+	});
+
+	test("deletes every node's refs for a closed repository", async () => {
+		await deleteAllSnapshotRefs(repoPath);
+		expect(listSnapshotRefs(repoPath)).toEqual([]);
+	});
+
+	test("leaves branches alone", async () => {
+		const branch = git(repoPath, ["symbolic-ref", "--short", "HEAD"]);
+		await deleteAllSnapshotRefs(repoPath);
+		expect(git(repoPath, ["rev-parse", "--verify", branch])).toMatch(/^[0-9a-f]{40}$/);
+	});
+
+	test("doesn't throw when the repository is gone", async () => {
+		rmSync(repoPath, { recursive: true, force: true });
+		await expect(deleteAllSnapshotRefs(repoPath)).resolves.toBeUndefined();
+	});
+});
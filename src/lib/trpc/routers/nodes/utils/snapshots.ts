@@ -0,0 +1,221 @@
+import { execFile } from "node:child_process";
+import { randomUUID } from "node:crypto";
+import { copyFile, rm } from "node:fs/promises";
+import { isAbsolute, join } from "node:path";
+import { promisify } from "node:util";
+import { desc, eq } from "drizzle-orm";
+import { nodeSnapshots, type SelectNodeSnapshot } from "lib/local-db";
+import { changesCache } from "main/lib/changes-cache";
+import { localDb } from "main/lib/local-db";
+import { getGitEnv } from "./git";
+
+const execFileAsync = promisify(execFile);
+
+/** Snapshot commits are pinned under this namespace, one folder per node */
+const SNAPSHOT_REF_PREFIX = "refs/caspian/snapshots";
+
+const SNAPSHOT_AUTHOR_ENV = {
+	GIT_AUTHOR_NAME: "Caspian",
+	GIT_AUTHOR_EMAIL: "snapshots@caspian.local",
+	GIT_COMMITTER_NAME: "Caspian",
+	GIT_COMMITTER_EMAIL: "snapshots@caspian.local",
+};
+
+async function git({
+	cwd,
+	args,
+	env,
+}: {
+	cwd: string;
+	args: string[];
+	env?: Record<string, string>;
+}): Promise<string> {
+	const gitEnv = await getGitEnv();
+	const { stdout } = await execFileAsync("git", args, {
+		cwd,
+		env: env ? { ...gitEnv, ...env } : gitEnv,
+		maxBuffer: 10 * 1024 * 1024,
+	});
+	return stdout.trim();
+}
+
+/** null when git fails, e.g. for a ref or commit that doesn't exist */
+async function tryGit({ cwd, args }: { cwd: string; args: string[] }): Promise<string | null> {
+	try {
+		return await git({ cwd, args });
+	} catch (error) {
+		console.warn(`[nodes/snapshots] git ${args.join(" ")} failed in ${cwd}:`, error);
+		return null;
+	}
+}
+
+/**
+ * Writes the whole working tree (tracked, modified and untracked files, minus
+ * ignored ones) to a tree object. Uses a throwaway copy of the index so the
+ * user's staging area is left untouched.
+ */
+async function writeWorkingTree(cwd: string): Promise<string> {
+	const gitIndexPath = await git({ cwd, args: ["rev-parse", "--git-path", "index"] });
+	const indexPath = isAbsolute(gitIndexPath) ? gitIndexPath : join(cwd, gitIndexPath);
+	const tempIndexPath = `${indexPath}.caspian-snapshot-${randomUUID()}`;
+
+	try {
+		// Starting from the real index lets git reuse cached stat info for unchanged files
+		await copyFile(indexPath, tempIndexPath);
+		const env = { GIT_INDEX_FILE: tempIndexPath };
+		await git({ cwd, args: ["add", "-A"], env });
+		return await git({ cwd, args: ["write-tree"], env });
+	} finally {
+		await rm(tempIndexPath, { force: true });
+	}
+}
+
+export async function createSnapshot({
+	nodeId,
+	cwd,
+	label,
+}: {
+	nodeId: string;
+	cwd: string;
+	label: string;
+}): Promise<SelectNodeSnapshot> {
+	const headSha = await tryGit({ cwd, args: ["rev-parse", "--verify", "--quiet", "HEAD"] });
+	if (!headSha) {
+		throw new Error("Cannot snapshot a node without any commits");
+	}
+
+	let indexTree: string;
+	try {
+		indexTree = await git({ cwd, args: ["write-tree"] });
+	} catch {
+		throw new Error("Cannot snapshot while there are unresolved merge conflicts");
+	}
+
+	const branch = await tryGit({ cwd, args: ["symbolic-ref", "--quiet", "--short", "HEAD"] });
+	const workingTree = await writeWorkingTree(cwd);
+	const commitSha = await git({
+		cwd,
+		args: ["commit-tree", workingTree, "-p", headSha, "-m", `Snapshot: ${label}`],
+		env: SNAPSHOT_AUTHOR_ENV,
+	});
+
+	const id = randomUUID();
+	const ref = `${SNAPSHOT_REF_PREFIX}/${nodeId}/${id}`;
+	await git({ cwd, args: ["update-ref", ref, commitSha] });
+
+	return localDb
+		.insert(nodeSnapshots)
+		.values({
+			id,
+			nodeId,
+			label,
+			branch: branch || null,
+			headSha,
+			commitSha,
+			indexTree,
+			ref,
+		})
+		.returning()
+		.get();
+}
+
+export function listSnapshots(nodeId: string): SelectNodeSnapshot[] {
+	return localDb
+		.select()
+		.from(nodeSnapshots)
+		.where(eq(nodeSnapshots.nodeId, nodeId))
+		.orderBy(desc(nodeSnapshots.createdAt))
+		.all();
+}
+
+/**
+ * Rolls the node back to a snapshot: moves the branch back to the snapshot's
+ * HEAD, replaces the working tree with the snapshot's files and restores the
+ * staging area. Ignored files are left alone.
+ *
+ * The current state is snapshotted first so a restore can itself be undone.
+ */
+export async function restoreSnapshot({
+	snapshot,
+	cwd,
+}: {
+	snapshot: SelectNodeSnapshot;
+	cwd: string;
+}): Promise<{ restored: SelectNodeSnapshot; backup: SelectNodeSnapshot }> {
+	const branch = await tryGit({ cwd, args: ["symbolic-ref", "--quiet", "--short", "HEAD"] });
+	if ((branch || null) !== snapshot.branch) {
+		throw new Error(
+			`Snapshot was taken on ${snapshot.branch ?? "a detached HEAD"}; switch back before restoring`,
+		);
+	}
+
+	const commitExists = await tryGit({
+		cwd,
+		args: ["cat-file", "-e", `${snapshot.commitSha}^{commit}`],
+	});
+	if (commitExists === null) {
+		throw new Error("Snapshot commit no longer exists in the repository");
+	}
+
+	const backup = await createSnapshot({
+		nodeId: snapshot.nodeId,
+		cwd,
+		label: `Before restoring "${snapshot.label}"`,
+	});
+
+	try {
+		await git({ cwd, args: ["reset", "--hard", snapshot.headSha] });
+		await git({ cwd, args: ["clean", "-fd"] });
+		// Brings back the snapshot's files, including ones that were untracked
+		await git({ cwd, args: ["read-tree", "-u", "--reset", snapshot.commitSha] });
+		// Index only; files that were untracked at snapshot time become untracked again
+		await git({ cwd, args: ["read-tree", snapshot.indexTree] });
+	} finally {
+		changesCache.invalidate(cwd);
+	}
+
+	console.log(`[nodes/snapshots] Restored ${snapshot.id} for node ${snapshot.nodeId}`);
+	return { restored: snapshot, backup };
+}
+
+export async function deleteSnapshot({
+	snapshot,
+	cwd,
+}: {
+	snapshot: SelectNodeSnapshot;
+	cwd: string;
+}): Promise<void> {
+	await tryGit({ cwd, args: ["update-ref", "-d", snapshot.ref] });
+	localDb.delete(nodeSnapshots).where(eq(nodeSnapshots.id, snapshot.id)).run();
+}
+
+async function deleteRefsUnder({
+	mainRepoPath,
+	prefix,
+}: {
+	mainRepoPath: string;
+	prefix: string;
+}): Promise<void> {
+	const refs = await tryGit({
+		cwd: mainRepoPath,
+		args: ["for-each-ref", "--format=%(refname)", prefix],
+	});
+	if (!refs) return;
+
+	for (const ref of refs.split("\n").filter(Boolean)) {
+		await tryGit({ cwd: mainRepoPath, args: ["update-ref", "-d", ref] });
+	}
+}
+
+/**
+ * Drops every snapshot ref for a node. Refs live in the shared repository, so
+ * they'd otherwise outlive the node's worktree. Best effort.
+ */
+export async function deleteSnapshotRefs(mainRepoPath: string, nodeId: string): Promise<void> {
+	await deleteRefsUnder({ mainRepoPath, prefix: `${SNAPSHOT_REF_PREFIX}/${nodeId}/` });
+}
+
+/** Drops the snapshot refs of every node, for a repository that's closed or removed */
+export async function deleteAllSnapshotRefs(mainRepoPath: string): Promise<void> {
+	await deleteRefsUnder({ mainRepoPath, prefix: `${SNAPSHOT_REF_PREFIX}/` });
+}
@@ -0,0 +1,60 @@
+import { describe, expect, test } from "bun:test";
+import { homedir } from "node:os";
+import { interpretSshTest, parseSshConfig, parseSshTarget } from "./ssh";
+
+describe("parseSshTarget", () => {
+	test("parses scp-like and ssh:// remotes", () => {
+		expect(parseSshTarget("git@github.com:acme/repo.git")).toEqual({
+			user: "git",
+			host: "github.com",
+			port: null,
+		});
+		expect(parseSshTarget("ssh://git@gitlab.example.com:2222/team/repo.git")).toEqual({
+			user: "git",
+			host: "gitlab.example.com",
+			port: 2222,
+		});
+	});
+
+	test("ignores HTTPS remotes and local paths", () => {
+		expect(parseSshTarget("https://github.com/acme/repo.git")).toBeNull();
+		expect(parseSshTarget("/srv/git/repo.git")).toBeNull();
+		expect(parseSshTarget("C:/src/repo")).toBeNull();
+	});
+});
+
+describe("parseSshConfig", () => {
+	test("collects identity files in order and expands ~", () => {
+		const output = [
+			"user git",
+			"hostname github.com",
+			"port 22",
+			"identityfile ~/.ssh/work",
+			"identityfile /k",
+		].join("\n");
+		const config = parseSshConfig(output);
+		expect(config).toEqual({
+			user: "git",
+			hostname: "github.com",
+			port: 22,
+			identityFiles: [`${homedir()}/.ssh/work`, "/k"],
+		});
+	});
+});
+
+describe("interpretSshTest", () => {
+	test("treats host greetings as success despite the exit code", () => {
+		const greeting =
+			"Hi octocat! You've successfully authenticated, but GitHub does not provide shell access.";
+		expect(interpretSshTest(1, greeting)).toEqual({ ok: true, problem: null, username: "octocat" });
+		expect(interpretSshTest(0, "Welcome to GitLab, @jane!").username).toBe("jane");
+	});
+
+	test("classifies failures", () => {
+		const denied = "git@github.com: Permission denied (publickey).";
+		expect(interpretSshTest(255, denied).problem).toBe("auth");
+		expect(interpretSshTest(255, "Host key verification failed.").problem).toBe("host_key");
+		expect(interpretSshTest(255, "ssh: Could not resolve hostname nope").problem).toBe("dns");
+		expect(interpretSshTest(255, "something else").problem).toBe("unknown");
+	});
+});
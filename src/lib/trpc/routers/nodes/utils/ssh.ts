@@ -0,0 +1,255 @@
+import { execFile } from "node:child_process";
+import { existsSync } from "node:fs";
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { promisify } from "node:util";
+import { getShellEnvironment } from "./shell-env";
+
+const execFileAsync = promisify(execFile);
+
+const CONNECT_TIMEOUT_SECONDS = 10;
+
+export interface SshTarget {
+	user: string | null;
+	host: string;
+	port: number | null;
+}
+
+export interface SshConfig {
+	user: string | null;
+	hostname: string | null;
+	port: number | null;
+	identityFiles: string[];
+}
+
+export type SshProblem = "auth" | "host_key" | "dns" | "refused" | "timeout" | "unknown";
+
+export interface SshTestResult {
+	ok: boolean;
+	problem: SshProblem | null;
+	/** Account the host greeted us as, when it says */
+	username: string | null;
+}
+
+export interface SshDiagnostics {
+	host: string;
+	agent: { running: boolean; keys: string[] };
+	/** Keys ssh would offer for this host, in order, from `ssh -G` */
+	identityFiles: { path: string; exists: boolean }[];
+	connection: SshTestResult & { output: string };
+	/** What to do about each problem found; empty when everything works */
+	problems: string[];
+}
+
+/** SSH user/host/port of a remote, or null for HTTP(S) and local remotes */
+export function parseSshTarget(url: string): SshTarget | null {
+	const trimmed = url.trim();
+	if (trimmed.startsWith("ssh://")) {
+		try {
+			const parsed = new URL(trimmed);
+			return {
+				user: parsed.username || null,
+				host: parsed.hostname,
+				port: parsed.port ? Number(parsed.port) : null,
+			};
+		} catch {
+			return null;
+		}
+	}
+
+	// scp-like syntax; a single letter before the colon is a Windows drive
+	const scpLike = trimmed.match(/^(?:([^@/]+)@)?([^:/]{2,}):(?!\/\/)/);
+	if (!scpLike || trimmed.includes("://")) return null;
+	return { user: scpLike[1] ?? null, host: scpLike[2], port: null };
+}
+
+/** Parses `ssh -G <host>`, which prints the effective config as lowercase `key value` lines */
+export function parseSshConfig(output: string): SshConfig {
+	const config: SshConfig = { user: null, hostname: null, port: null, identityFiles: [] };
+	for (const line of output.split("\n")) {
+		const [key, ...rest] = line.trim().split(" ");
+		const value = rest.join(" ");
+		if (!value) continue;
+
+		if (key === "user") config.user = value;
+		else if (key === "hostname") config.hostname = value;
+		else if (key === "port") config.port = Number(value);
+		else if (key === "identityfile") {
+			config.identityFiles.push(value.replace(/^~(?=\/|\\|$)/, homedir()));
+		}
+	}
+	return config;
+}
+
+const GREETING_PATTERNS = [
+	/Hi (\S+?)! You've successfully authenticated/, // GitHub
+	/Welcome to GitLab, @(\S+?)!/,
+	/logged in as (\S+?)\.?$/m, // Bitbucket
+];
+
+/**
+ * Interprets `ssh -T`. Git hosts refuse a shell, so a successful login can
+ * still exit non-zero; the greeting is what counts.
+ */
+export function interpretSshTest(exitCode: number, output: string): SshTestResult {
+	for (const pattern of GREETING_PATTERNS) {
+		const match = output.match(pattern);
+		if (match) return { ok: true, problem: null, username: match[1] };
+	}
+	if (/successfully authenticated|authenticated via ssh key/i.test(output)) {
+		return { ok: true, problem: null, username: null };
+	}
+
+	const lower = output.toLowerCase();
+	let problem: SshProblem | null = null;
+	if (lower.includes("permission denied")) problem = "auth";
+	else if (lower.includes("host key verification failed")) problem = "host_key";
+	else if (lower.includes("could not resolve hostname")) problem = "dns";
+	else if (lower.includes("connection refused")) problem = "refused";
+	else if (lower.includes("timed out")) problem = "timeout";
+	else if (exitCode !== 0) problem = "unknown";
+
+	return { ok: problem === null, problem, username: null };
+}
+
+function describeProblem(problem: SshProblem, target: SshTarget, login: string): string {
+	switch (problem) {
+		case "auth":
+			return `${target.host} rejected every key offered. Add your public key to your account there, or load the right key with ssh-add.`;
+		case "host_key":
+			return `${target.host}'s host key isn't trusted yet. Run \`ssh -T ${login}\` in a terminal once and confirm the fingerprint.`;
+		case "dns":
+			return `Could not resolve ${target.host}. Check the remote URL and your network or VPN.`;
+		case "refused":
+			return `${target.host} refused the connection. The SSH port may be blocked; try the HTTPS remote instead.`;
+		case "timeout":
+			return `Timed out connecting to ${target.host}. A firewall or proxy may be blocking SSH.`;
+		case "unknown":
+			return `Could not connect to ${target.host} over SSH. Run \`ssh -T ${login}\` in a terminal for details.`;
+	}
+}
+
+async function getSshEnv(): Promise<Record<string, string>> {
+	// SSH_AUTH_SOCK is often only set in the login shell, not for GUI-launched apps
+	const shellEnv = await getShellEnvironment();
+	const env: Record<string, string> = {};
+	for (const [key, value] of Object.entries(process.env)) {
+		if (typeof value === "string") env[key] = value;
+	}
+	if (!env.SSH_AUTH_SOCK && shellEnv.SSH_AUTH_SOCK) env.SSH_AUTH_SOCK = shellEnv.SSH_AUTH_SOCK;
+	if (shellEnv.PATH) env.PATH = shellEnv.PATH;
+	return env;
+}
+
+async function run(
+	command: string,
+	args: string[],
+	env: Record<string, string>,
+	timeout: number,
+): Promise<{ exitCode: number; output: string }> {
+	try {
+		const { stdout, stderr } = await execFileAsync(command, args, { env, timeout });
+		return { exitCode: 0, output: `${stdout}${stderr}` };
+	} catch (error) {
+		const failure = error as { code?: number | string; stdout?: string; stderr?: string };
+		return {
+			exitCode: typeof failure.code === "number" ? failure.code : -1,
+			output: `${failure.stdout ?? ""}${failure.stderr ?? ""}` || String(error),
+		};
+	}
+}
+
+async function getAgentKeys(env: Record<string, string>): Promise<SshDiagnostics["agent"]> {
+	if (!env.SSH_AUTH_SOCK) return { running: false, keys: [] };
+	// Exit code 1: agent running with no keys; 2: can't reach the agent
+	const { exitCode, output } = await run("ssh-add", ["-l"], env, 5_000);
+	if (exitCode === 0) {
+		return { running: true, keys: output.trim().split("\n").filter(Boolean) };
+	}
+	return { running: exitCode === 1, keys: [] };
+}
+
+async function getIdentityFiles(
+	target: SshTarget,
+	login: string,
+	env: Record<string, string>,
+): Promise<SshDiagnostics["identityFiles"]> {
+	const args = target.port ? ["-p", String(target.port), "-G", login] : ["-G", login];
+	const { exitCode, output } = await run("ssh", args, env, 5_000);
+	if (exitCode !== 0) return [];
+	return parseSshConfig(output).identityFiles.map((path) => ({ path, exists: existsSync(path) }));
+}
+
+function loginFor(target: SshTarget): string {
+	return target.user ? `${target.user}@${target.host}` : target.host;
+}
+
+/** Problems that stop ssh from offering any key, found without touching the network */
+function findLocalProblems(
+	agent: SshDiagnostics["agent"],
+	identityFiles: SshDiagnostics["identityFiles"],
+): string[] {
+	if (agent.keys.length > 0 || identityFiles.some((file) => file.exists)) return [];
+
+	const defaultKey = join(homedir(), ".ssh", "id_ed25519");
+	return [
+		agent.running
+			? `No SSH keys are loaded in ssh-agent and none were found on disk. Create one with \`ssh-keygen -t ed25519 -f ${defaultKey}\` and add it to your account.`
+			: `No SSH key was found and ssh-agent isn't running. Create one with \`ssh-keygen -t ed25519 -f ${defaultKey}\` and add it to your account.`,
+	];
+}
+
+/**
+ * Checks everything needed to reach a remote over SSH: the agent and its
+ * keys, the identity files ssh would offer and an actual `ssh -T` login.
+ * Returns null for remotes that don't use SSH.
+ */
+export async function checkSshSetup(remoteUrl: string): Promise<SshDiagnostics | null> {
+	const target = parseSshTarget(remoteUrl);
+	if (!target) return null;
+
+	const env = await getSshEnv();
+	const login = loginFor(target);
+	const [agent, identityFiles] = await Promise.all([
+		getAgentKeys(env),
+		getIdentityFiles(target, login, env),
+	]);
+
+	const args = [
+		"-T",
+		"-o",
+		"BatchMode=yes",
+		"-o",
+		`ConnectTimeout=${CONNECT_TIMEOUT_SECONDS}`,
+		...(target.port ? ["-p", String(target.port)] : []),
+		login,
+	];
+	const { exitCode, output } = await run("ssh", args, env, (CONNECT_TIMEOUT_SECONDS + 5) * 1000);
+	const connection = { ...interpretSshTest(exitCode, output), output: output.trim() };
+
+	const problems = findLocalProblems(agent, identityFiles);
+	if (connection.problem && !(connection.problem === "auth" && problems.length > 0)) {
+		problems.push(describeProblem(connection.problem, target, login));
+	}
+
+	return { host: target.host, agent, identityFiles, connection, problems };
+}
+
+/**
+ * Quick local check before clone/push over SSH. Returns an actionable
+ * message when ssh would have no key to offer, null otherwise.
+ */
+export async function preflightSsh(remoteUrl: string): Promise<string | null> {
+	const target = parseSshTarget(remoteUrl);
+	if (!target) return null;
+
+	const env = await getSshEnv();
+	const login = loginFor(target);
+	const [agent, identityFiles] = await Promise.all([
+		getAgentKeys(env),
+		getIdentityFiles(target, login, env),
+	]);
+	// ssh -G failing (e.g. ssh not installed) leaves nothing to judge by
+	if (identityFiles.length === 0) return null;
+	return findLocalProblems(agent, identityFiles)[0] ?? null;
+}
@@ -0,0 +1,163 @@
+import { describe, expect, test } from "bun:test";
+import {
+	detectTestFramework,
+	parseCargoTestOutput,
+	parseGoTestOutput,
+	parseJestOutput,
+	parsePytestOutput,
+	summarizeTestResults,
+} from "./test-results";
+
+describe("detectTestFramework", () => {
+	test("detects from the command", () => {
+		expect(detectTestFramework("cargo test --workspace", "")).toBe("cargo");
+		expect(detectTestFramework("python -m pytest -v", "")).toBe("pytest");
+		expect(detectTestFramework("go test ./... -v", "")).toBe("go");
+		expect(detectTestFramework("npx jest", "")).toBe("jest");
+	});
+
+	test("falls back to output signatures", () => {
+		expect(detectTestFramework("make test", "test result: ok. 3 passed")).toBe("cargo");
+		expect(detectTestFramework("make test", "===== test session starts =====")).toBe("pytest");
+		expect(detectTestFramework("make test", "--- PASS: TestA (0.00s)")).toBe("go");
+		expect(detectTestFramework("npm test", "PASS src/a.test.ts")).toBe("jest");
+		expect(detectTestFramework("make test", "all good")).toBeNull();
+	});
+});
+
+describe("parseCargoTestOutput", () => {
+	test("parses statuses and panic messages", () => {
+		const output = [
+			"running 3 tests",
+			"test parser::parses_empty ... ok",
+			"test parser::rejects_bad ... FAILED",
+			"test slow::network ... ignored",
+			"",
+			"failures:",
+			"",
+			"---- parser::rejects_bad stdout ----",
+			"thread 'parser::rejects_bad' panicked at src/parser.rs:10:5:",
+			"assertion failed: result.is_err()",
+			"",
+			"failures:",
+			"    parser::rejects_bad",
+			"",
+			"test result: FAILED. 1 passed; 1 failed; 1 ignored",
+		].join("\n");
+
+		const results = parseCargoTestOutput(output);
+		expect(results.map((r) => [r.name, r.status])).toEqual([
+			["parser::parses_empty", "passed"],
+			["parser::rejects_bad", "failed"],
+			["slow::network", "skipped"],
+		]);
+		expect(results[1].failureMessage).toBe(
+			"thread 'parser::rejects_bad' panicked at src/parser.rs:10:5:\nassertion failed: result.is_err()",
+		);
+	});
+});
+
+describe("parsePytestOutput", () => {
+	test("parses verbose lines, summary messages and durations", () => {
+		const output = [
+			"tests/test_api.py::test_login PASSED                    [ 33%]",
+			"tests/test_api.py::test_logout FAILED                   [ 66%]",
+			"tests/test_api.py::test_param[a-1] SKIPPED (no db)      [100%]",
+			"============ slowest durations ============",
+			"0.52s call     tests/test_api.py::test_logout",
+			"========= short test summary info =========",
+			"FAILED tests/test_api.py::test_logout - AssertionError: expected 200",
+		].join("\n");
+
+		const results = parsePytestOutput(output);
+		expect(results).toEqual([
+			{ name: "tests/test_api.py::test_login", status: "passed", durationMs: null },
+			{
+				name: "tests/test_api.py::test_logout",
+				status: "failed",
+				durationMs: 520,
+				failureMessage: "AssertionError: expected 200",
+			},
+			{ name: "tests/test_api.py::test_param[a-1]", status: "skipped", durationMs: null },
+		]);
+	});
+
+	test("picks up failures from the summary without -v", () => {
+		const results = parsePytestOutput("FAILED tests/test_x.py::test_y - ValueError");
+		expect(results).toEqual([
+			{
+				name: "tests/test_x.py::test_y",
+				status: "failed",
+				durationMs: null,
+				failureMessage: "ValueError",
+			},
+		]);
+	});
+});
+
+describe("parseJestOutput", () => {
+	test("parses verbose results and attaches failure blocks", () => {
+		const output = [
+			"PASS src/math.test.ts",
+			"  math",
+			"    ✓ adds (3 ms)",
+			"    ○ skipped divides",
+			"FAIL src/api.test.ts",
+			"  api",
+			"    ✕ fetches user (12 ms)",
+			"",
+			"  ● api › fetches user",
+			"",
+			"    expect(received).toBe(expected)",
+			"",
+			"Tests:       1 failed, 1 skipped, 1 passed, 3 total",
+		].join("\n");
+
+		const results = parseJestOutput(output);
+		expect(results.map((r) => [r.name, r.status, r.durationMs])).toEqual([
+			["src/math.test.ts › adds", "passed", 3],
+			["src/math.test.ts › divides", "skipped", null],
+			["src/api.test.ts › fetches user", "failed", 12],
+		]);
+		expect(results[2].failureMessage).toBe("expect(received).toBe(expected)");
+	});
+});
+
+describe("parseGoTestOutput", () => {
+	test("parses results, durations and logged failure output", () => {
+		const output = [
+			"=== RUN   TestAdd",
+			"--- PASS: TestAdd (0.00s)",
+			"=== RUN   TestParse",
+			"    parse_test.go:21: unexpected token",
+			"--- FAIL: TestParse (0.02s)",
+			"=== RUN   TestSlow",
+			"    slow_test.go:8: skipping in short mode",
+			"--- SKIP: TestSlow (0.00s)",
+			"FAIL",
+		].join("\n");
+
+		expect(parseGoTestOutput(output)).toEqual([
+			{ name: "TestAdd", status: "passed", durationMs: 0 },
+			{
+				name: "TestParse",
+				status: "failed",
+				durationMs: 20,
+				failureMessage: "parse_test.go:21: unexpected token",
+			},
+			{ name: "TestSlow", status: "skipped", durationMs: 0 },
+		]);
+	});
+});
+
+describe("summarizeTestResults", () => {
+	test("counts by status", () => {
+		expect(
+			summarizeTestResults([
+				{ name: "a", status: "passed", durationMs: null },
+				{ name: "b", status: "failed", durationMs: null },
+				{ name: "c", status: "passed", durationMs: null },
+			]),
+		).toEqual({ total: 3, passed: 2, failed: 1, skipped: 0 });
+	});
+});
@@ -0,0 +1,273 @@
+import type {
+	TestCaseResult,
+	TestCaseStatus,
+	TestFramework,
+	TestRunSummary,
+} from "shared/types/test-runs";
+
+/** Failure messages are trimmed to this many characters */
+const MAX_FAILURE_MESSAGE_LENGTH = 4000;
+
+// Strips ANSI color codes so colored runner output parses the same as plain output
+// biome-ignore lint/suspicious/noControlCharactersInRegex: matching escape sequences
+const ANSI_PATTERN = /\x1b\[[0-9;]*m/g;
+
+function toLines(output: string): string[] {
+	return output.replace(ANSI_PATTERN, "").replace(/\r\n/g, "\n").split("\n");
+}
+
+function trimMessage(lines: string[]): string | undefined {
+	const message = lines.join("\n").trim();
+	if (!message) return undefined;
+	return message.length > MAX_FAILURE_MESSAGE_LENGTH
+		? `${message.slice(0, MAX_FAILURE_MESSAGE_LENGTH)}…`
+		: message;
+}
+
+/**
+ * Guesses the framework from the command first, then from output signatures.
+ */
+export function detectTestFramework(command: string, output: string): TestFramework | null {
+	if (/\bcargo\s+(test|nextest)\b/.test(command)) return "cargo";
+	if (/\bpytest\b|\bpython3?\s+-m\s+pytest\b/.test(command)) return "pytest";
+	if (/\bgo\s+test\b/.test(command)) return "go";
+	if (/\b(jest|vitest)\b/.test(command)) return "jest";
+
+	if (/^test result: (ok|FAILED)\./m.test(output)) return "cargo";
+	if (/^=+ test session starts =+$/m.test(output)) return "pytest";
+	if (/^--- (PASS|FAIL|SKIP): /m.test(output)) return "go";
+	if (/^(PASS|FAIL) \S/m.test(output) || /^Tests:\s+\d+/m.test(output)) return "jest";
+	return null;
+}
+
+/**
+ * cargo test: `test path::name ... ok|FAILED|ignored`, with panic output in
+ * `---- path::name stdout ----` sections. Per-test durations aren't reported.
+ */
+export function parseCargoTestOutput(output: string): TestCaseResult[] {
+	const results = new Map<string, TestCaseResult>();
+	const messages = new Map<string, string[]>();
+	let capturing: string | null = null;
+
+	for (const line of toLines(output)) {
+		const result = line.match(/^test (\S+) \.\.\. (ok|FAILED|ignored)\b/);
+		if (result) {
+			const status: TestCaseStatus =
+				result[2] === "ok" ? "passed" : result[2] === "FAILED" ? "failed" : "skipped";
+			results.set(result[1], { name: result[1], status, durationMs: null });
+			continue;
+		}
+
+		const section = line.match(/^---- (\S+) stdout ----$/);
+		if (section) {
+			capturing = section[1];
+			messages.set(capturing, []);
+			continue;
+		}
+
+		if (capturing) {
+			if (/^(failures:|test result:)/.test(line)) {
+				capturing = null;
+			} else {
+				messages.get(capturing)?.push(line);
+			}
+		}
+	}
+
+	for (const [name, lines] of messages) {
+		const result = results.get(name);
+		if (result) result.failureMessage = trimMessage(lines);
+	}
+	return [...results.values()];
+}
+
+const PYTEST_STATUSES: Record<string, TestCaseStatus> = {
+	PASSED: "passed",
+	XPASS: "passed",
+	FAILED: "failed",
+	ERROR: "failed",
+	SKIPPED: "skipped",
+	XFAIL: "skipped",
+};
+
+/**
+ * pytest: verbose `path::test STATUS` lines, plus the short summary's
+ * `FAILED path::test - message` lines (present without -v as well).
+ * Durations come from `--durations` style `0.12s call path::test` lines when present.
+ */
+export function parsePytestOutput(output: string): TestCaseResult[] {
+	const results = new Map<string, TestCaseResult>();
+
+	const upsert = (name: string, status: TestCaseStatus) => {
+		const existing = results.get(name);
+		if (existing) {
+			existing.status = status;
+		} else {
+			results.set(name, { name, status, durationMs: null });
+		}
+		return results.get(name) as TestCaseResult;
+	};
+
+	for (const line of toLines(output)) {
+		const verbose = line.match(
+			/^(\S+::\S+(?:\[[^\]]*\])?) (PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)\b/,
+		);
+		if (verbose) {
+			upsert(verbose[1], PYTEST_STATUSES[verbose[2]]);
+			continue;
+		}
+
+		const summary = line.match(/^(FAILED|ERROR) (\S+::\S+)(?: - (.*))?$/);
+		if (summary) {
+			const result = upsert(summary[2], "failed");
+			if (summary[3]) result.failureMessage = trimMessage([summary[3]]);
+			continue;
+		}
+
+		const duration = line.match(/^(\d+(?:\.\d+)?)s call\s+(\S+::\S+)/);
+		if (duration) {
+			const result = results.get(duration[2]);
+			if (result) result.durationMs = Math.round(Number.parseFloat(duration[1]) * 1000);
+		}
+	}
+
+	return [...results.values()];
+}
+
+const JEST_TEST_LINE = /^\s+([✓✔√✕✗×○↓])\s+(?:skipped |todo )?(.+?)(?: \((\d+(?:\.\d+)?) ?ms\))?$/;
+
+/**
+ * Jest (and Vitest's default reporter): `PASS|FAIL file` headers, verbose
+ * `✓ name (5 ms)` / `✕ name` / `○ skipped name` lines, and `● Suite › name`
+ * failure blocks.
+ */
+export function parseJestOutput(output: string): TestCaseResult[] {
+	const results: TestCaseResult[] = [];
+	const failureBlocks = new Map<string, string[]>();
+	let currentFile = "";
+	let capturing: string | null = null;
+
+	for (const line of toLines(output)) {
+		const fileHeader = line.match(/^\s*(PASS|FAIL) (\S+)/);
+		if (fileHeader) {
+			currentFile = fileHeader[2];
+			capturing = null;
+			continue;
+		}
+
+		const failureHeader = line.match(/^\s*● (.+)$/);
+		if (failureHeader) {
+			capturing = failureHeader[1].trim();
+			failureBlocks.set(capturing, []);
+			continue;
+		}
+
+		if (capturing) {
+			if (/^(Test Suites|Tests):/.test(line.trim())) {
+				capturing = null;
+			} else {
+				failureBlocks.get(capturing)?.push(line.trim());
+			}
+			continue;
+		}
+
+		const testLine = line.match(JEST_TEST_LINE);
+		if (testLine) {
+			const marker = testLine[1];
+			const status: TestCaseStatus = "✓✔√".includes(marker)
+				? "passed"
+				: "✕✗×".includes(marker)
+					? "failed"
+					: "skipped";
+			results.push({
+				name: currentFile ? `${currentFile} › ${testLine[2]}` : testLine[2],
+				status,
+				durationMs: testLine[3] ? Math.round(Number.parseFloat(testLine[3])) : null,
+			});
+		}
+	}
+
+	// Failure blocks are titled "Describe › test"; match them to results by suffix
+	for (const [title, lines] of failureBlocks) {
+		const testName = title.split(" › ").pop() ?? title;
+		const result = results.find(
+			(candidate) =>
+				candidate.status === "failed" &&
+				!candidate.failureMessage &&
+				(candidate.name.endsWith(` › ${title}`) || candidate.name.endsWith(` › ${testName}`)),
+		);
+		if (result) {
+			result.failureMessage = trimMessage(lines);
+		} else {
+			results.push({
+				name: title,
+				status: "failed",
+				durationMs: null,
+				failureMessage: trimMessage(lines),
+			});
+		}
+	}
+
+	return results;
+}
+
+/**
+ * go test -v: `--- PASS|FAIL|SKIP: TestName (0.01s)` lines; output logged
+ * between `=== RUN` and the result line is used as the failure message.
+ */
+export function parseGoTestOutput(output: string): TestCaseResult[] {
+	const results: TestCaseResult[] = [];
+	const logs = new Map<string, string[]>();
+	let running: string | null = null;
+
+	for (const line of toLines(output)) {
+		const run = line.match(/^=== (?:RUN|CONT)\s+(\S+)/);
+		if (run) {
+			running = run[1];
+			if (!logs.has(running)) logs.set(running, []);
+			continue;
+		}
+
+		const result = line.match(/^\s*--- (PASS|FAIL|SKIP): (\S+) \((\d+(?:\.\d+)?)s\)/);
+		if (result) {
+			const name = result[2];
+			const status: TestCaseStatus =
+				result[1] === "PASS" ? "passed" : result[1] === "FAIL" ? "failed" : "skipped";
+			results.push({
+				name,
+				status,
+				durationMs: Math.round(Number.parseFloat(result[3]) * 1000),
+				...(status === "failed" && { failureMessage: trimMessage(logs.get(name) ?? []) }),
+			});
+			running = null;
+			continue;
+		}
+
+		if (running && /^\s{4}/.test(line)) {
+			logs.get(running)?.push(line.trim());
+		}
+	}
+
+	return results;
+}
+
+export function parseTestOutput(framework: TestFramework, output: string): TestCaseResult[] {
+	switch (framework) {
+		case "cargo":
+			return parseCargoTestOutput(output);
+		case "pytest":
+			return parsePytestOutput(output);
+		case "jest":
+			return parseJestOutput(output);
+		case "go":
+			return parseGoTestOutput(output);
+	}
+}
+
+export function summarizeTestResults(results: TestCaseResult[]): TestRunSummary {
+	const summary: TestRunSummary = { total: results.length, passed: 0, failed: 0, skipped: 0 };
+	for (const result of results) {
+		summary[result.status]++;
+	}
+	return summary;
+}
@@ -0,0 +1,114 @@
+import { readFile, stat } from "node:fs/promises";
+import { join } from "node:path";
+import { desc, eq } from "drizzle-orm";
+import { type SelectTestRun, testRuns } from "lib/local-db";
+import { recordAuditEvent } from "main/lib/audit";
+import { localDb } from "main/lib/local-db";
+import { redactOutput } from "main/lib/redaction";
+import type { FileCoverage, TestRunStatus } from "shared/types/test-runs";
+import { COVERAGE_REPORT_PATHS, parseCoverageReport } from "./coverage";
+import { runShellCommand, tailOutput } from "./run-command";
+import { detectTestFramework, parseTestOutput, summarizeTestResults } from "./test-results";
+
+export const DEFAULT_TEST_TIMEOUT_MS = 10 * 60_000;
+
+/**
+ * Reads the first coverage report written (or rewritten) since startedAt,
+ * so stale reports from earlier runs aren't attributed to this one.
+ */
+async function collectCoverage(
+	cwd: string,
+	startedAt: number,
+): Promise<{ reportPath: string; files: FileCoverage[] } | null> {
+	for (const reportPath of COVERAGE_REPORT_PATHS) {
+		try {
+			const fullPath = join(cwd, reportPath);
+			// Allow for filesystems with coarse (1s) mtime resolution
+			if ((await stat(fullPath)).mtimeMs < startedAt - 1000) continue;
+
+			const files = parseCoverageReport(reportPath, await readFile(fullPath, "utf-8"), cwd);
+			if (files.length > 0) {
+				return { reportPath, files };
+			}
+		} catch (error) {
+			// Most frameworks only write one of the candidate reports
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") continue;
+			console.warn(`[nodes/test-run] Failed to read coverage report ${reportPath}:`, error);
+		}
+	}
+	return null;
+}
+
+export function getLatestTestRun(nodeId: string): SelectTestRun | undefined {
+	return localDb
+		.select()
+		.from(testRuns)
+		.where(eq(testRuns.nodeId, nodeId))
+		.orderBy(desc(testRuns.createdAt))
+		.limit(1)
+		.get();
+}
+
+/**
+ * Runs a test command in a node's working directory, parses per-test results
+ * when the framework is recognized, and records the run.
+ */
+export async function executeTestRun({
+	nodeId,
+	command,
+	cwd,
+	timeoutMs = DEFAULT_TEST_TIMEOUT_MS,
+}: {
+	nodeId: string;
+	command: string;
+	cwd: string;
+	timeoutMs?: number;
+}): Promise<SelectTestRun> {
+	const startedAt = Date.now();
+	const result = await runShellCommand({ command, cwd, timeoutMs });
+	const coverage = await collectCoverage(cwd, startedAt);
+
+	const framework = detectTestFramework(command, result.output);
+	const results = framework ? parseTestOutput(framework, result.output) : [];
+	const summary = summarizeTestResults(results);
+
+	let status: TestRunStatus;
+	if (result.exitCode === 0 && summary.failed === 0) {
+		status = "passed";
+	} else if (summary.failed > 0) {
+		status = "failed";
+	} else {
+		status = "error";
+	}
+
+	console.log(
+		`[test-runs] ${command} in node ${nodeId}: ${status} (${summary.passed}/${summary.total} passed)`,
+	);
+
+	const run = localDb
+		.insert(testRuns)
+		.values({
+			nodeId,
+			command,
+			framework,
+			status,
+			exitCode: result.exitCode,
+			summary,
+			results,
+			output: redactOutput(tailOutput(result.output)),
+			durationMs: result.durationMs,
+			coverage: coverage?.files ?? null,
+			coverageReportPath: coverage?.reportPath ?? null,
+		})
+		.returning()
+		.get();
+
+	recordAuditEvent({
+		type: "test_run",
+		nodeId,
+		summary: `Tests ${status}: ${command}`,
+		data: { testRunId: run.id, exitCode: result.exitCode, ...summary },
+	});
+
+	return run;
+}
@@ -57,6 +57,7 @@ export function checkNodeUsability(
  *
  * The error includes a `cause` object with details that the frontend can use
  * to display appropriate UI (e.g., progress view for initializing, error for failed).
+ * Failed nodes are marked `retryable` so callers can offer `nodes.retryInit`.
  */
 export function assertNodeUsable(nodeId: string, worktreePath: string | null | undefined): void {
 	const check = checkNodeUsability(nodeId, worktreePath);
@@ -73,7 +74,7 @@ export function assertNodeUsable(nodeId: string, worktreePath: string | null | u
 				throw new TRPCError({
 					code: "PRECONDITION_FAILED",
 					message: "Node initialization failed",
-					cause: { reason: "failed", progress: check.progress },
+					cause: { reason: "failed", progress: check.progress, retryable: true },
 				});
 			case "path_missing":
 				throw new TRPCError({
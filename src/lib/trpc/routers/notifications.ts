@@ -1,11 +1,37 @@
 import { observable } from "@trpc/server/observable";
+import { handleNotificationAction } from "main/lib/notifications/actions";
+import { AGENT_SCENARIOS } from "main/lib/notifications/agent-scenarios";
+import { getBadgeCounts } from "main/lib/notifications/badge";
+import {
+	clearNotifications,
+	listNotifications,
+	markNotificationsRead,
+} from "main/lib/notifications/history";
 import {
 	type AgentLifecycleEvent,
 	type NotificationIds,
 	notificationsEmitter,
 } from "main/lib/notifications/server";
+import { cancelSimulatedAgents, simulateAgentScenario } from "main/lib/notifications/simulator";
+import { isLocalOnlyMode } from "main/lib/privacy";
+import {
+	addWebhook,
+	listWebhookDeliveries,
+	listWebhooks,
+	removeWebhook,
+	sendTestWebhook,
+	updateWebhook,
+} from "main/lib/webhooks";
 import { NOTIFICATION_EVENTS } from "shared/constants";
+import {
+	NOTIFICATION_ACTIONS,
+	type NotificationDigestEvent,
+	type OpenTerminalRequest,
+} from "shared/notification-types";
+import { WEBHOOK_EVENT_TYPES } from "shared/types/webhooks";
+import { z } from "zod";
 import { publicProcedure, router } from "..";
+import { blockedInLocalOnlyMode } from "../errors";
 
 type TerminalExitNotification = NotificationIds & {
 	exitCode: number;
@@ -22,7 +48,9 @@ type NotificationEvent =
 	| {
 			type: typeof NOTIFICATION_EVENTS.TERMINAL_EXIT;
 			data?: TerminalExitNotification;
-	  };
+	  }
+	| { type: typeof NOTIFICATION_EVENTS.DIGEST; data?: NotificationDigestEvent }
+	| { type: typeof NOTIFICATION_EVENTS.OPEN_TERMINAL; data?: OpenTerminalRequest };
 
 export const createNotificationsRouter = () => {
 	return router({
@@ -40,16 +68,137 @@ export const createNotificationsRouter = () => {
 					emit.next({ type: NOTIFICATION_EVENTS.TERMINAL_EXIT, data });
 				};
 
+				const onDigest = (data: NotificationDigestEvent) => {
+					emit.next({ type: NOTIFICATION_EVENTS.DIGEST, data });
+				};
+
+				const onOpenTerminal = (data: OpenTerminalRequest) => {
+					emit.next({ type: NOTIFICATION_EVENTS.OPEN_TERMINAL, data });
+				};
+
 				notificationsEmitter.on(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, onLifecycle);
 				notificationsEmitter.on(NOTIFICATION_EVENTS.FOCUS_TAB, onFocusTab);
 				notificationsEmitter.on(NOTIFICATION_EVENTS.TERMINAL_EXIT, onTerminalExit);
+				notificationsEmitter.on(NOTIFICATION_EVENTS.DIGEST, onDigest);
+				notificationsEmitter.on(NOTIFICATION_EVENTS.OPEN_TERMINAL, onOpenTerminal);
 
 				return () => {
 					notificationsEmitter.off(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, onLifecycle);
 					notificationsEmitter.off(NOTIFICATION_EVENTS.FOCUS_TAB, onFocusTab);
 					notificationsEmitter.off(NOTIFICATION_EVENTS.TERMINAL_EXIT, onTerminalExit);
+					notificationsEmitter.off(NOTIFICATION_EVENTS.DIGEST, onDigest);
+					notificationsEmitter.off(NOTIFICATION_EVENTS.OPEN_TERMINAL, onOpenTerminal);
 				};
 			});
 		}),
+
+		/** Notification history for the in-app inbox, newest first */
+		list: publicProcedure
+			.input(
+				z.object({
+					unreadOnly: z.boolean().optional(),
+					before: z.number().optional(),
+					limit: z.number().int().min(1).max(200).default(50),
+				}),
+			)
+			.query(({ input }) => listNotifications(input)),
+
+		/** Omitting ids marks everything read */
+		markRead: publicProcedure
+			.input(z.object({ ids: z.array(z.string()).optional() }))
+			.mutation(({ input }) => ({ updated: markNotificationsRead(input.ids) })),
+
+		/** Omitting ids clears the whole history */
+		clear: publicProcedure
+			.input(z.object({ ids: z.array(z.string()).optional() }))
+			.mutation(({ input }) => ({ deleted: clearNotifications(input.ids) })),
+
+		/** Nodes needing attention across all repositories, as shown on the app badge */
+		getBadgeCounts: publicProcedure.query(() => getBadgeCounts()),
+
+		handleAction: publicProcedure
+			.input(z.object({ id: z.string(), action: z.enum(NOTIFICATION_ACTIONS) }))
+			.mutation(({ input }) => handleNotificationAction(input)),
+
+		listWebhooks: publicProcedure.query(() => listWebhooks()),
+
+		/** The URL is kept in secure storage and never returned */
+		addWebhook: publicProcedure
+			.input(
+				z.object({
+					name: z.string().trim().min(1),
+					url: z.string().url(),
+					events: z.array(z.enum(WEBHOOK_EVENT_TYPES)).min(1),
+				}),
+			)
+			.mutation(({ input }) => addWebhook(input)),
+
+		updateWebhook: publicProcedure
+			.input(
+				z.object({
+					id: z.string(),
+					name: z.string().trim().min(1).optional(),
+					url: z.string().url().optional(),
+					events: z.array(z.enum(WEBHOOK_EVENT_TYPES)).min(1).optional(),
+					enabled: z.boolean().optional(),
+				}),
+			)
+			.mutation(({ input }) => {
+				const webhook = updateWebhook(input);
+				if (!webhook) {
+					throw new Error(`Webhook ${input.id} not found`);
+				}
+				return webhook;
+			}),
+
+		removeWebhook: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(({ input }) => {
+				removeWebhook(input.id);
+				return { success: true };
+			}),
+
+		/** Sends a sample event; the result shows up in the delivery log */
+		testWebhook: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(({ input }) => {
+				if (isLocalOnlyMode()) {
+					throw blockedInLocalOnlyMode("Sending webhooks");
+				}
+				const delivery = sendTestWebhook(input.id);
+				if (!delivery) {
+					throw new Error(`Webhook ${input.id} not found`);
+				}
+				return delivery;
+			}),
+
+		/** Delivery log, newest first; retries update an entry in place */
+		listWebhookDeliveries: publicProcedure
+			.input(
+				z.object({
+					webhookId: z.string().optional(),
+					limit: z.number().int().min(1).max(200).default(50),
+				}),
+			)
+			.query(({ input }) => listWebhookDeliveries(input)),
+
+		// Development helpers: replay scripted agent runs without a real agent
+		simulateAgentScenario: publicProcedure
+			.input(
+				z.object({
+					scenario: z.enum(AGENT_SCENARIOS),
+					nodeId: z.string().optional(),
+					tabId: z.string().optional(),
+					paneId: z.string().optional(),
+					speed: z.number().positive().optional(),
+				}),
+			)
+			.mutation(({ input }) => {
+				return { started: simulateAgentScenario(input) };
+			}),
+
+		cancelSimulatedAgents: publicProcedure.mutation(() => {
+			return { cancelled: cancelSimulatedAgents() };
+		}),
 	});
 };
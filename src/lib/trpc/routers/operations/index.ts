@@ -0,0 +1,43 @@
+import { observable } from "@trpc/server/observable";
+import { operationManager } from "main/lib/operation-manager";
+import type { OperationProgress } from "shared/types/operations";
+import { z } from "zod";
+import { publicProcedure, router } from "../..";
+
+export const createOperationsRouter = () => {
+	return router({
+		/** Running operations and recently finished ones, newest first */
+		list: publicProcedure.query(() => operationManager.list()),
+
+		/**
+		 * Cancels a running operation started with this operationId. Returns
+		 * false when it already finished, never started or can't be cancelled.
+		 */
+		cancel: publicProcedure
+			.input(z.object({ operationId: z.string().min(1) }))
+			.mutation(({ input }) => ({ cancelled: operationManager.cancel(input.operationId) })),
+
+		/** Streams every change to an operation, starting with the running ones */
+		onProgress: publicProcedure.subscription(() => {
+			return observable<OperationProgress>((emit) => {
+				const handler = (progress: OperationProgress) => {
+					emit.next(progress);
+				};
+
+				for (const operation of operationManager.list()) {
+					if (operation.status === "running") {
+						emit.next(operation);
+					}
+				}
+
+				operationManager.on("progress", handler);
+
+				return () => {
+					operationManager.off("progress", handler);
+				};
+			});
+		}),
+	});
+};
+
+export type OperationsRouter = ReturnType<typeof createOperationsRouter>;
@@ -1,25 +1,50 @@
 import { existsSync, statSync } from "node:fs";
-import { access } from "node:fs/promises";
-import { basename, join } from "node:path";
-import { TRPCError } from "@trpc/server";
+import { access, mkdir, readFile, writeFile } from "node:fs/promises";
+import { basename, dirname, join } from "node:path";
+import { observable } from "@trpc/server/observable";
 import { and, desc, eq, inArray, isNull, not } from "drizzle-orm";
 import type { BrowserWindow } from "electron";
 import { dialog } from "electron";
 import {
 	BRANCH_PREFIX_MODES,
+	COMMIT_SIGNING_FORMATS,
+	gateRuns,
 	nodes,
+	nodeSnapshots,
+	notificationHistory,
+	notificationPreferencesSchema,
 	repositories,
+	reviewComments,
 	type SelectRepository,
 	settings,
+	testRuns,
 	worktrees,
 } from "lib/local-db";
 import { track } from "main/lib/analytics";
+import { changesCache } from "main/lib/changes-cache";
+import { devServerManager } from "main/lib/dev-server-manager";
 import { localDb } from "main/lib/local-db";
+import { updateAppBadge } from "main/lib/notifications/badge";
 import { getNodeRuntimeRegistry } from "main/lib/node-runtime";
+import { operationManager } from "main/lib/operation-manager";
+import { isUntouchedPendingClone } from "main/lib/pending-clones";
+import { repositoryMaintenanceManager } from "main/lib/repository-maintenance-manager";
+import {
+	clearRepositoryEnv,
+	deleteRepositoryEnvVar,
+	isValidEnvVarName,
+	listRepositoryEnvVarNames,
+	setRepositoryEnvVar,
+} from "main/lib/secrets/repository-env";
 import { REPOSITORY_COLOR_VALUES } from "shared/constants/repository-colors";
+import { getTemplateFiles, REPOSITORY_TEMPLATE_IDS } from "shared/repository-templates";
+import { ISSUE_PROVIDERS } from "shared/types/issues";
+import type { RepositoryMaintenanceProgress } from "shared/types/repo-maintenance";
+import { getBranchTemplateError } from "shared/utils/branch";
 import simpleGit from "simple-git";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
+import { CaspianError, repositoryNotFound } from "../../errors";
 import {
 	activateRepository,
 	getBranchNode,
@@ -34,11 +59,76 @@ import {
 	refreshDefaultBranch,
 	sanitizeAuthorPrefix,
 } from "../nodes/utils/git";
+import { getGitHubAccount } from "../nodes/utils/github/accounts";
+import { getHostingAuthStatus, type HostingAuthStatus } from "../nodes/utils/hosting";
+import { loadSetupConfig, updateSetupConfig } from "../nodes/utils/setup";
+import { deleteAllSnapshotRefs } from "../nodes/utils/snapshots";
+import { checkSshSetup, preflightSsh, type SshDiagnostics } from "../nodes/utils/ssh";
+import { cloneRepository } from "./utils/clone";
 import { getDefaultRepositoryColor } from "./utils/colors";
+import { checkSigningConfigured } from "./utils/commit-signing";
 import { fetchGitHubOwner, getGitHubAvatarUrl } from "./utils/github";
+import { MaintenanceInProgressError, runRepositoryMaintenance } from "./utils/maintenance";
+import {
+	buildWorkspaceBundle,
+	importWorkspaceBundle,
+	parseWorkspaceBundle,
+	type WorkspaceBundle,
+} from "./utils/workspace-bundle";
 
 type Repository = SelectRepository;
 
+const commandListSchema = z.array(z.string().trim().min(1));
+
+const setupConfigPatchSchema = z.object({
+	setup: commandListSchema.nullable().optional(),
+	teardown: commandListSchema.nullable().optional(),
+	gate: z
+		.object({
+			lint: z.string().trim().min(1).optional(),
+			test: z.string().trim().min(1).optional(),
+			build: z.string().trim().min(1).optional(),
+			required: z.boolean().optional(),
+		})
+		.nullable()
+		.optional(),
+	defaults: z
+		.object({
+			testCommand: z.string().trim().min(1).optional(),
+			branchPrefix: z.string().trim().min(1).optional(),
+			branchTemplate: z
+				.string()
+				.trim()
+				.min(1)
+				.superRefine((template, ctx) => {
+					const error = getBranchTemplateError(template);
+					if (error) ctx.addIssue({ code: "custom", message: error });
+				})
+				.optional(),
+		})
+		.nullable()
+		.optional(),
+	dev: z
+		.object({
+			command: z.string().trim().min(1),
+			portEnv: z.string().regex(/^[A-Za-z_][A-Za-z0-9_]*$/).optional(),
+			basePort: z.number().int().min(1024).max(65000).optional(),
+		})
+		.nullable()
+		.optional(),
+	issues: z
+		.object({
+			provider: z.enum(ISSUE_PROVIDERS),
+			jiraSite: z.string().trim().min(1).optional(),
+			transitionOnClose: z.string().trim().min(1).optional(),
+		})
+		.refine((issues) => issues.provider !== "jira" || !!issues.jiraSite, {
+			message: "jiraSite is required for Jira",
+		})
+		.nullable()
+		.optional(),
+});
+
 type OpenNewCanceled = { canceled: true };
 type OpenNewSuccess = { canceled: false; repository: Repository };
 type OpenNewNeedsGitInit = {
@@ -52,7 +142,8 @@ export type OpenNewResult = OpenNewCanceled | OpenNewSuccess | OpenNewNeedsGitIn
 /**
  * Creates or updates a repository record in the database.
  * If a repository with the same mainRepoPath exists, updates lastOpenedAt.
- * Otherwise, creates a new repository.
+ * Otherwise, creates a new repository. Either way the repository is ready:
+ * a folder the user opened is theirs, even if a clone into it never finished.
  */
 function upsertRepository(mainRepoPath: string, defaultBranch: string): Repository {
 	const name = basename(mainRepoPath);
@@ -66,10 +157,10 @@ function upsertRepository(mainRepoPath: string, defaultBranch: string): Reposito
 	if (existing) {
 		localDb
 			.update(repositories)
-			.set({ lastOpenedAt: Date.now(), defaultBranch })
+			.set({ lastOpenedAt: Date.now(), defaultBranch, initStatus: "ready" })
 			.where(eq(repositories.id, existing.id))
 			.run();
-		return { ...existing, lastOpenedAt: Date.now(), defaultBranch };
+		return { ...existing, lastOpenedAt: Date.now(), defaultBranch, initStatus: "ready" };
 	}
 
 	const repository = localDb
@@ -237,17 +328,19 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				.get();
 
 			if (!repository) {
-				throw new TRPCError({
-					code: "NOT_FOUND",
-					message: `Repository ${input.id} not found`,
-				});
+				throw repositoryNotFound(input.id);
 			}
 
 			return repository;
 		}),
 
 		getRecents: publicProcedure.query((): Repository[] => {
-			return localDb.select().from(repositories).orderBy(desc(repositories.lastOpenedAt)).all();
+			return localDb
+				.select()
+				.from(repositories)
+				.where(eq(repositories.initStatus, "ready"))
+				.orderBy(desc(repositories.lastOpenedAt))
+				.all();
 		}),
 
 		getBranches: publicProcedure.input(z.object({ repositoryId: z.string() })).query(
@@ -268,7 +361,7 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 					.where(eq(repositories.id, input.repositoryId))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.repositoryId} not found`);
+					throw repositoryNotFound(input.repositoryId);
 				}
 
 				const git = simpleGit(repository.mainRepoPath);
@@ -520,7 +613,13 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 			}),
 
 		initGitAndOpen: publicProcedure
-			.input(z.object({ path: z.string() }))
+			.input(
+				z.object({
+					path: z.string(),
+					/** Starter .gitignore, README and Caspian config, committed as the initial commit */
+					template: z.enum(REPOSITORY_TEMPLATE_IDS).optional(),
+				}),
+			)
 			.mutation(async ({ input }) => {
 				const git = simpleGit(input.path);
 
@@ -532,6 +631,19 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 					await git.init();
 				}
 
+				if (input.template) {
+					// Files already in the folder win; only what was written gets committed
+					const written: string[] = [];
+					for (const file of getTemplateFiles(input.template, basename(input.path))) {
+						const filePath = join(input.path, file.path);
+						if (existsSync(filePath)) continue;
+						await mkdir(dirname(filePath), { recursive: true });
+						await writeFile(filePath, file.content);
+						written.push(file.path);
+					}
+					if (written.length > 0) await git.add(written);
+				}
+
 				try {
 					await git.raw(["commit", "--allow-empty", "-m", "Initial commit"]);
 				} catch (err) {
@@ -541,13 +653,18 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						errorMessage.includes("user.email") ||
 						errorMessage.includes("user.name")
 					) {
-						throw new Error(
-							"Git user not configured. Please run:\n" +
-								'  git config --global user.name "Your Name"\n' +
-								'  git config --global user.email "you@example.com"',
-						);
+						throw new CaspianError({
+							code: "GIT_IDENTITY_MISSING",
+							message: "Git user not configured",
+							hint:
+								'Run: git config --global user.name "Your Name" && ' +
+								'git config --global user.email "you@example.com"',
+						});
 					}
-					throw new Error(`Failed to create initial commit: ${errorMessage}`);
+					throw new CaspianError({
+						code: "GIT_OPERATION_FAILED",
+						message: `Failed to create initial commit: ${errorMessage}`,
+					});
 				}
 
 				const branchSummary = await git.branch();
@@ -574,6 +691,8 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						.trim()
 						.optional()
 						.transform((v) => (v && v.length > 0 ? v : undefined)),
+					/** Lets the renderer cancel the clone through operations.cancel */
+					operationId: z.string().optional(),
 				}),
 			)
 			.mutation(async ({ input }) => {
@@ -618,7 +737,11 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						.where(eq(repositories.mainRepoPath, clonePath))
 						.get();
 
-					if (existingRepository) {
+					// A clone interrupted by quitting is resumed rather than reported as a conflict
+					const interruptedClone =
+						existingRepository?.initStatus === "cloning" ? existingRepository : undefined;
+
+					if (existingRepository && !interruptedClone) {
 						try {
 							await access(clonePath);
 							localDb
@@ -648,7 +771,29 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						}
 					}
 
-					if (existsSync(clonePath)) {
+					const cloneInFlight = operationManager
+						.list()
+						.some((op) => op.status === "running" && op.subjectId === interruptedClone?.id);
+					if (cloneInFlight) {
+						return {
+							canceled: false as const,
+							success: false as const,
+							error: `"${repoName}" is already being cloned to this location.`,
+						};
+					}
+
+					// Only a folder holding that clone, untouched since, may be resumed or replaced
+					const resumable =
+						interruptedClone !== undefined &&
+						(await isUntouchedPendingClone({
+							clonePath,
+							repositoryId: interruptedClone.id,
+							url: input.url,
+						}));
+					if (!resumable && existsSync(clonePath)) {
+						if (interruptedClone) {
+							localDb.delete(repositories).where(eq(repositories.id, interruptedClone.id)).run();
+						}
 						return {
 							canceled: false as const,
 							success: false as const,
@@ -656,19 +801,64 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						};
 					}
 
-					const git = simpleGit();
-					await git.clone(input.url, clonePath);
+					const sshProblem = await preflightSsh(input.url);
+					if (sshProblem) {
+						return {
+							canceled: false as const,
+							success: false as const,
+							error: sshProblem,
+						};
+					}
+
+					const pending =
+						interruptedClone ??
+						localDb
+							.insert(repositories)
+							.values({
+								mainRepoPath: clonePath,
+								name: repoName,
+								color: getDefaultRepositoryColor(),
+								initStatus: "cloning",
+							})
+							.returning()
+							.get();
+
+					const cloned = await operationManager.run(
+						{
+							id: input.operationId,
+							kind: "clone",
+							title: `Clone ${repoName}`,
+							subjectId: pending.id,
+						},
+						async ({ signal, report }) => {
+							try {
+								await cloneRepository({
+									url: input.url,
+									clonePath,
+									repositoryId: pending.id,
+									signal,
+									onProgress: ({ stage, progress }) => report(stage, progress),
+								});
+								return true;
+							} catch (error) {
+								localDb.delete(repositories).where(eq(repositories.id, pending.id)).run();
+								if (!signal.aborted) throw error;
+								return false;
+							}
+						},
+					);
+					if (!cloned) {
+						return { canceled: true as const, success: false as const };
+					}
 
-					const name = basename(clonePath);
-					const defaultBranch = await getDefaultBranch(clonePath);
 					const repository = localDb
-						.insert(repositories)
-						.values({
-							mainRepoPath: clonePath,
-							name,
-							color: getDefaultRepositoryColor(),
-							defaultBranch,
+						.update(repositories)
+						.set({
+							initStatus: "ready",
+							defaultBranch: await getDefaultBranch(clonePath),
+							lastOpenedAt: Date.now(),
 						})
+						.where(eq(repositories.id, pending.id))
 						.returning()
 						.get();
 
@@ -709,6 +899,15 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 							.optional(),
 						branchPrefixMode: z.enum(BRANCH_PREFIX_MODES).nullable().optional(),
 						branchPrefixCustom: z.string().nullable().optional(),
+						commitAuthorName: z.string().trim().nullable().optional(),
+						commitAuthorEmail: z.string().trim().nullable().optional(),
+						commitSigningKey: z.string().trim().nullable().optional(),
+						commitSigningFormat: z.enum(COMMIT_SIGNING_FORMATS).nullable().optional(),
+						notificationPreferences: notificationPreferencesSchema
+							.omit({ digestWindowSeconds: true })
+							.nullable()
+							.optional(),
+						githubAccountId: z.string().nullable().optional(),
 					}),
 				}),
 			)
@@ -719,7 +918,13 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 					.where(eq(repositories.id, input.id))
 					.get();
 				if (!repository) {
-					throw new Error(`Repository ${input.id} not found`);
+					throw repositoryNotFound(input.id);
+				}
+				if (input.patch.githubAccountId && !getGitHubAccount(input.patch.githubAccountId)) {
+					throw new CaspianError({
+						code: "NOT_FOUND",
+						message: `GitHub account ${input.patch.githubAccountId} not found`,
+					});
 				}
 
 				localDb
@@ -735,6 +940,27 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 						...(input.patch.branchPrefixCustom !== undefined && {
 							branchPrefixCustom: input.patch.branchPrefixCustom,
 						}),
+						// Empty strings clear the override so git config applies again
+						...(input.patch.commitAuthorName !== undefined && {
+							commitAuthorName: input.patch.commitAuthorName || null,
+						}),
+						...(input.patch.commitAuthorEmail !== undefined && {
+							commitAuthorEmail: input.patch.commitAuthorEmail || null,
+						}),
+						...(input.patch.commitSigningKey !== undefined && {
+							commitSigningKey: input.patch.commitSigningKey || null,
+						}),
+						...(input.patch.commitSigningFormat !== undefined && {
+							commitSigningFormat: input.patch.commitSigningFormat,
+						}),
+						// null drops every override so the global preferences apply
+						...(input.patch.notificationPreferences !== undefined && {
+							notificationPreferences: input.patch.notificationPreferences,
+						}),
+						// null goes back to the default gh login and token
+						...(input.patch.githubAccountId !== undefined && {
+							githubAccountId: input.patch.githubAccountId,
+						}),
 						lastOpenedAt: Date.now(),
 					})
 					.where(eq(repositories.id, input.id))
@@ -767,7 +993,10 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 					toIndex < 0 ||
 					toIndex >= activeRepositories.length
 				) {
-					throw new Error("Invalid fromIndex or toIndex");
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: "Invalid fromIndex or toIndex",
+					});
 				}
 
 				const [removed] = activeRepositories.splice(fromIndex, 1);
@@ -794,7 +1023,7 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 					.get();
 
 				if (!repository) {
-					throw new Error(`Repository ${input.id} not found`);
+					throw repositoryNotFound(input.id);
 				}
 
 				const remoteDefaultBranch = await refreshDefaultBranch(repository.mainRepoPath);
@@ -835,7 +1064,7 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				.get();
 
 			if (!repository) {
-				throw new Error("Repository not found");
+				throw repositoryNotFound(input.id);
 			}
 
 			const repositoryNodes = localDb
@@ -850,12 +1079,32 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				const terminal = registry.getForNodeId(node.id).terminal;
 				const terminalResult = await terminal.killByWorkspaceId(node.id);
 				totalFailed += terminalResult.failed;
+				await devServerManager.remove(node.id);
+			}
+
+			const repositoryWorktrees = localDb
+				.select({ path: worktrees.path })
+				.from(worktrees)
+				.where(eq(worktrees.repositoryId, input.id))
+				.all();
+			for (const path of [repository.mainRepoPath, ...repositoryWorktrees.map((w) => w.path)]) {
+				changesCache.stopWatching(path);
 			}
 
 			const closedNodeIds = repositoryNodes.map((n) => n.id);
 
 			if (closedNodeIds.length > 0) {
+				localDb.delete(reviewComments).where(inArray(reviewComments.nodeId, closedNodeIds)).run();
+				localDb.delete(testRuns).where(inArray(testRuns.nodeId, closedNodeIds)).run();
+				localDb.delete(gateRuns).where(inArray(gateRuns.nodeId, closedNodeIds)).run();
+				localDb.delete(nodeSnapshots).where(inArray(nodeSnapshots.nodeId, closedNodeIds)).run();
+				await deleteAllSnapshotRefs(repository.mainRepoPath);
+				localDb
+					.delete(notificationHistory)
+					.where(inArray(notificationHistory.nodeId, closedNodeIds))
+					.run();
 				localDb.delete(nodes).where(inArray(nodes.id, closedNodeIds)).run();
+				updateAppBadge();
 			}
 
 			// Hide the repository by setting tabOrder to null
@@ -897,15 +1146,12 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				.get();
 
 			if (!repository) {
-				throw new TRPCError({ code: "NOT_FOUND", message: "Repository not found" });
+				throw repositoryNotFound(input.id);
 			}
 
 			const window = getWindow();
 			if (!window) {
-				throw new TRPCError({
-					code: "INTERNAL_SERVER_ERROR",
-					message: "No window available",
-				});
+				throw new CaspianError({ code: "NO_WINDOW", message: "No window available" });
 			}
 
 			const result = await dialog.showOpenDialog(window, {
@@ -950,7 +1196,7 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				.get();
 
 			if (!repository) {
-				throw new TRPCError({ code: "NOT_FOUND", message: "Repository not found" });
+				throw repositoryNotFound(input.id);
 			}
 
 			const repositoryNodes = localDb
@@ -963,16 +1209,37 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 			for (const node of repositoryNodes) {
 				const terminal = registry.getForNodeId(node.id).terminal;
 				await terminal.killByWorkspaceId(node.id);
+				await devServerManager.remove(node.id);
+			}
+
+			const repositoryWorktrees = localDb
+				.select({ path: worktrees.path })
+				.from(worktrees)
+				.where(eq(worktrees.repositoryId, input.id))
+				.all();
+			for (const path of [repository.mainRepoPath, ...repositoryWorktrees.map((w) => w.path)]) {
+				changesCache.stopWatching(path);
 			}
 
 			const closedNodeIds = repositoryNodes.map((n) => n.id);
 
 			if (closedNodeIds.length > 0) {
+				localDb.delete(reviewComments).where(inArray(reviewComments.nodeId, closedNodeIds)).run();
+				localDb.delete(testRuns).where(inArray(testRuns.nodeId, closedNodeIds)).run();
+				localDb.delete(gateRuns).where(inArray(gateRuns.nodeId, closedNodeIds)).run();
+				localDb.delete(nodeSnapshots).where(inArray(nodeSnapshots.nodeId, closedNodeIds)).run();
+				await deleteAllSnapshotRefs(repository.mainRepoPath);
+				localDb
+					.delete(notificationHistory)
+					.where(inArray(notificationHistory.nodeId, closedNodeIds))
+					.run();
 				localDb.delete(nodes).where(inArray(nodes.id, closedNodeIds)).run();
+				updateAppBadge();
 			}
 
 			localDb.delete(worktrees).where(eq(worktrees.repositoryId, input.id)).run();
 			localDb.delete(repositories).where(eq(repositories.id, input.id)).run();
+			clearRepositoryEnv(input.id);
 
 			const currentSettings = localDb.select().from(settings).get();
 			if (
@@ -1037,6 +1304,100 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				};
 			}),
 
+		/**
+		 * Which service hosts the repository's origin and whether its API is
+		 * usable: gh or glab signed in, or a Bitbucket access token set. Null
+		 * for other hosts, where pull request features are unavailable.
+		 */
+		/** Names of the variables exported into the repository's terminals; values aren't returned */
+		listEnvVars: publicProcedure.input(z.object({ id: z.string() })).query(({ input }) => {
+			return listRepositoryEnvVarNames(input.id);
+		}),
+
+		setEnvVar: publicProcedure
+			.input(
+				z.object({
+					id: z.string(),
+					name: z.string().refine(isValidEnvVarName, "Invalid environment variable name"),
+					value: z.string(),
+				}),
+			)
+			.mutation(({ input }) => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				try {
+					setRepositoryEnvVar(input.id, input.name, input.value);
+				} catch (error) {
+					throw new CaspianError({
+						code: "SECURE_STORAGE_UNAVAILABLE",
+						message: error instanceof Error ? error.message : String(error),
+					});
+				}
+				return { success: true };
+			}),
+
+		deleteEnvVar: publicProcedure
+			.input(z.object({ id: z.string(), name: z.string() }))
+			.mutation(({ input }) => {
+				deleteRepositoryEnvVar(input.id, input.name);
+				return { success: true };
+			}),
+
+		getHostingAuthStatus: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.query(async ({ input }): Promise<HostingAuthStatus | null> => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				return getHostingAuthStatus(repository.mainRepoPath);
+			}),
+
+		/**
+		 * Diagnoses SSH access to a repository's origin, or to a URL about to
+		 * be cloned: agent keys, the identity files ssh would use and a test
+		 * login. Null for remotes that don't use SSH.
+		 */
+		checkSshSetup: publicProcedure
+			.input(z.object({ id: z.string() }).or(z.object({ url: z.string().min(1) })))
+			.query(async ({ input }): Promise<SshDiagnostics | null> => {
+				if ("url" in input) {
+					return checkSshSetup(input.url);
+				}
+
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				let remoteUrl: string;
+				try {
+					const git = simpleGit(repository.mainRepoPath);
+					remoteUrl = (await git.remote(["get-url", "origin"])) || "";
+				} catch {
+					return null;
+				}
+				return checkSshSetup(remoteUrl);
+			}),
+
 		getGitAuthor: publicProcedure.input(z.object({ id: z.string() })).query(async ({ input }) => {
 			const repository = localDb
 				.select()
@@ -1058,6 +1419,218 @@ export const createRepositoriesRouter = (getWindow: () => BrowserWindow | null)
 				prefix: sanitizeAuthorPrefix(authorName),
 			};
 		}),
+
+		checkSigningConfigured: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.query(async ({ input }) => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				return checkSigningConfigured(repository);
+			}),
+
+		/**
+		 * Repository config from .caspian/config.json (setup/teardown scripts,
+		 * quality gate and node defaults). Empty when the file doesn't exist.
+		 */
+		getConfig: publicProcedure.input(z.object({ id: z.string() })).query(({ input }) => {
+			const repository = localDb
+				.select()
+				.from(repositories)
+				.where(eq(repositories.id, input.id))
+				.get();
+
+			if (!repository) {
+				throw repositoryNotFound(input.id);
+			}
+
+			return loadSetupConfig(repository.mainRepoPath) ?? {};
+		}),
+
+		updateConfig: publicProcedure
+			.input(z.object({ id: z.string(), config: setupConfigPatchSchema }))
+			.mutation(({ input }) => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				return updateSetupConfig(repository.mainRepoPath, input.config);
+			}),
+
+		/**
+		 * Writes the repository's nodes, settings and history to a single JSON
+		 * bundle. Worktree contents aren't included; branches carry the code.
+		 */
+		exportWorkspace: publicProcedure
+			.input(z.object({ id: z.string(), filePath: z.string().optional() }))
+			.mutation(async ({ input }) => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				let filePath = input.filePath;
+				if (!filePath) {
+					const window = getWindow();
+					if (!window) {
+						throw new CaspianError({ code: "NO_WINDOW", message: "No window available" });
+					}
+
+					const result = await dialog.showSaveDialog(window, {
+						title: "Export Workspace",
+						defaultPath: `${repository.name}-workspace.json`,
+						filters: [{ name: "Caspian Workspace", extensions: ["json"] }],
+					});
+
+					if (result.canceled || !result.filePath) {
+						return { canceled: true as const };
+					}
+					filePath = result.filePath;
+				}
+
+				const bundle = buildWorkspaceBundle(repository);
+				await writeFile(filePath, JSON.stringify(bundle, null, 2), "utf-8");
+
+				return { canceled: false as const, filePath, nodeCount: bundle.nodes.length };
+			}),
+
+		/**
+		 * Imports a workspace bundle into a checkout of the same repository on
+		 * this machine, opening the repository first if needed.
+		 */
+		importWorkspace: publicProcedure
+			.input(z.object({ mainRepoPath: z.string(), filePath: z.string().optional() }))
+			.mutation(async ({ input }) => {
+				let filePath = input.filePath;
+				if (!filePath) {
+					const window = getWindow();
+					if (!window) {
+						throw new CaspianError({ code: "NO_WINDOW", message: "No window available" });
+					}
+
+					const result = await dialog.showOpenDialog(window, {
+						title: "Import Workspace",
+						properties: ["openFile"],
+						filters: [{ name: "Caspian Workspace", extensions: ["json"] }],
+					});
+
+					if (result.canceled || result.filePaths.length === 0) {
+						return { canceled: true as const };
+					}
+					filePath = result.filePaths[0];
+				}
+
+				let bundle: WorkspaceBundle;
+				try {
+					bundle = parseWorkspaceBundle(await readFile(filePath, "utf-8"));
+				} catch (error) {
+					throw new CaspianError({
+						code: "INVALID_INPUT",
+						message: error instanceof Error ? error.message : "Could not read workspace bundle",
+					});
+				}
+
+				let mainRepoPath: string;
+				try {
+					mainRepoPath = await getGitRoot(input.mainRepoPath);
+				} catch {
+					throw new CaspianError({
+						code: "NOT_A_GIT_REPOSITORY",
+						message: `${input.mainRepoPath} is not a git repository`,
+					});
+				}
+
+				const defaultBranch = await getDefaultBranch(mainRepoPath);
+				const repository = upsertRepository(mainRepoPath, defaultBranch);
+				await ensureMainNode(repository);
+
+				const result = await importWorkspaceBundle({ bundle, repository });
+				return { canceled: false as const, ...result };
+			}),
+
+		runMaintenance: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(async ({ input }) => {
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, input.id))
+					.get();
+
+				if (!repository) {
+					throw repositoryNotFound(input.id);
+				}
+
+				try {
+					const result = await runRepositoryMaintenance({
+						repositoryId: repository.id,
+						mainRepoPath: repository.mainRepoPath,
+						trigger: "manual",
+					});
+
+					track("repository_maintenance_run", {
+						repository_id: repository.id,
+						reclaimed_bytes: result.reclaimedBytes,
+						duration_ms: result.durationMs,
+					});
+
+					return result;
+				} catch (error) {
+					if (error instanceof MaintenanceInProgressError) {
+						throw new CaspianError({ code: "OPERATION_IN_PROGRESS", message: error.message });
+					}
+					throw new CaspianError({
+						code: "GIT_OPERATION_FAILED",
+						message: `Maintenance failed: ${error instanceof Error ? error.message : String(error)}`,
+						cause: error,
+					});
+				}
+			}),
+
+		onMaintenanceProgress: publicProcedure
+			.input(z.object({ repositoryIds: z.array(z.string()).optional() }).optional())
+			.subscription(({ input }) => {
+				return observable<RepositoryMaintenanceProgress>((emit) => {
+					const matches = (progress: RepositoryMaintenanceProgress) =>
+						!input?.repositoryIds || input.repositoryIds.includes(progress.repositoryId);
+
+					const handler = (progress: RepositoryMaintenanceProgress) => {
+						if (matches(progress)) {
+							emit.next(progress);
+						}
+					};
+
+					for (const progress of repositoryMaintenanceManager.getAllProgress()) {
+						if (matches(progress)) {
+							emit.next(progress);
+						}
+					}
+
+					repositoryMaintenanceManager.on("progress", handler);
+
+					return () => {
+						repositoryMaintenanceManager.off("progress", handler);
+					};
+				});
+			}),
 	});
 };
 
@@ -0,0 +1,63 @@
+import { existsSync } from "node:fs";
+import { rm } from "node:fs/promises";
+import { isUntouchedPendingClone, PENDING_CLONE_CONFIG_KEY } from "main/lib/pending-clones";
+import simpleGit, { type SimpleGitProgressEvent } from "simple-git";
+import { getDefaultBranch } from "../../nodes/utils/git";
+
+interface CloneOptions {
+	url: string;
+	clonePath: string;
+	/** The "cloning" repository row, recorded in the clone so it can be recognized later */
+	repositoryId: string;
+	signal: AbortSignal;
+	onProgress: (event: SimpleGitProgressEvent) => void;
+}
+
+/**
+ * Picks up a clone interrupted by the app quitting. git writes the origin
+ * remote before fetching anything, so the rest can be fetched and checked
+ * out. Returns false when that fails and the clone has to start over.
+ */
+async function resumeClone({ clonePath, signal, onProgress }: CloneOptions): Promise<boolean> {
+	const git = simpleGit(clonePath, { abort: signal, progress: onProgress });
+	try {
+		await git.fetch(["origin"]);
+		await git.remote(["set-head", "origin", "--auto"]);
+		const branch = await getDefaultBranch(clonePath);
+		await git.raw(["checkout", "--force", "-B", branch, "--track", `origin/${branch}`]);
+		console.log(`[clone] Resumed interrupted clone at ${clonePath}`);
+		return true;
+	} catch (error) {
+		if (signal.aborted) throw error;
+		console.warn(`[clone] Could not resume clone at ${clonePath}, starting over:`, error);
+		return false;
+	}
+}
+
+/**
+ * Clones `url` into `clonePath`, resuming an unfinished clone of the same
+ * repository row found there. Refuses any other existing folder, so the only
+ * thing ever replaced or removed is a clone this app started and nobody has
+ * touched. Leaves nothing behind on failure.
+ */
+export async function cloneRepository(options: CloneOptions): Promise<void> {
+	const { url, clonePath, repositoryId, signal, onProgress } = options;
+	const resumable = await isUntouchedPendingClone({ clonePath, repositoryId, url });
+	if (!resumable && existsSync(clonePath)) {
+		throw new Error(`${clonePath} already exists`);
+	}
+
+	try {
+		if (!resumable || !(await resumeClone(options))) {
+			await rm(clonePath, { recursive: true, force: true });
+			await simpleGit({ abort: signal, progress: onProgress }).clone(url, clonePath, [
+				"--config",
+				`${PENDING_CLONE_CONFIG_KEY}=${repositoryId}`,
+			]);
+		}
+		await simpleGit(clonePath).raw(["config", "--local", "--unset", PENDING_CLONE_CONFIG_KEY]);
+	} catch (error) {
+		await rm(clonePath, { recursive: true, force: true });
+		throw error;
+	}
+}
@@ -0,0 +1,47 @@
+import { describe, expect, it } from "bun:test";
+import { buildCommitIdentityConfig, type CommitIdentity } from "./commit-identity";
+
+const EMPTY: CommitIdentity = {
+	commitAuthorName: null,
+	commitAuthorEmail: null,
+	commitSigningKey: null,
+	commitSigningFormat: null,
+};
+
+describe("buildCommitIdentityConfig", () => {
+	it("returns no overrides when nothing is configured", () => {
+		expect(buildCommitIdentityConfig(EMPTY)).toEqual([]);
+	});
+
+	it("overrides author name and email", () => {
+		expect(
+			buildCommitIdentityConfig({
+				...EMPTY,
+				commitAuthorName: "Ada Lovelace",
+				commitAuthorEmail: "ada@example.com",
+			}),
+		).toEqual(["user.name=Ada Lovelace", "user.email=ada@example.com"]);
+	});
+
+	it("ignores whitespace-only values", () => {
+		expect(buildCommitIdentityConfig({ ...EMPTY, commitAuthorName: "   " })).toEqual([]);
+	});
+
+	it("enables signing with the configured key and format", () => {
+		expect(
+			buildCommitIdentityConfig({
+				...EMPTY,
+				commitSigningKey: "~/.ssh/id_ed25519.pub",
+				commitSigningFormat: "ssh",
+			}),
+		).toEqual([
+			"user.signingkey=~/.ssh/id_ed25519.pub",
+			"commit.gpgsign=true",
+			"gpg.format=ssh",
+		]);
+	});
+
+	it("does not set a format without a signing key", () => {
+		expect(buildCommitIdentityConfig({ ...EMPTY, commitSigningFormat: "ssh" })).toEqual([]);
+	});
+});
@@ -0,0 +1,38 @@
+import type { CommitSigningFormat } from "lib/local-db";
+
+export interface CommitIdentity {
+	commitAuthorName: string | null;
+	commitAuthorEmail: string | null;
+	commitSigningKey: string | null;
+	commitSigningFormat: CommitSigningFormat | null;
+}
+
+/**
+ * Builds `-c key=value` overrides for a repository's commit identity.
+ * Unset fields are omitted so git falls back to the user's own config.
+ * Setting a signing key turns signing on; without one, signing is left to git config.
+ */
+export function buildCommitIdentityConfig(identity: CommitIdentity): string[] {
+	const config: string[] = [];
+
+	const name = identity.commitAuthorName?.trim();
+	if (name) {
+		config.push(`user.name=${name}`);
+	}
+
+	const email = identity.commitAuthorEmail?.trim();
+	if (email) {
+		config.push(`user.email=${email}`);
+	}
+
+	const signingKey = identity.commitSigningKey?.trim();
+	if (signingKey) {
+		config.push(`user.signingkey=${signingKey}`);
+		config.push("commit.gpgsign=true");
+		if (identity.commitSigningFormat) {
+			config.push(`gpg.format=${identity.commitSigningFormat}`);
+		}
+	}
+
+	return config;
+}
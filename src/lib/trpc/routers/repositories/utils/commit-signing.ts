@@ -0,0 +1,148 @@
+import { existsSync } from "node:fs";
+import { homedir } from "node:os";
+import { eq } from "drizzle-orm";
+import { repositories, type SelectRepository, worktrees } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { execWithShellEnv } from "../../nodes/utils/shell-env";
+import { buildCommitIdentityConfig } from "./commit-identity";
+
+/**
+ * Resolves the repository that owns a worktree path (or a branch node's main repo path).
+ */
+function getRepositoryForPath(workspacePath: string): SelectRepository | undefined {
+	const worktree = localDb
+		.select()
+		.from(worktrees)
+		.where(eq(worktrees.path, workspacePath))
+		.get();
+
+	if (worktree) {
+		return localDb
+			.select()
+			.from(repositories)
+			.where(eq(repositories.id, worktree.repositoryId))
+			.get();
+	}
+
+	return localDb
+		.select()
+		.from(repositories)
+		.where(eq(repositories.mainRepoPath, workspacePath))
+		.get();
+}
+
+/**
+ * Git config overrides for commits Caspian makes in a workspace.
+ * Pass to `simpleGit(path, { config })`; empty when the repository has no overrides.
+ */
+export function getCommitConfigForPath(workspacePath: string): string[] {
+	const repository = getRepositoryForPath(workspacePath);
+	return repository ? buildCommitIdentityConfig(repository) : [];
+}
+
+export interface SigningDiagnostics {
+	authorName: string | null;
+	authorEmail: string | null;
+	signingEnabled: boolean;
+	format: string;
+	signingKey: string | null;
+	/** Whether identity values come from Caspian's repository settings or plain git config */
+	source: "repository" | "git_config";
+	ok: boolean;
+	problems: string[];
+}
+
+async function readEffectiveConfig(
+	repoPath: string,
+	overrides: string[],
+	key: string,
+): Promise<string | null> {
+	const args = overrides.flatMap((entry) => ["-c", entry]);
+	try {
+		const { stdout } = await execWithShellEnv("git", [
+			...args,
+			"-C",
+			repoPath,
+			"config",
+			"--get",
+			key,
+		]);
+		return stdout.trim() || null;
+	} catch {
+		// Exit code 1 means the key is unset
+		return null;
+	}
+}
+
+function expandHome(path: string): string {
+	return path.startsWith("~/") ? `${homedir()}${path.slice(1)}` : path;
+}
+
+/**
+ * Checks that commits in a repository will have an author and, if signing is on,
+ * that the signing key can actually be used.
+ */
+export async function checkSigningConfigured(
+	repository: SelectRepository,
+): Promise<SigningDiagnostics> {
+	const overrides = buildCommitIdentityConfig(repository);
+	const repoPath = repository.mainRepoPath;
+
+	const [authorName, authorEmail, signingKey, format, gpgSign, gpgProgram] = await Promise.all([
+		readEffectiveConfig(repoPath, overrides, "user.name"),
+		readEffectiveConfig(repoPath, overrides, "user.email"),
+		readEffectiveConfig(repoPath, overrides, "user.signingkey"),
+		readEffectiveConfig(repoPath, overrides, "gpg.format"),
+		readEffectiveConfig(repoPath, overrides, "commit.gpgsign"),
+		readEffectiveConfig(repoPath, overrides, "gpg.program"),
+	]);
+
+	const effectiveFormat = format ?? "openpgp";
+	const signingEnabled = gpgSign === "true";
+	const problems: string[] = [];
+
+	if (!authorName) {
+		problems.push("No commit author name configured (user.name)");
+	}
+	if (!authorEmail) {
+		problems.push("No commit author email configured (user.email)");
+	}
+
+	if (signingEnabled) {
+		if (effectiveFormat === "ssh") {
+			if (!signingKey) {
+				problems.push("SSH signing is enabled but no signing key is set (user.signingkey)");
+			} else if (!signingKey.startsWith("key::") && !signingKey.startsWith("ssh-")) {
+				const keyPath = expandHome(signingKey);
+				if (!existsSync(keyPath)) {
+					problems.push(`SSH signing key not found at ${keyPath}`);
+				}
+			}
+		} else {
+			// openpgp and x509 both go through a gpg-compatible program
+			const program = gpgProgram ?? (effectiveFormat === "x509" ? "gpgsm" : "gpg");
+			const keyId = signingKey ?? authorEmail;
+			try {
+				await execWithShellEnv(program, ["--list-secret-keys", ...(keyId ? [keyId] : [])]);
+			} catch (error) {
+				const code = (error as NodeJS.ErrnoException).code;
+				problems.push(
+					code === "ENOENT"
+						? `Signing program "${program}" is not installed`
+						: `No secret key available for ${keyId ?? "the configured identity"}`,
+				);
+			}
+		}
+	}
+
+	return {
+		authorName,
+		authorEmail,
+		signingEnabled,
+		format: effectiveFormat,
+		signingKey,
+		source: overrides.length > 0 ? "repository" : "git_config",
+		ok: problems.length === 0,
+		problems,
+	};
+}
@@ -1,4 +1,5 @@
 import { z } from "zod";
+import { getGhEnvForPath } from "../../nodes/utils/github/accounts";
 import { execWithShellEnv } from "../../nodes/utils/shell-env";
 
 const GHRepoOwnerResponseSchema = z.object({
@@ -16,6 +17,7 @@ export async function fetchGitHubOwner(repoPath: string): Promise<string | null>
 		console.log("[fetchGitHubOwner] Running gh repo view in:", repoPath);
 		const { stdout, stderr } = await execWithShellEnv("gh", ["repo", "view", "--json", "owner"], {
 			cwd: repoPath,
+			env: getGhEnvForPath(repoPath),
 		});
 		if (stderr) {
 			console.log("[fetchGitHubOwner] stderr:", stderr);
@@ -0,0 +1,142 @@
+import { basename } from "node:path";
+import { eq } from "drizzle-orm";
+import { repositories } from "lib/local-db";
+import { localDb } from "main/lib/local-db";
+import { nodeInitManager } from "main/lib/node-init-manager";
+import { type OperationHandle, operationManager } from "main/lib/operation-manager";
+import { repositoryMaintenanceManager } from "main/lib/repository-maintenance-manager";
+import {
+	MAINTENANCE_STEP_MESSAGES,
+	type RepositoryMaintenanceResult,
+	type RepositoryMaintenanceStep,
+	type RepositoryMaintenanceTrigger,
+} from "shared/types/repo-maintenance";
+import { countObjects, pruneWorktrees, runGitMaintenance } from "../../nodes/utils/git";
+import { parseCountObjects } from "./object-store";
+
+export class MaintenanceInProgressError extends Error {
+	constructor(repositoryId: string) {
+		super(`Maintenance is already running for repository ${repositoryId}`);
+		this.name = "MaintenanceInProgressError";
+	}
+}
+
+/**
+ * Runs object store maintenance for a repository and reports size before/after.
+ * Holds the per-repository lock so gc never races worktree creation or removal.
+ */
+export async function runRepositoryMaintenance({
+	repositoryId,
+	mainRepoPath,
+	trigger,
+}: {
+	repositoryId: string;
+	mainRepoPath: string;
+	trigger: RepositoryMaintenanceTrigger;
+}): Promise<RepositoryMaintenanceResult> {
+	if (!repositoryMaintenanceManager.begin(repositoryId)) {
+		throw new MaintenanceInProgressError(repositoryId);
+	}
+
+	let operation: OperationHandle | null = null;
+	let locked = false;
+	const report = (
+		step: RepositoryMaintenanceStep,
+		extra?: { result?: RepositoryMaintenanceResult; error?: string },
+	) => {
+		repositoryMaintenanceManager.updateProgress({
+			repositoryId,
+			trigger,
+			step,
+			message: MAINTENANCE_STEP_MESSAGES[step],
+			...extra,
+		});
+		operation?.report(MAINTENANCE_STEP_MESSAGES[step]);
+	};
+
+	const startedAt = Date.now();
+	try {
+		// git gc isn't safe to interrupt, so this one can't be cancelled
+		operation = operationManager.begin({
+			kind: "maintenance",
+			title: `Maintain ${basename(mainRepoPath)}`,
+			subjectId: repositoryId,
+			cancellable: false,
+		});
+
+		report("queued");
+		await nodeInitManager.acquireRepositoryLock(repositoryId);
+		locked = true;
+
+		report("measuring");
+		const before = parseCountObjects(await countObjects(mainRepoPath));
+
+		report("pruning_worktrees");
+		await pruneWorktrees(mainRepoPath);
+
+		report("repacking");
+		// Scheduled runs defer to git's own heuristics; manual runs always repack
+		const { usedGcFallback } = await runGitMaintenance(mainRepoPath, {
+			auto: trigger === "scheduled",
+		});
+
+		report("measuring");
+		const after = parseCountObjects(await countObjects(mainRepoPath));
+
+		const result: RepositoryMaintenanceResult = {
+			repositoryId,
+			trigger,
+			before,
+			after,
+			reclaimedBytes: before.totalBytes - after.totalBytes,
+			durationMs: Date.now() - startedAt,
+			usedGcFallback,
+		};
+
+		repositoryMaintenanceManager.finish(repositoryId, true);
+		report("complete", { result });
+		operation.finish("succeeded");
+		console.log(
+			`[repositories/maintenance] ${repositoryId} reclaimed ${result.reclaimedBytes} bytes in ${result.durationMs}ms`,
+		);
+		return result;
+	} catch (error) {
+		const errorMessage = error instanceof Error ? error.message : String(error);
+		repositoryMaintenanceManager.finish(repositoryId, false);
+		report("failed", { error: errorMessage });
+		operation?.finish("failed", errorMessage);
+		console.error(`[repositories/maintenance] Failed for ${repositoryId}:`, errorMessage);
+		throw error;
+	} finally {
+		if (locked) nodeInitManager.releaseRepositoryLock(repositoryId);
+	}
+}
+
+/**
+ * Records a worktree removal and, once enough churn has accumulated,
+ * starts a scheduled maintenance run in the background.
+ * Must be called after the repository lock has been released.
+ */
+export function recordWorktreeChurn(repositoryId: string): void {
+	if (!repositoryMaintenanceManager.recordChurn(repositoryId)) {
+		return;
+	}
+
+	const repository = localDb
+		.select()
+		.from(repositories)
+		.where(eq(repositories.id, repositoryId))
+		.get();
+	if (!repository) {
+		return;
+	}
+
+	runRepositoryMaintenance({
+		repositoryId,
+		mainRepoPath: repository.mainRepoPath,
+		trigger: "scheduled",
+	}).catch((error) => {
+		// Scheduled runs are best-effort; progress events already show the failure
+		console.warn(`[repositories/maintenance] Scheduled run failed for ${repositoryId}:`, error);
+	});
+}
@@ -0,0 +1,42 @@
+import { describe, expect, it } from "bun:test";
+import { parseCountObjects } from "./object-store";
+
+describe("parseCountObjects", () => {
+	it("converts KiB sizes to bytes and sums the total", () => {
+		const output = [
+			"count: 12",
+			"size: 48",
+			"in-pack: 3021",
+			"packs: 2",
+			"size-pack: 1200",
+			"prune-packable: 0",
+			"garbage: 1",
+			"size-garbage: 4",
+			"",
+		].join("\n");
+
+		expect(parseCountObjects(output)).toEqual({
+			looseObjects: 12,
+			looseBytes: 48 * 1024,
+			packs: 2,
+			packBytes: 1200 * 1024,
+			garbageBytes: 4 * 1024,
+			totalBytes: (48 + 1200 + 4) * 1024,
+		});
+	});
+
+	it("defaults missing fields to zero", () => {
+		expect(parseCountObjects("count: 3\n")).toEqual({
+			looseObjects: 3,
+			looseBytes: 0,
+			packs: 0,
+			packBytes: 0,
+			garbageBytes: 0,
+			totalBytes: 0,
+		});
+	});
+
+	it("returns zeros for empty output", () => {
+		expect(parseCountObjects("").totalBytes).toBe(0);
+	});
+});
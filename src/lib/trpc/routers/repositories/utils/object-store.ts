@@ -0,0 +1,33 @@
+import type { ObjectStoreSize } from "shared/types/repo-maintenance";
+
+/**
+ * Parses `git count-objects -v` output into byte counts.
+ * Git reports sizes in KiB; missing fields default to zero.
+ */
+export function parseCountObjects(output: string): ObjectStoreSize {
+	const fields = new Map<string, number>();
+
+	for (const line of output.split("\n")) {
+		const separator = line.indexOf(":");
+		if (separator === -1) continue;
+
+		const key = line.slice(0, separator).trim();
+		const value = Number.parseInt(line.slice(separator + 1).trim(), 10);
+		if (key && Number.isFinite(value)) {
+			fields.set(key, value);
+		}
+	}
+
+	const looseBytes = (fields.get("size") ?? 0) * 1024;
+	const packBytes = (fields.get("size-pack") ?? 0) * 1024;
+	const garbageBytes = (fields.get("size-garbage") ?? 0) * 1024;
+
+	return {
+		looseObjects: fields.get("count") ?? 0,
+		looseBytes,
+		packs: fields.get("packs") ?? 0,
+		packBytes,
+		garbageBytes,
+		totalBytes: looseBytes + packBytes + garbageBytes,
+	};
+}
@@ -0,0 +1,130 @@
+import { describe, expect, it } from "bun:test";
+import {
+	parseWorkspaceBundle,
+	rebindBundleRows,
+	WORKSPACE_BUNDLE_VERSION,
+	type WorkspaceBundle,
+} from "./workspace-bundle";
+
+const bundle: WorkspaceBundle = {
+	schemaVersion: WORKSPACE_BUNDLE_VERSION,
+	exportedAt: "2026-01-05T10:00:00.000Z",
+	repository: {
+		name: "caspian",
+		color: "blue",
+		defaultBranch: "main",
+		githubOwner: null,
+		branchPrefixMode: null,
+		branchPrefixCustom: null,
+		commitAuthorName: null,
+		commitAuthorEmail: null,
+		notificationPreferences: null,
+	},
+	nodes: [
+		{
+			id: "node-a",
+			type: "worktree",
+			branch: "feat/search",
+			name: "Search",
+			tabOrder: 1,
+			baseBranch: "main",
+			customTeardownScript: null,
+		},
+		{
+			id: "node-b",
+			type: "worktree",
+			branch: "feat/gone",
+			name: "Gone",
+			tabOrder: 2,
+			baseBranch: "main",
+			customTeardownScript: null,
+		},
+	],
+	reviewComments: [],
+	testRuns: [
+		{
+			id: "test-run-1",
+			nodeId: "node-a",
+			command: "npm test",
+			framework: "jest",
+			status: "passed",
+			exitCode: 0,
+			summary: { total: 1, passed: 1, failed: 0, skipped: 0 },
+			results: [],
+			output: "1 pass",
+			durationMs: 120,
+			coverage: null,
+			coverageReportPath: null,
+			createdAt: 1767607200000,
+		},
+		{
+			id: "test-run-2",
+			nodeId: "node-b",
+			command: "npm test",
+			framework: "jest",
+			status: "failed",
+			exitCode: 1,
+			summary: { total: 1, passed: 0, failed: 1, skipped: 0 },
+			results: [],
+			output: "1 fail",
+			durationMs: 95,
+			coverage: null,
+			coverageReportPath: null,
+			createdAt: 1767607200000,
+		},
+	],
+	gateRuns: [
+		{
+			id: "gate-run-1",
+			nodeId: "node-a",
+			headSha: "592c6eb7c566c2668ce874df125f57a5c4d3957d",
+			status: "passed",
+			steps: [
+				{
+					step: "test",
+					command: "npm test",
+					status: "passed",
+					exitCode: 0,
+					durationMs: 120,
+					testRunId: "test-run-1",
+				},
+			],
+			createdAt: 1767607200000,
+			completedAt: 1767607201000,
+		},
+	],
+	auditEvents: [],
+};
+
+describe("workspace bundle round trip", () => {
+	const parsed = parseWorkspaceBundle(JSON.stringify(bundle));
+	const nodeIdMap = new Map([["node-a", "local-node-a"]]);
+
+	it("parses what was exported", () => {
+		expect(parsed).toEqual(bundle);
+	});
+
+	it("keeps row ids and points rows at the local nodes", () => {
+		const testRuns = rebindBundleRows(parsed.testRuns, nodeIdMap);
+		expect(testRuns.map(({ id, nodeId }) => ({ id, nodeId }))).toEqual([
+			{ id: "test-run-1", nodeId: "local-node-a" },
+		]);
+	});
+
+	it("leaves gate steps linked to their imported test runs", () => {
+		const testRunIds = rebindBundleRows(parsed.testRuns, nodeIdMap).map((run) => run.id);
+		const [gateRun] = rebindBundleRows(parsed.gateRuns, nodeIdMap);
+		expect(testRunIds).toContain(gateRun.steps[0].testRunId);
+	});
+
+	it("produces the same rows when imported again", () => {
+		expect(rebindBundleRows(parsed.gateRuns, nodeIdMap)).toEqual(
+			rebindBundleRows(parseWorkspaceBundle(JSON.stringify(bundle)).gateRuns, nodeIdMap),
+		);
+	});
+
+	it("rejects bundles from a newer version", () => {
+		const newer = JSON.stringify({ ...bundle, schemaVersion: WORKSPACE_BUNDLE_VERSION + 1 });
+		expect(() => parseWorkspaceBundle(newer)).toThrow("Not a Caspian workspace bundle");
+	});
+});
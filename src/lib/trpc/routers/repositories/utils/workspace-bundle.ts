@@ -0,0 +1,302 @@
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { and, eq, inArray, isNull } from "drizzle-orm";
+import {
+	auditEvents,
+	gateRuns,
+	nodes,
+	type NodeType,
+	nodeTypeSchema,
+	repositories,
+	reviewComments,
+	type SelectAuditEvent,
+	type SelectGateRun,
+	type SelectNode,
+	type SelectRepository,
+	type SelectReviewComment,
+	type SelectTestRun,
+	type SelectWorktree,
+	testRuns,
+	worktrees,
+} from "lib/local-db";
+import { importAuditEvents } from "main/lib/audit";
+import { localDb } from "main/lib/local-db";
+import { nodeInitManager } from "main/lib/node-init-manager";
+import { CASPIAN_DIR_NAME, WORKTREES_DIR_NAME } from "shared/constants";
+import { z } from "zod";
+import { getBranchNode, insertWorktreeNode } from "../../nodes/utils/db-helpers";
+import { listBranches } from "../../nodes/utils/git";
+import { initializeNodeWorktree } from "../../nodes/utils/node-init";
+import { getDefaultRepositoryColor } from "./colors";
+
+/** Bumped when the bundle layout changes; import rejects newer versions */
+export const WORKSPACE_BUNDLE_VERSION = 1;
+
+export interface WorkspaceBundleNode {
+	id: string;
+	type: NodeType;
+	branch: string;
+	name: string;
+	tabOrder: number;
+	baseBranch: string | null;
+	customTeardownScript: string | null;
+}
+
+/**
+ * A repository's Caspian state without any git data. Worktrees are recreated
+ * from their branches on import, so only pushed or otherwise present branches
+ * come back as nodes.
+ */
+export interface WorkspaceBundle {
+	schemaVersion: number;
+	exportedAt: string;
+	repository: Pick<
+		SelectRepository,
+		| "name"
+		| "color"
+		| "defaultBranch"
+		| "githubOwner"
+		| "branchPrefixMode"
+		| "branchPrefixCustom"
+		| "commitAuthorName"
+		| "commitAuthorEmail"
+		| "notificationPreferences"
+	>;
+	nodes: WorkspaceBundleNode[];
+	reviewComments: SelectReviewComment[];
+	testRuns: SelectTestRun[];
+	gateRuns: SelectGateRun[];
+	auditEvents: SelectAuditEvent[];
+}
+
+// Row contents are trusted as written by exportWorkspace; only the envelope is checked
+const workspaceBundleSchema = z.object({
+	schemaVersion: z.number().int().max(WORKSPACE_BUNDLE_VERSION),
+	exportedAt: z.string(),
+	repository: z.object({ name: z.string(), color: z.string() }).passthrough(),
+	nodes: z.array(
+		z.object({ id: z.string(), type: nodeTypeSchema, branch: z.string() }).passthrough(),
+	),
+	reviewComments: z.array(z.object({ nodeId: z.string() }).passthrough()),
+	testRuns: z.array(z.object({ nodeId: z.string() }).passthrough()),
+	gateRuns: z.array(z.object({ nodeId: z.string() }).passthrough()),
+	auditEvents: z.array(z.object({ id: z.string() }).passthrough()),
+});
+
+export function parseWorkspaceBundle(content: string): WorkspaceBundle {
+	const result = workspaceBundleSchema.safeParse(JSON.parse(content));
+	if (!result.success) {
+		throw new Error(`Not a Caspian workspace bundle: ${result.error.issues[0]?.message}`);
+	}
+	return result.data as unknown as WorkspaceBundle;
+}
+
+export function buildWorkspaceBundle(repository: SelectRepository): WorkspaceBundle {
+	const repositoryNodes = localDb
+		.select({ node: nodes, baseBranch: worktrees.baseBranch })
+		.from(nodes)
+		.leftJoin(worktrees, eq(nodes.worktreeId, worktrees.id))
+		.where(and(eq(nodes.repositoryId, repository.id), isNull(nodes.deletingAt)))
+		.all();
+	const nodeIds = repositoryNodes.map(({ node }) => node.id);
+
+	return {
+		schemaVersion: WORKSPACE_BUNDLE_VERSION,
+		exportedAt: new Date().toISOString(),
+		repository: {
+			name: repository.name,
+			color: repository.color,
+			defaultBranch: repository.defaultBranch,
+			githubOwner: repository.githubOwner,
+			branchPrefixMode: repository.branchPrefixMode,
+			branchPrefixCustom: repository.branchPrefixCustom,
+			commitAuthorName: repository.commitAuthorName,
+			commitAuthorEmail: repository.commitAuthorEmail,
+			notificationPreferences: repository.notificationPreferences,
+		},
+		nodes: repositoryNodes.map(({ node, baseBranch }) => ({
+			id: node.id,
+			type: node.type,
+			branch: node.branch,
+			name: node.name,
+			tabOrder: node.tabOrder,
+			baseBranch,
+			customTeardownScript: node.customTeardownScript,
+		})),
+		reviewComments:
+			nodeIds.length > 0
+				? localDb.select().from(reviewComments).where(inArray(reviewComments.nodeId, nodeIds)).all()
+				: [],
+		testRuns:
+			nodeIds.length > 0
+				? localDb.select().from(testRuns).where(inArray(testRuns.nodeId, nodeIds)).all()
+				: [],
+		gateRuns:
+			nodeIds.length > 0
+				? localDb.select().from(gateRuns).where(inArray(gateRuns.nodeId, nodeIds)).all()
+				: [],
+		auditEvents: localDb
+			.select()
+			.from(auditEvents)
+			.where(eq(auditEvents.repositoryId, repository.id))
+			.all(),
+	};
+}
+
+export interface WorkspaceImportResult {
+	repository: SelectRepository;
+	/** Worktree nodes created; nodes that already existed only receive history */
+	importedNodes: number;
+	/** Branches that exist neither locally nor on origin, so their nodes were left out */
+	skippedBranches: string[];
+}
+
+/**
+ * Bundle history rows for the nodes that made it into this repository,
+ * pointed at their local node ids. Row ids are kept, so links between rows
+ * (a gate step's testRunId) still resolve and importing the same bundle
+ * twice finds the rows already there.
+ */
+export function rebindBundleRows<T extends { id: string; nodeId: string }>(
+	rows: T[],
+	nodeIdMap: Map<string, string>,
+): T[] {
+	return rows.flatMap((row) => {
+		const nodeId = nodeIdMap.get(row.nodeId);
+		return nodeId ? [{ ...row, nodeId }] : [];
+	});
+}
+
+/**
+ * Rebinds a bundle to a repository checkout on this machine. Worktree nodes
+ * are recreated from their branches in the background once the database
+ * changes have committed; history (review comments, test and gate runs,
+ * audit events) is attached to the local node ids.
+ */
+export async function importWorkspaceBundle({
+	bundle,
+	repository,
+}: {
+	bundle: WorkspaceBundle;
+	repository: SelectRepository;
+}): Promise<WorkspaceImportResult> {
+	const { local, remote } = await listBranches(repository.mainRepoPath);
+	const availableBranches = new Set([...local, ...remote]);
+
+	const nodeIdMap = new Map<string, string>();
+	const skippedBranches: string[] = [];
+	const created: { node: SelectNode; worktree: SelectWorktree; baseBranch: string }[] = [];
+
+	localDb.transaction(() => {
+		localDb
+			.update(repositories)
+			.set({
+				color: bundle.repository.color || getDefaultRepositoryColor(),
+				branchPrefixMode: bundle.repository.branchPrefixMode,
+				branchPrefixCustom: bundle.repository.branchPrefixCustom,
+				commitAuthorName: bundle.repository.commitAuthorName,
+				commitAuthorEmail: bundle.repository.commitAuthorEmail,
+				notificationPreferences:
+					bundle.repository.notificationPreferences ?? repository.notificationPreferences,
+				githubOwner: repository.githubOwner ?? bundle.repository.githubOwner,
+			})
+			.where(eq(repositories.id, repository.id))
+			.run();
+
+		const existingNodeIds = new Map(
+			localDb
+				.select({ id: nodes.id, branch: nodes.branch })
+				.from(nodes)
+				.where(
+					and(
+						eq(nodes.repositoryId, repository.id),
+						eq(nodes.type, "worktree"),
+						isNull(nodes.deletingAt),
+					),
+				)
+				.all()
+				.map((node) => [node.branch, node.id]),
+		);
+
+		for (const bundleNode of [...bundle.nodes].sort((a, b) => a.tabOrder - b.tabOrder)) {
+			if (bundleNode.type === "branch") {
+				const branchNode = getBranchNode(repository.id);
+				if (branchNode) nodeIdMap.set(bundleNode.id, branchNode.id);
+				continue;
+			}
+
+			// Already open here (e.g. importing twice); attach history to the existing node
+			const existingNodeId = existingNodeIds.get(bundleNode.branch);
+			if (existingNodeId) {
+				nodeIdMap.set(bundleNode.id, existingNodeId);
+				continue;
+			}
+
+			if (!availableBranches.has(bundleNode.branch)) {
+				skippedBranches.push(bundleNode.branch);
+				continue;
+			}
+
+			const baseBranch = bundleNode.baseBranch ?? repository.defaultBranch ?? "main";
+			const { node, worktree } = insertWorktreeNode({
+				repositoryId: repository.id,
+				worktreePath: join(
+					homedir(),
+					CASPIAN_DIR_NAME,
+					WORKTREES_DIR_NAME,
+					repository.name,
+					bundleNode.branch,
+				),
+				branch: bundleNode.branch,
+				baseBranch,
+				node: { name: bundleNode.name, customTeardownScript: bundleNode.customTeardownScript },
+			});
+			nodeIdMap.set(bundleNode.id, node.id);
+			created.push({ node, worktree, baseBranch });
+		}
+
+		const importedComments = rebindBundleRows(bundle.reviewComments, nodeIdMap);
+		const importedTestRuns = rebindBundleRows(bundle.testRuns, nodeIdMap);
+		const importedGateRuns = rebindBundleRows(bundle.gateRuns, nodeIdMap);
+		if (importedComments.length > 0) {
+			localDb.insert(reviewComments).values(importedComments).onConflictDoNothing().run();
+		}
+		if (importedTestRuns.length > 0) {
+			localDb.insert(testRuns).values(importedTestRuns).onConflictDoNothing().run();
+		}
+		if (importedGateRuns.length > 0) {
+			localDb.insert(gateRuns).values(importedGateRuns).onConflictDoNothing().run();
+		}
+	});
+
+	for (const { node, worktree, baseBranch } of created) {
+		nodeInitManager.startJob(node.id, repository.id);
+		initializeNodeWorktree({
+			nodeId: node.id,
+			repositoryId: repository.id,
+			worktreeId: worktree.id,
+			worktreePath: worktree.path,
+			branch: worktree.branch,
+			baseBranch,
+			baseBranchWasExplicit: true,
+			mainRepoPath: repository.mainRepoPath,
+			useExistingBranch: true,
+		});
+	}
+
+	await importAuditEvents(
+		bundle.auditEvents.map((event) => ({
+			...event,
+			repositoryId: repository.id,
+			nodeId: event.nodeId ? (nodeIdMap.get(event.nodeId) ?? null) : null,
+			data: event.data ?? undefined,
+		})),
+	);
+
+	console.log(
+		`[workspace-bundle] Imported ${created.length} nodes into ${repository.name}` +
+			(skippedBranches.length > 0 ? `, skipped ${skippedBranches.join(", ")}` : ""),
+	);
+
+	return { repository, importedNodes: created.length, skippedBranches };
+}
@@ -1,23 +1,51 @@
+import { existsSync } from "node:fs";
 import { TRPCError } from "@trpc/server";
+import { eq } from "drizzle-orm";
 import { app } from "electron";
 import {
 	BRANCH_PREFIX_MODES,
 	EXECUTION_MODES,
+	EXTERNAL_APPS,
+	githubAccounts,
+	notificationPreferencesSchema,
+	OUTPUT_REDACTION_MODES,
+	repositories,
 	settings,
+	TELEMETRY_LEVELS,
 	TERMINAL_LINK_BEHAVIORS,
 	type TerminalPreset,
 } from "lib/local-db";
 import { quitWithoutConfirmation } from "main/index";
+import { applyUpdateChannel, getUpdateChannel } from "main/lib/auto-updater";
+import { getAutomationApiToken, regenerateAutomationApiToken } from "main/lib/automation-api";
+import { getLocaleSetting, getSystemLocale, setLocale } from "main/lib/i18n";
 import { localDb } from "main/lib/local-db";
+import { getPrivacySettings, getTelemetryLevel, setPrivacySettings } from "main/lib/privacy";
+import { getOutputRedactionMode, setOutputRedactionMode } from "main/lib/redaction";
+import {
+	deleteSecret,
+	hasSecret,
+	isSecretStorageAvailable,
+	storeSecret,
+} from "main/lib/secrets/store";
+import { UPDATE_CHANNELS } from "shared/auto-update";
 import {
 	DEFAULT_AUTO_APPLY_DEFAULT_PRESET,
 	DEFAULT_CONFIRM_ON_QUIT,
 	DEFAULT_TERMINAL_LINK_BEHAVIOR,
+	PORTS,
 } from "shared/constants";
+import { LOCALES } from "shared/i18n/translate";
+import { getSystemTimeZone, isValidTimeZone } from "shared/utils/time";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
 import { getGitAuthorName, getGitHubUsername } from "../nodes/utils/git";
+import { fetchGhUserForConfigDir, getAccountSecretKey } from "../nodes/utils/github/accounts";
+import { fetchGitHubUserWithToken } from "../nodes/utils/github/api";
 import { checkToolStatus } from "./utils/check-tools";
+import { runDiagnostics } from "./utils/diagnostics";
+import { GH_INSTALL_METHODS, getGhInstallOptions, installGh } from "./utils/gh-install";
+import { getOnboardingStatus } from "./utils/onboarding";
 
 function getSettings() {
 	let row = localDb.select().from(settings).get();
@@ -33,6 +61,26 @@ export const createSettingsRouter = () => {
 			const row = getSettings();
 			return row.lastUsedApp ?? "cursor";
 		}),
+		getEditorLaunchers: publicProcedure.query(() => {
+			return getSettings().editorLaunchers ?? {};
+		}),
+		/** Overrides the launcher command used to open an app; null goes back to detection */
+		setEditorLauncher: publicProcedure
+			.input(z.object({ app: z.enum(EXTERNAL_APPS), command: z.string().trim().min(1).nullable() }))
+			.mutation(({ input }) => {
+				const launchers = { ...getSettings().editorLaunchers };
+				if (input.command) {
+					launchers[input.app] = input.command;
+				} else {
+					delete launchers[input.app];
+				}
+				localDb
+					.insert(settings)
+					.values({ id: 1, editorLaunchers: launchers })
+					.onConflictDoUpdate({ target: settings.id, set: { editorLaunchers: launchers } })
+					.run();
+				return { success: true };
+			}),
 		getTerminalPresets: publicProcedure.query(() => {
 			const row = getSettings();
 			return row.terminalPresets ?? [];
@@ -242,6 +290,43 @@ export const createSettingsRouter = () => {
 				return { success: true };
 			}),
 
+		getUpdateChannel: publicProcedure.query(() => {
+			return getUpdateChannel();
+		}),
+
+		setUpdateChannel: publicProcedure
+			.input(z.object({ channel: z.enum(UPDATE_CHANNELS) }))
+			.mutation(({ input }) => {
+				localDb
+					.insert(settings)
+					.values({ id: 1, updateChannel: input.channel })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { updateChannel: input.channel },
+					})
+					.run();
+				applyUpdateChannel(input.channel);
+
+				return { success: true };
+			}),
+
+		/** Stored settings plus the telemetry level in effect (local-only forces "off") */
+		getPrivacy: publicProcedure.query(() => {
+			return { ...getPrivacySettings(), effectiveTelemetryLevel: getTelemetryLevel() };
+		}),
+
+		setPrivacy: publicProcedure
+			.input(
+				z.object({
+					telemetryLevel: z.enum(TELEMETRY_LEVELS).optional(),
+					localOnly: z.boolean().optional(),
+				}),
+			)
+			.mutation(({ input }) => {
+				const privacy = setPrivacySettings(input);
+				return { ...privacy, effectiveTelemetryLevel: getTelemetryLevel() };
+			}),
+
 		getAutoApplyDefaultPreset: publicProcedure.query(() => {
 			const row = getSettings();
 			return row.autoApplyDefaultPreset ?? DEFAULT_AUTO_APPLY_DEFAULT_PRESET;
@@ -307,6 +392,149 @@ export const createSettingsRouter = () => {
 			return checkToolStatus();
 		}),
 
+		/** Git, GitHub auth, agents, notifications and the data folder, for first-run setup */
+		getOnboardingStatus: publicProcedure.query(async () => {
+			return getOnboardingStatus();
+		}),
+
+		/** Agents, git/gh, node/npm, disk space and keychain; slow, so run on demand */
+		runDiagnostics: publicProcedure.mutation(async () => {
+			return runDiagnostics();
+		}),
+
+		/** Package managers on this machine that can install gh */
+		getGhInstallOptions: publicProcedure.query(async () => {
+			return getGhInstallOptions();
+		}),
+
+		installGh: publicProcedure
+			.input(z.object({ method: z.enum(GH_INSTALL_METHODS) }))
+			.mutation(async ({ input }) => {
+				try {
+					return { path: await installGh(input.method) };
+				} catch (error) {
+					throw new TRPCError({
+						code: "PRECONDITION_FAILED",
+						message: error instanceof Error ? error.message : String(error),
+					});
+				}
+			}),
+
+		/** Token-based GitHub access for users without gh; gh is still preferred when signed in */
+		getGitHubTokenStatus: publicProcedure.query(() => {
+			return {
+				configured: hasSecret("github-token"),
+				storageAvailable: isSecretStorageAvailable(),
+			};
+		}),
+
+		setGitHubToken: publicProcedure
+			.input(z.object({ token: z.string().trim().min(1) }))
+			.mutation(async ({ input }) => {
+				let username: string;
+				try {
+					username = await fetchGitHubUserWithToken(input.token);
+				} catch (error) {
+					const reason = error instanceof Error ? error.message : String(error);
+					throw new TRPCError({
+						code: "BAD_REQUEST",
+						message: `GitHub rejected the token: ${reason}`,
+					});
+				}
+
+				try {
+					storeSecret("github-token", input.token);
+				} catch (error) {
+					throw new TRPCError({
+						code: "PRECONDITION_FAILED",
+						message: error instanceof Error ? error.message : String(error),
+					});
+				}
+				return { username };
+			}),
+
+		clearGitHubToken: publicProcedure.mutation(() => {
+			deleteSecret("github-token");
+			return { success: true };
+		}),
+
+		/** Extra GitHub identities that repositories can be bound to via repositories.update */
+		listGitHubAccounts: publicProcedure.query(() => {
+			return localDb.select().from(githubAccounts).all();
+		}),
+
+		addGitHubAccount: publicProcedure
+			.input(
+				z
+					.object({
+						label: z.string().trim().min(1),
+						token: z.string().trim().min(1).optional(),
+						ghConfigDir: z.string().trim().min(1).optional(),
+					})
+					.refine((input) => input.token || input.ghConfigDir, {
+						message: "Provide a token or a gh config directory",
+					}),
+			)
+			.mutation(async ({ input }) => {
+				if (input.ghConfigDir && !existsSync(input.ghConfigDir)) {
+					throw new TRPCError({
+						code: "BAD_REQUEST",
+						message: `gh config directory not found: ${input.ghConfigDir}`,
+					});
+				}
+
+				let username: string;
+				try {
+					username = input.token
+						? await fetchGitHubUserWithToken(input.token)
+						: await fetchGhUserForConfigDir(input.ghConfigDir as string);
+				} catch (error) {
+					const reason = error instanceof Error ? error.message : String(error);
+					throw new TRPCError({
+						code: "BAD_REQUEST",
+						message: `Could not sign in to GitHub with this account: ${reason}`,
+					});
+				}
+
+				const account = localDb
+					.insert(githubAccounts)
+					.values({
+						label: input.label,
+						username,
+						ghConfigDir: input.ghConfigDir ?? null,
+						hasToken: Boolean(input.token),
+					})
+					.returning()
+					.get();
+
+				if (input.token) {
+					try {
+						storeSecret(getAccountSecretKey(account.id), input.token);
+					} catch (error) {
+						localDb.delete(githubAccounts).where(eq(githubAccounts.id, account.id)).run();
+						throw new TRPCError({
+							code: "PRECONDITION_FAILED",
+							message: error instanceof Error ? error.message : String(error),
+						});
+					}
+				}
+				return account;
+			}),
+
+		removeGitHubAccount: publicProcedure
+			.input(z.object({ id: z.string() }))
+			.mutation(({ input }) => {
+				// Bound repositories fall back to the default login
+				localDb
+					.update(repositories)
+					.set({ githubAccountId: null })
+					.where(eq(repositories.githubAccountId, input.id))
+					.run();
+				localDb.delete(githubAccounts).where(eq(githubAccounts.id, input.id)).run();
+				deleteSecret(getAccountSecretKey(input.id));
+				return { success: true };
+			}),
+
 		getGitInfo: publicProcedure.query(async () => {
 			const githubUsername = await getGitHubUsername();
 			const authorName = await getGitAuthorName();
@@ -336,5 +564,164 @@ export const createSettingsRouter = () => {
 
 				return { success: true };
 			}),
+
+		getNotificationPreferences: publicProcedure.query(() => {
+			const row = getSettings();
+			return row.notificationPreferences ?? {};
+		}),
+
+		/** Global defaults; repositories can override them via repositories.update */
+		setNotificationPreferences: publicProcedure
+			.input(z.object({ preferences: notificationPreferencesSchema.omit({ sound: true }) }))
+			.mutation(({ input }) => {
+				localDb
+					.insert(settings)
+					.values({ id: 1, notificationPreferences: input.preferences })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { notificationPreferences: input.preferences },
+					})
+					.run();
+
+				return { success: true };
+			}),
+
+		getMetricsEndpointEnabled: publicProcedure.query(() => {
+			const row = getSettings();
+			return row.metricsEndpointEnabled ?? false;
+		}),
+
+		setMetricsEndpointEnabled: publicProcedure
+			.input(z.object({ enabled: z.boolean() }))
+			.mutation(({ input }) => {
+				localDb
+					.insert(settings)
+					.values({ id: 1, metricsEndpointEnabled: input.enabled })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { metricsEndpointEnabled: input.enabled },
+					})
+					.run();
+
+				return { success: true };
+			}),
+
+		getAutomationApiStatus: publicProcedure.query(() => {
+			const row = getSettings();
+			return {
+				enabled: row.automationApiEnabled ?? false,
+				url: `http://127.0.0.1:${PORTS.NOTIFICATIONS}/api/v1`,
+			};
+		}),
+
+		/** Enabling creates a token on first use; the token lives in the keychain */
+		setAutomationApiEnabled: publicProcedure
+			.input(z.object({ enabled: z.boolean() }))
+			.mutation(({ input }) => {
+				if (input.enabled && !getAutomationApiToken()) {
+					if (!isSecretStorageAvailable()) {
+						throw new TRPCError({
+							code: "PRECONDITION_FAILED",
+							message: "Secure storage is unavailable, so an API token can't be stored",
+						});
+					}
+					regenerateAutomationApiToken();
+				}
+
+				localDb
+					.insert(settings)
+					.values({ id: 1, automationApiEnabled: input.enabled })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { automationApiEnabled: input.enabled },
+					})
+					.run();
+
+				return { success: true };
+			}),
+
+		getAutomationApiToken: publicProcedure.query(() => {
+			return getAutomationApiToken();
+		}),
+
+		/** Replaces the token; clients using the old one get 401s */
+		regenerateAutomationApiToken: publicProcedure.mutation(() => {
+			if (!isSecretStorageAvailable()) {
+				throw new TRPCError({
+					code: "PRECONDITION_FAILED",
+					message: "Secure storage is unavailable, so an API token can't be stored",
+				});
+			}
+			return { token: regenerateAutomationApiToken() };
+		}),
+
+		getTimeZone: publicProcedure.query(() => {
+			const row = getSettings();
+			return {
+				timeZone: row.timeZone ?? null,
+				systemTimeZone: getSystemTimeZone(),
+			};
+		}),
+
+		setTimeZone: publicProcedure
+			.input(
+				z.object({
+					timeZone: z.string().refine(isValidTimeZone, "Unknown time zone").nullable(),
+				}),
+			)
+			.mutation(({ input }) => {
+				localDb
+					.insert(settings)
+					.values({ id: 1, timeZone: input.timeZone })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { timeZone: input.timeZone },
+					})
+					.run();
+
+				return { success: true };
+			}),
+
+		/** Language for notifications and dialogs the main process shows */
+		getLocale: publicProcedure.query(() => {
+			return { locale: getLocaleSetting(), systemLocale: getSystemLocale() };
+		}),
+
+		setLocale: publicProcedure
+			.input(z.object({ locale: z.enum(LOCALES).nullable() }))
+			.mutation(({ input }) => {
+				setLocale(input.locale);
+				return { success: true };
+			}),
+
+		getAgentMemoryLimit: publicProcedure.query(() => {
+			return getSettings().agentMemoryLimitMb ?? null;
+		}),
+
+		/** Agent terminals over this many megabytes are stopped; null turns the limit off */
+		setAgentMemoryLimit: publicProcedure
+			.input(z.object({ limitMb: z.number().int().min(256).nullable() }))
+			.mutation(({ input }) => {
+				localDb
+					.insert(settings)
+					.values({ id: 1, agentMemoryLimitMb: input.limitMb })
+					.onConflictDoUpdate({
+						target: settings.id,
+						set: { agentMemoryLimitMb: input.limitMb },
+					})
+					.run();
+
+				return { success: true };
+			}),
+
+		getOutputRedaction: publicProcedure.query(() => getOutputRedactionMode()),
+
+		/** Applies to output stored from now on; existing history is left as is */
+		setOutputRedaction: publicProcedure
+			.input(z.object({ mode: z.enum(OUTPUT_REDACTION_MODES) }))
+			.mutation(({ input }) => {
+				setOutputRedactionMode(input.mode);
+				return { success: true };
+			}),
 	});
 };
@@ -3,6 +3,8 @@ import { afterEach, describe, expect, mock, test } from "bun:test";
 let execBehavior: Record<string, { stdout?: string; error?: Error }> = {};
 
 mock.module("../../nodes/utils/shell-env", () => ({
+	addExecutableDirToPath: mock(() => {}),
+	findExecutable: mock(() => Promise.resolve(null)),
 	execWithShellEnv: mock((cmd: string, _args: string[]) => {
 		const key = cmd;
 		const behavior = execBehavior[key];
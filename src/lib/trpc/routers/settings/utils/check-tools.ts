@@ -1,4 +1,8 @@
-import { execWithShellEnv } from "../../nodes/utils/shell-env";
+import {
+	addExecutableDirToPath,
+	execWithShellEnv,
+	findExecutable,
+} from "../../nodes/utils/shell-env";
 
 export type ToolStatus = {
 	git: { available: boolean };
@@ -25,10 +29,7 @@ async function checkGit(): Promise<ToolStatus["git"]> {
 }
 
 async function checkGh(): Promise<ToolStatus["gh"]> {
-	try {
-		await execWithShellEnv("gh", ["--version"]);
-	} catch (error) {
-		console.log("[settings/check-tools] gh not installed:", errorMessage(error));
+	if (!(await isGhOnPath())) {
 		return { installed: false, authenticated: false, username: null };
 	}
 
@@ -46,6 +47,23 @@ async function checkGh(): Promise<ToolStatus["gh"]> {
 	}
 }
 
+async function isGhOnPath(): Promise<boolean> {
+	try {
+		await execWithShellEnv("gh", ["--version"]);
+		return true;
+	} catch (error) {
+		console.log("[settings/check-tools] gh not installed:", errorMessage(error));
+	}
+
+	// Installers on Windows only update the PATH of processes started after them
+	if (process.platform !== "win32") return false;
+	const ghPath = await findExecutable("gh");
+	if (!ghPath) return false;
+	console.log("[settings/check-tools] Found gh outside PATH:", ghPath);
+	addExecutableDirToPath(ghPath);
+	return true;
+}
+
 function errorMessage(error: unknown): string {
 	return error instanceof Error ? error.message : String(error);
 }
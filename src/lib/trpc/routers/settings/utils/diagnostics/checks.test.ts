@@ -0,0 +1,53 @@
+import { describe, expect, test } from "bun:test";
+import {
+	DISK_ERROR_BYTES,
+	DISK_WARNING_BYTES,
+	getDiskSpaceStatus,
+	isVersionAtLeast,
+	parseToolVersion,
+	worstStatus,
+} from "./checks";
+
+describe("parseToolVersion", () => {
+	test("extracts versions from common --version output", () => {
+		expect(parseToolVersion("git version 2.39.5 (Apple Git-154)")).toBe("2.39.5");
+		expect(parseToolVersion("gh version 2.62.0 (2024-11-14)\nhttps://github.com/cli/cli")).toBe(
+			"2.62.0",
+		);
+		expect(parseToolVersion("v20.11.1")).toBe("20.11.1");
+	});
+
+	test("pads two-part versions", () => {
+		expect(parseToolVersion("codex-cli 0.46")).toBe("0.46.0");
+	});
+
+	test("returns null without a version", () => {
+		expect(parseToolVersion("command not found")).toBeNull();
+	});
+});
+
+describe("isVersionAtLeast", () => {
+	test("compares numerically, not lexically", () => {
+		expect(isVersionAtLeast("2.39.0", "2.25.0")).toBe(true);
+		expect(isVersionAtLeast("2.9.0", "2.25.0")).toBe(false);
+		expect(isVersionAtLeast("2.25.0", "2.25.0")).toBe(true);
+		expect(isVersionAtLeast("3.0.0", "2.25.1")).toBe(true);
+	});
+});
+
+describe("getDiskSpaceStatus", () => {
+	test("warns when space runs low and errors when nearly full", () => {
+		expect(getDiskSpaceStatus(DISK_WARNING_BYTES * 2)).toBe("ok");
+		expect(getDiskSpaceStatus(DISK_WARNING_BYTES - 1)).toBe("warning");
+		expect(getDiskSpaceStatus(DISK_ERROR_BYTES - 1)).toBe("error");
+	});
+});
+
+describe("worstStatus", () => {
+	test("picks the most severe status", () => {
+		expect(worstStatus([])).toBe("ok");
+		expect(worstStatus(["ok", "skipped"])).toBe("ok");
+		expect(worstStatus(["ok", "warning", "ok"])).toBe("warning");
+		expect(worstStatus(["warning", "error", "ok"])).toBe("error");
+	});
+});
@@ -0,0 +1,48 @@
+import type { DiagnosticStatus } from "shared/types/diagnostics";
+
+const GB = 1024 * 1024 * 1024;
+
+/** Below these, new worktrees and dependency installs start failing */
+export const DISK_WARNING_BYTES = 5 * GB;
+export const DISK_ERROR_BYTES = 1 * GB;
+
+const STATUS_RANK: Record<DiagnosticStatus, number> = {
+	ok: 0,
+	skipped: 0,
+	warning: 1,
+	error: 2,
+};
+
+/** First dotted version in --version output: "2.39.5" from "git version 2.39.5 (Apple Git-154)" */
+export function parseToolVersion(output: string): string | null {
+	const match = output.match(/(\d+)\.(\d+)(?:\.(\d+))?/);
+	if (!match) return null;
+	return `${match[1]}.${match[2]}.${match[3] ?? "0"}`;
+}
+
+export function isVersionAtLeast(version: string, minimum: string): boolean {
+	const actual = version.split(".").map(Number);
+	const required = minimum.split(".").map(Number);
+	for (let i = 0; i < Math.max(actual.length, required.length); i++) {
+		const diff = (actual[i] ?? 0) - (required[i] ?? 0);
+		if (diff !== 0) return diff > 0;
+	}
+	return true;
+}
+
+export function getDiskSpaceStatus(freeBytes: number): DiagnosticStatus {
+	if (freeBytes < DISK_ERROR_BYTES) return "error";
+	if (freeBytes < DISK_WARNING_BYTES) return "warning";
+	return "ok";
+}
+
+export function formatGigabytes(bytes: number): string {
+	return `${(bytes / GB).toFixed(1)} GB`;
+}
+
+export function worstStatus(statuses: DiagnosticStatus[]): DiagnosticStatus {
+	return statuses.reduce<DiagnosticStatus>(
+		(worst, status) => (STATUS_RANK[status] > STATUS_RANK[worst] ? status : worst),
+		"ok",
+	);
+}
@@ -0,0 +1,293 @@
+import { execFile } from "node:child_process";
+import { existsSync } from "node:fs";
+import { stat, statfs } from "node:fs/promises";
+import { homedir } from "node:os";
+import { delimiter, dirname, join, resolve } from "node:path";
+import { promisify } from "node:util";
+import { safeStorage } from "electron";
+import {
+	getClaudeWrapperPath,
+	getCodexWrapperPath,
+	getOpenCodeWrapperPath,
+} from "main/lib/agent-setup/agent-wrappers";
+import { BIN_DIR } from "main/lib/agent-setup/paths";
+import { isSecretStorageAvailable } from "main/lib/secrets/store";
+import { CASPIAN_DIR_NAME, WORKTREES_DIR_NAME } from "shared/constants";
+import type { DiagnosticCheck, DiagnosticsReport } from "shared/types/diagnostics";
+import { getShellEnvironment } from "../../../nodes/utils/shell-env";
+import {
+	formatGigabytes,
+	getDiskSpaceStatus,
+	isVersionAtLeast,
+	parseToolVersion,
+	worstStatus,
+} from "./checks";
+
+const execFileAsync = promisify(execFile);
+
+const PROBE_TIMEOUT_MS = 10_000;
+
+/** Cone-mode sparse checkout, used for sparse nodes, arrived in 2.25 */
+const MIN_GIT_VERSION = "2.25.0";
+
+interface AgentAdapter {
+	id: string;
+	label: string;
+	binary: string;
+	/** Caspian's wrapper that reports the agent's status, for agents that have one */
+	wrapperPath?: string;
+}
+
+const AGENT_ADAPTERS: AgentAdapter[] = [
+	{ id: "claude", label: "Claude Code", binary: "claude", wrapperPath: getClaudeWrapperPath() },
+	{ id: "codex", label: "Codex", binary: "codex", wrapperPath: getCodexWrapperPath() },
+	{ id: "opencode", label: "OpenCode", binary: "opencode", wrapperPath: getOpenCodeWrapperPath() },
+	{ id: "gemini", label: "Gemini CLI", binary: "gemini" },
+	{ id: "cursor-agent", label: "Cursor Agent", binary: "cursor-agent" },
+];
+
+type Env = Record<string, string>;
+
+/** Probes every agent, tool and resource Caspian depends on */
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+	const env = await getShellEnvironment();
+	const checks = await Promise.all([
+		...AGENT_ADAPTERS.map((adapter) => checkAgent({ adapter, env })),
+		checkGit(env),
+		checkGh(env),
+		checkRuntime({ command: "node", label: "Node.js", env }),
+		checkRuntime({ command: "npm", label: "npm", env }),
+		checkDiskSpace(),
+		checkKeychain(),
+	]);
+
+	return {
+		generatedAt: Date.now(),
+		platform: process.platform,
+		checks,
+		status: worstStatus(checks.map((check) => check.status)),
+	};
+}
+
+/** Agents with a real binary on the shell's PATH, wrappers aside */
+export async function findInstalledAgents(): Promise<{ id: string; label: string }[]> {
+	const env = await getShellEnvironment();
+	const found = await Promise.all(
+		AGENT_ADAPTERS.map(async ({ id, label, binary }) =>
+			(await findRealBinary(binary, env)) ? [{ id, label }] : [],
+		),
+	);
+	return found.flat();
+}
+
+async function checkAgent({
+	adapter,
+	env,
+}: {
+	adapter: AgentAdapter;
+	env: Env;
+}): Promise<DiagnosticCheck> {
+	const base = { id: `agent:${adapter.id}`, category: "agents" as const, label: adapter.label };
+
+	const binaryPath = await findRealBinary(adapter.binary, env);
+	if (!binaryPath) {
+		return { ...base, status: "skipped", detail: "Not installed", hint: null };
+	}
+
+	let version: string | null;
+	try {
+		version = await getVersion(binaryPath, env);
+	} catch (error) {
+		return {
+			...base,
+			status: "error",
+			detail: binaryPath,
+			hint: `\`${adapter.binary} --version\` failed: ${errorMessage(error)}`,
+		};
+	}
+
+	const detail = `${version ?? "Unknown version"} at ${binaryPath}`;
+	if (adapter.wrapperPath && !(await isExecutableFile(adapter.wrapperPath))) {
+		return {
+			...base,
+			status: "warning",
+			detail,
+			hint: "Caspian's status wrapper is missing. Restart Caspian to reinstall it.",
+		};
+	}
+	return { ...base, status: "ok", detail, hint: null };
+}
+
+async function checkGit(env: Env): Promise<DiagnosticCheck> {
+	const base = { id: "git", category: "tools" as const, label: "Git" };
+	let version: string | null;
+	try {
+		version = await getVersion("git", env);
+	} catch {
+		return { ...base, status: "error", detail: "Not found", hint: "Install git, then restart" };
+	}
+
+	if (version && !isVersionAtLeast(version, MIN_GIT_VERSION)) {
+		return {
+			...base,
+			status: "warning",
+			detail: version,
+			hint: `Git ${MIN_GIT_VERSION} or newer is needed for sparse checkouts`,
+		};
+	}
+	return { ...base, status: "ok", detail: version, hint: null };
+}
+
+async function checkGh(env: Env): Promise<DiagnosticCheck> {
+	const base = { id: "gh", category: "tools" as const, label: "GitHub CLI" };
+	let version: string | null;
+	try {
+		version = await getVersion("gh", env);
+	} catch {
+		return {
+			...base,
+			status: "warning",
+			detail: "Not found",
+			hint: "Install gh for pull request status, reviews and GitHub issues",
+		};
+	}
+
+	try {
+		await execFileAsync("gh", ["auth", "status"], { env, timeout: PROBE_TIMEOUT_MS });
+	} catch {
+		return {
+			...base,
+			status: "warning",
+			detail: `${version ?? "Unknown version"}, not signed in`,
+			hint: "Run `gh auth login`",
+		};
+	}
+	return { ...base, status: "ok", detail: version, hint: null };
+}
+
+/** Setup and teardown commands commonly need node and npm */
+async function checkRuntime({
+	command,
+	label,
+	env,
+}: {
+	command: string;
+	label: string;
+	env: Env;
+}): Promise<DiagnosticCheck> {
+	const base = { id: command, category: "tools" as const, label };
+	try {
+		const version = await getVersion(command, env);
+		return { ...base, status: "ok", detail: version, hint: null };
+	} catch {
+		return {
+			...base,
+			status: "warning",
+			detail: "Not found",
+			hint: `Setup commands that run ${command} will fail until it's on your shell's PATH`,
+		};
+	}
+}
+
+async function checkDiskSpace(): Promise<DiagnosticCheck> {
+	const base = { id: "disk", category: "system" as const, label: "Disk space" };
+	const worktreesDir = join(homedir(), CASPIAN_DIR_NAME, WORKTREES_DIR_NAME);
+
+	// Before the first node there's no worktrees directory; measure the disk it'll be on
+	let dir = worktreesDir;
+	while (!existsSync(dir) && dirname(dir) !== dir) {
+		dir = dirname(dir);
+	}
+
+	try {
+		const stats = await statfs(dir);
+		const free = stats.bavail * stats.bsize;
+		const status = getDiskSpaceStatus(free);
+		return {
+			...base,
+			status,
+			detail: `${formatGigabytes(free)} free under ${worktreesDir}`,
+			hint: status === "ok" ? null : "Free up space or delete nodes you no longer need",
+		};
+	} catch (error) {
+		return { ...base, status: "warning", detail: null, hint: errorMessage(error) };
+	}
+}
+
+async function checkKeychain(): Promise<DiagnosticCheck> {
+	const base = { id: "keychain", category: "system" as const, label: "Keychain" };
+	if (!isSecretStorageAvailable()) {
+		return {
+			...base,
+			status: "error",
+			detail: "Unavailable",
+			hint: "Tokens can't be saved until an OS keychain is available",
+		};
+	}
+
+	// Without a keyring daemon, Linux falls back to a hardcoded key
+	if (process.platform === "linux" && safeStorage.getSelectedStorageBackend() === "basic_text") {
+		return {
+			...base,
+			status: "warning",
+			detail: "No keyring found",
+			hint: "Install and unlock GNOME Keyring or KWallet so tokens are encrypted properly",
+		};
+	}
+
+	try {
+		const probe = "caspian-diagnostics";
+		const roundTrip = safeStorage.decryptString(safeStorage.encryptString(probe));
+		if (roundTrip !== probe) throw new Error("Decrypted value didn't match");
+	} catch (error) {
+		return { ...base, status: "error", detail: "Access denied", hint: errorMessage(error) };
+	}
+	return { ...base, status: "ok", detail: null, hint: null };
+}
+
+/**
+ * Windows can only run .cmd and .bat shims, npm among them, through cmd.exe.
+ * Anything without an .exe extension goes through the shell there so PATHEXT
+ * resolves bare names like "npm" too.
+ */
+function needsShell(command: string): boolean {
+	return process.platform === "win32" && !/\.exe$/i.test(command);
+}
+
+async function getVersion(command: string, env: Env): Promise<string | null> {
+	const shell = needsShell(command);
+	const { stdout, stderr } = await execFileAsync(
+		// The shell splits on spaces, as in C:\Program Files
+		shell ? `"${command}"` : command,
+		["--version"],
+		{ env, timeout: PROBE_TIMEOUT_MS, shell },
+	);
+	return parseToolVersion(stdout) ?? parseToolVersion(stderr);
+}
+
+/** Like the wrappers' find_real_binary: the first match on PATH that isn't a Caspian wrapper */
+async function findRealBinary(name: string, env: Env): Promise<string | null> {
+	const extensions = process.platform === "win32" ? [".exe", ".cmd", ""] : [""];
+	const path = env.PATH ?? env.Path ?? process.env.PATH ?? "";
+	for (const dir of path.split(delimiter)) {
+		if (!dir || resolve(dir) === BIN_DIR) continue;
+		for (const extension of extensions) {
+			const candidate = join(dir, `${name}${extension}`);
+			if (await isExecutableFile(candidate)) return candidate;
+		}
+	}
+	return null;
+}
+
+async function isExecutableFile(filePath: string): Promise<boolean> {
+	try {
+		const stats = await stat(filePath);
+		return stats.isFile() && (process.platform === "win32" || (stats.mode & 0o111) !== 0);
+	} catch {
+		return false;
+	}
+}
+
+function errorMessage(error: unknown): string {
+	return error instanceof Error ? error.message : String(error);
+}
@@ -0,0 +1,37 @@
+import { describe, expect, test } from "bun:test";
+import { formatInstallCommand, getInstallRecipes } from "./gh-install";
+
+function methodsFor(platform: NodeJS.Platform): string[] {
+	return getInstallRecipes(platform).map((recipe) => recipe.method);
+}
+
+describe("getInstallRecipes", () => {
+	test("offers winget and scoop on Windows", () => {
+		expect(methodsFor("win32")).toEqual(["winget", "scoop"]);
+	});
+
+	test("offers Homebrew on macOS", () => {
+		expect(methodsFor("darwin")).toEqual(["brew"]);
+	});
+
+	test("offers Linux package managers and Linuxbrew on Linux", () => {
+		expect(methodsFor("linux")).toEqual(["brew", "apt", "dnf", "pacman", "zypper"]);
+	});
+});
+
+describe("formatInstallCommand", () => {
+	test("prefixes commands that need root with sudo", () => {
+		const [apt] = getInstallRecipes("linux").filter((recipe) => recipe.method === "apt");
+		expect(formatInstallCommand(apt)).toBe("sudo apt install -y gh");
+	});
+
+	test("runs user-level package managers as is", () => {
+		const [scoop] = getInstallRecipes("win32").filter((recipe) => recipe.method === "scoop");
+		expect(formatInstallCommand(scoop)).toBe("scoop install gh");
+	});
+
+	test("uses the package name each manager knows gh by", () => {
+		const [pacman] = getInstallRecipes("linux").filter((recipe) => recipe.method === "pacman");
+		expect(formatInstallCommand(pacman)).toBe("sudo pacman -S --noconfirm github-cli");
+	});
+});
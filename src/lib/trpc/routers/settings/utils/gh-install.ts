@@ -0,0 +1,158 @@
+import {
+	addExecutableDirToPath,
+	execWithShellEnv,
+	findExecutable,
+} from "../../nodes/utils/shell-env";
+
+export const GH_INSTALL_METHODS = [
+	"brew",
+	"winget",
+	"scoop",
+	"apt",
+	"dnf",
+	"pacman",
+	"zypper",
+] as const;
+export type GhInstallMethod = (typeof GH_INSTALL_METHODS)[number];
+
+export interface InstallRecipe {
+	method: GhInstallMethod;
+	platforms: NodeJS.Platform[];
+	/** Package manager executable, used to detect whether it's installed */
+	manager: string;
+	args: string[];
+	/** Needs sudo, which can only prompt for a password in a terminal */
+	requiresTerminal: boolean;
+	/** The manager is a PowerShell script shim that can't be spawned directly */
+	powershell?: boolean;
+}
+
+const RECIPES: InstallRecipe[] = [
+	{
+		method: "brew",
+		platforms: ["darwin", "linux"],
+		manager: "brew",
+		args: ["install", "gh"],
+		requiresTerminal: false,
+	},
+	{
+		method: "winget",
+		platforms: ["win32"],
+		manager: "winget",
+		args: [
+			"install",
+			"--id",
+			"GitHub.cli",
+			"--exact",
+			"--silent",
+			"--accept-source-agreements",
+			"--accept-package-agreements",
+		],
+		requiresTerminal: false,
+	},
+	{
+		method: "scoop",
+		platforms: ["win32"],
+		manager: "scoop",
+		args: ["install", "gh"],
+		requiresTerminal: false,
+		powershell: true,
+	},
+	{
+		method: "apt",
+		platforms: ["linux"],
+		manager: "apt",
+		args: ["install", "-y", "gh"],
+		requiresTerminal: true,
+	},
+	{
+		method: "dnf",
+		platforms: ["linux"],
+		manager: "dnf",
+		args: ["install", "-y", "gh"],
+		requiresTerminal: true,
+	},
+	{
+		method: "pacman",
+		platforms: ["linux"],
+		manager: "pacman",
+		args: ["-S", "--noconfirm", "github-cli"],
+		requiresTerminal: true,
+	},
+	{
+		method: "zypper",
+		platforms: ["linux"],
+		manager: "zypper",
+		args: ["install", "-y", "gh"],
+		requiresTerminal: true,
+	},
+];
+
+const INSTALL_TIMEOUT_MS = 10 * 60_000;
+
+export interface GhInstallOption {
+	method: GhInstallMethod;
+	/** Command line to show the user, or to run in a terminal when requiresTerminal is set */
+	command: string;
+	requiresTerminal: boolean;
+}
+
+export function getInstallRecipes(platform: NodeJS.Platform): InstallRecipe[] {
+	return RECIPES.filter((recipe) => recipe.platforms.includes(platform));
+}
+
+export function formatInstallCommand(recipe: InstallRecipe): string {
+	const command = [recipe.manager, ...recipe.args].join(" ");
+	return recipe.requiresTerminal ? `sudo ${command}` : command;
+}
+
+/** Install methods whose package manager is present on this machine */
+export async function getGhInstallOptions(): Promise<GhInstallOption[]> {
+	const recipes = getInstallRecipes(process.platform);
+	const found = await Promise.all(recipes.map((recipe) => findExecutable(recipe.manager)));
+	return recipes
+		.filter((_, index) => found[index] !== null)
+		.map((recipe) => ({
+			method: recipe.method,
+			command: formatInstallCommand(recipe),
+			requiresTerminal: recipe.requiresTerminal,
+		}));
+}
+
+/**
+ * Installs gh with a package manager that doesn't need elevation. Returns
+ * the installed gh path, which is added to PATH since installers only
+ * update the PATH of processes started afterwards.
+ */
+export async function installGh(method: GhInstallMethod): Promise<string> {
+	const recipe = getInstallRecipes(process.platform).find((r) => r.method === method);
+	if (!recipe) {
+		throw new Error(`${method} is not available on ${process.platform}`);
+	}
+	if (recipe.requiresTerminal) {
+		throw new Error(`Run "${formatInstallCommand(recipe)}" in a terminal to install gh`);
+	}
+
+	const managerPath = await findExecutable(recipe.manager);
+	if (!managerPath) {
+		throw new Error(`${recipe.manager} is not installed`);
+	}
+
+	console.log(`[settings/gh-install] Installing gh with ${method}`);
+	if (recipe.powershell) {
+		await execWithShellEnv(
+			"powershell.exe",
+			["-NoProfile", "-NonInteractive", "-Command", formatInstallCommand(recipe)],
+			{ timeout: INSTALL_TIMEOUT_MS },
+		);
+	} else {
+		await execWithShellEnv(managerPath, recipe.args, { timeout: INSTALL_TIMEOUT_MS });
+	}
+
+	const ghPath = await findExecutable("gh");
+	if (!ghPath) {
+		throw new Error(`${method} finished but gh could not be found`);
+	}
+	addExecutableDirToPath(ghPath);
+	return ghPath;
+}
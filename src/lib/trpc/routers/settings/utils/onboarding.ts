@@ -0,0 +1,78 @@
+import { constants } from "node:fs";
+import { access } from "node:fs/promises";
+import { eq } from "drizzle-orm";
+import { Notification } from "electron";
+import { githubAccounts } from "lib/local-db";
+import { CASPIAN_HOME_DIR } from "main/lib/app-environment";
+import { localDb } from "main/lib/local-db";
+import { execWithShellEnv } from "../../nodes/utils/shell-env";
+import { checkToolStatus } from "./check-tools";
+import { findInstalledAgents } from "./diagnostics";
+
+export type OnboardingStatus = {
+	git: { installed: boolean; userName: string | null; userEmail: string | null };
+	github: {
+		ghInstalled: boolean;
+		/** Set when gh is signed in */
+		ghUsername: string | null;
+		/** Accounts added in Settings with a personal access token */
+		tokenAccounts: number;
+	};
+	agents: { id: string; label: string }[];
+	/**
+	 * Whether the OS can show notifications at all. Electron can't read the
+	 * user's per-app permission, so a denied prompt still reports true.
+	 */
+	notificationsSupported: boolean;
+	dataDir: { path: string; writable: boolean };
+	/** Everything required is in place; GitHub and notifications are optional */
+	ready: boolean;
+};
+
+/** Everything the setup screen needs, in one round trip */
+export async function getOnboardingStatus(): Promise<OnboardingStatus> {
+	const [tools, userName, userEmail, agents, dataDirWritable] = await Promise.all([
+		checkToolStatus(),
+		getGitConfig("user.name"),
+		getGitConfig("user.email"),
+		findInstalledAgents(),
+		isWritable(CASPIAN_HOME_DIR),
+	]);
+	const tokenAccounts = localDb
+		.select({ id: githubAccounts.id })
+		.from(githubAccounts)
+		.where(eq(githubAccounts.hasToken, true))
+		.all().length;
+
+	return {
+		git: { installed: tools.git.available, userName, userEmail },
+		github: {
+			ghInstalled: tools.gh.installed,
+			ghUsername: tools.gh.authenticated ? tools.gh.username : null,
+			tokenAccounts,
+		},
+		agents,
+		notificationsSupported: Notification.isSupported(),
+		dataDir: { path: CASPIAN_HOME_DIR, writable: dataDirWritable },
+		ready: tools.git.available && !!userName && !!userEmail && agents.length > 0 && dataDirWritable,
+	};
+}
+
+/** Commits fail without user.name and user.email, so both count as setup */
+async function getGitConfig(key: string): Promise<string | null> {
+	try {
+		const { stdout } = await execWithShellEnv("git", ["config", "--get", key]);
+		return stdout.trim() || null;
+	} catch {
+		return null;
+	}
+}
+
+async function isWritable(dir: string): Promise<boolean> {
+	try {
+		await access(dir, constants.W_OK);
+		return true;
+	} catch {
+		return false;
+	}
+}
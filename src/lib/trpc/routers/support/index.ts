@@ -0,0 +1,45 @@
+import { writeFile } from "node:fs/promises";
+import { type BrowserWindow, dialog } from "electron";
+import { publicProcedure, router } from "../..";
+import { buildSupportBundle } from "./utils/bundle";
+
+type SupportBundleResult =
+	| { canceled: true }
+	| { canceled: false; path: string }
+	| { canceled: false; error: string };
+
+export const createSupportRouter = (getWindow: () => BrowserWindow | null) => {
+	return router({
+		/** Saves a zip of diagnostics, versions and logs to attach to a GitHub issue */
+		generateSupportBundle: publicProcedure.mutation(async (): Promise<SupportBundleResult> => {
+			const window = getWindow();
+			if (!window) {
+				return { canceled: false, error: "No window available" };
+			}
+
+			const date = new Date().toISOString().slice(0, 10);
+			const result = await dialog.showSaveDialog(window, {
+				title: "Save Support Bundle",
+				defaultPath: `caspian-support-${date}.zip`,
+				filters: [{ name: "Zip archives", extensions: ["zip"] }],
+			});
+
+			if (result.canceled || !result.filePath) {
+				return { canceled: true };
+			}
+
+			try {
+				await writeFile(result.filePath, await buildSupportBundle());
+			} catch (error) {
+				console.error(`[support/bundle] Failed to save to ${result.filePath}:`, error);
+				const message = error instanceof Error ? error.message : "Failed to write file";
+				return { canceled: false, error: message };
+			}
+
+			console.log(`[support] Saved support bundle to ${result.filePath}`);
+			return { canceled: false, path: result.filePath };
+		}),
+	});
+};
+
+export type SupportRouter = ReturnType<typeof createSupportRouter>;
@@ -0,0 +1,55 @@
+import { homedir, release } from "node:os";
+import { app } from "electron";
+import { getUpdateChannel } from "main/lib/auto-updater";
+import { getSchemaVersion, getTableRowCounts } from "main/lib/local-db";
+import { readAllLogs } from "main/lib/logger";
+import { getPrivacySettings } from "main/lib/privacy";
+import { redactSecrets } from "shared/utils/redact";
+import { runDiagnostics } from "../../settings/utils/diagnostics";
+import { createZip } from "./zip";
+
+/** Redacts secrets and replaces the home directory, which usually contains the user's name */
+function scrub(text: string): string {
+	return redactSecrets(text).replaceAll(homedir(), "~");
+}
+
+function toJson(value: unknown): string {
+	return `${JSON.stringify(value, null, 2)}\n`;
+}
+
+/**
+ * Collects what's needed to debug a bug report into a zip: app and schema
+ * versions, diagnostics (including agent versions), row counts per table
+ * and the retained logs. Nothing from the database beyond counts is
+ * included, and every file is scrubbed of secrets and home directory paths.
+ */
+export async function buildSupportBundle(): Promise<Buffer> {
+	const [diagnostics, logs] = await Promise.all([runDiagnostics(), readAllLogs()]);
+	const { telemetryLevel, localOnly } = getPrivacySettings();
+
+	const manifest = {
+		generatedAt: new Date().toISOString(),
+		app: { name: app.getName(), version: app.getVersion() },
+		versions: {
+			electron: process.versions.electron,
+			chrome: process.versions.chrome,
+			node: process.versions.node,
+		},
+		os: { platform: process.platform, arch: process.arch, release: release() },
+		schemaVersion: getSchemaVersion(),
+		updateChannel: getUpdateChannel(),
+		privacy: { telemetryLevel, localOnly },
+		agents: diagnostics.checks
+			.filter((check) => check.category === "agents")
+			.map(({ id, status, detail }) => ({ id, status, detail })),
+	};
+
+	const files = [
+		{ name: "manifest.json", content: toJson(manifest) },
+		{ name: "diagnostics.json", content: toJson(diagnostics) },
+		{ name: "db-summary.json", content: toJson({ tables: getTableRowCounts() }) },
+		{ name: "logs/caspian.log", content: logs },
+	];
+
+	return createZip(files.map(({ name, content }) => ({ name, content: scrub(content) })));
+}
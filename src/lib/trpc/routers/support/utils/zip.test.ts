@@ -0,0 +1,43 @@
+import { describe, expect, test } from "bun:test";
+import { inflateRawSync } from "node:zlib";
+import { createZip } from "./zip";
+
+/** Reads entries back through the central directory, like an unzip tool would */
+function readZip(zip: Buffer): Record<string, string> {
+	const end = zip.length - 22;
+	const count = zip.readUInt16LE(end + 10);
+	let cursor = zip.readUInt32LE(end + 16);
+
+	const files: Record<string, string> = {};
+	for (let i = 0; i < count; i++) {
+		const compressedSize = zip.readUInt32LE(cursor + 20);
+		const nameLength = zip.readUInt16LE(cursor + 28);
+		const localOffset = zip.readUInt32LE(cursor + 42);
+		const name = zip.toString("utf-8", cursor + 46, cursor + 46 + nameLength);
+
+		const dataStart = localOffset + 30 + zip.readUInt16LE(localOffset + 26);
+		const data = inflateRawSync(zip.subarray(dataStart, dataStart + compressedSize));
+		files[name] = data.toString("utf-8");
+		cursor += 46 + nameLength;
+	}
+	return files;
+}
+
+describe("createZip", () => {
+	test("round-trips entries", () => {
+		const zip = createZip([
+			{ name: "manifest.json", content: '{"version":"1.0.0"}' },
+			{ name: "logs/main.log", content: Buffer.from("line\n".repeat(1000)) },
+		]);
+
+		expect(zip.readUInt32LE(0)).toBe(0x04034b50);
+		expect(readZip(zip)).toEqual({
+			"manifest.json": '{"version":"1.0.0"}',
+			"logs/main.log": "line\n".repeat(1000),
+		});
+	});
+
+	test("writes a valid empty archive", () => {
+		expect(readZip(createZip([]))).toEqual({});
+	});
+});
@@ -0,0 +1,85 @@
+import { crc32, deflateRawSync } from "node:zlib";
+
+export interface ZipEntry {
+	/** Path inside the archive, using forward slashes */
+	name: string;
+	content: string | Buffer;
+}
+
+const LOCAL_FILE_HEADER = 0x04034b50;
+const CENTRAL_DIRECTORY_HEADER = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY = 0x06054b50;
+
+/** Zip 2.0: deflate, no zip64 */
+const ZIP_VERSION = 20;
+const DEFLATE = 8;
+/** Names are UTF-8 */
+const UTF8_FLAG = 0x0800;
+
+function toDosDateTime(date: Date): { time: number; date: number } {
+	return {
+		time: (date.getHours() << 11) | (date.getMinutes() << 5) | (date.getSeconds() >> 1),
+		date: ((date.getFullYear() - 1980) << 9) | ((date.getMonth() + 1) << 5) | date.getDate(),
+	};
+}
+
+/**
+ * Builds a deflated zip archive in memory. Meant for small bundles of text
+ * files: there's no zip64 support, so entries and the archive must stay
+ * under 4 GB.
+ */
+export function createZip(entries: ZipEntry[], modifiedAt = new Date()): Buffer {
+	const dos = toDosDateTime(modifiedAt);
+	const localParts: Buffer[] = [];
+	const centralParts: Buffer[] = [];
+	let offset = 0;
+
+	for (const entry of entries) {
+		const name = Buffer.from(entry.name, "utf-8");
+		const data = typeof entry.content === "string" ? Buffer.from(entry.content) : entry.content;
+		const compressed = deflateRawSync(data);
+		const checksum = crc32(data);
+
+		const local = Buffer.alloc(30);
+		local.writeUInt32LE(LOCAL_FILE_HEADER, 0);
+		local.writeUInt16LE(ZIP_VERSION, 4);
+		local.writeUInt16LE(UTF8_FLAG, 6);
+		local.writeUInt16LE(DEFLATE, 8);
+		local.writeUInt16LE(dos.time, 10);
+		local.writeUInt16LE(dos.date, 12);
+		local.writeUInt32LE(checksum, 14);
+		local.writeUInt32LE(compressed.length, 18);
+		local.writeUInt32LE(data.length, 22);
+		local.writeUInt16LE(name.length, 26);
+		local.writeUInt16LE(0, 28);
+
+		const central = Buffer.alloc(46);
+		central.writeUInt32LE(CENTRAL_DIRECTORY_HEADER, 0);
+		central.writeUInt16LE(ZIP_VERSION, 4);
+		central.writeUInt16LE(ZIP_VERSION, 6);
+		central.writeUInt16LE(UTF8_FLAG, 8);
+		central.writeUInt16LE(DEFLATE, 10);
+		central.writeUInt16LE(dos.time, 12);
+		central.writeUInt16LE(dos.date, 14);
+		central.writeUInt32LE(checksum, 16);
+		central.writeUInt32LE(compressed.length, 20);
+		central.writeUInt32LE(data.length, 24);
+		central.writeUInt16LE(name.length, 28);
+		// Extra field, comment, disk number, attributes: all zero
+		central.writeUInt32LE(offset, 42);
+
+		localParts.push(local, name, compressed);
+		centralParts.push(central, name);
+		offset += local.length + name.length + compressed.length;
+	}
+
+	const centralDirectory = Buffer.concat(centralParts);
+	const end = Buffer.alloc(22);
+	end.writeUInt32LE(END_OF_CENTRAL_DIRECTORY, 0);
+	end.writeUInt16LE(entries.length, 8);
+	end.writeUInt16LE(entries.length, 10);
+	end.writeUInt32LE(centralDirectory.length, 12);
+	end.writeUInt32LE(offset, 16);
+
+	return Buffer.concat([...localParts, centralDirectory, end]);
+}
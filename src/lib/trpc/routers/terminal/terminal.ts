@@ -1,19 +1,28 @@
 import fs from "node:fs/promises";
 import path from "node:path";
-import { TRPCError } from "@trpc/server";
 import { observable } from "@trpc/server/observable";
 import { eq } from "drizzle-orm";
 import { nodes, repositories, worktrees } from "lib/local-db";
+import { changesCache } from "main/lib/changes-cache";
 import { localDb } from "main/lib/local-db";
+import { nodeInitManager } from "main/lib/node-init-manager";
 import { getNodeRuntimeRegistry } from "main/lib/node-runtime";
+import { getRepositoryEnv } from "main/lib/secrets/repository-env";
 import { getDaemonTerminalManager } from "main/lib/terminal";
+import { buildTerminalEnv, getDefaultShell } from "main/lib/terminal/env";
 import { TERMINAL_SESSION_KILLED_MESSAGE, TerminalKilledError } from "main/lib/terminal/errors";
+import { rateLimitCoordinator } from "main/lib/terminal/rate-limit";
+import { resourceMonitor } from "main/lib/terminal/resource-monitor";
 import { getTerminalHostClient } from "main/lib/terminal-host/client";
+import type { RateLimitState } from "shared/types/rate-limit";
+import type { AgentMemoryLimitEvent } from "shared/types/resource-usage";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
-import { assertNodeUsable } from "../nodes/utils/usability";
+import { CaspianError } from "../../errors";
+import { retryNodeInit } from "../nodes/utils/node-init";
+import { assertNodeUsable, checkNodeUsability } from "../nodes/utils/usability";
 import { getNodePath } from "../nodes/utils/worktree";
-import { resolveCwd } from "./utils";
+import { maskEnv, resolveCwd } from "./utils";
 
 const DEBUG_TERMINAL = process.env.CASPIAN_TERMINAL_DEBUG === "1";
 const logger = console;
@@ -39,6 +48,8 @@ const SAFE_ID = z
  * - CASPIAN_WORKSPACE_PATH: The worktree path (used by setup/teardown scripts)
  * - CASPIAN_ROOT_PATH: The main repo path (used by setup/teardown scripts)
  * - CASPIAN_PORT: The hooks server port for agent completion notifications
+ *
+ * Plus the repository's own environment variables from the secret store.
  */
 export const createTerminalRouter = () => {
 	const registry = getNodeRuntimeRegistry();
@@ -60,6 +71,8 @@ export const createTerminalRouter = () => {
 					initialCommands: z.array(z.string()).optional(),
 					skipColdRestore: z.boolean().optional(),
 					allowKilled: z.boolean().optional(),
+					/** Restart worktree initialization instead of failing when the last attempt failed */
+					retryFailedInit: z.boolean().optional(),
 				}),
 			)
 			.mutation(async ({ input }) => {
@@ -75,14 +88,21 @@ export const createTerminalRouter = () => {
 					initialCommands,
 					skipColdRestore,
 					allowKilled,
+					retryFailedInit,
 				} = input;
 
 				const node = localDb.select().from(nodes).where(eq(nodes.id, nodeId)).get();
 				const nodePath = node ? (getNodePath(node) ?? undefined) : undefined;
 				if (node?.type === "worktree") {
+					// Never start a session on a half-created worktree. When asked, kick off a retry
+					// so the guard below reports "initializing" and the UI shows progress instead.
+					if (retryFailedInit && nodeInitManager.hasFailed(nodeId)) {
+						retryNodeInit(nodeId);
+					}
 					assertNodeUsable(nodeId, nodePath);
 				}
-				const cwd = resolveCwd(cwdOverride, nodePath);
+				// Scoped nodes start in their subdirectory
+				const cwd = resolveCwd(cwdOverride ?? node?.scopePath ?? undefined, nodePath);
 
 				if (DEBUG_TERMINAL) {
 					console.log("[Terminal Router] createOrAttach called:", {
@@ -108,6 +128,7 @@ export const createTerminalRouter = () => {
 						workspaceName: node?.name,
 						workspacePath: nodePath,
 						rootPath: repository?.mainRepoPath,
+						extraEnv: repository ? getRepositoryEnv(repository.id) : undefined,
 						cwd,
 						cols,
 						rows,
@@ -116,6 +137,9 @@ export const createTerminalRouter = () => {
 						allowKilled,
 					});
 
+					// Agents run in these sessions; keep the node's worktree watched while it exists
+					if (nodePath) changesCache.startWatching(nodePath, node?.scopePath ?? null);
+
 					if (DEBUG_TERMINAL) {
 						console.log("[Terminal Router] createOrAttach result:", {
 							callId,
@@ -148,8 +172,8 @@ export const createTerminalRouter = () => {
 								nodeId,
 							});
 						}
-						throw new TRPCError({
-							code: "BAD_REQUEST",
+						throw new CaspianError({
+							code: "TERMINAL_SESSION_KILLED",
 							message: TERMINAL_SESSION_KILLED_MESSAGE,
 						});
 					}
@@ -166,6 +190,55 @@ export const createTerminalRouter = () => {
 				}
 			}),
 
+		/**
+		 * Dry run of createOrAttach: the shell, cwd, env and commands a new session
+		 * would start with, without spawning anything. Secrets are masked.
+		 */
+		previewLaunch: publicProcedure
+			.input(
+				z.object({
+					paneId: SAFE_ID,
+					tabId: z.string(),
+					nodeId: SAFE_ID,
+					cwd: z.string().optional(),
+					initialCommands: z.array(z.string()).optional(),
+				}),
+			)
+			.query(({ input }) => {
+				const node = localDb.select().from(nodes).where(eq(nodes.id, input.nodeId)).get();
+				if (!node) {
+					throw new CaspianError({ code: "NOT_FOUND", message: `Node ${input.nodeId} not found` });
+				}
+				const nodePath = getNodePath(node) ?? undefined;
+				const repository = localDb
+					.select()
+					.from(repositories)
+					.where(eq(repositories.id, node.repositoryId))
+					.get();
+				const extraEnv = repository ? getRepositoryEnv(repository.id) : {};
+				const usability = node.type === "worktree" ? checkNodeUsability(node.id, nodePath) : null;
+				const shell = getDefaultShell();
+				const env = buildTerminalEnv({
+					shell,
+					paneId: input.paneId,
+					tabId: input.tabId,
+					workspaceId: node.id,
+					workspaceName: node.name,
+					workspacePath: nodePath,
+					rootPath: repository?.mainRepoPath,
+					extraEnv,
+				});
+
+				return {
+					shell,
+					cwd: resolveCwd(input.cwd ?? node.scopePath ?? undefined, nodePath) ?? null,
+					env: maskEnv(env, Object.keys(extraEnv)),
+					initialCommands: input.initialCommands ?? [],
+					// createOrAttach refuses worktrees that aren't ready; report why instead
+					blockedBy: usability?.reason ?? null,
+				};
+			}),
+
 		write: publicProcedure
 			.input(
 				z.object({
@@ -257,6 +330,42 @@ export const createTerminalRouter = () => {
 			return { sessions };
 		}),
 
+		/** CPU and memory per terminal process tree, from the last background sample */
+		getAgentResourceUsage: publicProcedure
+			.input(z.object({ nodeId: z.string().optional() }).optional())
+			.query(({ input }) => resourceMonitor.getUsage(input?.nodeId)),
+
+		onAgentMemoryLimit: publicProcedure.subscription(() => {
+			return observable<AgentMemoryLimitEvent>((emit) => {
+				const onLimit = (event: AgentMemoryLimitEvent) => emit.next(event);
+				resourceMonitor.on("memory-limit", onLimit);
+				return () => {
+					resourceMonitor.off("memory-limit", onLimit);
+				};
+			});
+		}),
+
+		/** Agents waiting out a rate limit before they're told to continue */
+		getRateLimitState: publicProcedure.query(() => rateLimitCoordinator.getState()),
+
+		onRateLimitChange: publicProcedure.subscription(() => {
+			return observable<RateLimitState>((emit) => {
+				const onChange = (state: RateLimitState) => emit.next(state);
+				rateLimitCoordinator.on("change", onChange);
+				return () => {
+					rateLimitCoordinator.off("change", onChange);
+				};
+			});
+		}),
+
+		/** Leaves the agents for the user to resume; omit paneId to cancel every wait */
+		cancelRateLimitResume: publicProcedure
+			.input(z.object({ paneId: z.string().optional() }))
+			.mutation(({ input }) => {
+				rateLimitCoordinator.cancel(input.paneId);
+				return { success: true };
+			}),
+
 		killAllDaemonSessions: publicProcedure.mutation(async () => {
 			const client = getTerminalHostClient();
 			const before = await terminal.management.listSessions();
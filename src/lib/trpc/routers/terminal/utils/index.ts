@@ -1 +1,2 @@
+export { maskEnv } from "./mask-env";
 export { resolveCwd } from "./resolve-cwd";
@@ -0,0 +1,21 @@
+import { describe, expect, test } from "bun:test";
+import { REDACTED } from "shared/utils/redact";
+import { maskEnv } from "./mask-env";
+
+describe("maskEnv", () => {
+	test("masks repository secrets outright", () => {
+		expect(maskEnv({ DATABASE_HOST: "localhost" }, ["DATABASE_HOST"])).toEqual({
+			DATABASE_HOST: REDACTED,
+		});
+	});
+
+	test("redacts credential-looking values by variable name", () => {
+		const env = maskEnv({ GITHUB_TOKEN: "abc123", PATH: "/usr/bin:/bin" }, []);
+		expect(env).toEqual({ GITHUB_TOKEN: REDACTED, PATH: "/usr/bin:/bin" });
+	});
+
+	test("redacts credentials embedded in other values", () => {
+		const env = maskEnv({ DATABASE_URL: "postgres://admin:hunter2@db:5432/app" }, []);
+		expect(env.DATABASE_URL).toBe(`postgres://${REDACTED}@db:5432/app`);
+	});
+});
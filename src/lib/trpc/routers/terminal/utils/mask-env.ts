@@ -0,0 +1,21 @@
+import { REDACTED, redactSecrets } from "shared/utils/redact";
+
+/**
+ * Hides values that shouldn't leave the main process. Repository env vars are
+ * secrets by definition and are masked outright; everything else only loses
+ * the parts that look like credentials, so paths and flags stay readable.
+ */
+export function maskEnv(
+	env: Record<string, string>,
+	secretNames: Iterable<string>,
+): Record<string, string> {
+	const secrets = new Set(secretNames);
+	const masked: Record<string, string> = {};
+	for (const [name, value] of Object.entries(env)) {
+		// Redacted as an assignment so names like GITHUB_TOKEN are recognized
+		masked[name] = secrets.has(name)
+			? REDACTED
+			: redactSecrets(`${name}=${value}`).slice(name.length + 1);
+	}
+	return masked;
+}
@@ -7,6 +7,7 @@ import {
 	HOTKEYS_STATE_VERSION,
 	type HotkeysState,
 } from "shared/hotkeys";
+import { nowUtcIso } from "shared/utils/time";
 import { z } from "zod";
 import { publicProcedure, router } from "../..";
 
@@ -244,7 +245,7 @@ export const createUiStateRouter = () => {
 				await appState.write();
 				hotkeysEmitter.emit("change", {
 					version: normalized.version,
-					updatedAt: new Date().toISOString(),
+					updatedAt: nowUtcIso(),
 				});
 				return { success: true };
 			}),
@@ -4,14 +4,30 @@ import { makeAppSetup } from "lib/electron-app/factories/app/setup";
 import { settings } from "lib/local-db";
 import { DEFAULT_CONFIRM_ON_QUIT, PROTOCOL_SCHEME } from "shared/constants";
 import { setupAgentHooks } from "./lib/agent-setup";
+import { findRunningAgents, flushBeforeQuit, stopAgents } from "./lib/agent-shutdown";
+import { CASPIAN_HOME_DIR, INSTANCE_LOCK_PATH } from "./lib/app-environment";
 import { initAppState } from "./lib/app-state";
 import { setupAutoUpdater } from "./lib/auto-updater";
+import { devServerManager } from "./lib/dev-server-manager";
+import { t } from "./lib/i18n";
+import { acquireInstanceLock, releaseInstanceLock } from "./lib/instance-lock";
 import { localDb } from "./lib/local-db";
+import { installFileLogger } from "./lib/logger";
+import { startMetricsCollection } from "./lib/metrics";
+import { handleNotificationAction } from "./lib/notifications/actions";
+import { updateAppBadge } from "./lib/notifications/badge";
+import { sweepInterruptedClones } from "./lib/pending-clones";
+import { reconcileAgentStatuses, startRestoreReport } from "./lib/restore-report";
+import type { AgentPane } from "./lib/restore-report/collect";
 import { initSentry } from "./lib/sentry";
 import { reconcileDaemonSessions } from "./lib/terminal";
+import { resourceMonitor } from "./lib/terminal/resource-monitor";
 import { disposeTray, initTray } from "./lib/tray";
+import { startWebhookDispatch } from "./lib/webhooks";
 import { MainWindow } from "./windows/main";
 
+installFileLogger();
+
 // Initialize local SQLite database (runs migrations + legacy data migration on import)
 console.log("[main] Local database ready:", !!localDb);
 
@@ -54,6 +70,18 @@ async function processDeepLink(url: string): Promise<void> {
 
 	const deepLinkPath = `/${url.split("://")[1]}`;
 
+	// caspian://notification/<id> opens a notification's pane and marks it read
+	const notificationId = deepLinkPath.match(/^\/notification\/([^/?#]+)/)?.[1];
+	if (notificationId) {
+		try {
+			handleNotificationAction({ id: notificationId, action: "open" });
+		} catch (error) {
+			console.warn("[main] Failed to open notification from deep link:", error);
+			focusMainWindow();
+		}
+		return;
+	}
+
 	focusMainWindow();
 
 	const windows = BrowserWindow.getAllWindows();
@@ -120,34 +148,66 @@ export function quitWithoutConfirmation(): void {
 	app.exit(0);
 }
 
+type QuitChoice = "quit" | "stop-agents" | "cancel";
+
+/**
+ * Running agents get their own prompt, since quitting would otherwise leave
+ * them in the daemon without a word. Otherwise falls back to the plain
+ * confirm-on-quit dialog.
+ */
+async function confirmQuit(runningAgents: number, shouldConfirm: boolean): Promise<QuitChoice> {
+	if (runningAgents > 0) {
+		const { response } = await dialog.showMessageBox({
+			type: "warning",
+			buttons: [t("quit.keepAgentsRunning"), t("quit.stopAgents"), t("quit.cancel")],
+			defaultId: 0,
+			cancelId: 2,
+			title: t("quit.title"),
+			message: t("quit.agentsRunning", { count: runningAgents }),
+			detail: t("quit.agentsRunningDetail"),
+		});
+		return (["quit", "stop-agents", "cancel"] as const)[response] ?? "cancel";
+	}
+
+	if (!shouldConfirm) return "quit";
+
+	const { response } = await dialog.showMessageBox({
+		type: "question",
+		buttons: [t("quit.confirm"), t("quit.cancel")],
+		defaultId: 0,
+		cancelId: 1,
+		title: t("quit.title"),
+		message: t("quit.message"),
+	});
+	return response === 1 ? "cancel" : "quit";
+}
+
 app.on("before-quit", async (event) => {
 	if (isQuitting) return;
 
-	const shouldConfirm = !skipConfirmation && !isE2ETest && getConfirmOnQuitSetting();
+	// Updates and E2E runs quit without asking; agents stay running in the daemon
+	const canPrompt = !skipConfirmation && !isE2ETest;
+	let runningAgents: AgentPane[] = [];
+	let choice: QuitChoice = "quit";
 
-	if (shouldConfirm) {
+	if (canPrompt) {
 		event.preventDefault();
-
+		runningAgents = await findRunningAgents();
 		try {
-			const { response } = await dialog.showMessageBox({
-				type: "question",
-				buttons: ["Quit", "Cancel"],
-				defaultId: 0,
-				cancelId: 1,
-				title: "Quit Caspian",
-				message: "Are you sure you want to quit?",
-			});
-
-			if (response === 1) {
-				return;
-			}
+			choice = await confirmQuit(runningAgents.length, getConfirmOnQuitSetting());
 		} catch (error) {
 			console.error("[main] Quit confirmation dialog failed:", error);
 		}
+		if (choice === "cancel") return;
 	}
 
 	isQuitting = true;
 	disposeTray();
+	if (choice === "stop-agents") {
+		await stopAgents(runningAgents);
+	}
+	await flushBeforeQuit();
+	await devServerManager.stopAll();
 	app.exit(0);
 });
 
@@ -200,11 +260,31 @@ if (process.env.NODE_ENV === "development") {
 	parentCheckInterval.unref();
 }
 
+/**
+ * Electron's lock only covers instances sharing a userData dir. This also
+ * stops a differently named build from using the same Caspian home dir.
+ */
+function acquireHomeDirLock(): boolean {
+	const result = acquireInstanceLock({ lockPath: INSTANCE_LOCK_PATH });
+	if (!result.acquired) {
+		console.warn(`[main] Home dir is locked by pid ${result.ownerPid}, exiting`);
+		const owner = result.ownerPid ? ` (pid ${result.ownerPid})` : "";
+		dialog.showErrorBox(
+			"Caspian is already running",
+			`Another Caspian process${owner} is using ${CASPIAN_HOME_DIR}. Quit it and try again.`,
+		);
+		return false;
+	}
+
+	process.on("exit", () => releaseInstanceLock({ lockPath: INSTANCE_LOCK_PATH }));
+	return true;
+}
+
 // Single instance lock - required for second-instance event on Windows/Linux
 // Skip in E2E test mode to allow tests to launch alongside a dev instance.
 // Uses a custom env var because process.env.NODE_ENV is replaced at build time.
 const isE2ETest = process.env.CASPIAN_E2E_TEST === "1";
-const gotTheLock = isE2ETest || app.requestSingleInstanceLock();
+const gotTheLock = isE2ETest || (app.requestSingleInstanceLock() && acquireHomeDirLock());
 
 if (!gotTheLock) {
 	app.exit(0);
@@ -224,9 +304,18 @@ if (!gotTheLock) {
 
 		await initAppState();
 
+		startMetricsCollection();
+		startWebhookDispatch();
+		sweepInterruptedClones().catch((error) => {
+			console.error("[main] Failed to sweep interrupted clones:", error);
+		});
+
 		// Clean up stale daemon sessions from previous app runs
 		// Must happen BEFORE renderer restore runs
 		await reconcileDaemonSessions();
+		startRestoreReport();
+		await reconcileAgentStatuses();
+		resourceMonitor.start();
 
 		try {
 			setupAgentHooks();
@@ -239,6 +328,7 @@ if (!gotTheLock) {
 		setupAutoUpdater();
 
 		initTray();
+		updateAppBadge();
 
 		const coldStartUrl = findDeepLinkInArgv(process.argv);
 		if (coldStartUrl) {
@@ -4,7 +4,8 @@ import { PORTS } from "shared/constants";
 import { HOOKS_DIR } from "./paths";
 
 export const NOTIFY_SCRIPT_NAME = "notify.sh";
-export const NOTIFY_SCRIPT_MARKER = "# Caspian agent notification hook";
+// Bump the version when the script changes so existing installs are rewritten
+export const NOTIFY_SCRIPT_MARKER = "# Caspian agent notification hook v2";
 
 const NOTIFY_SCRIPT_TEMPLATE_PATH = path.join(__dirname, "templates", "notify-hook.template.sh");
 
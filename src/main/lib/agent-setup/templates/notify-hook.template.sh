@@ -37,6 +37,7 @@ fi
 # Timeouts prevent blocking agent completion if notification server is unresponsive
 curl -sG "http://127.0.0.1:${CASPIAN_PORT:-{{DEFAULT_PORT}}}/hook/complete" \
   --connect-timeout 1 --max-time 2 \
+  -H "X-Caspian-Hook: 1" \
   --data-urlencode "paneId=$CASPIAN_PANE_ID" \
   --data-urlencode "tabId=$CASPIAN_TAB_ID" \
   --data-urlencode "workspaceId=$CASPIAN_WORKSPACE_ID" \
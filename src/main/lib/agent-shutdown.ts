@@ -0,0 +1,67 @@
+import { type AgentPane, listAgentPanes } from "main/lib/restore-report/collect";
+import { appState } from "./app-state";
+import { recordAuditEvent } from "./audit";
+import { getDaemonTerminalManager } from "./terminal";
+
+/** Agents working or waiting on a prompt in a terminal the daemon still has */
+export async function findRunningAgents(): Promise<AgentPane[]> {
+	try {
+		const { sessions } = await getDaemonTerminalManager().listDaemonSessions();
+		const alive = new Set(sessions.filter((s) => s.isAlive).map((s) => s.sessionId));
+		return listAgentPanes(appState.data.tabsState).filter((pane) => alive.has(pane.paneId));
+	} catch (error) {
+		console.warn("[agent-shutdown] Failed to list running agents:", error);
+		return [];
+	}
+}
+
+/**
+ * Kills the agents' terminals and records them as interrupted, so the next
+ * launch's restore report lists them with a resume command.
+ */
+export async function stopAgents(agents: AgentPane[]): Promise<void> {
+	const manager = getDaemonTerminalManager();
+	const interruptedAt = Date.now();
+
+	await Promise.all(
+		agents.map(async ({ paneId, tabId, name, resumeCommand, nodeId }) => {
+			try {
+				await manager.kill({ paneId });
+			} catch (error) {
+				console.warn(`[agent-shutdown] Failed to stop agent in ${paneId}:`, error);
+				return;
+			}
+			appState.data.interruptedAgents.push({
+				paneId,
+				tabId,
+				name,
+				resumeCommand,
+				nodeId,
+				interruptedAt,
+			});
+			recordAuditEvent({
+				type: "agent_interrupted",
+				nodeId,
+				actor: "user",
+				summary: "Agent stopped when Caspian quit",
+				data: { paneId, resumeCommand },
+			});
+		}),
+	);
+
+	await appState.write();
+}
+
+/**
+ * Last writes before exiting: terminal scrollback for sessions left running
+ * in the daemon, and the tabs state the renderer last synced.
+ */
+export async function flushBeforeQuit(): Promise<void> {
+	try {
+		await getDaemonTerminalManager().flushHistory();
+		appState.data.lastQuitAt = Date.now();
+		await appState.write();
+	} catch (error) {
+		console.warn("[agent-shutdown] Failed to flush state before quit:", error);
+	}
+}
@@ -1,5 +1,6 @@
 // import { app } from "electron";
 // import { PostHog } from "posthog-node";
+// import { getTelemetryLevel } from "../privacy";
 
 // PostHog analytics - currently disabled
 // To enable, uncomment the code below and set POSTHOG_KEY
@@ -30,7 +31,7 @@ export function setUserId(_id: string | null): void {
 }
 
 export function track(_event: string, _properties?: Record<string, unknown>): void {
-	// if (!userId) return;
+	// if (!userId || getTelemetryLevel() !== "full") return;
 	// const client = getClient();
 	// if (!client) return;
 	// client.capture({
@@ -34,3 +34,13 @@ export const APP_STATE_PATH = join(CASPIAN_HOME_DIR, "app-state.json");
 
 // Window geometry state (separate from UI state - main process only, sync I/O)
 export const WINDOW_STATE_PATH = join(CASPIAN_HOME_DIR, "window-state.json");
+
+// Held by the running instance so a second build sharing this home dir doesn't start
+export const INSTANCE_LOCK_PATH = join(CASPIAN_HOME_DIR, "instance.lock");
+
+// Tokens and other secrets, encrypted with the OS keychain via safeStorage. Keeps the
+// credentials.json name so tokens saved by earlier versions are still found.
+export const SECRETS_PATH = join(CASPIAN_HOME_DIR, "credentials.json");
+
+// Rotating main process logs, see main/lib/logger
+export const LOGS_DIR = join(CASPIAN_HOME_DIR, "logs");
@@ -30,6 +30,8 @@ function ensureValidShape(data: Partial<AppState>): AppState {
 				...(data.hotkeysState?.byPlatform ?? {}),
 			},
 		},
+		interruptedAgents: data.interruptedAgents ?? defaultAppState.interruptedAgents,
+		lastQuitAt: data.lastQuitAt ?? defaultAppState.lastQuitAt,
 	};
 }
 
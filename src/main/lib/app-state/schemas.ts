@@ -4,6 +4,7 @@
 import { createDefaultHotkeysState, type HotkeysState } from "shared/hotkeys";
 import type { BaseTabsState } from "shared/tabs-types";
 import type { Theme } from "shared/themes";
+import type { InterruptedAgent } from "shared/types/restore-report";
 
 // Re-export for convenience
 export type { BaseTabsState as TabsState, Pane } from "shared/tabs-types";
@@ -17,6 +18,10 @@ export interface AppState {
 	tabsState: BaseTabsState;
 	themeState: ThemeState;
 	hotkeysState: HotkeysState;
+	/** Written by the main process when quitting stops running agents */
+	interruptedAgents: InterruptedAgent[];
+	/** When the main process last quit cleanly; null after a crash or on first launch */
+	lastQuitAt: number | null;
 }
 
 export const defaultAppState: AppState = {
@@ -32,4 +37,6 @@ export const defaultAppState: AppState = {
 		customThemes: [],
 	},
 	hotkeysState: createDefaultHotkeysState(),
+	interruptedAgents: [],
+	lastQuitAt: null,
 };
@@ -0,0 +1,56 @@
+import { describe, expect, test } from "bun:test";
+import type { AuditEvent } from "shared/types/audit";
+import {
+	decodeActivityCursor,
+	encodeActivityCursor,
+	formatAuditEvents,
+	parseAuditLines,
+} from "./format";
+
+const event: AuditEvent = {
+	id: "e1",
+	timestamp: Date.UTC(2026, 0, 2, 3, 4, 5),
+	type: "test_run",
+	repositoryId: "repo",
+	nodeId: "node",
+	actor: "user",
+	summary: 'bun test, "passed"',
+	data: { exitCode: 0 },
+};
+
+describe("formatAuditEvents", () => {
+	test("writes one JSON object per line", () => {
+		const output = formatAuditEvents([event, { ...event, id: "e2" }], "jsonl");
+		expect(output.split("\n")).toHaveLength(3);
+		expect(parseAuditLines(output).map((e) => e.id)).toEqual(["e1", "e2"]);
+	});
+
+	test("quotes CSV fields that need it", () => {
+		expect(formatAuditEvents([{ ...event, nodeId: null }], "csv")).toBe(
+			"timestamp,type,repositoryId,nodeId,actor,summary,data,id\r\n" +
+				'2026-01-02T03:04:05.000Z,test_run,repo,,user,"bun test, ""passed""","{""exitCode"":0}",e1\r\n',
+		);
+	});
+});
+
+describe("parseAuditLines", () => {
+	test("skips partial lines", () => {
+		const content = `${JSON.stringify(event)}\n{"id":"trunc`;
+		expect(parseAuditLines(content)).toEqual([event]);
+	});
+});
+
+describe("activity cursors", () => {
+	test("round-trip", () => {
+		expect(decodeActivityCursor(encodeActivityCursor(event))).toEqual({
+			timestamp: event.timestamp,
+			id: "e1",
+		});
+	});
+
+	test("reject malformed cursors", () => {
+		expect(decodeActivityCursor("e1")).toBeNull();
+		expect(decodeActivityCursor("abc:e1")).toBeNull();
+		expect(decodeActivityCursor("123:")).toBeNull();
+	});
+});
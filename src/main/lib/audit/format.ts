@@ -0,0 +1,71 @@
+import type { AuditEvent, AuditExportFormat } from "shared/types/audit";
+
+const CSV_COLUMNS = [
+	"timestamp",
+	"type",
+	"repositoryId",
+	"nodeId",
+	"actor",
+	"summary",
+	"data",
+	"id",
+] as const;
+
+function escapeCsv(value: string): string {
+	return /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
+
+function toCsvRow(event: AuditEvent): string {
+	const values: Record<(typeof CSV_COLUMNS)[number], string> = {
+		timestamp: new Date(event.timestamp).toISOString(),
+		type: event.type,
+		repositoryId: event.repositoryId,
+		nodeId: event.nodeId ?? "",
+		actor: event.actor,
+		summary: event.summary,
+		data: event.data ? JSON.stringify(event.data) : "",
+		id: event.id,
+	};
+	return CSV_COLUMNS.map((column) => escapeCsv(values[column])).join(",");
+}
+
+/**
+ * Serializes events for export. JSONL keeps the events as recorded; CSV
+ * flattens `data` into a JSON column and uses ISO timestamps.
+ */
+export function formatAuditEvents(events: AuditEvent[], format: AuditExportFormat): string {
+	if (format === "csv") {
+		return `${[CSV_COLUMNS.join(","), ...events.map(toCsvRow)].join("\r\n")}\r\n`;
+	}
+	return events.map((event) => `${JSON.stringify(event)}\n`).join("");
+}
+
+/**
+ * Parses a JSONL audit file, skipping lines that don't parse (e.g. a partial
+ * line left by a crash mid-append).
+ */
+export function parseAuditLines(content: string): AuditEvent[] {
+	const events: AuditEvent[] = [];
+	for (const line of content.split("\n")) {
+		if (!line.trim()) continue;
+		try {
+			events.push(JSON.parse(line) as AuditEvent);
+		} catch {
+			// Ignore corrupt lines
+		}
+	}
+	return events;
+}
+
+/** Activity feed cursors point just past the last item shown: `<timestamp>:<id>` */
+export function encodeActivityCursor(event: { timestamp: number; id: string }): string {
+	return `${event.timestamp}:${event.id}`;
+}
+
+export function decodeActivityCursor(cursor: string): { timestamp: number; id: string } | null {
+	const separator = cursor.indexOf(":");
+	const timestamp = Number(cursor.slice(0, separator));
+	const id = cursor.slice(separator + 1);
+	if (separator < 1 || !Number.isInteger(timestamp) || !id) return null;
+	return { timestamp, id };
+}
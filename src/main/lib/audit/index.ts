@@ -0,0 +1,297 @@
+import { randomUUID } from "node:crypto";
+import { appendFile, mkdir, readdir, readFile } from "node:fs/promises";
+import { dirname, join } from "node:path";
+import { and, desc, eq, inArray, lt, or, type SQL } from "drizzle-orm";
+import { auditEvents, nodes, repositories, type SelectAuditEvent } from "lib/local-db";
+import { CASPIAN_HOME_DIR } from "main/lib/app-environment";
+import { localDb } from "main/lib/local-db";
+import { redactOutput, redactOutputData } from "main/lib/redaction";
+import type { ActivityPage, AuditActor, AuditEvent, AuditEventType } from "shared/types/audit";
+import { decodeActivityCursor, encodeActivityCursor, parseAuditLines } from "./format";
+
+/** One folder per repository, one JSONL file per node */
+const AUDIT_DIR = join(CASPIAN_HOME_DIR, "audit");
+
+/** File for events that don't belong to a node */
+const REPOSITORY_LOG_NAME = "_repository";
+
+function toFileName(id: string): string {
+	return id.replace(/[^a-zA-Z0-9_-]/g, "_");
+}
+
+function getRepositoryAuditDir(repositoryId: string): string {
+	return join(AUDIT_DIR, toFileName(repositoryId));
+}
+
+function getNodeRepositoryId(nodeId: string): string | undefined {
+	return localDb
+		.select({ repositoryId: nodes.repositoryId })
+		.from(nodes)
+		.where(eq(nodes.id, nodeId))
+		.get()?.repositoryId;
+}
+
+function indexAuditEvent(event: AuditEvent): void {
+	try {
+		localDb
+			.insert(auditEvents)
+			.values({ ...event, data: event.data ?? null })
+			.onConflictDoNothing()
+			.run();
+	} catch (error) {
+		console.error(`[audit] Failed to index ${event.type} event:`, error);
+	}
+}
+
+export interface RecordAuditEventInput {
+	type: AuditEventType;
+	summary: string;
+	nodeId?: string | null;
+	/** Looked up from the node when omitted */
+	repositoryId?: string;
+	actor?: AuditActor;
+	data?: Record<string, unknown>;
+}
+
+/**
+ * Appends an event to the node's audit file. Fire-and-forget: failures are
+ * logged and never propagate into the operation being audited.
+ */
+export function recordAuditEvent(input: RecordAuditEventInput): void {
+	const nodeId = input.nodeId ?? null;
+	const repositoryId = input.repositoryId ?? (nodeId ? getNodeRepositoryId(nodeId) : undefined);
+
+	if (!repositoryId) {
+		console.warn(`[audit] Dropping ${input.type} event: no repository for node ${nodeId}`);
+		return;
+	}
+
+	const event: AuditEvent = {
+		id: randomUUID(),
+		timestamp: Date.now(),
+		type: input.type,
+		repositoryId,
+		nodeId,
+		actor: input.actor ?? "user",
+		// Summaries and data can quote command output
+		summary: redactOutput(input.summary),
+		...(input.data && { data: redactOutputData(input.data) }),
+	};
+
+	indexAuditEvent(event);
+
+	const dir = getRepositoryAuditDir(repositoryId);
+	const file = join(dir, `${toFileName(nodeId ?? REPOSITORY_LOG_NAME)}.jsonl`);
+	void mkdir(dir, { recursive: true })
+		.then(() => appendFile(file, `${JSON.stringify(event)}\n`, "utf-8"))
+		.catch((error) => {
+			console.error(`[audit] Failed to write ${input.type} event:`, error);
+		});
+}
+
+/**
+ * Reads every audit file for a repository, oldest event first.
+ * `from`/`to` are inclusive epoch-millisecond bounds.
+ */
+export async function readAuditEvents({
+	repositoryId,
+	from,
+	to,
+}: {
+	repositoryId: string;
+	from?: number;
+	to?: number;
+}): Promise<AuditEvent[]> {
+	const dir = getRepositoryAuditDir(repositoryId);
+
+	let files: string[];
+	try {
+		files = (await readdir(dir)).filter((name) => name.endsWith(".jsonl"));
+	} catch {
+		return [];
+	}
+
+	const events: AuditEvent[] = [];
+	for (const file of files) {
+		const content = await readFile(join(dir, file), "utf-8");
+		for (const event of parseAuditLines(content)) {
+			if (from !== undefined && event.timestamp < from) continue;
+			if (to !== undefined && event.timestamp > to) continue;
+			events.push(event);
+		}
+	}
+
+	return events.sort((a, b) => a.timestamp - b.timestamp);
+}
+
+/**
+ * Re-imports every audit file into the audit_events index, e.g. after the
+ * database was reset. Events already indexed are skipped.
+ */
+export async function rebuildAuditIndex(): Promise<{ indexed: number }> {
+	let repositoryDirs: string[];
+	try {
+		repositoryDirs = await readdir(AUDIT_DIR);
+	} catch {
+		return { indexed: 0 };
+	}
+
+	let indexed = 0;
+	for (const repositoryDir of repositoryDirs) {
+		const dir = join(AUDIT_DIR, repositoryDir);
+		let files: string[];
+		try {
+			files = (await readdir(dir)).filter((name) => name.endsWith(".jsonl"));
+		} catch {
+			continue;
+		}
+
+		for (const file of files) {
+			const events = parseAuditLines(await readFile(join(dir, file), "utf-8"));
+			for (const event of events) {
+				const result = localDb
+					.insert(auditEvents)
+					.values({ ...event, data: event.data ?? null })
+					.onConflictDoNothing()
+					.run();
+				indexed += result.changes;
+			}
+		}
+	}
+
+	console.log(`[audit] Rebuilt index: ${indexed} events added`);
+	return { indexed };
+}
+
+/**
+ * Writes events recorded elsewhere (e.g. a workspace bundle) into the audit
+ * files and index. Events whose id is already indexed are skipped, so
+ * importing the same history twice doesn't duplicate it.
+ */
+export async function importAuditEvents(events: AuditEvent[]): Promise<{ imported: number }> {
+	const linesByFile = new Map<string, string[]>();
+	for (const event of events) {
+		const result = localDb
+			.insert(auditEvents)
+			.values({ ...event, data: event.data ?? null })
+			.onConflictDoNothing()
+			.run();
+		if (result.changes === 0) continue;
+
+		const file = join(
+			getRepositoryAuditDir(event.repositoryId),
+			`${toFileName(event.nodeId ?? REPOSITORY_LOG_NAME)}.jsonl`,
+		);
+		const lines = linesByFile.get(file) ?? [];
+		lines.push(JSON.stringify(event));
+		linesByFile.set(file, lines);
+	}
+
+	let imported = 0;
+	for (const [file, lines] of linesByFile) {
+		await mkdir(dirname(file), { recursive: true });
+		await appendFile(file, `${lines.join("\n")}\n`, "utf-8");
+		imported += lines.length;
+	}
+
+	return { imported };
+}
+
+/**
+ * Condition for events strictly older than a page cursor. Ties on timestamp fall
+ * back to id so events recorded in the same millisecond aren't skipped between pages.
+ */
+function olderThanCursor(cursor: string | null | undefined): SQL | undefined {
+	const after = cursor ? decodeActivityCursor(cursor) : null;
+	if (!after) return undefined;
+	return or(
+		lt(auditEvents.timestamp, after.timestamp),
+		and(eq(auditEvents.timestamp, after.timestamp), lt(auditEvents.id, after.id)),
+	);
+}
+
+/**
+ * Most recent events first from the audit index. Omitting repositoryId
+ * queries across all repositories; pass `nextCursor` back as `cursor` for older events.
+ */
+export function queryAuditEvents({
+	repositoryId,
+	nodeId,
+	types,
+	cursor,
+	limit,
+}: {
+	repositoryId?: string;
+	nodeId?: string;
+	types?: AuditEventType[];
+	cursor?: string | null;
+	limit: number;
+}): { items: SelectAuditEvent[]; nextCursor: string | null } {
+	const conditions: SQL[] = [];
+	if (repositoryId) conditions.push(eq(auditEvents.repositoryId, repositoryId));
+	if (nodeId) conditions.push(eq(auditEvents.nodeId, nodeId));
+	if (types?.length) conditions.push(inArray(auditEvents.type, types));
+	const older = olderThanCursor(cursor);
+	if (older) conditions.push(older);
+
+	const rows = localDb
+		.select()
+		.from(auditEvents)
+		.where(conditions.length > 0 ? and(...conditions) : undefined)
+		.orderBy(desc(auditEvents.timestamp), desc(auditEvents.id))
+		.limit(limit + 1)
+		.all();
+
+	const items = rows.slice(0, limit);
+	const last = items.at(-1);
+
+	return {
+		items,
+		nextCursor: rows.length > limit && last ? encodeActivityCursor(last) : null,
+	};
+}
+
+/**
+ * Events across every repository, newest first, with repository and node
+ * names for display.
+ */
+export function queryGlobalActivity({
+	types,
+	cursor,
+	limit,
+}: {
+	types: readonly AuditEventType[];
+	cursor?: string | null;
+	limit: number;
+}): ActivityPage {
+	const conditions: SQL[] = [inArray(auditEvents.type, [...types])];
+	const older = olderThanCursor(cursor);
+	if (older) conditions.push(older);
+
+	const rows = localDb
+		.select({
+			event: auditEvents,
+			repositoryName: repositories.name,
+			nodeName: nodes.name,
+		})
+		.from(auditEvents)
+		.leftJoin(repositories, eq(repositories.id, auditEvents.repositoryId))
+		.leftJoin(nodes, eq(nodes.id, auditEvents.nodeId))
+		.where(and(...conditions))
+		.orderBy(desc(auditEvents.timestamp), desc(auditEvents.id))
+		.limit(limit + 1)
+		.all();
+
+	const items = rows.slice(0, limit).map(({ event, repositoryName, nodeName }) => ({
+		...event,
+		data: event.data ?? undefined,
+		repositoryName,
+		nodeName,
+	}));
+	const last = items.at(-1);
+
+	return {
+		items,
+		nextCursor: rows.length > limit && last ? encodeActivityCursor(last) : null,
+	};
+}
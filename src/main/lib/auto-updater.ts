@@ -1,10 +1,19 @@
 import { EventEmitter } from "node:events";
 import { app, dialog } from "electron";
 import { autoUpdater } from "electron-updater";
+import { settings } from "lib/local-db";
 import { setSkipQuitConfirmation } from "main/index";
 import { prerelease } from "semver";
-import { AUTO_UPDATE_STATUS, type AutoUpdateStatus } from "shared/auto-update";
+import {
+	AUTO_UPDATE_STATUS,
+	type AutoUpdateStatus,
+	type UpdateChannel,
+	type UpdateCheckResult,
+} from "shared/auto-update";
 import { PLATFORM } from "shared/constants";
+import { t } from "./i18n";
+import { localDb } from "./local-db";
+import { isLocalOnlyMode } from "./privacy";
 
 const isDev = !app.isPackaged;
 
@@ -26,9 +35,16 @@ const IS_PRERELEASE = isPrereleaseBuild();
 // Use explicit feed URLs to ensure we always fetch latest-mac.yml from the correct release
 // - Stable: fetches from /releases/latest/download/ (latest non-prerelease)
 // - Canary: fetches from /releases/download/desktop-canary/ (rolling canary tag)
-const UPDATE_FEED_URL = IS_PRERELEASE
-	? "https://github.com/TheCaspianAI/Caspian/releases/download/desktop-canary"
-	: "https://github.com/TheCaspianAI/Caspian/releases/latest/download";
+const UPDATE_FEED_URLS: Record<UpdateChannel, string> = {
+	stable: "https://github.com/TheCaspianAI/Caspian/releases/latest/download",
+	canary: "https://github.com/TheCaspianAI/Caspian/releases/download/desktop-canary",
+};
+
+/** The channel chosen in settings, or the installed build's own channel */
+export function getUpdateChannel(): UpdateChannel {
+	const row = localDb.select().from(settings).get();
+	return row?.updateChannel ?? (IS_PRERELEASE ? "canary" : "stable");
+}
 
 export interface AutoUpdateStatusEvent {
 	status: AutoUpdateStatus;
@@ -97,19 +113,72 @@ export function dismissUpdate(): void {
 }
 
 export function checkForUpdates(): void {
+	void checkForUpdatesNow();
+}
+
+/**
+ * Checks the selected channel and reports the version it offers. Like the
+ * periodic check, an available update starts downloading right away.
+ */
+export async function checkForUpdatesNow(): Promise<UpdateCheckResult> {
+	const channel = getUpdateChannel();
+	const currentVersion = app.getVersion();
 	if (isDev || !PLATFORM.IS_MAC) {
-		return;
+		return {
+			channel,
+			currentVersion,
+			availableVersion: null,
+			error: "Updates are only available in packaged macOS builds",
+		};
+	}
+	if (isLocalOnlyMode()) {
+		return {
+			channel,
+			currentVersion,
+			availableVersion: null,
+			error: "Update checks are off in local-only mode",
+		};
 	}
+
 	isDismissed = false;
 	emitStatus(AUTO_UPDATE_STATUS.CHECKING);
-	autoUpdater.checkForUpdates().catch((error) => {
-		if (isNetworkError(error)) {
+	try {
+		const result = await autoUpdater.checkForUpdates();
+		const version = result?.updateInfo.version;
+		return {
+			channel,
+			currentVersion,
+			availableVersion: version && version !== currentVersion ? version : null,
+		};
+	} catch (error) {
+		const message = error instanceof Error ? error.message : String(error);
+		if (isNetworkError(message)) {
 			console.info("[auto-updater] Network unavailable, will retry later");
 			emitStatus(AUTO_UPDATE_STATUS.IDLE);
-			return;
+		} else {
+			console.error("[auto-updater] Failed to check for updates:", error);
+			emitStatus(AUTO_UPDATE_STATUS.ERROR, undefined, message);
 		}
-		console.error("[auto-updater] Failed to check for updates:", error);
-		emitStatus(AUTO_UPDATE_STATUS.ERROR, undefined, error.message);
+		return { channel, currentVersion, availableVersion: null, error: message };
+	}
+}
+
+/** Points the updater at a newly selected channel and checks it */
+export function applyUpdateChannel(channel: UpdateChannel): void {
+	if (isDev || !PLATFORM.IS_MAC) {
+		return;
+	}
+	console.info(`[auto-updater] Switched to the ${channel} channel`);
+	setFeedUrl(channel);
+	checkForUpdates();
+}
+
+function setFeedUrl(channel: UpdateChannel): void {
+	// Use generic provider with explicit feed URL
+	// This ensures we always fetch latest-mac.yml from the correct GitHub release
+	autoUpdater.setFeedURL({
+		provider: "generic",
+		url: UPDATE_FEED_URLS[channel],
 	});
 }
 
@@ -117,16 +186,25 @@ export function checkForUpdatesInteractive(): void {
 	if (isDev) {
 		dialog.showMessageBox({
 			type: "info",
-			title: "Updates",
-			message: "Auto-updates are disabled in development mode.",
+			title: t("updates.title"),
+			message: t("updates.disabledInDev"),
 		});
 		return;
 	}
 	if (!PLATFORM.IS_MAC) {
 		dialog.showMessageBox({
 			type: "info",
-			title: "Updates",
-			message: "Auto-updates are only available on macOS.",
+			title: t("updates.title"),
+			message: t("updates.macOnly"),
+		});
+		return;
+	}
+	if (isLocalOnlyMode()) {
+		dialog.showMessageBox({
+			type: "info",
+			title: t("updates.title"),
+			message: t("updates.localOnly"),
+			detail: t("updates.localOnlyDetail"),
 		});
 		return;
 	}
@@ -141,9 +219,9 @@ export function checkForUpdatesInteractive(): void {
 				emitStatus(AUTO_UPDATE_STATUS.IDLE);
 				dialog.showMessageBox({
 					type: "info",
-					title: "No Updates",
-					message: "You're up to date!",
-					detail: `Version ${app.getVersion()} is the latest version.`,
+					title: t("updates.upToDateTitle"),
+					message: t("updates.upToDate"),
+					detail: t("updates.upToDateDetail", { version: app.getVersion() }),
 				});
 			}
 		})
@@ -153,8 +231,8 @@ export function checkForUpdatesInteractive(): void {
 				emitStatus(AUTO_UPDATE_STATUS.IDLE);
 				dialog.showMessageBox({
 					type: "info",
-					title: "No Internet Connection",
-					message: "Unable to check for updates. Please check your internet connection.",
+					title: t("updates.offlineTitle"),
+					message: t("updates.offline"),
 				});
 				return;
 			}
@@ -162,8 +240,8 @@ export function checkForUpdatesInteractive(): void {
 			emitStatus(AUTO_UPDATE_STATUS.ERROR, undefined, error.message);
 			dialog.showMessageBox({
 				type: "error",
-				title: "Update Error",
-				message: "Failed to check for updates. Please try again later.",
+				title: t("updates.errorTitle"),
+				message: t("updates.error"),
 			});
 		});
 }
@@ -197,12 +275,7 @@ export function setupAutoUpdater(): void {
 	// Allow downgrade for prerelease builds so users can switch back to stable
 	autoUpdater.allowDowngrade = IS_PRERELEASE;
 
-	// Use generic provider with explicit feed URL
-	// This ensures we always fetch latest-mac.yml from the correct GitHub release
-	autoUpdater.setFeedURL({
-		provider: "generic",
-		url: UPDATE_FEED_URL,
-	});
+	setFeedUrl(getUpdateChannel());
 
 	autoUpdater.on("error", (error) => {
 		if (isNetworkError(error)) {
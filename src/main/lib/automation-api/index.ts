@@ -0,0 +1,153 @@
+import { randomBytes, timingSafeEqual } from "node:crypto";
+import type { EventEmitter } from "node:events";
+import { existsSync } from "node:fs";
+import { eq, isNull } from "drizzle-orm";
+import express, { type NextFunction, type Request, type Response } from "express";
+import { nodes, repositories, settings, worktrees } from "lib/local-db";
+import { NOTIFICATION_EVENTS } from "shared/constants";
+import type { OpenTerminalRequest } from "shared/notification-types";
+import { z } from "zod";
+import { localDb } from "../local-db";
+import { getSecret, storeSecret } from "../secrets/store";
+
+/**
+ * Optional HTTP API for scripts and other tools (Raycast, editor
+ * extensions), served under /api/v1 on the local hooks server. Off by
+ * default; every request needs `Authorization: Bearer <token>`. Events are
+ * streamed with Server-Sent Events from /api/v1/events.
+ *
+ * Browsers can't send the Authorization header cross-origin here, since
+ * the server doesn't allow it in CORS preflights, so web pages can't use it.
+ */
+
+const SpawnAgentSchema = z.object({ command: z.string().trim().min(1) });
+
+/** Events forwarded to /events subscribers */
+const STREAMED_EVENTS = [NOTIFICATION_EVENTS.AGENT_LIFECYCLE, NOTIFICATION_EVENTS.TERMINAL_EXIT];
+
+const HEARTBEAT_INTERVAL_MS = 30_000;
+
+export function isAutomationApiEnabled(): boolean {
+	return localDb.select().from(settings).get()?.automationApiEnabled ?? false;
+}
+
+export function getAutomationApiToken(): string | null {
+	return getSecret("automation-api-token");
+}
+
+/** Creates a new token, invalidating the old one; throws without a keychain */
+export function regenerateAutomationApiToken(): string {
+	const token = randomBytes(32).toString("base64url");
+	storeSecret("automation-api-token", token);
+	return token;
+}
+
+export function tokensMatch(expected: string, received: string): boolean {
+	const a = Buffer.from(expected);
+	const b = Buffer.from(received);
+	return a.length === b.length && timingSafeEqual(a, b);
+}
+
+function requireToken(req: Request, res: Response, next: NextFunction): void {
+	if (!isAutomationApiEnabled()) {
+		res.status(404).json({ error: "Not found" });
+		return;
+	}
+
+	const token = getAutomationApiToken();
+	const header = req.header("authorization") ?? "";
+	const received = header.startsWith("Bearer ") ? header.slice("Bearer ".length) : "";
+	if (!token || !received || !tokensMatch(token, received)) {
+		res.status(401).json({ error: "Invalid or missing API token" });
+		return;
+	}
+	next();
+}
+
+function listNodes() {
+	return localDb
+		.select({
+			id: nodes.id,
+			name: nodes.name,
+			type: nodes.type,
+			branch: nodes.branch,
+			repositoryId: nodes.repositoryId,
+			repositoryName: repositories.name,
+			mainRepoPath: repositories.mainRepoPath,
+			worktreePath: worktrees.path,
+			isUnread: nodes.isUnread,
+			lastOpenedAt: nodes.lastOpenedAt,
+		})
+		.from(nodes)
+		.innerJoin(repositories, eq(nodes.repositoryId, repositories.id))
+		.leftJoin(worktrees, eq(nodes.worktreeId, worktrees.id))
+		.where(isNull(nodes.deletingAt))
+		.all()
+		.map(({ mainRepoPath, worktreePath, ...node }) => ({
+			...node,
+			path: node.type === "branch" ? mainRepoPath : worktreePath,
+		}));
+}
+
+/** Routes for the automation API; lifecycle events are read from the hooks server's emitter */
+export function createAutomationApiRouter(events: EventEmitter): express.Router {
+	const api = express.Router();
+	api.use(requireToken);
+
+	api.get("/nodes", (_req, res) => {
+		res.json({ nodes: listNodes() });
+	});
+
+	api.get("/nodes/:id", (req, res) => {
+		const node = listNodes().find((candidate) => candidate.id === req.params.id);
+		if (!node) {
+			return res.status(404).json({ error: "Node not found" });
+		}
+		res.json({ node });
+	});
+
+	// Opens a terminal tab in the node running the command, like starting an agent by hand
+	api.post("/nodes/:id/agents", (req, res) => {
+		const parsed = SpawnAgentSchema.safeParse(req.body);
+		if (!parsed.success) {
+			return res.status(400).json({ error: "Expected a JSON body with a command" });
+		}
+
+		const node = listNodes().find((candidate) => candidate.id === req.params.id);
+		if (!node?.path || !existsSync(node.path)) {
+			return res.status(404).json({ error: "Node not found" });
+		}
+
+		events.emit(NOTIFICATION_EVENTS.OPEN_TERMINAL, {
+			nodeId: node.id,
+			command: parsed.data.command,
+		} satisfies OpenTerminalRequest);
+		res.status(202).json({ accepted: true, nodeId: node.id });
+	});
+
+	api.get("/events", (req, res) => {
+		res.setHeader("Content-Type", "text/event-stream");
+		res.setHeader("Cache-Control", "no-cache");
+		res.setHeader("Connection", "keep-alive");
+		res.flushHeaders();
+
+		const listeners = STREAMED_EVENTS.map((type) => {
+			const listener = (data: unknown) => {
+				res.write(`event: ${type}\ndata: ${JSON.stringify(data)}\n\n`);
+			};
+			events.on(type, listener);
+			return { type, listener };
+		});
+		// Comment lines keep proxies and idle timeouts from closing the stream
+		const heartbeat = setInterval(() => res.write(": ping\n\n"), HEARTBEAT_INTERVAL_MS);
+
+		req.on("close", () => {
+			clearInterval(heartbeat);
+			for (const { type, listener } of listeners) {
+				events.off(type, listener);
+			}
+		});
+	});
+
+	return api;
+}
@@ -0,0 +1,104 @@
+import { describe, expect, it } from "bun:test";
+import { classifyFileEvents, diffStatusSnapshots, parsePorcelainStatus } from "./file-events";
+
+function state(exists: string[], tracked: string[] = []) {
+	return { exists: new Set(exists), tracked: new Set(tracked) };
+}
+
+describe("classifyFileEvents", () => {
+	it("reports content writes as modifications", () => {
+		const changes = classifyFileEvents(
+			[
+				{ type: "change", path: "src/a.ts" },
+				{ type: "change", path: "src/a.ts" },
+			],
+			state(["src/a.ts"], ["src/a.ts"]),
+		);
+		expect(changes).toEqual([{ path: "src/a.ts", kind: "modified", tracked: true }]);
+	});
+
+	it("reports new untracked files as created", () => {
+		const changes = classifyFileEvents(
+			[
+				{ type: "rename", path: "notes.md" },
+				{ type: "change", path: "notes.md" },
+			],
+			state(["notes.md"]),
+		);
+		expect(changes).toEqual([{ path: "notes.md", kind: "created", tracked: false }]);
+	});
+
+	it("treats an atomic save over a tracked file as a modification", () => {
+		const changes = classifyFileEvents(
+			[{ type: "rename", path: "package.json" }],
+			state(["package.json"], ["package.json"]),
+		);
+		expect(changes).toEqual([{ path: "package.json", kind: "modified", tracked: true }]);
+	});
+
+	it("reports paths that no longer exist as deleted", () => {
+		const changes = classifyFileEvents(
+			[{ type: "rename", path: "old.txt" }],
+			state([], ["old.txt"]),
+		);
+		expect(changes).toEqual([{ path: "old.txt", kind: "deleted", tracked: true }]);
+	});
+
+	it("pairs a deletion followed by a creation into a rename", () => {
+		const changes = classifyFileEvents(
+			[
+				{ type: "change", path: "src/b.ts" },
+				{ type: "rename", path: "src/a.ts" },
+				{ type: "rename", path: "src/renamed.ts" },
+			],
+			state(["src/b.ts", "src/renamed.ts"], ["src/a.ts", "src/b.ts"]),
+		);
+		expect(changes).toEqual([
+			{ path: "src/b.ts", kind: "modified", tracked: true },
+			{ path: "src/renamed.ts", oldPath: "src/a.ts", kind: "renamed", tracked: true },
+		]);
+	});
+
+	it("doesn't pair non-adjacent deletions and creations", () => {
+		const changes = classifyFileEvents(
+			[
+				{ type: "rename", path: "gone.txt" },
+				{ type: "change", path: "other.txt" },
+				{ type: "rename", path: "new.txt" },
+			],
+			state(["other.txt", "new.txt"], ["gone.txt", "other.txt"]),
+		);
+		expect(changes.map((change) => change.kind)).toEqual(["deleted", "modified", "created"]);
+	});
+});
+
+describe("parsePorcelainStatus", () => {
+	it("parses entries and rename sources", () => {
+		const entries = parsePorcelainStatus(" M src/a.ts\0R  new.ts\0old.ts\0?? notes.md\0");
+		expect([...entries]).toEqual([
+			["src/a.ts", { code: " M" }],
+			["new.ts", { code: "R ", oldPath: "old.ts" }],
+			["notes.md", { code: "??" }],
+		]);
+	});
+});
+
+describe("diffStatusSnapshots", () => {
+	it("reports entries whose status changed", () => {
+		const previous = parsePorcelainStatus(" M a.ts\0");
+		const next = parsePorcelainStatus(" M a.ts\0?? b.ts\0 D c.ts\0A  d.ts\0");
+		expect(diffStatusSnapshots(previous, next, new Set())).toEqual([
+			{ path: "b.ts", kind: "created", tracked: false },
+			{ path: "c.ts", kind: "deleted", tracked: true },
+			{ path: "d.ts", kind: "created", tracked: true },
+		]);
+	});
+
+	it("reports paths that left the status by whether they still exist", () => {
+		const previous = parsePorcelainStatus(" M a.ts\0?? b.ts\0");
+		expect(diffStatusSnapshots(previous, new Map(), new Set(["a.ts"]))).toEqual([
+			{ path: "a.ts", kind: "modified", tracked: true },
+			{ path: "b.ts", kind: "deleted", tracked: false },
+		]);
+	});
+});
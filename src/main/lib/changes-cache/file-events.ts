@@ -0,0 +1,131 @@
+import type { FileChange } from "shared/changes-types";
+
+/** An fs.watch event: "rename" for create/delete/move, "change" for content writes */
+export interface RawFileEvent {
+	type: "rename" | "change";
+	path: string;
+}
+
+/**
+ * Collapses a burst of raw watcher events into one change per path.
+ * fs.watch doesn't say what a "rename" was, so the outcome is inferred
+ * from whether the path exists now and whether git tracks it: a tracked
+ * path replaced via rename (atomic saves) is a modification, not a creation.
+ * A deletion immediately followed by a creation is reported as a rename.
+ */
+export function classifyFileEvents(
+	events: RawFileEvent[],
+	{ exists, tracked }: { exists: Set<string>; tracked: Set<string> },
+): FileChange[] {
+	const sawRename = new Map<string, boolean>();
+	for (const event of events) {
+		sawRename.set(event.path, (sawRename.get(event.path) ?? false) || event.type === "rename");
+	}
+
+	const changes = new Map<string, FileChange>();
+	for (const [path, renamed] of sawRename) {
+		const isTracked = tracked.has(path);
+		const kind = !exists.has(path)
+			? "deleted"
+			: renamed && !isTracked
+				? "created"
+				: "modified";
+		changes.set(path, { path, kind, tracked: isTracked });
+	}
+
+	// Moves arrive as two adjacent rename events: old path, then new path
+	for (let i = 0; i + 1 < events.length; i++) {
+		const from = changes.get(events[i].path);
+		const to = changes.get(events[i + 1].path);
+		if (
+			!from ||
+			!to ||
+			from === to ||
+			events[i].type !== "rename" ||
+			events[i + 1].type !== "rename" ||
+			from.kind !== "deleted" ||
+			to.kind !== "created"
+		) {
+			continue;
+		}
+
+		changes.delete(from.path);
+		changes.set(to.path, {
+			path: to.path,
+			oldPath: from.path,
+			kind: "renamed",
+			tracked: from.tracked,
+		});
+		i++;
+	}
+
+	return [...changes.values()];
+}
+
+export interface StatusEntry {
+	/** Two-letter porcelain code, e.g. " M", "A ", "??" */
+	code: string;
+	oldPath?: string;
+}
+
+/** Parses `git status --porcelain=v1 -z` into entries keyed by path */
+export function parsePorcelainStatus(output: string): Map<string, StatusEntry> {
+	const entries = new Map<string, StatusEntry>();
+	const fields = output.split("\0");
+	for (let i = 0; i < fields.length; i++) {
+		const field = fields[i];
+		if (field.length < 4) continue;
+
+		const code = field.slice(0, 2);
+		const path = field.slice(3);
+		if (code.includes("R") || code.includes("C")) {
+			// Renames and copies are followed by the original path
+			entries.set(path, { code, oldPath: fields[i + 1] });
+			i++;
+		} else {
+			entries.set(path, { code });
+		}
+	}
+	return entries;
+}
+
+/**
+ * Derives file changes from two git status snapshots, for worktrees that
+ * are polled instead of watched. Edits to a file that was already modified
+ * don't change its status, so they aren't seen here.
+ */
+export function diffStatusSnapshots(
+	previous: Map<string, StatusEntry>,
+	next: Map<string, StatusEntry>,
+	exists: Set<string>,
+): FileChange[] {
+	const changes: FileChange[] = [];
+
+	for (const [path, entry] of next) {
+		const before = previous.get(path);
+		if (before && before.code === entry.code && before.oldPath === entry.oldPath) continue;
+
+		if (entry.code === "??") {
+			changes.push({ path, kind: "created", tracked: false });
+		} else if (entry.code.includes("D")) {
+			changes.push({ path, kind: "deleted", tracked: true });
+		} else if (entry.oldPath && entry.code.includes("R")) {
+			changes.push({ path, oldPath: entry.oldPath, kind: "renamed", tracked: true });
+		} else {
+			const kind = entry.code.includes("A") ? "created" : "modified";
+			changes.push({ path, kind, tracked: true });
+		}
+	}
+
+	// Paths that dropped out of the status were reverted, committed or removed
+	for (const [path, entry] of previous) {
+		if (next.has(path)) continue;
+		changes.push({
+			path,
+			kind: exists.has(path) ? "modified" : "deleted",
+			tracked: entry.code !== "??",
+		});
+	}
+
+	return changes;
+}
@@ -0,0 +1,59 @@
+import { describe, expect, it } from "bun:test";
+import { createIgnoreMatcher, parseIgnoreRules } from "./ignore-rules";
+
+describe("parseIgnoreRules", () => {
+	it("skips comments and blank lines", () => {
+		expect(parseIgnoreRules("# comment\n\n   \n*.log\n")).toHaveLength(1);
+	});
+});
+
+describe("createIgnoreMatcher", () => {
+	const isIgnored = createIgnoreMatcher([
+		["venv/", "*.log", "!keep.log", "/dist", "coverage/", "build/**/tmp", "\\#notes"].join("\n"),
+	]);
+
+	it("always ignores git metadata and node_modules", () => {
+		const none = createIgnoreMatcher([]);
+		expect(none(".git/index")).toBe(true);
+		expect(none("packages/app/node_modules/react/index.js")).toBe(true);
+		expect(none("src/index.ts")).toBe(false);
+	});
+
+	it("matches unanchored patterns at any depth", () => {
+		expect(isIgnored("venv/lib/site.py")).toBe(true);
+		expect(isIgnored("services/api/venv/bin/python")).toBe(true);
+		expect(isIgnored("logs/server.log")).toBe(true);
+	});
+
+	it("anchors patterns containing a slash to the root", () => {
+		expect(isIgnored("dist/main.js")).toBe(true);
+		expect(isIgnored("src/dist/main.js")).toBe(false);
+	});
+
+	it("applies directory-only patterns to contents, not a file of that name", () => {
+		expect(isIgnored("coverage/lcov.info")).toBe(true);
+		expect(isIgnored("coverage")).toBe(false);
+	});
+
+	it("supports ** and escaped characters", () => {
+		expect(isIgnored("build/tmp")).toBe(true);
+		expect(isIgnored("build/a/b/tmp")).toBe(true);
+		expect(isIgnored("#notes")).toBe(true);
+	});
+
+	it("lets negations re-include files but not contents of ignored directories", () => {
+		expect(isIgnored("keep.log")).toBe(false);
+		const matcher = createIgnoreMatcher(["venv/\n!venv/keep.txt"]);
+		expect(matcher("venv/keep.txt")).toBe(true);
+	});
+
+	it("gives later sources precedence", () => {
+		const matcher = createIgnoreMatcher(["*.generated.ts", "!api.generated.ts"]);
+		expect(matcher("src/api.generated.ts")).toBe(false);
+		expect(matcher("src/db.generated.ts")).toBe(true);
+	});
+
+	it("accepts Windows separators", () => {
+		expect(isIgnored("services\\api\\venv\\lib.py")).toBe(true);
+	});
+});
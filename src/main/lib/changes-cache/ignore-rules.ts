@@ -0,0 +1,119 @@
+/**
+ * Minimal .gitignore matcher for filtering watcher events. Supports comments,
+ * negation, directory-only and anchored patterns, and the *, ** and ?
+ * wildcards. Only the files passed in are read; nested .gitignore files in
+ * subdirectories are not.
+ */
+
+interface IgnoreRule {
+	regex: RegExp;
+	negate: boolean;
+	dirOnly: boolean;
+}
+
+/** Per-repository rules on top of .gitignore, same syntax */
+export const CASPIAN_IGNORE_FILE = ".caspianignore";
+
+/** Always ignored: git metadata is tracked through the HEAD/index stamp instead */
+const BUILT_IN_RULES = [".git/", "node_modules/"];
+
+function globToRegexSource(glob: string): string {
+	let source = "";
+	for (let i = 0; i < glob.length; i++) {
+		const char = glob[i];
+		if (char === "*") {
+			if (glob[i + 1] === "*") {
+				if (glob[i + 2] === "/") {
+					// "**/" matches zero or more leading directories
+					source += "(?:.*/)?";
+					i += 2;
+				} else {
+					source += ".*";
+					i += 1;
+				}
+			} else {
+				source += "[^/]*";
+			}
+		} else if (char === "?") {
+			source += "[^/]";
+		} else if (char === "[") {
+			const end = glob.indexOf("]", i + 1);
+			if (end === -1) {
+				source += "\\[";
+			} else {
+				const body = glob.slice(i + 1, end).replace(/^!/, "^").replace(/\\/g, "\\\\");
+				source += `[${body}]`;
+				i = end;
+			}
+		} else if (char === "\\" && i + 1 < glob.length) {
+			source += glob[i + 1].replace(/[.*+?^${}()|[\]\\/]/g, "\\$&");
+			i += 1;
+		} else {
+			source += char.replace(/[.*+?^${}()|[\]\\/]/g, "\\$&");
+		}
+	}
+	return source;
+}
+
+function parseRule(line: string): IgnoreRule | null {
+	let pattern = line.replace(/(?<!\\)\s+$/, "");
+	if (!pattern || pattern.startsWith("#")) return null;
+
+	const negate = pattern.startsWith("!");
+	if (negate) pattern = pattern.slice(1);
+	if (pattern.startsWith("\\!") || pattern.startsWith("\\#")) pattern = pattern.slice(1);
+
+	const dirOnly = pattern.endsWith("/");
+	if (dirOnly) pattern = pattern.replace(/\/+$/, "");
+	if (!pattern) return null;
+
+	// A slash anywhere but the end anchors the pattern to the ignore file's directory
+	const anchored = pattern.includes("/");
+	pattern = pattern.replace(/^\//, "");
+
+	const source = globToRegexSource(pattern);
+	return {
+		regex: new RegExp(anchored ? `^${source}$` : `^(?:.*/)?${source}$`),
+		negate,
+		dirOnly,
+	};
+}
+
+export function parseIgnoreRules(content: string): IgnoreRule[] {
+	return content
+		.split(/\r?\n/)
+		.map(parseRule)
+		.filter((rule): rule is IgnoreRule => rule !== null);
+}
+
+/** null when no rule matched, so a parent's verdict can stand */
+function evaluate(rules: IgnoreRule[], path: string, isDirectory: boolean): boolean | null {
+	let ignored: boolean | null = null;
+	for (const rule of rules) {
+		if (rule.dirOnly && !isDirectory) continue;
+		if (rule.regex.test(path)) {
+			ignored = !rule.negate;
+		}
+	}
+	return ignored;
+}
+
+/**
+ * Builds a predicate over paths relative to the worktree root (either slash
+ * style). Later sources take precedence, like later lines in one file. As in
+ * git, nothing inside an ignored directory can be re-included.
+ */
+export function createIgnoreMatcher(sources: string[]): (relativePath: string) => boolean {
+	const rules = [BUILT_IN_RULES.join("\n"), ...sources].flatMap(parseIgnoreRules);
+
+	return (relativePath) => {
+		const segments = relativePath.replace(/\\/g, "/").split("/").filter(Boolean);
+		for (let i = 1; i <= segments.length; i++) {
+			const isLast = i === segments.length;
+			const verdict = evaluate(rules, segments.slice(0, i).join("/"), !isLast);
+			if (verdict && !isLast) return true;
+			if (isLast) return verdict ?? false;
+		}
+		return false;
+	};
+}
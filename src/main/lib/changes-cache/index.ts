@@ -0,0 +1,450 @@
+import { EventEmitter } from "node:events";
+import { type FSWatcher, readFileSync, watch } from "node:fs";
+import { stat } from "node:fs/promises";
+import { join, resolve } from "node:path";
+import type { FilesChangedEvent, WatchMode, WorktreeWatchStatus } from "shared/changes-types";
+import { isPathInScope } from "shared/utils/scope";
+import simpleGit from "simple-git";
+import {
+	classifyFileEvents,
+	diffStatusSnapshots,
+	parsePorcelainStatus,
+	type RawFileEvent,
+	type StatusEntry,
+} from "./file-events";
+import { CASPIAN_IGNORE_FILE, createIgnoreMatcher } from "./ignore-rules";
+import { StampedCache } from "./stamped-cache";
+
+/** Upper bound on staleness for state the stamp can't see (e.g. remote refs updated elsewhere) */
+const MAX_AGE_MS = 30_000;
+const MAX_ENTRIES = 100;
+/** Stop watching worktrees nobody has asked about for this long */
+const IDLE_WATCH_MS = 5 * 60_000;
+/** Raw events are batched this long before being classified and emitted */
+const FILE_EVENT_DEBOUNCE_MS = 200;
+/** Paths per `git ls-files` call when checking which changed paths are tracked */
+const LS_FILES_CHUNK_SIZE = 200;
+/**
+ * Native watchers kept open at once. Recursive watching costs one inotify
+ * watch per directory on Linux, so large worktrees exhaust the user limit quickly.
+ */
+const MAX_NATIVE_WATCHES = 16;
+/** How often worktrees without a native watcher are checked with `git status` */
+const POLL_INTERVAL_MS = 3_000;
+
+interface WorktreeWatch {
+	mode: WatchMode;
+	/** Null in polling mode */
+	watcher: FSWatcher | null;
+	pollTimer: ReturnType<typeof setInterval> | null;
+	/** Last `git status` seen by the poller, to diff against */
+	lastStatus: Map<string, StatusEntry> | null;
+	polling: boolean;
+	/** Why native watching failed, shown to the user */
+	error: string | null;
+	generation: number;
+	lastUsedAt: number;
+	isIgnored: (relativePath: string) => boolean;
+	/** Subdirectory being watched for scoped nodes; changes elsewhere are ignored */
+	scopePath: string | null;
+	/** Active onFilesChanged subscriptions; watched worktrees with any aren't swept */
+	subscribers: number;
+	/** Started for a node's lifecycle; kept until stopWatching rather than swept when idle */
+	pinned: boolean;
+	pendingEvents: RawFileEvent[];
+	flushTimer: ReturnType<typeof setTimeout> | null;
+}
+
+const IGNORE_FILES = [".gitignore", CASPIAN_IGNORE_FILE];
+
+function readIgnoreFile(path: string): string {
+	try {
+		return readFileSync(path, "utf-8");
+	} catch {
+		return "";
+	}
+}
+
+function loadIgnoreMatcher(worktreePath: string): (relativePath: string) => boolean {
+	return createIgnoreMatcher(IGNORE_FILES.map((name) => readIgnoreFile(join(worktreePath, name))));
+}
+
+/**
+ * Caches git-derived views of a worktree (status, diff stats) so that polling
+ * from node cards and the changes panel doesn't spawn several git processes every tick.
+ *
+ * A cached value is reused while HEAD, the index mtime and the worktree
+ * watcher generation are all unchanged. Changes to paths matched by the
+ * root .gitignore or .caspianignore don't bump the generation, so build
+ * output doesn't keep invalidating the cache.
+ *
+ * At most MAX_NATIVE_WATCHES worktrees get a native watcher; the least
+ * recently used one that isn't pinned or subscribed to is closed to make room. When no
+ * watcher can be freed, or native watching fails (unsupported platform,
+ * watch limit reached), the worktree is polled with `git status` instead.
+ * Polling can't see further edits to an already modified file, so those
+ * are only picked up once MAX_AGE_MS expires.
+ *
+ * The same watcher feeds `subscribe`, which reports classified file changes
+ * (created/modified/deleted/renamed) in debounced batches.
+ */
+class ChangesCache extends EventEmitter {
+	private cache = new StampedCache<unknown>({ maxAgeMs: MAX_AGE_MS, maxEntries: MAX_ENTRIES });
+	private watches = new Map<string, WorktreeWatch>();
+	private scopes = new Map<string, string>();
+	/** Keyed by worktree so a card per node doesn't pile listeners onto one event */
+	private listeners = new Map<string, Set<(event: FilesChangedEvent) => void>>();
+	private sweepTimer: ReturnType<typeof setInterval> | null = null;
+
+	async get<T>(worktreePath: string, variant: string, compute: () => Promise<T>): Promise<T> {
+		const stamp = await this.getStamp(worktreePath);
+		if (!stamp) {
+			return compute();
+		}
+		return this.cache.get(`${worktreePath}\0${variant}`, stamp, compute) as Promise<T>;
+	}
+
+	/** Drops cached values for a worktree, e.g. after fetch/push moved remote refs */
+	invalidate(worktreePath: string): void {
+		this.cache.invalidate(`${worktreePath}\0`);
+	}
+
+	/**
+	 * Calls `listener` with batches of file changes in a worktree until the
+	 * returned function is called.
+	 */
+	subscribe(worktreePath: string, listener: (event: FilesChangedEvent) => void): () => void {
+		const watched = this.ensureWatching(worktreePath);
+
+		// Wrapped so subscribing twice with the same listener is counted twice
+		const handler = (event: FilesChangedEvent) => listener(event);
+		let listeners = this.listeners.get(worktreePath);
+		if (!listeners) {
+			listeners = new Set();
+			this.listeners.set(worktreePath, listeners);
+		}
+		listeners.add(handler);
+		watched.subscribers++;
+
+		return () => {
+			const current = this.listeners.get(worktreePath);
+			current?.delete(handler);
+			if (current?.size === 0) this.listeners.delete(worktreePath);
+			const entry = this.watches.get(worktreePath);
+			if (entry) {
+				entry.subscribers = Math.max(0, entry.subscribers - 1);
+				entry.lastUsedAt = Date.now();
+			}
+		};
+	}
+
+	/**
+	 * Watches a worktree until stopWatching, e.g. once a node's worktree is
+	 * ready. Nodes scoped to a subdirectory only watch that directory; leave
+	 * scopePath out to keep the current scope.
+	 */
+	startWatching(worktreePath: string, scopePath?: string | null): void {
+		if (scopePath !== undefined && (this.scopes.get(worktreePath) ?? null) !== scopePath) {
+			if (scopePath) this.scopes.set(worktreePath, scopePath);
+			else this.scopes.delete(worktreePath);
+			this.rewatch(worktreePath);
+		}
+		this.ensureWatching(worktreePath).pinned = true;
+	}
+
+	/**
+	 * Undoes startWatching. The watcher closes right away unless something
+	 * still subscribes to it, in which case it's swept once they're gone.
+	 */
+	stopWatching(worktreePath: string): void {
+		const entry = this.watches.get(worktreePath);
+		if (!entry) return;
+		entry.pinned = false;
+		if (entry.subscribers === 0) {
+			this.unwatch(worktreePath);
+		}
+	}
+
+	getWatcherStatus(): WorktreeWatchStatus[] {
+		return [...this.watches].map(([worktreePath, entry]) => this.toStatus(worktreePath, entry));
+	}
+
+	dispose(): void {
+		for (const worktreePath of [...this.watches.keys()]) {
+			this.unwatch(worktreePath);
+		}
+		if (this.sweepTimer) {
+			clearInterval(this.sweepTimer);
+			this.sweepTimer = null;
+		}
+	}
+
+	private async getStamp(worktreePath: string): Promise<string | null> {
+		const watched = this.ensureWatching(worktreePath);
+		watched.lastUsedAt = Date.now();
+
+		try {
+			const output = await simpleGit(worktreePath).raw([
+				"rev-parse",
+				"HEAD",
+				"--git-path",
+				"index",
+			]);
+			const [head, indexPath] = output.trim().split("\n");
+			let indexMtime = 0;
+			try {
+				indexMtime = (await stat(resolve(worktreePath, indexPath))).mtimeMs;
+			} catch {
+				// No index yet (fresh repo)
+			}
+			return `${head}:${indexMtime}:${watched.generation}`;
+		} catch {
+			// Unborn HEAD or not a repository - don't cache
+			return null;
+		}
+	}
+
+	private ensureWatching(worktreePath: string): WorktreeWatch {
+		const existing = this.watches.get(worktreePath);
+		if (existing) return existing;
+
+		const entry: WorktreeWatch = {
+			mode: "native",
+			watcher: null,
+			pollTimer: null,
+			lastStatus: null,
+			polling: false,
+			error: null,
+			generation: 0,
+			lastUsedAt: Date.now(),
+			isIgnored: loadIgnoreMatcher(worktreePath),
+			scopePath: this.scopes.get(worktreePath) ?? null,
+			subscribers: 0,
+			pinned: false,
+			pendingEvents: [],
+			flushTimer: null,
+		};
+		this.watches.set(worktreePath, entry);
+
+		if (this.makeRoomForNativeWatch(worktreePath)) {
+			try {
+				entry.watcher = this.watchNatively(worktreePath, entry.scopePath);
+			} catch (error) {
+				this.startPolling(worktreePath, entry, error);
+			}
+		} else {
+			const reason = `More than ${MAX_NATIVE_WATCHES} worktrees in active use`;
+			this.startPolling(worktreePath, entry, reason);
+		}
+		this.startSweep();
+		return entry;
+	}
+
+	/** Closes the least recently used native watcher nobody needs, if at the cap */
+	private makeRoomForNativeWatch(worktreePath: string): boolean {
+		let nativeCount = 0;
+		let oldest: [string, WorktreeWatch] | null = null;
+		for (const [path, entry] of this.watches) {
+			if (entry.mode !== "native" || path === worktreePath) continue;
+			nativeCount++;
+			const evictable = entry.subscribers === 0 && !entry.pinned;
+			if (evictable && (!oldest || entry.lastUsedAt < oldest[1].lastUsedAt)) {
+				oldest = [path, entry];
+			}
+		}
+
+		if (nativeCount < MAX_NATIVE_WATCHES) return true;
+		if (!oldest) return false;
+		this.unwatch(oldest[0]);
+		return true;
+	}
+
+	private watchNatively(worktreePath: string, scopePath: string | null): FSWatcher {
+		const root = scopePath ? join(worktreePath, scopePath) : worktreePath;
+		const watcher = watch(root, { recursive: true }, (eventType, filename) => {
+			const entry = this.watches.get(worktreePath);
+			if (!entry) return;
+
+			// Unknown paths always count as a change
+			const name = filename?.toString().replace(/\\/g, "/");
+			const relativePath = name && scopePath ? `${scopePath}/${name}` : name;
+			if (relativePath && IGNORE_FILES.includes(relativePath)) {
+				entry.isIgnored = loadIgnoreMatcher(worktreePath);
+			} else if (relativePath && entry.isIgnored(relativePath)) {
+				return;
+			}
+			entry.generation++;
+
+			if (entry.subscribers > 0 && relativePath) {
+				this.queueFileEvent(worktreePath, entry, { type: eventType, path: relativePath });
+			}
+		});
+		watcher.on("error", (error) => {
+			const entry = this.watches.get(worktreePath);
+			if (!entry || entry.watcher !== watcher) return;
+			watcher.close();
+			entry.watcher = null;
+			// The worktree itself going away is expected; anything else falls back to polling
+			stat(worktreePath).then(
+				() => this.startPolling(worktreePath, entry, error),
+				() => this.unwatch(worktreePath),
+			);
+		});
+		// Don't keep Electron alive just for file watching
+		watcher.unref();
+		return watcher;
+	}
+
+	private startPolling(worktreePath: string, entry: WorktreeWatch, reason: unknown): void {
+		if (this.watches.get(worktreePath) !== entry || entry.pollTimer) return;
+
+		entry.mode = "polling";
+		entry.error = reason instanceof Error ? reason.message : String(reason);
+		console.warn(`[changes-cache] Cannot watch ${worktreePath}, polling instead:`, reason);
+
+		entry.pollTimer = setInterval(() => {
+			void this.poll(worktreePath, entry);
+		}, POLL_INTERVAL_MS);
+		entry.pollTimer.unref();
+		void this.poll(worktreePath, entry);
+		this.emit("watcher-status", this.toStatus(worktreePath, entry));
+	}
+
+	private async poll(worktreePath: string, entry: WorktreeWatch): Promise<void> {
+		if (entry.polling) return;
+		entry.polling = true;
+		try {
+			const output = await simpleGit(worktreePath).raw(["status", "--porcelain=v1", "-z"]);
+			const next = parsePorcelainStatus(output);
+			const previous = entry.lastStatus;
+			entry.lastStatus = next;
+			if (!previous || this.watches.get(worktreePath) !== entry) return;
+
+			const gone = [...previous.keys()].filter((path) => !next.has(path));
+			const exists = new Set<string>();
+			await Promise.all(
+				gone.map(async (path) => {
+					try {
+						await stat(join(worktreePath, path));
+						exists.add(path);
+					} catch {
+						// Deleted
+					}
+				}),
+			);
+
+			const changes = diffStatusSnapshots(previous, next, exists).filter(
+				(change) => isPathInScope(entry.scopePath, change.path) && !entry.isIgnored(change.path),
+			);
+			if (changes.length === 0) return;
+			entry.generation++;
+			if (changes.some((change) => IGNORE_FILES.includes(change.path))) {
+				entry.isIgnored = loadIgnoreMatcher(worktreePath);
+			}
+			if (entry.subscribers > 0) {
+				this.notify({ worktreePath, changes });
+			}
+		} catch (error) {
+			console.warn(`[changes-cache] Failed to poll ${worktreePath}:`, error);
+		} finally {
+			entry.polling = false;
+		}
+	}
+
+	private toStatus(worktreePath: string, entry: WorktreeWatch): WorktreeWatchStatus {
+		return {
+			worktreePath,
+			mode: entry.mode,
+			subscribers: entry.subscribers,
+			pinned: entry.pinned,
+			error: entry.error,
+		};
+	}
+
+	private queueFileEvent(worktreePath: string, entry: WorktreeWatch, event: RawFileEvent): void {
+		entry.pendingEvents.push(event);
+		if (entry.flushTimer) return;
+
+		entry.flushTimer = setTimeout(() => {
+			entry.flushTimer = null;
+			const events = entry.pendingEvents;
+			entry.pendingEvents = [];
+			void this.emitFileChanges(worktreePath, events).catch((error) => {
+				console.warn(`[changes-cache] Failed to classify changes in ${worktreePath}:`, error);
+			});
+		}, FILE_EVENT_DEBOUNCE_MS);
+	}
+
+	private async emitFileChanges(worktreePath: string, events: RawFileEvent[]): Promise<void> {
+		const paths = [...new Set(events.map((event) => event.path))];
+
+		const exists = new Set<string>();
+		await Promise.all(
+			paths.map(async (path) => {
+				try {
+					await stat(join(worktreePath, path));
+					exists.add(path);
+				} catch {
+					// Deleted or moved away
+				}
+			}),
+		);
+
+		const tracked = new Set<string>();
+		const git = simpleGit(worktreePath);
+		for (let i = 0; i < paths.length; i += LS_FILES_CHUNK_SIZE) {
+			const chunk = paths.slice(i, i + LS_FILES_CHUNK_SIZE);
+			const pathspecs = chunk.map((path) => `:(literal)${path}`);
+			const output = await git.raw(["ls-files", "-z", "--", ...pathspecs]);
+			for (const path of output.split("\0")) {
+				if (path) tracked.add(path);
+			}
+		}
+
+		const changes = classifyFileEvents(events, { exists, tracked });
+		if (changes.length > 0) {
+			this.notify({ worktreePath, changes });
+		}
+	}
+
+	private notify(event: FilesChangedEvent): void {
+		for (const listener of this.listeners.get(event.worktreePath) ?? []) {
+			listener(event);
+		}
+	}
+
+	/** Recreates a worktree's watch, keeping its subscribers and pin */
+	private rewatch(worktreePath: string): void {
+		const entry = this.watches.get(worktreePath);
+		if (!entry) return;
+		this.unwatch(worktreePath);
+		const next = this.ensureWatching(worktreePath);
+		next.subscribers = entry.subscribers;
+		next.pinned = entry.pinned;
+	}
+
+	private unwatch(worktreePath: string): void {
+		const entry = this.watches.get(worktreePath);
+		if (!entry) return;
+		entry.watcher?.close();
+		if (entry.pollTimer) clearInterval(entry.pollTimer);
+		if (entry.flushTimer) clearTimeout(entry.flushTimer);
+		this.watches.delete(worktreePath);
+		this.invalidate(worktreePath);
+	}
+
+	private startSweep(): void {
+		if (this.sweepTimer) return;
+		this.sweepTimer = setInterval(() => {
+			const cutoff = Date.now() - IDLE_WATCH_MS;
+			for (const [worktreePath, entry] of this.watches) {
+				if (entry.subscribers === 0 && !entry.pinned && entry.lastUsedAt < cutoff) {
+					this.unwatch(worktreePath);
+				}
+			}
+		}, IDLE_WATCH_MS);
+		this.sweepTimer.unref();
+	}
+}
+
+export const changesCache = new ChangesCache();
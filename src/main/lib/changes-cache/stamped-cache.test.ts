@@ -0,0 +1,82 @@
+import { describe, expect, it } from "bun:test";
+import { StampedCache } from "./stamped-cache";
+
+function createCache(maxEntries = 10) {
+	let time = 0;
+	const cache = new StampedCache<number>({ maxAgeMs: 1000, maxEntries }, () => time);
+	return {
+		cache,
+		advance: (ms: number) => {
+			time += ms;
+		},
+	};
+}
+
+describe("StampedCache", () => {
+	it("reuses values while the stamp is unchanged", async () => {
+		const { cache } = createCache();
+		let calls = 0;
+		const compute = async () => ++calls;
+
+		expect(await cache.get("a", "s1", compute)).toBe(1);
+		expect(await cache.get("a", "s1", compute)).toBe(1);
+		expect(await cache.get("a", "s2", compute)).toBe(2);
+		expect(calls).toBe(2);
+	});
+
+	it("recomputes after maxAgeMs", async () => {
+		const { cache, advance } = createCache();
+		let calls = 0;
+		const compute = async () => ++calls;
+
+		await cache.get("a", "s1", compute);
+		advance(1000);
+		expect(await cache.get("a", "s1", compute)).toBe(2);
+	});
+
+	it("shares concurrent computations", async () => {
+		const { cache } = createCache();
+		let calls = 0;
+		const compute = async () => ++calls;
+
+		const [first, second] = await Promise.all([
+			cache.get("a", "s1", compute),
+			cache.get("a", "s1", compute),
+		]);
+		expect(first).toBe(1);
+		expect(second).toBe(1);
+		expect(calls).toBe(1);
+	});
+
+	it("does not cache failures", async () => {
+		const { cache } = createCache();
+
+		await expect(
+			cache.get("a", "s1", async () => {
+				throw new Error("boom");
+			}),
+		).rejects.toThrow("boom");
+		expect(await cache.get("a", "s1", async () => 1)).toBe(1);
+	});
+
+	it("invalidates by key prefix", async () => {
+		const { cache } = createCache();
+		await cache.get("/repo\0main", "s1", async () => 1);
+		await cache.get("/other\0main", "s1", async () => 1);
+
+		cache.invalidate("/repo\0");
+		expect(cache.size).toBe(1);
+		expect(await cache.get("/repo\0main", "s1", async () => 2)).toBe(2);
+	});
+
+	it("evicts the least recently used entry", async () => {
+		const { cache } = createCache(2);
+		await cache.get("a", "s", async () => 1);
+		await cache.get("b", "s", async () => 1);
+		await cache.get("a", "s", async () => 1);
+		await cache.get("c", "s", async () => 1);
+
+		expect(await cache.get("a", "s", async () => 2)).toBe(1);
+		expect(await cache.get("b", "s", async () => 2)).toBe(2);
+	});
+});
@@ -0,0 +1,73 @@
+interface Entry<T> {
+	stamp: string;
+	value: T;
+	createdAt: number;
+}
+
+/**
+ * Memoizes async results per key, reusing a value only while the caller-supplied
+ * stamp is unchanged and the entry is younger than maxAgeMs.
+ * Concurrent requests for the same key and stamp share one computation.
+ */
+export class StampedCache<T> {
+	private entries = new Map<string, Entry<T>>();
+	private inFlight = new Map<string, { stamp: string; promise: Promise<T> }>();
+
+	constructor(
+		private readonly options: { maxAgeMs: number; maxEntries: number },
+		private readonly now: () => number = Date.now,
+	) {}
+
+	async get(key: string, stamp: string, compute: () => Promise<T>): Promise<T> {
+		const entry = this.entries.get(key);
+		if (entry && entry.stamp === stamp && this.now() - entry.createdAt < this.options.maxAgeMs) {
+			// Refresh recency for LRU eviction
+			this.entries.delete(key);
+			this.entries.set(key, entry);
+			return entry.value;
+		}
+
+		const pending = this.inFlight.get(key);
+		if (pending && pending.stamp === stamp) {
+			return pending.promise;
+		}
+
+		const createdAt = this.now();
+		const promise = compute();
+		this.inFlight.set(key, { stamp, promise });
+
+		try {
+			const value = await promise;
+			this.set(key, { stamp, value, createdAt });
+			return value;
+		} finally {
+			if (this.inFlight.get(key)?.promise === promise) {
+				this.inFlight.delete(key);
+			}
+		}
+	}
+
+	/** Drops cached values for every key starting with prefix */
+	invalidate(prefix: string): void {
+		for (const key of this.entries.keys()) {
+			if (key.startsWith(prefix)) {
+				this.entries.delete(key);
+			}
+		}
+	}
+
+	get size(): number {
+		return this.entries.size;
+	}
+
+	private set(key: string, entry: Entry<T>): void {
+		this.entries.delete(key);
+		this.entries.set(key, entry);
+
+		while (this.entries.size > this.options.maxEntries) {
+			const oldest = this.entries.keys().next().value;
+			if (oldest === undefined) break;
+			this.entries.delete(oldest);
+		}
+	}
+}
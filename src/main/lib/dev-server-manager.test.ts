@@ -0,0 +1,25 @@
+import { describe, expect, test } from "bun:test";
+import { allocatePort } from "./dev-server-manager";
+
+const allFree = async () => true;
+
+describe("allocatePort", () => {
+	test("starts at the base port", async () => {
+		expect(await allocatePort(3000, new Set(), allFree)).toBe(3000);
+	});
+
+	test("skips ports held by other dev servers", async () => {
+		expect(await allocatePort(3000, new Set([3000, 3001]), allFree)).toBe(3002);
+	});
+
+	test("skips ports something else is listening on", async () => {
+		const isFree = async (port: number) => port !== 3000;
+		expect(await allocatePort(3000, new Set(), isFree)).toBe(3001);
+	});
+
+	test("gives up after a bounded range", async () => {
+		await expect(allocatePort(3000, new Set(), async () => false)).rejects.toThrow(
+			"No free port between 3000 and 3099",
+		);
+	});
+});
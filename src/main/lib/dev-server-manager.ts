@@ -0,0 +1,205 @@
+import { type ChildProcess, spawn } from "node:child_process";
+import { EventEmitter } from "node:events";
+import { createServer } from "node:net";
+import type { DevServerLogLine, DevServerStatus } from "shared/types/dev-server";
+import { treeKillWithEscalation } from "./tree-kill-with-escalation";
+
+const DEFAULT_BASE_PORT = 3000;
+const MAX_PORT_ATTEMPTS = 100;
+/** Lines kept per server for clients that subscribe after startup */
+const MAX_LOG_LINES = 2000;
+
+interface DevServer {
+	status: DevServerStatus;
+	process: ChildProcess | null;
+	logs: DevServerLogLine[];
+}
+
+function isPortFree(port: number): Promise<boolean> {
+	return new Promise((resolve) => {
+		const server = createServer();
+		server.once("error", () => resolve(false));
+		server.once("listening", () => server.close(() => resolve(true)));
+		server.listen(port, "127.0.0.1");
+	});
+}
+
+/**
+ * First port from basePort that no other dev server holds and nothing is
+ * listening on. Ports of exited servers are free again.
+ */
+export async function allocatePort(
+	basePort: number,
+	taken: Set<number>,
+	isFree: (port: number) => Promise<boolean> = isPortFree,
+): Promise<number> {
+	for (let port = basePort; port < basePort + MAX_PORT_ATTEMPTS; port++) {
+		if (!taken.has(port) && (await isFree(port))) {
+			return port;
+		}
+	}
+	throw new Error(`No free port between ${basePort} and ${basePort + MAX_PORT_ATTEMPTS - 1}`);
+}
+
+/**
+ * Runs one dev server per node with:
+ * - A port allocated per server, passed in PORT (or the configured variable)
+ * - Log streaming via EventEmitter, with recent lines buffered
+ * - Process tree cleanup when the server is stopped or its node closes
+ *
+ * Like the quality gate manager, state is in-memory only and resets on restart.
+ */
+class DevServerManager extends EventEmitter {
+	private servers = new Map<string, DevServer>();
+
+	getStatus(nodeId: string): DevServerStatus | undefined {
+		return this.servers.get(nodeId)?.status;
+	}
+
+	getAllStatuses(): DevServerStatus[] {
+		return Array.from(this.servers.values(), (server) => server.status);
+	}
+
+	getLogs(nodeId: string): DevServerLogLine[] {
+		return this.servers.get(nodeId)?.logs ?? [];
+	}
+
+	isRunning(nodeId: string): boolean {
+		const state = this.servers.get(nodeId)?.status.state;
+		return state === "running" || state === "stopping";
+	}
+
+	async start({
+		nodeId,
+		command,
+		cwd,
+		env,
+		portEnv = "PORT",
+		basePort = DEFAULT_BASE_PORT,
+	}: {
+		nodeId: string;
+		command: string;
+		cwd: string;
+		env: Record<string, string>;
+		portEnv?: string;
+		basePort?: number;
+	}): Promise<DevServerStatus> {
+		if (this.isRunning(nodeId)) {
+			throw new Error("A dev server is already running for this node");
+		}
+
+		const taken = new Set(
+			this.getAllStatuses()
+				.filter((status) => this.isRunning(status.nodeId))
+				.map((status) => status.port),
+		);
+		const port = await allocatePort(basePort, taken);
+
+		const child = spawn(command, {
+			cwd,
+			env: { ...env, [portEnv]: String(port) },
+			shell: true,
+			stdio: ["ignore", "pipe", "pipe"],
+		});
+
+		const server: DevServer = {
+			process: child,
+			logs: [],
+			status: {
+				nodeId,
+				command,
+				state: "running",
+				port,
+				url: `http://localhost:${port}`,
+				pid: child.pid ?? null,
+				startedAt: Date.now(),
+				exitCode: null,
+				error: null,
+			},
+		};
+		this.servers.set(nodeId, server);
+		console.log(`[dev-server] Starting "${command}" for node ${nodeId} on port ${port}`);
+
+		this.pipeLines(server, child.stdout, "stdout");
+		this.pipeLines(server, child.stderr, "stderr");
+
+		child.once("error", (error) => {
+			this.updateStatus(server, { state: "failed", error: error.message });
+		});
+		child.once("exit", (code, signal) => {
+			server.process = null;
+			const stopped = server.status.state === "stopping";
+			this.updateStatus(server, {
+				state: stopped || code === 0 ? "exited" : "failed",
+				exitCode: code,
+				pid: null,
+				error: stopped || code === 0 ? null : `Exited with ${code ?? signal}`,
+			});
+		});
+
+		this.emit("status", server.status);
+		return server.status;
+	}
+
+	async stop(nodeId: string): Promise<void> {
+		const server = this.servers.get(nodeId);
+		const pid = server?.process?.pid;
+		if (!server || !pid || server.status.state !== "running") return;
+
+		this.updateStatus(server, { state: "stopping" });
+		const result = await treeKillWithEscalation({ pid });
+		if (!result.success) {
+			console.warn(`[dev-server] Failed to stop node ${nodeId}:`, result.error);
+		}
+	}
+
+	/** Stops the server and forgets it, for nodes that are closed or deleted */
+	async remove(nodeId: string): Promise<void> {
+		await this.stop(nodeId);
+		this.servers.delete(nodeId);
+	}
+
+	stopAll(): Promise<void[]> {
+		return Promise.all(Array.from(this.servers.keys(), (nodeId) => this.stop(nodeId)));
+	}
+
+	private updateStatus(server: DevServer, patch: Partial<DevServerStatus>): void {
+		server.status = { ...server.status, ...patch };
+		this.emit("status", server.status);
+	}
+
+	private pipeLines(
+		server: DevServer,
+		stream: NodeJS.ReadableStream | null,
+		name: DevServerLogLine["stream"],
+	): void {
+		if (!stream) return;
+		let partial = "";
+		stream.setEncoding("utf8");
+		stream.on("data", (chunk: string) => {
+			const lines = (partial + chunk).split(/\r?\n/);
+			partial = lines.pop() ?? "";
+			for (const text of lines) this.appendLog(server, name, text);
+		});
+		stream.on("end", () => {
+			if (partial) this.appendLog(server, name, partial);
+		});
+	}
+
+	private appendLog(server: DevServer, stream: DevServerLogLine["stream"], text: string): void {
+		const line: DevServerLogLine = {
+			nodeId: server.status.nodeId,
+			stream,
+			text,
+			timestamp: Date.now(),
+		};
+		server.logs.push(line);
+		if (server.logs.length > MAX_LOG_LINES) {
+			server.logs.splice(0, server.logs.length - MAX_LOG_LINES);
+		}
+		this.emit("log", line);
+	}
+}
+
+/** Singleton dev server manager instance */
+export const devServerManager = new DevServerManager();
@@ -0,0 +1,54 @@
+import { app } from "electron";
+import { settings } from "lib/local-db";
+import type { MessageKey } from "shared/i18n/messages";
+import {
+	createTranslator,
+	type Locale,
+	type MessageParams,
+	resolveLocale,
+	type Translate,
+	translate,
+} from "shared/i18n/translate";
+import { localDb } from "./local-db";
+
+/**
+ * Locale for strings the main process shows itself (notifications, native
+ * dialogs). Cached like the privacy settings and reloaded through setLocale.
+ */
+let cached: Locale | null = null;
+
+/** The locale chosen in settings, or null to follow the system */
+export function getLocaleSetting(): Locale | null {
+	return localDb.select().from(settings).get()?.locale ?? null;
+}
+
+export function getSystemLocale(): Locale {
+	return resolveLocale(app.getPreferredSystemLanguages());
+}
+
+export function getLocale(): Locale {
+	if (!cached) {
+		cached = getLocaleSetting() ?? getSystemLocale();
+	}
+	return cached;
+}
+
+export function setLocale(locale: Locale | null): Locale {
+	localDb
+		.insert(settings)
+		.values({ id: 1, locale })
+		.onConflictDoUpdate({ target: settings.id, set: { locale } })
+		.run();
+
+	cached = null;
+	return getLocale();
+}
+
+export function t(key: MessageKey, params?: MessageParams): string {
+	return translate(getLocale(), key, params);
+}
+
+/** For pure helpers that take a translator, e.g. the notification digest */
+export function getTranslator(): Translate {
+	return createTranslator(getLocale());
+}
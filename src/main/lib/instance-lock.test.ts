@@ -0,0 +1,92 @@
+import { afterAll, beforeAll, describe, expect, it } from "bun:test";
+import { existsSync, mkdirSync, readFileSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir, uptime } from "node:os";
+import { join } from "node:path";
+import { acquireInstanceLock, releaseInstanceLock } from "./instance-lock";
+
+const TEST_DIR = join(tmpdir(), `caspian-instance-lock-test-${Date.now()}`);
+
+// Far above any real pid_max, so never a running process
+const DEAD_PID = 2 ** 30;
+
+beforeAll(() => {
+	mkdirSync(TEST_DIR, { recursive: true });
+});
+
+afterAll(() => {
+	rmSync(TEST_DIR, { recursive: true, force: true });
+});
+
+describe("acquireInstanceLock", () => {
+	it("creates the lock file with the owner's pid", () => {
+		const lockPath = join(TEST_DIR, "fresh.lock");
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({ acquired: true });
+		expect(readFileSync(lockPath, "utf-8")).toStartWith(`${process.pid} `);
+	});
+
+	it("is re-entrant for the owning pid", () => {
+		const lockPath = join(TEST_DIR, "reentrant.lock");
+		acquireInstanceLock({ lockPath, pid: process.pid });
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({ acquired: true });
+	});
+
+	it("refuses while another live process owns the lock", () => {
+		const lockPath = join(TEST_DIR, "held.lock");
+		acquireInstanceLock({ lockPath, pid: process.pid });
+		expect(acquireInstanceLock({ lockPath, pid: DEAD_PID })).toEqual({
+			acquired: false,
+			ownerPid: process.pid,
+		});
+	});
+
+	it("takes over a lock left by a process that exited", () => {
+		const lockPath = join(TEST_DIR, "stale.lock");
+		writeFileSync(lockPath, String(DEAD_PID));
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({ acquired: true });
+		expect(readFileSync(lockPath, "utf-8")).toStartWith(`${process.pid} `);
+	});
+
+	it("takes over a lock from before the last reboot, even if its pid is in use", () => {
+		const lockPath = join(TEST_DIR, "rebooted.lock");
+		const previousBoot = Date.now() - uptime() * 1000 - 24 * 60 * 60 * 1000;
+		writeFileSync(lockPath, `${process.ppid} ${previousBoot}`);
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({ acquired: true });
+	});
+
+	it("honors a live owner of a lock without a boot time", () => {
+		const lockPath = join(TEST_DIR, "legacy.lock");
+		writeFileSync(lockPath, String(process.ppid));
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({
+			acquired: false,
+			ownerPid: process.ppid,
+		});
+	});
+
+	it("leaves no temp file behind", () => {
+		const lockPath = join(TEST_DIR, "temp.lock");
+		acquireInstanceLock({ lockPath, pid: process.pid });
+		expect(existsSync(`${lockPath}.${process.pid}.tmp`)).toBe(false);
+	});
+
+	it("takes over an unreadable lock file", () => {
+		const lockPath = join(TEST_DIR, "garbage.lock");
+		writeFileSync(lockPath, "not a pid");
+		expect(acquireInstanceLock({ lockPath, pid: process.pid })).toEqual({ acquired: true });
+	});
+});
+
+describe("releaseInstanceLock", () => {
+	it("removes the lock when the pid owns it", () => {
+		const lockPath = join(TEST_DIR, "release.lock");
+		acquireInstanceLock({ lockPath, pid: process.pid });
+		releaseInstanceLock({ lockPath, pid: process.pid });
+		expect(existsSync(lockPath)).toBe(false);
+	});
+
+	it("leaves another process's lock in place", () => {
+		const lockPath = join(TEST_DIR, "foreign.lock");
+		acquireInstanceLock({ lockPath, pid: process.pid });
+		releaseInstanceLock({ lockPath, pid: DEAD_PID });
+		expect(existsSync(lockPath)).toBe(true);
+	});
+});
@@ -0,0 +1,128 @@
+import { linkSync, readFileSync, unlinkSync, writeFileSync } from "node:fs";
+import { uptime } from "node:os";
+
+/**
+ * Electron's single-instance lock is keyed on the userData directory, but
+ * the database, daemon sessions and worktrees live in the Caspian home dir.
+ * Builds with different app names (dev, canary, packaged) pointed at the same
+ * home dir would otherwise both run startup cleanup against the same state.
+ */
+
+/** Boot times computed from uptime drift by a second or so between reads */
+const BOOT_TIME_TOLERANCE_MS = 60_000;
+
+interface LockOwner {
+	pid: number;
+	/** null for locks written before boot times were recorded */
+	bootedAt: number | null;
+}
+
+function getBootTime(): number {
+	return Date.now() - uptime() * 1000;
+}
+
+function isProcessAlive(pid: number): boolean {
+	try {
+		// Signal 0 doesn't actually send a signal, just checks if process exists
+		process.kill(pid, 0);
+		return true;
+	} catch (error) {
+		// EPERM: the process exists but belongs to another user
+		return (error as NodeJS.ErrnoException).code === "EPERM";
+	}
+}
+
+/** Reads "<pid> <boot time>", or the bare pid older versions wrote */
+function readLockOwner(lockPath: string): LockOwner | null {
+	try {
+		const [pidField, bootField] = readFileSync(lockPath, "utf-8").trim().split(/\s+/);
+		const pid = Number(pidField);
+		if (!Number.isInteger(pid) || pid <= 0) return null;
+		const bootedAt = bootField === undefined ? null : Number(bootField);
+		return { pid, bootedAt: Number.isFinite(bootedAt) ? bootedAt : null };
+	} catch {
+		return null;
+	}
+}
+
+/**
+ * PIDs start over after a reboot, so a lock from before one can name an
+ * unrelated process that happens to be running now.
+ */
+function isOwnerRunning(owner: LockOwner): boolean {
+	const rebooted =
+		owner.bootedAt !== null && Math.abs(owner.bootedAt - getBootTime()) > BOOT_TIME_TOLERANCE_MS;
+	return !rebooted && isProcessAlive(owner.pid);
+}
+
+/**
+ * Writes the lock to a temp file and hard-links it into place, so another
+ * instance never sees a created but still empty lock file and mistakes it for stale.
+ */
+function tryCreateLock({ lockPath, pid }: { lockPath: string; pid: number }): boolean {
+	const tempPath = `${lockPath}.${pid}.tmp`;
+	writeFileSync(tempPath, `${pid} ${Math.round(getBootTime())}`);
+	try {
+		linkSync(tempPath, lockPath);
+		return true;
+	} catch (error) {
+		if ((error as NodeJS.ErrnoException).code === "EEXIST") return false;
+		throw error;
+	} finally {
+		unlinkSync(tempPath);
+	}
+}
+
+export type InstanceLockResult = { acquired: true } | { acquired: false; ownerPid: number | null };
+
+/**
+ * Claims the lock file for `pid`. A lock left behind by a process that is no
+ * longer running, or by one from before the last reboot, is taken over.
+ */
+export function acquireInstanceLock({
+	lockPath,
+	pid = process.pid,
+}: {
+	lockPath: string;
+	pid?: number;
+}): InstanceLockResult {
+	for (let attempt = 0; attempt < 2; attempt++) {
+		if (tryCreateLock({ lockPath, pid })) {
+			return { acquired: true };
+		}
+
+		const owner = readLockOwner(lockPath);
+		if (owner?.pid === pid) {
+			return { acquired: true };
+		}
+		if (owner && isOwnerRunning(owner)) {
+			return { acquired: false, ownerPid: owner.pid };
+		}
+
+		console.log(`[instance-lock] Removing stale lock from pid ${owner?.pid ?? "unknown"}`);
+		try {
+			unlinkSync(lockPath);
+		} catch {
+			// Another instance may have removed it first; retry the create
+		}
+	}
+
+	// Lost the race to another instance starting at the same time
+	return { acquired: false, ownerPid: readLockOwner(lockPath)?.pid ?? null };
+}
+
+/** Removes the lock file if `pid` still owns it */
+export function releaseInstanceLock({
+	lockPath,
+	pid = process.pid,
+}: {
+	lockPath: string;
+	pid?: number;
+}): void {
+	if (readLockOwner(lockPath)?.pid !== pid) return;
+	try {
+		unlinkSync(lockPath);
+	} catch {
+		// Already gone
+	}
+}
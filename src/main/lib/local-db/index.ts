@@ -10,6 +10,8 @@ import {
 	CASPIAN_SENSITIVE_FILE_MODE,
 	ensureCaspianHomeDirExists,
 } from "../app-environment";
+import { dbQueryDurationSeconds } from "../metrics/metrics";
+import { backupBeforeMigrating, getAppliedMigrationTag } from "./migration-backup";
 
 const isDev = process.env.NODE_ENV === "development";
 
@@ -57,6 +59,29 @@ function getMigrationsDirectory(): string {
 
 const migrationsFolder = getMigrationsDirectory();
 
+/**
+ * Times every prepared statement execution for the metrics endpoint.
+ * Drizzle's logger only sees SQL text, so latency is measured at the driver.
+ */
+function instrumentStatements(db: Database.Database): void {
+	const prepare = db.prepare.bind(db);
+	db.prepare = ((source: string) => {
+		const statement = prepare(source);
+		for (const operation of ["run", "get", "all"] as const) {
+			const execute = statement[operation].bind(statement) as (...args: unknown[]) => unknown;
+			(statement as unknown as Record<string, unknown>)[operation] = (...args: unknown[]) => {
+				const startedAt = performance.now();
+				try {
+					return execute(...args);
+				} finally {
+					dbQueryDurationSeconds.observe((performance.now() - startedAt) / 1000, { operation });
+				}
+			};
+		}
+		return statement;
+	}) as typeof db.prepare;
+}
+
 const sqlite = new Database(DB_PATH);
 try {
 	chmodSync(DB_PATH, CASPIAN_SENSITIVE_FILE_MODE);
@@ -64,7 +89,13 @@ try {
 	// Best-effort; directory permissions should still protect the DB.
 }
 sqlite.pragma("journal_mode = WAL");
+// Waits out locks held by another process on the same file (e.g. a second
+// instance or an external sqlite3 shell) instead of failing with SQLITE_BUSY
+sqlite.pragma("busy_timeout = 5000");
+// Safe with WAL: a crash can lose the last commits but can't corrupt the database
+sqlite.pragma("synchronous = NORMAL");
 sqlite.pragma("foreign_keys = OFF");
+instrumentStatements(sqlite);
 
 console.log(`[local-db] Database initialized at: ${DB_PATH}`);
 console.log(`[local-db] Running migrations from: ${migrationsFolder}`);
@@ -77,6 +108,14 @@ if (process.env.CASPIAN_EXPOSE_TEST_DB === "1") {
 	(globalThis as Record<string, unknown>).__caspianTestDb = sqlite;
 }
 
+// A failed backup shouldn't keep the app from starting; migrations still run in a transaction
+let backupPath: string | null = null;
+try {
+	backupPath = backupBeforeMigrating({ sqlite, dbPath: DB_PATH, migrationsFolder });
+} catch (error) {
+	console.error("[local-db] Failed to back up database before migrating:", error);
+}
+
 try {
 	migrate(localDb, { migrationsFolder });
 } catch (error) {
@@ -93,10 +132,35 @@ try {
 	if (isSqliteError && isIdempotentMessage) {
 		console.log(`[local-db] Skipped idempotent error: ${sqliteError.message}`);
 	} else {
+		if (backupPath) {
+			console.error(`[local-db] Migration failed; the previous database is at ${backupPath}`);
+		}
 		throw error;
 	}
 }
 
 console.log("[local-db] Migrations complete");
 
+export function getSchemaVersion(): string | null {
+	return getAppliedMigrationTag(sqlite, migrationsFolder);
+}
+
+/** Row count per table; no contents, so safe to share in support bundles */
+export function getTableRowCounts(): Record<string, number> {
+	const tables = sqlite
+		.prepare(
+			"SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+		)
+		.all() as { name: string }[];
+
+	const counts: Record<string, number> = {};
+	for (const { name } of tables) {
+		const row = sqlite.prepare(`SELECT COUNT(*) AS count FROM "${name}"`).get() as {
+			count: number;
+		};
+		counts[name] = row.count;
+	}
+	return counts;
+}
+
 export type LocalDb = typeof localDb;
@@ -0,0 +1,34 @@
+import { describe, expect, test } from "bun:test";
+import { getBackupsToPrune, getPendingMigrationTags } from "./migration-backup";
+
+const entries = [
+	{ idx: 0, when: 100, tag: "0000_init" },
+	{ idx: 2, when: 300, tag: "0002_c" },
+	{ idx: 1, when: 200, tag: "0001_b" },
+];
+
+describe("getPendingMigrationTags", () => {
+	test("returns entries newer than the last applied migration, in order", () => {
+		expect(getPendingMigrationTags(entries, 100)).toEqual(["0001_b", "0002_c"]);
+		expect(getPendingMigrationTags(entries, 300)).toEqual([]);
+	});
+
+	test("treats a database without migrations as fully pending", () => {
+		expect(getPendingMigrationTags(entries, null)).toHaveLength(3);
+	});
+});
+
+describe("getBackupsToPrune", () => {
+	test("keeps the newest backups and ignores other files", () => {
+		const files = [
+			"local.db",
+			"local.db-wal",
+			"local.db.pre-migration-2026-01-03T00-00-00-000Z",
+			"local.db.pre-migration-2026-01-01T00-00-00-000Z",
+			"local.db.pre-migration-2026-01-02T00-00-00-000Z",
+		];
+		expect(getBackupsToPrune(files, 2)).toEqual([
+			"local.db.pre-migration-2026-01-01T00-00-00-000Z",
+		]);
+	});
+});
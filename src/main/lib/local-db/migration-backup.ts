@@ -0,0 +1,106 @@
+import { chmodSync, existsSync, readdirSync, readFileSync, rmSync } from "node:fs";
+import { join } from "node:path";
+import type Database from "better-sqlite3";
+import { CASPIAN_SENSITIVE_FILE_MODE } from "../app-environment";
+
+/** Pre-migration backups kept next to the database; older ones are pruned */
+const MAX_MIGRATION_BACKUPS = 3;
+
+const BACKUP_PREFIX = "local.db.pre-migration-";
+
+interface JournalEntry {
+	idx: number;
+	when: number;
+	tag: string;
+}
+
+/**
+ * Tags of journal entries newer than the last applied migration. Mirrors the
+ * drizzle migrator, which applies entries whose `when` is after the newest
+ * `created_at` in __drizzle_migrations.
+ */
+export function getPendingMigrationTags(
+	entries: JournalEntry[],
+	lastAppliedAt: number | null,
+): string[] {
+	return entries
+		.filter((entry) => lastAppliedAt === null || entry.when > lastAppliedAt)
+		.sort((a, b) => a.idx - b.idx)
+		.map((entry) => entry.tag);
+}
+
+/** Backup file names beyond the newest `keep`, oldest first */
+export function getBackupsToPrune(fileNames: string[], keep: number): string[] {
+	const backups = fileNames.filter((name) => name.startsWith(BACKUP_PREFIX)).sort();
+	return backups.slice(0, Math.max(0, backups.length - keep));
+}
+
+function readJournal(migrationsFolder: string): JournalEntry[] {
+	const journalPath = join(migrationsFolder, "meta", "_journal.json");
+	if (!existsSync(journalPath)) return [];
+	return (JSON.parse(readFileSync(journalPath, "utf-8")) as { entries: JournalEntry[] }).entries;
+}
+
+function getLastAppliedAt(sqlite: Database.Database): number | null {
+	try {
+		const row = sqlite
+			.prepare("SELECT created_at FROM __drizzle_migrations ORDER BY created_at DESC LIMIT 1")
+			.get() as { created_at: number } | undefined;
+		return row ? Number(row.created_at) : null;
+	} catch {
+		// No migrations table yet
+		return null;
+	}
+}
+
+/** Tag of the newest applied migration, e.g. "0040_add_privacy_settings" */
+export function getAppliedMigrationTag(
+	sqlite: Database.Database,
+	migrationsFolder: string,
+): string | null {
+	const lastAppliedAt = getLastAppliedAt(sqlite);
+	if (lastAppliedAt === null) return null;
+
+	const applied = readJournal(migrationsFolder).filter((entry) => entry.when <= lastAppliedAt);
+	return applied.sort((a, b) => b.idx - a.idx)[0]?.tag ?? null;
+}
+
+/**
+ * Copies the database aside before pending migrations run so a failed or bad
+ * migration can be recovered from. Skipped for fresh databases, where there
+ * is nothing to lose. Returns the backup path, or null when none was made.
+ */
+export function backupBeforeMigrating({
+	sqlite,
+	dbPath,
+	migrationsFolder,
+}: {
+	sqlite: Database.Database;
+	dbPath: string;
+	migrationsFolder: string;
+}): string | null {
+	const lastAppliedAt = getLastAppliedAt(sqlite);
+	if (lastAppliedAt === null) return null;
+
+	const pending = getPendingMigrationTags(readJournal(migrationsFolder), lastAppliedAt);
+	if (pending.length === 0) return null;
+
+	const dir = join(dbPath, "..");
+	const backupPath = join(dir, `${BACKUP_PREFIX}${new Date().toISOString().replace(/[:.]/g, "-")}`);
+	sqlite.prepare("VACUUM INTO ?").run(backupPath);
+	// VACUUM INTO creates the file with the default umask; it holds the same data as the database
+	try {
+		chmodSync(backupPath, CASPIAN_SENSITIVE_FILE_MODE);
+	} catch (error) {
+		console.warn(`[local-db] Failed to restrict permissions on ${backupPath}:`, error);
+	}
+	console.log(
+		`[local-db] Backed up database to ${backupPath} before applying: ${pending.join(", ")}`,
+	);
+
+	for (const name of getBackupsToPrune(readdirSync(dir), MAX_MIGRATION_BACKUPS)) {
+		rmSync(join(dir, name), { force: true });
+	}
+
+	return backupPath;
+}
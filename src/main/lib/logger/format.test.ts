@@ -0,0 +1,43 @@
+import { describe, expect, test } from "bun:test";
+import { filterLogEntries, formatLogLine, parseLogEntries } from "./format";
+
+const at = Date.UTC(2026, 0, 2, 3, 4, 5);
+
+describe("parseLogEntries", () => {
+	test("round-trips formatted lines", () => {
+		const content =
+			formatLogLine(at, "info", "[main] Ready") + formatLogLine(at + 1, "error", "[git] Failed");
+		expect(parseLogEntries(content)).toEqual([
+			{ timestamp: at, level: "info", message: "[main] Ready" },
+			{ timestamp: at + 1, level: "error", message: "[git] Failed" },
+		]);
+	});
+
+	test("attaches continuation lines to the entry above", () => {
+		const stack = "Error: boom\n    at run (a.ts:1:1)";
+		const content = `partial line\n${formatLogLine(at, "error", stack)}`;
+		expect(parseLogEntries(content)).toEqual([{ timestamp: at, level: "error", message: stack }]);
+	});
+});
+
+describe("filterLogEntries", () => {
+	const entries = parseLogEntries(
+		["debug", "info", "warn", "error", "info"]
+			.map((level, i) => formatLogLine(at + i, level as "info", `line ${i}`))
+			.join(""),
+	);
+
+	test("keeps entries at or above the level", () => {
+		expect(filterLogEntries(entries, "warn", 100).map((e) => e.message)).toEqual([
+			"line 2",
+			"line 3",
+		]);
+	});
+
+	test("returns the most recent entries", () => {
+		expect(filterLogEntries(entries, "debug", 2).map((e) => e.message)).toEqual([
+			"line 3",
+			"line 4",
+		]);
+	});
+});
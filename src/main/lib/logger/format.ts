@@ -0,0 +1,44 @@
+import { LOG_LEVELS, type LogEntry, type LogLevel } from "shared/types/logs";
+
+const LINE_PATTERN = /^(\d{4}-\d{2}-\d{2}T[\d:.]+Z) (DEBUG|INFO|WARN|ERROR) {1,2}(.*)$/;
+
+/** `2026-01-01T12:00:00.000Z INFO  message`, with the level padded so messages line up */
+export function formatLogLine(timestamp: number, level: LogLevel, message: string): string {
+	const label = level.toUpperCase().padEnd(5);
+	return `${new Date(timestamp).toISOString()} ${label} ${message}\n`;
+}
+
+/**
+ * Parses log file content. Lines that don't start with a timestamp (stack
+ * traces, multi-line objects) belong to the entry above them; anything
+ * before the first entry, like the tail of a rotated-out entry, is dropped.
+ */
+export function parseLogEntries(content: string): LogEntry[] {
+	const entries: LogEntry[] = [];
+	for (const line of content.split("\n")) {
+		const match = LINE_PATTERN.exec(line);
+		if (match) {
+			entries.push({
+				timestamp: Date.parse(match[1]),
+				level: match[2].toLowerCase() as LogLevel,
+				message: match[3],
+			});
+			continue;
+		}
+
+		const last = entries.at(-1);
+		if (last && line) last.message += `\n${line}`;
+	}
+	return entries;
+}
+
+/** The last `lines` entries at or above `minLevel` */
+export function filterLogEntries(
+	entries: LogEntry[],
+	minLevel: LogLevel,
+	lines: number,
+): LogEntry[] {
+	const minSeverity = LOG_LEVELS.indexOf(minLevel);
+	const matching = entries.filter((entry) => LOG_LEVELS.indexOf(entry.level) >= minSeverity);
+	return lines > 0 ? matching.slice(-lines) : [];
+}
@@ -0,0 +1,161 @@
+import {
+	createWriteStream,
+	existsSync,
+	mkdirSync,
+	renameSync,
+	statSync,
+	type WriteStream,
+} from "node:fs";
+import { readFile, writeFile } from "node:fs/promises";
+import { join } from "node:path";
+import { format } from "node:util";
+import { CASPIAN_HOME_DIR, CASPIAN_SENSITIVE_FILE_MODE, LOGS_DIR } from "main/lib/app-environment";
+import type { LogEntry, LogLevel } from "shared/types/logs";
+import { redactSecrets } from "shared/utils/redact";
+import { filterLogEntries, formatLogLine, parseLogEntries } from "./format";
+
+const LOG_FILE_NAME = "main";
+
+/** Rotate once the current file passes this size */
+const MAX_LOG_FILE_BYTES = 5 * 1024 * 1024;
+
+/** Rotated files kept alongside the current one: main.1.log, main.2.log */
+const MAX_ROTATED_FILES = 2;
+
+/** Written by the terminal host daemon; included in exports */
+const DAEMON_LOG_PATH = join(CASPIAN_HOME_DIR, "daemon.log");
+
+const CONSOLE_LEVELS: Record<"debug" | "log" | "info" | "warn" | "error", LogLevel> = {
+	debug: "debug",
+	log: "info",
+	info: "info",
+	warn: "warn",
+	error: "error",
+};
+
+/** Current file first, then older rotations */
+function getLogFilePaths(): string[] {
+	const paths = [join(LOGS_DIR, `${LOG_FILE_NAME}.log`)];
+	for (let i = 1; i <= MAX_ROTATED_FILES; i++) {
+		paths.push(join(LOGS_DIR, `${LOG_FILE_NAME}.${i}.log`));
+	}
+	return paths;
+}
+
+let stream: WriteStream | null = null;
+let bytesWritten = 0;
+let rotateAtBytes = MAX_LOG_FILE_BYTES;
+
+function openStream(): void {
+	const [currentPath] = getLogFilePaths();
+	bytesWritten = existsSync(currentPath) ? statSync(currentPath).size : 0;
+	stream = createWriteStream(currentPath, { flags: "a", mode: CASPIAN_SENSITIVE_FILE_MODE });
+	stream.on("error", (error) => {
+		stream = null;
+		// Don't go through the patched console, which would try the stream again
+		process.stderr.write(`[logger] Log file stream failed: ${error}\n`);
+	});
+}
+
+function rotate(): void {
+	stream?.end();
+	stream = null;
+
+	const paths = getLogFilePaths();
+	for (let i = paths.length - 1; i > 0; i--) {
+		try {
+			if (existsSync(paths[i - 1])) renameSync(paths[i - 1], paths[i]);
+		} catch (error) {
+			// Windows won't rename a file that's still open, e.g. by a log viewer
+			process.stderr.write(`[logger] Failed to rotate ${paths[i - 1]}: ${error}\n`);
+		}
+	}
+	openStream();
+
+	// Still appending to the full file; try again once it has grown by another
+	// rotation's worth instead of on every write
+	rotateAtBytes =
+		bytesWritten >= MAX_LOG_FILE_BYTES ? bytesWritten + MAX_LOG_FILE_BYTES : MAX_LOG_FILE_BYTES;
+}
+
+function writeEntry(level: LogLevel, args: unknown[]): void {
+	if (!stream) return;
+	const line = formatLogLine(Date.now(), level, format(...args));
+	stream.write(line);
+	bytesWritten += Buffer.byteLength(line);
+	if (bytesWritten >= rotateAtBytes) rotate();
+}
+
+/**
+ * Mirrors console output from the main process to ~/.caspian/logs/main.log,
+ * keeping the last few rotations. Call once, as early as possible, so
+ * startup failures are captured too.
+ */
+export function installFileLogger(): void {
+	if (stream) return;
+
+	try {
+		mkdirSync(LOGS_DIR, { recursive: true });
+		openStream();
+	} catch (error) {
+		console.warn("[logger] Failed to open log file, logging to console only:", error);
+		return;
+	}
+
+	for (const method of Object.keys(CONSOLE_LEVELS) as (keyof typeof CONSOLE_LEVELS)[]) {
+		const original = console[method].bind(console);
+		console[method] = (...args: unknown[]) => {
+			original(...args);
+			writeEntry(CONSOLE_LEVELS[method], args);
+		};
+	}
+}
+
+async function readLogFile(filePath: string): Promise<string | null> {
+	try {
+		return await readFile(filePath, "utf-8");
+	} catch {
+		return null;
+	}
+}
+
+/**
+ * The most recent `lines` entries at or above `level`, oldest first. Older
+ * rotations are only read when the current file doesn't have enough.
+ */
+export async function getRecentLogs({
+	level,
+	lines,
+}: {
+	level: LogLevel;
+	lines: number;
+}): Promise<LogEntry[]> {
+	let entries: LogEntry[] = [];
+	for (const filePath of getLogFilePaths()) {
+		const content = await readLogFile(filePath);
+		if (content === null) break;
+
+		entries = [...filterLogEntries(parseLogEntries(content), level, lines), ...entries];
+		if (entries.length >= lines) break;
+	}
+	return entries.slice(-lines);
+}
+
+/**
+ * Every retained main process log, oldest first, followed by the terminal
+ * daemon's log, each under a `==> name <==` header. Secrets are redacted
+ * since this is meant to be attached to bug reports.
+ */
+export async function readAllLogs(): Promise<string> {
+	const sources = [...getLogFilePaths().reverse(), DAEMON_LOG_PATH];
+	const sections: string[] = [];
+	for (const source of sources) {
+		const content = await readLogFile(source);
+		if (content) sections.push(`==> ${source} <==\n${content}`);
+	}
+	return redactSecrets(sections.join("\n"));
+}
+
+export async function exportLogs(filePath: string): Promise<void> {
+	await writeFile(filePath, await readAllLogs(), "utf-8");
+}
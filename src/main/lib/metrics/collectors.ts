@@ -0,0 +1,79 @@
+import { NOTIFICATION_EVENTS } from "shared/constants";
+import type { AgentLifecycleEvent } from "shared/notification-types";
+import type { NodeInitProgress } from "shared/types/node-init";
+import type { RepositoryMaintenanceProgress } from "shared/types/repo-maintenance";
+import { nodeInitManager } from "../node-init-manager";
+import { notificationsEmitter } from "../notifications/server";
+import { repositoryMaintenanceManager } from "../repository-maintenance-manager";
+import {
+	agentEventsTotal,
+	agentRunDurationSeconds,
+	agentRunsTotal,
+	metricsRegistry,
+	nodeInitDurationSeconds,
+	nodeInitTotal,
+	repoMaintenanceTotal,
+} from "./metrics";
+
+let started = false;
+
+/**
+ * Subscribes metrics to app event sources. Safe to call more than once.
+ */
+export function startMetricsCollection(): void {
+	if (started) return;
+	started = true;
+
+	metricsRegistry.gauge(
+		"caspian_node_init_in_progress",
+		"Node initializations currently queued or running",
+		() =>
+			nodeInitManager
+				.getAllProgress()
+				.filter((progress) => nodeInitManager.isInitializing(progress.nodeId)).length,
+	);
+
+	// Keyed by pane (falling back to node) so concurrent agents are timed separately
+	const agentStartTimes = new Map<string, number>();
+	notificationsEmitter.on(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, (event: AgentLifecycleEvent) => {
+		agentEventsTotal.inc({ event: event.eventType });
+
+		const key = event.paneId ?? event.nodeId;
+		if (!key) return;
+
+		if (event.eventType === "Start") {
+			agentRunsTotal.inc();
+			agentStartTimes.set(key, Date.now());
+			return;
+		}
+
+		const startedAt = agentStartTimes.get(key);
+		if (startedAt !== undefined) {
+			agentStartTimes.delete(key);
+			agentRunDurationSeconds.observe((Date.now() - startedAt) / 1000);
+		}
+	});
+
+	const initStartTimes = new Map<string, number>();
+	nodeInitManager.on("progress", (progress: NodeInitProgress) => {
+		if (progress.step !== "ready" && progress.step !== "failed") {
+			if (!initStartTimes.has(progress.nodeId)) {
+				initStartTimes.set(progress.nodeId, Date.now());
+			}
+			return;
+		}
+
+		nodeInitTotal.inc({ result: progress.step });
+		const startedAt = initStartTimes.get(progress.nodeId);
+		if (startedAt !== undefined) {
+			initStartTimes.delete(progress.nodeId);
+			nodeInitDurationSeconds.observe((Date.now() - startedAt) / 1000);
+		}
+	});
+
+	repositoryMaintenanceManager.on("progress", (progress: RepositoryMaintenanceProgress) => {
+		if (progress.step === "complete" || progress.step === "failed") {
+			repoMaintenanceTotal.inc({ result: progress.step, trigger: progress.trigger });
+		}
+	});
+}
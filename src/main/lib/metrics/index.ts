@@ -0,0 +1,2 @@
+export { startMetricsCollection } from "./collectors";
+export { dbQueryDurationSeconds, metricsRegistry } from "./metrics";
@@ -0,0 +1,44 @@
+import { MetricsRegistry } from "./registry";
+
+export const metricsRegistry = new MetricsRegistry();
+
+const DURATION_BUCKETS_SECONDS = [1, 5, 15, 30, 60, 120, 300, 600, 1800, 3600];
+const DB_BUCKETS_SECONDS = [0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1];
+
+export const agentRunsTotal = metricsRegistry.counter(
+	"caspian_agent_runs_total",
+	"Agent runs started (prompt submitted)",
+);
+
+export const agentEventsTotal = metricsRegistry.counter(
+	"caspian_agent_events_total",
+	"Agent lifecycle events received from hooks, by event type",
+);
+
+export const agentRunDurationSeconds = metricsRegistry.histogram(
+	"caspian_agent_run_duration_seconds",
+	"Time from agent start to stop or permission request",
+	DURATION_BUCKETS_SECONDS,
+);
+
+export const nodeInitTotal = metricsRegistry.counter(
+	"caspian_node_init_total",
+	"Node initializations finished, by result",
+);
+
+export const nodeInitDurationSeconds = metricsRegistry.histogram(
+	"caspian_node_init_duration_seconds",
+	"Time to initialize a node worktree",
+	DURATION_BUCKETS_SECONDS,
+);
+
+export const repoMaintenanceTotal = metricsRegistry.counter(
+	"caspian_repo_maintenance_total",
+	"Repository maintenance runs finished, by result",
+);
+
+export const dbQueryDurationSeconds = metricsRegistry.histogram(
+	"caspian_db_query_duration_seconds",
+	"Local SQLite statement latency, by operation",
+	DB_BUCKETS_SECONDS,
+);
@@ -0,0 +1,50 @@
+import { describe, expect, test } from "bun:test";
+import { MetricsRegistry } from "./registry";
+
+describe("MetricsRegistry", () => {
+	test("renders counters with sorted, escaped labels", () => {
+		const registry = new MetricsRegistry();
+		const counter = registry.counter("jobs_total", "Jobs run");
+		counter.inc({ result: "ok", agent: 'say "hi"' });
+		counter.inc({ agent: 'say "hi"', result: "ok" }, 2);
+
+		expect(registry.render()).toBe(
+			[
+				"# HELP jobs_total Jobs run",
+				"# TYPE jobs_total counter",
+				'jobs_total{agent="say \\"hi\\"",result="ok"} 3',
+				"",
+			].join("\n"),
+		);
+	});
+
+	test("renders cumulative histogram buckets", () => {
+		const registry = new MetricsRegistry();
+		const histogram = registry.histogram("latency_seconds", "Latency", [0.1, 1]);
+		histogram.observe(0.25);
+		histogram.observe(0.5);
+		histogram.observe(5);
+
+		const output = registry.render();
+		expect(output).toContain('latency_seconds_bucket{le="0.1"} 1');
+		expect(output).toContain('latency_seconds_bucket{le="1"} 2');
+		expect(output).toContain('latency_seconds_bucket{le="+Inf"} 3');
+		expect(output).toContain("latency_seconds_sum 5.75");
+		expect(output).toContain("latency_seconds_count 3");
+	});
+
+	test("evaluates collected gauges at render time", () => {
+		const registry = new MetricsRegistry();
+		let depth = 1;
+		registry.gauge("queue_depth", "Queue depth", () => depth);
+		depth = 4;
+
+		expect(registry.render()).toContain("queue_depth 4");
+	});
+
+	test("rejects duplicate metric names", () => {
+		const registry = new MetricsRegistry();
+		registry.counter("dup_total", "First");
+		expect(() => registry.counter("dup_total", "Second")).toThrow();
+	});
+});
@@ -0,0 +1,155 @@
+/**
+ * Minimal in-process metrics registry that renders the Prometheus text exposition format.
+ * Kept dependency-free so it can be used from the local DB layer without import cycles.
+ */
+
+export type MetricLabels = Record<string, string>;
+
+function labelKey(labels: MetricLabels | undefined): string {
+	if (!labels) return "";
+	return Object.keys(labels)
+		.sort()
+		.map((key) => `${key}="${escapeLabelValue(labels[key])}"`)
+		.join(",");
+}
+
+function escapeLabelValue(value: string): string {
+	return value.replace(/\\/g, "\\\\").replace(/"/g, '\\"').replace(/\n/g, "\\n");
+}
+
+function formatSample(name: string, key: string, value: number): string {
+	return key ? `${name}{${key}} ${value}` : `${name} ${value}`;
+}
+
+interface Metric {
+	readonly name: string;
+	readonly help: string;
+	readonly type: "counter" | "gauge" | "histogram";
+	samples(): string[];
+}
+
+export class Counter implements Metric {
+	readonly type = "counter";
+	private values = new Map<string, number>();
+
+	constructor(
+		readonly name: string,
+		readonly help: string,
+	) {}
+
+	inc(labels?: MetricLabels, value = 1): void {
+		const key = labelKey(labels);
+		this.values.set(key, (this.values.get(key) ?? 0) + value);
+	}
+
+	samples(): string[] {
+		return Array.from(this.values, ([key, value]) => formatSample(this.name, key, value));
+	}
+}
+
+export class Gauge implements Metric {
+	readonly type = "gauge";
+	private values = new Map<string, number>();
+
+	/**
+	 * @param collect - Optional callback evaluated at scrape time, for values owned elsewhere
+	 */
+	constructor(
+		readonly name: string,
+		readonly help: string,
+		private readonly collect?: () => number,
+	) {}
+
+	set(value: number, labels?: MetricLabels): void {
+		this.values.set(labelKey(labels), value);
+	}
+
+	samples(): string[] {
+		if (this.collect) {
+			return [formatSample(this.name, "", this.collect())];
+		}
+		return Array.from(this.values, ([key, value]) => formatSample(this.name, key, value));
+	}
+}
+
+interface HistogramSeries {
+	buckets: number[];
+	sum: number;
+	count: number;
+}
+
+export class Histogram implements Metric {
+	readonly type = "histogram";
+	private series = new Map<string, HistogramSeries>();
+
+	constructor(
+		readonly name: string,
+		readonly help: string,
+		private readonly bounds: number[],
+	) {}
+
+	observe(value: number, labels?: MetricLabels): void {
+		const key = labelKey(labels);
+		let series = this.series.get(key);
+		if (!series) {
+			series = { buckets: this.bounds.map(() => 0), sum: 0, count: 0 };
+			this.series.set(key, series);
+		}
+
+		for (let i = 0; i < this.bounds.length; i++) {
+			if (value <= this.bounds[i]) {
+				series.buckets[i]++;
+			}
+		}
+		series.sum += value;
+		series.count++;
+	}
+
+	samples(): string[] {
+		const lines: string[] = [];
+		for (const [key, series] of this.series) {
+			const prefix = key ? `${key},` : "";
+			this.bounds.forEach((bound, i) => {
+				lines.push(`${this.name}_bucket{${prefix}le="${bound}"} ${series.buckets[i]}`);
+			});
+			lines.push(`${this.name}_bucket{${prefix}le="+Inf"} ${series.count}`);
+			lines.push(formatSample(`${this.name}_sum`, key, series.sum));
+			lines.push(formatSample(`${this.name}_count`, key, series.count));
+		}
+		return lines;
+	}
+}
+
+export class MetricsRegistry {
+	private metrics: Metric[] = [];
+
+	counter(name: string, help: string): Counter {
+		return this.register(new Counter(name, help));
+	}
+
+	gauge(name: string, help: string, collect?: () => number): Gauge {
+		return this.register(new Gauge(name, help, collect));
+	}
+
+	histogram(name: string, help: string, bounds: number[]): Histogram {
+		return this.register(new Histogram(name, help, bounds));
+	}
+
+	render(): string {
+		const lines: string[] = [];
+		for (const metric of this.metrics) {
+			lines.push(`# HELP ${metric.name} ${metric.help}`);
+			lines.push(`# TYPE ${metric.name} ${metric.type}`);
+			lines.push(...metric.samples());
+		}
+		return `${lines.join("\n")}\n`;
+	}
+
+	private register<T extends Metric>(metric: T): T {
+		if (this.metrics.some((existing) => existing.name === metric.name)) {
+			throw new Error(`Metric ${metric.name} is already registered`);
+		}
+		this.metrics.push(metric);
+		return metric;
+	}
+}
@@ -0,0 +1,54 @@
+import { eq } from "drizzle-orm";
+import { nodes, repositories } from "lib/local-db";
+import { getNodePath } from "lib/trpc/routers/nodes/utils/worktree";
+import { localDb } from "./local-db";
+import { getNodeHookPayload, type RepoHook, runPostHook, runPreHook } from "./repo-hooks";
+
+export interface NodeHookParams {
+	mainRepoPath: string;
+	cwd: string;
+	payload: Record<string, unknown>;
+}
+
+/**
+ * Hook parameters for a node, looked up up front so post-delete hooks can
+ * still describe a node after its row is gone.
+ */
+export function getNodeHookParams(
+	nodeId: string,
+	extra?: Record<string, unknown>,
+): NodeHookParams | null {
+	const node = localDb.select().from(nodes).where(eq(nodes.id, nodeId)).get();
+	if (!node) return null;
+	const repository = localDb
+		.select()
+		.from(repositories)
+		.where(eq(repositories.id, node.repositoryId))
+		.get();
+	if (!repository) return null;
+
+	const path = getNodePath(node);
+	return {
+		mainRepoPath: repository.mainRepoPath,
+		cwd: path ?? repository.mainRepoPath,
+		payload: { ...getNodeHookPayload({ node, repository, path }), ...extra },
+	};
+}
+
+/** Throws HookFailedError when the node's repository has a failing hook */
+export async function runNodePreHook({
+	hook,
+	nodeId,
+	extra,
+}: {
+	hook: RepoHook;
+	nodeId: string;
+	extra?: Record<string, unknown>;
+}): Promise<void> {
+	const params = getNodeHookParams(nodeId, extra);
+	if (params) await runPreHook({ hook, ...params });
+}
+
+export function runNodePostHook(hook: RepoHook, params: NodeHookParams | null | undefined): void {
+	if (params) runPostHook({ hook, ...params });
+}
@@ -0,0 +1,47 @@
+import { eq } from "drizzle-orm";
+import { BrowserWindow } from "electron";
+import { notificationHistory, type SelectNotificationHistory } from "lib/local-db";
+import { NOTIFICATION_EVENTS } from "shared/constants";
+import type { NotificationAction } from "shared/notification-types";
+import { localDb } from "../local-db";
+import { markNotificationsRead } from "./history";
+import { notificationsEmitter } from "./server";
+
+/**
+ * Runs an action on a notification history entry. Shared by OS notification
+ * clicks, the in-app inbox and caspian://notification/<id> deep links.
+ */
+export function handleNotificationAction({
+	id,
+	action,
+}: {
+	id: string;
+	action: NotificationAction;
+}): SelectNotificationHistory {
+	const entry = localDb
+		.select()
+		.from(notificationHistory)
+		.where(eq(notificationHistory.id, id))
+		.get();
+	if (!entry) {
+		throw new Error(`Notification ${id} not found`);
+	}
+
+	markNotificationsRead([id]);
+
+	if (action === "open") {
+		const [window] = BrowserWindow.getAllWindows();
+		if (window) {
+			if (window.isMinimized()) window.restore();
+			window.show();
+			window.focus();
+		}
+		notificationsEmitter.emit(NOTIFICATION_EVENTS.FOCUS_TAB, {
+			paneId: entry.paneId ?? undefined,
+			tabId: entry.tabId ?? undefined,
+			nodeId: entry.nodeId ?? undefined,
+		});
+	}
+
+	return entry;
+}
@@ -0,0 +1,28 @@
+import { describe, expect, it } from "bun:test";
+import { AGENT_SCENARIOS, getScenarioSteps } from "./agent-scenarios";
+
+describe("getScenarioSteps", () => {
+	it("starts every scenario with an immediate Start event", () => {
+		for (const scenario of AGENT_SCENARIOS) {
+			const [first] = getScenarioSteps(scenario);
+			expect(first).toEqual({ kind: "lifecycle", delayMs: 0, eventType: "Start" });
+		}
+	});
+
+	it("includes a permission request in the permission scenario", () => {
+		const eventTypes = getScenarioSteps("permission").map((step) =>
+			step.kind === "lifecycle" ? step.eventType : step.kind,
+		);
+		expect(eventTypes).toEqual(["Start", "PermissionRequest", "Start", "Stop"]);
+	});
+
+	it("ends the failure scenario with a non-zero terminal exit", () => {
+		const steps = getScenarioSteps("failure");
+		expect(steps[steps.length - 1]).toMatchObject({ kind: "terminal-exit", exitCode: 1 });
+	});
+
+	it("scales delays by speed", () => {
+		expect(getScenarioSteps("complete", 2)[1].delayMs).toBe(1500);
+		expect(getScenarioSteps("complete", 0)[1].delayMs).toBe(300000);
+	});
+});
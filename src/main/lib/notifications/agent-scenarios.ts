@@ -0,0 +1,53 @@
+import type { AgentLifecycleEvent } from "shared/notification-types";
+
+export const AGENT_SCENARIOS = ["complete", "permission", "multi-turn", "failure"] as const;
+export type AgentScenario = (typeof AGENT_SCENARIOS)[number];
+
+export type AgentScenarioStep =
+	| { kind: "lifecycle"; delayMs: number; eventType: AgentLifecycleEvent["eventType"] }
+	| {
+			kind: "terminal-exit";
+			delayMs: number;
+			exitCode: number;
+			reason: "killed" | "exited" | "error";
+	  };
+
+/**
+ * Scripted lifecycle sequences, mirroring what agent hooks send for a real run.
+ * Delays are relative to the previous step.
+ */
+const SCENARIO_STEPS: Record<AgentScenario, AgentScenarioStep[]> = {
+	complete: [
+		{ kind: "lifecycle", delayMs: 0, eventType: "Start" },
+		{ kind: "lifecycle", delayMs: 3000, eventType: "Stop" },
+	],
+	permission: [
+		{ kind: "lifecycle", delayMs: 0, eventType: "Start" },
+		{ kind: "lifecycle", delayMs: 2000, eventType: "PermissionRequest" },
+		// The user answering the prompt resumes the agent
+		{ kind: "lifecycle", delayMs: 4000, eventType: "Start" },
+		{ kind: "lifecycle", delayMs: 2000, eventType: "Stop" },
+	],
+	"multi-turn": [
+		{ kind: "lifecycle", delayMs: 0, eventType: "Start" },
+		{ kind: "lifecycle", delayMs: 2000, eventType: "Stop" },
+		{ kind: "lifecycle", delayMs: 1500, eventType: "Start" },
+		{ kind: "lifecycle", delayMs: 2500, eventType: "Stop" },
+	],
+	failure: [
+		{ kind: "lifecycle", delayMs: 0, eventType: "Start" },
+		{ kind: "terminal-exit", delayMs: 2000, exitCode: 1, reason: "error" },
+	],
+};
+
+/**
+ * Returns the steps for a scenario with delays scaled by `speed`
+ * (2 = twice as fast). Speeds outside (0, 100] are clamped.
+ */
+export function getScenarioSteps(scenario: AgentScenario, speed = 1): AgentScenarioStep[] {
+	const factor = Math.min(Math.max(speed, 0.01), 100);
+	return SCENARIO_STEPS[scenario].map((step) => ({
+		...step,
+		delayMs: Math.round(step.delayMs / factor),
+	}));
+}
@@ -0,0 +1,57 @@
+import { and, eq, exists, isNull, or, sql } from "drizzle-orm";
+import { app } from "electron";
+import { nodes, notificationHistory } from "lib/local-db";
+import { localDb } from "../local-db";
+
+export interface BadgeCounts {
+	/** Nodes flagged unread */
+	unread: number;
+	/** Nodes with an unread input request */
+	needsInput: number;
+	/** Nodes needing attention for either reason, shown on the dock/taskbar badge */
+	total: number;
+}
+
+export function getBadgeCounts(): BadgeCounts {
+	const pendingInput = exists(
+		localDb
+			.select({ id: notificationHistory.id })
+			.from(notificationHistory)
+			.where(
+				and(
+					eq(notificationHistory.nodeId, nodes.id),
+					eq(notificationHistory.type, "needs_input"),
+					isNull(notificationHistory.readAt),
+				),
+			),
+	);
+
+	const row = localDb
+		.select({
+			unread: sql<number>`coalesce(sum(${nodes.isUnread} = 1), 0)`,
+			needsInput: sql<number>`coalesce(sum(${pendingInput}), 0)`,
+			total: sql<number>`count(*)`,
+		})
+		.from(nodes)
+		.where(and(isNull(nodes.deletingAt), or(eq(nodes.isUnread, true), pendingInput)))
+		.get();
+
+	return {
+		unread: row?.unread ?? 0,
+		needsInput: row?.needsInput ?? 0,
+		total: row?.total ?? 0,
+	};
+}
+
+/**
+ * Sets the dock (macOS) or launcher (Linux Unity) badge to the number of
+ * nodes needing attention. Windows has no numeric badge API; there it's a
+ * no-op. Called whenever unread or notification state changes.
+ */
+export function updateAppBadge(): void {
+	try {
+		app.setBadgeCount(getBadgeCounts().total);
+	} catch (error) {
+		console.error("[notifications] Failed to update app badge:", error);
+	}
+}
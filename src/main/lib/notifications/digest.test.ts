@@ -0,0 +1,70 @@
+import { describe, expect, it } from "bun:test";
+import { type DigestEntry, NotificationDigest, summarizeDigest } from "./digest";
+
+function entry(overrides: Partial<DigestEntry>): DigestEntry {
+	return {
+		type: "agent_complete",
+		nodeName: "main",
+		title: "Agent Complete — main",
+		body: '"Terminal" has finished its task',
+		sound: true,
+		historyId: null,
+		...overrides,
+	};
+}
+
+describe("summarizeDigest", () => {
+	it("counts completions and input requests", () => {
+		const summary = summarizeDigest([
+			entry({ nodeName: "api" }),
+			entry({ nodeName: "web" }),
+			entry({ nodeName: "docs" }),
+			entry({ type: "needs_input", nodeName: "api" }),
+		]);
+
+		expect(summary.completed).toBe(3);
+		expect(summary.needsInput).toBe(1);
+		expect(summary.title).toBe("3 agents finished, 1 needs input");
+		expect(summary.body).toBe("api, web, docs");
+		expect(summary.items).toHaveLength(4);
+	});
+
+	it("uses singular and plural forms", () => {
+		expect(summarizeDigest([entry({})]).title).toBe("1 agent finished");
+		expect(
+			summarizeDigest([entry({ type: "needs_input" }), entry({ type: "needs_input" })]).title,
+		).toBe("2 need input");
+	});
+
+	it("truncates long node lists", () => {
+		const names = ["a", "b", "c", "d", "e", "f"];
+		const summary = summarizeDigest(names.map((nodeName) => entry({ nodeName })));
+		expect(summary.body).toBe("a, b, c, d and 2 more");
+	});
+});
+
+describe("NotificationDigest", () => {
+	it("flushes everything added within the window as one batch", async () => {
+		const batches: DigestEntry[][] = [];
+		const digest = new NotificationDigest((entries) => batches.push(entries));
+
+		digest.add(entry({ nodeName: "api" }), 20);
+		digest.add(entry({ nodeName: "web" }), 20);
+		expect(batches).toHaveLength(0);
+
+		await new Promise((resolve) => setTimeout(resolve, 50));
+		expect(batches).toHaveLength(1);
+		expect(batches[0].map((e) => e.nodeName)).toEqual(["api", "web"]);
+	});
+
+	it("drops pending entries on dispose", async () => {
+		const batches: DigestEntry[][] = [];
+		const digest = new NotificationDigest((entries) => batches.push(entries));
+
+		digest.add(entry({}), 20);
+		digest.dispose();
+
+		await new Promise((resolve) => setTimeout(resolve, 50));
+		expect(batches).toHaveLength(0);
+	});
+});
@@ -0,0 +1,88 @@
+import { createTranslator, type Translate } from "shared/i18n/translate";
+import type { NotificationDigestEvent, NotificationDigestItem } from "shared/notification-types";
+
+export interface DigestEntry extends NotificationDigestItem {
+	/** The notification that would have been shown on its own */
+	title: string;
+	body: string;
+	sound: boolean;
+	/** Notification history row, marked read when the notification is clicked */
+	historyId: string | null;
+}
+
+const MAX_LISTED_NODES = 4;
+
+/** e.g. "3 agents finished, 1 needs input" with the affected nodes as the body */
+export function summarizeDigest(
+	entries: DigestEntry[],
+	t: Translate = createTranslator("en"),
+): NotificationDigestEvent & {
+	title: string;
+	body: string;
+} {
+	const completed = entries.filter((entry) => entry.type === "agent_complete").length;
+	const needsInput = entries.length - completed;
+
+	const parts: string[] = [];
+	if (completed > 0) parts.push(t("digest.agentsFinished", { count: completed }));
+	if (needsInput > 0) parts.push(t("digest.needInput", { count: needsInput }));
+
+	const separator = t("list.separator");
+	const nodeNames = [...new Set(entries.map((entry) => entry.nodeName))];
+	const listed = nodeNames.slice(0, MAX_LISTED_NODES).join(separator);
+	const remaining = nodeNames.length - MAX_LISTED_NODES;
+
+	return {
+		completed,
+		needsInput,
+		items: entries.map(({ type, nodeName, nodeId, tabId, paneId }) => ({
+			type,
+			nodeName,
+			nodeId,
+			tabId,
+			paneId,
+		})),
+		title: parts.join(separator),
+		body: remaining > 0 ? t("digest.andMore", { list: listed, count: remaining }) : listed,
+	};
+}
+
+/**
+ * Holds notifications for a window that starts with the first one, then
+ * hands the batch to `onFlush`. The window doesn't extend as more arrive,
+ * so a steady stream still produces a notification every window.
+ */
+export class NotificationDigest {
+	private entries: DigestEntry[] = [];
+	private timer: ReturnType<typeof setTimeout> | null = null;
+
+	constructor(private readonly onFlush: (entries: DigestEntry[]) => void) {}
+
+	add(entry: DigestEntry, windowMs: number): void {
+		this.entries.push(entry);
+		if (!this.timer) {
+			this.timer = setTimeout(() => this.flush(), windowMs);
+		}
+	}
+
+	flush(): void {
+		if (this.timer) {
+			clearTimeout(this.timer);
+			this.timer = null;
+		}
+
+		const entries = this.entries;
+		this.entries = [];
+		if (entries.length > 0) {
+			this.onFlush(entries);
+		}
+	}
+
+	dispose(): void {
+		if (this.timer) {
+			clearTimeout(this.timer);
+			this.timer = null;
+		}
+		this.entries = [];
+	}
+}
@@ -0,0 +1,86 @@
+import { and, desc, inArray, isNull, lt, type SQL } from "drizzle-orm";
+import {
+	type InsertNotificationHistory,
+	notificationHistory,
+	type SelectNotificationHistory,
+} from "lib/local-db";
+import { localDb } from "../local-db";
+import { updateAppBadge } from "./badge";
+
+/** Oldest entries beyond this are dropped as new ones come in */
+const MAX_HISTORY_ENTRIES = 500;
+
+export function recordNotification(
+	entry: Omit<InsertNotificationHistory, "id" | "readAt" | "createdAt">,
+): SelectNotificationHistory | null {
+	try {
+		const row = localDb.insert(notificationHistory).values(entry).returning().get();
+
+		const cutoff = localDb
+			.select({ createdAt: notificationHistory.createdAt })
+			.from(notificationHistory)
+			.orderBy(desc(notificationHistory.createdAt))
+			.limit(1)
+			.offset(MAX_HISTORY_ENTRIES - 1)
+			.get();
+		if (cutoff) {
+			localDb
+				.delete(notificationHistory)
+				.where(lt(notificationHistory.createdAt, cutoff.createdAt))
+				.run();
+		}
+
+		updateAppBadge();
+		return row;
+	} catch (error) {
+		console.error("[notifications] Failed to record notification:", error);
+		return null;
+	}
+}
+
+/** Newest first; `before` pages by createdAt */
+export function listNotifications({
+	unreadOnly,
+	before,
+	limit,
+}: {
+	unreadOnly?: boolean;
+	before?: number;
+	limit: number;
+}): SelectNotificationHistory[] {
+	const conditions: SQL[] = [];
+	if (unreadOnly) conditions.push(isNull(notificationHistory.readAt));
+	if (before !== undefined) conditions.push(lt(notificationHistory.createdAt, before));
+
+	return localDb
+		.select()
+		.from(notificationHistory)
+		.where(conditions.length > 0 ? and(...conditions) : undefined)
+		.orderBy(desc(notificationHistory.createdAt))
+		.limit(limit)
+		.all();
+}
+
+/** Marks the given entries read, or every unread entry when ids is omitted */
+export function markNotificationsRead(ids?: string[]): number {
+	if (ids?.length === 0) return 0;
+	const unread = isNull(notificationHistory.readAt);
+	const { changes } = localDb
+		.update(notificationHistory)
+		.set({ readAt: Date.now() })
+		.where(ids ? and(unread, inArray(notificationHistory.id, ids)) : unread)
+		.run();
+	if (changes > 0) updateAppBadge();
+	return changes;
+}
+
+/** Deletes the given entries, or the whole history when ids is omitted */
+export function clearNotifications(ids?: string[]): number {
+	if (ids?.length === 0) return 0;
+	const { changes } = localDb
+		.delete(notificationHistory)
+		.where(ids ? inArray(notificationHistory.id, ids) : undefined)
+		.run();
+	if (changes > 0) updateAppBadge();
+	return changes;
+}
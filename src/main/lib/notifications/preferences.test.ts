@@ -0,0 +1,83 @@
+import { describe, expect, it } from "bun:test";
+import { isWithinQuietHours, resolveNotificationPreferences, shouldNotify } from "./preferences";
+
+describe("resolveNotificationPreferences", () => {
+	it("enables everything by default", () => {
+		expect(resolveNotificationPreferences(null, null)).toEqual({
+			agentComplete: true,
+			needsInput: true,
+			quietHours: null,
+			sound: true,
+			digestWindowSeconds: 0,
+		});
+	});
+
+	it("lets repository values override global ones", () => {
+		const resolved = resolveNotificationPreferences(
+			{
+				agentComplete: false,
+				quietHours: { start: "22:00", end: "07:00" },
+				digestWindowSeconds: 30,
+			},
+			{ agentComplete: true, needsInput: false, sound: false },
+		);
+		expect(resolved).toEqual({
+			agentComplete: true,
+			needsInput: false,
+			quietHours: { start: "22:00", end: "07:00" },
+			sound: false,
+			digestWindowSeconds: 30,
+		});
+	});
+
+	it("lets a repository turn off global quiet hours with null", () => {
+		const resolved = resolveNotificationPreferences(
+			{ quietHours: { start: "22:00", end: "07:00" } },
+			{ quietHours: null },
+		);
+		expect(resolved.quietHours).toBeNull();
+	});
+});
+
+describe("isWithinQuietHours", () => {
+	const sameDay = { start: "12:00", end: "13:30" };
+	const overnight = { start: "22:00", end: "07:00" };
+
+	it("handles a window within the same day", () => {
+		expect(isWithinQuietHours(sameDay, { hour: 12, minute: 0 })).toBe(true);
+		expect(isWithinQuietHours(sameDay, { hour: 13, minute: 29 })).toBe(true);
+		expect(isWithinQuietHours(sameDay, { hour: 13, minute: 30 })).toBe(false);
+		expect(isWithinQuietHours(sameDay, { hour: 11, minute: 59 })).toBe(false);
+	});
+
+	it("handles a window that wraps past midnight", () => {
+		expect(isWithinQuietHours(overnight, { hour: 23, minute: 15 })).toBe(true);
+		expect(isWithinQuietHours(overnight, { hour: 3, minute: 0 })).toBe(true);
+		expect(isWithinQuietHours(overnight, { hour: 7, minute: 0 })).toBe(false);
+		expect(isWithinQuietHours(overnight, { hour: 15, minute: 0 })).toBe(false);
+	});
+
+	it("treats missing or empty windows as no quiet hours", () => {
+		expect(isWithinQuietHours(null, { hour: 3, minute: 0 })).toBe(false);
+		const empty = { start: "09:00", end: "09:00" };
+		expect(isWithinQuietHours(empty, { hour: 9, minute: 0 })).toBe(false);
+	});
+});
+
+describe("shouldNotify", () => {
+	const preferences = resolveNotificationPreferences(
+		{ needsInput: false, quietHours: { start: "22:00", end: "07:00" } },
+		null,
+	);
+
+	it("respects per-event toggles", () => {
+		const time = { hour: 12, minute: 0 };
+		expect(shouldNotify({ preferences, kind: "agentComplete", time })).toBe(true);
+		expect(shouldNotify({ preferences, kind: "needsInput", time })).toBe(false);
+	});
+
+	it("stays silent during quiet hours", () => {
+		const time = { hour: 23, minute: 0 };
+		expect(shouldNotify({ preferences, kind: "agentComplete", time })).toBe(false);
+	});
+});
@@ -0,0 +1,68 @@
+import type { NotificationPreferences } from "lib/local-db";
+import type { ZonedTimeParts } from "shared/utils/time";
+
+export type NotificationKind = "agentComplete" | "needsInput";
+
+export interface ResolvedNotificationPreferences {
+	agentComplete: boolean;
+	needsInput: boolean;
+	quietHours: { start: string; end: string } | null;
+	sound: boolean;
+	digestWindowSeconds: number;
+}
+
+/**
+ * Layers a repository's overrides on top of the global preferences.
+ * Everything is on and quiet hours and digests are off unless configured.
+ */
+export function resolveNotificationPreferences(
+	global: NotificationPreferences | null | undefined,
+	repository: NotificationPreferences | null | undefined,
+): ResolvedNotificationPreferences {
+	return {
+		agentComplete: repository?.agentComplete ?? global?.agentComplete ?? true,
+		needsInput: repository?.needsInput ?? global?.needsInput ?? true,
+		quietHours:
+			repository?.quietHours !== undefined
+				? repository.quietHours
+				: (global?.quietHours ?? null),
+		sound: repository?.sound ?? true,
+		digestWindowSeconds: global?.digestWindowSeconds ?? 0,
+	};
+}
+
+function toMinutes(clockTime: string): number {
+	const [hours, minutes] = clockTime.split(":").map(Number);
+	return hours * 60 + minutes;
+}
+
+/**
+ * Whether a wall-clock time falls in [start, end). A window whose end is
+ * earlier than its start wraps past midnight (22:00-07:00); equal bounds
+ * mean no quiet hours.
+ */
+export function isWithinQuietHours(
+	quietHours: { start: string; end: string } | null,
+	time: Pick<ZonedTimeParts, "hour" | "minute">,
+): boolean {
+	if (!quietHours) return false;
+
+	const start = toMinutes(quietHours.start);
+	const end = toMinutes(quietHours.end);
+	const now = time.hour * 60 + time.minute;
+
+	if (start === end) return false;
+	return start < end ? now >= start && now < end : now >= start || now < end;
+}
+
+export function shouldNotify({
+	preferences,
+	kind,
+	time,
+}: {
+	preferences: ResolvedNotificationPreferences;
+	kind: NotificationKind;
+	time: Pick<ZonedTimeParts, "hour" | "minute">;
+}): boolean {
+	return preferences[kind] && !isWithinQuietHours(preferences.quietHours, time);
+}
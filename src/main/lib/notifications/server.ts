@@ -1,8 +1,15 @@
 import { EventEmitter } from "node:events";
+import { and, eq, isNull } from "drizzle-orm";
 import express from "express";
+import { nodes, settings } from "lib/local-db";
 import { NOTIFICATION_EVENTS } from "shared/constants";
 import type { AgentLifecycleEvent } from "shared/notification-types";
 import { appState } from "../app-state";
+import { recordAuditEvent } from "../audit";
+import { createAutomationApiRouter } from "../automation-api";
+import { localDb } from "../local-db";
+import { metricsRegistry } from "../metrics/metrics";
+import { getNodeHookParams, runNodePostHook } from "../node-hooks";
 import { HOOK_PROTOCOL_VERSION } from "../terminal/env";
 
 // Re-export types for backwards compatibility
@@ -17,6 +24,13 @@ export type {
  */
 const SERVER_ENV = process.env.NODE_ENV === "development" ? "development" : "production";
 
+/**
+ * Set by the notify hook script. Browsers only send custom headers after a CORS
+ * preflight, and the preflight below doesn't allow any, so a web page can't pass
+ * itself off as an agent hook.
+ */
+const HOOK_REQUEST_HEADER = "X-Caspian-Hook";
+
 export const notificationsEmitter = new EventEmitter();
 
 const app = express();
@@ -24,7 +38,7 @@ const app = express();
 // Parse JSON request bodies
 app.use(express.json());
 
-// CORS
+// CORS. No Access-Control-Allow-Headers, so preflighted requests with custom headers fail
 app.use((req, res, next) => {
 	res.setHeader("Access-Control-Allow-Origin", "*");
 	res.setHeader("Access-Control-Allow-Methods", "GET, POST, OPTIONS");
@@ -112,6 +126,31 @@ function resolvePaneId(
 	return undefined;
 }
 
+/**
+ * Resolves the target pane and emits a lifecycle event to all listeners
+ * (OS notifications, renderer subscription, metrics).
+ * Shared by the hook endpoint and the dev-only agent simulator.
+ */
+export function emitAgentLifecycle(event: AgentLifecycleEvent): string | undefined {
+	const resolvedPaneId = resolvePaneId(event.paneId, event.tabId, event.nodeId);
+
+	notificationsEmitter.emit(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, {
+		...event,
+		paneId: resolvedPaneId,
+	} satisfies AgentLifecycleEvent);
+
+	return resolvedPaneId;
+}
+
+function isActiveNode(nodeId: string): boolean {
+	const node = localDb
+		.select({ id: nodes.id })
+		.from(nodes)
+		.where(and(eq(nodes.id, nodeId), isNull(nodes.deletingAt)))
+		.get();
+	return node !== undefined;
+}
+
 // Agent lifecycle hook
 app.get("/hook/complete", (req, res) => {
 	const {
@@ -155,20 +194,30 @@ app.get("/hook/complete", (req, res) => {
 		return res.json({ success: true, ignored: true });
 	}
 
-	const resolvedPaneId = resolvePaneId(
-		paneId as string | undefined,
-		tabId as string | undefined,
-		nodeId,
-	);
-
-	const event: AgentLifecycleEvent = {
-		paneId: resolvedPaneId,
+	const resolvedPaneId = emitAgentLifecycle({
+		paneId: paneId as string | undefined,
 		tabId: tabId as string | undefined,
 		nodeId,
 		eventType: mappedEventType,
-	};
-
-	notificationsEmitter.emit(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, event);
+	});
+
+	// Simulated agents call emitAgentLifecycle directly, so only real hooks are audited.
+	// Audit rows and repository hooks need the hook header and a live node.
+	const fromHook = req.get(HOOK_REQUEST_HEADER) === "1";
+	if (nodeId && fromHook && isActiveNode(nodeId)) {
+		const started = mappedEventType === "Start";
+		const needsInput = mappedEventType === "PermissionRequest";
+		recordAuditEvent({
+			type: started ? "agent_started" : needsInput ? "agent_needs_input" : "agent_completed",
+			nodeId,
+			actor: "agent",
+			summary: started ? "Agent started" : needsInput ? "Agent needs input" : "Agent completed",
+			data: { paneId: resolvedPaneId },
+		});
+		if (!started && !needsInput) {
+			runNodePostHook("post-agent-complete", getNodeHookParams(nodeId, { paneId: resolvedPaneId }));
+		}
+	}
 
 	res.json({ success: true, paneId: resolvedPaneId, tabId });
 });
@@ -178,6 +227,20 @@ app.get("/health", (_req, res) => {
 	res.json({ status: "ok" });
 });
 
+// Prometheus scrape endpoint - opt-in via settings, off by default
+app.get("/metrics", (_req, res) => {
+	const row = localDb.select().from(settings).get();
+	if (!row?.metricsEndpointEnabled) {
+		return res.status(404).json({ error: "Not found" });
+	}
+
+	res.setHeader("Content-Type", "text/plain; version=0.0.4; charset=utf-8");
+	res.send(metricsRegistry.render());
+});
+
+// Automation API - opt-in via settings, token-protected
+app.use("/api/v1", createAutomationApiRouter(notificationsEmitter));
+
 // 404
 app.use((_req, res) => {
 	res.status(404).json({ error: "Not found" });
@@ -0,0 +1,57 @@
+import { NOTIFICATION_EVENTS } from "shared/constants";
+import { type AgentScenario, getScenarioSteps } from "./agent-scenarios";
+import { emitAgentLifecycle, notificationsEmitter } from "./server";
+
+const activeTimers = new Set<ReturnType<typeof setTimeout>>();
+
+/**
+ * Replays a scripted agent run through the same path as real hook requests,
+ * so notifications, sounds, pane status and metrics can be exercised without an agent.
+ * Development only.
+ */
+export function simulateAgentScenario({
+	scenario,
+	nodeId,
+	tabId,
+	paneId,
+	speed,
+}: {
+	scenario: AgentScenario;
+	nodeId?: string;
+	tabId?: string;
+	paneId?: string;
+	speed?: number;
+}): boolean {
+	if (process.env.NODE_ENV !== "development") return false;
+
+	let offsetMs = 0;
+	for (const step of getScenarioSteps(scenario, speed)) {
+		offsetMs += step.delayMs;
+		const timer = setTimeout(() => {
+			activeTimers.delete(timer);
+			if (step.kind === "lifecycle") {
+				emitAgentLifecycle({ paneId, tabId, nodeId, eventType: step.eventType });
+			} else if (paneId) {
+				notificationsEmitter.emit(NOTIFICATION_EVENTS.TERMINAL_EXIT, {
+					paneId,
+					exitCode: step.exitCode,
+					reason: step.reason,
+				});
+			}
+		}, offsetMs);
+		activeTimers.add(timer);
+	}
+
+	console.log(`[agent-simulator] Replaying "${scenario}" scenario for node ${nodeId ?? "-"}`);
+	return true;
+}
+
+/** Cancels all pending simulated events */
+export function cancelSimulatedAgents(): number {
+	const count = activeTimers.size;
+	for (const timer of activeTimers) {
+		clearTimeout(timer);
+	}
+	activeTimers.clear();
+	return count;
+}
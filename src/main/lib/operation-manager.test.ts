@@ -0,0 +1,53 @@
+import { describe, expect, test } from "bun:test";
+import type { OperationProgress } from "shared/types/operations";
+import { OperationManager } from "./operation-manager";
+
+describe("OperationManager", () => {
+	test("streams progress and records success", async () => {
+		const manager = new OperationManager();
+		const events: OperationProgress[] = [];
+		manager.on("progress", (progress: OperationProgress) => events.push(progress));
+
+		const result = await manager.run({ id: "op", kind: "clone", title: "Clone" }, async (op) => {
+			op.report("Receiving objects", 50);
+			return "done";
+		});
+
+		expect(result).toBe("done");
+		expect(events.map((event) => [event.status, event.percent])).toEqual([
+			["running", null],
+			["running", 50],
+			["succeeded", 50],
+		]);
+		expect(manager.get("op")?.finishedAt).not.toBeNull();
+	});
+
+	test("cancel aborts the signal and marks the operation cancelled", async () => {
+		const manager = new OperationManager();
+		const run = manager.run({ id: "op", kind: "fetch", title: "Fetch" }, (op) => {
+			return new Promise((_, reject) => {
+				op.signal.addEventListener("abort", () => reject(new Error("aborted")));
+			});
+		});
+
+		expect(manager.cancel("op")).toBe(true);
+		await expect(run).rejects.toThrow("aborted");
+		expect(manager.get("op")?.status).toBe("cancelled");
+		expect(manager.cancel("op")).toBe(false);
+	});
+
+	test("records failures and refuses to cancel non-cancellable operations", async () => {
+		const manager = new OperationManager();
+		const op = manager.begin({ id: "gc", kind: "maintenance", title: "GC", cancellable: false });
+
+		expect(manager.cancel("gc")).toBe(false);
+		op.finish("failed", "boom");
+		expect(manager.get("gc")).toMatchObject({ status: "failed", error: "boom" });
+	});
+
+	test("rejects a second operation with a running id", () => {
+		const manager = new OperationManager();
+		manager.begin({ id: "op", kind: "diff", title: "Diff" });
+		expect(() => manager.begin({ id: "op", kind: "diff", title: "Diff" })).toThrow();
+	});
+});
@@ -0,0 +1,142 @@
+import { randomUUID } from "node:crypto";
+import { EventEmitter } from "node:events";
+import type { OperationKind, OperationProgress, OperationStatus } from "shared/types/operations";
+
+/** Finished operations kept for operations.list, newest first */
+const MAX_FINISHED_OPERATIONS = 20;
+
+export interface OperationOptions {
+	/** Chosen by the caller so it can cancel before the request returns */
+	id?: string;
+	kind: OperationKind;
+	title: string;
+	subjectId?: string;
+	/** False for operations that don't watch their signal */
+	cancellable?: boolean;
+}
+
+export interface OperationHandle {
+	readonly id: string;
+	readonly signal: AbortSignal;
+	report(message: string, percent?: number | null): void;
+	finish(status: Exclude<OperationStatus, "running">, error?: string): void;
+}
+
+/**
+ * Registry of long-running operations with:
+ * - Progress streaming via EventEmitter, one event shape for every kind
+ * - Cancellation by id through an AbortSignal
+ * - A short history of finished operations
+ *
+ * Like the node init manager, state is in-memory only and resets on restart.
+ */
+export class OperationManager extends EventEmitter {
+	private operations = new Map<string, OperationProgress>();
+	private controllers = new Map<string, AbortController>();
+
+	list(): OperationProgress[] {
+		return Array.from(this.operations.values()).sort((a, b) => b.startedAt - a.startedAt);
+	}
+
+	get(operationId: string): OperationProgress | undefined {
+		return this.operations.get(operationId);
+	}
+
+	/** Registers a running operation; the caller must finish it */
+	begin({
+		id = randomUUID(),
+		kind,
+		title,
+		subjectId,
+		cancellable = true,
+	}: OperationOptions): OperationHandle {
+		if (this.controllers.has(id)) {
+			throw new Error(`Operation ${id} is already running`);
+		}
+
+		const controller = new AbortController();
+		this.controllers.set(id, controller);
+		this.update({
+			id,
+			kind,
+			title,
+			subjectId: subjectId ?? null,
+			status: "running",
+			message: null,
+			percent: null,
+			cancellable,
+			startedAt: Date.now(),
+			finishedAt: null,
+			error: null,
+		});
+
+		return {
+			id,
+			signal: controller.signal,
+			report: (message, percent = null) => {
+				const operation = this.operations.get(id);
+				if (operation?.status === "running") {
+					this.update({ ...operation, message, percent });
+				}
+			},
+			finish: (status, error) => this.finish(id, status, error),
+		};
+	}
+
+	/**
+	 * Runs `task` as an operation. It ends as cancelled when the signal was
+	 * aborted, whether the task then returns or throws.
+	 */
+	async run<T>(
+		options: OperationOptions,
+		task: (operation: OperationHandle) => Promise<T>,
+	): Promise<T> {
+		const operation = this.begin(options);
+		try {
+			const result = await task(operation);
+			operation.finish(operation.signal.aborted ? "cancelled" : "succeeded");
+			return result;
+		} catch (error) {
+			const message = error instanceof Error ? error.message : String(error);
+			operation.finish(operation.signal.aborted ? "cancelled" : "failed", message);
+			throw error;
+		}
+	}
+
+	/** Returns false when no cancellable operation with this id is running */
+	cancel(operationId: string): boolean {
+		const controller = this.controllers.get(operationId);
+		if (!controller || !this.operations.get(operationId)?.cancellable) return false;
+		controller.abort();
+		return true;
+	}
+
+	private finish(id: string, status: Exclude<OperationStatus, "running">, error?: string): void {
+		const operation = this.operations.get(id);
+		if (!operation || operation.status !== "running") return;
+
+		this.controllers.delete(id);
+		this.update({
+			...operation,
+			status,
+			finishedAt: Date.now(),
+			error: status === "failed" ? (error ?? null) : null,
+		});
+		this.pruneFinished();
+	}
+
+	private update(operation: OperationProgress): void {
+		this.operations.set(operation.id, operation);
+		this.emit("progress", operation);
+	}
+
+	private pruneFinished(): void {
+		const finished = this.list().filter((operation) => operation.status !== "running");
+		for (const operation of finished.slice(MAX_FINISHED_OPERATIONS)) {
+			this.operations.delete(operation.id);
+		}
+	}
+}
+
+/** Singleton operation manager instance */
+export const operationManager = new OperationManager();
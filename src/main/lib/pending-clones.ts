@@ -0,0 +1,105 @@
+import { existsSync } from "node:fs";
+import { join } from "node:path";
+import { eq } from "drizzle-orm";
+import { repositories } from "lib/local-db";
+import simpleGit, { type SimpleGit } from "simple-git";
+import { localDb } from "./local-db";
+
+/**
+ * Written into .git/config by `git clone --config` before anything is
+ * fetched and removed once the clone finishes, so a folder carrying it
+ * holds a clone this app started and never handed to the user.
+ */
+export const PENDING_CLONE_CONFIG_KEY = "caspian.pendingclone";
+
+/** Keeps `git hash-object` argument lists well under the OS limit */
+const HASH_BATCH_SIZE = 200;
+
+function splitNul(output: string): string[] {
+	return output.split("\0").filter((entry) => entry.length > 0);
+}
+
+/**
+ * A partial checkout only lacks files, or has written some of them without
+ * updating the index yet. Anything else in the worktree came from someone
+ * other than git clone.
+ */
+async function isWorktreeUntouched(git: SimpleGit): Promise<boolean> {
+	const status = splitNul(await git.raw(["status", "--porcelain", "-z", "--untracked-files=all"]));
+	const untracked: string[] = [];
+	for (const entry of status) {
+		const code = entry.slice(0, 2);
+		if (code === "??") {
+			untracked.push(entry.slice(3));
+		} else if (!/^[ D]{2}$/.test(code)) {
+			return false;
+		}
+	}
+	if (untracked.length === 0) return true;
+
+	const checkedOut = new Map<string, string>();
+	for (const line of splitNul(await git.raw(["ls-tree", "-r", "-z", "HEAD"]))) {
+		const [info, path] = line.split("\t");
+		checkedOut.set(path, info.split(" ")[2]);
+	}
+	for (let start = 0; start < untracked.length; start += HASH_BATCH_SIZE) {
+		const batch = untracked.slice(start, start + HASH_BATCH_SIZE);
+		const hashes = (await git.raw(["hash-object", "--", ...batch])).trim().split("\n");
+		if (batch.some((path, i) => checkedOut.get(path) !== hashes[i])) return false;
+	}
+	return true;
+}
+
+/**
+ * Whether `clonePath` holds an unfinished clone this app started for
+ * `repositoryId` that nobody has worked in since, and so can be resumed or
+ * removed without losing anything.
+ */
+export async function isUntouchedPendingClone({
+	clonePath,
+	repositoryId,
+	url,
+}: {
+	clonePath: string;
+	repositoryId: string;
+	/** Checked against origin when known */
+	url?: string;
+}): Promise<boolean> {
+	if (!existsSync(join(clonePath, ".git"))) return false;
+
+	const git = simpleGit(clonePath);
+	try {
+		const { value } = await git.getConfig(PENDING_CLONE_CONFIG_KEY, "local");
+		if (value !== repositoryId) return false;
+		if (url !== undefined && (await git.remote(["get-url", "origin"]))?.trim() !== url) {
+			return false;
+		}
+		return await isWorktreeUntouched(git);
+	} catch (error) {
+		console.warn(`[pending-clones] Could not inspect ${clonePath}, leaving it alone:`, error);
+		return false;
+	}
+}
+
+/**
+ * Runs at startup for clones a quit left in "cloning". Rows whose folder is
+ * gone or has been worked in are dropped, leaving the folder as it is; the
+ * rest stay so cloning to the same path again resumes them.
+ */
+export async function sweepInterruptedClones(): Promise<void> {
+	const pending = localDb
+		.select()
+		.from(repositories)
+		.where(eq(repositories.initStatus, "cloning"))
+		.all();
+
+	for (const repository of pending) {
+		const resumable = await isUntouchedPendingClone({
+			clonePath: repository.mainRepoPath,
+			repositoryId: repository.id,
+		});
+		if (resumable) continue;
+		localDb.delete(repositories).where(eq(repositories.id, repository.id)).run();
+		console.log(`[pending-clones] Dropped interrupted clone record for ${repository.mainRepoPath}`);
+	}
+}
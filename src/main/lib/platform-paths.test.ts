@@ -0,0 +1,63 @@
+import { describe, expect, test } from "bun:test";
+import {
+	getPathBasename,
+	isPathInside,
+	normalizePathForComparison,
+	pathsEqual,
+	stripLongPathPrefix,
+	toNativePath,
+} from "./platform-paths";
+
+describe("stripLongPathPrefix", () => {
+	test("removes drive and UNC long path prefixes", () => {
+		expect(stripLongPathPrefix("\\\\?\\C:\\repo")).toBe("C:\\repo");
+		expect(stripLongPathPrefix("\\\\?\\UNC\\server\\share\\repo")).toBe("\\\\server\\share\\repo");
+		expect(stripLongPathPrefix("/home/user/repo")).toBe("/home/user/repo");
+	});
+});
+
+describe("toNativePath", () => {
+	test("converts git's forward slashes on Windows", () => {
+		expect(toNativePath("C:/Users/dev/repo", "win32")).toBe("C:\\Users\\dev\\repo");
+	});
+
+	test("leaves POSIX paths untouched", () => {
+		expect(toNativePath("/Users/dev/repo", "darwin")).toBe("/Users/dev/repo");
+	});
+});
+
+describe("pathsEqual", () => {
+	test("is case- and separator-insensitive on Windows", () => {
+		expect(pathsEqual("C:/Users/Dev/Repo/", "c:\\users\\dev\\repo", "win32")).toBe(true);
+		expect(pathsEqual("\\\\?\\C:\\repo", "C:\\repo", "win32")).toBe(true);
+	});
+
+	test("is case-sensitive on POSIX", () => {
+		expect(pathsEqual("/repo/Wt", "/repo/wt", "linux")).toBe(false);
+		expect(pathsEqual("/repo/wt/", "/repo/wt", "linux")).toBe(true);
+	});
+
+	test("keeps roots intact", () => {
+		expect(normalizePathForComparison("/", "linux")).toBe("/");
+		expect(normalizePathForComparison("C:\\", "win32")).toBe("c:\\");
+	});
+});
+
+describe("isPathInside", () => {
+	test("matches descendants but not siblings with a shared prefix", () => {
+		expect(isPathInside("/repo/wt", "/repo/wt/src/a.ts", "linux")).toBe(true);
+		expect(isPathInside("/repo/wt", "/repo/wt", "linux")).toBe(true);
+		expect(isPathInside("/repo/wt", "/repo/wt-2/a.ts", "linux")).toBe(false);
+	});
+
+	test("handles mixed separators and case on Windows", () => {
+		expect(isPathInside("C:\\Repo\\WT", "c:/repo/wt/src/a.ts", "win32")).toBe(true);
+	});
+});
+
+describe("getPathBasename", () => {
+	test("splits on either separator on Windows", () => {
+		expect(getPathBasename("C:/repo/worktrees/feature", "win32")).toBe("feature");
+		expect(getPathBasename("/repo/worktrees/feature", "linux")).toBe("feature");
+	});
+});
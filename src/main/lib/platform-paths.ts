@@ -0,0 +1,98 @@
+import { posix, win32 } from "node:path";
+
+/**
+ * Path helpers that behave correctly on Windows as well as macOS/Linux.
+ *
+ * Git reports Windows paths with forward slashes (C:/repo/wt) while Node APIs and the
+ * database use backslashes (C:\repo\wt), and NTFS compares paths case-insensitively.
+ * Anything that compares or prefix-matches filesystem paths should go through here
+ * instead of using string equality.
+ *
+ * Each function takes an optional platform so behavior can be tested on any OS.
+ */
+
+const LONG_PATH_PREFIX = "\\\\?\\";
+const LONG_UNC_PREFIX = "\\\\?\\UNC\\";
+
+function pathApi(platform: NodeJS.Platform) {
+	return platform === "win32" ? win32 : posix;
+}
+
+/**
+ * Removes the Win32 long path prefix: `\\?\C:\x` becomes `C:\x`
+ * and `\\?\UNC\server\share` becomes `\\server\share`.
+ */
+export function stripLongPathPrefix(path: string): string {
+	if (path.startsWith(LONG_UNC_PREFIX)) {
+		return `\\\\${path.slice(LONG_UNC_PREFIX.length)}`;
+	}
+	if (path.startsWith(LONG_PATH_PREFIX)) {
+		return path.slice(LONG_PATH_PREFIX.length);
+	}
+	return path;
+}
+
+/**
+ * Converts a path to the platform's native form: on Windows, forward slashes from git
+ * output become backslashes and the long path prefix is removed.
+ */
+export function toNativePath(path: string, platform: NodeJS.Platform = process.platform): string {
+	if (platform !== "win32") return path;
+	return win32.normalize(stripLongPathPrefix(path));
+}
+
+/**
+ * Canonical form for comparing paths: native separators, no trailing separator,
+ * and lowercased on Windows.
+ */
+export function normalizePathForComparison(
+	path: string,
+	platform: NodeJS.Platform = process.platform,
+): string {
+	const api = pathApi(platform);
+	let normalized = api.normalize(toNativePath(path, platform));
+
+	const root = api.parse(normalized).root;
+	while (normalized.length > root.length && normalized.endsWith(api.sep)) {
+		normalized = normalized.slice(0, -1);
+	}
+
+	return platform === "win32" ? normalized.toLowerCase() : normalized;
+}
+
+export function pathsEqual(
+	a: string,
+	b: string,
+	platform: NodeJS.Platform = process.platform,
+): boolean {
+	return normalizePathForComparison(a, platform) === normalizePathForComparison(b, platform);
+}
+
+/**
+ * Whether `child` is `parent` or somewhere beneath it.
+ * Separator-aware, so /repo/wt-2 is not inside /repo/wt.
+ */
+export function isPathInside(
+	parent: string,
+	child: string,
+	platform: NodeJS.Platform = process.platform,
+): boolean {
+	const api = pathApi(platform);
+	const normalizedParent = normalizePathForComparison(parent, platform);
+	const normalizedChild = normalizePathForComparison(child, platform);
+
+	if (normalizedChild === normalizedParent) return true;
+	const prefix = normalizedParent.endsWith(api.sep)
+		? normalizedParent
+		: `${normalizedParent}${api.sep}`;
+	return normalizedChild.startsWith(prefix);
+}
+
+/** Last segment of a path, accepting either separator on Windows */
+export function getPathBasename(
+	path: string,
+	platform: NodeJS.Platform = process.platform,
+): string {
+	const api = pathApi(platform);
+	return api.basename(toNativePath(path, platform));
+}
@@ -0,0 +1,57 @@
+import { settings, type TelemetryLevel } from "lib/local-db";
+import { DEFAULT_TELEMETRY_LEVEL } from "shared/constants";
+import { localDb } from "./local-db";
+
+/**
+ * Privacy settings, read on hot paths (analytics, error reporting), so
+ * they're cached and only reloaded when changed through setPrivacySettings.
+ *
+ * Local-only mode forces telemetry off and stops the network traffic
+ * Caspian starts on its own: update checks, webhooks, pull request status
+ * polling and issue tracker calls. Git and agent commands the user runs
+ * are never blocked.
+ */
+export interface PrivacySettings {
+	telemetryLevel: TelemetryLevel;
+	localOnly: boolean;
+}
+
+let cached: PrivacySettings | null = null;
+
+export function getPrivacySettings(): PrivacySettings {
+	if (!cached) {
+		const row = localDb.select().from(settings).get();
+		cached = {
+			telemetryLevel: row?.telemetryLevel ?? DEFAULT_TELEMETRY_LEVEL,
+			localOnly: row?.localOnlyMode ?? false,
+		};
+	}
+	return cached;
+}
+
+export function setPrivacySettings(patch: Partial<PrivacySettings>): PrivacySettings {
+	const values = {
+		...(patch.telemetryLevel !== undefined && { telemetryLevel: patch.telemetryLevel }),
+		...(patch.localOnly !== undefined && { localOnlyMode: patch.localOnly }),
+	};
+	if (Object.keys(values).length > 0) {
+		localDb
+			.insert(settings)
+			.values({ id: 1, ...values })
+			.onConflictDoUpdate({ target: settings.id, set: values })
+			.run();
+	}
+
+	cached = null;
+	return getPrivacySettings();
+}
+
+export function isLocalOnlyMode(): boolean {
+	return getPrivacySettings().localOnly;
+}
+
+/** The level telemetry actually runs at: always "off" in local-only mode */
+export function getTelemetryLevel(): TelemetryLevel {
+	const { telemetryLevel, localOnly } = getPrivacySettings();
+	return localOnly ? "off" : telemetryLevel;
+}
@@ -0,0 +1,42 @@
+import { EventEmitter } from "node:events";
+import type { QualityGateProgress } from "shared/types/quality-gate";
+
+/**
+ * Tracks quality gate runs with:
+ * - Progress streaming via EventEmitter
+ * - Guarding against overlapping runs on the same node
+ *
+ * Like the node init manager, state is in-memory only and resets on restart.
+ * Completed results are persisted in the gate_runs table.
+ */
+class QualityGateManager extends EventEmitter {
+	private progress = new Map<string, QualityGateProgress>();
+	private running = new Set<string>();
+
+	getAllProgress(): QualityGateProgress[] {
+		return Array.from(this.progress.values());
+	}
+
+	/**
+	 * Mark a run as started. Returns false if one is already in flight.
+	 */
+	begin(nodeId: string): boolean {
+		if (this.running.has(nodeId)) {
+			return false;
+		}
+		this.running.add(nodeId);
+		return true;
+	}
+
+	finish(nodeId: string): void {
+		this.running.delete(nodeId);
+	}
+
+	updateProgress(progress: QualityGateProgress): void {
+		this.progress.set(progress.nodeId, progress);
+		this.emit("progress", progress);
+	}
+}
+
+/** Singleton quality gate manager instance */
+export const qualityGateManager = new QualityGateManager();
@@ -0,0 +1,63 @@
+import { type OutputRedactionMode, settings } from "lib/local-db";
+import { DEFAULT_OUTPUT_REDACTION } from "shared/constants";
+import {
+	type RedactionEdit,
+	redactHighEntropy,
+	redactSecrets,
+	redactTerminalOutput,
+} from "shared/utils/redact";
+import { localDb } from "./local-db";
+
+/**
+ * Scrubs secrets from command and agent output before Caspian stores it:
+ * terminal history, test and gate output, audit events. What the terminal
+ * shows live is never changed. The mode is read on every terminal write, so
+ * it's cached and only reloaded when changed through setOutputRedactionMode.
+ */
+let cached: OutputRedactionMode | null = null;
+
+export function getOutputRedactionMode(): OutputRedactionMode {
+	if (!cached) {
+		const row = localDb.select().from(settings).get();
+		cached = row?.outputRedaction ?? DEFAULT_OUTPUT_REDACTION;
+	}
+	return cached;
+}
+
+export function setOutputRedactionMode(mode: OutputRedactionMode): void {
+	localDb
+		.insert(settings)
+		.values({ id: 1, outputRedaction: mode })
+		.onConflictDoUpdate({ target: settings.id, set: { outputRedaction: mode } })
+		.run();
+	cached = mode;
+}
+
+function redactWithMode(mode: OutputRedactionMode, text: string, edits?: RedactionEdit[]): string {
+	if (mode === "off") return text;
+	const redacted = redactSecrets(text, edits);
+	return mode === "strict" ? redactHighEntropy(redacted, edits) : redacted;
+}
+
+export function redactOutput(text: string): string {
+	return redactWithMode(getOutputRedactionMode(), text);
+}
+
+/** redactOutput for raw PTY data, which can have escape sequences anywhere in it */
+export function redactTerminalData(data: string): string {
+	const mode = getOutputRedactionMode();
+	if (mode === "off") return data;
+	return redactTerminalOutput(data, (plain, edits) => redactWithMode(mode, plain, edits));
+}
+
+/** redactOutput applied to every string inside JSON-like data */
+export function redactOutputData<T>(value: T): T {
+	if (typeof value === "string") return redactOutput(value) as T;
+	if (Array.isArray(value)) return value.map(redactOutputData) as T;
+	if (value && typeof value === "object") {
+		return Object.fromEntries(
+			Object.entries(value).map(([key, entry]) => [key, redactOutputData(entry)]),
+		) as T;
+	}
+	return value;
+}
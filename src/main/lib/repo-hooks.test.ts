@@ -0,0 +1,71 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { chmodSync, mkdirSync, mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { executeHook, findHookExecutable, HookFailedError } from "./repo-hooks";
+
+// Hook scripts are shell scripts here; Windows resolves .cmd/.bat hooks instead
+describe.skipIf(process.platform === "win32")("repository hooks", () => {
+	let repoPath: string;
+	let hooksDir: string;
+
+	beforeEach(() => {
+		repoPath = mkdtempSync(join(tmpdir(), "caspian-hooks-"));
+		hooksDir = join(repoPath, ".caspian", "hooks");
+		mkdirSync(hooksDir, { recursive: true });
+	});
+
+	afterEach(() => {
+		rmSync(repoPath, { recursive: true, force: true });
+	});
+
+	function writeHook(name: string, script: string, executable = true): string {
+		const path = join(hooksDir, name);
+		writeFileSync(path, `#!/bin/sh\n${script}\n`);
+		chmodSync(path, executable ? 0o755 : 0o644);
+		return path;
+	}
+
+	test("finds only hooks that exist and are executable", () => {
+		const path = writeHook("pre-push", "exit 0");
+		writeHook("post-node-create", "exit 0", false);
+
+		expect(findHookExecutable(repoPath, "pre-push")).toBe(path);
+		expect(findHookExecutable(repoPath, "post-node-create")).toBeNull();
+		expect(findHookExecutable(repoPath, "pre-node-delete")).toBeNull();
+	});
+
+	test("passes the payload on stdin and reports the exit code", async () => {
+		const path = writeHook("pre-push", 'cat; echo "blocked" >&2; exit 3');
+
+		const result = await executeHook({
+			hook: "pre-push",
+			path,
+			cwd: repoPath,
+			input: '{"nodeId":"node-1"}',
+			env: process.env,
+		});
+
+		expect(result.exitCode).toBe(3);
+		expect(result.timedOut).toBe(false);
+		expect(result.output).toContain('{"nodeId":"node-1"}');
+		expect(result.output).toContain("blocked");
+		expect(new HookFailedError(result).message).toStartWith("The pre-push hook exited with 3");
+	});
+
+	test("kills hooks that run past the timeout", async () => {
+		const path = writeHook("pre-push", "exec sleep 5");
+
+		const result = await executeHook({
+			hook: "pre-push",
+			path,
+			cwd: repoPath,
+			input: "{}",
+			env: process.env,
+			timeoutMs: 100,
+		});
+
+		expect(result.timedOut).toBe(true);
+		expect(result.exitCode).toBeNull();
+	});
+});
@@ -0,0 +1,194 @@
+import { spawn } from "node:child_process";
+import { accessSync, constants, existsSync } from "node:fs";
+import { join } from "node:path";
+import type { SelectNode, SelectRepository } from "lib/local-db";
+import { CaspianError } from "lib/trpc/errors";
+import { tailOutput } from "lib/trpc/routers/nodes/utils/run-command";
+import { getShellEnvironment } from "lib/trpc/routers/nodes/utils/shell-env";
+import { REPOSITORY_CASPIAN_DIR_NAME } from "shared/constants";
+
+/**
+ * Repository hooks: executables in .caspian/hooks (in the main repo, next to
+ * config.json) named after a lifecycle event. Each gets a JSON payload on
+ * stdin. A failing pre-* hook blocks the operation; post-* hooks are
+ * fire-and-forget and only logged.
+ */
+
+export const REPO_HOOKS = [
+	"post-node-create",
+	"pre-node-delete",
+	"post-node-delete",
+	"pre-push",
+	"pre-create-pr",
+	"post-agent-complete",
+] as const;
+
+export type RepoHook = (typeof REPO_HOOKS)[number];
+
+export const HOOKS_DIR_NAME = "hooks";
+
+const HOOK_TIMEOUT_MS = 60_000;
+
+/** Windows can't run extensionless scripts, so hooks there need one of these */
+const WINDOWS_HOOK_EXTENSIONS = [".exe", ".cmd", ".bat"];
+
+export interface RepoHookResult {
+	hook: RepoHook;
+	/** null when the hook was killed (timeout) or couldn't start */
+	exitCode: number | null;
+	output: string;
+	timedOut: boolean;
+}
+
+export class HookFailedError extends CaspianError {
+	constructor(readonly result: RepoHookResult) {
+		const reason = result.timedOut ? "timed out" : `exited with ${result.exitCode ?? "an error"}`;
+		const output = result.output.trim();
+		super({
+			code: "HOOK_FAILED",
+			message: `The ${result.hook} hook ${reason}${output ? `:\n${output}` : ""}`,
+			hint: `Fix or remove ${REPOSITORY_CASPIAN_DIR_NAME}/${HOOKS_DIR_NAME}/${result.hook}`,
+		});
+		this.name = "HookFailedError";
+	}
+}
+
+function isExecutable(path: string): boolean {
+	try {
+		accessSync(path, constants.X_OK);
+		return true;
+	} catch {
+		return false;
+	}
+}
+
+/** Path of the hook's executable, or null when the repository doesn't define it */
+export function findHookExecutable(mainRepoPath: string, hook: RepoHook): string | null {
+	const base = join(mainRepoPath, REPOSITORY_CASPIAN_DIR_NAME, HOOKS_DIR_NAME, hook);
+
+	if (process.platform === "win32") {
+		return (
+			WINDOWS_HOOK_EXTENSIONS.map((extension) => base + extension).find((path) =>
+				existsSync(path),
+			) ?? null
+		);
+	}
+
+	if (!existsSync(base)) return null;
+	if (!isExecutable(base)) {
+		console.warn(`[repo-hooks] Skipping ${base}: not executable (chmod +x to enable it)`);
+		return null;
+	}
+	return base;
+}
+
+/** Runs an executable with `input` on stdin. Never rejects */
+export function executeHook({
+	hook,
+	path,
+	cwd,
+	input,
+	env,
+	timeoutMs = HOOK_TIMEOUT_MS,
+}: {
+	hook: RepoHook;
+	path: string;
+	cwd: string;
+	input: string;
+	env: Record<string, string | undefined>;
+	timeoutMs?: number;
+}): Promise<RepoHookResult> {
+	return new Promise((resolve) => {
+		const chunks: string[] = [];
+		const child = spawn(path, [], {
+			cwd,
+			env,
+			// .cmd and .bat files only run through cmd.exe
+			shell: /\.(cmd|bat)$/i.test(path),
+			timeout: timeoutMs,
+		});
+
+		child.stdout.setEncoding("utf8").on("data", (chunk: string) => chunks.push(chunk));
+		child.stderr.setEncoding("utf8").on("data", (chunk: string) => chunks.push(chunk));
+		// Hooks that exit without reading stdin close the pipe early
+		child.stdin.on("error", () => {});
+		child.stdin.end(input);
+
+		let settled = false;
+		const finish = (exitCode: number | null, timedOut: boolean) => {
+			if (settled) return;
+			settled = true;
+			resolve({ hook, exitCode, output: tailOutput(chunks.join("")), timedOut });
+		};
+		child.once("error", (error) => {
+			chunks.push(error.message);
+			finish(null, false);
+		});
+		child.once("close", (code, signal) => finish(code, code === null && signal === "SIGTERM"));
+	});
+}
+
+/** Runs the hook if the repository defines one; null when it doesn't */
+export async function runRepoHook({
+	hook,
+	mainRepoPath,
+	cwd,
+	payload,
+}: {
+	hook: RepoHook;
+	mainRepoPath: string;
+	cwd: string;
+	payload: Record<string, unknown>;
+}): Promise<RepoHookResult | null> {
+	const path = findHookExecutable(mainRepoPath, hook);
+	if (!path) return null;
+
+	const shellEnv = await getShellEnvironment();
+	const result = await executeHook({
+		hook,
+		path,
+		cwd: existsSync(cwd) ? cwd : mainRepoPath,
+		input: JSON.stringify({ hook, ...payload }),
+		env: { ...shellEnv, CASPIAN_HOOK: hook, CASPIAN_ROOT_PATH: mainRepoPath },
+	});
+	if (result.exitCode !== 0) {
+		console.warn(`[repo-hooks] ${hook} failed (exit ${result.exitCode}):`, result.output);
+	}
+	return result;
+}
+
+/** Throws HookFailedError when the hook exists and fails, blocking the operation */
+export async function runPreHook(params: Parameters<typeof runRepoHook>[0]): Promise<void> {
+	const result = await runRepoHook(params);
+	if (result && result.exitCode !== 0) {
+		throw new HookFailedError(result);
+	}
+}
+
+export function runPostHook(params: Parameters<typeof runRepoHook>[0]): void {
+	void runRepoHook(params).catch((error) => {
+		console.error(`[repo-hooks] Failed to run ${params.hook}:`, error);
+	});
+}
+
+/** Standard payload fields describing a node */
+export function getNodeHookPayload({
+	node,
+	repository,
+	path,
+}: {
+	node: SelectNode;
+	repository: SelectRepository;
+	path: string | null;
+}): Record<string, unknown> {
+	return {
+		nodeId: node.id,
+		nodeName: node.name,
+		nodeType: node.type,
+		branch: node.branch,
+		path,
+		repositoryId: repository.id,
+		repositoryName: repository.name,
+		mainRepoPath: repository.mainRepoPath,
+	};
+}
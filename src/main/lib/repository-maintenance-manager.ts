@@ -0,0 +1,80 @@
+import { EventEmitter } from "node:events";
+import type { RepositoryMaintenanceProgress } from "shared/types/repo-maintenance";
+
+/** Worktree removals before a scheduled maintenance run is considered */
+const CHURN_THRESHOLD = 10;
+/** Minimum time between scheduled maintenance runs for the same repository */
+const MIN_SCHEDULED_INTERVAL_MS = 6 * 60 * 60 * 1000;
+
+/**
+ * Tracks repository maintenance runs with:
+ * - Progress streaming via EventEmitter
+ * - Worktree churn counting to decide when a scheduled run is due
+ * - Guarding against overlapping runs on the same repository
+ *
+ * Like the node init manager, state is in-memory only and resets on restart.
+ */
+class RepositoryMaintenanceManager extends EventEmitter {
+	private progress = new Map<string, RepositoryMaintenanceProgress>();
+	private running = new Set<string>();
+	private churn = new Map<string, number>();
+	private lastRunAt = new Map<string, number>();
+
+	isRunning(repositoryId: string): boolean {
+		return this.running.has(repositoryId);
+	}
+
+	getProgress(repositoryId: string): RepositoryMaintenanceProgress | undefined {
+		return this.progress.get(repositoryId);
+	}
+
+	getAllProgress(): RepositoryMaintenanceProgress[] {
+		return Array.from(this.progress.values());
+	}
+
+	/**
+	 * Mark a run as started. Returns false if one is already in flight.
+	 */
+	begin(repositoryId: string): boolean {
+		if (this.running.has(repositoryId)) {
+			return false;
+		}
+		this.running.add(repositoryId);
+		return true;
+	}
+
+	/**
+	 * Mark a run as finished and reset churn tracking for the repository.
+	 */
+	finish(repositoryId: string, success: boolean): void {
+		this.running.delete(repositoryId);
+		if (success) {
+			this.churn.delete(repositoryId);
+			this.lastRunAt.set(repositoryId, Date.now());
+		}
+	}
+
+	updateProgress(progress: RepositoryMaintenanceProgress): void {
+		this.progress.set(progress.repositoryId, progress);
+		this.emit("progress", progress);
+	}
+
+	/**
+	 * Record a worktree removal. Returns true when enough churn has built up
+	 * that a scheduled maintenance run should be started.
+	 */
+	recordChurn(repositoryId: string): boolean {
+		const count = (this.churn.get(repositoryId) ?? 0) + 1;
+		this.churn.set(repositoryId, count);
+
+		if (count < CHURN_THRESHOLD || this.running.has(repositoryId)) {
+			return false;
+		}
+
+		const lastRunAt = this.lastRunAt.get(repositoryId);
+		return lastRunAt === undefined || Date.now() - lastRunAt >= MIN_SCHEDULED_INTERVAL_MS;
+	}
+}
+
+/** Singleton repository maintenance manager instance */
+export const repositoryMaintenanceManager = new RepositoryMaintenanceManager();
@@ -0,0 +1,168 @@
+import { describe, expect, test } from "bun:test";
+import type { BaseTab, Pane } from "shared/tabs-types";
+import {
+	collectAgentPanes,
+	findSilentSessions,
+	getResumeCommand,
+	listAgentPanes,
+	resetStalePaneStatuses,
+} from "./collect";
+
+function tab(id: string, nodeId: string): BaseTab {
+	return { id, nodeId, name: id, createdAt: 0 };
+}
+
+function pane(id: string, tabId: string, overrides: Partial<Pane> = {}): Pane {
+	return { id, tabId, type: "terminal", name: `Terminal ${id}`, ...overrides };
+}
+
+function reportPane(paneId: string, tabId: string) {
+	return { paneId, tabId, name: `Terminal ${paneId}`, resumeCommand: null };
+}
+
+const tabs = [tab("tab-a", "node-a"), tab("tab-b", "node-b")];
+
+describe("getResumeCommand", () => {
+	test("recognizes agents from the terminal title", () => {
+		expect(getResumeCommand("✳ Claude Code")).toBe("claude --continue");
+		expect(getResumeCommand("codex")).toBe("codex resume --last");
+		expect(getResumeCommand("opencode - fix tests")).toBe("opencode --continue");
+	});
+
+	test("returns null for anything else", () => {
+		expect(getResumeCommand("Terminal")).toBeNull();
+		expect(getResumeCommand("zsh")).toBeNull();
+	});
+});
+
+describe("listAgentPanes", () => {
+	test("lists working and prompting terminal panes with their node", () => {
+		const result = listAgentPanes({
+			tabs,
+			panes: {
+				p1: pane("p1", "tab-a", { status: "working", name: "claude" }),
+				p2: pane("p2", "tab-b", { status: "permission" }),
+				idle: pane("idle", "tab-a", { status: "idle" }),
+				review: pane("review", "tab-a", { status: "review" }),
+				orphan: pane("orphan", "tab-gone", { status: "working" }),
+				web: pane("web", "tab-b", { type: "webview", status: "working" }),
+			},
+		});
+
+		expect(result).toEqual([
+			{
+				paneId: "p1",
+				tabId: "tab-a",
+				name: "claude",
+				resumeCommand: "claude --continue",
+				nodeId: "node-a",
+				status: "working",
+			},
+			{ ...reportPane("p2", "tab-b"), nodeId: "node-b", status: "permission" },
+		]);
+	});
+});
+
+describe("collectAgentPanes", () => {
+	test("reports working and prompting agents whose session is gone as interrupted", () => {
+		const result = collectAgentPanes(
+			{
+				tabs,
+				panes: {
+					p1: pane("p1", "tab-a", { status: "working" }),
+					p2: pane("p2", "tab-a", { status: "permission" }),
+				},
+			},
+			new Set(),
+		);
+
+		expect(result.get("node-a")).toEqual({
+			interruptedAgents: [reportPane("p1", "tab-a"), reportPane("p2", "tab-a")],
+			pendingInput: [],
+		});
+	});
+
+	test("reports prompting agents with a live session as pending input", () => {
+		const result = collectAgentPanes(
+			{ tabs, panes: { p1: pane("p1", "tab-b", { status: "permission" }) } },
+			new Set(["p1"]),
+		);
+
+		expect(result.get("node-b")).toEqual({
+			interruptedAgents: [],
+			pendingInput: [reportPane("p1", "tab-b")],
+		});
+	});
+
+	test("skips agents still working in a live session", () => {
+		const result = collectAgentPanes(
+			{ tabs, panes: { alive: pane("alive", "tab-a", { status: "working" }) } },
+			new Set(["alive"]),
+		);
+
+		expect(result.size).toBe(0);
+	});
+
+	test("adds agents stopped on quit once, even after their pane went idle", () => {
+		const stopped = [
+			{ ...reportPane("p1", "tab-a"), nodeId: "node-a", interruptedAt: 1 },
+			{ ...reportPane("p2", "tab-a"), nodeId: "node-a", interruptedAt: 1 },
+		];
+		const result = collectAgentPanes(
+			{
+				tabs,
+				panes: {
+					p1: pane("p1", "tab-a", { status: "working" }),
+					p2: pane("p2", "tab-a", { status: "idle" }),
+				},
+			},
+			new Set(),
+			stopped,
+		);
+
+		expect(result.get("node-a")?.interruptedAgents).toEqual([
+			reportPane("p1", "tab-a"),
+			reportPane("p2", "tab-a"),
+		]);
+	});
+});
+
+describe("resetStalePaneStatuses", () => {
+	test("resets agent statuses only where the session is gone", () => {
+		const panes = {
+			dead: pane("dead", "tab-a", { status: "working" }),
+			prompt: pane("prompt", "tab-a", { status: "permission" }),
+			alive: pane("alive", "tab-a", { status: "working" }),
+			review: pane("review", "tab-a", { status: "review" }),
+		};
+
+		const result = resetStalePaneStatuses(panes, new Set(["alive"]));
+
+		expect(result.dead.status).toBe("idle");
+		expect(result.prompt.status).toBe("idle");
+		expect(result.alive).toBe(panes.alive);
+		expect(result.review).toBe(panes.review);
+	});
+});
+
+describe("findSilentSessions", () => {
+	const quitAt = Date.parse("2026-01-05T10:00:00.000Z");
+
+	test("finds live sessions with no output since quitting", () => {
+		const sessions = [
+			{ sessionId: "quiet", isAlive: true, lastOutputAt: "2026-01-05T09:59:00.000Z" },
+			{ sessionId: "never", isAlive: true, lastOutputAt: null },
+			{ sessionId: "busy", isAlive: true, lastOutputAt: "2026-01-05T10:01:00.000Z" },
+			{ sessionId: "dead", isAlive: false, lastOutputAt: null },
+			{ sessionId: "old-daemon", isAlive: true },
+		];
+
+		expect(findSilentSessions(sessions, quitAt)).toEqual(new Set(["quiet", "never"]));
+	});
+
+	test("trusts every session after a crash", () => {
+		const sessions = [{ sessionId: "quiet", isAlive: true, lastOutputAt: null }];
+
+		expect(findSilentSessions(sessions, null).size).toBe(0);
+	});
+});
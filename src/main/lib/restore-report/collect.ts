@@ -0,0 +1,128 @@
+import type { BaseTabsState, Pane } from "shared/tabs-types";
+import type { InterruptedAgent, RestoreReportPane } from "shared/types/restore-report";
+
+type TabsSnapshot = Pick<BaseTabsState, "tabs" | "panes">;
+
+export interface AgentPane extends RestoreReportPane {
+	nodeId: string;
+	status: "working" | "permission";
+}
+
+export interface NodeAgentPanes {
+	interruptedAgents: RestoreReportPane[];
+	pendingInput: RestoreReportPane[];
+}
+
+const RESUME_COMMANDS: Array<[RegExp, string]> = [
+	[/\bclaude\b/i, "claude --continue"],
+	[/\bcodex\b/i, "codex resume --last"],
+	[/\bopencode\b/i, "opencode --continue"],
+];
+
+/** Agents set the terminal title, which becomes the pane name */
+export function getResumeCommand(paneName: string): string | null {
+	return RESUME_COMMANDS.find(([pattern]) => pattern.test(paneName))?.[1] ?? null;
+}
+
+function isAgentStatus(status: Pane["status"]): status is AgentPane["status"] {
+	return status === "working" || status === "permission";
+}
+
+/** Terminal panes an agent was working in or prompting from, with their node */
+export function listAgentPanes(tabsState: TabsSnapshot): AgentPane[] {
+	const nodeIdByTabId = new Map(tabsState.tabs.map((tab) => [tab.id, tab.nodeId]));
+	const agentPanes: AgentPane[] = [];
+
+	for (const pane of Object.values(tabsState.panes)) {
+		if (pane.type !== "terminal" || !isAgentStatus(pane.status)) continue;
+
+		const nodeId = nodeIdByTabId.get(pane.tabId);
+		if (!nodeId) continue;
+
+		agentPanes.push({
+			paneId: pane.id,
+			tabId: pane.tabId,
+			name: pane.name,
+			resumeCommand: getResumeCommand(pane.name),
+			nodeId,
+			status: pane.status,
+		});
+	}
+
+	return agentPanes;
+}
+
+/**
+ * Groups the previous session's agent panes by node. An agent is
+ * interrupted when the daemon no longer has its session, or when quitting
+ * stopped it (its pane may have gone idle since); one still waiting on a
+ * prompt with a live session needs the user's input.
+ */
+export function collectAgentPanes(
+	tabsState: TabsSnapshot,
+	aliveSessionIds: ReadonlySet<string>,
+	stoppedOnQuit: InterruptedAgent[] = [],
+): Map<string, NodeAgentPanes> {
+	const byNode = new Map<string, NodeAgentPanes>();
+	const getEntry = (nodeId: string) => {
+		let entry = byNode.get(nodeId);
+		if (!entry) {
+			entry = { interruptedAgents: [], pendingInput: [] };
+			byNode.set(nodeId, entry);
+		}
+		return entry;
+	};
+
+	for (const { nodeId, status, ...pane } of listAgentPanes(tabsState)) {
+		const interrupted = !aliveSessionIds.has(pane.paneId);
+		if (!interrupted && status !== "permission") continue;
+
+		const entry = getEntry(nodeId);
+		(interrupted ? entry.interruptedAgents : entry.pendingInput).push(pane);
+	}
+
+	for (const { nodeId, paneId, tabId, name, resumeCommand } of stoppedOnQuit) {
+		const entry = getEntry(nodeId);
+		if (!entry.interruptedAgents.some((agent) => agent.paneId === paneId)) {
+			entry.interruptedAgents.push({ paneId, tabId, name, resumeCommand });
+		}
+	}
+
+	return byNode;
+}
+
+/**
+ * Surviving sessions that haven't printed anything since Caspian quit. Agents
+ * report status through hooks that had nowhere to go while it was closed, so
+ * a working or prompting status on one of these is left over from before.
+ * Sessions from daemons that don't report output times are left out.
+ */
+export function findSilentSessions(
+	sessions: Array<{ sessionId: string; isAlive: boolean; lastOutputAt?: string | null }>,
+	lastQuitAt: number | null,
+): Set<string> {
+	const silent = new Set<string>();
+	if (lastQuitAt === null) return silent;
+	for (const { sessionId, isAlive, lastOutputAt } of sessions) {
+		if (!isAlive || lastOutputAt === undefined) continue;
+		if (lastOutputAt === null || Date.parse(lastOutputAt) < lastQuitAt) silent.add(sessionId);
+	}
+	return silent;
+}
+
+/**
+ * Agent statuses that outlived their agent go back to idle. Panes in
+ * `activeSessionIds` keep theirs, since the agent is still there and its next
+ * hook event will update it.
+ */
+export function resetStalePaneStatuses(
+	panes: Record<string, Pane>,
+	activeSessionIds: ReadonlySet<string>,
+): Record<string, Pane> {
+	const next: Record<string, Pane> = {};
+	for (const [paneId, pane] of Object.entries(panes)) {
+		const stale = isAgentStatus(pane.status) && !activeSessionIds.has(paneId);
+		next[paneId] = stale ? { ...pane, status: "idle" } : pane;
+	}
+	return next;
+}
@@ -0,0 +1,122 @@
+import { eq, isNull } from "drizzle-orm";
+import { nodes, repositories, type SelectNode } from "lib/local-db";
+import { hasUncommittedChanges } from "lib/trpc/routers/nodes/utils/git";
+import { getNodePath } from "lib/trpc/routers/nodes/utils/worktree";
+import { appState } from "main/lib/app-state";
+import { localDb } from "main/lib/local-db";
+import { getDaemonTerminalManager } from "main/lib/terminal";
+import type { RestoreReport, RestoreReportNode } from "shared/types/restore-report";
+import { collectAgentPanes, findSilentSessions, resetStalePaneStatuses } from "./collect";
+
+let pendingReport: Promise<RestoreReport> | null = null;
+let dismissed = false;
+
+async function nodeHasUncommittedChanges(node: SelectNode): Promise<boolean> {
+	const path = getNodePath(node);
+	if (!path) return false;
+	try {
+		return await hasUncommittedChanges(path);
+	} catch {
+		// Missing worktrees are reported by the node itself
+		return false;
+	}
+}
+
+async function buildRestoreReport(): Promise<RestoreReport> {
+	const agentPanes = collectAgentPanes(
+		appState.data.tabsState,
+		getDaemonTerminalManager().getAliveSessionIds(),
+		appState.data.interruptedAgents,
+	);
+
+	const rows = localDb
+		.select({ node: nodes, repositoryName: repositories.name })
+		.from(nodes)
+		.leftJoin(repositories, eq(nodes.repositoryId, repositories.id))
+		.where(isNull(nodes.deletingAt))
+		.all()
+		.sort((a, b) => a.node.tabOrder - b.node.tabOrder);
+
+	const reportNodes = await Promise.all(
+		rows.map(async ({ node, repositoryName }): Promise<RestoreReportNode> => {
+			const panes = agentPanes.get(node.id);
+			return {
+				nodeId: node.id,
+				nodeName: node.name,
+				repositoryName,
+				interruptedAgents: panes?.interruptedAgents ?? [],
+				pendingInput: panes?.pendingInput ?? [],
+				hasUncommittedChanges: await nodeHasUncommittedChanges(node),
+			};
+		}),
+	);
+
+	return {
+		generatedAt: Date.now(),
+		nodes: reportNodes.filter(
+			(node) =>
+				node.interruptedAgents.length > 0 ||
+				node.pendingInput.length > 0 ||
+				node.hasUncommittedChanges,
+		),
+	};
+}
+
+/**
+ * Snapshots what the previous session left behind. Must run after daemon
+ * reconciliation (so surviving sessions are known) and before the renderer
+ * restores tabs, which clears stale agent statuses from app state.
+ */
+export function startRestoreReport(): void {
+	if (pendingReport) return;
+	pendingReport = buildRestoreReport().catch((error) => {
+		console.warn("[restore-report] Failed to build restore report:", error);
+		return { generatedAt: Date.now(), nodes: [] };
+	});
+}
+
+/**
+ * Clears agent statuses whose terminal didn't survive the restart, or that
+ * hasn't printed anything since Caspian quit. Agents still running in the
+ * daemon keep theirs so the renderer picks them back up. Run after
+ * startRestoreReport, which needs the statuses as the previous session left them.
+ */
+export async function reconcileAgentStatuses(): Promise<void> {
+	const manager = getDaemonTerminalManager();
+	let activeSessionIds: ReadonlySet<string>;
+	try {
+		const { sessions } = await manager.listDaemonSessions();
+		const silent = findSilentSessions(sessions, appState.data.lastQuitAt);
+		activeSessionIds = new Set(
+			sessions.filter((s) => s.isAlive && !silent.has(s.sessionId)).map((s) => s.sessionId),
+		);
+	} catch (error) {
+		console.warn("[restore-report] Failed to list daemon sessions:", error);
+		activeSessionIds = manager.getAliveSessionIds();
+	}
+
+	const { tabsState } = appState.data;
+	appState.data.tabsState = {
+		...tabsState,
+		panes: resetStalePaneStatuses(tabsState.panes, activeSessionIds),
+	};
+	try {
+		await appState.write();
+	} catch (error) {
+		console.warn("[restore-report] Failed to save reconciled agent statuses:", error);
+	}
+}
+
+/** null until started, and once the user has dismissed it */
+export async function getRestoreReport(): Promise<RestoreReport | null> {
+	if (!pendingReport || dismissed) return null;
+	return pendingReport;
+}
+
+/** Also forgets agents stopped on quit, so they aren't reported again next launch */
+export async function dismissRestoreReport(): Promise<void> {
+	dismissed = true;
+	if (appState.data.interruptedAgents.length === 0) return;
+	appState.data.interruptedAgents = [];
+	await appState.write();
+}
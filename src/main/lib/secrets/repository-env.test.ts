@@ -0,0 +1,22 @@
+import { describe, expect, test } from "bun:test";
+import { isValidEnvVarName } from "./repository-env";
+
+describe("isValidEnvVarName", () => {
+	test("accepts shell-style names", () => {
+		expect(isValidEnvVarName("DATABASE_URL")).toBe(true);
+		expect(isValidEnvVarName("_private")).toBe(true);
+		expect(isValidEnvVarName("API_KEY_2")).toBe(true);
+	});
+
+	test("rejects names a shell can't export", () => {
+		expect(isValidEnvVarName("")).toBe(false);
+		expect(isValidEnvVarName("2FA_SECRET")).toBe(false);
+		expect(isValidEnvVarName("MY-VAR")).toBe(false);
+		expect(isValidEnvVarName("A=B")).toBe(false);
+	});
+
+	test("rejects variables the terminal sets itself", () => {
+		expect(isValidEnvVarName("CASPIAN_PANE_ID")).toBe(false);
+		expect(isValidEnvVarName("caspian_port")).toBe(false);
+	});
+});
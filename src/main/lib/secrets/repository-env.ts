@@ -0,0 +1,63 @@
+import { deleteSecret, getSecret, type SecretKey, storeSecret } from "./store";
+
+/**
+ * Per-repository environment variables (API keys, database URLs) that are
+ * exported into the repository's terminals. Values may be secrets, so the
+ * whole map is kept in the secret store rather than in SQLite.
+ */
+
+const ENV_VAR_NAME = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+/** Names the terminal sets itself; letting a repository override them would break hooks */
+const RESERVED_PREFIXES = ["CASPIAN_"];
+
+function secretKey(repositoryId: string): SecretKey {
+	return `repo-env:${repositoryId}`;
+}
+
+export function isValidEnvVarName(name: string): boolean {
+	return (
+		ENV_VAR_NAME.test(name) &&
+		!RESERVED_PREFIXES.some((prefix) => name.toUpperCase().startsWith(prefix))
+	);
+}
+
+export function getRepositoryEnv(repositoryId: string): Record<string, string> {
+	const raw = getSecret(secretKey(repositoryId));
+	if (!raw) return {};
+	try {
+		return JSON.parse(raw);
+	} catch (error) {
+		console.warn(`[secrets] Ignoring unreadable env vars for ${repositoryId}:`, error);
+		return {};
+	}
+}
+
+/** Variable names only, for listing in the UI without exposing values */
+export function listRepositoryEnvVarNames(repositoryId: string): string[] {
+	return Object.keys(getRepositoryEnv(repositoryId)).sort();
+}
+
+export function setRepositoryEnvVar(repositoryId: string, name: string, value: string): void {
+	if (!isValidEnvVarName(name)) {
+		throw new Error(`Invalid environment variable name: ${name}`);
+	}
+	const env = getRepositoryEnv(repositoryId);
+	env[name] = value;
+	storeSecret(secretKey(repositoryId), JSON.stringify(env));
+}
+
+export function deleteRepositoryEnvVar(repositoryId: string, name: string): void {
+	const env = getRepositoryEnv(repositoryId);
+	if (!(name in env)) return;
+	delete env[name];
+	if (Object.keys(env).length === 0) {
+		deleteSecret(secretKey(repositoryId));
+	} else {
+		storeSecret(secretKey(repositoryId), JSON.stringify(env));
+	}
+}
+
+export function clearRepositoryEnv(repositoryId: string): void {
+	deleteSecret(secretKey(repositoryId));
+}
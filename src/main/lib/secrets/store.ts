@@ -0,0 +1,81 @@
+import { chmodSync, readFileSync, writeFileSync } from "node:fs";
+import { safeStorage } from "electron";
+import { CASPIAN_SENSITIVE_FILE_MODE, SECRETS_PATH } from "../app-environment";
+
+/**
+ * Namespaced secret keys:
+ * - github-token: the default GitHub personal access token
+ * - github-token:<accountId>: tokens for additional GitHub accounts
+ * - repo-env:<repositoryId>: a repository's environment variables, as JSON
+ * - automation-api-token: bearer token for the local automation API
+ * - webhook-url:<webhookId>: an outbound webhook's URL
+ */
+export type SecretKey =
+	| "github-token"
+	| `github-token:${string}`
+	| `repo-env:${string}`
+	| "automation-api-token"
+	| `webhook-url:${string}`;
+
+/**
+ * Secrets are encrypted with safeStorage, which keys off the OS keychain
+ * (Keychain, DPAPI, libsecret/kwallet), and stored base64-encoded in the
+ * Caspian home dir. Nothing is stored in plaintext, here or in SQLite:
+ * when no keychain is available, storeSecret throws.
+ */
+
+function readStore(): Partial<Record<SecretKey, string>> {
+	try {
+		return JSON.parse(readFileSync(SECRETS_PATH, "utf-8"));
+	} catch (error) {
+		if ((error as NodeJS.ErrnoException).code === "ENOENT") return {};
+		// Rethrow so a corrupt or unreadable file is never overwritten with an empty store
+		console.error("[secrets/read] Failed to read secrets store:", error);
+		throw error;
+	}
+}
+
+function writeStore(store: Partial<Record<SecretKey, string>>): void {
+	writeFileSync(SECRETS_PATH, JSON.stringify(store, null, 2), {
+		mode: CASPIAN_SENSITIVE_FILE_MODE,
+	});
+	// writeFileSync only applies the mode when creating the file
+	chmodSync(SECRETS_PATH, CASPIAN_SENSITIVE_FILE_MODE);
+}
+
+export function isSecretStorageAvailable(): boolean {
+	return safeStorage.isEncryptionAvailable();
+}
+
+export function getSecret(key: SecretKey): string | null {
+	const encrypted = readStore()[key];
+	if (!encrypted || !isSecretStorageAvailable()) return null;
+
+	try {
+		return safeStorage.decryptString(Buffer.from(encrypted, "base64"));
+	} catch (error) {
+		// Keychain entry changed (e.g. copied home dir); the secret has to be entered again
+		console.warn(`[secrets] Failed to decrypt ${key}:`, error);
+		return null;
+	}
+}
+
+export function storeSecret(key: SecretKey, value: string): void {
+	if (!isSecretStorageAvailable()) {
+		throw new Error("Secure storage is unavailable: no OS keychain was found");
+	}
+	const store = readStore();
+	store[key] = safeStorage.encryptString(value).toString("base64");
+	writeStore(store);
+}
+
+export function deleteSecret(key: SecretKey): void {
+	const store = readStore();
+	if (!(key in store)) return;
+	delete store[key];
+	writeStore(store);
+}
+
+export function hasSecret(key: SecretKey): boolean {
+	return Boolean(readStore()[key]);
+}
@@ -1,9 +1,11 @@
 // import * as Sentry from "@sentry/electron/main";
 // import { IPCMode } from "@sentry/electron/main";
 // import { session } from "electron";
+// import { getTelemetryLevel } from "./privacy";
 
 // Sentry error tracking - currently disabled
 // To enable, uncomment the code below and set SENTRY_DSN
+// The privacy setting is checked per event, so changing it needs no restart
 
 // const SENTRY_DSN = "";
 
@@ -20,6 +22,8 @@ export function initSentry(): void {
 	// 		environment: process.env.NODE_ENV,
 	// 		tracesSampleRate: 0.1,
 	// 		sendDefaultPii: false,
+	// 		beforeSend: (event) => (getTelemetryLevel() === "off" ? null : event),
+	// 		beforeBreadcrumb: (breadcrumb) => (getTelemetryLevel() === "full" ? breadcrumb : null),
 	// 		ipcMode: IPCMode.Classic,
 	// 		getSessions: () => [
 	// 			session.defaultSession,
@@ -18,6 +18,7 @@ import { createWriteStream, promises as fs, type WriteStream } from "node:fs";
 import { homedir } from "node:os";
 import { join, relative, resolve, sep } from "node:path";
 import { CASPIAN_DIR_NAME } from "shared/constants";
+import { nowUtcIso } from "shared/utils/time";
 
 const MAX_HISTORY_BYTES = 5 * 1024 * 1024; // 5MB per session
 const MAX_PENDING_WRITE_BYTES = 256 * 1024; // cap in-memory backlog when disk is slow
@@ -133,7 +134,7 @@ export class HistoryWriter {
 			cwd,
 			cols,
 			rows,
-			startedAt: new Date().toISOString(),
+			startedAt: nowUtcIso(),
 		};
 	}
 
@@ -347,7 +348,7 @@ export class HistoryWriter {
 		this.stream = null;
 
 		// Update metadata with end time
-		this.metadata.endedAt = new Date().toISOString();
+		this.metadata.endedAt = nowUtcIso();
 		if (exitCode !== undefined) {
 			this.metadata.exitCode = exitCode;
 		}
@@ -379,7 +380,7 @@ export class HistoryWriter {
 		this.warnedBackpressureDrop = false;
 
 		// Reset metadata with new start time
-		this.metadata.startedAt = new Date().toISOString();
+		this.metadata.startedAt = nowUtcIso();
 		delete this.metadata.endedAt;
 		delete this.metadata.exitCode;
 
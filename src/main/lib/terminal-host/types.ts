@@ -114,6 +114,8 @@ export interface SessionMeta {
 	rows: number;
 	createdAt: string;
 	lastAttachedAt: string;
+	/** null until the PTY prints anything */
+	lastOutputAt: string | null;
 	shell: string;
 }
 
@@ -225,6 +227,8 @@ export interface ListSessionsResponse {
 		createdAt?: string;
 		/** ISO timestamp */
 		lastAttachedAt?: string;
+		/** ISO timestamp; absent from daemons started by older versions */
+		lastOutputAt?: string | null;
 		shell?: string;
 	}>;
 }
@@ -13,6 +13,7 @@ import type { ListSessionsResponse } from "../../terminal-host/types";
 import { buildTerminalEnv, getDefaultShell } from "../env";
 import { TerminalKilledError } from "../errors";
 import { portManager } from "../port-manager";
+import { rateLimitCoordinator } from "../rate-limit";
 import type { CreateSessionParams, SessionResult } from "../types";
 import {
 	CREATE_OR_ATTACH_CONCURRENCY,
@@ -42,6 +43,7 @@ export class DaemonTerminalManager extends EventEmitter {
 	constructor() {
 		super();
 		this.initializeClient();
+		rateLimitCoordinator.setResumeHandler((paneId, input) => this.write({ paneId, data: input }));
 	}
 
 	private recordKilledSession(paneId: string): void {
@@ -129,6 +131,11 @@ export class DaemonTerminalManager extends EventEmitter {
 		}
 	}
 
+	/** Sessions the daemon kept alive across the last restart, as of reconcileOnStartup */
+	getAliveSessionIds(): ReadonlySet<string> {
+		return new Set(this.daemonAliveSessionIds);
+	}
+
 	private async ensureDaemonSessionIdsHydrated(): Promise<void> {
 		if (this.daemonSessionIdsHydrated) return;
 
@@ -159,6 +166,7 @@ export class DaemonTerminalManager extends EventEmitter {
 			}
 
 			portManager.checkOutputForHint(data, paneId);
+			rateLimitCoordinator.checkOutput(data, paneId);
 			this.historyManager.writeToHistory(paneId, data, () => this.sessions.get(paneId));
 			this.emit(`data:${paneId}`, data);
 		});
@@ -174,6 +182,7 @@ export class DaemonTerminalManager extends EventEmitter {
 			}
 
 			portManager.unregisterDaemonSession(paneId);
+			rateLimitCoordinator.cancel(paneId);
 			this.historyManager.closeHistoryWriter(paneId, exitCode);
 			const reason = session?.exitReason ?? (this.isSessionKilled(paneId) ? "killed" : "exited");
 			if (session) {
@@ -283,6 +292,7 @@ export class DaemonTerminalManager extends EventEmitter {
 			workspaceName,
 			workspacePath,
 			rootPath,
+			extraEnv,
 			cwd,
 			cols = 80,
 			rows = 24,
@@ -345,6 +355,7 @@ export class DaemonTerminalManager extends EventEmitter {
 				workspaceName,
 				workspacePath,
 				rootPath,
+				extraEnv,
 			});
 
 			if (DEBUG_TERMINAL) {
@@ -776,6 +787,11 @@ export class DaemonTerminalManager extends EventEmitter {
 		}
 	}
 
+	/** Writes out buffered scrollback so detached sessions cold-restore with everything */
+	async flushHistory(): Promise<void> {
+		await this.historyManager.cleanup();
+	}
+
 	async cleanup(): Promise<void> {
 		for (const timeout of this.cleanupTimeouts.values()) {
 			clearTimeout(timeout);
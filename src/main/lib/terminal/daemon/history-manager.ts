@@ -1,3 +1,4 @@
+import { redactTerminalData } from "../../redaction";
 import {
 	containsClearScrollbackSequence,
 	extractContentAfterClear,
@@ -64,11 +65,15 @@ export class HistoryManager {
 		}
 	}
 
+	/**
+	 * Secrets are redacted per chunk, so one split across two PTY reads can
+	 * slip through; the live stream is never redacted.
+	 */
 	writeToHistory(paneId: string, data: string, getSession: () => SessionInfo | undefined): void {
 		if (this.historyInitializing.has(paneId)) {
 			const buffer = this.pendingHistoryData.get(paneId);
 			if (buffer) {
-				buffer.push(data);
+				buffer.push(redactTerminalData(data));
 			}
 			return;
 		}
@@ -86,7 +91,7 @@ export class HistoryManager {
 				});
 				this.historyWriters.delete(paneId);
 
-				const contentAfterClear = extractContentAfterClear(data);
+				const contentAfterClear = redactTerminalData(extractContentAfterClear(data));
 				this.initHistoryWriter({
 					paneId,
 					workspaceId: session.workspaceId,
@@ -104,7 +109,7 @@ export class HistoryManager {
 			return;
 		}
 
-		writer.write(data);
+		writer.write(redactTerminalData(data));
 	}
 
 	closeHistoryWriter(paneId: string, exitCode?: number): void {
@@ -331,8 +331,10 @@ export function buildTerminalEnv(params: {
 	workspaceName?: string;
 	workspacePath?: string;
 	rootPath?: string;
+	extraEnv?: Record<string, string>;
 }): Record<string, string> {
-	const { shell, paneId, tabId, workspaceId, workspaceName, workspacePath, rootPath } = params;
+	const { shell, paneId, tabId, workspaceId, workspaceName, workspacePath, rootPath, extraEnv } =
+		params;
 
 	// Get Electron's process.env and filter to only allowlisted safe vars
 	// This prevents secrets and app config from leaking to user terminals
@@ -346,6 +348,8 @@ export function buildTerminalEnv(params: {
 
 	const terminalEnv: Record<string, string> = {
 		...baseEnv,
+		// Deliberately set by the user for this repository, so they bypass the allowlist
+		...extraEnv,
 		...shellEnv,
 		TERM_PROGRAM: "Caspian",
 		TERM_PROGRAM_VERSION: process.env.npm_package_version || "1.0.0",
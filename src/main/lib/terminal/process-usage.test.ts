@@ -0,0 +1,89 @@
+import { describe, expect, test } from "bun:test";
+import type { PaneResourceUsage } from "shared/types/resource-usage";
+import {
+	getUsageSince,
+	parseCpuTime,
+	parsePsUsage,
+	summarizeByNode,
+	sumProcessUsage,
+} from "./process-usage";
+
+describe("parseCpuTime", () => {
+	test("parses Linux and macOS cputime formats", () => {
+		expect(parseCpuTime("00:01:05")).toBe(65);
+		expect(parseCpuTime("2-01:00:00")).toBe(2 * 86_400 + 3600);
+		expect(parseCpuTime("0:01.50")).toBe(1.5);
+	});
+});
+
+describe("parsePsUsage", () => {
+	test("parses pid, cputime and rss columns", () => {
+		const output = "  101 00:00:12  20480\n  102 00:00:00   1024\n";
+
+		expect(parsePsUsage(output)).toEqual(
+			new Map([
+				[101, { cpuSeconds: 12, memoryBytes: 20480 * 1024 }],
+				[102, { cpuSeconds: 0, memoryBytes: 1024 * 1024 }],
+			]),
+		);
+	});
+
+	test("skips blank and malformed lines", () => {
+		expect(parsePsUsage("\n  abc 00:00:01 10\n  103 x 10\n").size).toBe(0);
+	});
+});
+
+describe("getUsageSince", () => {
+	test("measures CPU over the time between samples", () => {
+		const previous = new Map([[1, { cpuSeconds: 100, memoryBytes: 10 }]]);
+		const current = new Map([
+			[1, { cpuSeconds: 105, memoryBytes: 20 }],
+			[2, { cpuSeconds: 50, memoryBytes: 30 }],
+		]);
+
+		expect(getUsageSince({ current, previous, elapsedMs: 10_000 })).toEqual(
+			new Map([
+				[1, { cpuPercent: 50, memoryBytes: 20 }],
+				[2, { cpuPercent: 0, memoryBytes: 30 }],
+			]),
+		);
+	});
+
+	test("reports no CPU on the first sample or for a reused PID", () => {
+		const current = new Map([[1, { cpuSeconds: 5, memoryBytes: 10 }]]);
+		const previous = new Map([[1, { cpuSeconds: 500, memoryBytes: 10 }]]);
+
+		expect(getUsageSince({ current, previous: null, elapsedMs: 0 }).get(1)?.cpuPercent).toBe(0);
+		expect(getUsageSince({ current, previous, elapsedMs: 5000 }).get(1)?.cpuPercent).toBe(0);
+	});
+});
+
+describe("sumProcessUsage", () => {
+	test("totals the tree and skips exited processes", () => {
+		const usage = new Map([
+			[1, { cpuPercent: 10, memoryBytes: 100 }],
+			[2, { cpuPercent: 5.5, memoryBytes: 50 }],
+		]);
+
+		expect(sumProcessUsage([1, 2, 3], usage)).toEqual({
+			cpuPercent: 15.5,
+			memoryBytes: 150,
+			processCount: 2,
+		});
+	});
+});
+
+describe("summarizeByNode", () => {
+	function pane(paneId: string, nodeId: string, memoryBytes: number): PaneResourceUsage {
+		return { paneId, nodeId, isAgent: true, processCount: 1, cpuPercent: 1, memoryBytes };
+	}
+
+	test("totals panes per node, heaviest first", () => {
+		const panes = [pane("a1", "node-a", 100), pane("b1", "node-b", 300), pane("a2", "node-a", 50)];
+
+		expect(summarizeByNode(panes)).toEqual([
+			{ nodeId: "node-b", cpuPercent: 1, memoryBytes: 300, panes: [panes[1]] },
+			{ nodeId: "node-a", cpuPercent: 2, memoryBytes: 150, panes: [panes[0], panes[2]] },
+		]);
+	});
+});
@@ -0,0 +1,128 @@
+import { execFile } from "node:child_process";
+import os from "node:os";
+import { promisify } from "node:util";
+import type { NodeResourceUsage, PaneResourceUsage } from "shared/types/resource-usage";
+
+const execFileAsync = promisify(execFile);
+
+/** Timeout for ps to prevent hanging (ms) */
+const EXEC_TIMEOUT_MS = 5000;
+
+export interface ProcessUsage {
+	/** Share of one core, so a busy multi-threaded process can exceed 100 */
+	cpuPercent: number;
+	/** Resident set size */
+	memoryBytes: number;
+}
+
+/** What ps reports for a process at one point in time */
+export interface ProcessSample {
+	/** CPU time used since the process started */
+	cpuSeconds: number;
+	memoryBytes: number;
+}
+
+/** Parses ps's `[[dd-]hh:]mm:ss[.ss]` cputime format into seconds */
+export function parseCpuTime(value: string): number {
+	const [days, clock] = value.includes("-") ? value.split("-") : ["0", value];
+	const seconds = clock.split(":").reduce((total, part) => total * 60 + Number(part), 0);
+	return Number(days) * 86_400 + seconds;
+}
+
+/** Parses `ps -o pid=,time=,rss=` output; rss is in kilobytes */
+export function parsePsUsage(output: string): Map<number, ProcessSample> {
+	const samples = new Map<number, ProcessSample>();
+	for (const line of output.split("\n")) {
+		const columns = line.trim().split(/\s+/);
+		if (columns.length !== 3) continue;
+		const pid = Number(columns[0]);
+		const cpuSeconds = parseCpuTime(columns[1]);
+		const rss = Number(columns[2]);
+		if (!Number.isInteger(pid) || Number.isNaN(cpuSeconds) || Number.isNaN(rss)) continue;
+		samples.set(pid, { cpuSeconds, memoryBytes: rss * 1024 });
+	}
+	return samples;
+}
+
+/**
+ * CPU use between two samples. ps's own pcpu is averaged over the process's
+ * whole lifetime on Linux, so a long-lived agent that just got busy would
+ * barely register. Processes missing from the previous sample report 0.
+ */
+export function getUsageSince({
+	current,
+	previous,
+	elapsedMs,
+}: {
+	current: Map<number, ProcessSample>;
+	previous: Map<number, ProcessSample> | null;
+	elapsedMs: number;
+}): Map<number, ProcessUsage> {
+	const usage = new Map<number, ProcessUsage>();
+	for (const [pid, sample] of current) {
+		const before = previous?.get(pid);
+		let cpuPercent = 0;
+		if (before && elapsedMs > 0) {
+			const cpuSeconds = sample.cpuSeconds - before.cpuSeconds;
+			// A reused PID can go backwards
+			cpuPercent = Math.max(0, (cpuSeconds / (elapsedMs / 1000)) * 100);
+		}
+		usage.set(pid, { cpuPercent, memoryBytes: sample.memoryBytes });
+	}
+	return usage;
+}
+
+/** Totals for a process tree; pids that have exited since are skipped */
+export function sumProcessUsage(
+	pids: number[],
+	usageByPid: Map<number, ProcessUsage>,
+): ProcessUsage & { processCount: number } {
+	let cpuPercent = 0;
+	let memoryBytes = 0;
+	let processCount = 0;
+	for (const pid of pids) {
+		const usage = usageByPid.get(pid);
+		if (!usage) continue;
+		cpuPercent += usage.cpuPercent;
+		memoryBytes += usage.memoryBytes;
+		processCount++;
+	}
+	return { cpuPercent, memoryBytes, processCount };
+}
+
+/** Per-node totals, heaviest memory user first */
+export function summarizeByNode(panes: PaneResourceUsage[]): NodeResourceUsage[] {
+	const byNode = new Map<string, NodeResourceUsage>();
+	for (const pane of panes) {
+		const node = byNode.get(pane.nodeId) ?? {
+			nodeId: pane.nodeId,
+			cpuPercent: 0,
+			memoryBytes: 0,
+			panes: [],
+		};
+		node.cpuPercent += pane.cpuPercent;
+		node.memoryBytes += pane.memoryBytes;
+		node.panes.push(pane);
+		byNode.set(pane.nodeId, node);
+	}
+	return [...byNode.values()].sort((a, b) => b.memoryBytes - a.memoryBytes);
+}
+
+/**
+ * CPU time and memory for a set of PIDs in one ps call. Not implemented on
+ * Windows, where this returns an empty map.
+ */
+export async function getProcessSamples(pids: number[]): Promise<Map<number, ProcessSample>> {
+	if (pids.length === 0 || os.platform() === "win32") return new Map();
+
+	try {
+		const { stdout } = await execFileAsync("ps", ["-o", "pid=,time=,rss=", "-p", pids.join(",")], {
+			timeout: EXEC_TIMEOUT_MS,
+		});
+		return parsePsUsage(stdout);
+	} catch (error) {
+		// ps exits 1 when none of the PIDs exist anymore
+		const stdout = (error as { stdout?: string }).stdout;
+		return stdout ? parsePsUsage(stdout) : new Map();
+	}
+}
@@ -0,0 +1,49 @@
+import { describe, expect, test } from "bun:test";
+import { containsRateLimitError, getBackoffDelayMs, getNextResumeAt } from "./rate-limit";
+
+describe("containsRateLimitError", () => {
+	test("matches rate limit and overload errors from agent CLIs", () => {
+		expect(
+			containsRateLimitError(
+				'API Error: 429 {"type":"error","error":{"type":"rate_limit_error","message":"..."}}',
+			),
+		).toBe(true);
+		expect(containsRateLimitError("API Error: 529 Overloaded")).toBe(true);
+		expect(
+			containsRateLimitError(
+				"stream error: exceeded retry limit, last status: 429 Too Many Requests",
+			),
+		).toBe(true);
+	});
+
+	test("ignores ordinary output that mentions rate limits", () => {
+		expect(containsRateLimitError("Added rate limiting to the API client")).toBe(false);
+		expect(containsRateLimitError("src/errors.ts:429: export class RateLimitError")).toBe(false);
+		expect(containsRateLimitError("HTTP/1.1 429 Too Many Requests")).toBe(false);
+		expect(containsRateLimitError('{"type": "rate_limit_error"}')).toBe(false);
+	});
+});
+
+describe("getBackoffDelayMs", () => {
+	test("doubles with each consecutive hit up to ten minutes", () => {
+		expect(getBackoffDelayMs(1)).toBe(30_000);
+		expect(getBackoffDelayMs(2)).toBe(60_000);
+		expect(getBackoffDelayMs(3)).toBe(120_000);
+		expect(getBackoffDelayMs(10)).toBe(600_000);
+	});
+});
+
+describe("getNextResumeAt", () => {
+	test("resumes the first agent when the backoff ends", () => {
+		expect(getNextResumeAt(100_000, [])).toBe(100_000);
+	});
+
+	test("staggers agents after ones already scheduled", () => {
+		expect(getNextResumeAt(100_000, [100_000])).toBe(115_000);
+		expect(getNextResumeAt(100_000, [100_000, 115_000])).toBe(130_000);
+	});
+
+	test("waits for a backoff that was pushed out past earlier resumes", () => {
+		expect(getNextResumeAt(200_000, [100_000])).toBe(200_000);
+	});
+});
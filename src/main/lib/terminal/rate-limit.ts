@@ -0,0 +1,147 @@
+import { EventEmitter } from "node:events";
+import { appState } from "main/lib/app-state";
+import { listAgentPanes } from "main/lib/restore-report/collect";
+import type { RateLimitedPane, RateLimitState } from "shared/types/rate-limit";
+import stripAnsi from "strip-ansi";
+
+/** First backoff; doubles with each rate limit hit in a row */
+const BASE_BACKOFF_MS = 30_000;
+
+const MAX_BACKOFF_MS = 10 * 60_000;
+
+/** Hits further apart than this start the backoff over */
+const HIT_WINDOW_MS = 15 * 60_000;
+
+/** Gap between agents resuming after the same backoff */
+const RESUME_STAGGER_MS = 15_000;
+
+/**
+ * The rest of the error and the redrawn prompt arrive right after the hit.
+ * Output later than this means the agent or the user carried on without us.
+ */
+const OUTPUT_SETTLE_MS = 5_000;
+
+/** Typed into the agent's prompt to pick up where it stopped */
+const RESUME_INPUT = "continue\r";
+
+/**
+ * Lines agent CLIs print once their own retries give up: Claude Code's
+ * "API Error: 429 {...}" and Codex's "exceeded retry limit, last status: 429".
+ * A bare 429 or error type in a diff or log an agent prints doesn't count.
+ */
+const RATE_LIMIT_PATTERNS = [
+	/\bAPI Error: (?:429|529)\b/,
+	/\bAPI Error: .*"type"\s*:\s*"(?:rate_limit_error|overloaded_error)"/,
+	/\bexceeded retry limit, last status: 429\b/,
+];
+
+export function containsRateLimitError(data: string): boolean {
+	return RATE_LIMIT_PATTERNS.some((pattern) => pattern.test(data));
+}
+
+export function getBackoffDelayMs(consecutiveHits: number): number {
+	return Math.min(BASE_BACKOFF_MS * 2 ** Math.max(consecutiveHits - 1, 0), MAX_BACKOFF_MS);
+}
+
+/**
+ * Spaces resumes out after the shared backoff: the next free slot that's at
+ * least `backoffUntil` and a stagger after every resume already scheduled.
+ */
+export function getNextResumeAt(backoffUntil: number, scheduled: number[]): number {
+	const latest = Math.max(...scheduled, backoffUntil - RESUME_STAGGER_MS);
+	return Math.max(backoffUntil, latest + RESUME_STAGGER_MS);
+}
+
+type ResumeHandler = (paneId: string, input: string) => void;
+
+/**
+ * Watches agent terminal output for rate limit errors and backs off
+ * centrally: every hit pushes out a shared backoff, and affected agents are
+ * resumed one at a time after it. Emits "change" with the RateLimitState.
+ */
+class RateLimitCoordinator extends EventEmitter {
+	private waiting = new Map<string, RateLimitedPane>();
+	private timers = new Map<string, NodeJS.Timeout>();
+	private consecutiveHits = 0;
+	private lastHitAt = 0;
+	private backoffUntil = 0;
+	private resumeHandler: ResumeHandler | null = null;
+
+	setResumeHandler(handler: ResumeHandler): void {
+		this.resumeHandler = handler;
+	}
+
+	checkOutput(data: string, paneId: string): void {
+		const waiting = this.waiting.get(paneId);
+		if (waiting) {
+			if (Date.now() - waiting.detectedAt > OUTPUT_SETTLE_MS && stripAnsi(data).trim()) {
+				console.log(`[RateLimit] Agent in ${paneId} produced output, not resuming it`);
+				this.cancel(paneId);
+			}
+			return;
+		}
+		if (!containsRateLimitError(data)) return;
+
+		const agent = listAgentPanes(appState.data.tabsState).find((pane) => pane.paneId === paneId);
+		if (!agent) return;
+
+		const now = Date.now();
+		this.consecutiveHits = now - this.lastHitAt < HIT_WINDOW_MS ? this.consecutiveHits + 1 : 1;
+		this.lastHitAt = now;
+		this.backoffUntil = Math.max(this.backoffUntil, now + getBackoffDelayMs(this.consecutiveHits));
+
+		const resumeAt = getNextResumeAt(
+			this.backoffUntil,
+			[...this.waiting.values()].map((pane) => pane.resumeAt),
+		);
+		const timer = setTimeout(() => this.resume(paneId), resumeAt - now);
+		timer.unref();
+		this.timers.set(paneId, timer);
+		this.waiting.set(paneId, { paneId, nodeId: agent.nodeId, detectedAt: now, resumeAt });
+
+		const delaySeconds = Math.round((resumeAt - now) / 1000);
+		console.log(`[RateLimit] Agent in ${paneId} hit a rate limit, resuming in ${delaySeconds}s`);
+		this.emitChange();
+	}
+
+	getState(): RateLimitState {
+		return { panes: [...this.waiting.values()].sort((a, b) => a.resumeAt - b.resumeAt) };
+	}
+
+	/** Stops waiting to resume one agent, or all of them. Also called when a session exits. */
+	cancel(paneId?: string): void {
+		let changed = false;
+		for (const [id, timer] of this.timers) {
+			if (paneId && id !== paneId) continue;
+			clearTimeout(timer);
+			this.timers.delete(id);
+			this.waiting.delete(id);
+			changed = true;
+		}
+		if (changed) this.emitChange();
+	}
+
+	private resume(paneId: string): void {
+		this.timers.delete(paneId);
+		this.waiting.delete(paneId);
+		this.emitChange();
+
+		// Only type into an agent that's back at its prompt. Still working means its own
+		// retries got through; a permission prompt or a finished turn waiting for review
+		// shouldn't be answered for the user.
+		const status = appState.data.tabsState.panes[paneId]?.status;
+		if (status !== "idle" || !this.resumeHandler) return;
+
+		try {
+			this.resumeHandler(paneId, RESUME_INPUT);
+		} catch (error) {
+			console.warn(`[RateLimit] Failed to resume agent in ${paneId}:`, error);
+		}
+	}
+
+	private emitChange(): void {
+		this.emit("change", this.getState());
+	}
+}
+
+export const rateLimitCoordinator = new RateLimitCoordinator();
@@ -0,0 +1,149 @@
+import { EventEmitter } from "node:events";
+import { settings } from "lib/local-db";
+import { appState } from "main/lib/app-state";
+import { recordAuditEvent } from "main/lib/audit";
+import { localDb } from "main/lib/local-db";
+import { listAgentPanes } from "main/lib/restore-report/collect";
+import type {
+	AgentMemoryLimitEvent,
+	AgentResourceUsage,
+	PaneResourceUsage,
+} from "shared/types/resource-usage";
+import { getDaemonTerminalManager } from "./daemon";
+import { getProcessTree } from "./port-scanner";
+import {
+	getProcessSamples,
+	getUsageSince,
+	type ProcessSample,
+	summarizeByNode,
+	sumProcessUsage,
+} from "./process-usage";
+
+const SAMPLE_INTERVAL_MS = 5000;
+
+const BYTES_PER_MB = 1024 * 1024;
+
+function getMemoryLimitMb(): number | null {
+	return localDb.select().from(settings).get()?.agentMemoryLimitMb ?? null;
+}
+
+/**
+ * Samples CPU and memory of every live terminal's process tree, and stops
+ * agent terminals that go over the memory limit set in preferences.
+ * Emits "memory-limit" with an AgentMemoryLimitEvent when it does.
+ */
+class ResourceMonitor extends EventEmitter {
+	private interval: ReturnType<typeof setInterval> | null = null;
+	private sampling = false;
+	private latest: AgentResourceUsage = { sampledAt: null, nodes: [] };
+	/** CPU use is measured against the previous sample's CPU time */
+	private previous: { sampledAt: number; byPid: Map<number, ProcessSample> } | null = null;
+
+	start(): void {
+		if (this.interval) return;
+		this.interval = setInterval(() => {
+			this.sample().catch((error) => {
+				console.warn("[ResourceMonitor] Sampling failed:", error);
+			});
+		}, SAMPLE_INTERVAL_MS);
+		// Don't keep Electron alive just for sampling
+		this.interval.unref();
+	}
+
+	stop(): void {
+		if (this.interval) {
+			clearInterval(this.interval);
+			this.interval = null;
+		}
+	}
+
+	/** The latest sample, optionally narrowed to one node */
+	getUsage(nodeId?: string): AgentResourceUsage {
+		if (!nodeId) return this.latest;
+		return {
+			sampledAt: this.latest.sampledAt,
+			nodes: this.latest.nodes.filter((node) => node.nodeId === nodeId),
+		};
+	}
+
+	private async sample(): Promise<void> {
+		// A slow ps shouldn't stack samples up
+		if (this.sampling) return;
+		this.sampling = true;
+
+		try {
+			const { sessions } = await getDaemonTerminalManager().listDaemonSessions();
+			const agentPaneIds = new Set(
+				listAgentPanes(appState.data.tabsState).map((pane) => pane.paneId),
+			);
+
+			const liveSessions = sessions.flatMap(({ isAlive, pid, paneId, workspaceId }) =>
+				isAlive && pid !== null ? [{ pid, paneId, nodeId: workspaceId }] : [],
+			);
+			const trees = await Promise.all(
+				liveSessions.map(async (session) => ({
+					...session,
+					pids: await getProcessTree(session.pid),
+				})),
+			);
+			const samples = await getProcessSamples([...new Set(trees.flatMap((tree) => tree.pids))]);
+			const sampledAt = Date.now();
+			const usageByPid = getUsageSince({
+				current: samples,
+				previous: this.previous?.byPid ?? null,
+				elapsedMs: this.previous ? sampledAt - this.previous.sampledAt : 0,
+			});
+			this.previous = { sampledAt, byPid: samples };
+
+			const panes: PaneResourceUsage[] = trees.map(({ paneId, nodeId, pids }) => ({
+				paneId,
+				nodeId,
+				isAgent: agentPaneIds.has(paneId),
+				...sumProcessUsage(pids, usageByPid),
+			}));
+
+			this.latest = { sampledAt, nodes: summarizeByNode(panes) };
+			await this.enforceMemoryLimit(panes);
+		} finally {
+			this.sampling = false;
+		}
+	}
+
+	private async enforceMemoryLimit(panes: PaneResourceUsage[]): Promise<void> {
+		const limitMb = getMemoryLimitMb();
+		if (!limitMb) return;
+		const limitBytes = limitMb * BYTES_PER_MB;
+
+		for (const pane of panes) {
+			if (!pane.isAgent || pane.memoryBytes <= limitBytes) continue;
+
+			const usedMb = Math.round(pane.memoryBytes / BYTES_PER_MB);
+			const reason = `Agent stopped: it used ${usedMb} MB of memory, over the ${limitMb} MB limit`;
+			console.warn(`[ResourceMonitor] ${reason} (pane ${pane.paneId})`);
+
+			try {
+				await getDaemonTerminalManager().kill({ paneId: pane.paneId });
+			} catch (error) {
+				console.warn(`[ResourceMonitor] Failed to stop agent in ${pane.paneId}:`, error);
+				continue;
+			}
+
+			recordAuditEvent({
+				type: "agent_interrupted",
+				nodeId: pane.nodeId,
+				actor: "system",
+				summary: reason,
+				data: { paneId: pane.paneId, memoryBytes: pane.memoryBytes, limitBytes },
+			});
+			this.emit("memory-limit", {
+				paneId: pane.paneId,
+				nodeId: pane.nodeId,
+				memoryBytes: pane.memoryBytes,
+				limitBytes,
+				reason,
+			} satisfies AgentMemoryLimitEvent);
+		}
+	}
+}
+
+export const resourceMonitor = new ResourceMonitor();
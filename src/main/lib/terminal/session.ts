@@ -86,6 +86,7 @@ export async function createSession(
 		workspaceName,
 		workspacePath,
 		rootPath,
+		extraEnv,
 		cwd,
 		cols,
 		rows,
@@ -117,6 +118,7 @@ export async function createSession(
 		workspaceName,
 		workspacePath,
 		rootPath,
+		extraEnv,
 	});
 
 	const { headless, serializer } = createHeadlessTerminal({
@@ -96,6 +96,8 @@ export interface CreateSessionParams {
 	workspaceName?: string;
 	workspacePath?: string;
 	rootPath?: string;
+	/** The repository's own environment variables; Caspian's variables take precedence */
+	extraEnv?: Record<string, string>;
 	cwd?: string;
 	cols?: number;
 	rows?: number;
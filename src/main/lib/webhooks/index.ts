@@ -0,0 +1,356 @@
+import { and, desc, eq, lt } from "drizzle-orm";
+import {
+	nodes,
+	repositories,
+	type SelectWebhook,
+	type SelectWebhookDelivery,
+	webhookDeliveries,
+	webhooks,
+} from "lib/local-db";
+import { NOTIFICATION_EVENTS } from "shared/constants";
+import type { AgentLifecycleEvent } from "shared/notification-types";
+import type { NodeInitProgress } from "shared/types/node-init";
+import type { WebhookEvent, WebhookEventType, WebhookPayload } from "shared/types/webhooks";
+import { localDb } from "../local-db";
+import { nodeInitManager } from "../node-init-manager";
+import { notificationsEmitter } from "../notifications/server";
+import { isLocalOnlyMode } from "../privacy";
+import { deleteSecret, getSecret, type SecretKey, storeSecret } from "../secrets/store";
+import { buildWebhookPayload, getRetryDelayMs, isRetryableStatus } from "./payload";
+
+const MAX_ATTEMPTS = 5;
+const REQUEST_TIMEOUT_MS = 10_000;
+/** Oldest deliveries beyond this are dropped per webhook */
+const MAX_DELIVERIES_PER_WEBHOOK = 100;
+
+function secretKey(webhookId: string): SecretKey {
+	return `webhook-url:${webhookId}`;
+}
+
+/** Throws for anything but an absolute http(s) URL */
+export function parseWebhookUrl(url: string): URL {
+	let parsed: URL;
+	try {
+		parsed = new URL(url);
+	} catch {
+		throw new Error("Webhook URL is not a valid URL");
+	}
+	if (parsed.protocol !== "https:" && parsed.protocol !== "http:") {
+		throw new Error("Webhook URL must use http or https");
+	}
+	return parsed;
+}
+
+export function listWebhooks(): SelectWebhook[] {
+	return localDb.select().from(webhooks).orderBy(webhooks.createdAt).all();
+}
+
+export function addWebhook({
+	name,
+	url,
+	events,
+}: {
+	name: string;
+	url: string;
+	events: WebhookEventType[];
+}): SelectWebhook {
+	const { host } = parseWebhookUrl(url);
+	const row = localDb.insert(webhooks).values({ name, urlHost: host, events }).returning().get();
+	try {
+		storeSecret(secretKey(row.id), url);
+	} catch (error) {
+		localDb.delete(webhooks).where(eq(webhooks.id, row.id)).run();
+		throw error;
+	}
+	return row;
+}
+
+export function updateWebhook({
+	id,
+	url,
+	...patch
+}: {
+	id: string;
+	name?: string;
+	url?: string;
+	events?: WebhookEventType[];
+	enabled?: boolean;
+}): SelectWebhook | undefined {
+	const existing = localDb.select().from(webhooks).where(eq(webhooks.id, id)).get();
+	if (!existing) return undefined;
+
+	const set: Partial<SelectWebhook> = { ...patch };
+	if (url !== undefined) {
+		set.urlHost = parseWebhookUrl(url).host;
+		storeSecret(secretKey(id), url);
+	}
+	if (Object.keys(set).length === 0) return existing;
+	return localDb.update(webhooks).set(set).where(eq(webhooks.id, id)).returning().get();
+}
+
+export function removeWebhook(id: string): void {
+	// Foreign keys are off, so the delivery log isn't cascaded
+	localDb.delete(webhookDeliveries).where(eq(webhookDeliveries.webhookId, id)).run();
+	localDb.delete(webhooks).where(eq(webhooks.id, id)).run();
+	deleteSecret(secretKey(id));
+}
+
+/** Newest first, optionally for a single webhook */
+export function listWebhookDeliveries({
+	webhookId,
+	limit,
+}: {
+	webhookId?: string;
+	limit: number;
+}): SelectWebhookDelivery[] {
+	return localDb
+		.select()
+		.from(webhookDeliveries)
+		.where(webhookId ? eq(webhookDeliveries.webhookId, webhookId) : undefined)
+		.orderBy(desc(webhookDeliveries.createdAt))
+		.limit(limit)
+		.all();
+}
+
+function pruneDeliveries(webhookId: string): void {
+	const cutoff = localDb
+		.select({ createdAt: webhookDeliveries.createdAt })
+		.from(webhookDeliveries)
+		.where(eq(webhookDeliveries.webhookId, webhookId))
+		.orderBy(desc(webhookDeliveries.createdAt))
+		.limit(1)
+		.offset(MAX_DELIVERIES_PER_WEBHOOK - 1)
+		.get();
+	if (cutoff) {
+		localDb
+			.delete(webhookDeliveries)
+			.where(
+				and(
+					eq(webhookDeliveries.webhookId, webhookId),
+					lt(webhookDeliveries.createdAt, cutoff.createdAt),
+				),
+			)
+			.run();
+	}
+}
+
+async function post(url: string, payload: WebhookPayload): Promise<number> {
+	const response = await fetch(url, {
+		method: "POST",
+		headers: { "Content-Type": "application/json", "User-Agent": "Caspian-Webhooks" },
+		body: JSON.stringify(payload),
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	return response.status;
+}
+
+/**
+ * Attempts one delivery and schedules the next attempt on retryable
+ * failures. Each attempt is recorded on the delivery's log row.
+ */
+async function attemptDelivery({
+	webhookId,
+	deliveryId,
+	payload,
+	attempt,
+}: {
+	webhookId: string;
+	deliveryId: string;
+	payload: WebhookPayload;
+	attempt: number;
+}): Promise<void> {
+	// Retries scheduled before local-only mode was turned on give up
+	const localOnly = isLocalOnlyMode();
+	const url = localOnly ? null : getSecret(secretKey(webhookId));
+	let responseStatus: number | null = null;
+	let error: string | null = null;
+
+	if (localOnly) {
+		error = "Not sent: local-only mode is on";
+	} else if (!url) {
+		error = "Webhook URL is missing from secure storage";
+	} else {
+		try {
+			responseStatus = await post(url, payload);
+			if (responseStatus >= 300) error = `HTTP ${responseStatus}`;
+		} catch (fetchError) {
+			error = fetchError instanceof Error ? fetchError.message : String(fetchError);
+		}
+	}
+
+	const willRetry =
+		url !== null && error !== null && attempt < MAX_ATTEMPTS && isRetryableStatus(responseStatus);
+	localDb
+		.update(webhookDeliveries)
+		.set({
+			status: error === null ? "delivered" : willRetry ? "pending" : "failed",
+			attempts: attempt,
+			responseStatus,
+			error,
+			updatedAt: Date.now(),
+		})
+		.where(eq(webhookDeliveries.id, deliveryId))
+		.run();
+
+	if (willRetry) {
+		setTimeout(() => {
+			void attemptDelivery({ webhookId, deliveryId, payload, attempt: attempt + 1 });
+		}, getRetryDelayMs(attempt));
+	} else if (error !== null) {
+		console.warn(`[webhooks] Delivery of ${payload.type} to ${webhookId} failed: ${error}`);
+	}
+}
+
+function deliver(webhook: SelectWebhook, payload: WebhookPayload): SelectWebhookDelivery {
+	const delivery = localDb
+		.insert(webhookDeliveries)
+		.values({ webhookId: webhook.id, event: payload.type, status: "pending" })
+		.returning()
+		.get();
+	pruneDeliveries(webhook.id);
+	void attemptDelivery({ webhookId: webhook.id, deliveryId: delivery.id, payload, attempt: 1 });
+	return delivery;
+}
+
+function describeNode(nodeId: string): Pick<WebhookEvent, "nodeName" | "repositoryName"> {
+	const row = localDb
+		.select({ nodeName: nodes.name, repositoryName: repositories.name })
+		.from(nodes)
+		.leftJoin(repositories, eq(nodes.repositoryId, repositories.id))
+		.where(eq(nodes.id, nodeId))
+		.get();
+	return { nodeName: row?.nodeName ?? null, repositoryName: row?.repositoryName ?? null };
+}
+
+/**
+ * Sends an event to every enabled webhook subscribed to it. Fire-and-forget:
+ * failures end up in the delivery log. Names are looked up from the node
+ * when not given, so pass them for nodes that are about to be deleted.
+ */
+export function dispatchWebhookEvent({
+	type,
+	nodeId = null,
+	detail,
+	...names
+}: {
+	type: WebhookEventType;
+	nodeId?: string | null;
+	nodeName?: string;
+	repositoryName?: string;
+	detail?: string;
+}): void {
+	if (isLocalOnlyMode()) return;
+	try {
+		const targets = listWebhooks().filter(
+			(webhook) => webhook.enabled && webhook.events.includes(type),
+		);
+		if (targets.length === 0) return;
+
+		const event: WebhookEvent = {
+			type,
+			timestamp: Date.now(),
+			nodeId,
+			...(nodeId ? describeNode(nodeId) : { nodeName: null, repositoryName: null }),
+			...names,
+			...(detail && { detail }),
+		};
+		const payload = buildWebhookPayload(event);
+		for (const webhook of targets) deliver(webhook, payload);
+	} catch (error) {
+		console.error(`[webhooks] Failed to dispatch ${type}:`, error);
+	}
+}
+
+/** Sends a sample event to one webhook regardless of its subscriptions */
+export function sendTestWebhook(id: string): SelectWebhookDelivery | undefined {
+	const webhook = localDb.select().from(webhooks).where(eq(webhooks.id, id)).get();
+	if (!webhook) return undefined;
+	return deliver(
+		webhook,
+		buildWebhookPayload({
+			type: "agent_completed",
+			timestamp: Date.now(),
+			nodeId: null,
+			nodeName: null,
+			repositoryName: null,
+			detail: "Test event from Caspian",
+		}),
+	);
+}
+
+/**
+ * Retries live in timers, and payloads aren't stored, so deliveries still
+ * pending when the app quit can't be sent anymore.
+ */
+function failInterruptedDeliveries(): void {
+	const { changes } = localDb
+		.update(webhookDeliveries)
+		.set({
+			status: "failed",
+			error: "Not sent: Caspian quit before delivery",
+			updatedAt: Date.now(),
+		})
+		.where(eq(webhookDeliveries.status, "pending"))
+		.run();
+	if (changes > 0) {
+		console.log(`[webhooks] Marked ${changes} interrupted deliveries as failed`);
+	}
+}
+
+let started = false;
+
+/**
+ * Subscribes webhooks to agent and node events. Safe to call more than once.
+ */
+export function startWebhookDispatch(): void {
+	if (started) return;
+	started = true;
+
+	try {
+		failInterruptedDeliveries();
+	} catch (error) {
+		console.error("[webhooks] Failed to clean up interrupted deliveries:", error);
+	}
+
+	// Panes with an agent between Start and Stop; exiting early counts as a failure
+	const runningAgents = new Map<string, string | undefined>();
+	notificationsEmitter.on(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, (event: AgentLifecycleEvent) => {
+		if (event.eventType === "Start") {
+			if (event.paneId) runningAgents.set(event.paneId, event.nodeId);
+			return;
+		}
+		if (event.eventType === "Stop") {
+			if (event.paneId) runningAgents.delete(event.paneId);
+			dispatchWebhookEvent({ type: "agent_completed", nodeId: event.nodeId });
+			return;
+		}
+		dispatchWebhookEvent({ type: "agent_needs_input", nodeId: event.nodeId });
+	});
+
+	notificationsEmitter.on(
+		NOTIFICATION_EVENTS.TERMINAL_EXIT,
+		(event: { paneId: string; exitCode: number; reason?: "killed" | "exited" | "error" }) => {
+			if (!runningAgents.has(event.paneId)) return;
+			const nodeId = runningAgents.get(event.paneId);
+			runningAgents.delete(event.paneId);
+			if (event.reason === "killed" || (event.reason !== "error" && event.exitCode === 0)) return;
+			dispatchWebhookEvent({
+				type: "agent_failed",
+				nodeId,
+				detail: `Exited with code ${event.exitCode}`,
+			});
+		},
+	);
+
+	nodeInitManager.on("progress", (progress: NodeInitProgress) => {
+		if (progress.step === "ready") {
+			dispatchWebhookEvent({ type: "node_ready", nodeId: progress.nodeId });
+		} else if (progress.step === "failed") {
+			dispatchWebhookEvent({
+				type: "node_setup_failed",
+				nodeId: progress.nodeId,
+				detail: progress.error,
+			});
+		}
+	});
+}
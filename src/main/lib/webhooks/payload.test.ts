@@ -0,0 +1,68 @@
+import { describe, expect, it } from "bun:test";
+import type { WebhookEvent } from "shared/types/webhooks";
+import {
+	buildWebhookPayload,
+	formatWebhookText,
+	getRetryDelayMs,
+	isRetryableStatus,
+} from "./payload";
+
+function event(overrides: Partial<WebhookEvent>): WebhookEvent {
+	return {
+		type: "agent_completed",
+		timestamp: 1_700_000_000_000,
+		nodeId: "node-1",
+		nodeName: "fix-login",
+		repositoryName: "web",
+		...overrides,
+	};
+}
+
+describe("formatWebhookText", () => {
+	it("names the node and repository", () => {
+		expect(formatWebhookText(event({}))).toBe("Agent completed in fix-login (web)");
+	});
+
+	it("appends the detail", () => {
+		expect(formatWebhookText(event({ type: "agent_failed", detail: "Exited with code 1" }))).toBe(
+			"Agent failed in fix-login (web): Exited with code 1",
+		);
+	});
+
+	it("omits the location when the node is unknown", () => {
+		expect(formatWebhookText(event({ nodeId: null, nodeName: null, repositoryName: null }))).toBe(
+			"Agent completed",
+		);
+	});
+});
+
+describe("buildWebhookPayload", () => {
+	it("adds Slack's text field to the event", () => {
+		const payload = buildWebhookPayload(event({ type: "agent_needs_input" }));
+		expect(payload.text).toBe("Agent needs input in fix-login (web)");
+		expect(payload.type).toBe("agent_needs_input");
+		expect(payload.nodeId).toBe("node-1");
+	});
+});
+
+describe("getRetryDelayMs", () => {
+	it("doubles each attempt up to the cap", () => {
+		expect(getRetryDelayMs(1)).toBe(2_000);
+		expect(getRetryDelayMs(2)).toBe(4_000);
+		expect(getRetryDelayMs(3)).toBe(8_000);
+		expect(getRetryDelayMs(20)).toBe(5 * 60_000);
+	});
+});
+
+describe("isRetryableStatus", () => {
+	it("retries network errors, rate limits and server errors", () => {
+		expect(isRetryableStatus(null)).toBe(true);
+		expect(isRetryableStatus(429)).toBe(true);
+		expect(isRetryableStatus(503)).toBe(true);
+	});
+
+	it("gives up on other client errors", () => {
+		expect(isRetryableStatus(400)).toBe(false);
+		expect(isRetryableStatus(404)).toBe(false);
+	});
+});
@@ -0,0 +1,35 @@
+import type { WebhookEvent, WebhookEventType, WebhookPayload } from "shared/types/webhooks";
+
+const RETRY_BASE_DELAY_MS = 2_000;
+const RETRY_MAX_DELAY_MS = 5 * 60_000;
+
+const EVENT_LABELS: Record<WebhookEventType, string> = {
+	agent_completed: "Agent completed",
+	agent_failed: "Agent failed",
+	agent_needs_input: "Agent needs input",
+	node_ready: "Node ready",
+	node_setup_failed: "Node setup failed",
+	node_deleted: "Node deleted",
+};
+
+export function formatWebhookText(event: WebhookEvent): string {
+	const where = event.nodeName
+		? ` in ${event.nodeName}${event.repositoryName ? ` (${event.repositoryName})` : ""}`
+		: "";
+	const detail = event.detail ? `: ${event.detail}` : "";
+	return `${EVENT_LABELS[event.type]}${where}${detail}`;
+}
+
+export function buildWebhookPayload(event: WebhookEvent): WebhookPayload {
+	return { text: formatWebhookText(event), ...event };
+}
+
+/** Exponential backoff: 2s, 4s, 8s... capped at 5 minutes. `attempt` starts at 1 */
+export function getRetryDelayMs(attempt: number): number {
+	return Math.min(RETRY_BASE_DELAY_MS * 2 ** (attempt - 1), RETRY_MAX_DELAY_MS);
+}
+
+/** Network errors, timeouts, rate limits and server errors are worth retrying */
+export function isRetryableStatus(status: number | null): boolean {
+	return status === null || status === 408 || status === 429 || status >= 500;
+}
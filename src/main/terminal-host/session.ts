@@ -77,6 +77,7 @@ export class Session {
 	private clientSocketsWaitingForDrain: Set<Socket> = new Set();
 	private subprocessStdoutPaused = false;
 	private lastAttachedAt: Date;
+	private lastOutputAt: Date | null = null;
 	private exitCode: number | null = null;
 	private disposed = false;
 	private terminatingAt: number | null = null;
@@ -230,6 +231,7 @@ export class Session {
 			case PtySubprocessIpcType.Data: {
 				if (payload.length === 0) break;
 				const data = payload.toString("utf8");
+				this.lastOutputAt = new Date();
 
 				this.enqueueEmulatorWrite(data);
 
@@ -704,6 +706,7 @@ export class Session {
 			rows: dims.rows,
 			createdAt: this.createdAt.toISOString(),
 			lastAttachedAt: this.lastAttachedAt.toISOString(),
+			lastOutputAt: this.lastOutputAt?.toISOString() ?? null,
 			shell: this.shell,
 		};
 	}
@@ -277,6 +277,7 @@ export class TerminalHost {
 				pid: session.pid,
 				createdAt: meta.createdAt,
 				lastAttachedAt: meta.lastAttachedAt,
+				lastOutputAt: meta.lastOutputAt,
 				shell: meta.shell,
 			};
 		});
@@ -3,18 +3,29 @@ import { eq } from "drizzle-orm";
 import type { BrowserWindow } from "electron";
 import { Notification } from "electron";
 import { createWindow } from "lib/electron-app/factories/windows/create";
-import { nodes, worktrees } from "lib/local-db";
+import { nodes, repositories, settings, worktrees } from "lib/local-db";
 import { createAppRouter } from "lib/trpc/routers";
 import { localDb } from "main/lib/local-db";
 import { NOTIFICATION_EVENTS, PORTS } from "shared/constants";
+import type { NotificationDigestEvent } from "shared/notification-types";
+import { getSystemTimeZone, getZonedTimeParts } from "shared/utils/time";
 import { createIPCHandler } from "trpc-electron/main";
 import { productName } from "~/package.json";
 import { appState } from "../lib/app-state";
+import { getTranslator, t } from "../lib/i18n";
 import { createApplicationMenu, registerMenuHotkeyUpdates } from "../lib/menu";
 import { getNodeRuntimeRegistry } from "../lib/node-runtime";
 import { playNotificationSound } from "../lib/notification-sound";
+import { NotificationDigest, summarizeDigest } from "../lib/notifications/digest";
+import { markNotificationsRead, recordNotification } from "../lib/notifications/history";
+import {
+	type ResolvedNotificationPreferences,
+	resolveNotificationPreferences,
+	shouldNotify,
+} from "../lib/notifications/preferences";
 import {
 	type AgentLifecycleEvent,
+	type NotificationIds,
 	notificationsApp,
 	notificationsEmitter,
 } from "../lib/notifications/server";
@@ -43,6 +54,36 @@ function getNodeNameFromDb(nodeId: string | undefined): string {
 	}
 }
 
+function getNotificationPreferences(nodeId: string | undefined): {
+	preferences: ResolvedNotificationPreferences;
+	timeZone: string;
+} {
+	try {
+		const row = localDb.select().from(settings).get();
+		const repository = nodeId
+			? localDb
+					.select({ notificationPreferences: repositories.notificationPreferences })
+					.from(nodes)
+					.innerJoin(repositories, eq(nodes.repositoryId, repositories.id))
+					.where(eq(nodes.id, nodeId))
+					.get()
+			: undefined;
+		return {
+			preferences: resolveNotificationPreferences(
+				row?.notificationPreferences,
+				repository?.notificationPreferences,
+			),
+			timeZone: row?.timeZone || getSystemTimeZone(),
+		};
+	} catch (error) {
+		console.error("[notifications] Failed to load notification preferences:", error);
+		return {
+			preferences: resolveNotificationPreferences(null, null),
+			timeZone: getSystemTimeZone(),
+		};
+	}
+}
+
 let currentWindow: BrowserWindow | null = null;
 const getWindow = () => currentWindow;
 
@@ -95,6 +136,61 @@ export async function MainWindow() {
 		console.log(`[notifications] Listening on http://127.0.0.1:${PORTS.NOTIFICATIONS}`);
 	});
 
+	const showAgentNotification = ({
+		title,
+		body,
+		sound,
+		target,
+		historyIds,
+	}: {
+		title: string;
+		body: string;
+		sound: boolean;
+		target: NotificationIds;
+		historyIds: string[];
+	}) => {
+		const notification = new Notification({ title, body, silent: true });
+
+		if (sound) {
+			playNotificationSound();
+		}
+
+		notification.on("click", () => {
+			window.show();
+			window.focus();
+			markNotificationsRead(historyIds);
+			notificationsEmitter.emit(NOTIFICATION_EVENTS.FOCUS_TAB, target);
+		});
+
+		notification.show();
+	};
+
+	const digest = new NotificationDigest((entries) => {
+		const toTarget = ({ paneId, tabId, nodeId }: NotificationIds) => ({ paneId, tabId, nodeId });
+
+		if (entries.length === 1) {
+			const [entry] = entries;
+			showAgentNotification({
+				...entry,
+				target: toTarget(entry),
+				historyIds: entry.historyId ? [entry.historyId] : [],
+			});
+			return;
+		}
+
+		// Clicking goes to the latest input request, or else the latest completion
+		const latest = entries.findLast((entry) => entry.type === "needs_input") ?? entries.at(-1);
+		const { title, body, ...event } = summarizeDigest(entries, getTranslator());
+		showAgentNotification({
+			title,
+			body,
+			sound: entries.some((entry) => entry.sound),
+			target: latest ? toTarget(latest) : {},
+			historyIds: entries.flatMap((entry) => (entry.historyId ? [entry.historyId] : [])),
+		});
+		notificationsEmitter.emit(NOTIFICATION_EVENTS.DIGEST, event satisfies NotificationDigestEvent);
+	});
+
 	notificationsEmitter.on(NOTIFICATION_EVENTS.AGENT_LIFECYCLE, (event: AgentLifecycleEvent) => {
 		if (event.eventType === "Start") return;
 
@@ -111,8 +207,7 @@ export async function MainWindow() {
 			if (isVisible) return;
 		}
 
-		if (!Notification.isSupported()) return;
-
+		const isPermissionRequest = event.eventType === "PermissionRequest";
 		const nodeName = getNodeNameFromDb(event.nodeId);
 		const title = getNotificationTitle({
 			tabId: event.tabId,
@@ -120,29 +215,49 @@ export async function MainWindow() {
 			tabs: appState.data?.tabsState?.tabs,
 			panes: appState.data?.tabsState?.panes,
 		});
+		const content = isPermissionRequest
+			? {
+					title: t("notification.needsInput.title", { node: nodeName }),
+					body: t("notification.needsInput.body", { tab: title }),
+				}
+			: {
+					title: t("notification.agentComplete.title", { node: nodeName }),
+					body: t("notification.agentComplete.body", { tab: title }),
+				};
 
-		const isPermissionRequest = event.eventType === "PermissionRequest";
-		const notification = new Notification({
-			title: isPermissionRequest ? `Input Needed — ${nodeName}` : `Agent Complete — ${nodeName}`,
-			body: isPermissionRequest
-				? `"${title}" needs your attention`
-				: `"${title}" has finished its task`,
-			silent: true,
-		});
+		const target = { paneId: event.paneId, tabId: event.tabId, nodeId: event.nodeId };
+		const type = isPermissionRequest ? "needs_input" : "agent_complete";
 
-		playNotificationSound();
+		// Kept in the inbox even when preferences or quiet hours suppress the OS notification
+		const historyId = recordNotification({ type, ...target, ...content })?.id ?? null;
 
-		notification.on("click", () => {
-			window.show();
-			window.focus();
-			notificationsEmitter.emit(NOTIFICATION_EVENTS.FOCUS_TAB, {
-				paneId: event.paneId,
-				tabId: event.tabId,
-				nodeId: event.nodeId,
-			});
-		});
+		if (!Notification.isSupported()) return;
 
-		notification.show();
+		const { preferences, timeZone } = getNotificationPreferences(event.nodeId);
+		if (
+			!shouldNotify({
+				preferences,
+				kind: isPermissionRequest ? "needsInput" : "agentComplete",
+				time: getZonedTimeParts(Date.now(), timeZone),
+			})
+		) {
+			return;
+		}
+
+		if (preferences.digestWindowSeconds > 0) {
+			digest.add(
+				{ type, nodeName, ...target, ...content, sound: preferences.sound, historyId },
+				preferences.digestWindowSeconds * 1000,
+			);
+			return;
+		}
+
+		showAgentNotification({
+			...content,
+			sound: preferences.sound,
+			target,
+			historyIds: historyId ? [historyId] : [],
+		});
 	});
 
 	// Forward low-volume terminal lifecycle events to the renderer via the existing
@@ -210,6 +325,7 @@ export async function MainWindow() {
 		});
 
 		server.close();
+		digest.dispose();
 		notificationsEmitter.removeAllListeners();
 		// Prevent duplicate listeners when window reopens on macOS
 		getNodeRuntimeRegistry().getDefault().terminal.detachAllListeners();
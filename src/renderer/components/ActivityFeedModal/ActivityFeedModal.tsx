@@ -0,0 +1,154 @@
+import { useNavigate } from "@tanstack/react-router";
+import { useEffect, useState } from "react";
+import {
+	HiChatBubbleLeftEllipsis,
+	HiCheckCircle,
+	HiOutlineArrowPath,
+	HiOutlineArrowsRightLeft,
+	HiOutlineCodeBracketSquare,
+	HiStopCircle,
+} from "react-icons/hi2";
+import { electronTrpc } from "renderer/lib/electron-trpc";
+import { formatRelativeTime } from "renderer/lib/formatRelativeTime";
+import { navigateToNode } from "renderer/routes/_authenticated/_dashboard/utils/node-navigation";
+import {
+	useActivityFeedModalOpen,
+	useCloseActivityFeedModal,
+} from "renderer/stores/activity-feed-modal";
+import type { ActivityItem, AuditEventType } from "shared/types/audit";
+import { Button } from "ui/components/ui/button";
+import {
+	Dialog,
+	DialogContent,
+	DialogDescription,
+	DialogHeader,
+	DialogTitle,
+} from "ui/components/ui/dialog";
+
+/** When the feed was last closed; newer items are marked as new */
+const LAST_SEEN_KEY = "activityFeedLastSeenAt";
+
+function ActivityIcon({ type }: { type: AuditEventType }) {
+	switch (type) {
+		case "agent_completed":
+			return <HiCheckCircle className="size-4 shrink-0 text-[var(--status-running)]" />;
+		case "agent_needs_input":
+			return <HiChatBubbleLeftEllipsis className="size-4 shrink-0 text-[var(--status-warning)]" />;
+		case "agent_interrupted":
+			return <HiStopCircle className="size-4 shrink-0 text-muted-foreground" />;
+		case "pr_merged":
+			return <HiOutlineArrowsRightLeft className="size-4 shrink-0 text-[var(--status-info)]" />;
+		case "pr_created":
+		case "pr_updated":
+			return <HiOutlineCodeBracketSquare className="size-4 shrink-0 text-muted-foreground" />;
+		default:
+			return <HiOutlineArrowPath className="size-4 shrink-0 text-muted-foreground" />;
+	}
+}
+
+function ActivityRow({
+	item,
+	isNew,
+	onSelect,
+}: {
+	item: ActivityItem;
+	isNew: boolean;
+	onSelect: (item: ActivityItem) => void;
+}) {
+	const location = [item.repositoryName, item.nodeName].filter(Boolean).join(" / ");
+	return (
+		<button
+			type="button"
+			className="flex w-full items-start gap-3 rounded-md px-2 py-2 text-left hover:bg-accent disabled:hover:bg-transparent"
+			onClick={() => onSelect(item)}
+			disabled={!item.nodeId || !item.nodeName}
+		>
+			<ActivityIcon type={item.type} />
+			<div className="min-w-0 flex-1">
+				<p className="truncate text-sm">{item.summary}</p>
+				<p className="truncate text-xs text-muted-foreground">{location || "Removed"}</p>
+			</div>
+			<div className="flex shrink-0 items-center gap-2">
+				{isNew && <span className="size-1.5 rounded-full bg-[var(--status-info)]" />}
+				<span className="text-xs text-muted-foreground">
+					{formatRelativeTime(item.timestamp)}
+				</span>
+			</div>
+		</button>
+	);
+}
+
+export function ActivityFeedModal() {
+	const isOpen = useActivityFeedModalOpen();
+	const closeModal = useCloseActivityFeedModal();
+	const navigate = useNavigate();
+	const [lastSeenAt, setLastSeenAt] = useState(() => Number(localStorage.getItem(LAST_SEEN_KEY)));
+
+	const activity = electronTrpc.audit.getGlobalActivity.useInfiniteQuery(
+		{ limit: 50 },
+		{ enabled: isOpen, getNextPageParam: (page) => page.nextCursor },
+	);
+	const items = activity.data?.pages.flatMap((page) => page.items) ?? [];
+
+	// Re-read on open so items stay marked as new until the feed is closed again
+	useEffect(() => {
+		if (isOpen) setLastSeenAt(Number(localStorage.getItem(LAST_SEEN_KEY)));
+	}, [isOpen]);
+
+	const handleOpenChange = (open: boolean) => {
+		if (open) return;
+		localStorage.setItem(LAST_SEEN_KEY, String(Date.now()));
+		closeModal();
+	};
+
+	const handleSelect = (item: ActivityItem) => {
+		if (!item.nodeId) return;
+		handleOpenChange(false);
+		navigateToNode(item.nodeId, navigate);
+	};
+
+	const newCount = items.filter((item) => item.timestamp > lastSeenAt).length;
+
+	return (
+		<Dialog modal open={isOpen} onOpenChange={handleOpenChange}>
+			<DialogContent className="sm:max-w-xl">
+				<DialogHeader>
+					<DialogTitle>Activity</DialogTitle>
+					<DialogDescription>
+						{newCount > 0
+							? `${newCount} new since you last looked`
+							: "Agent completions, input requests and pull request updates across repositories"}
+					</DialogDescription>
+				</DialogHeader>
+
+				<div className="max-h-[60vh] overflow-y-auto -mx-2">
+					{activity.isLoading ? (
+						<p className="px-2 py-4 text-sm text-muted-foreground">Loading...</p>
+					) : items.length === 0 ? (
+						<p className="px-2 py-4 text-sm text-muted-foreground">Nothing has happened yet</p>
+					) : (
+						items.map((item) => (
+							<ActivityRow
+								key={item.id}
+								item={item}
+								isNew={item.timestamp > lastSeenAt}
+								onSelect={handleSelect}
+							/>
+						))
+					)}
+				</div>
+
+				{activity.hasNextPage && (
+					<Button
+						variant="outline"
+						size="sm"
+						onClick={() => activity.fetchNextPage()}
+						disabled={activity.isFetchingNextPage}
+					>
+						{activity.isFetchingNextPage ? "Loading..." : "Load older"}
+					</Button>
+				)}
+			</DialogContent>
+		</Dialog>
+	);
+}
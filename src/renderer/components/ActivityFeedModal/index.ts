@@ -0,0 +1 @@
+export { ActivityFeedModal } from "./ActivityFeedModal";
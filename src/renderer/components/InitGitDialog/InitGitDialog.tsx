@@ -1,6 +1,15 @@
 import { useCallback, useEffect, useId, useRef, useState } from "react";
 import { electronTrpc } from "renderer/lib/electron-trpc";
+import { REPOSITORY_TEMPLATES, type RepositoryTemplateId } from "shared/repository-templates";
 import { Button } from "ui/components/ui/button";
+import { Label } from "ui/components/ui/label";
+import {
+	Select,
+	SelectContent,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from "ui/components/ui/select";
 
 function getBasename(path: string): string {
 	// Handle both Unix and Windows paths
@@ -35,6 +44,7 @@ export function InitGitDialog({
 	const initGitAndOpen = electronTrpc.repositories.initGitAndOpen.useMutation();
 
 	const [isProcessing, setIsProcessing] = useState(false);
+	const [template, setTemplate] = useState<RepositoryTemplateId | "none">("none");
 
 	// Prevents setState-after-unmount when the async init outlives the component
 	const isMountedRef = useRef(true);
@@ -103,7 +113,10 @@ export function InitGitDialog({
 		try {
 			let result: Awaited<ReturnType<typeof initGitAndOpen.mutateAsync>>;
 			try {
-				result = await initGitAndOpen.mutateAsync({ path: selectedPath });
+				result = await initGitAndOpen.mutateAsync({
+					path: selectedPath,
+					template: template === "none" ? undefined : template,
+				});
 			} catch (err) {
 				onError(`Failed to initialize git repository: ${getErrorMessage(err)}`);
 				return;
@@ -161,10 +174,35 @@ export function InitGitDialog({
 					<span className="text-xs text-muted-foreground block mt-1 break-all">{selectedPath}</span>
 				</div>
 
-				<p className="text-sm text-muted-foreground mb-6">
+				<p className="text-sm text-muted-foreground mb-4">
 					Would you like to initialize a git repository in this folder?
 				</p>
 
+				<div className="space-y-1.5 mb-6">
+					<Label className="text-sm">Start from a template</Label>
+					<Select
+						value={template}
+						onValueChange={(value) => setTemplate(value as RepositoryTemplateId | "none")}
+						disabled={isProcessing}
+					>
+						<SelectTrigger className="w-full">
+							<SelectValue />
+						</SelectTrigger>
+						<SelectContent>
+							<SelectItem value="none">Empty repository</SelectItem>
+							{Object.entries(REPOSITORY_TEMPLATES).map(([id, { label }]) => (
+								<SelectItem key={id} value={id}>
+									{label}
+								</SelectItem>
+							))}
+						</SelectContent>
+					</Select>
+					<p className="text-xs text-muted-foreground">
+						Adds a .gitignore, README and Caspian config with setup and test commands. Existing
+						files are kept.
+					</p>
+				</div>
+
 				<div className="flex gap-3 justify-end">
 					<Button variant="outline" onClick={onClose} disabled={isProcessing}>
 						Cancel
@@ -14,8 +14,14 @@ import {
 	useNewNodeModalOpen,
 	usePreSelectedRepositoryId,
 } from "renderer/stores/new-node-modal";
-import { resolveBranchPrefix, sanitizeBranchName, sanitizeSegment } from "shared/utils/branch";
+import {
+	renderBranchTemplate,
+	resolveBranchPrefix,
+	sanitizeBranchName,
+	sanitizeSegment,
+} from "shared/utils/branch";
 import { Button } from "ui/components/ui/button";
+import { Checkbox } from "ui/components/ui/checkbox";
 import { Collapsible, CollapsibleContent, CollapsibleTrigger } from "ui/components/ui/collapsible";
 import {
 	Command,
@@ -63,6 +69,10 @@ export function NewNodeModal() {
 	const [showAdvanced, setShowAdvanced] = useState(false);
 	const [setupScript, setSetupScript] = useState("");
 	const [teardownScript, setTeardownScript] = useState("");
+	const [scopePath, setScopePath] = useState("");
+	const [sparseCheckout, setSparseCheckout] = useState(false);
+	const [ticket, setTicket] = useState("");
+	const [issue, setIssue] = useState("");
 	const [initGitDialog, setInitGitDialog] = useState<{ isOpen: boolean; selectedPath: string }>({
 		isOpen: false,
 		selectedPath: "",
@@ -86,6 +96,10 @@ export function NewNodeModal() {
 		{ id: selectedRepositoryId ?? "" },
 		{ enabled: !!selectedRepositoryId },
 	);
+	const { data: repositoryConfig } = electronTrpc.repositories.getConfig.useQuery(
+		{ id: selectedRepositoryId ?? "" },
+		{ enabled: !!selectedRepositoryId },
+	);
 	const { data: globalBranchPrefix } = electronTrpc.settings.getBranchPrefix.useQuery();
 	const { data: gitInfo } = electronTrpc.settings.getGitInfo.useQuery();
 	const createNode = useCreateNode();
@@ -137,8 +151,15 @@ export function NewNodeModal() {
 
 	const applyPrefix = !branchNameEdited;
 
+	// The repository's branch template wins over any prefix, matching nodes.create
+	const branchTemplate = repositoryConfig?.defaults?.branchTemplate?.trim();
+	const prefixedSlug = resolvedPrefix ? `${resolvedPrefix}/${branchSlug}` : branchSlug;
 	const branchPreview =
-		branchSlug && applyPrefix && resolvedPrefix ? `${resolvedPrefix}/${branchSlug}` : branchSlug;
+		branchSlug && applyPrefix
+			? branchTemplate
+				? renderBranchTemplate(branchTemplate, { user: gitAuthor?.name, ticket, slug: branchSlug })
+				: prefixedSlug
+			: branchSlug;
 
 	const branchStatus = useBranchStatus({
 		branchPreview,
@@ -158,6 +179,10 @@ export function NewNodeModal() {
 		setShowAdvanced(false);
 		setSetupScript("");
 		setTeardownScript("");
+		setScopePath("");
+		setSparseCheckout(false);
+		setTicket("");
+		setIssue("");
 	};
 
 	useEffect(() => {
@@ -261,6 +286,8 @@ export function NewNodeModal() {
 					useExistingBranch: true,
 					setupScript: setupScript.trim() || undefined,
 					teardownScript: teardownScript.trim() || undefined,
+					scopePath: scopePath.trim() || undefined,
+					sparseCheckout: sparseCheckout && !!scopePath.trim(),
 				});
 				handleClose();
 				if (result.isInitializing) {
@@ -289,8 +316,12 @@ export function NewNodeModal() {
 				branchName: branchSlug || undefined,
 				baseBranch: effectiveBaseBranch || undefined,
 				applyPrefix,
+				ticket: ticket.trim() || undefined,
+				issue: issue.trim() || undefined,
 				setupScript: setupScript.trim() || undefined,
 				teardownScript: teardownScript.trim() || undefined,
+				scopePath: scopePath.trim() || undefined,
+				sparseCheckout: sparseCheckout && !!scopePath.trim(),
 			});
 
 			handleClose();
@@ -416,6 +447,16 @@ export function NewNodeModal() {
 											onChange={(e) => setTitle(e.target.value)}
 										/>
 
+										{branchTemplate?.includes("{ticket}") && !branchNameEdited && (
+											<Input
+												id="ticket"
+												className="h-8 text-sm font-mono"
+												placeholder="Ticket ID, e.g. PROJ-123"
+												value={ticket}
+												onChange={(e) => setTicket(e.target.value)}
+											/>
+										)}
+
 										{(title || branchNameEdited) && (
 											<>
 												<p className="text-xs text-muted-foreground flex items-center gap-1.5">
@@ -447,6 +488,21 @@ export function NewNodeModal() {
 												Advanced options
 											</CollapsibleTrigger>
 											<CollapsibleContent className="pt-3 space-y-3">
+												{repositoryConfig?.issues && (
+													<div className="space-y-1.5">
+														<label htmlFor="issue" className="text-xs text-muted-foreground">
+															Issue
+														</label>
+														<Input
+															id="issue"
+															className="h-8 text-sm font-mono"
+															placeholder="Key or URL; names the node and branch"
+															value={issue}
+															onChange={(e) => setIssue(e.target.value)}
+														/>
+													</div>
+												)}
+
 												<div className="space-y-1.5">
 													<label htmlFor="branch" className="text-xs text-muted-foreground">
 														Branch name
@@ -546,6 +602,34 @@ export function NewNodeModal() {
 													)}
 												</div>
 
+												<div className="space-y-1.5">
+													<label htmlFor="scope-path" className="text-xs text-muted-foreground">
+														Scope
+													</label>
+													<Input
+														id="scope-path"
+														className="h-8 text-sm font-mono"
+														placeholder="whole repository, or e.g. packages/api"
+														value={scopePath}
+														onChange={(e) => setScopePath(e.target.value)}
+													/>
+													{scopePath.trim() && (
+														<div className="flex items-center gap-2 pt-1">
+															<Checkbox
+																id="sparse-checkout"
+																checked={sparseCheckout}
+																onCheckedChange={(checked) => setSparseCheckout(checked === true)}
+															/>
+															<label
+																htmlFor="sparse-checkout"
+																className="text-xs text-muted-foreground cursor-pointer"
+															>
+																Only check out this directory (sparse checkout)
+															</label>
+														</div>
+													)}
+												</div>
+
 												<div className="space-y-1.5">
 													<label htmlFor="setup-script" className="text-xs text-muted-foreground">
 														Setup script
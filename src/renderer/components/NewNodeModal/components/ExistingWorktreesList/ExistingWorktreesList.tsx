@@ -17,6 +17,13 @@ export function ExistingWorktreesList({ repositoryId, onOpenSuccess }: ExistingW
 	const openWorktree = useOpenWorktree();
 	const createNode = useCreateNode();
 	const createFromPr = useCreateFromPr();
+	const utils = electronTrpc.useUtils();
+	const importExistingNodes = electronTrpc.nodes.importExistingNodes.useMutation({
+		onSuccess: async () => {
+			await utils.nodes.invalidate();
+			await utils.repositories.getRecents.invalidate();
+		},
+	});
 
 	const [branchOpen, setBranchOpen] = useState(false);
 	const [branchSearch, setBranchSearch] = useState("");
@@ -135,8 +142,26 @@ export function ExistingWorktreesList({ repositoryId, onOpenSuccess }: ExistingW
 		}
 	};
 
+	const handleImportExisting = async () => {
+		try {
+			const { nodes } = await importExistingNodes.mutateAsync({ repositoryId });
+			if (nodes.length === 0) {
+				toast.info("No untracked worktrees found");
+				return;
+			}
+			onOpenSuccess();
+			toast.success(`Imported ${nodes.length} ${nodes.length === 1 ? "node" : "nodes"}`);
+		} catch (err) {
+			toast.error(err instanceof Error ? err.message : "Failed to import worktrees");
+		}
+	};
+
 	const isLoading = isWorktreesLoading || isBranchesLoading;
-	const isPending = openWorktree.isPending || createNode.isPending || createFromPr.isPending;
+	const isPending =
+		openWorktree.isPending ||
+		createNode.isPending ||
+		createFromPr.isPending ||
+		importExistingNodes.isPending;
 
 	if (isLoading) {
 		return <div className="py-6 text-center text-xs text-muted-foreground">Loading...</div>;
@@ -184,6 +209,15 @@ export function ExistingWorktreesList({ repositoryId, onOpenSuccess }: ExistingW
 					Paste a PR URL above or create a new branch.
 				</div>
 			)}
+
+			<button
+				type="button"
+				onClick={handleImportExisting}
+				disabled={isPending}
+				className="w-full py-1 text-center text-xs text-muted-foreground hover:text-foreground transition-colors disabled:opacity-50"
+			>
+				Find worktrees missing from Caspian
+			</button>
 		</div>
 	);
 }
@@ -0,0 +1 @@
+export { useRateLimitListener } from "./useRateLimitListener";
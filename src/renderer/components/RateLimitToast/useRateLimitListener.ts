@@ -0,0 +1,34 @@
+import { electronTrpc } from "renderer/lib/electron-trpc";
+import { toast } from "ui/components/ui/sonner";
+
+const RATE_LIMIT_TOAST_ID = "agent-rate-limit";
+
+function formatResumeTime(timestamp: number): string {
+	return new Date(timestamp).toLocaleTimeString([], { hour: "numeric", minute: "2-digit" });
+}
+
+/** Keeps a toast up while agents are backing off from a rate limit */
+export function useRateLimitListener() {
+	const cancelResume = electronTrpc.terminal.cancelRateLimitResume.useMutation();
+
+	electronTrpc.terminal.onRateLimitChange.useSubscription(undefined, {
+		onData: ({ panes }) => {
+			if (panes.length === 0) {
+				toast.dismiss(RATE_LIMIT_TOAST_ID);
+				return;
+			}
+
+			const [next] = panes;
+			const agents = panes.length === 1 ? "1 agent" : `${panes.length} agents`;
+			toast.warning(`Rate limited: ${agents} waiting`, {
+				id: RATE_LIMIT_TOAST_ID,
+				description: `Resuming one at a time from ${formatResumeTime(next.resumeAt)}`,
+				duration: Number.POSITIVE_INFINITY,
+				action: {
+					label: "Don't resume",
+					onClick: () => cancelResume.mutate({}),
+				},
+			});
+		},
+	});
+}
@@ -0,0 +1,124 @@
+import { useNavigate } from "@tanstack/react-router";
+import { useState } from "react";
+import { electronTrpc } from "renderer/lib/electron-trpc";
+import { navigateToNode } from "renderer/routes/_authenticated/_dashboard/utils/node-navigation";
+import { useTabsStore } from "renderer/stores/tabs/store";
+import type { RestoreReportNode } from "shared/types/restore-report";
+import { Button } from "ui/components/ui/button";
+import {
+	Dialog,
+	DialogContent,
+	DialogDescription,
+	DialogFooter,
+	DialogHeader,
+	DialogTitle,
+} from "ui/components/ui/dialog";
+
+function describeNode(node: RestoreReportNode): string {
+	const parts: string[] = [];
+	if (node.interruptedAgents.length > 0) {
+		const count = node.interruptedAgents.length;
+		parts.push(count === 1 ? "1 agent interrupted" : `${count} agents interrupted`);
+	}
+	if (node.pendingInput.length > 0) {
+		const count = node.pendingInput.length;
+		parts.push(count === 1 ? "1 agent needs input" : `${count} agents need input`);
+	}
+	if (node.hasUncommittedChanges) parts.push("uncommitted changes");
+	const summary = parts.join(", ");
+	return summary.charAt(0).toUpperCase() + summary.slice(1);
+}
+
+function RestoreRow({
+	node,
+	onResume,
+}: {
+	node: RestoreReportNode;
+	onResume: (node: RestoreReportNode) => void;
+}) {
+	const location = [node.repositoryName, node.nodeName].filter(Boolean).join(" / ");
+	const resumeCommands = [
+		...new Set(node.interruptedAgents.flatMap((agent) => agent.resumeCommand ?? [])),
+	];
+	return (
+		<div className="flex items-center gap-3 rounded-md px-2 py-2 hover:bg-accent">
+			<div className="min-w-0 flex-1">
+				<p className="truncate text-sm">{location}</p>
+				<p className="truncate text-xs text-muted-foreground">{describeNode(node)}</p>
+				{resumeCommands.length > 0 && (
+					<p className="truncate text-xs text-muted-foreground">
+						To continue, run{" "}
+						{resumeCommands.map((command, index) => (
+							<span key={command}>
+								{index > 0 && " or "}
+								<code className="font-mono">{command}</code>
+							</span>
+						))}
+					</p>
+				)}
+			</div>
+			<Button variant="outline" size="sm" onClick={() => onResume(node)}>
+				Resume
+			</Button>
+		</div>
+	);
+}
+
+/**
+ * Shown once after launch when the previous session left agents interrupted,
+ * prompts unanswered or changes uncommitted, with a shortcut back to each node.
+ */
+export function RestoreReportDialog() {
+	const navigate = useNavigate();
+	const [isOpen, setIsOpen] = useState(true);
+	const { data: report } = electronTrpc.nodes.getRestoreReport.useQuery(undefined, {
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+	const dismissReport = electronTrpc.nodes.dismissRestoreReport.useMutation();
+	const setActiveTab = useTabsStore((s) => s.setActiveTab);
+	const setFocusedPane = useTabsStore((s) => s.setFocusedPane);
+
+	const close = () => {
+		setIsOpen(false);
+		dismissReport.mutate();
+	};
+
+	const handleResume = (node: RestoreReportNode) => {
+		close();
+		navigateToNode(node.nodeId, navigate);
+
+		// Prefer the prompt that's still waiting, since that agent can carry on as is
+		const pane = node.pendingInput[0] ?? node.interruptedAgents[0];
+		if (pane && useTabsStore.getState().tabs.some((tab) => tab.id === pane.tabId)) {
+			setActiveTab(node.nodeId, pane.tabId);
+			setFocusedPane(pane.tabId, pane.paneId);
+		}
+	};
+
+	if (!report || report.nodes.length === 0) return null;
+
+	return (
+		<Dialog modal open={isOpen} onOpenChange={(open) => !open && close()}>
+			<DialogContent className="sm:max-w-xl">
+				<DialogHeader>
+					<DialogTitle>Pick up where you left off</DialogTitle>
+					<DialogDescription>
+						These nodes had work in progress when Caspian last quit
+					</DialogDescription>
+				</DialogHeader>
+
+				<div className="max-h-[60vh] overflow-y-auto -mx-2">
+					{report.nodes.map((node) => (
+						<RestoreRow key={node.nodeId} node={node} onResume={handleResume} />
+					))}
+				</div>
+
+				<DialogFooter>
+					<Button variant="ghost" onClick={close}>
+						Dismiss
+					</Button>
+				</DialogFooter>
+			</DialogContent>
+		</Dialog>
+	);
+}
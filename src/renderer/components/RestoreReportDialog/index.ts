@@ -0,0 +1 @@
+export { RestoreReportDialog } from "./RestoreReportDialog";
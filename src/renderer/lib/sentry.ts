@@ -1,5 +1,8 @@
+// import { electronTrpcClient } from "./trpc-client";
+
 // Sentry error tracking - currently disabled
 // To enable, uncomment the code below and set SENTRY_DSN
+// The privacy setting lives in the main process; it's read once at startup here
 
 // const SENTRY_DSN = "";
 
@@ -11,14 +14,17 @@ export async function initSentry(): Promise<void> {
 	// 	return;
 	// }
 	// try {
+	// 	const { effectiveTelemetryLevel } = await electronTrpcClient.settings.getPrivacy.query();
+	// 	if (effectiveTelemetryLevel === "off") return;
 	// 	// Dynamic import to avoid bundler issues
 	// 	const Sentry = await import("@sentry/electron/renderer");
 	// 	Sentry.init({
 	// 		dsn: SENTRY_DSN,
 	// 		environment: process.env.NODE_ENV,
 	// 		tracesSampleRate: 0.1,
-	// 		replaysSessionSampleRate: 0.1,
+	// 		replaysSessionSampleRate: effectiveTelemetryLevel === "full" ? 0.1 : 0,
 	// 		replaysOnErrorSampleRate: 1.0,
+	// 		beforeBreadcrumb: (breadcrumb) => (effectiveTelemetryLevel === "full" ? breadcrumb : null),
 	// 	});
 	// 	sentryInitialized = true;
 	// 	console.log("[sentry] Initialized in renderer process");
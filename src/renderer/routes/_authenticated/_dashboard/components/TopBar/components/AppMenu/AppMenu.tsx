@@ -1,11 +1,13 @@
 import { FaGithub, FaXTwitter } from "react-icons/fa6";
 import {
+	HiOutlineBellAlert,
 	HiOutlineChatBubbleLeftRight,
 	HiOutlineCog6Tooth,
 	HiOutlineEnvelope,
 } from "react-icons/hi2";
 import { IoBugOutline } from "react-icons/io5";
 import { LuKeyboard, LuSettings2 } from "react-icons/lu";
+import { useOpenActivityFeedModal } from "renderer/stores/activity-feed-modal";
 import { useHotkeyText } from "renderer/stores/hotkeys";
 import { useOpenSettings } from "renderer/stores/settings-state";
 import { COMPANY } from "shared/shared-constants";
@@ -23,6 +25,7 @@ import {
 
 export function AppMenu() {
 	const openSettings = useOpenSettings();
+	const openActivityFeed = useOpenActivityFeedModal();
 	const settingsHotkey = useHotkeyText("OPEN_SETTINGS");
 	const shortcutsHotkey = useHotkeyText("SHOW_HOTKEYS");
 
@@ -51,6 +54,11 @@ export function AppMenu() {
 					)}
 				</DropdownMenuItem>
 
+				<DropdownMenuItem onSelect={() => openActivityFeed()}>
+					<HiOutlineBellAlert className="h-4 w-4" />
+					<span>Activity</span>
+				</DropdownMenuItem>
+
 				<DropdownMenuSeparator />
 
 				{/* Help & Support */}
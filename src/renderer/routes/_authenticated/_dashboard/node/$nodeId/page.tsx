@@ -23,6 +23,7 @@ import {
 	getPreviousPaneId,
 	resolveActiveTabIdForNode,
 } from "renderer/stores/tabs/utils";
+import { getCaspianErrorData } from "shared/types/errors";
 
 export const Route = createFileRoute("/_authenticated/_dashboard/node/$nodeId/")({
 	component: NodePage,
@@ -37,7 +38,7 @@ export const Route = createFileRoute("/_authenticated/_dashboard/node/$nodeId/")
 			});
 		} catch (error) {
 			// If node not found, throw notFound() to render 404 page
-			if (error instanceof Error && error.message.includes("not found")) {
+			if (getCaspianErrorData(error)?.code === "NODE_NOT_FOUND") {
 				throw notFound();
 			}
 			// Re-throw other errors
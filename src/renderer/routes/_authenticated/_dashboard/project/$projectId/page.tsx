@@ -8,6 +8,7 @@ import { formatRelativeTime } from "renderer/lib/formatRelativeTime";
 import { electronTrpcClient as trpcClient } from "renderer/lib/trpc-client";
 import { useCreateNode } from "renderer/react-query/nodes";
 import { NotFound } from "renderer/routes/not-found";
+import { getCaspianErrorData } from "shared/types/errors";
 import { Button } from "ui/components/ui/button";
 import { Collapsible, CollapsibleTrigger } from "ui/components/ui/collapsible";
 import {
@@ -34,7 +35,7 @@ export const Route = createFileRoute("/_authenticated/_dashboard/project/$projec
 				queryFn: () => trpcClient.repositories.get.query({ id: params.projectId }),
 			});
 		} catch (error) {
-			if (error instanceof Error && error.message.includes("not found")) {
+			if (getCaspianErrorData(error)?.code === "REPOSITORY_NOT_FOUND") {
 				throw notFound();
 			}
 			throw error;
@@ -1,8 +1,11 @@
 import { createFileRoute, Outlet, useNavigate } from "@tanstack/react-router";
 import { DndProvider } from "react-dnd";
+import { ActivityFeedModal } from "renderer/components/ActivityFeedModal";
 import { DashboardModal } from "renderer/components/DashboardModal";
 import { NewNodeModal } from "renderer/components/NewNodeModal";
 import { NodeSwitcherModal } from "renderer/components/NodeSwitcherModal";
+import { useRateLimitListener } from "renderer/components/RateLimitToast";
+import { RestoreReportDialog } from "renderer/components/RestoreReportDialog";
 import { SettingsModal } from "renderer/components/SettingsModal";
 import { useUpdateListener } from "renderer/components/UpdateToast";
 import { dragDropManager } from "renderer/lib/dnd";
@@ -12,6 +15,7 @@ import { useHotkeysSync } from "renderer/stores/hotkeys";
 import { useNodeInitStore } from "renderer/stores/node-init";
 import { useSettingsStore } from "renderer/stores/settings-state";
 import { useAgentHookListener } from "renderer/stores/tabs/useAgentHookListener";
+import { toast } from "ui/components/ui/sonner";
 import { AgentHooks } from "./components/AgentHooks";
 import { CollectionsProvider } from "./providers/CollectionsProvider";
 
@@ -27,6 +31,7 @@ function AuthenticatedLayout() {
 	// Global hooks and subscriptions (these don't need CollectionsProvider)
 	useAgentHookListener();
 	useUpdateListener();
+	useRateLimitListener();
 	useHotkeysSync();
 
 	// Node initialization progress subscription
@@ -45,6 +50,17 @@ function AuthenticatedLayout() {
 		},
 	});
 
+	electronTrpc.terminal.onAgentMemoryLimit.useSubscription(undefined, {
+		onData: (event) => {
+			toast.error(event.reason, {
+				action: {
+					label: "Open",
+					onClick: () => navigate({ to: `/node/${event.nodeId}` }),
+				},
+			});
+		},
+	});
+
 	// Menu navigation subscription
 	electronTrpc.menu.subscribe.useSubscription(undefined, {
 		onData: (event) => {
@@ -63,8 +79,10 @@ function AuthenticatedLayout() {
 				<Outlet />
 				<NodeInitEffects />
 				<DashboardModal />
+				<ActivityFeedModal />
 				<NewNodeModal />
 				<NodeSwitcherModal />
+				<RestoreReportDialog />
 				<SettingsModal />
 			</CollectionsProvider>
 		</DndProvider>
@@ -1,4 +1,9 @@
-import type { BranchPrefixMode, TerminalLinkBehavior } from "lib/local-db";
+import type {
+	BranchPrefixMode,
+	OutputRedactionMode,
+	TelemetryLevel,
+	TerminalLinkBehavior,
+} from "lib/local-db";
 import { useEffect, useMemo, useState } from "react";
 import { HiMagnifyingGlass } from "react-icons/hi2";
 import { electronTrpc } from "renderer/lib/electron-trpc";
@@ -9,6 +14,7 @@ import {
 	useHotkeysByCategory,
 	useHotkeysStore,
 } from "renderer/stores/hotkeys";
+import type { UpdateChannel } from "shared/auto-update";
 import {
 	formatHotkeyText,
 	HOTKEYS,
@@ -18,6 +24,7 @@ import {
 	isOsReservedHotkey,
 	isTerminalReservedHotkey,
 } from "shared/hotkeys";
+import { LOCALE_NAMES, type Locale } from "shared/i18n/translate";
 import { resolveBranchPrefix, sanitizeSegment } from "shared/utils/branch";
 import {
 	AlertDialog,
@@ -41,9 +48,12 @@ import {
 import { toast } from "ui/components/ui/sonner";
 import { Switch } from "ui/components/ui/switch";
 import { BRANCH_PREFIX_MODE_LABELS } from "../../../utils/branch-prefix";
+import { DiagnosticsSection } from "./components/DiagnosticsSection";
 
 const CATEGORY_ORDER: HotkeyCategory[] = ["Node", "Terminal", "Layout", "Window", "Help"];
 
+const MEMORY_LIMIT_OPTIONS_MB = [2048, 4096, 8192, 16384];
+
 function HotkeyRow({
 	id,
 	label,
@@ -289,6 +299,72 @@ export function PreferencesSettings() {
 		setConfirmOnQuit.mutate({ enabled });
 	};
 
+	const { data: updateChannel, isLoading: isUpdateChannelLoading } =
+		electronTrpc.settings.getUpdateChannel.useQuery();
+	const setUpdateChannel = electronTrpc.settings.setUpdateChannel.useMutation({
+		onMutate: async ({ channel }) => {
+			await utils.settings.getUpdateChannel.cancel();
+			const previous = utils.settings.getUpdateChannel.getData();
+			utils.settings.getUpdateChannel.setData(undefined, channel);
+			return { previous };
+		},
+		onError: (_err, _vars, context) => {
+			if (context?.previous !== undefined) {
+				utils.settings.getUpdateChannel.setData(undefined, context.previous);
+			}
+		},
+		onSettled: () => {
+			utils.settings.getUpdateChannel.invalidate();
+		},
+	});
+
+	const { data: localeSettings, isLoading: isLocaleLoading } =
+		electronTrpc.settings.getLocale.useQuery();
+	const setLocale = electronTrpc.settings.setLocale.useMutation({
+		onSettled: () => {
+			utils.settings.getLocale.invalidate();
+		},
+	});
+
+	const { data: agentMemoryLimit, isLoading: isMemoryLimitLoading } =
+		electronTrpc.settings.getAgentMemoryLimit.useQuery();
+	const setAgentMemoryLimit = electronTrpc.settings.setAgentMemoryLimit.useMutation({
+		onSettled: () => {
+			utils.settings.getAgentMemoryLimit.invalidate();
+		},
+	});
+
+	const { data: outputRedaction, isLoading: isOutputRedactionLoading } =
+		electronTrpc.settings.getOutputRedaction.useQuery();
+	const setOutputRedaction = electronTrpc.settings.setOutputRedaction.useMutation({
+		onSettled: () => {
+			utils.settings.getOutputRedaction.invalidate();
+		},
+	});
+
+	const { data: privacy, isLoading: isPrivacyLoading } =
+		electronTrpc.settings.getPrivacy.useQuery();
+	const setPrivacy = electronTrpc.settings.setPrivacy.useMutation({
+		onSuccess: (data) => {
+			utils.settings.getPrivacy.setData(undefined, data);
+		},
+		onError: (error) => {
+			toast.error(`Failed to update privacy settings: ${error.message}`);
+		},
+	});
+
+	const checkForUpdates = electronTrpc.autoUpdate.check.useMutation({
+		onSuccess: (result) => {
+			if (result.error) {
+				toast.error(result.error);
+			} else if (result.availableVersion) {
+				toast.success(`Version ${result.availableVersion} is downloading`);
+			} else {
+				toast.success(`You're up to date (${result.currentVersion})`);
+			}
+		},
+	});
+
 	const { data: branchPrefix, isLoading: isBranchPrefixLoading } =
 		electronTrpc.settings.getBranchPrefix.useQuery();
 	const { data: gitInfo } = electronTrpc.settings.getGitInfo.useQuery();
@@ -487,6 +563,68 @@ export function PreferencesSettings() {
 						/>
 					</div>
 
+					<div className="flex items-center justify-between">
+						<div className="space-y-0.5">
+							<Label className="text-sm font-medium">Language</Label>
+							<p className="text-xs text-muted-foreground">
+								Used for system notifications and dialogs
+							</p>
+						</div>
+						<Select
+							value={localeSettings?.locale ?? "system"}
+							onValueChange={(value) =>
+								setLocale.mutate({ locale: value === "system" ? null : (value as Locale) })
+							}
+							disabled={isLocaleLoading || setLocale.isPending}
+						>
+							<SelectTrigger className="w-[180px]">
+								<SelectValue />
+							</SelectTrigger>
+							<SelectContent>
+								<SelectItem value="system">
+									System
+									{localeSettings && ` (${LOCALE_NAMES[localeSettings.systemLocale]})`}
+								</SelectItem>
+								{(Object.entries(LOCALE_NAMES) as [Locale, string][]).map(([value, label]) => (
+									<SelectItem key={value} value={value}>
+										{label}
+									</SelectItem>
+								))}
+							</SelectContent>
+						</Select>
+					</div>
+
+					{/* Memory isn't sampled on Windows, so the limit would never apply */}
+					{platform !== "win32" && (
+						<div className="flex items-center justify-between">
+							<div className="space-y-0.5">
+								<Label className="text-sm font-medium">Agent memory limit</Label>
+								<p className="text-xs text-muted-foreground">
+									Stop an agent's terminal when it uses more memory than this
+								</p>
+							</div>
+							<Select
+								value={agentMemoryLimit ? String(agentMemoryLimit) : "off"}
+								onValueChange={(value) =>
+									setAgentMemoryLimit.mutate({ limitMb: value === "off" ? null : Number(value) })
+								}
+								disabled={isMemoryLimitLoading || setAgentMemoryLimit.isPending}
+							>
+								<SelectTrigger className="w-[180px]">
+									<SelectValue />
+								</SelectTrigger>
+								<SelectContent>
+									<SelectItem value="off">No limit</SelectItem>
+									{MEMORY_LIMIT_OPTIONS_MB.map((limitMb) => (
+										<SelectItem key={limitMb} value={String(limitMb)}>
+											{limitMb / 1024} GB
+										</SelectItem>
+									))}
+								</SelectContent>
+							</Select>
+						</div>
+					)}
+
 					<div className="flex items-center justify-between">
 						<div className="space-y-0.5">
 							<Label className="text-sm font-medium">Branch prefix</Label>
@@ -530,6 +668,119 @@ export function PreferencesSettings() {
 					</div>
 				</section>
 
+				{/* Updates Section */}
+				<section className="space-y-4">
+					<h3 className="text-sm font-medium">Updates</h3>
+
+					<div className="flex items-center justify-between">
+						<div className="space-y-0.5">
+							<Label className="text-sm font-medium">Release channel</Label>
+							<p className="text-xs text-muted-foreground">
+								Canary gets new features first and may be less stable
+							</p>
+						</div>
+						<div className="flex items-center gap-2">
+							<Button
+								variant="outline"
+								size="sm"
+								onClick={() => checkForUpdates.mutate()}
+								disabled={checkForUpdates.isPending}
+							>
+								{checkForUpdates.isPending ? "Checking..." : "Check now"}
+							</Button>
+							<Select
+								value={updateChannel ?? "stable"}
+								onValueChange={(value) =>
+									setUpdateChannel.mutate({ channel: value as UpdateChannel })
+								}
+								disabled={isUpdateChannelLoading || setUpdateChannel.isPending}
+							>
+								<SelectTrigger className="w-[180px]">
+									<SelectValue />
+								</SelectTrigger>
+								<SelectContent>
+									<SelectItem value="stable">Stable</SelectItem>
+									<SelectItem value="canary">Canary</SelectItem>
+								</SelectContent>
+							</Select>
+						</div>
+					</div>
+				</section>
+
+				{/* Privacy Section */}
+				<section className="space-y-4">
+					<h3 className="text-sm font-medium">Privacy</h3>
+
+					<div className="flex items-center justify-between">
+						<div className="space-y-0.5">
+							<Label className="text-sm font-medium">Telemetry</Label>
+							<p className="text-xs text-muted-foreground">
+								What error reporting and usage analytics may send
+							</p>
+						</div>
+						<Select
+							value={privacy?.effectiveTelemetryLevel ?? "errors"}
+							onValueChange={(value) =>
+								setPrivacy.mutate({ telemetryLevel: value as TelemetryLevel })
+							}
+							disabled={isPrivacyLoading || setPrivacy.isPending || privacy?.localOnly}
+						>
+							<SelectTrigger className="w-[180px]">
+								<SelectValue />
+							</SelectTrigger>
+							<SelectContent>
+								<SelectItem value="off">Off</SelectItem>
+								<SelectItem value="errors">Errors only</SelectItem>
+								<SelectItem value="full">Errors and usage</SelectItem>
+							</SelectContent>
+						</Select>
+					</div>
+
+					<div className="flex items-center justify-between">
+						<div className="space-y-0.5">
+							<Label htmlFor="local-only-mode" className="text-sm font-medium">
+								Local-only mode
+							</Label>
+							<p className="text-xs text-muted-foreground">
+								Turn off telemetry, update checks, webhooks, pull request status and issue
+								trackers. Git and agent commands you run still use the network.
+							</p>
+						</div>
+						<Switch
+							id="local-only-mode"
+							checked={privacy?.localOnly ?? false}
+							onCheckedChange={(localOnly) => setPrivacy.mutate({ localOnly })}
+							disabled={isPrivacyLoading || setPrivacy.isPending}
+						/>
+					</div>
+
+					<div className="flex items-center justify-between">
+						<div className="space-y-0.5">
+							<Label className="text-sm font-medium">Redact secrets in stored output</Label>
+							<p className="text-xs text-muted-foreground">
+								Scrub tokens and passwords from terminal history, test and gate output, and the
+								activity log. Strict also catches random-looking keys.
+							</p>
+						</div>
+						<Select
+							value={outputRedaction ?? "patterns"}
+							onValueChange={(value) =>
+								setOutputRedaction.mutate({ mode: value as OutputRedactionMode })
+							}
+							disabled={isOutputRedactionLoading || setOutputRedaction.isPending}
+						>
+							<SelectTrigger className="w-[180px]">
+								<SelectValue />
+							</SelectTrigger>
+							<SelectContent>
+								<SelectItem value="off">Off</SelectItem>
+								<SelectItem value="patterns">Known formats</SelectItem>
+								<SelectItem value="strict">Strict</SelectItem>
+							</SelectContent>
+						</Select>
+					</div>
+				</section>
+
 				{/* Link Handling Section */}
 				<section className="space-y-4">
 					<h3 className="text-sm font-medium">Link Handling</h3>
@@ -558,6 +809,8 @@ export function PreferencesSettings() {
 						</Select>
 					</div>
 				</section>
+
+				<DiagnosticsSection />
 			</div>
 
 			{/* Conflict dialog */}
@@ -0,0 +1,136 @@
+import { HiCheckCircle, HiExclamationTriangle, HiMinusCircle, HiXCircle } from "react-icons/hi2";
+import { electronTrpc } from "renderer/lib/electron-trpc";
+import type {
+	DiagnosticCategory,
+	DiagnosticCheck,
+	DiagnosticStatus,
+} from "shared/types/diagnostics";
+import { Button } from "ui/components/ui/button";
+import { toast } from "ui/components/ui/sonner";
+
+const CATEGORY_LABELS: Record<DiagnosticCategory, string> = {
+	agents: "Agents",
+	tools: "Tools",
+	system: "System",
+};
+
+function StatusIcon({ status }: { status: DiagnosticStatus }) {
+	switch (status) {
+		case "ok":
+			return <HiCheckCircle className="size-4 shrink-0 text-emerald-500" />;
+		case "skipped":
+			return <HiMinusCircle className="size-4 shrink-0 text-muted-foreground" />;
+		case "warning":
+			return <HiExclamationTriangle className="size-4 shrink-0 text-amber-500" />;
+		case "error":
+			return <HiXCircle className="size-4 shrink-0 text-destructive" />;
+	}
+}
+
+function CheckRow({ check }: { check: DiagnosticCheck }) {
+	return (
+		<div className="flex items-start gap-2 py-1.5">
+			<StatusIcon status={check.status} />
+			<div className="min-w-0 space-y-0.5">
+				<p className="text-sm">
+					{check.label}
+					{check.detail && (
+						<span className="ml-2 text-xs text-muted-foreground font-mono break-all">
+							{check.detail}
+						</span>
+					)}
+				</p>
+				{check.hint && <p className="text-xs text-muted-foreground">{check.hint}</p>}
+			</div>
+		</div>
+	);
+}
+
+export function DiagnosticsSection() {
+	const runDiagnostics = electronTrpc.settings.runDiagnostics.useMutation({
+		onError: (error) => toast.error(`Diagnostics failed: ${error.message}`),
+	});
+	const exportLogs = electronTrpc.logs.exportLogs.useMutation();
+	const supportBundle = electronTrpc.support.generateSupportBundle.useMutation();
+	const report = runDiagnostics.data;
+
+	const handleCopy = async () => {
+		if (!report) return;
+		await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+		toast.success("Report copied to clipboard");
+	};
+
+	const handleExportLogs = async () => {
+		try {
+			const result = await exportLogs.mutateAsync();
+			if (result.canceled) return;
+			if ("error" in result) {
+				toast.error("Failed to export logs", { description: result.error });
+				return;
+			}
+			toast.success("Logs exported", { description: result.path });
+		} catch (error) {
+			toast.error("Failed to export logs", {
+				description: error instanceof Error ? error.message : undefined,
+			});
+		}
+	};
+
+	const handleSupportBundle = async () => {
+		try {
+			const result = await supportBundle.mutateAsync();
+			if (result.canceled) return;
+			if ("error" in result) {
+				toast.error("Failed to create support bundle", { description: result.error });
+				return;
+			}
+			toast.success("Support bundle saved", {
+				description: "Attach it to your GitHub issue. Secrets and home paths were removed.",
+			});
+		} catch (error) {
+			toast.error("Failed to create support bundle", {
+				description: error instanceof Error ? error.message : undefined,
+			});
+		}
+	};
+
+	return (
+		<section className="space-y-4">
+			<div className="flex items-center justify-between">
+				<div className="space-y-0.5">
+					<h3 className="text-sm font-medium">Diagnostics</h3>
+					<p className="text-xs text-muted-foreground">
+						Check agents, git, gh, node, disk space and keychain access
+					</p>
+				</div>
+				<div className="flex items-center gap-2">
+					{report && (
+						<Button variant="outline" size="sm" onClick={handleCopy}>
+							Copy report
+						</Button>
+					)}
+					<Button
+						variant="outline"
+						size="sm"
+						onClick={() => runDiagnostics.mutate()}
+						disabled={runDiagnostics.isPending}
+					>
+						{runDiagnostics.isPending ? "Running..." : report ? "Run again" : "Run diagnostics"}
+					</Button>
+				</div>
+			</div>
+
+			{report &&
+				(Object.keys(CATEGORY_LABELS) as DiagnosticCategory[]).map((category) => (
+					<div key={category}>
+						<p className="text-xs font-medium text-muted-foreground">{CATEGORY_LABELS[category]}</p>
+						{report.checks
+							.filter((check) => check.category === category)
+							.map((check) => (
+								<CheckRow key={check.id} check={check} />
+							))}
+					</div>
+				))}
+		</section>
+	);
+}
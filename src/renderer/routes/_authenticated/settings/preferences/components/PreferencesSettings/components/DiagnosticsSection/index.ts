@@ -0,0 +1 @@
+export { DiagnosticsSection } from "./DiagnosticsSection";
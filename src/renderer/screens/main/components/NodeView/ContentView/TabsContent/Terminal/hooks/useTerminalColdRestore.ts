@@ -2,6 +2,7 @@ import type { FitAddon } from "@xterm/addon-fit";
 import type { Terminal as XTerm } from "@xterm/xterm";
 import { useCallback, useRef, useState } from "react";
 import { electronTrpcClient as trpcClient } from "renderer/lib/trpc-client";
+import { getCaspianErrorData } from "shared/types/errors";
 import { coldRestoreState } from "../state";
 import type { CreateOrAttachMutate, CreateOrAttachResult, TerminalStreamEvent } from "../types";
 import { scrollToBottom } from "../utils";
@@ -129,7 +130,7 @@ export function useTerminalColdRestore({
 					}
 				},
 				onError: (error: { message?: string }) => {
-					if (error.message?.includes("TERMINAL_SESSION_KILLED")) {
+					if (getCaspianErrorData(error)?.code === "TERMINAL_SESSION_KILLED") {
 						wasKilledByUserRef.current = true;
 						isExitedRef.current = true;
 						isStreamReadyRef.current = false;
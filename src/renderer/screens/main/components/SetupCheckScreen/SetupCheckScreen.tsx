@@ -9,7 +9,7 @@ interface SetupCheckScreenProps {
 }
 
 export function SetupCheckScreen({ onContinue }: SetupCheckScreenProps) {
-	const { data, isLoading, isError } = electronTrpc.settings.getToolStatus.useQuery();
+	const { data, isLoading, isError } = electronTrpc.settings.getOnboardingStatus.useQuery();
 
 	return (
 		<div className="flex-1 h-full flex flex-col items-center justify-center bg-background px-6">
@@ -56,12 +56,32 @@ export function SetupCheckScreen({ onContinue }: SetupCheckScreenProps) {
 						</div>
 					) : data ? (
 						<>
-							<GitStatus available={data.git.available} />
+							<GitStatus
+								available={data.git.installed}
+								configured={!!data.git.userName && !!data.git.userEmail}
+							/>
 							<GhStatus
-								installed={data.gh.installed}
-								authenticated={data.gh.authenticated}
-								username={data.gh.username}
+								installed={data.github.ghInstalled}
+								username={data.github.ghUsername}
+								tokenAccounts={data.github.tokenAccounts}
 							/>
+							<AgentsStatus agents={data.agents} />
+							{!data.dataDir.writable && (
+								<StatusRow
+									icon={<LuCircleX className="w-4 h-4 text-red-400" />}
+									label="Data folder"
+									description={`Can't write to ${data.dataDir.path}`}
+									variant="error"
+								/>
+							)}
+							{!data.notificationsSupported && (
+								<StatusRow
+									icon={<LuTriangleAlert className="w-4 h-4 text-amber-400" />}
+									label="Notifications"
+									description="Not supported on this system"
+									variant="warning"
+								/>
+							)}
 						</>
 					) : null}
 				</motion.div>
@@ -84,8 +104,8 @@ export function SetupCheckScreen({ onContinue }: SetupCheckScreenProps) {
 	);
 }
 
-function GitStatus({ available }: { available: boolean }) {
-	if (available) {
+function GitStatus({ available, configured }: { available: boolean; configured: boolean }) {
+	if (available && configured) {
 		return (
 			<StatusRow
 				icon={<LuCheck className="w-4 h-4 text-emerald-400" />}
@@ -96,6 +116,32 @@ function GitStatus({ available }: { available: boolean }) {
 		);
 	}
 
+	if (available) {
+		return (
+			<StatusRow
+				icon={<LuTriangleAlert className="w-4 h-4 text-amber-400" />}
+				label="Git"
+				description="Name and email not set"
+				variant="warning"
+			>
+				<Hint>
+					<LuTerminal className="w-3 h-3 shrink-0 mt-0.5" />
+					<span>
+						Run{" "}
+						<code className="px-1 py-0.5 rounded bg-muted text-foreground text-xs">
+							git config --global user.name
+						</code>{" "}
+						and{" "}
+						<code className="px-1 py-0.5 rounded bg-muted text-foreground text-xs">
+							user.email
+						</code>{" "}
+						so agents can commit.
+					</span>
+				</Hint>
+			</StatusRow>
+		);
+	}
+
 	return (
 		<StatusRow
 			icon={<LuCircleX className="w-4 h-4 text-red-400" />}
@@ -108,14 +154,14 @@ function GitStatus({ available }: { available: boolean }) {
 
 function GhStatus({
 	installed,
-	authenticated,
 	username,
+	tokenAccounts,
 }: {
 	installed: boolean;
-	authenticated: boolean;
 	username: string | null;
+	tokenAccounts: number;
 }) {
-	if (authenticated && username) {
+	if (username) {
 		return (
 			<StatusRow
 				icon={<LuCheck className="w-4 h-4 text-emerald-400" />}
@@ -126,6 +172,17 @@ function GhStatus({
 		);
 	}
 
+	if (tokenAccounts > 0) {
+		return (
+			<StatusRow
+				icon={<LuCheck className="w-4 h-4 text-emerald-400" />}
+				label="GitHub"
+				description="Connected with an access token"
+				variant="success"
+			/>
+		);
+	}
+
 	if (installed) {
 		return (
 			<StatusRow
@@ -172,6 +229,32 @@ function GhStatus({
 	);
 }
 
+function AgentsStatus({ agents }: { agents: { id: string; label: string }[] }) {
+	if (agents.length > 0) {
+		return (
+			<StatusRow
+				icon={<LuCheck className="w-4 h-4 text-emerald-400" />}
+				label="Agents"
+				description={agents.map((agent) => agent.label).join(", ")}
+				variant="success"
+			/>
+		);
+	}
+
+	return (
+		<StatusRow
+			icon={<LuTriangleAlert className="w-4 h-4 text-amber-400" />}
+			label="Agents"
+			description="No coding agent found"
+			variant="warning"
+		>
+			<Hint>
+				<span>Install Claude Code, Codex or OpenCode to run agents in your nodes.</span>
+			</Hint>
+		</StatusRow>
+	);
+}
+
 function StatusRow({
 	icon,
 	label,
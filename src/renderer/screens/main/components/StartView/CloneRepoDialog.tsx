@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useRef, useState } from "react";
 import { electronTrpc } from "renderer/lib/electron-trpc";
 import { useCreateNode } from "renderer/react-query/nodes";
 
@@ -12,6 +12,8 @@ export function CloneRepoDialog({ isOpen, onClose, onError }: CloneRepoDialogPro
 	const [url, setUrl] = useState("");
 	const utils = electronTrpc.useUtils();
 	const cloneRepo = electronTrpc.repositories.cloneRepo.useMutation();
+	const cancelOperation = electronTrpc.operations.cancel.useMutation();
+	const cloneOperationId = useRef<string | null>(null);
 	const createNode = useCreateNode();
 
 	const handleClone = async () => {
@@ -20,9 +22,14 @@ export function CloneRepoDialog({ isOpen, onClose, onError }: CloneRepoDialogPro
 			return;
 		}
 
+		const operationId = crypto.randomUUID();
+		cloneOperationId.current = operationId;
 		cloneRepo.mutate(
-			{ url: url.trim() },
+			{ url: url.trim(), operationId },
 			{
+				onSettled: () => {
+					cloneOperationId.current = null;
+				},
 				onSuccess: (result: {
 					canceled?: boolean;
 					success?: boolean;
@@ -56,6 +63,15 @@ export function CloneRepoDialog({ isOpen, onClose, onError }: CloneRepoDialogPro
 
 	const isLoading = cloneRepo.isPending || createNode.isPending;
 
+	const handleCancel = () => {
+		// Cancelling mid-clone stops git; the dialog stays open
+		if (cloneOperationId.current) {
+			cancelOperation.mutate({ operationId: cloneOperationId.current });
+			return;
+		}
+		onClose();
+	};
+
 	return (
 		<div className="fixed inset-0 z-50 flex items-center justify-center bg-black/70">
 			<div className="bg-card border border-border rounded-lg p-5 sm:p-8 w-full max-w-[calc(100%-2rem)] sm:max-w-md mx-4 sm:mx-0 elevation-3">
@@ -90,8 +106,8 @@ export function CloneRepoDialog({ isOpen, onClose, onError }: CloneRepoDialogPro
 					<div className="flex gap-3 justify-end pt-2">
 						<button
 							type="button"
-							onClick={onClose}
-							disabled={isLoading}
+							onClick={handleCancel}
+							disabled={createNode.isPending}
 							className="px-4 py-2 rounded-md border border-border text-foreground hover:bg-accent transition-colors disabled:opacity-50 disabled:cursor-not-allowed text-sm"
 						>
 							Cancel
@@ -0,0 +1,32 @@
+import { create } from "zustand";
+import { devtools } from "zustand/middleware";
+
+interface ActivityFeedModalState {
+	isOpen: boolean;
+	openModal: () => void;
+	closeModal: () => void;
+}
+
+export const useActivityFeedModalStore = create<ActivityFeedModalState>()(
+	devtools(
+		(set) => ({
+			isOpen: false,
+
+			openModal: () => {
+				set({ isOpen: true });
+			},
+
+			closeModal: () => {
+				set({ isOpen: false });
+			},
+		}),
+		{ name: "ActivityFeedModalStore" },
+	),
+);
+
+// Convenience hooks
+export const useActivityFeedModalOpen = () => useActivityFeedModalStore((state) => state.isOpen);
+export const useOpenActivityFeedModal = () =>
+	useActivityFeedModalStore((state) => state.openModal);
+export const useCloseActivityFeedModal = () =>
+	useActivityFeedModalStore((state) => state.closeModal);
@@ -1065,17 +1065,9 @@ export const useTabsStore = create<TabsStore>()(
 				},
 				merge: (persistedState, currentState) => {
 					const persisted = persistedState as TabsState;
-					// Clear stale transient statuses on startup:
-					// - "working": Agent can't be working if app just restarted
-					// - "permission": Permission dialog is gone after restart
-					// Note: "review" is intentionally preserved so users see missed completions
-					if (persisted.panes) {
-						for (const pane of Object.values(persisted.panes)) {
-							if (pane.status === "working" || pane.status === "permission") {
-								pane.status = "idle";
-							}
-						}
-					}
+					// Stale "working"/"permission" statuses were already cleared by the main
+					// process on startup. Agents whose terminal survived in the daemon keep
+					// theirs, and "review" is preserved so users see missed completions.
 
 					const mergedState = { ...currentState, ...persisted };
 
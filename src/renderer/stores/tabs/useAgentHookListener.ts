@@ -88,6 +88,10 @@ export function useAgentHookListener() {
 				if (currentPane?.status === "working" || currentPane?.status === "permission") {
 					state.setPaneStatus(paneId, "idle");
 				}
+			} else if (event.type === NOTIFICATION_EVENTS.OPEN_TERMINAL) {
+				if (!event.data) return;
+				state.addTab(nodeId, { initialCommands: [event.data.command] });
+				navigateToNode(nodeId, navigate);
 			} else if (event.type === NOTIFICATION_EVENTS.FOCUS_TAB) {
 				navigateToNode(nodeId, navigate);
 
@@ -0,0 +1,4 @@
+ALTER TABLE `projects` ADD `commit_author_name` text;--> statement-breakpoint
+ALTER TABLE `projects` ADD `commit_author_email` text;--> statement-breakpoint
+ALTER TABLE `projects` ADD `commit_signing_key` text;--> statement-breakpoint
+ALTER TABLE `projects` ADD `commit_signing_format` text;
@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `metrics_endpoint_enabled` integer;
@@ -0,0 +1,7 @@
+CREATE INDEX IF NOT EXISTS `projects_created_at_idx` ON `projects` (`created_at`);--> statement-breakpoint
+CREATE INDEX IF NOT EXISTS `worktrees_created_at_idx` ON `worktrees` (`created_at`);--> statement-breakpoint
+CREATE INDEX IF NOT EXISTS `nodes_created_at_idx` ON `nodes` (`created_at`);--> statement-breakpoint
+CREATE INDEX IF NOT EXISTS `users_created_at_idx` ON `users` (`created_at`);--> statement-breakpoint
+CREATE INDEX IF NOT EXISTS `organizations_created_at_idx` ON `organizations` (`created_at`);--> statement-breakpoint
+CREATE INDEX IF NOT EXISTS `organization_members_created_at_idx` ON `organization_members` (`created_at`);--> statement-breakpoint
+ALTER TABLE `settings` ADD `time_zone` text;
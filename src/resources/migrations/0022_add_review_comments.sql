@@ -0,0 +1,15 @@
+CREATE TABLE `review_comments` (
+	`id` text PRIMARY KEY NOT NULL,
+	`node_id` text NOT NULL,
+	`file_path` text NOT NULL,
+	`line` integer NOT NULL,
+	`side` text NOT NULL,
+	`body` text NOT NULL,
+	`resolved_at` integer,
+	`created_at` integer NOT NULL,
+	`updated_at` integer NOT NULL,
+	FOREIGN KEY (`node_id`) REFERENCES `nodes`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `review_comments_node_id_idx` ON `review_comments` (`node_id`);--> statement-breakpoint
+CREATE INDEX `review_comments_created_at_idx` ON `review_comments` (`created_at`);
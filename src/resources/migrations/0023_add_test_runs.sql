@@ -0,0 +1,17 @@
+CREATE TABLE `test_runs` (
+	`id` text PRIMARY KEY NOT NULL,
+	`node_id` text NOT NULL,
+	`command` text NOT NULL,
+	`framework` text,
+	`status` text NOT NULL,
+	`exit_code` integer,
+	`summary` text NOT NULL,
+	`results` text NOT NULL,
+	`output` text NOT NULL,
+	`duration_ms` integer NOT NULL,
+	`created_at` integer NOT NULL,
+	FOREIGN KEY (`node_id`) REFERENCES `nodes`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `test_runs_node_id_idx` ON `test_runs` (`node_id`);--> statement-breakpoint
+CREATE INDEX `test_runs_created_at_idx` ON `test_runs` (`created_at`);
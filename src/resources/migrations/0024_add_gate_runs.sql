@@ -0,0 +1,13 @@
+CREATE TABLE `gate_runs` (
+	`id` text PRIMARY KEY NOT NULL,
+	`node_id` text NOT NULL,
+	`head_sha` text,
+	`status` text NOT NULL,
+	`steps` text NOT NULL,
+	`created_at` integer NOT NULL,
+	`completed_at` integer,
+	FOREIGN KEY (`node_id`) REFERENCES `nodes`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `gate_runs_node_id_idx` ON `gate_runs` (`node_id`);--> statement-breakpoint
+CREATE INDEX `gate_runs_created_at_idx` ON `gate_runs` (`created_at`);
@@ -0,0 +1,2 @@
+ALTER TABLE `test_runs` ADD `coverage` text;--> statement-breakpoint
+ALTER TABLE `test_runs` ADD `coverage_report_path` text;
@@ -0,0 +1,15 @@
+CREATE TABLE `node_snapshots` (
+	`id` text PRIMARY KEY NOT NULL,
+	`node_id` text NOT NULL,
+	`label` text NOT NULL,
+	`branch` text,
+	`head_sha` text NOT NULL,
+	`commit_sha` text NOT NULL,
+	`index_tree` text NOT NULL,
+	`ref` text NOT NULL,
+	`created_at` integer NOT NULL,
+	FOREIGN KEY (`node_id`) REFERENCES `nodes`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `node_snapshots_node_id_idx` ON `node_snapshots` (`node_id`);--> statement-breakpoint
+CREATE INDEX `node_snapshots_created_at_idx` ON `node_snapshots` (`created_at`);
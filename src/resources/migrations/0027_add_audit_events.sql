@@ -0,0 +1,14 @@
+CREATE TABLE `audit_events` (
+	`id` text PRIMARY KEY NOT NULL,
+	`repository_id` text NOT NULL,
+	`node_id` text,
+	`type` text NOT NULL,
+	`actor` text NOT NULL,
+	`summary` text NOT NULL,
+	`data` text,
+	`timestamp` integer NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX `audit_events_repository_timestamp_idx` ON `audit_events` (`repository_id`,`timestamp`);--> statement-breakpoint
+CREATE INDEX `audit_events_node_timestamp_idx` ON `audit_events` (`node_id`,`timestamp`);--> statement-breakpoint
+CREATE INDEX `audit_events_timestamp_idx` ON `audit_events` (`timestamp`);
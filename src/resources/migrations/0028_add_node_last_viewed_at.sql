@@ -0,0 +1 @@
+ALTER TABLE `nodes` ADD `last_viewed_at` integer;
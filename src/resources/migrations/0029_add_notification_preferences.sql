@@ -0,0 +1,2 @@
+ALTER TABLE `projects` ADD `notification_preferences` text;--> statement-breakpoint
+ALTER TABLE `settings` ADD `notification_preferences` text;
\ No newline at end of file
@@ -0,0 +1,14 @@
+CREATE TABLE `notification_history` (
+	`id` text PRIMARY KEY NOT NULL,
+	`type` text NOT NULL,
+	`node_id` text,
+	`tab_id` text,
+	`pane_id` text,
+	`title` text NOT NULL,
+	`body` text NOT NULL,
+	`read_at` integer,
+	`created_at` integer NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX `notification_history_created_at_idx` ON `notification_history` (`created_at`);--> statement-breakpoint
+CREATE INDEX `notification_history_node_id_idx` ON `notification_history` (`node_id`);
\ No newline at end of file
@@ -0,0 +1,10 @@
+CREATE TABLE `github_accounts` (
+	`id` text PRIMARY KEY NOT NULL,
+	`label` text NOT NULL,
+	`username` text NOT NULL,
+	`gh_config_dir` text,
+	`has_token` integer DEFAULT false NOT NULL,
+	`created_at` integer NOT NULL
+);
+--> statement-breakpoint
+ALTER TABLE `projects` ADD `github_account_id` text;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `editor_launchers` text;
\ No newline at end of file
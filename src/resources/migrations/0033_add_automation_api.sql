@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `automation_api_enabled` integer;
\ No newline at end of file
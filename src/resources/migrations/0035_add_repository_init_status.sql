@@ -0,0 +1 @@
+ALTER TABLE `projects` ADD `init_status` text DEFAULT 'ready' NOT NULL;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `nodes` ADD `scope_path` text;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `nodes` ADD `sparse_patterns` text;
\ No newline at end of file
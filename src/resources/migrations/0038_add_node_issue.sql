@@ -0,0 +1 @@
+ALTER TABLE `nodes` ADD `issue` text;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `update_channel` text;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE `settings` ADD `telemetry_level` text;--> statement-breakpoint
+ALTER TABLE `settings` ADD `local_only_mode` integer;
\ No newline at end of file
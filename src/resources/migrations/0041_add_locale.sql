@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `locale` text;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `agent_memory_limit_mb` integer;
\ No newline at end of file
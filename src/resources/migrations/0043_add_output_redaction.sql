@@ -0,0 +1 @@
+ALTER TABLE `settings` ADD `output_redaction` text;
\ No newline at end of file
@@ -92,6 +92,8 @@ describe("constants", () => {
 			expect(NOTIFICATION_EVENTS.AGENT_LIFECYCLE).toBe("agent-lifecycle");
 			expect(NOTIFICATION_EVENTS.FOCUS_TAB).toBe("focus-tab");
 			expect(NOTIFICATION_EVENTS.TERMINAL_EXIT).toBe("terminal-exit");
+			expect(NOTIFICATION_EVENTS.DIGEST).toBe("notification-digest");
+			expect(NOTIFICATION_EVENTS.OPEN_TERMINAL).toBe("open-terminal");
 		});
 	});
 
@@ -8,4 +8,20 @@ export const AUTO_UPDATE_STATUS = {
 
 export type AutoUpdateStatus = (typeof AUTO_UPDATE_STATUS)[keyof typeof AUTO_UPDATE_STATUS];
 
+/**
+ * Release channels. Stable follows the latest release; canary follows the
+ * rolling desktop-canary prerelease.
+ */
+export const UPDATE_CHANNELS = ["stable", "canary"] as const;
+
+export type UpdateChannel = (typeof UPDATE_CHANNELS)[number];
+
+export interface UpdateCheckResult {
+	channel: UpdateChannel;
+	currentVersion: string;
+	/** Version the channel offers when it differs from the running one */
+	availableVersion: string | null;
+	error?: string;
+}
+
 export const RELEASES_URL = "https://github.com/TheCaspianAI/Caspian/releases";
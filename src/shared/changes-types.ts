@@ -44,6 +44,46 @@ export interface GitChangesStatus {
 	hasUpstream: boolean; // Whether branch has an upstream tracking branch
 }
 
+/** Line counts for a node's branch, pushed as its worktree changes */
+export interface BranchStats {
+	worktreePath: string;
+	filesChanged: number; // Distinct paths changed on the branch or in the worktree
+	additions: number;
+	deletions: number;
+	ahead: number;
+	behind: number;
+	hasUncommittedChanges: boolean;
+}
+
+/** What happened to a path in the worktree, as seen by the file watcher */
+export type FileChangeKind = "created" | "modified" | "deleted" | "renamed";
+
+/** A watcher-observed change to one path */
+export interface FileChange {
+	path: string; // Relative path from worktree root
+	oldPath?: string; // Previous path for renames
+	kind: FileChangeKind;
+	tracked: boolean; // Whether git tracks the path (the old path for renames)
+}
+
+/** Batched watcher changes for one worktree */
+export interface FilesChangedEvent {
+	worktreePath: string;
+	changes: FileChange[];
+}
+
+/** How a worktree is being watched: fs events, or periodic git status when those fail */
+export type WatchMode = "native" | "polling";
+
+export interface WorktreeWatchStatus {
+	worktreePath: string;
+	mode: WatchMode;
+	subscribers: number;
+	/** Kept open for a node's lifetime instead of only while in use */
+	pinned: boolean;
+	error: string | null; // Why native watching isn't used, if it failed
+}
+
 /** Diff view mode toggle */
 export type DiffViewMode = "side-by-side" | "inline";
 
@@ -55,9 +95,77 @@ export interface FileDiffInput {
 	commitHash?: string; // For committed category: which commit to show
 }
 
+/** Line kind within a structured diff hunk */
+export type DiffLineType = "context" | "addition" | "deletion";
+
+/** A single line within a diff hunk */
+export interface DiffLine {
+	type: DiffLineType;
+	content: string; // Line text without the leading +/-/space marker
+	oldLineNumber: number | null; // null for additions
+	newLineNumber: number | null; // null for deletions
+	noNewlineAtEnd?: boolean; // Followed by "\ No newline at end of file"
+}
+
+/** A hunk (@@ block) of a file diff */
+export interface DiffHunk {
+	header: string; // Full "@@ -a,b +c,d @@ context" line
+	oldStart: number;
+	oldLines: number;
+	newStart: number;
+	newLines: number;
+	lines: DiffLine[];
+}
+
+/** One file's diff, parsed from unified diff output */
+export interface StructuredFileDiff {
+	path: string; // New path (or the deleted path for deletions)
+	oldPath?: string; // Original path for renames/copies
+	status: FileStatus;
+	isBinary: boolean;
+	similarity?: number; // Rename/copy similarity percentage
+	oldMode?: string;
+	newMode?: string;
+	additions: number;
+	deletions: number;
+	hunks: DiffHunk[];
+	truncated?: boolean; // Some or all hunks were dropped by the size cap
+	skippedReason?: "generated" | "size-limit"; // Hunks omitted entirely; fetch the file alone to see them
+}
+
 /** File contents for Monaco diff editor */
 export interface FileContents {
 	original: string; // Original content (before changes)
 	modified: string; // Modified content (after changes)
 	language: string; // Detected language for syntax highlighting
+	isBinary?: boolean; // Contents omitted; use getBinaryFileInfo
+}
+
+/** One side of a binary/image change */
+export interface BinaryFileVersion {
+	exists: boolean; // false when the file was added/deleted on this side
+	size: number | null; // Bytes
+	sha256: string | null; // null when missing or too large to read
+	mimeType: string | null; // Set for previewable image types
+	dataBase64: string | null; // Image payload for before/after previews
+}
+
+/** A file's content at a single revision, for full-file context and "open base version" */
+export interface FileAtRevision {
+	revision: string; // As requested: "HEAD", "base", "index" or a ref/sha
+	commitSha: string | null; // Resolved commit; null for the index
+	exists: boolean; // false when the path isn't present at that revision
+	content: string; // Empty when missing, binary or too large
+	isBinary: boolean;
+	tooLarge: boolean;
+	size: number | null; // Bytes
+	language: string;
+}
+
+/** Binary/image diff details for the review panel */
+export interface BinaryFileInfo {
+	isBinary: boolean;
+	isImage: boolean;
+	original: BinaryFileVersion;
+	modified: BinaryFileVersion;
 }
@@ -39,6 +39,9 @@ export const NOTIFICATION_EVENTS = {
 	AGENT_LIFECYCLE: "agent-lifecycle",
 	FOCUS_TAB: "focus-tab",
 	TERMINAL_EXIT: "terminal-exit",
+	DIGEST: "notification-digest",
+	// Automation API asked for a terminal tab running a command in a node
+	OPEN_TERMINAL: "open-terminal",
 } as const;
 
 // Development/testing mock values (used when SKIP_ENV_VALIDATION is set)
@@ -48,6 +51,8 @@ export const MOCK_ORG_ID = "mock-org-id";
 export const DEFAULT_CONFIRM_ON_QUIT = true;
 export const DEFAULT_TERMINAL_LINK_BEHAVIOR = "external-editor" as const;
 export const DEFAULT_AUTO_APPLY_DEFAULT_PRESET = true;
+export const DEFAULT_TELEMETRY_LEVEL = "errors" as const;
+export const DEFAULT_OUTPUT_REDACTION = "patterns" as const;
 
 // External links (documentation, help resources, etc.)
 export const EXTERNAL_LINKS = {
@@ -1,3 +1,5 @@
+import type { FileStatus } from "./changes-types";
+
 export interface FileTreeNode {
 	id: string;
 	name: string;
@@ -21,3 +23,21 @@ export interface DirectoryEntry {
 	relativePath: string;
 	isDirectory: boolean;
 }
+
+/** A file or directory in a node's worktree, with what git knows about it */
+export interface WorktreeFileEntry {
+	name: string;
+	/** Relative to the worktree root, with forward slashes */
+	relativePath: string;
+	isDirectory: boolean;
+	/** Matched by .gitignore or an exclude file; directories inside aren't listed */
+	ignored: boolean;
+	/** For directories: any file inside is tracked */
+	tracked: boolean;
+	/** Staged, unstaged or untracked changes; for directories, anywhere inside */
+	modified: boolean;
+	/** The change to this file, when it has one; always null for directories */
+	status: FileStatus | null;
+	/** null when the entry is a file, ignored, or past the requested depth */
+	children: WorktreeFileEntry[] | null;
+}
@@ -0,0 +1,192 @@
+/**
+ * Strings the main process shows on its own: OS notifications and native
+ * dialogs. English is the source; other locales may leave keys out and fall
+ * back to it. `{name}` placeholders are filled in by translate(), and
+ * messages with `one`/`other` forms are picked by `count`.
+ */
+
+export type Message = string | { one?: string; other: string };
+
+export const en = {
+	"list.separator": ", ",
+	"notification.agentComplete.title": "Agent Complete — {node}",
+	"notification.agentComplete.body": '"{tab}" has finished its task',
+	"notification.needsInput.title": "Input Needed — {node}",
+	"notification.needsInput.body": '"{tab}" needs your attention',
+	"digest.agentsFinished": { one: "{count} agent finished", other: "{count} agents finished" },
+	"digest.needInput": { one: "{count} needs input", other: "{count} need input" },
+	"digest.andMore": "{list} and {count} more",
+	"quit.title": "Quit Caspian",
+	"quit.message": "Are you sure you want to quit?",
+	"quit.confirm": "Quit",
+	"quit.cancel": "Cancel",
+	"quit.agentsRunning": {
+		one: "{count} agent is still running",
+		other: "{count} agents are still running",
+	},
+	"quit.agentsRunningDetail":
+		"Keep them running in the background to pick them up next time, or stop them now. Stopped agents are listed on the next launch so you can resume them.",
+	"quit.keepAgentsRunning": "Keep Running",
+	"quit.stopAgents": "Stop Agents and Quit",
+	"updates.title": "Updates",
+	"updates.disabledInDev": "Auto-updates are disabled in development mode.",
+	"updates.macOnly": "Auto-updates are only available on macOS.",
+	"updates.localOnly": "Update checks are off in local-only mode.",
+	"updates.localOnlyDetail":
+		"Turn off local-only mode in Settings > Preferences to check for updates.",
+	"updates.upToDateTitle": "No Updates",
+	"updates.upToDate": "You're up to date!",
+	"updates.upToDateDetail": "Version {version} is the latest version.",
+	"updates.offlineTitle": "No Internet Connection",
+	"updates.offline": "Unable to check for updates. Please check your internet connection.",
+	"updates.errorTitle": "Update Error",
+	"updates.error": "Failed to check for updates. Please try again later.",
+} as const satisfies Record<string, Message>;
+
+export type MessageKey = keyof typeof en;
+
+export type MessageCatalog = Partial<Record<MessageKey, Message>>;
+
+export const de: MessageCatalog = {
+	"notification.agentComplete.title": "Agent fertig — {node}",
+	"notification.agentComplete.body": "„{tab}“ hat seine Aufgabe abgeschlossen",
+	"notification.needsInput.title": "Eingabe erforderlich — {node}",
+	"notification.needsInput.body": "„{tab}“ braucht deine Aufmerksamkeit",
+	"digest.agentsFinished": { one: "{count} Agent fertig", other: "{count} Agents fertig" },
+	"digest.needInput": {
+		one: "{count} braucht eine Eingabe",
+		other: "{count} brauchen eine Eingabe",
+	},
+	"digest.andMore": "{list} und {count} weitere",
+	"quit.title": "Caspian beenden",
+	"quit.message": "Möchtest du Caspian wirklich beenden?",
+	"quit.confirm": "Beenden",
+	"quit.cancel": "Abbrechen",
+	"quit.agentsRunning": { one: "{count} Agent läuft noch", other: "{count} Agents laufen noch" },
+	"quit.agentsRunningDetail":
+		"Lass sie im Hintergrund weiterlaufen und mach beim nächsten Start weiter, oder beende sie jetzt. Beendete Agents werden beim nächsten Start aufgelistet, damit du sie fortsetzen kannst.",
+	"quit.keepAgentsRunning": "Weiterlaufen lassen",
+	"quit.stopAgents": "Agents beenden",
+	"updates.title": "Updates",
+	"updates.disabledInDev": "Automatische Updates sind im Entwicklungsmodus deaktiviert.",
+	"updates.macOnly": "Automatische Updates sind nur unter macOS verfügbar.",
+	"updates.localOnly": "Im Nur-lokal-Modus wird nicht nach Updates gesucht.",
+	"updates.localOnlyDetail":
+		"Deaktiviere den Nur-lokal-Modus unter Settings > Preferences, um nach Updates zu suchen.",
+	"updates.upToDateTitle": "Keine Updates",
+	"updates.upToDate": "Du bist auf dem neuesten Stand!",
+	"updates.upToDateDetail": "Version {version} ist die neueste Version.",
+	"updates.offlineTitle": "Keine Internetverbindung",
+	"updates.offline":
+		"Es konnte nicht nach Updates gesucht werden. Bitte überprüfe deine Internetverbindung.",
+	"updates.errorTitle": "Update-Fehler",
+	"updates.error": "Die Suche nach Updates ist fehlgeschlagen. Bitte versuche es später erneut.",
+};
+
+export const es: MessageCatalog = {
+	"notification.agentComplete.title": "Agente terminado — {node}",
+	"notification.agentComplete.body": "«{tab}» ha terminado su tarea",
+	"notification.needsInput.title": "Se necesita tu respuesta — {node}",
+	"notification.needsInput.body": "«{tab}» requiere tu atención",
+	"digest.agentsFinished": { one: "{count} agente terminó", other: "{count} agentes terminaron" },
+	"digest.needInput": {
+		one: "{count} necesita tu respuesta",
+		other: "{count} necesitan tu respuesta",
+	},
+	"digest.andMore": "{list} y {count} más",
+	"quit.title": "Salir de Caspian",
+	"quit.message": "¿Seguro que quieres salir?",
+	"quit.confirm": "Salir",
+	"quit.cancel": "Cancelar",
+	"quit.agentsRunning": {
+		one: "{count} agente sigue en ejecución",
+		other: "{count} agentes siguen en ejecución",
+	},
+	"quit.agentsRunningDetail":
+		"Déjalos en segundo plano para retomarlos la próxima vez, o detenlos ahora. Los agentes detenidos se mostrarán al volver a abrir Caspian para que puedas reanudarlos.",
+	"quit.keepAgentsRunning": "Mantener en ejecución",
+	"quit.stopAgents": "Detener agentes y salir",
+	"updates.title": "Actualizaciones",
+	"updates.disabledInDev":
+		"Las actualizaciones automáticas están desactivadas en el modo de desarrollo.",
+	"updates.macOnly": "Las actualizaciones automáticas solo están disponibles en macOS.",
+	"updates.localOnly": "La búsqueda de actualizaciones está desactivada en el modo solo local.",
+	"updates.localOnlyDetail":
+		"Desactiva el modo solo local en Settings > Preferences para buscar actualizaciones.",
+	"updates.upToDateTitle": "No hay actualizaciones",
+	"updates.upToDate": "¡Estás al día!",
+	"updates.upToDateDetail": "La versión {version} es la más reciente.",
+	"updates.offlineTitle": "Sin conexión a Internet",
+	"updates.offline": "No se pudo buscar actualizaciones. Comprueba tu conexión a Internet.",
+	"updates.errorTitle": "Error de actualización",
+	"updates.error": "No se pudo buscar actualizaciones. Inténtalo de nuevo más tarde.",
+};
+
+export const fr: MessageCatalog = {
+	"notification.agentComplete.title": "Agent terminé — {node}",
+	"notification.agentComplete.body": "« {tab} » a terminé sa tâche",
+	"notification.needsInput.title": "Action requise — {node}",
+	"notification.needsInput.body": "« {tab} » requiert votre attention",
+	"digest.agentsFinished": { one: "{count} agent a terminé", other: "{count} agents ont terminé" },
+	"digest.needInput": { one: "{count} attend une réponse", other: "{count} attendent une réponse" },
+	"digest.andMore": "{list} et {count} autres",
+	"quit.title": "Quitter Caspian",
+	"quit.message": "Voulez-vous vraiment quitter ?",
+	"quit.confirm": "Quitter",
+	"quit.cancel": "Annuler",
+	"quit.agentsRunning": {
+		one: "{count} agent est toujours en cours d'exécution",
+		other: "{count} agents sont toujours en cours d'exécution",
+	},
+	"quit.agentsRunningDetail":
+		"Laissez-les tourner en arrière-plan pour les retrouver au prochain lancement, ou arrêtez-les maintenant. Les agents arrêtés seront listés au prochain lancement pour que vous puissiez les reprendre.",
+	"quit.keepAgentsRunning": "Laisser tourner",
+	"quit.stopAgents": "Arrêter les agents et quitter",
+	"updates.title": "Mises à jour",
+	"updates.disabledInDev": "Les mises à jour automatiques sont désactivées en mode développement.",
+	"updates.macOnly": "Les mises à jour automatiques ne sont disponibles que sur macOS.",
+	"updates.localOnly": "La recherche de mises à jour est désactivée en mode local uniquement.",
+	"updates.localOnlyDetail":
+		"Désactivez le mode local uniquement dans Settings > Preferences pour rechercher des mises à jour.",
+	"updates.upToDateTitle": "Aucune mise à jour",
+	"updates.upToDate": "Vous êtes à jour !",
+	"updates.upToDateDetail": "La version {version} est la plus récente.",
+	"updates.offlineTitle": "Pas de connexion Internet",
+	"updates.offline":
+		"Impossible de rechercher des mises à jour. Vérifiez votre connexion Internet.",
+	"updates.errorTitle": "Erreur de mise à jour",
+	"updates.error": "La recherche de mises à jour a échoué. Veuillez réessayer plus tard.",
+};
+
+export const ja: MessageCatalog = {
+	"list.separator": "、",
+	"notification.agentComplete.title": "エージェント完了 — {node}",
+	"notification.agentComplete.body": "「{tab}」のタスクが完了しました",
+	"notification.needsInput.title": "入力が必要です — {node}",
+	"notification.needsInput.body": "「{tab}」が対応を待っています",
+	"digest.agentsFinished": { other: "{count} 件のエージェントが完了" },
+	"digest.needInput": { other: "{count} 件が入力待ち" },
+	"digest.andMore": "{list} ほか {count} 件",
+	"quit.title": "Caspian を終了",
+	"quit.message": "Caspian を終了してもよろしいですか？",
+	"quit.confirm": "終了",
+	"quit.cancel": "キャンセル",
+	"quit.agentsRunning": { other: "{count} 件のエージェントが実行中です" },
+	"quit.agentsRunningDetail":
+		"バックグラウンドで実行を続けて次回再開するか、今すぐ停止できます。停止したエージェントは次回起動時に一覧表示され、再開できます。",
+	"quit.keepAgentsRunning": "実行を続ける",
+	"quit.stopAgents": "エージェントを停止して終了",
+	"updates.title": "アップデート",
+	"updates.disabledInDev": "開発モードでは自動アップデートは無効です。",
+	"updates.macOnly": "自動アップデートは macOS でのみ利用できます。",
+	"updates.localOnly": "ローカル専用モードではアップデートを確認しません。",
+	"updates.localOnlyDetail":
+		"アップデートを確認するには、Settings > Preferences でローカル専用モードをオフにしてください。",
+	"updates.upToDateTitle": "アップデートはありません",
+	"updates.upToDate": "最新の状態です",
+	"updates.upToDateDetail": "バージョン {version} が最新です。",
+	"updates.offlineTitle": "インターネットに接続されていません",
+	"updates.offline": "アップデートを確認できませんでした。インターネット接続を確認してください。",
+	"updates.errorTitle": "アップデートエラー",
+	"updates.error": "アップデートの確認に失敗しました。しばらくしてからもう一度お試しください。",
+};
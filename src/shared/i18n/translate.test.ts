@@ -0,0 +1,38 @@
+import { describe, expect, it } from "bun:test";
+import { resolveLocale, translate } from "./translate";
+
+describe("translate", () => {
+	it("fills in placeholders", () => {
+		expect(translate("en", "notification.agentComplete.title", { node: "api" })).toBe(
+			"Agent Complete — api",
+		);
+		expect(translate("de", "updates.upToDateDetail", { version: "1.2.0" })).toBe(
+			"Version 1.2.0 ist die neueste Version.",
+		);
+	});
+
+	it("picks plural forms by count", () => {
+		expect(translate("en", "digest.agentsFinished", { count: 1 })).toBe("1 agent finished");
+		expect(translate("en", "digest.agentsFinished", { count: 3 })).toBe("3 agents finished");
+		expect(translate("fr", "digest.needInput", { count: 2 })).toBe("2 attendent une réponse");
+		expect(translate("ja", "digest.agentsFinished", { count: 1 })).toBe(
+			"1 件のエージェントが完了",
+		);
+	});
+
+	it("falls back to English for missing keys", () => {
+		expect(translate("de", "list.separator")).toBe(", ");
+	});
+});
+
+describe("resolveLocale", () => {
+	it("matches on the language and skips unsupported ones", () => {
+		expect(resolveLocale(["de-AT", "en-US"])).toBe("de");
+		expect(resolveLocale(["pt-BR", "fr_CA"])).toBe("fr");
+	});
+
+	it("defaults to English", () => {
+		expect(resolveLocale([])).toBe("en");
+		expect(resolveLocale(["pt-BR"])).toBe("en");
+	});
+});
@@ -0,0 +1,63 @@
+import {
+	de,
+	en,
+	es,
+	fr,
+	ja,
+	type Message,
+	type MessageCatalog,
+	type MessageKey,
+} from "./messages";
+
+export const LOCALES = ["en", "de", "es", "fr", "ja"] as const;
+
+export type Locale = (typeof LOCALES)[number];
+
+export const DEFAULT_LOCALE: Locale = "en";
+
+/** Each language's name for itself, for the language picker */
+export const LOCALE_NAMES: Record<Locale, string> = {
+	en: "English",
+	de: "Deutsch",
+	es: "Español",
+	fr: "Français",
+	ja: "日本語",
+};
+
+const CATALOGS: Record<Locale, MessageCatalog> = { en, de, es, fr, ja };
+
+export type MessageParams = Record<string, string | number>;
+
+export type Translate = (key: MessageKey, params?: MessageParams) => string;
+
+function selectForm(locale: Locale, message: Message, count: number): string {
+	if (typeof message === "string") return message;
+	const category = new Intl.PluralRules(locale).select(count);
+	return (category === "one" && message.one) || message.other;
+}
+
+/** Looks up a message, falling back to English, and fills in its placeholders */
+export function translate(locale: Locale, key: MessageKey, params: MessageParams = {}): string {
+	const message: Message = CATALOGS[locale][key] ?? en[key];
+	const template = selectForm(locale, message, Number(params.count));
+	return template.replace(/\{(\w+)\}/g, (placeholder, name: string) =>
+		name in params ? String(params[name]) : placeholder,
+	);
+}
+
+export function createTranslator(locale: Locale): Translate {
+	return (key, params) => translate(locale, key, params);
+}
+
+/**
+ * The first supported locale among the user's preferred languages, matched
+ * on the language alone (so "de-AT" picks German), else English.
+ */
+export function resolveLocale(preferred: readonly string[]): Locale {
+	for (const tag of preferred) {
+		const language = tag.toLowerCase().split(/[-_]/)[0];
+		const match = LOCALES.find((locale) => locale === language);
+		if (match) return match;
+	}
+	return DEFAULT_LOCALE;
+}
@@ -12,3 +12,31 @@ export interface NotificationIds {
 export interface AgentLifecycleEvent extends NotificationIds {
 	eventType: "Start" | "Stop" | "PermissionRequest";
 }
+
+/** A terminal tab to open in a node, running a command (e.g. an agent) */
+export interface OpenTerminalRequest {
+	nodeId: string;
+	command: string;
+}
+
+/** Kinds of entries kept in the in-app notification history */
+export const NOTIFICATION_TYPES = ["agent_complete", "needs_input"] as const;
+
+export type NotificationType = (typeof NOTIFICATION_TYPES)[number];
+
+/** open focuses the notification's pane; dismiss only marks it read */
+export const NOTIFICATION_ACTIONS = ["open", "dismiss"] as const;
+
+export type NotificationAction = (typeof NOTIFICATION_ACTIONS)[number];
+
+export interface NotificationDigestItem extends NotificationIds {
+	type: NotificationType;
+	nodeName: string;
+}
+
+/** Agent notifications coalesced into one OS notification by digest mode */
+export interface NotificationDigestEvent {
+	completed: number;
+	needsInput: number;
+	items: NotificationDigestItem[];
+}
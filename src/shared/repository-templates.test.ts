@@ -0,0 +1,31 @@
+import { describe, expect, test } from "bun:test";
+import { getTemplateFiles, REPOSITORY_TEMPLATE_IDS } from "./repository-templates";
+
+describe("getTemplateFiles", () => {
+	test("every template has a gitignore, README and Caspian config", () => {
+		for (const id of REPOSITORY_TEMPLATE_IDS) {
+			const paths = getTemplateFiles(id, "demo").map((file) => file.path);
+			expect(paths).toEqual([".gitignore", "README.md", ".caspian/config.json"]);
+		}
+	});
+
+	test("titles the README with the project name", () => {
+		const readme = getTemplateFiles("rust", "my-app").find((file) => file.path === "README.md");
+		expect(readme?.content).toBe("# my-app\n");
+	});
+
+	test("writes a config that parses back to the template's commands", () => {
+		const config = getTemplateFiles("node", "demo").find((file) => file.path.endsWith(".json"));
+		expect(JSON.parse(config?.content ?? "")).toMatchObject({
+			setup: ["npm install"],
+			defaults: { testCommand: "npm test" },
+		});
+	});
+
+	test("ignores secrets in every language", () => {
+		for (const id of REPOSITORY_TEMPLATE_IDS) {
+			const gitignore = getTemplateFiles(id, "demo")[0].content.split("\n");
+			expect(gitignore).toContain(".env");
+		}
+	});
+});
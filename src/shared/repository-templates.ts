@@ -0,0 +1,70 @@
+import type { SetupConfig } from "./types/config";
+
+/**
+ * Starter files for a brand-new repository: a .gitignore for the language,
+ * a README and a .caspian/config.json with setup and gate commands, so a
+ * node can run an agent against it right away.
+ */
+export const REPOSITORY_TEMPLATE_IDS = ["node", "python", "rust", "go"] as const;
+
+export type RepositoryTemplateId = (typeof REPOSITORY_TEMPLATE_IDS)[number];
+
+interface RepositoryTemplate {
+	label: string;
+	gitignore: string[];
+	config: SetupConfig;
+}
+
+const COMMON_IGNORES = [".DS_Store", ".env", ".env.local"];
+
+export const REPOSITORY_TEMPLATES: Record<RepositoryTemplateId, RepositoryTemplate> = {
+	node: {
+		label: "Node.js",
+		gitignore: ["node_modules/", "dist/", "build/", "coverage/", "*.log"],
+		config: {
+			setup: ["npm install"],
+			gate: { lint: "npm run lint --if-present", test: "npm test" },
+			defaults: { testCommand: "npm test" },
+		},
+	},
+	python: {
+		label: "Python",
+		gitignore: ["__pycache__/", "*.py[cod]", ".venv/", "dist/", "build/", "*.egg-info/"],
+		config: {
+			setup: ["python3 -m venv .venv"],
+			gate: { test: ".venv/bin/python -m pytest" },
+			defaults: { testCommand: ".venv/bin/python -m pytest" },
+		},
+	},
+	rust: {
+		label: "Rust",
+		gitignore: ["/target"],
+		config: {
+			gate: { lint: "cargo clippy -- -D warnings", test: "cargo test", build: "cargo build" },
+			defaults: { testCommand: "cargo test" },
+		},
+	},
+	go: {
+		label: "Go",
+		gitignore: ["/bin", "*.test", "*.out"],
+		config: {
+			gate: { lint: "go vet ./...", test: "go test ./...", build: "go build ./..." },
+			defaults: { testCommand: "go test ./..." },
+		},
+	},
+};
+
+export interface TemplateFile {
+	/** Relative to the repository root, with forward slashes */
+	path: string;
+	content: string;
+}
+
+export function getTemplateFiles(id: RepositoryTemplateId, projectName: string): TemplateFile[] {
+	const template = REPOSITORY_TEMPLATES[id];
+	return [
+		{ path: ".gitignore", content: `${[...template.gitignore, ...COMMON_IGNORES].join("\n")}\n` },
+		{ path: "README.md", content: `# ${projectName}\n` },
+		{ path: ".caspian/config.json", content: `${JSON.stringify(template.config, null, "\t")}\n` },
+	];
+}
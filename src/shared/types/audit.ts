@@ -0,0 +1,65 @@
+/**
+ * Audit log types.
+ * A per-node trail of what happened to a repository, exportable for reviews.
+ */
+
+export const AUDIT_EVENT_TYPES = [
+	"agent_started",
+	"agent_completed",
+	"agent_needs_input",
+	"agent_interrupted",
+	"worktree_created",
+	"worktree_removed",
+	"commit",
+	"push",
+	"pull",
+	"pr_created",
+	"pr_updated",
+	"pr_merged",
+	"test_run",
+	"gate_run",
+] as const;
+
+export type AuditEventType = (typeof AUDIT_EVENT_TYPES)[number];
+
+/** Who triggered the event, as far as the app can tell */
+export type AuditActor = "user" | "agent" | "system";
+
+export interface AuditEvent {
+	id: string;
+	timestamp: number;
+	type: AuditEventType;
+	repositoryId: string;
+	/** null for repository-level events */
+	nodeId: string | null;
+	actor: AuditActor;
+	/** One-line human readable description */
+	summary: string;
+	data?: Record<string, unknown>;
+}
+
+/** What the global activity feed shows by default: outcomes, not every step */
+export const ACTIVITY_EVENT_TYPES = [
+	"agent_completed",
+	"agent_needs_input",
+	"agent_interrupted",
+	"pr_created",
+	"pr_updated",
+	"pr_merged",
+] as const satisfies readonly AuditEventType[];
+
+export interface ActivityItem extends AuditEvent {
+	/** null when the repository or node has since been removed */
+	repositoryName: string | null;
+	nodeName: string | null;
+}
+
+export interface ActivityPage {
+	items: ActivityItem[];
+	/** Pass back as `cursor` for the next (older) page; null on the last page */
+	nextCursor: string | null;
+}
+
+export const AUDIT_EXPORT_FORMATS = ["jsonl", "csv"] as const;
+
+export type AuditExportFormat = (typeof AUDIT_EXPORT_FORMATS)[number];
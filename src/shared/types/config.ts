@@ -1,4 +1,52 @@
+import type { IssueProviderId } from "./issues";
+
+export interface QualityGateConfig {
+	lint?: string;
+	test?: string;
+	build?: string;
+	/** When true, PRs can't be created until the gate has passed for the current HEAD */
+	required?: boolean;
+}
+
+/** Defaults applied when nodes are created or run in this repository */
+export interface NodeDefaultsConfig {
+	/** Used by runTests when no command is given */
+	testCommand?: string;
+	/** Branch prefix for new nodes; takes precedence over the prefix settings */
+	branchPrefix?: string;
+	/**
+	 * Template for new and renamed branches, e.g. "feat/{ticket}-{slug}", using
+	 * {user}, {ticket} and {slug}. Takes precedence over any branch prefix.
+	 */
+	branchTemplate?: string;
+}
+
+/** Issue tracker that nodes in this repository link to */
+export interface IssueTrackerConfig {
+	provider: IssueProviderId;
+	/** Jira site host, e.g. "acme.atlassian.net" */
+	jiraSite?: string;
+	/**
+	 * State to move a linked issue to when its node is closed or deleted, e.g.
+	 * "closed" on GitHub or "Done" on Jira and Linear. Unset leaves issues alone.
+	 */
+	transitionOnClose?: string;
+}
+
+/** How to run the repository's dev server for a node */
+export interface DevServerConfig {
+	command: string;
+	/** Environment variable the allocated port is passed in; defaults to PORT */
+	portEnv?: string;
+	/** First port tried when allocating; defaults to 3000 */
+	basePort?: number;
+}
+
 export interface SetupConfig {
 	setup?: string[];
 	teardown?: string[];
+	gate?: QualityGateConfig;
+	defaults?: NodeDefaultsConfig;
+	dev?: DevServerConfig;
+	issues?: IssueTrackerConfig;
 }
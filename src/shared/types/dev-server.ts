@@ -0,0 +1,31 @@
+/**
+ * Dev server types.
+ * Each node can run the repository's dev command in its worktree on its own port,
+ * so several branches can be previewed side by side.
+ */
+
+export type DevServerState = "running" | "stopping" | "exited" | "failed";
+
+export interface DevServerStatus {
+	nodeId: string;
+	command: string;
+	state: DevServerState;
+	port: number;
+	url: string;
+	pid: number | null;
+	startedAt: number;
+	/** Set once the process has exited */
+	exitCode: number | null;
+	error: string | null;
+}
+
+export interface DevServerLogLine {
+	nodeId: string;
+	stream: "stdout" | "stderr";
+	text: string;
+	timestamp: number;
+}
+
+export type DevServerEvent =
+	| { type: "status"; status: DevServerStatus }
+	| { type: "log"; line: DevServerLogLine };
@@ -0,0 +1,29 @@
+/**
+ * Environment diagnostics: one check per agent, tool and system resource
+ * Caspian depends on, so problems show up before a node fails to start.
+ */
+
+/** "skipped" marks checks that don't apply, e.g. an agent that isn't installed */
+export type DiagnosticStatus = "ok" | "skipped" | "warning" | "error";
+
+export type DiagnosticCategory = "agents" | "tools" | "system";
+
+export interface DiagnosticCheck {
+	/** Stable identifier, e.g. "agent:claude", "git", "disk" */
+	id: string;
+	category: DiagnosticCategory;
+	label: string;
+	status: DiagnosticStatus;
+	/** Version, path or measurement the status is based on */
+	detail: string | null;
+	/** What to do about a warning or error */
+	hint: string | null;
+}
+
+export interface DiagnosticsReport {
+	generatedAt: number;
+	platform: string;
+	checks: DiagnosticCheck[];
+	/** Worst status across all checks */
+	status: DiagnosticStatus;
+}
@@ -0,0 +1,49 @@
+/**
+ * Structured error codes.
+ * Procedures throw CaspianError (lib/trpc/errors) with one of these codes and
+ * an optional remediation hint; the renderer reads them from the error's
+ * `data.caspian` instead of matching on message text.
+ */
+
+export const CASPIAN_ERROR_CODES = [
+	"NODE_NOT_FOUND",
+	"NODE_NO_WORKING_DIRECTORY",
+	"NOT_A_WORKTREE_NODE",
+	"REPOSITORY_NOT_FOUND",
+	"NOT_A_GIT_REPOSITORY",
+	"WORKTREE_NOT_FOUND",
+	"WORKTREE_MISSING",
+	"PATH_NOT_FOUND",
+	"BRANCH_NOT_FOUND",
+	"BRANCH_EXISTS",
+	"BRANCH_CHECKED_OUT",
+	"GIT_IDENTITY_MISSING",
+	"GIT_OPERATION_FAILED",
+	"HOOK_FAILED",
+	"ISSUE_TRACKER_FAILED",
+	"CONFIG_MISSING",
+	"OPERATION_IN_PROGRESS",
+	"OPERATION_CANCELLED",
+	"CONFLICT",
+	"SECURE_STORAGE_UNAVAILABLE",
+	"LOCAL_ONLY_MODE",
+	"NO_WINDOW",
+	"TERMINAL_SESSION_KILLED",
+	"INVALID_INPUT",
+	"NOT_FOUND",
+] as const;
+
+export type CaspianErrorCode = (typeof CASPIAN_ERROR_CODES)[number];
+
+export interface CaspianErrorData {
+	code: CaspianErrorCode;
+	/** What the user can do about it, when there's something to suggest */
+	hint?: string;
+}
+
+/** Structured data from an error returned by a procedure, or null for other errors */
+export function getCaspianErrorData(error: unknown): CaspianErrorData | null {
+	if (typeof error !== "object" || error === null || !("data" in error)) return null;
+	const data = (error as { data?: { caspian?: CaspianErrorData | null } }).data;
+	return data?.caspian ?? null;
+}
@@ -1,9 +1,21 @@
+export * from "./audit";
 export * from "./config";
 export * from "./database";
+export * from "./dev-server";
+export * from "./diagnostics";
+export * from "./issues";
+export * from "./logs";
 export * from "./electron";
 export * from "./mosaic";
 export * from "./node";
 export * from "./node-init";
 export * from "./ports";
+export * from "./quality-gate";
+export * from "./rate-limit";
+export * from "./repo-maintenance";
+export * from "./resource-usage";
+export * from "./restore-report";
 export * from "./tab";
+export * from "./test-runs";
+export * from "./webhooks";
 export * from "./worktree";
@@ -0,0 +1,29 @@
+/**
+ * Issue tracker types.
+ * A node can be linked to an issue on GitHub, Jira or Linear; which tracker a
+ * repository uses is set under `issues` in .caspian/config.json.
+ */
+
+export const ISSUE_PROVIDERS = ["github", "jira", "linear"] as const;
+
+export type IssueProviderId = (typeof ISSUE_PROVIDERS)[number];
+
+/** An issue attached to a node */
+export interface IssueLink {
+	provider: IssueProviderId;
+	/** "123" for GitHub, "PROJ-123" for Jira and Linear */
+	key: string;
+	url: string;
+	/** Title when the issue was linked */
+	title: string | null;
+}
+
+export interface IssueDetails {
+	provider: IssueProviderId;
+	key: string;
+	url: string;
+	title: string;
+	description: string | null;
+	/** Provider's state name, e.g. "open" or "In Progress" */
+	state: string | null;
+}
@@ -0,0 +1,16 @@
+/**
+ * Main process log types.
+ * Console output is mirrored to rotating files so it can be attached to bug reports.
+ */
+
+/** Ordered from least to most severe */
+export const LOG_LEVELS = ["debug", "info", "warn", "error"] as const;
+
+export type LogLevel = (typeof LOG_LEVELS)[number];
+
+export interface LogEntry {
+	timestamp: number;
+	level: LogLevel;
+	/** May span several lines, e.g. an error with its stack */
+	message: string;
+}
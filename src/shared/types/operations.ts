@@ -0,0 +1,29 @@
+/**
+ * Long-running operation types.
+ * Clones, fetches, diffs, maintenance and quality gates report through one
+ * registry so the renderer can list them, show progress and cancel them.
+ */
+
+export const OPERATION_KINDS = ["clone", "fetch", "diff", "maintenance", "quality_gate"] as const;
+
+export type OperationKind = (typeof OPERATION_KINDS)[number];
+
+export type OperationStatus = "running" | "succeeded" | "failed" | "cancelled";
+
+export interface OperationProgress {
+	id: string;
+	kind: OperationKind;
+	title: string;
+	/** Repository or node the operation works on, when there is one */
+	subjectId: string | null;
+	status: OperationStatus;
+	/** Latest progress message, e.g. the current git stage */
+	message: string | null;
+	/** 0-100 when the operation knows how far along it is */
+	percent: number | null;
+	/** Whether operations.cancel can stop it */
+	cancellable: boolean;
+	startedAt: number;
+	finishedAt: number | null;
+	error: string | null;
+}
@@ -0,0 +1,32 @@
+/**
+ * Quality gate types.
+ * A gate runs the repository's lint, test and build commands in order before review.
+ */
+
+export const QUALITY_GATE_STEPS = ["lint", "test", "build"] as const;
+
+export type QualityGateStep = (typeof QUALITY_GATE_STEPS)[number];
+
+export type QualityGateStepStatus = "pending" | "running" | "passed" | "failed" | "skipped";
+
+export type QualityGateStatus = "running" | "passed" | "failed";
+
+export interface QualityGateStepResult {
+	/** "scope" checks that a scoped node only changed files inside its subdirectory */
+	step: QualityGateStep | "scope";
+	command: string;
+	status: QualityGateStepStatus;
+	exitCode: number | null;
+	durationMs: number | null;
+	/** Tail of the step's output, set once it has finished */
+	output?: string;
+	/** Linked parsed results for the test step */
+	testRunId?: string;
+}
+
+export interface QualityGateProgress {
+	nodeId: string;
+	runId: string;
+	status: QualityGateStatus;
+	steps: QualityGateStepResult[];
+}
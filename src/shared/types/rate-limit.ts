@@ -0,0 +1,12 @@
+export interface RateLimitedPane {
+	paneId: string;
+	nodeId: string;
+	detectedAt: number;
+	/** When the agent is told to continue, staggered so they don't all retry at once */
+	resumeAt: number;
+}
+
+/** Agents waiting out a rate limit; empty when nothing is backing off */
+export interface RateLimitState {
+	panes: RateLimitedPane[];
+}
@@ -0,0 +1,59 @@
+/**
+ * Repository maintenance progress types.
+ * Used for streaming progress updates while gc/maintenance runs on a repository.
+ */
+
+export type RepositoryMaintenanceStep =
+	| "queued" // Waiting for the repository lock
+	| "measuring" // Measuring object store size
+	| "pruning_worktrees" // Pruning stale worktree metadata
+	| "repacking" // Running git maintenance / gc
+	| "complete"
+	| "failed";
+
+export type RepositoryMaintenanceTrigger = "manual" | "scheduled";
+
+export interface ObjectStoreSize {
+	/** Number of loose objects */
+	looseObjects: number;
+	/** Bytes used by loose objects */
+	looseBytes: number;
+	/** Number of packfiles */
+	packs: number;
+	/** Bytes used by packfiles */
+	packBytes: number;
+	/** Bytes used by unreachable garbage files */
+	garbageBytes: number;
+	/** Total bytes on disk (loose + packs + garbage) */
+	totalBytes: number;
+}
+
+export interface RepositoryMaintenanceResult {
+	repositoryId: string;
+	trigger: RepositoryMaintenanceTrigger;
+	before: ObjectStoreSize;
+	after: ObjectStoreSize;
+	/** Bytes reclaimed (negative if the store grew) */
+	reclaimedBytes: number;
+	durationMs: number;
+	/** Whether `git maintenance` was unavailable and `git gc` was used instead */
+	usedGcFallback: boolean;
+}
+
+export interface RepositoryMaintenanceProgress {
+	repositoryId: string;
+	trigger: RepositoryMaintenanceTrigger;
+	step: RepositoryMaintenanceStep;
+	message: string;
+	result?: RepositoryMaintenanceResult;
+	error?: string;
+}
+
+export const MAINTENANCE_STEP_MESSAGES: Record<RepositoryMaintenanceStep, string> = {
+	queued: "Waiting for other git operations...",
+	measuring: "Measuring object store...",
+	pruning_worktrees: "Pruning stale worktrees...",
+	repacking: "Repacking objects...",
+	complete: "Maintenance complete",
+	failed: "Maintenance failed",
+};
@@ -0,0 +1,32 @@
+/** A terminal's process tree: the shell, the agent and anything they spawned */
+export interface PaneResourceUsage {
+	paneId: string;
+	nodeId: string;
+	/** Whether an agent was working or waiting on a prompt in this terminal */
+	isAgent: boolean;
+	processCount: number;
+	cpuPercent: number;
+	memoryBytes: number;
+}
+
+export interface NodeResourceUsage {
+	nodeId: string;
+	cpuPercent: number;
+	memoryBytes: number;
+	panes: PaneResourceUsage[];
+}
+
+export interface AgentResourceUsage {
+	/** null until the first sample has been taken */
+	sampledAt: number | null;
+	nodes: NodeResourceUsage[];
+}
+
+/** Sent when an agent's terminal is stopped for going over the memory limit */
+export interface AgentMemoryLimitEvent {
+	paneId: string;
+	nodeId: string;
+	memoryBytes: number;
+	limitBytes: number;
+	reason: string;
+}
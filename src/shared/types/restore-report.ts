@@ -0,0 +1,30 @@
+export interface RestoreReportPane {
+	paneId: string;
+	tabId: string;
+	name: string;
+	/** Command that picks the agent's conversation back up, when the agent is recognized */
+	resumeCommand: string | null;
+}
+
+/** An agent stopped by quitting, kept until the next launch's report is dismissed */
+export interface InterruptedAgent extends RestoreReportPane {
+	nodeId: string;
+	interruptedAt: number;
+}
+
+export interface RestoreReportNode {
+	nodeId: string;
+	nodeName: string;
+	repositoryName: string | null;
+	/** Agents that were running when the app quit and whose terminal didn't survive */
+	interruptedAgents: RestoreReportPane[];
+	/** Agents still alive in the terminal daemon, waiting on a permission prompt */
+	pendingInput: RestoreReportPane[];
+	hasUncommittedChanges: boolean;
+}
+
+/** What the previous session left behind, computed once at startup */
+export interface RestoreReport {
+	generatedAt: number;
+	nodes: RestoreReportNode[];
+}
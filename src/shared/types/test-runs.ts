@@ -0,0 +1,56 @@
+/**
+ * Test run types.
+ * Parsed from test command output so the UI can show which tests broke after an agent run.
+ */
+
+export const TEST_FRAMEWORKS = ["cargo", "pytest", "jest", "go"] as const;
+
+export type TestFramework = (typeof TEST_FRAMEWORKS)[number];
+
+export type TestCaseStatus = "passed" | "failed" | "skipped";
+
+export interface TestCaseResult {
+	/** Fully qualified test name, e.g. `tests/test_api.py::test_login` */
+	name: string;
+	status: TestCaseStatus;
+	/** null when the framework doesn't report per-test timings */
+	durationMs: number | null;
+	failureMessage?: string;
+}
+
+export interface TestRunSummary {
+	total: number;
+	passed: number;
+	failed: number;
+	skipped: number;
+}
+
+/**
+ * Overall outcome. "error" means the command failed without any parseable
+ * test failures (compile error, missing binary, timeout).
+ */
+export type TestRunStatus = "passed" | "failed" | "error";
+
+export interface FileCoverage {
+	/** Path relative to the node's working directory */
+	path: string;
+	linesFound: number;
+	linesHit: number;
+}
+
+export interface CoverageFileDelta extends FileCoverage {
+	percent: number | null;
+	/** Coverage of the same file on the parent branch; null when unknown */
+	basePercent: number | null;
+	delta: number | null;
+}
+
+export interface NodeCoverage {
+	testRunId: string;
+	reportPath: string;
+	percent: number | null;
+	/** Overall coverage of the parent branch's latest report, when one exists */
+	basePercent: number | null;
+	baseTestRunId: string | null;
+	files: CoverageFileDelta[];
+}
@@ -0,0 +1,32 @@
+/**
+ * Outbound webhook types.
+ * Agent and node events are POSTed as Slack-compatible JSON to user-configured URLs.
+ */
+
+export const WEBHOOK_EVENT_TYPES = [
+	"agent_completed",
+	"agent_failed",
+	"agent_needs_input",
+	"node_ready",
+	"node_setup_failed",
+	"node_deleted",
+] as const;
+
+export type WebhookEventType = (typeof WEBHOOK_EVENT_TYPES)[number];
+
+export interface WebhookEvent {
+	type: WebhookEventType;
+	timestamp: number;
+	nodeId: string | null;
+	nodeName: string | null;
+	repositoryName: string | null;
+	/** Extra context, e.g. an exit code or setup error */
+	detail?: string;
+}
+
+/** Slack reads `text`; other receivers can use the structured fields */
+export interface WebhookPayload extends WebhookEvent {
+	text: string;
+}
+
+export type WebhookDeliveryStatus = "pending" | "delivered" | "failed";
@@ -1,5 +1,7 @@
 import { describe, expect, it } from "bun:test";
 import {
+	getBranchTemplateError,
+	renderBranchTemplate,
 	resolveBranchPrefix,
 	sanitizeAuthorPrefix,
 	sanitizeBranchName,
@@ -289,3 +291,33 @@ describe("resolveBranchPrefix", () => {
 		});
 	});
 });
+
+describe("getBranchTemplateError", () => {
+	it("accepts templates with a slug and known placeholders", () => {
+		expect(getBranchTemplateError("feat/{ticket}-{slug}")).toBeNull();
+		expect(getBranchTemplateError("{user}/{slug}")).toBeNull();
+	});
+
+	it("rejects unknown placeholders and templates without a slug", () => {
+		expect(getBranchTemplateError("{team}/{slug}")).toContain("{team}");
+		expect(getBranchTemplateError("feat/{ticket}")).toContain("{slug}");
+	});
+});
+
+describe("renderBranchTemplate", () => {
+	it("fills placeholders and keeps the ticket's case", () => {
+		expect(
+			renderBranchTemplate("feat/{ticket}-{slug}", { ticket: "PROJ-123", slug: "Add login" }),
+		).toBe("feat/PROJ-123-add-login");
+		expect(renderBranchTemplate("{user}/{slug}", { user: "Jane Doe", slug: "fix" })).toBe(
+			"jane-doe/fix",
+		);
+	});
+
+	it("drops separators around empty placeholders", () => {
+		expect(renderBranchTemplate("feat/{ticket}-{slug}", { slug: "add-login" })).toBe(
+			"feat/add-login",
+		);
+		expect(renderBranchTemplate("{user}/{slug}", { slug: "fix" })).toBe("fix");
+	});
+});
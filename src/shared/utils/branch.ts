@@ -50,3 +50,44 @@ export function resolveBranchPrefix({
 	}
 	return prefix ? sanitizeSegment(prefix) : null;
 }
+
+export const BRANCH_TEMPLATE_PLACEHOLDERS = ["user", "ticket", "slug"] as const;
+
+export type BranchTemplateValues = Partial<
+	Record<(typeof BRANCH_TEMPLATE_PLACEHOLDERS)[number], string | null>
+>;
+
+/** Why a branch template can't be used, or null if it's valid */
+export function getBranchTemplateError(template: string): string | null {
+	const placeholders = Array.from(template.matchAll(/\{([^{}]*)\}/g), (match) => match[1]);
+	const unknown = placeholders.find(
+		(name) => !(BRANCH_TEMPLATE_PLACEHOLDERS as readonly string[]).includes(name),
+	);
+	if (unknown !== undefined) {
+		return `Unknown placeholder {${unknown}}; use {user}, {ticket} or {slug}`;
+	}
+	// Without the slug every node would get the same branch name
+	if (!placeholders.includes("slug")) {
+		return "Branch template must include {slug}";
+	}
+	return null;
+}
+
+/**
+ * Fills a branch template such as "feat/{ticket}-{slug}". Ticket IDs keep
+ * their case; other values are sanitized like branch segments. Separators
+ * left dangling by empty placeholders are dropped.
+ */
+export function renderBranchTemplate(template: string, values: BranchTemplateValues): string {
+	const rendered = template.replace(/\{(user|ticket|slug)\}/g, (_, name: string) => {
+		const value = values[name as keyof BranchTemplateValues]?.trim() ?? "";
+		if (name === "ticket") return value.replace(/[^A-Za-z0-9_-]/g, "");
+		if (name === "slug") return sanitizeBranchName(value);
+		return sanitizeSegment(value);
+	});
+	return rendered
+		.split("/")
+		.map((segment) => segment.replace(/-+/g, "-").replace(/^-|-$/g, ""))
+		.filter(Boolean)
+		.join("/");
+}
@@ -0,0 +1,179 @@
+export const REDACTED = "[REDACTED]";
+
+/** Tokens recognizable by their prefix, replaced whole */
+const TOKEN_PATTERNS: RegExp[] = [
+	// GitHub personal, OAuth, app and refresh tokens
+	/\b(?:gh[pousr]_[A-Za-z0-9]{20,}|github_pat_[A-Za-z0-9_]{20,})\b/g,
+	// Anthropic, OpenAI and most other "sk-" API keys
+	/\bsk-[A-Za-z0-9_-]{20,}/g,
+	/\bxox[abprs]-[A-Za-z0-9-]{10,}/g,
+	/\bAKIA[0-9A-Z]{16}\b/g,
+	/\bAIza[0-9A-Za-z_-]{35}\b/g,
+	// JWTs
+	/\beyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]+/g,
+];
+
+/** `user:password@` in connection strings and URLs */
+const URL_CREDENTIALS = /\b([a-z][a-z0-9+.-]*:\/\/)[^\s:/@]+:[^\s/@]+@/gi;
+
+const AUTHORIZATION = /\b(Bearer|Basic|token)\s+[A-Za-z0-9._~+/=-]{8,}/g;
+
+/** `password=...`, `"apiKey": "..."`, `GITHUB_TOKEN=...` and the like */
+const SECRET_ASSIGNMENT =
+	/\b([A-Za-z0-9_]*(?:token|secret|password|passwd|api[_-]?key|access[_-]?key|private[_-]?key)["']?\s*[:=]\s*["']?)[^\s"'&,;}]+/gi;
+
+/** A span replaced while redacting, in the coordinates of the text at that step */
+export interface RedactionEdit {
+	index: number;
+	length: number;
+	replacementLength: number;
+}
+
+/**
+ * String.replace that records what it changed in `edits`, trimmed to the
+ * part of each match that actually differs from its replacement.
+ */
+function replaceTracked(
+	text: string,
+	pattern: RegExp,
+	replacer: (match: string, ...groups: string[]) => string,
+	edits?: RedactionEdit[],
+): string {
+	let shift = 0;
+	return text.replace(pattern, (match: string, ...args: unknown[]) => {
+		const groups = args.filter((arg) => typeof arg === "string" || arg === undefined);
+		const offset = args.find((arg) => typeof arg === "number") as number;
+		const replacement = replacer(match, ...(groups as string[]));
+		if (edits && replacement !== match) {
+			let prefix = 0;
+			while (prefix < match.length && match[prefix] === replacement[prefix]) prefix++;
+			let suffix = 0;
+			while (
+				suffix < Math.min(match.length, replacement.length) - prefix &&
+				match[match.length - 1 - suffix] === replacement[replacement.length - 1 - suffix]
+			) {
+				suffix++;
+			}
+			edits.push({
+				index: offset + shift + prefix,
+				length: match.length - prefix - suffix,
+				replacementLength: replacement.length - prefix - suffix,
+			});
+		}
+		shift += replacement.length - match.length;
+		return replacement;
+	});
+}
+
+/**
+ * Replaces anything that looks like a credential with [REDACTED]. Errs on
+ * the side of redacting; meant for logs and reports that leave the machine,
+ * not for values that need to round-trip.
+ */
+export function redactSecrets(text: string, edits?: RedactionEdit[]): string {
+	let result = text;
+	for (const pattern of TOKEN_PATTERNS) {
+		result = replaceTracked(result, pattern, () => REDACTED, edits);
+	}
+	result = replaceTracked(result, URL_CREDENTIALS, (_, scheme) => `${scheme}${REDACTED}@`, edits);
+	result = replaceTracked(result, AUTHORIZATION, (_, scheme) => `${scheme} ${REDACTED}`, edits);
+	return replaceTracked(result, SECRET_ASSIGNMENT, (_, key) => `${key}${REDACTED}`, edits);
+}
+
+/** Long unbroken runs of key-like characters. `/` and `.` are left out so paths never match. */
+const KEY_CANDIDATE = /[A-Za-z0-9+_-]{32,}={0,2}/g;
+
+/** Random base64 keys land well above this; identifiers and prose stay below */
+const MIN_KEY_ENTROPY = 4.2;
+
+/** Shannon entropy in bits per character */
+export function shannonEntropy(text: string): number {
+	const counts = new Map<string, number>();
+	for (const char of text) {
+		counts.set(char, (counts.get(char) ?? 0) + 1);
+	}
+	let entropy = 0;
+	for (const count of counts.values()) {
+		const p = count / text.length;
+		entropy -= p * Math.log2(p);
+	}
+	return entropy;
+}
+
+function looksRandom(candidate: string): boolean {
+	// Requiring all three character classes rules out hex hashes, UUIDs and
+	// SCREAMING_SNAKE names, which are long but not secret
+	return (
+		/[A-Z]/.test(candidate) &&
+		/[a-z]/.test(candidate) &&
+		/[0-9]/.test(candidate) &&
+		shannonEntropy(candidate) >= MIN_KEY_ENTROPY
+	);
+}
+
+/**
+ * Catches keys without a recognizable prefix: long mixed-case alphanumeric
+ * runs that are close to random. Noisier than redactSecrets, so callers
+ * opt into it.
+ */
+export function redactHighEntropy(text: string, edits?: RedactionEdit[]): string {
+	return replaceTracked(
+		text,
+		KEY_CANDIDATE,
+		(candidate) => (looksRandom(candidate) ? REDACTED : candidate),
+		edits,
+	);
+}
+
+const ESC = "\x1b";
+
+/** CSI (colors, cursor movement), OSC (titles, links) and two-character escapes like ESC 7 */
+const ESCAPE_SEQUENCE = new RegExp(
+	`${ESC}(?:\\[[0-?]*[ -/]*[@-~]|\\][^\x07${ESC}]*(?:\x07|${ESC}\\\\)?|[ -~])`,
+	"g",
+);
+
+/**
+ * Runs `redact` over terminal output with the escape sequences taken out,
+ * so a color code between a key and its value (`password:\x1b[0m hunter2`)
+ * can't hide the value or get redacted in its place. The sequences are put
+ * back afterwards; any that fell inside a redacted span move to its end.
+ */
+export function redactTerminalOutput(
+	text: string,
+	redact: (plain: string, edits: RedactionEdit[]) => string,
+): string {
+	if (!text.includes(ESC)) return redact(text, []);
+
+	const sequences: { position: number; sequence: string }[] = [];
+	let plain = "";
+	let last = 0;
+	for (const match of text.matchAll(ESCAPE_SEQUENCE)) {
+		plain += text.slice(last, match.index);
+		sequences.push({ position: plain.length, sequence: match[0] });
+		last = match.index + match[0].length;
+	}
+	plain += text.slice(last);
+
+	const edits: RedactionEdit[] = [];
+	const redacted = redact(plain, edits);
+	if (edits.length === 0) return text;
+
+	for (const edit of edits) {
+		for (const entry of sequences) {
+			if (entry.position <= edit.index) continue;
+			entry.position =
+				entry.position >= edit.index + edit.length
+					? entry.position + edit.replacementLength - edit.length
+					: edit.index + edit.replacementLength;
+		}
+	}
+
+	let result = "";
+	let cursor = 0;
+	for (const { position, sequence } of sequences) {
+		result += redacted.slice(cursor, position) + sequence;
+		cursor = position;
+	}
+	return result + redacted.slice(cursor);
+}
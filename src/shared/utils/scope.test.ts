@@ -0,0 +1,44 @@
+import { describe, expect, it } from "bun:test";
+import { isPathInScope, normalizeScopePath, normalizeSparsePatterns } from "./scope";
+
+describe("normalizeScopePath", () => {
+	it("normalizes separators and dot segments", () => {
+		expect(normalizeScopePath("./packages/api/")).toBe("packages/api");
+		expect(normalizeScopePath("packages\\api")).toBe("packages/api");
+	});
+
+	it("treats empty and root scopes as unscoped", () => {
+		expect(normalizeScopePath(undefined)).toBeNull();
+		expect(normalizeScopePath("  ")).toBeNull();
+		expect(normalizeScopePath(".")).toBeNull();
+	});
+
+	it("rejects absolute paths and parent segments", () => {
+		expect(() => normalizeScopePath("/etc")).toThrow();
+		expect(() => normalizeScopePath("C:\\repo")).toThrow();
+		expect(() => normalizeScopePath("packages/../../other")).toThrow();
+	});
+});
+
+describe("isPathInScope", () => {
+	it("matches the scope and paths beneath it only", () => {
+		expect(isPathInScope("packages/api", "packages/api/src/index.ts")).toBe(true);
+		expect(isPathInScope("packages/api", "packages/api")).toBe(true);
+		expect(isPathInScope("packages/api", "packages/api-client/index.ts")).toBe(false);
+		expect(isPathInScope(null, "anything")).toBe(true);
+	});
+});
+
+describe("normalizeSparsePatterns", () => {
+	it("normalizes directories and drops duplicates and the root", () => {
+		expect(normalizeSparsePatterns(["packages/api/", "./packages/api", ".", "libs\\ui"])).toEqual([
+			"packages/api",
+			"libs/ui",
+		]);
+	});
+
+	it("returns null for a full checkout", () => {
+		expect(normalizeSparsePatterns(undefined)).toBeNull();
+		expect(normalizeSparsePatterns(["", "."])).toBeNull();
+	});
+});
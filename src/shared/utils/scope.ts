@@ -0,0 +1,40 @@
+/**
+ * Normalizes a node scope (a subdirectory of the repository, e.g.
+ * "packages/api") to forward slashes without leading "./" or trailing "/".
+ * Returns null for an empty scope or the repository root, and throws for
+ * absolute paths and paths that leave the repository.
+ */
+export function normalizeScopePath(scopePath: string | null | undefined): string | null {
+	const trimmed = scopePath?.trim() ?? "";
+	if (/^([a-zA-Z]:)?[\\/]/.test(trimmed)) {
+		throw new Error("Scope must be a path relative to the repository root");
+	}
+
+	const segments = trimmed
+		.replace(/\\/g, "/")
+		.split("/")
+		.filter((segment) => segment !== "" && segment !== ".");
+	if (segments.includes("..")) {
+		throw new Error("Scope can't point outside the repository");
+	}
+	return segments.length > 0 ? segments.join("/") : null;
+}
+
+/** Whether a repository-relative path (forward slashes) is inside the scope */
+export function isPathInScope(scopePath: string | null, relativePath: string): boolean {
+	if (!scopePath) return true;
+	return relativePath === scopePath || relativePath.startsWith(`${scopePath}/`);
+}
+
+/**
+ * Normalizes cone-mode sparse-checkout directories like node scopes, dropping
+ * duplicates. Returns null when nothing is left, meaning a full checkout.
+ */
+export function normalizeSparsePatterns(patterns: string[] | null | undefined): string[] | null {
+	const directories = new Set<string>();
+	for (const pattern of patterns ?? []) {
+		const directory = normalizeScopePath(pattern);
+		if (directory) directories.add(directory);
+	}
+	return directories.size > 0 ? Array.from(directories) : null;
+}
@@ -0,0 +1,19 @@
+import { describe, expect, it } from "bun:test";
+import { getZonedTimeParts, isValidTimeZone } from "./time";
+
+describe("isValidTimeZone", () => {
+	it("accepts IANA zones and rejects garbage", () => {
+		expect(isValidTimeZone("Europe/Berlin")).toBe(true);
+		expect(isValidTimeZone("UTC")).toBe(true);
+		expect(isValidTimeZone("Mars/Olympus_Mons")).toBe(false);
+		expect(isValidTimeZone("")).toBe(false);
+	});
+});
+
+describe("getZonedTimeParts", () => {
+	it("returns wall-clock parts in the requested zone", () => {
+		// 2024-01-15T23:30:00Z is Tuesday 08:30 in Tokyo
+		const parts = getZonedTimeParts(Date.UTC(2024, 0, 15, 23, 30), "Asia/Tokyo");
+		expect(parts).toEqual({ year: 2024, month: 1, day: 16, hour: 8, minute: 30, weekday: 2 });
+	});
+});
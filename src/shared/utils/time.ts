@@ -0,0 +1,68 @@
+/**
+ * Timestamp helpers. Local tables store epoch milliseconds; anything written as text
+ * (synced rows, metadata files, events) must be UTC ISO-8601 so string ordering matches
+ * time ordering.
+ */
+
+/** Current time as a UTC ISO-8601 string (always ends in "Z") */
+export function nowUtcIso(): string {
+	return new Date().toISOString();
+}
+
+/** Whether a string is an IANA time zone the runtime understands (e.g. "Europe/Berlin") */
+export function isValidTimeZone(timeZone: string): boolean {
+	if (!timeZone) return false;
+	try {
+		new Intl.DateTimeFormat("en-US", { timeZone });
+		return true;
+	} catch {
+		return false;
+	}
+}
+
+/** The runtime's local IANA time zone */
+export function getSystemTimeZone(): string {
+	return Intl.DateTimeFormat().resolvedOptions().timeZone;
+}
+
+export interface ZonedTimeParts {
+	year: number;
+	month: number; // 1-12
+	day: number;
+	hour: number; // 0-23
+	minute: number;
+	weekday: number; // 0 = Sunday
+}
+
+const WEEKDAYS = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/**
+ * Wall-clock components of an instant in a given time zone.
+ * Used for scheduling things like "daily at 9:00 in the user's zone".
+ */
+export function getZonedTimeParts(epochMs: number, timeZone: string): ZonedTimeParts {
+	const formatter = new Intl.DateTimeFormat("en-US", {
+		timeZone,
+		hourCycle: "h23",
+		year: "numeric",
+		month: "numeric",
+		day: "numeric",
+		hour: "numeric",
+		minute: "numeric",
+		weekday: "short",
+	});
+
+	const parts: Record<string, string> = {};
+	for (const part of formatter.formatToParts(new Date(epochMs))) {
+		parts[part.type] = part.value;
+	}
+
+	return {
+		year: Number(parts.year),
+		month: Number(parts.month),
+		day: Number(parts.day),
+		hour: Number(parts.hour),
+		minute: Number(parts.minute),
+		weekday: WEEKDAYS.indexOf(parts.weekday),
+	};
+}
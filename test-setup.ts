@@ -131,6 +131,11 @@ mock.module("electron", () => ({
 		buildFromTemplate: mock(() => ({})),
 		setApplicationMenu: mock(),
 	},
+	safeStorage: {
+		isEncryptionAvailable: mock(() => false),
+		encryptString: mock((value: string) => Buffer.from(value)),
+		decryptString: mock((value: Buffer) => value.toString()),
+	},
 }));
 
 // =============================================================================